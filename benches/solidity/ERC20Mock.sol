@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.4;
+
+import {ERC20} from "solady/tokens/ERC20.sol";
+
+/// @notice Solidity equivalent of `samples/src/erc20_mock.rs`, built on Solady.
+contract ERC20Mock is ERC20 {
+    function name() public pure override returns (string memory) {
+        return "ERC20 Stylus Example";
+    }
+
+    function symbol() public pure override returns (string memory) {
+        return "MOCK";
+    }
+
+    function mint(uint256 qty) external {
+        _mint(msg.sender, qty);
+    }
+
+    function burn(uint256 qty) external {
+        _burn(msg.sender, qty);
+    }
+}
@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.4;
+
+import {ERC721} from "solady/tokens/ERC721.sol";
+import {LibString} from "solady/utils/LibString.sol";
+
+/// @notice Solidity equivalent of `samples/src/erc721_mock.rs`, built on Solady.
+contract ERC721Mock is ERC721 {
+    uint256 public totalSupply;
+
+    function name() public pure override returns (string memory) {
+        return "ERC721 Stylus Example";
+    }
+
+    function symbol() public pure override returns (string memory) {
+        return "MOCK";
+    }
+
+    function tokenURI(uint256 id) public pure override returns (string memory) {
+        return string.concat("ipfs://QmZcH4YvBVVRJtdn4RdbaqgspFU8gH6P9vomDpBVpAL3u4/", LibString.toString(id));
+    }
+
+    function mintLoop(uint256 qty) external {
+        uint256 supply = totalSupply;
+        for (uint256 i; i < qty; ++i) {
+            _mint(msg.sender, supply + i);
+        }
+        totalSupply = supply + qty;
+    }
+
+    function burn(uint256 id) external {
+        _burn(msg.sender, id);
+        --totalSupply;
+    }
+}
@@ -0,0 +1,95 @@
+//! Benchmarks of the ERC20 samples: `samples/src/erc20_mock.rs` and `solidity/ERC20Mock.sol`.
+
+use std::sync::Arc;
+
+use ethers::{
+    abi::{encode, Token},
+    contract::abigen,
+    types::{Address, H256, U256},
+    utils::keccak256,
+};
+use eyre::Result;
+
+use crate::{execution_gas, report::Language, report::Report, Client};
+
+abigen!(
+    ERC20Mock,
+    r#"[
+        function nonces(address owner) external view returns (uint256)
+        function DOMAIN_SEPARATOR() external view returns (bytes32)
+        function mint(uint256 qty) external
+        function burn(uint256 qty) external
+        function transfer(address to, uint256 amount) external returns (bool)
+        function approve(address spender, uint256 amount) external returns (bool)
+        function transferFrom(address from, address to, uint256 amount) external returns (bool)
+        function permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external
+    ]"#
+);
+
+/// Records the gas used by mints, transfers, approvals and permits of the token at `address`.
+pub async fn bench(
+    client: Arc<Client>,
+    address: Address,
+    language: Language,
+    report: &mut Report,
+) -> Result<()> {
+    let token = ERC20Mock::new(address, client.clone());
+    let owner = client.address();
+    // A new recipient for each run, so that its balance starts at zero
+    let recipient = Address::random();
+    let amount = U256::exp10(18);
+    let mut record = |operation: &str, gas: u64| report.record("ERC20", operation, language, gas);
+
+    let receipt = token.mint(amount * 1000).send().await?.await?;
+    record("mint", execution_gas(receipt)?);
+
+    let receipt = token.transfer(recipient, amount).send().await?.await?;
+    record("transfer to new holder", execution_gas(receipt)?);
+
+    let receipt = token.transfer(recipient, amount).send().await?.await?;
+    record("transfer to holder", execution_gas(receipt)?);
+
+    let receipt = token.approve(owner, amount * 10).send().await?.await?;
+    record("approve", execution_gas(receipt)?);
+
+    let receipt = token
+        .transfer_from(owner, recipient, amount)
+        .send()
+        .await?
+        .await?;
+    record("transferFrom", execution_gas(receipt)?);
+
+    let receipt = token.burn(amount).send().await?.await?;
+    record("burn", execution_gas(receipt)?);
+
+    // Approves the recipient through a permit signed by the owner
+    let nonce = token.nonces(owner).call().await?;
+    let domain_separator = token.domain_separator().call().await?;
+    let deadline = U256::MAX;
+    let struct_hash = keccak256(encode(&[
+        Token::FixedBytes(
+            keccak256(
+                "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)",
+            )
+            .to_vec(),
+        ),
+        Token::Address(owner),
+        Token::Address(recipient),
+        Token::Uint(amount),
+        Token::Uint(nonce),
+        Token::Uint(deadline),
+    ]));
+    let digest = keccak256([&[0x19, 0x01], &domain_separator[..], &struct_hash[..]].concat());
+    let signature = client.signer().sign_hash(H256(digest))?;
+    let mut r = [0; 32];
+    let mut s = [0; 32];
+    signature.r.to_big_endian(&mut r);
+    signature.s.to_big_endian(&mut s);
+    let receipt = token
+        .permit(owner, recipient, amount, deadline, signature.v as u8, r, s)
+        .send()
+        .await?
+        .await?;
+    record("permit", execution_gas(receipt)?);
+    Ok(())
+}
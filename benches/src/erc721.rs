@@ -0,0 +1,83 @@
+//! Benchmarks of the ERC721 samples: `samples/src/erc721_mock.rs` and `solidity/ERC721Mock.sol`.
+
+use std::sync::Arc;
+
+use ethers::{
+    contract::abigen,
+    types::{Address, U256},
+};
+use eyre::Result;
+
+use crate::{execution_gas, report::Language, report::Report, Client};
+
+abigen!(
+    ERC721Mock,
+    r#"[
+        function totalSupply() external view returns (uint256)
+        function mintLoop(uint256 qty) external
+        function burn(uint256 tokenId) external
+        function approve(address approved, uint256 tokenId) external
+        function setApprovalForAll(address operator, bool approved) external
+        function transferFrom(address from, address to, uint256 tokenId) external
+        function safeTransferFrom(address from, address to, uint256 tokenId) external
+    ]"#
+);
+
+/// Records the gas used by mints, transfers, approvals and burns of the collection at `address`.
+pub async fn bench(
+    client: Arc<Client>,
+    address: Address,
+    language: Language,
+    report: &mut Report,
+) -> Result<()> {
+    let nft = ERC721Mock::new(address, client.clone());
+    let owner = client.address();
+    // A new recipient for each run, so that its balance starts at zero
+    let recipient = Address::random();
+    let mut record = |operation: &str, gas: u64| report.record("ERC721", operation, language, gas);
+
+    // The samples mint consecutive ids starting from the total supply
+    let first = nft.total_supply().call().await?;
+    let id = |offset: u64| first + U256::from(offset);
+
+    let receipt = nft.mint_loop(U256::one()).send().await?.await?;
+    record("mint 1", execution_gas(receipt)?);
+
+    let receipt = nft.mint_loop(U256::from(5)).send().await?.await?;
+    record("mint 5", execution_gas(receipt)?);
+
+    let receipt = nft
+        .transfer_from(owner, recipient, id(0))
+        .send()
+        .await?
+        .await?;
+    record("transferFrom to new holder", execution_gas(receipt)?);
+
+    let receipt = nft
+        .transfer_from(owner, recipient, id(1))
+        .send()
+        .await?
+        .await?;
+    record("transferFrom to holder", execution_gas(receipt)?);
+
+    let receipt = nft
+        .safe_transfer_from(owner, recipient, id(2))
+        .send()
+        .await?
+        .await?;
+    record("safeTransferFrom to EOA", execution_gas(receipt)?);
+
+    let receipt = nft.approve(recipient, id(3)).send().await?.await?;
+    record("approve", execution_gas(receipt)?);
+
+    let receipt = nft
+        .set_approval_for_all(recipient, true)
+        .send()
+        .await?
+        .await?;
+    record("setApprovalForAll", execution_gas(receipt)?);
+
+    let receipt = nft.burn(id(4)).send().await?.await?;
+    record("burn", execution_gas(receipt)?);
+    Ok(())
+}
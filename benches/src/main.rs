@@ -0,0 +1,79 @@
+//! Records the gas used by the inkmate samples and by equivalent Solidity contracts on a local
+//! nitro dev node, and writes the results as markdown and JSON reports.
+//!
+//! The contracts must already be deployed (see `benches/README.md`). Their addresses, and the node
+//! and key used to send the transactions, are read from the environment:
+//! - `RPC_URL`: the node, `http://localhost:8547` by default
+//! - `PRIVATE_KEY`: the key of a funded account
+//! - `ERC20_STYLUS`, `ERC20_SOLIDITY`: the ERC20 samples (`erc20_mock.rs` and `ERC20Mock.sol`)
+//! - `ERC721_STYLUS`, `ERC721_SOLIDITY`: the ERC721 samples (`erc721_mock.rs` and `ERC721Mock.sol`)
+//! - `REPORT_DIR`: the directory of the reports, `target/gas-report` by default
+//!
+//! The contracts without an address are skipped.
+
+mod erc20;
+mod erc721;
+mod report;
+
+use std::{env, path::PathBuf, sync::Arc};
+
+use ethers::{
+    middleware::SignerMiddleware,
+    providers::{Http, Middleware, Provider},
+    signers::{LocalWallet, Signer},
+    types::{Address, TransactionReceipt, U256},
+};
+use eyre::{eyre, Result};
+
+use report::{Language, Report};
+
+pub type Client = SignerMiddleware<Provider<Http>, LocalWallet>;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let rpc_url = env::var("RPC_URL").unwrap_or_else(|_| "http://localhost:8547".into());
+    let provider = Provider::<Http>::try_from(rpc_url)?;
+    let chain_id = provider.get_chainid().await?.as_u64();
+    let wallet = env::var("PRIVATE_KEY")
+        .map_err(|_| eyre!("PRIVATE_KEY must be set to the key of a funded account"))?
+        .parse::<LocalWallet>()?
+        .with_chain_id(chain_id);
+    let client = Arc::new(SignerMiddleware::new(provider, wallet));
+
+    let mut report = Report::default();
+    for language in [Language::Stylus, Language::Solidity] {
+        if let Some(address) = address("ERC20", language)? {
+            erc20::bench(client.clone(), address, language, &mut report).await?;
+        }
+        if let Some(address) = address("ERC721", language)? {
+            erc721::bench(client.clone(), address, language, &mut report).await?;
+        }
+    }
+
+    let dir = PathBuf::from(env::var("REPORT_DIR").unwrap_or_else(|_| "target/gas-report".into()));
+    report.write(&dir)?;
+    println!("{}", report.markdown());
+    println!("Reports written to {}", dir.display());
+    Ok(())
+}
+
+/// Returns the address of the `contract` sample written in `language`, if it is set.
+fn address(contract: &str, language: Language) -> Result<Option<Address>> {
+    let var = format!("{contract}_{}", language.name().to_uppercase());
+    match env::var(&var) {
+        Ok(address) => Ok(Some(address.parse()?)),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Returns the gas used by the L2 execution of the transaction of `receipt`, which excludes the
+/// gas paying for its L1 data.
+pub fn execution_gas(receipt: Option<TransactionReceipt>) -> Result<u64> {
+    let receipt = receipt.ok_or_else(|| eyre!("transaction dropped"))?;
+    let gas_used = receipt.gas_used.unwrap_or_default();
+    let l1_gas = match receipt.other.get_deserialized::<U256>("gasUsedForL1") {
+        Some(l1_gas) => l1_gas?,
+        None => U256::zero(),
+    };
+    Ok((gas_used - l1_gas).as_u64())
+}
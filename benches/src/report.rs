@@ -0,0 +1,120 @@
+//! The gas report, written as markdown and JSON.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use eyre::Result;
+use serde::Serialize;
+
+/// The language of a benchmarked contract
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum Language {
+    Stylus,
+    Solidity,
+}
+
+impl Language {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::Stylus => "Stylus",
+            Language::Solidity => "Solidity",
+        }
+    }
+}
+
+/// The gas used by an operation of a contract, in each language
+#[derive(Debug, Default, Serialize)]
+pub struct Measurement {
+    pub stylus: Option<u64>,
+    pub solidity: Option<u64>,
+}
+
+/// The gas used by the operations of each contract
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    /// The measurements by contract and operation, in the order they were recorded
+    contracts: BTreeMap<String, Vec<(String, Measurement)>>,
+}
+
+impl Report {
+    /// Records the `gas` used by `operation` of `contract` written in `language`.
+    pub fn record(&mut self, contract: &str, operation: &str, language: Language, gas: u64) {
+        let operations = self.contracts.entry(contract.into()).or_default();
+        let index = match operations.iter().position(|(name, _)| name == operation) {
+            Some(index) => index,
+            None => {
+                operations.push((operation.into(), Measurement::default()));
+                operations.len() - 1
+            }
+        };
+        let measurement = &mut operations[index].1;
+        match language {
+            Language::Stylus => measurement.stylus = Some(gas),
+            Language::Solidity => measurement.solidity = Some(gas),
+        }
+        println!("{contract} {operation} ({}): {gas} gas", language.name());
+    }
+
+    /// Returns the report as markdown tables, one per contract.
+    pub fn markdown(&self) -> String {
+        let mut markdown = String::from("# Gas report\n");
+        for (contract, operations) in &self.contracts {
+            markdown.push_str(&format!(
+                "\n## {contract}\n\n| Operation | Stylus | Solidity | Difference |\n| --- | ---: | ---: | ---: |\n"
+            ));
+            for (operation, measurement) in operations {
+                let gas = |gas: Option<u64>| gas.map_or("-".into(), |gas| gas.to_string());
+                let difference = match (measurement.stylus, measurement.solidity) {
+                    (Some(stylus), Some(solidity)) if solidity > 0 => {
+                        let percent = (stylus as f64 - solidity as f64) / solidity as f64 * 100.0;
+                        format!("{percent:+.1}%")
+                    }
+                    _ => "-".into(),
+                };
+                markdown.push_str(&format!(
+                    "| {operation} | {} | {} | {difference} |\n",
+                    gas(measurement.stylus),
+                    gas(measurement.solidity),
+                ));
+            }
+        }
+        markdown
+    }
+
+    /// Writes `gas-report.md` and `gas-report.json` to `dir`.
+    pub fn write(&self, dir: &Path) -> Result<()> {
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join("gas-report.md"), self.markdown())?;
+        let json: BTreeMap<_, BTreeMap<_, _>> = self
+            .contracts
+            .iter()
+            .map(|(contract, operations)| {
+                let operations = operations
+                    .iter()
+                    .map(|(operation, measurement)| (operation.as_str(), measurement))
+                    .collect();
+                (contract.as_str(), operations)
+            })
+            .collect();
+        fs::write(
+            dir.join("gas-report.json"),
+            serde_json::to_string_pretty(&json)?,
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown() {
+        let mut report = Report::default();
+        report.record("ERC20", "transfer", Language::Stylus, 90);
+        report.record("ERC20", "transfer", Language::Solidity, 100);
+        report.record("ERC20", "permit", Language::Stylus, 50);
+        let markdown = report.markdown();
+        assert!(markdown.contains("| transfer | 90 | 100 | -10.0% |"));
+        assert!(markdown.contains("| permit | 50 | - | - |"));
+    }
+}
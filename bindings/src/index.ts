@@ -0,0 +1,5 @@
+// Generated by `cargo xtask bindings`. Do not edit.
+
+export { erc20MinterPauserCappedAbi } from "./erc20MinterPauserCapped";
+export { erc721DropAbi } from "./erc721Drop";
+export { erc1155GameItemsAbi } from "./erc1155GameItems";
@@ -0,0 +1,107 @@
+//! Base64 encoding as described in RFC 4648, which is commonly used to build data URIs for fully
+//! on-chain token metadata. Encoding writes straight into a preallocated string instead of going
+//! through `format!` or an external crate, which keeps the compiled WASM small.
+//!
+//! This code is based off of Solady's implementation:
+//! https://github.com/Vectorized/solady/blob/main/src/utils/Base64.sol
+
+/// The standard base64 alphabet
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The file and URL safe base64 alphabet, which replaces '+' with '-' and '/' with '_'
+const FILE_SAFE_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Returns the length of the base64 encoding of `len` bytes.
+pub fn encoded_len(len: usize, no_padding: bool) -> usize {
+    if no_padding {
+        (len * 4).div_ceil(3)
+    } else {
+        len.div_ceil(3) * 4
+    }
+}
+
+/// Returns the base64 encoding of `data`, with padding.
+pub fn encode(data: &[u8]) -> String {
+    encode_with(data, false, false)
+}
+
+/// Returns the base64 encoding of `data`.
+/// `file_safe` uses the file and URL safe alphabet, and `no_padding` omits the trailing '='.
+pub fn encode_with(data: &[u8], file_safe: bool, no_padding: bool) -> String {
+    let mut result = String::with_capacity(encoded_len(data.len(), no_padding));
+    encode_into(&mut result, data, file_safe, no_padding);
+    result
+}
+
+/// Appends the base64 encoding of `data` to `out`.
+/// `file_safe` uses the file and URL safe alphabet, and `no_padding` omits the trailing '='.
+pub fn encode_into(out: &mut String, data: &[u8], file_safe: bool, no_padding: bool) {
+    let alphabet = if file_safe {
+        FILE_SAFE_ALPHABET
+    } else {
+        ALPHABET
+    };
+    out.reserve(encoded_len(data.len(), no_padding));
+
+    let mut chunks = data.chunks_exact(3);
+    for chunk in &mut chunks {
+        let n = (chunk[0] as u32) << 16 | (chunk[1] as u32) << 8 | chunk[2] as u32;
+        out.push(alphabet[(n >> 18) as usize & 0x3f] as char);
+        out.push(alphabet[(n >> 12) as usize & 0x3f] as char);
+        out.push(alphabet[(n >> 6) as usize & 0x3f] as char);
+        out.push(alphabet[n as usize & 0x3f] as char);
+    }
+
+    // Encode the last 1 or 2 bytes, padding the output to a multiple of 4 characters
+    let remainder = chunks.remainder();
+    if remainder.is_empty() {
+        return;
+    }
+    let n = (remainder[0] as u32) << 16 | (*remainder.get(1).unwrap_or(&0) as u32) << 8;
+    out.push(alphabet[(n >> 18) as usize & 0x3f] as char);
+    out.push(alphabet[(n >> 12) as usize & 0x3f] as char);
+    if remainder.len() == 2 {
+        out.push(alphabet[(n >> 6) as usize & 0x3f] as char);
+    }
+    if !no_padding {
+        for _ in remainder.len()..3 {
+            out.push('=');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        // Test vectors from RFC 4648
+        let vectors = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+        for (input, expected) in vectors {
+            assert_eq!(encode(input.as_bytes()), expected);
+            assert_eq!(encoded_len(input.len(), false), expected.len());
+            assert_eq!(
+                encode_with(input.as_bytes(), false, true),
+                expected.trim_end_matches('=')
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_file_safe() {
+        let data = [0xfb_u8, 0xff, 0xbf];
+        assert_eq!(encode(&data), "+/+/");
+        assert_eq!(encode_with(&data, true, false), "-_-_");
+        assert_eq!(encode_with(&[0xff_u8], true, true), "_w");
+    }
+}
@@ -0,0 +1,88 @@
+//! Defines the clones trait which builds EIP-1167 minimal proxy init code and predicts the address
+//! of deterministic clones, building on the CREATE2 address computation of `CreateTrait`.
+//!
+//! This code is based off of OpenZeppelin's implementation:
+//! https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/proxy/Clones.sol
+
+use crate::create::{CreateTrait, NUM_BYTES_ADDRESS};
+
+/// The byte length of the EIP-1167 minimal proxy init code
+pub const CLONE_INIT_CODE_LEN: usize = 55;
+
+/// The init code preceding the implementation address
+const CLONE_PREFIX: [u8; 20] = [
+    0x3d, 0x60, 0x2d, 0x80, 0x60, 0x0a, 0x3d, 0x39, 0x81, 0xf3, 0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d,
+    0x3d, 0x36, 0x3d, 0x73,
+];
+
+/// The runtime code following the implementation address
+const CLONE_SUFFIX: [u8; 15] = [
+    0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b, 0xf3,
+];
+
+pub trait ClonesTrait: CreateTrait {
+    /// Returns the init code of an EIP-1167 minimal proxy delegating to `implementation`.
+    fn clone_init_code(implementation: [u8; NUM_BYTES_ADDRESS]) -> [u8; CLONE_INIT_CODE_LEN] {
+        let mut code = [0_u8; CLONE_INIT_CODE_LEN];
+        code[..20].copy_from_slice(&CLONE_PREFIX);
+        code[20..40].copy_from_slice(&implementation);
+        code[40..].copy_from_slice(&CLONE_SUFFIX);
+        code
+    }
+
+    /// Returns the address of a clone of `implementation` deployed by `deployer` with `salt`.
+    fn predict_deterministic_address(
+        implementation: [u8; NUM_BYTES_ADDRESS],
+        salt: [u8; 32],
+        deployer: [u8; NUM_BYTES_ADDRESS],
+    ) -> [u8; NUM_BYTES_ADDRESS] {
+        let init_code_hash = Self::keccak256(&Self::clone_init_code(implementation));
+        Self::compute_create2_address(deployer, salt, init_code_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{types::H160, utils::keccak256};
+
+    struct RustClones;
+
+    impl CreateTrait for RustClones {
+        fn keccak256(data: &[u8]) -> [u8; 32] {
+            keccak256(data)
+        }
+    }
+
+    impl ClonesTrait for RustClones {}
+
+    #[test]
+    fn test_clone_init_code() {
+        let implementation = [0xbe_u8; NUM_BYTES_ADDRESS];
+        let expected = ethers::utils::hex::decode(
+            "3d602d80600a3d3981f3363d3d373d3d3d363d73bebebebebebebebebebebebebebebebebebebebe5af43d82803e903d91602b57fd5bf3",
+        )
+        .unwrap();
+        assert_eq!(
+            RustClones::clone_init_code(implementation).to_vec(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_predict_deterministic_address() {
+        let implementation = [0x11_u8; NUM_BYTES_ADDRESS];
+        let deployer = [0x22_u8; NUM_BYTES_ADDRESS];
+        let salt = [0x33_u8; 32];
+
+        let expected = ethers::utils::get_create2_address(
+            H160::from(deployer),
+            salt,
+            RustClones::clone_init_code(implementation),
+        );
+        assert_eq!(
+            RustClones::predict_deterministic_address(implementation, salt, deployer),
+            expected.0
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! Defines the create trait which predicts the addresses of contracts deployed through CREATE2
+//! and CREATE3. The hashing function is left to the implementation so that contracts can use the
+//! VM's native keccak while tests can use a pure rust implementation.
+//!
+//! This code is based off of Solady's implementation:
+//! https://github.com/Vectorized/solady/blob/main/src/utils/CREATE3.sol
+
+/// The number of bytes it takes to represent an Ethereum address
+pub const NUM_BYTES_ADDRESS: usize = 20;
+
+/// The init code of the CREATE3 proxy, which deploys the calldata as init code through CREATE.
+///
+/// 0x67363d3d37363d34f03d5260086018f3:
+/// - PUSH8 0x363d3d37363d34f0 (runtime: CALLDATASIZE RETURNDATASIZE RETURNDATASIZE CALLDATACOPY
+///   CALLDATASIZE RETURNDATASIZE CALLVALUE CREATE)
+/// - RETURNDATASIZE MSTORE PUSH1 0x08 PUSH1 0x18 RETURN
+pub const CREATE3_PROXY_INIT_CODE: [u8; 16] = [
+    0x67, 0x36, 0x3d, 0x3d, 0x37, 0x36, 0x3d, 0x34, 0xf0, 0x3d, 0x52, 0x60, 0x08, 0x60, 0x18, 0xf3,
+];
+
+pub trait CreateTrait {
+    /// Returns the address of a contract deployed by `deployer` through CREATE2 with `salt`
+    /// and the init code hashing to `init_code_hash`.
+    fn compute_create2_address(
+        deployer: [u8; NUM_BYTES_ADDRESS],
+        salt: [u8; 32],
+        init_code_hash: [u8; 32],
+    ) -> [u8; NUM_BYTES_ADDRESS] {
+        // keccak256(0xff ‖ deployer ‖ salt ‖ init_code_hash)[12..]
+        let mut input = [0_u8; 85];
+        input[0] = 0xff;
+        input[1..21].copy_from_slice(&deployer);
+        input[21..53].copy_from_slice(&salt);
+        input[53..].copy_from_slice(&init_code_hash);
+        let hash = Self::keccak256(&input);
+        hash[12..].try_into().unwrap()
+    }
+
+    /// Returns the address of the CREATE3 proxy deployed by `deployer` with `salt`.
+    fn compute_create3_proxy_address(
+        deployer: [u8; NUM_BYTES_ADDRESS],
+        salt: [u8; 32],
+    ) -> [u8; NUM_BYTES_ADDRESS] {
+        let proxy_init_code_hash = Self::keccak256(&CREATE3_PROXY_INIT_CODE);
+        Self::compute_create2_address(deployer, salt, proxy_init_code_hash)
+    }
+
+    /// Returns the address of a contract deployed by `deployer` through CREATE3 with `salt`.
+    /// The address does not depend on the deployed contract's init code.
+    fn compute_create3_address(
+        deployer: [u8; NUM_BYTES_ADDRESS],
+        salt: [u8; 32],
+    ) -> [u8; NUM_BYTES_ADDRESS] {
+        let proxy = Self::compute_create3_proxy_address(deployer, salt);
+        // The proxy deploys the contract with CREATE at nonce 1:
+        // keccak256(rlp([proxy, 1]))[12..] = keccak256(0xd6 ‖ 0x94 ‖ proxy ‖ 0x01)[12..]
+        let mut input = [0_u8; 23];
+        input[0] = 0xd6;
+        input[1] = 0x94;
+        input[2..22].copy_from_slice(&proxy);
+        input[22] = 0x01;
+        let hash = Self::keccak256(&input);
+        hash[12..].try_into().unwrap()
+    }
+
+    fn keccak256(data: &[u8]) -> [u8; 32];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{types::H160, utils::keccak256};
+
+    struct RustCreate;
+
+    impl CreateTrait for RustCreate {
+        fn keccak256(data: &[u8]) -> [u8; 32] {
+            keccak256(data)
+        }
+    }
+
+    #[test]
+    fn test_compute_create2_address() {
+        let deployer = [0x22_u8; NUM_BYTES_ADDRESS];
+        let salt = [0x33_u8; 32];
+        let init_code = [0x60_u8, 0x00, 0x60, 0x00, 0xf3];
+
+        let expected = ethers::utils::get_create2_address(H160::from(deployer), salt, init_code);
+        assert_eq!(
+            RustCreate::compute_create2_address(deployer, salt, keccak256(init_code)),
+            expected.0
+        );
+    }
+
+    #[test]
+    fn test_compute_create3_address() {
+        let deployer = [0x22_u8; NUM_BYTES_ADDRESS];
+        let salt = [0x33_u8; 32];
+
+        let proxy =
+            ethers::utils::get_create2_address(H160::from(deployer), salt, CREATE3_PROXY_INIT_CODE);
+        let expected = ethers::utils::get_contract_address(proxy, 1);
+        assert_eq!(
+            RustCreate::compute_create3_address(deployer, salt),
+            expected.0
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! Defines the merkle proof trait which verifies single and multi proofs over sorted keccak pairs.
+//! The hashing function is left to the implementation so that contracts can use the VM's native
+//! keccak while tests can use a pure rust implementation.
+//!
+//! This code is based off of OpenZeppelin's implementation:
+//! https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/utils/cryptography/MerkleProof.sol
+
+/// The number of bytes in a merkle tree node
+pub const NODE_SIZE: usize = 32;
+
+/// An error that occurs when a multi proof is malformed
+#[derive(Debug)]
+pub struct MerkleProofError;
+
+pub trait MerkleProofTrait {
+    /// Returns true if `leaf` can be proved to be a part of a merkle tree defined by `root`.
+    /// Each pair of leaves and each pair of pre-images are assumed to be sorted.
+    fn verify(proof: &[[u8; NODE_SIZE]], root: [u8; NODE_SIZE], leaf: [u8; NODE_SIZE]) -> bool {
+        Self::process_proof(proof, leaf) == root
+    }
+
+    /// Returns the rebuilt hash obtained by traversing the merkle tree up from `leaf` using `proof`.
+    fn process_proof(proof: &[[u8; NODE_SIZE]], leaf: [u8; NODE_SIZE]) -> [u8; NODE_SIZE] {
+        proof
+            .iter()
+            .fold(leaf, |computed, node| Self::hash_pair(computed, *node))
+    }
+
+    /// Returns true if `leaves` can be simultaneously proved to be a part of a merkle tree defined
+    /// by `root`, according to `proof` and `proof_flags` as described in `process_multi_proof`.
+    fn verify_multi_proof(
+        proof: &[[u8; NODE_SIZE]],
+        proof_flags: &[bool],
+        root: [u8; NODE_SIZE],
+        leaves: &[[u8; NODE_SIZE]],
+    ) -> Result<bool, MerkleProofError> {
+        Ok(Self::process_multi_proof(proof, proof_flags, leaves)? == root)
+    }
+
+    /// Returns the root of a tree reconstructed from `leaves` and sibling nodes in `proof`.
+    /// The reconstruction proceeds by incrementally reconstructing all inner nodes by combining a
+    /// leaf/inner node with either another leaf/inner node or a proof sibling node, depending on
+    /// whether each `proof_flags` item is true or false respectively.
+    fn process_multi_proof(
+        proof: &[[u8; NODE_SIZE]],
+        proof_flags: &[bool],
+        leaves: &[[u8; NODE_SIZE]],
+    ) -> Result<[u8; NODE_SIZE], MerkleProofError> {
+        let total_hashes = proof_flags.len();
+        // Check proof validity
+        if leaves.len() + proof.len() != total_hashes + 1 {
+            return Err(MerkleProofError);
+        }
+
+        // The `hashes` array is used as a queue of nodes to be hashed, along with `leaves`.
+        // Leaves are consumed first, then the computed hashes.
+        let mut hashes = Vec::with_capacity(total_hashes);
+        let mut leaf_pos = 0;
+        let mut hash_pos = 0;
+        let mut proof_pos = 0;
+
+        let mut next_node = |hashes: &Vec<[u8; NODE_SIZE]>| {
+            if leaf_pos < leaves.len() {
+                leaf_pos += 1;
+                Ok(leaves[leaf_pos - 1])
+            } else {
+                hash_pos += 1;
+                hashes.get(hash_pos - 1).copied().ok_or(MerkleProofError)
+            }
+        };
+
+        for flag in proof_flags {
+            let a = next_node(&hashes)?;
+            let b = if *flag {
+                next_node(&hashes)?
+            } else {
+                proof_pos += 1;
+                *proof.get(proof_pos - 1).ok_or(MerkleProofError)?
+            };
+            hashes.push(Self::hash_pair(a, b));
+        }
+
+        if total_hashes > 0 {
+            if proof_pos != proof.len() {
+                return Err(MerkleProofError);
+            }
+            Ok(hashes[total_hashes - 1])
+        } else if !leaves.is_empty() {
+            Ok(leaves[0])
+        } else {
+            Ok(proof[0])
+        }
+    }
+
+    /// Hashes a pair of nodes in sorted order.
+    fn hash_pair(a: [u8; NODE_SIZE], b: [u8; NODE_SIZE]) -> [u8; NODE_SIZE] {
+        let mut input = [0_u8; 2 * NODE_SIZE];
+        if a < b {
+            input[..NODE_SIZE].copy_from_slice(&a);
+            input[NODE_SIZE..].copy_from_slice(&b);
+        } else {
+            input[..NODE_SIZE].copy_from_slice(&b);
+            input[NODE_SIZE..].copy_from_slice(&a);
+        }
+        Self::keccak256(&input)
+    }
+
+    fn keccak256(data: &[u8]) -> [u8; NODE_SIZE];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::utils::keccak256;
+
+    struct RustMerkleProof;
+
+    impl MerkleProofTrait for RustMerkleProof {
+        fn keccak256(data: &[u8]) -> [u8; NODE_SIZE] {
+            keccak256(data)
+        }
+    }
+
+    /// Builds a 4 leaf tree, returning the leaves and each level of the tree
+    fn build_tree() -> ([[u8; NODE_SIZE]; 4], [[u8; NODE_SIZE]; 2], [u8; NODE_SIZE]) {
+        let leaves = [
+            keccak256("a"),
+            keccak256("b"),
+            keccak256("c"),
+            keccak256("d"),
+        ];
+        let level = [
+            RustMerkleProof::hash_pair(leaves[0], leaves[1]),
+            RustMerkleProof::hash_pair(leaves[2], leaves[3]),
+        ];
+        let root = RustMerkleProof::hash_pair(level[0], level[1]);
+        (leaves, level, root)
+    }
+
+    #[test]
+    fn test_verify_single_proof() {
+        let (leaves, level, root) = build_tree();
+
+        assert!(RustMerkleProof::verify(
+            &[leaves[1], level[1]],
+            root,
+            leaves[0]
+        ));
+        assert!(RustMerkleProof::verify(
+            &[leaves[2], level[0]],
+            root,
+            leaves[3]
+        ));
+        assert!(!RustMerkleProof::verify(
+            &[leaves[2], level[0]],
+            root,
+            leaves[0]
+        ));
+    }
+
+    #[test]
+    fn test_verify_multi_proof() {
+        let (leaves, level, root) = build_tree();
+
+        // Prove leaves 0 and 1 together using the sibling subtree as the only proof node
+        let valid = RustMerkleProof::verify_multi_proof(
+            &[level[1]],
+            &[true, false],
+            root,
+            &[leaves[0], leaves[1]],
+        )
+        .expect("Multi proof should be well formed");
+        assert!(valid, "Multi proof should verify");
+
+        let invalid = RustMerkleProof::verify_multi_proof(
+            &[level[0]],
+            &[true, false],
+            root,
+            &[leaves[0], leaves[1]],
+        )
+        .expect("Multi proof should be well formed");
+        assert!(
+            !invalid,
+            "Multi proof with a wrong sibling should not verify"
+        );
+
+        assert!(
+            RustMerkleProof::verify_multi_proof(&[level[1]], &[true], root, &[leaves[0]]).is_err(),
+            "Malformed multi proof should error"
+        );
+    }
+}
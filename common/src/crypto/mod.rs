@@ -1,3 +1,4 @@
 //! Cryptography utilities
 
 pub mod ecrecover;
+pub mod merkle_proof;
@@ -0,0 +1,349 @@
+//! Defines arithmetic on 18 decimal fixed point numbers (WAD), full precision `mul_div`, integer
+//! roots, and the natural exponential and logarithm. Every function returns `None` where the
+//! Solidity implementation would revert (ex. on overflow or division by zero).
+//!
+//! This code is based off of Solady's implementation:
+//! https://github.com/Vectorized/solady/blob/main/src/utils/FixedPointMathLib.sol
+
+use alloy_primitives::{Uint, I256, U256};
+
+/// A 512-bit unsigned integer, used for full precision intermediate products
+type U512 = Uint<512, 8>;
+
+/// The scalar of ETH and most ERC20s, 1e18
+pub const WAD: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+
+/// `WAD` as a signed integer
+const WAD_I: I256 = I256::from_raw(WAD);
+
+/// Returns a signed constant from its little endian limbs.
+const fn int(limbs: [u64; 4]) -> I256 {
+    I256::from_raw(U256::from_limbs(limbs))
+}
+
+// Constants of the rational approximations of `exp_wad` and `ln_wad`.
+// See https://xn--2-umb.com/22/exp-ln for their derivation.
+const EXP_MIN: I256 = int([0x3f2fa8f6da5b9d29, 0x2, 0, 0]);
+const EXP_MAX: I256 = int([0x55bf798b4a1bf1e5, 0x7, 0, 0]);
+const FIVE_POW_18: I256 = int([0x3782dace9d9, 0, 0, 0]);
+const LN2_X96: I256 = int([0xd1cf79abc9e3b398, 0xb17217f7, 0, 0]);
+const EXP_Y0: I256 = int([0x37d0007b713f7650, 0x10fe68e7fd, 0, 0]);
+const EXP_Y1: I256 = int([0xd19bf614176fe9ea, 0x2d16720577b, 0, 0]);
+const EXP_P0: I256 = int([0x96949216d2255a6c, 0x4a4fd9f2a8b, 0, 0]);
+const EXP_P1: I256 = int([0x9d25fcb740196450, 0x587f503bb6ea2, 0, 0]);
+const EXP_P2: I256 = int([0, 0x3b2ca45c00000000, 0xebba824c98fb31b8, 0xd835]);
+const EXP_Q0: I256 = int([0xb2d9cbaf0fd5aafc, 0x240c330e9f, 0, 0]);
+const EXP_Q1: I256 = int([0xc85f6e2461837cd9, 0x277594991cf, 0, 0]);
+const EXP_Q2: I256 = int([0x6a5061b25ef1c9c4, 0x1a521255e34f, 0, 0]);
+const EXP_Q3: I256 = int([0xcf962f1a1d3db4a5, 0xb1bbb201f443, 0, 0]);
+const EXP_Q4: I256 = int([0x51a9331fed693f15, 0x2c72388d9f74f, 0, 0]);
+const EXP_Q5: I256 = int([0x47a8a8cb2a527d57, 0x5180bb14799ab, 0, 0]);
+const EXP_SCALE: U256 = U256::from_limbs([0xee70ef65f9978af3, 0x63c32e5c2f6dc192, 0x29d9dc385, 0]);
+const LN_P0: I256 = int([0x43d8aa4df2abee78, 0x29508e4585, 0, 0]);
+const LN_P1: I256 = int([0xbe717e604cbb4894, 0x139601a2efa, 0, 0]);
+const LN_P2: I256 = int([0x94320649aa03aba1, 0x2247f7a7b65, 0, 0]);
+const LN_P3: I256 = int([0x6b1ff2ab1c3b3437, 0x8c3f38e95a, 0, 0]);
+const LN_P4: I256 = int([0xac5676facced6091, 0x2384773bdf1, 0, 0]);
+const LN_P5: I256 = int([0xb29c212b8b1a07ce, 0xb9a025d814, 0, 0]);
+const LN_P6: I256 = int([0, 0xe71ea86a00000000, 0x9507084cc699bb0, 0xa]);
+const LN_Q0: I256 = int([0xbb5f824b15207a30, 0x465772b2bb, 0, 0]);
+const LN_Q1: I256 = int([0xd5aca026815d636e, 0x388eaa27412, 0, 0]);
+const LN_Q2: I256 = int([0x1bf953eff472fdcc, 0xdf99ac50203, 0, 0]);
+const LN_Q3: I256 = int([0xd99322bdff5f2211, 0x13cdffb29d51, 0, 0]);
+const LN_Q4: I256 = int([0xf783a307a986912e, 0xa0f742023de, 0, 0]);
+const LN_Q5: I256 = int([0x89b5239253284e42, 0x1920d8043ca, 0, 0]);
+const LN_Q6: I256 = int([0x5468fac667a0a527, 0xb7a86d737, 0, 0]);
+const LN_SCALE: I256 = int([0x5cef59f0815a5506, 0xdaa0d5f769dba191, 0x1340, 0]);
+const LN_LN2: I256 = int([
+    0xd803ae7b6687f2b3,
+    0x7614a3f75373f047,
+    0xb3975ab3ee5b203a,
+    0x267a36c0c95,
+]);
+const LN_OFFSET: I256 = int([
+    0xe8028c72b8864284,
+    0x356a1b7863008a5a,
+    0x7177eebf7cd370a3,
+    0x57115e47018c,
+]);
+
+/// Returns `x * y / d` rounded down, or `None` if `x * y` overflows or `d` is zero.
+pub fn mul_div(x: U256, y: U256, d: U256) -> Option<U256> {
+    x.checked_mul(y)?.checked_div(d)
+}
+
+/// Returns `x * y / d` rounded up, or `None` if `x * y` overflows or `d` is zero.
+pub fn mul_div_up(x: U256, y: U256, d: U256) -> Option<U256> {
+    let z = x.checked_mul(y)?;
+    if d.is_zero() {
+        return None;
+    }
+    Some(z.div_ceil(d))
+}
+
+/// Returns `x * y / d` rounded down, computing `x * y` with full 512-bit precision.
+/// Returns `None` if the result overflows or `d` is zero.
+pub fn full_mul_div(x: U256, y: U256, d: U256) -> Option<U256> {
+    if d.is_zero() {
+        return None;
+    }
+    let product: U512 = x.widening_mul(y);
+    to_u256(product / U512::from(d))
+}
+
+/// Returns `x * y / d` rounded up, computing `x * y` with full 512-bit precision.
+/// Returns `None` if the result overflows or `d` is zero.
+pub fn full_mul_div_up(x: U256, y: U256, d: U256) -> Option<U256> {
+    if d.is_zero() {
+        return None;
+    }
+    let product: U512 = x.widening_mul(y);
+    to_u256(product.div_ceil(U512::from(d)))
+}
+
+/// Returns `x * y / WAD` rounded down, or `None` if `x * y` overflows.
+pub fn mul_wad(x: U256, y: U256) -> Option<U256> {
+    mul_div(x, y, WAD)
+}
+
+/// Returns `x * y / WAD` rounded up, or `None` if `x * y` overflows.
+pub fn mul_wad_up(x: U256, y: U256) -> Option<U256> {
+    mul_div_up(x, y, WAD)
+}
+
+/// Returns `x * WAD / y` rounded down, or `None` if `x * WAD` overflows or `y` is zero.
+pub fn div_wad(x: U256, y: U256) -> Option<U256> {
+    mul_div(x, WAD, y)
+}
+
+/// Returns `x * WAD / y` rounded up, or `None` if `x * WAD` overflows or `y` is zero.
+pub fn div_wad_up(x: U256, y: U256) -> Option<U256> {
+    mul_div_up(x, WAD, y)
+}
+
+/// Returns the square root of `x`, rounded down.
+pub fn sqrt(x: U256) -> U256 {
+    if x.is_zero() {
+        return U256::ZERO;
+    }
+    // Newton's method, starting from a power of two that is at least the root
+    let mut z = U256::from(1) << x.bit_len().div_ceil(2);
+    loop {
+        let y = (z + x / z) >> 1;
+        if y >= z {
+            return z;
+        }
+        z = y;
+    }
+}
+
+/// Returns the cube root of `x`, rounded down.
+pub fn cbrt(x: U256) -> U256 {
+    if x.is_zero() {
+        return U256::ZERO;
+    }
+    // Newton's method, starting from a power of two that is at least the root
+    let mut z = U256::from(1) << x.bit_len().div_ceil(3);
+    loop {
+        let y = (x / (z * z) + (z << 1)) / U256::from(3);
+        if y >= z {
+            return z;
+        }
+        z = y;
+    }
+}
+
+/// Returns `exp(x)`, where `x` and the result are WADs.
+/// Returns zero when the result would round to zero, and `None` if the result overflows.
+pub fn exp_wad(x: I256) -> Option<I256> {
+    // When the result is less than 0.5, it rounds to zero
+    if x <= -EXP_MIN {
+        return Some(I256::ZERO);
+    }
+    // When the result is greater than (2**255 - 1) / 1e18, it cannot be represented
+    if x >= EXP_MAX {
+        return None;
+    }
+
+    // x is now in the range (-42, 136) * 1e18. Convert it to (-42, 136) * 2**96 for more
+    // intermediate precision and a binary basis
+    let x = x.wrapping_shl(78).wrapping_div(FIVE_POW_18);
+
+    // Reduce the range of x to (-½ ln 2, ½ ln 2) * 2**96 by factoring out powers of two such
+    // that exp(x) = exp(x') * 2**k, where k is an integer
+    let k = (x.wrapping_shl(96).wrapping_div(LN2_X96) + (I256::ONE << 95)).asr(96);
+    let x = x - k * LN2_X96;
+
+    // Evaluate using a (6, 7)-term rational approximation. p is made monic
+    let mut y = x + EXP_Y0;
+    y = (y.wrapping_mul(x)).asr(96) + EXP_Y1;
+    let mut p = y + x - EXP_P0;
+    p = (p.wrapping_mul(y)).asr(96) + EXP_P1;
+    // p is left in a 2**192 basis so it does not need to be scaled back up for the division
+    p = p.wrapping_mul(x).wrapping_add(EXP_P2);
+
+    let mut q = x - EXP_Q0;
+    q = (q.wrapping_mul(x)).asr(96) + EXP_Q1;
+    q = (q.wrapping_mul(x)).asr(96) - EXP_Q2;
+    q = (q.wrapping_mul(x)).asr(96) + EXP_Q3;
+    q = (q.wrapping_mul(x)).asr(96) - EXP_Q4;
+    q = (q.wrapping_mul(x)).asr(96) + EXP_Q5;
+
+    // The q polynomial has no roots in the domain, and r is in the range (0.09, 0.25) * 2**96
+    let r = p.wrapping_div(q);
+
+    // Multiply r by the scale factor s ≈ 6.031367120, the 2**k factor of the range reduction and
+    // the 1e18 / 2**96 base conversion all at once, with an intermediate result in a 2**213 basis
+    let k = i64::try_from(k).unwrap();
+    let r = r.into_raw().wrapping_mul(EXP_SCALE) >> (195 - k) as usize;
+    Some(I256::from_raw(r))
+}
+
+/// Returns `ln(x)`, where `x` and the result are WADs.
+/// Returns `None` if `x` is not positive.
+pub fn ln_wad(x: I256) -> Option<I256> {
+    if x <= I256::ZERO {
+        return None;
+    }
+
+    // Compute k = log2(x) - 96, and reduce the range of x to (1, 2) * 2**96, as
+    // ln(2**k * x) = k * ln(2) + ln(x). The conversion of x from a 1e18 basis to a 2**96 basis is
+    // done by adding ln(2**96 / 1e18) to the result
+    let log2 = x.into_raw().bit_len() as i64 - 1;
+    let k = log2 - 96;
+    let x = I256::from_raw((x.into_raw() << (159 - k) as usize) >> 159);
+
+    // Evaluate using an (8, 8)-term rational approximation. p is made monic
+    let mut p = x + LN_P0;
+    p = (p.wrapping_mul(x)).asr(96) + LN_P1;
+    p = (p.wrapping_mul(x)).asr(96) + LN_P2;
+    p = (p.wrapping_mul(x)).asr(96) - LN_P3;
+    p = (p.wrapping_mul(x)).asr(96) - LN_P4;
+    p = (p.wrapping_mul(x)).asr(96) - LN_P5;
+    // p is left in a 2**192 basis so it does not need to be scaled back up for the division
+    p = p.wrapping_mul(x).wrapping_sub(LN_P6);
+
+    let mut q = x + LN_Q0;
+    q = (q.wrapping_mul(x)).asr(96) + LN_Q1;
+    q = (q.wrapping_mul(x)).asr(96) + LN_Q2;
+    q = (q.wrapping_mul(x)).asr(96) + LN_Q3;
+    q = (q.wrapping_mul(x)).asr(96) + LN_Q4;
+    q = (q.wrapping_mul(x)).asr(96) + LN_Q5;
+    q = (q.wrapping_mul(x)).asr(96) + LN_Q6;
+
+    // The q polynomial has no roots in the domain, and r is in the range (0, 0.125) * 2**96
+    let mut r = p.wrapping_div(q);
+
+    // Multiply by the scale factor s ≈ 5.549, add k * ln(2) and ln(2**96 / 1e18), all in a
+    // 5**18 * 2**192 basis, then convert back to a 1e18 basis
+    r = r.wrapping_mul(LN_SCALE);
+    r = r.wrapping_add(LN_LN2.wrapping_mul(I256::try_from(k).unwrap()));
+    r = r.wrapping_add(LN_OFFSET);
+    Some(r.asr(174))
+}
+
+/// Returns `x ** y`, where `x`, `y` and the result are WADs, computed as `exp(ln(x) * y)`.
+/// Returns `None` if `x` is not positive or the result overflows.
+pub fn pow_wad(x: I256, y: I256) -> Option<I256> {
+    exp_wad(ln_wad(x)?.checked_mul(y)? / WAD_I)
+}
+
+/// Returns `x` as a `U256`, or `None` if it does not fit.
+fn to_u256(x: U512) -> Option<U256> {
+    let limbs = x.as_limbs();
+    if limbs[4..].iter().any(|limb| *limb != 0) {
+        return None;
+    }
+    Some(U256::from_limbs([limbs[0], limbs[1], limbs[2], limbs[3]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wad(value: i64) -> I256 {
+        I256::try_from(value).unwrap() * WAD_I
+    }
+
+    #[test]
+    fn test_mul_div() {
+        let x = U256::MAX / U256::from(3);
+        assert_eq!(mul_div(x, U256::from(4), U256::from(2)), None);
+        assert_eq!(
+            full_mul_div(x, U256::from(4), U256::from(8)),
+            Some(x / U256::from(2))
+        );
+        assert_eq!(
+            full_mul_div_up(U256::from(5), U256::from(3), U256::from(2)),
+            Some(U256::from(8))
+        );
+        assert_eq!(full_mul_div(U256::MAX, U256::MAX, U256::from(1)), None);
+        assert_eq!(full_mul_div(U256::from(1), U256::from(1), U256::ZERO), None);
+        assert_eq!(
+            mul_wad(WAD * U256::from(3), WAD / U256::from(2)),
+            Some(WAD * U256::from(3) / U256::from(2))
+        );
+        assert_eq!(
+            mul_wad_up(U256::from(1), U256::from(1)),
+            Some(U256::from(1))
+        );
+        assert_eq!(
+            div_wad(U256::from(1), U256::from(3)),
+            Some(U256::from(333_333_333_333_333_333_u64))
+        );
+        assert_eq!(
+            div_wad_up(U256::from(1), U256::from(3)),
+            Some(U256::from(333_333_333_333_333_334_u64))
+        );
+        assert_eq!(div_wad(U256::from(1), U256::ZERO), None);
+    }
+
+    #[test]
+    fn test_roots() {
+        for x in [0_u64, 1, 2, 3, 4, 8, 26, 27, 28, 1_000_000, u64::MAX] {
+            let x = U256::from(x);
+            let s = sqrt(x);
+            assert!(s * s <= x && (s + U256::from(1)) * (s + U256::from(1)) > x);
+            let c = cbrt(x);
+            let next = c + U256::from(1);
+            assert!(c * c * c <= x && next * next * next > x);
+        }
+        assert_eq!(sqrt(U256::MAX), U256::from(u128::MAX));
+        assert_eq!(cbrt(U256::MAX), U256::from(48740834812604276470692694_u128));
+    }
+
+    #[test]
+    fn test_exp_ln() {
+        // Expected values from Solady's test suite
+        assert_eq!(exp_wad(I256::ZERO), Some(WAD_I));
+        assert_eq!(
+            exp_wad(wad(1)),
+            Some(I256::try_from(2_718_281_828_459_045_235_u64).unwrap())
+        );
+        assert_eq!(exp_wad(-EXP_MIN), Some(I256::ZERO));
+        assert_eq!(exp_wad(EXP_MAX), None);
+        assert_eq!(ln_wad(WAD_I), Some(I256::ZERO));
+        assert_eq!(
+            ln_wad(wad(2)),
+            Some(I256::try_from(693_147_180_559_945_309_u64).unwrap())
+        );
+        assert_eq!(ln_wad(I256::ZERO), None);
+        // The rounding error of ln(2) is amplified by the exponentiation
+        assert_eq!(
+            pow_wad(wad(2), wad(10)),
+            Some(I256::try_from(1_023_999_999_999_999_995_727_u128).unwrap())
+        );
+
+        for value in [-10_i64, -3, -1, 2, 5, 40] {
+            let expected = (value as f64).exp();
+            let actual = exp_wad(wad(value)).unwrap();
+            let actual = i128::try_from(actual).unwrap() as f64 / 1e18;
+            assert!((actual - expected).abs() / expected < 1e-12, "exp({value})");
+        }
+        for value in [1_i64, 3, 10, 1_000_000] {
+            let expected = (value as f64).ln();
+            let actual = i128::try_from(ln_wad(wad(value)).unwrap()).unwrap() as f64 / 1e18;
+            assert!((actual - expected).abs() < 1e-12, "ln({value})");
+        }
+    }
+}
@@ -1,3 +1,11 @@
 //! Common utility functions
 
+pub mod base64;
+pub mod clones;
+pub mod create;
 pub mod crypto;
+pub mod fixed_point_math;
+pub mod metadata;
+pub mod sstore2;
+pub mod strings;
+pub mod svg;
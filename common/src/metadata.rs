@@ -0,0 +1,199 @@
+//! Builds ERC721 metadata JSON and base64 data URIs for fully on-chain tokens.
+//!
+//! `MetadataBuilder` writes every field into a single preallocated string as it is added,
+//! escaping values for JSON, so building metadata only allocates when the buffer grows. The
+//! attributes are written into a second buffer that is appended when the metadata is built.
+//!
+//! See https://docs.opensea.io/docs/metadata-standards for the metadata standard.
+
+use crate::base64;
+
+/// The prefix of base64 encoded JSON data URIs
+pub const JSON_DATA_URI_PREFIX: &str = "data:application/json;base64,";
+
+/// The default capacity of the metadata buffer, which fits the metadata of most tokens
+const DEFAULT_CAPACITY: usize = 256;
+
+/// Builder of the metadata JSON of a token
+pub struct MetadataBuilder {
+    /// The top-level fields, starting with the opening brace
+    json: String,
+    /// The attributes, without the enclosing brackets
+    attributes: String,
+}
+
+impl Default for MetadataBuilder {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+}
+
+impl MetadataBuilder {
+    /// Returns an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns an empty builder whose buffer can hold `capacity` bytes without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut json = String::with_capacity(capacity);
+        json.push('{');
+        Self {
+            json,
+            attributes: String::new(),
+        }
+    }
+
+    /// Sets the name of the token.
+    pub fn name(self, name: &str) -> Self {
+        self.field("name", name)
+    }
+
+    /// Sets the description of the token.
+    pub fn description(self, description: &str) -> Self {
+        self.field("description", description)
+    }
+
+    /// Sets the image URI of the token, which can be a data URI (ex. a base64 encoded SVG).
+    pub fn image(self, image: &str) -> Self {
+        self.field("image", image)
+    }
+
+    /// Sets the URI of the token page on an external site.
+    pub fn external_url(self, external_url: &str) -> Self {
+        self.field("external_url", external_url)
+    }
+
+    /// Sets the URI of a multimedia attachment of the token.
+    pub fn animation_url(self, animation_url: &str) -> Self {
+        self.field("animation_url", animation_url)
+    }
+
+    /// Sets a top-level string field `key` to `value`.
+    pub fn field(mut self, key: &str, value: &str) -> Self {
+        if self.json.len() > 1 {
+            self.json.push(',');
+        }
+        push_json_string(&mut self.json, key);
+        self.json.push(':');
+        push_json_string(&mut self.json, value);
+        self
+    }
+
+    /// Adds an attribute with a string `value`.
+    pub fn attribute(mut self, trait_type: &str, value: &str) -> Self {
+        self.start_attribute(trait_type);
+        push_json_string(&mut self.attributes, value);
+        self.attributes.push('}');
+        self
+    }
+
+    /// Adds an attribute with a numeric `value`, which is written as is and must be a valid JSON
+    /// number (ex. the output of `StringsTrait::to_decimal_string`).
+    pub fn numeric_attribute(mut self, trait_type: &str, value: &str) -> Self {
+        self.start_attribute(trait_type);
+        self.attributes.push_str(value);
+        self.attributes.push('}');
+        self
+    }
+
+    /// Returns the metadata JSON.
+    pub fn build(mut self) -> String {
+        if !self.attributes.is_empty() {
+            if self.json.len() > 1 {
+                self.json.push(',');
+            }
+            self.json.push_str("\"attributes\":[");
+            self.json.push_str(&self.attributes);
+            self.json.push(']');
+        }
+        self.json.push('}');
+        self.json
+    }
+
+    /// Returns the metadata JSON as a base64 encoded data URI, which can be returned by `tokenURI`.
+    pub fn build_data_uri(self) -> String {
+        json_data_uri(&self.build())
+    }
+
+    /// Writes the opening of an attribute up to its value.
+    fn start_attribute(&mut self, trait_type: &str) {
+        if !self.attributes.is_empty() {
+            self.attributes.push(',');
+        }
+        self.attributes.push_str("{\"trait_type\":");
+        push_json_string(&mut self.attributes, trait_type);
+        self.attributes.push_str(",\"value\":");
+    }
+}
+
+/// Returns `json` as a base64 encoded data URI.
+pub fn json_data_uri(json: &str) -> String {
+    let mut uri =
+        String::with_capacity(JSON_DATA_URI_PREFIX.len() + base64::encoded_len(json.len(), false));
+    uri.push_str(JSON_DATA_URI_PREFIX);
+    base64::encode_into(&mut uri, json.as_bytes(), false, false);
+    uri
+}
+
+/// Appends `value` to `out` as a quoted JSON string, escaping quotes, backslashes and control
+/// characters.
+pub fn push_json_string(out: &mut String, value: &str) {
+    const HEX_SYMBOLS: &[u8; 16] = b"0123456789abcdef";
+
+    out.reserve(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                out.push_str("\\u00");
+                out.push(HEX_SYMBOLS[(c as usize) >> 4] as char);
+                out.push(HEX_SYMBOLS[(c as usize) & 0x0f] as char);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build() {
+        assert_eq!(MetadataBuilder::new().build(), "{}");
+        let json = MetadataBuilder::new()
+            .name("Token #1")
+            .description("A \"quoted\"\nline")
+            .image("ipfs://image")
+            .attribute("Color", "Red")
+            .numeric_attribute("Level", "5")
+            .build();
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"name":"Token #1","description":"A \"quoted\"\nline","image":"ipfs://image","#,
+                r#""attributes":[{"trait_type":"Color","value":"Red"},{"trait_type":"Level","value":5}]}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        let mut out = String::new();
+        push_json_string(&mut out, "a\\b\u{1}é");
+        assert_eq!(out, "\"a\\\\b\\u0001é\"");
+    }
+
+    #[test]
+    fn test_build_data_uri() {
+        let uri = MetadataBuilder::new().name("foo").build_data_uri();
+        assert_eq!(uri, "data:application/json;base64,eyJuYW1lIjoiZm9vIn0=");
+    }
+}
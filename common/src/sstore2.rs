@@ -0,0 +1,72 @@
+//! Builds the creation code of SSTORE2 data contracts, which store a blob of bytes as their
+//! runtime code.
+//!
+//! This code is based off of Solady's implementation:
+//! https://github.com/Vectorized/solady/blob/main/src/utils/SSTORE2.sol
+
+/// The runtime code of a data contract is `DATA_CONTRACT_PREFIX ‖ data`. Unlike Solady's data
+/// contracts, which start with STOP so that they cannot be called, the prefix returns the data
+/// when called. Stylus contracts cannot EXTCODECOPY, so the data is read by calling the contract.
+///
+/// 0x600b80380380913d393df3:
+/// - PUSH1 0x0b DUP1 CODESIZE SUB DUP1 SWAP2 (stack: 0x0b, size - 0x0b, size - 0x0b)
+/// - RETURNDATASIZE CODECOPY (copies the data to memory 0)
+/// - RETURNDATASIZE RETURN (returns the data)
+pub const DATA_CONTRACT_PREFIX: [u8; 11] = [
+    0x60, 0x0b, 0x80, 0x38, 0x03, 0x80, 0x91, 0x3d, 0x39, 0x3d, 0xf3,
+];
+
+/// The maximum size of the data, as contract code is limited to 24576 bytes (EIP-170)
+pub const MAX_DATA_SIZE: usize = 24576 - DATA_CONTRACT_PREFIX.len();
+
+/// The creation code of a data contract is `CREATION_CODE_PREFIX ‖ runtime code`, where the two
+/// zero bytes are replaced by the big endian size of the runtime code.
+///
+/// 0x61000080600a3d393df3:
+/// - PUSH2 size DUP1 PUSH1 0x0a (stack: 0x0a, size, size)
+/// - RETURNDATASIZE CODECOPY (copies the runtime code to memory 0)
+/// - RETURNDATASIZE RETURN (returns the runtime code)
+pub const CREATION_CODE_PREFIX: [u8; 10] =
+    [0x61, 0x00, 0x00, 0x80, 0x60, 0x0a, 0x3d, 0x39, 0x3d, 0xf3];
+
+/// Returns the creation code of a data contract storing `data`, or `None` if `data` is larger
+/// than `MAX_DATA_SIZE`.
+pub fn creation_code(data: &[u8]) -> Option<Vec<u8>> {
+    if data.len() > MAX_DATA_SIZE {
+        return None;
+    }
+    let runtime_size = (DATA_CONTRACT_PREFIX.len() + data.len()) as u16;
+    let mut code = Vec::with_capacity(CREATION_CODE_PREFIX.len() + runtime_size as usize);
+    code.extend_from_slice(&CREATION_CODE_PREFIX);
+    code[1..3].copy_from_slice(&runtime_size.to_be_bytes());
+    code.extend_from_slice(&DATA_CONTRACT_PREFIX);
+    code.extend_from_slice(data);
+    Some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_creation_code() {
+        let data = [0xaa_u8; 300];
+        let code = creation_code(&data).unwrap();
+        let runtime = &code[CREATION_CODE_PREFIX.len()..];
+        // PUSH2 is followed by the runtime size, and PUSH1 by the creation prefix size
+        assert_eq!(
+            u16::from_be_bytes([code[1], code[2]]) as usize,
+            runtime.len()
+        );
+        assert_eq!(code[5] as usize, CREATION_CODE_PREFIX.len());
+        // PUSH1 in the runtime is followed by the runtime prefix size
+        assert_eq!(runtime[1] as usize, DATA_CONTRACT_PREFIX.len());
+        assert_eq!(&runtime[DATA_CONTRACT_PREFIX.len()..], &data);
+    }
+
+    #[test]
+    fn test_creation_code_too_large() {
+        assert!(creation_code(&vec![0_u8; MAX_DATA_SIZE]).is_some());
+        assert!(creation_code(&vec![0_u8; MAX_DATA_SIZE + 1]).is_none());
+    }
+}
@@ -0,0 +1,152 @@
+//! Defines the strings trait which converts integers and addresses into their string
+//! representations without going through `format!`, as the formatting machinery significantly
+//! inflates the size of the compiled WASM. The hashing function used for address checksums is left
+//! to the implementation so that contracts can use the VM's native keccak while tests can use a pure
+//! rust implementation.
+//!
+//! This code is based off of Solady's implementation:
+//! https://github.com/Vectorized/solady/blob/main/src/utils/LibString.sol
+
+use alloy_primitives::{Address, U256};
+
+/// The maximum number of digits in the decimal representation of an unsigned 256-bit integer
+pub const MAX_DECIMAL_DIGITS: usize = 78;
+
+/// The number of bytes it takes to represent an Ethereum address
+pub const NUM_BYTES_ADDRESS: usize = 20;
+
+/// The lowercase hexadecimal alphabet
+const HEX_SYMBOLS: &[u8; 16] = b"0123456789abcdef";
+
+pub trait StringsTrait {
+    /// Returns the base 10 decimal representation of `value`.
+    fn to_decimal_string(value: U256) -> String {
+        let mut buffer = [0_u8; MAX_DECIMAL_DIGITS];
+        let mut pos = MAX_DECIMAL_DIGITS;
+        let mut limbs = value.into_limbs();
+        loop {
+            // Long division of the limbs by 10, starting from the most significant limb
+            let mut remainder = 0_u128;
+            for limb in limbs.iter_mut().rev() {
+                let dividend = (remainder << 64) | *limb as u128;
+                *limb = (dividend / 10) as u64;
+                remainder = dividend % 10;
+            }
+            pos -= 1;
+            buffer[pos] = b'0' + remainder as u8;
+            if limbs == [0; 4] {
+                break;
+            }
+        }
+        buffer[pos..].iter().map(|b| *b as char).collect()
+    }
+
+    /// Returns the hexadecimal representation of `value`, left-padded to an even length and
+    /// prefixed with "0x". The output is "0x00" when `value` is zero.
+    fn to_hex_string(value: U256) -> String {
+        let bytes = value.to_be_bytes::<32>();
+        // Always keep at least the last byte so that zero is represented as "0x00"
+        let start = bytes[..31].iter().take_while(|b| **b == 0).count();
+        let mut result = String::with_capacity(2 + 2 * (32 - start));
+        result.push_str("0x");
+        for byte in &bytes[start..] {
+            result.push(HEX_SYMBOLS[(byte >> 4) as usize] as char);
+            result.push(HEX_SYMBOLS[(byte & 0x0f) as usize] as char);
+        }
+        result
+    }
+
+    /// Returns the EIP-55 mixed-case checksummed hexadecimal representation of `address`,
+    /// prefixed with "0x".
+    fn address_checksum_string(address: Address) -> String {
+        let mut lowercase = [0_u8; 2 * NUM_BYTES_ADDRESS];
+        for (i, byte) in address.0.iter().enumerate() {
+            lowercase[2 * i] = HEX_SYMBOLS[(byte >> 4) as usize];
+            lowercase[2 * i + 1] = HEX_SYMBOLS[(byte & 0x0f) as usize];
+        }
+
+        // A letter is uppercased if the corresponding nibble of the hash is at least 8
+        let hash = Self::keccak256(&lowercase);
+        let mut result = String::with_capacity(2 + 2 * NUM_BYTES_ADDRESS);
+        result.push_str("0x");
+        for (i, c) in lowercase.iter().enumerate() {
+            let nibble = if i % 2 == 0 {
+                hash[i / 2] >> 4
+            } else {
+                hash[i / 2] & 0x0f
+            };
+            if nibble >= 8 {
+                result.push(c.to_ascii_uppercase() as char);
+            } else {
+                result.push(*c as char);
+            }
+        }
+        result
+    }
+
+    fn keccak256(data: &[u8]) -> [u8; 32];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{types::H160, utils::keccak256};
+
+    struct RustStrings;
+
+    impl StringsTrait for RustStrings {
+        fn keccak256(data: &[u8]) -> [u8; 32] {
+            keccak256(data)
+        }
+    }
+
+    #[test]
+    fn test_to_decimal_string() {
+        let values = [
+            U256::ZERO,
+            U256::from(7),
+            U256::from(10),
+            U256::from(1234567890_u64),
+            U256::from(u64::MAX),
+            U256::from(u128::MAX) + U256::from(1),
+            U256::MAX,
+        ];
+        for value in values {
+            assert_eq!(RustStrings::to_decimal_string(value), value.to_string());
+        }
+    }
+
+    #[test]
+    fn test_to_hex_string() {
+        assert_eq!(RustStrings::to_hex_string(U256::ZERO), "0x00");
+        assert_eq!(RustStrings::to_hex_string(U256::from(0xf)), "0x0f");
+        assert_eq!(RustStrings::to_hex_string(U256::from(0x1234)), "0x1234");
+        assert_eq!(RustStrings::to_hex_string(U256::from(0x12345)), "0x012345");
+        assert_eq!(
+            RustStrings::to_hex_string(U256::MAX),
+            format!("0x{}", "ff".repeat(32))
+        );
+    }
+
+    #[test]
+    fn test_address_checksum_string() {
+        let addresses = [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+        for expected in addresses {
+            let address = Address::from_slice(&hex_to_bytes(expected));
+            assert_eq!(RustStrings::address_checksum_string(address), expected);
+            assert_eq!(
+                RustStrings::address_checksum_string(address),
+                ethers::utils::to_checksum(&H160::from(address.0 .0), None)
+            );
+        }
+    }
+
+    fn hex_to_bytes(s: &str) -> Vec<u8> {
+        ethers::utils::hex::decode(s.trim_start_matches("0x")).unwrap()
+    }
+}
@@ -0,0 +1,279 @@
+//! Composes SVG images into a string for generative on-chain art, without going through
+//! `format!`, as the formatting machinery significantly inflates the size of the compiled WASM.
+//!
+//! `Svg` writes every element into a single preallocated buffer as it is added. Numeric attributes
+//! are written with a small integer formatter, and text content and string attributes are escaped
+//! for XML. The finished image can be embedded in token metadata as a base64 data URI.
+
+use crate::base64;
+
+/// The prefix of base64 encoded SVG data URIs
+pub const SVG_DATA_URI_PREFIX: &str = "data:image/svg+xml;base64,";
+
+/// The default capacity of the SVG buffer
+const DEFAULT_CAPACITY: usize = 1024;
+
+/// The maximum number of digits in the decimal representation of an unsigned 32-bit integer
+const MAX_U32_DIGITS: usize = 10;
+
+/// Builder of an SVG image
+pub struct Svg {
+    buf: String,
+}
+
+impl Svg {
+    /// Returns an SVG image of `width` by `height` pixels, with a matching view box.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self::with_capacity(width, height, DEFAULT_CAPACITY)
+    }
+
+    /// Returns an SVG image of `width` by `height` pixels, whose buffer can hold `capacity` bytes
+    /// without reallocating.
+    pub fn with_capacity(width: u32, height: u32, capacity: usize) -> Self {
+        let mut buf = String::with_capacity(capacity);
+        buf.push_str("<svg xmlns=\"http://www.w3.org/2000/svg\"");
+        push_number_attribute(&mut buf, "width", width);
+        push_number_attribute(&mut buf, "height", height);
+        buf.push_str(" viewBox=\"0 0 ");
+        push_u32(&mut buf, width);
+        buf.push(' ');
+        push_u32(&mut buf, height);
+        buf.push_str("\">");
+        Self { buf }
+    }
+
+    /// Adds a rectangle with its top left corner at (`x`, `y`).
+    pub fn rect(&mut self, x: u32, y: u32, width: u32, height: u32, fill: &str) -> &mut Self {
+        self.buf.push_str("<rect");
+        push_number_attribute(&mut self.buf, "x", x);
+        push_number_attribute(&mut self.buf, "y", y);
+        push_number_attribute(&mut self.buf, "width", width);
+        push_number_attribute(&mut self.buf, "height", height);
+        push_attribute(&mut self.buf, "fill", fill);
+        self.buf.push_str("/>");
+        self
+    }
+
+    /// Adds a circle centered at (`cx`, `cy`).
+    pub fn circle(&mut self, cx: u32, cy: u32, r: u32, fill: &str) -> &mut Self {
+        self.buf.push_str("<circle");
+        push_number_attribute(&mut self.buf, "cx", cx);
+        push_number_attribute(&mut self.buf, "cy", cy);
+        push_number_attribute(&mut self.buf, "r", r);
+        push_attribute(&mut self.buf, "fill", fill);
+        self.buf.push_str("/>");
+        self
+    }
+
+    /// Adds a path following the commands of `d`.
+    /// The stroke is omitted when `stroke` is empty.
+    pub fn path(&mut self, d: &str, fill: &str, stroke: &str) -> &mut Self {
+        self.buf.push_str("<path");
+        push_attribute(&mut self.buf, "d", d);
+        push_attribute(&mut self.buf, "fill", fill);
+        if !stroke.is_empty() {
+            push_attribute(&mut self.buf, "stroke", stroke);
+        }
+        self.buf.push_str("/>");
+        self
+    }
+
+    /// Adds `content` as text starting at (`x`, `y`), with additional `attributes`
+    /// (ex. `font-size` or `text-anchor`).
+    pub fn text(
+        &mut self,
+        x: u32,
+        y: u32,
+        content: &str,
+        attributes: &[(&str, &str)],
+    ) -> &mut Self {
+        self.buf.push_str("<text");
+        push_number_attribute(&mut self.buf, "x", x);
+        push_number_attribute(&mut self.buf, "y", y);
+        for (name, value) in attributes {
+            push_attribute(&mut self.buf, name, value);
+        }
+        self.buf.push('>');
+        push_escaped(&mut self.buf, content);
+        self.buf.push_str("</text>");
+        self
+    }
+
+    /// Defines a horizontal linear gradient with `id`, which elements can use as a fill with
+    /// `url(#id)`. Each stop is an offset in percent and a color.
+    pub fn linear_gradient(&mut self, id: &str, stops: &[(u32, &str)]) -> &mut Self {
+        self.gradient("linearGradient", id, stops)
+    }
+
+    /// Defines a radial gradient with `id`, which elements can use as a fill with `url(#id)`.
+    /// Each stop is an offset in percent and a color.
+    pub fn radial_gradient(&mut self, id: &str, stops: &[(u32, &str)]) -> &mut Self {
+        self.gradient("radialGradient", id, stops)
+    }
+
+    /// Adds an element `tag` with `attributes` and no children.
+    pub fn element(&mut self, tag: &str, attributes: &[(&str, &str)]) -> &mut Self {
+        self.open(tag, attributes);
+        self.buf.truncate(self.buf.len() - 1);
+        self.buf.push_str("/>");
+        self
+    }
+
+    /// Opens an element `tag` with `attributes` (ex. a `g` group), whose children are the
+    /// elements added until it is closed with `close`.
+    pub fn open(&mut self, tag: &str, attributes: &[(&str, &str)]) -> &mut Self {
+        self.buf.push('<');
+        self.buf.push_str(tag);
+        for (name, value) in attributes {
+            push_attribute(&mut self.buf, name, value);
+        }
+        self.buf.push('>');
+        self
+    }
+
+    /// Closes the element `tag` opened with `open`.
+    pub fn close(&mut self, tag: &str) -> &mut Self {
+        self.buf.push_str("</");
+        self.buf.push_str(tag);
+        self.buf.push('>');
+        self
+    }
+
+    /// Appends `markup` as is. The caller is responsible for it being valid SVG.
+    pub fn raw(&mut self, markup: &str) -> &mut Self {
+        self.buf.push_str(markup);
+        self
+    }
+
+    /// Returns the SVG image.
+    pub fn finish(mut self) -> String {
+        self.buf.push_str("</svg>");
+        self.buf
+    }
+
+    /// Returns the SVG image as a base64 encoded data URI, which can be used as a metadata image.
+    pub fn finish_data_uri(self) -> String {
+        let svg = self.finish();
+        let mut uri = String::with_capacity(
+            SVG_DATA_URI_PREFIX.len() + base64::encoded_len(svg.len(), false),
+        );
+        uri.push_str(SVG_DATA_URI_PREFIX);
+        base64::encode_into(&mut uri, svg.as_bytes(), false, false);
+        uri
+    }
+
+    /// Defines a gradient `tag` with `id` and `stops` inside a `defs` element.
+    fn gradient(&mut self, tag: &str, id: &str, stops: &[(u32, &str)]) -> &mut Self {
+        self.buf.push_str("<defs>");
+        self.open(tag, &[("id", id)]);
+        for (offset, color) in stops {
+            self.buf.push_str("<stop offset=\"");
+            push_u32(&mut self.buf, *offset);
+            self.buf.push_str("%\"");
+            push_attribute(&mut self.buf, "stop-color", color);
+            self.buf.push_str("/>");
+        }
+        self.close(tag);
+        self.buf.push_str("</defs>");
+        self
+    }
+}
+
+/// Appends the decimal representation of `value` to `out`.
+pub fn push_u32(out: &mut String, mut value: u32) {
+    let mut buffer = [0_u8; MAX_U32_DIGITS];
+    let mut pos = MAX_U32_DIGITS;
+    loop {
+        pos -= 1;
+        buffer[pos] = b'0' + (value % 10) as u8;
+        value /= 10;
+        if value == 0 {
+            break;
+        }
+    }
+    for digit in &buffer[pos..] {
+        out.push(*digit as char);
+    }
+}
+
+/// Appends `value` to `out`, escaping the characters reserved by XML.
+pub fn push_escaped(out: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+}
+
+/// Appends the attribute ` name="value"` to `out`, escaping `value`.
+fn push_attribute(out: &mut String, name: &str, value: &str) {
+    out.push(' ');
+    out.push_str(name);
+    out.push_str("=\"");
+    push_escaped(out, value);
+    out.push('"');
+}
+
+/// Appends the numeric attribute ` name="value"` to `out`.
+fn push_number_attribute(out: &mut String, name: &str, value: u32) {
+    out.push(' ');
+    out.push_str(name);
+    out.push_str("=\"");
+    push_u32(out, value);
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_u32() {
+        for value in [0, 7, 10, 1234567890, u32::MAX] {
+            let mut out = String::new();
+            push_u32(&mut out, value);
+            assert_eq!(out, value.to_string());
+        }
+    }
+
+    #[test]
+    fn test_svg() {
+        let mut svg = Svg::new(100, 50);
+        svg.linear_gradient("bg", &[(0, "#000"), (100, "#fff")])
+            .rect(0, 0, 100, 50, "url(#bg)")
+            .circle(50, 25, 10, "red")
+            .path("M0 0L10 10", "none", "blue")
+            .text(5, 45, "a < b & \"c\"", &[("font-size", "8")])
+            .open("g", &[("opacity", "0.5")])
+            .element("line", &[("x1", "0"), ("x2", "100")])
+            .close("g");
+        assert_eq!(
+            svg.finish(),
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="50" viewBox="0 0 100 50">"#,
+                r##"<defs><linearGradient id="bg"><stop offset="0%" stop-color="#000"/><stop offset="100%" stop-color="#fff"/></linearGradient></defs>"##,
+                r##"<rect x="0" y="0" width="100" height="50" fill="url(#bg)"/>"##,
+                r#"<circle cx="50" cy="25" r="10" fill="red"/>"#,
+                r#"<path d="M0 0L10 10" fill="none" stroke="blue"/>"#,
+                r#"<text x="5" y="45" font-size="8">a &lt; b &amp; &quot;c&quot;</text>"#,
+                r#"<g opacity="0.5"><line x1="0" x2="100"/></g>"#,
+                "</svg>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_finish_data_uri() {
+        let uri = Svg::new(1, 1).finish_data_uri();
+        assert!(uri.starts_with(SVG_DATA_URI_PREFIX));
+        assert_eq!(
+            &uri[SVG_DATA_URI_PREFIX.len()..],
+            base64::encode(Svg::new(1, 1).finish().as_bytes())
+        );
+    }
+}
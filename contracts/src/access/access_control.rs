@@ -0,0 +1,181 @@
+//! Role-based access control mixin.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/access/AccessControl.sol
+//!
+//! Roles are referred to by their `bytes32` identifier, usually the keccak256 hash of the role name.
+//! Each role has an admin role, and only accounts holding a role's admin role can grant or revoke it.
+//! By default, the admin role for all roles is `DEFAULT_ADMIN_ROLE`.
+//!
+//! The inheriting contract must call `_grant_role(DEFAULT_ADMIN_ROLE, admin)` during its
+//! [initialization](crate#initialization).
+
+use stylus_sdk::{
+    alloy_primitives::{Address, B256},
+    alloy_sol_types::sol,
+    evm, msg,
+    prelude::*,
+};
+
+/// The default admin role, which is the admin role of all roles unless changed.
+pub const DEFAULT_ADMIN_ROLE: B256 = B256::ZERO;
+
+sol_storage! {
+    /// AccessControl stores role memberships and the admin role of each role
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.AccessControl"))]
+    pub struct AccessControl {
+        /// Maps role to whether an account has been granted the role
+        mapping(bytes32 => mapping(address => bool)) members;
+        /// Maps role to its admin role
+        mapping(bytes32 => bytes32) admin_roles;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when `newAdminRole` is set as `role`'s admin role, replacing `previousAdminRole`.
+    event RoleAdminChanged(bytes32 indexed role, bytes32 indexed previousAdminRole, bytes32 indexed newAdminRole);
+    /// Emitted when `account` is granted `role` by `sender`.
+    event RoleGranted(bytes32 indexed role, address indexed account, address indexed sender);
+    /// Emitted when `account` is revoked `role` by `sender`.
+    event RoleRevoked(bytes32 indexed role, address indexed account, address indexed sender);
+
+    /// The `account` is missing the `neededRole`.
+    error AccessControlUnauthorizedAccount(address account, bytes32 neededRole);
+    /// The caller of a function is not the expected one (ex. renouncing a role for another account).
+    error AccessControlBadConfirmation();
+}
+
+#[derive(SolidityError)]
+pub enum AccessControlError {
+    AccessControlUnauthorizedAccount(AccessControlUnauthorizedAccount),
+    AccessControlBadConfirmation(AccessControlBadConfirmation),
+}
+
+// Internal functions
+impl AccessControl {
+    /// Throws if the sender does not have `role`.
+    pub fn only_role(&self, role: B256) -> Result<(), AccessControlError> {
+        self._check_role(role, msg::sender())
+    }
+
+    /// Throws if `account` does not have `role`.
+    pub fn _check_role(&self, role: B256, account: Address) -> Result<(), AccessControlError> {
+        if !self.has_role(role, account) {
+            return Err(AccessControlError::AccessControlUnauthorizedAccount(
+                AccessControlUnauthorizedAccount {
+                    account,
+                    neededRole: role.0,
+                },
+            ));
+        }
+        Ok(())
+    }
+
+    /// Sets `admin_role` as `role`'s admin role.
+    ///
+    /// Emits a {RoleAdminChanged} event.
+    pub fn _set_role_admin(&mut self, role: B256, admin_role: B256) {
+        let previous_admin_role = self.admin_roles.replace(role, admin_role);
+        evm::log(RoleAdminChanged {
+            role: role.0,
+            previousAdminRole: previous_admin_role.0,
+            newAdminRole: admin_role.0,
+        });
+    }
+
+    /// Grants `role` to `account` without access restriction.
+    /// Returns whether the role was granted.
+    ///
+    /// Emits a {RoleGranted} event if `account` did not already have `role`.
+    pub fn _grant_role(&mut self, role: B256, account: Address) -> bool {
+        let mut members = self.members.setter(role);
+        let mut member = members.setter(account);
+        if member.get() {
+            return false;
+        }
+        member.set(true);
+        evm::log(RoleGranted {
+            role: role.0,
+            account,
+            sender: msg::sender(),
+        });
+        true
+    }
+
+    /// Revokes `role` from `account` without access restriction.
+    /// Returns whether the role was revoked.
+    ///
+    /// Emits a {RoleRevoked} event if `account` had `role`.
+    pub fn _revoke_role(&mut self, role: B256, account: Address) -> bool {
+        let mut members = self.members.setter(role);
+        let mut member = members.setter(account);
+        if !member.get() {
+            return false;
+        }
+        member.set(false);
+        evm::log(RoleRevoked {
+            role: role.0,
+            account,
+            sender: msg::sender(),
+        });
+        true
+    }
+}
+
+// External functions
+#[external]
+impl AccessControl {
+    /// Returns whether `account` has been granted `role`.
+    pub fn has_role(&self, role: B256, account: Address) -> bool {
+        self.members.getter(role).get(account)
+    }
+
+    /// Returns the admin role that controls `role`.
+    pub fn get_role_admin(&self, role: B256) -> B256 {
+        self.admin_roles.get(role)
+    }
+
+    /// Grants `role` to `account`.
+    ///
+    /// Requirements:
+    /// - The caller must have `role`'s admin role.
+    ///
+    /// Emits a {RoleGranted} event if `account` did not already have `role`.
+    pub fn grant_role(&mut self, role: B256, account: Address) -> Result<(), AccessControlError> {
+        self.only_role(self.get_role_admin(role))?;
+        self._grant_role(role, account);
+        Ok(())
+    }
+
+    /// Revokes `role` from `account`.
+    ///
+    /// Requirements:
+    /// - The caller must have `role`'s admin role.
+    ///
+    /// Emits a {RoleRevoked} event if `account` had `role`.
+    pub fn revoke_role(&mut self, role: B256, account: Address) -> Result<(), AccessControlError> {
+        self.only_role(self.get_role_admin(role))?;
+        self._revoke_role(role, account);
+        Ok(())
+    }
+
+    /// Revokes `role` from the calling account.
+    ///
+    /// Requirements:
+    /// - The caller must be `caller_confirmation`.
+    ///
+    /// Emits a {RoleRevoked} event if the caller had `role`.
+    pub fn renounce_role(
+        &mut self,
+        role: B256,
+        caller_confirmation: Address,
+    ) -> Result<(), AccessControlError> {
+        if caller_confirmation != msg::sender() {
+            return Err(AccessControlError::AccessControlBadConfirmation(
+                AccessControlBadConfirmation {},
+            ));
+        }
+        self._revoke_role(role, caller_confirmation);
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+//! Access control contracts for gating privileged functions
+
+#[cfg(feature = "access-control")]
+pub mod access_control;
+
+#[cfg(feature = "ownable")]
+pub mod ownable;
@@ -0,0 +1,201 @@
+//! Simple single owner authorization mixin, compatible with ERC-173.
+//! Provides both a one-step (`Ownable`) and a two-step (`Ownable2Step`) ownership transfer flow.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/access/Ownable2Step.sol
+//! Doc comments are forked from: https://github.com/Vectorized/solady/blob/main/src/auth/Ownable.sol
+//!
+//! The inheriting contract must call `_initialize_owner` exactly once, see
+//! [initialization](crate#initialization).
+
+use stylus_sdk::{alloy_primitives::Address, alloy_sol_types::sol, evm, msg, prelude::*};
+
+sol_storage! {
+    /// Ownable stores the current owner of the contract
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.Ownable"))]
+    pub struct Ownable {
+        address owner;
+    }
+
+    /// Ownable2Step requires the new owner to accept a pending ownership transfer
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.Ownable2Step"))]
+    pub struct Ownable2Step {
+        Ownable ownable;
+        address pending_owner;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// The ownership is transferred from `previousOwner` to `newOwner`.
+    event OwnershipTransferred(address indexed previousOwner, address indexed newOwner);
+    /// An ownership transfer to `newOwner` has been started by `previousOwner`.
+    event OwnershipTransferStarted(address indexed previousOwner, address indexed newOwner);
+
+    /// The caller is not authorized to call the function.
+    error OwnableUnauthorizedAccount(address account);
+    /// The owner is not a valid owner account (ex. the zero address).
+    error OwnableInvalidOwner(address owner);
+    /// The owner has already been initialized.
+    error OwnableAlreadyInitialized();
+}
+
+#[derive(SolidityError)]
+pub enum OwnableError {
+    OwnableUnauthorizedAccount(OwnableUnauthorizedAccount),
+    OwnableInvalidOwner(OwnableInvalidOwner),
+    OwnableAlreadyInitialized(OwnableAlreadyInitialized),
+}
+
+// Internal functions
+impl Ownable {
+    /// Initializes the owner directly without authorization guard.
+    /// This function must be called upon initialization,
+    /// regardless of whether the contract is upgradeable or not.
+    ///
+    /// Emits a {OwnershipTransferred} event.
+    pub fn _initialize_owner(&mut self, new_owner: Address) -> Result<(), OwnableError> {
+        if !self.owner.get().is_zero() {
+            return Err(OwnableError::OwnableAlreadyInitialized(
+                OwnableAlreadyInitialized {},
+            ));
+        }
+        if new_owner.is_zero() {
+            return Err(OwnableError::OwnableInvalidOwner(OwnableInvalidOwner {
+                owner: new_owner,
+            }));
+        }
+        self._set_owner(new_owner);
+        Ok(())
+    }
+
+    /// Sets the owner directly without authorization guard.
+    ///
+    /// Emits a {OwnershipTransferred} event.
+    pub fn _set_owner(&mut self, new_owner: Address) {
+        let previous_owner = self.owner.get();
+        self.owner.set(new_owner);
+        evm::log(OwnershipTransferred {
+            previousOwner: previous_owner,
+            newOwner: new_owner,
+        });
+    }
+
+    /// Throws if the sender is not the owner.
+    pub fn only_owner(&self) -> Result<(), OwnableError> {
+        if msg::sender() != self.owner.get() {
+            return Err(OwnableError::OwnableUnauthorizedAccount(
+                OwnableUnauthorizedAccount {
+                    account: msg::sender(),
+                },
+            ));
+        }
+        Ok(())
+    }
+}
+
+// External functions
+#[external]
+impl Ownable {
+    /// Returns the owner of the contract.
+    pub fn owner(&self) -> Address {
+        self.owner.get()
+    }
+
+    /// Allows the owner to transfer the ownership to `new_owner`.
+    ///
+    /// Emits a {OwnershipTransferred} event.
+    pub fn transfer_ownership(&mut self, new_owner: Address) -> Result<(), OwnableError> {
+        self.only_owner()?;
+        if new_owner.is_zero() {
+            return Err(OwnableError::OwnableInvalidOwner(OwnableInvalidOwner {
+                owner: new_owner,
+            }));
+        }
+        self._set_owner(new_owner);
+        Ok(())
+    }
+
+    /// Allows the owner to renounce their ownership.
+    ///
+    /// Emits a {OwnershipTransferred} event.
+    pub fn renounce_ownership(&mut self) -> Result<(), OwnableError> {
+        self.only_owner()?;
+        self._set_owner(Address::ZERO);
+        Ok(())
+    }
+}
+
+// Internal functions
+impl Ownable2Step {
+    /// Initializes the owner directly without authorization guard.
+    /// See [`Ownable::_initialize_owner`].
+    pub fn _initialize_owner(&mut self, new_owner: Address) -> Result<(), OwnableError> {
+        self.ownable._initialize_owner(new_owner)
+    }
+
+    /// Sets the owner directly and clears any pending ownership transfer.
+    ///
+    /// Emits a {OwnershipTransferred} event.
+    pub fn _set_owner(&mut self, new_owner: Address) {
+        self.pending_owner.set(Address::ZERO);
+        self.ownable._set_owner(new_owner);
+    }
+
+    /// Throws if the sender is not the owner.
+    pub fn only_owner(&self) -> Result<(), OwnableError> {
+        self.ownable.only_owner()
+    }
+}
+
+// External functions
+#[external]
+impl Ownable2Step {
+    /// Returns the owner of the contract.
+    pub fn owner(&self) -> Address {
+        self.ownable.owner.get()
+    }
+
+    /// Returns the address of the pending owner.
+    pub fn pending_owner(&self) -> Address {
+        self.pending_owner.get()
+    }
+
+    /// Starts the ownership transfer of the contract to `new_owner`.
+    /// Replaces the pending transfer if there is one.
+    /// Setting `new_owner` to the zero address cancels the pending transfer.
+    ///
+    /// Emits a {OwnershipTransferStarted} event.
+    pub fn transfer_ownership(&mut self, new_owner: Address) -> Result<(), OwnableError> {
+        self.only_owner()?;
+        self.pending_owner.set(new_owner);
+        evm::log(OwnershipTransferStarted {
+            previousOwner: self.ownable.owner.get(),
+            newOwner: new_owner,
+        });
+        Ok(())
+    }
+
+    /// Allows the pending owner to accept the ownership transfer.
+    ///
+    /// Emits a {OwnershipTransferred} event.
+    pub fn accept_ownership(&mut self) -> Result<(), OwnableError> {
+        let sender = msg::sender();
+        if sender != self.pending_owner.get() {
+            return Err(OwnableError::OwnableUnauthorizedAccount(
+                OwnableUnauthorizedAccount { account: sender },
+            ));
+        }
+        self._set_owner(sender);
+        Ok(())
+    }
+
+    /// Allows the owner to renounce their ownership.
+    ///
+    /// Emits a {OwnershipTransferred} event.
+    pub fn renounce_ownership(&mut self) -> Result<(), OwnableError> {
+        self.only_owner()?;
+        self._set_owner(Address::ZERO);
+        Ok(())
+    }
+}
@@ -0,0 +1,276 @@
+//! ERC-4337 smart account base with a single owner, compatible with the v0.7 EntryPoint.
+//! The logic was based off of: https://github.com/eth-infinitism/account-abstraction/blob/develop/contracts/samples/SimpleAccount.sol
+//!
+//! User operations are validated by checking that the owner signed the `eth_sign` hash of the
+//! user operation hash. Nonces are managed by the EntryPoint, which rejects operations whose
+//! nonce does not match the account's current nonce for the operation's key.
+//!
+//! Stylus does not support `receive` or `fallback` functions, so the account can only be funded
+//! through the payable `add_deposit` method or by depositing to the EntryPoint on its behalf.
+//! The inheriting contract is responsible for calling `_initialize_owner` exactly once.
+
+use alloc::vec::Vec;
+use core::{
+    borrow::{Borrow, BorrowMut},
+    marker::PhantomData,
+};
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::sol,
+    call::{transfer_eth, Call, RawCall},
+    contract,
+    crypto::keccak,
+    evm, msg,
+    prelude::*,
+};
+
+use crate::utils::signature_checker::is_valid_ecdsa_signature_now;
+
+/// Returned by `validate_user_op` when the signature is valid.
+pub const SIG_VALIDATION_SUCCESS: U256 = U256::ZERO;
+
+/// Returned by `validate_user_op` when the signature is invalid.
+pub const SIG_VALIDATION_FAILED: U256 = U256::from_limbs([1, 0, 0, 0]);
+
+/// The v0.7 `PackedUserOperation` struct:
+/// `(sender, nonce, initCode, callData, accountGasLimits, preVerificationGas, gasFees,
+/// paymasterAndData, signature)`.
+pub type PackedUserOperation = (Address, U256, Bytes, Bytes, B256, U256, B256, Bytes, Bytes);
+
+pub trait ERC4337AccountParams {
+    /// The EntryPoint contract trusted by the account.
+    const ENTRY_POINT: Address;
+}
+
+sol_storage! {
+    /// ERC4337Account stores the owner allowed to sign user operations
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC4337Account"))]
+    pub struct ERC4337Account<T> {
+        address owner;
+        PhantomData<T> phantom;
+    }
+}
+
+sol_interface! {
+    /// Allows calls to the nonce and deposit management methods of the ERC-4337 EntryPoint.
+    interface IEntryPoint {
+        function getNonce(address sender, uint192 key) external view returns (uint256 nonce);
+        function balanceOf(address account) external view returns (uint256);
+        function depositTo(address account) external payable;
+        function withdrawTo(address withdraw_address, uint256 amount) external;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// The account has been initialized with `owner`.
+    event AccountInitialized(address indexed entryPoint, address indexed owner);
+
+    /// The caller is not authorized to call the function.
+    error AccountUnauthorized(address caller);
+    /// The account has already been initialized.
+    error AccountAlreadyInitialized();
+    /// The array arguments of a batch have mismatched lengths.
+    error AccountInvalidBatchLength();
+    /// The call to the EntryPoint has failed.
+    error AccountEntryPointCallFailed();
+}
+
+#[derive(SolidityError)]
+pub enum ERC4337AccountError {
+    AccountUnauthorized(AccountUnauthorized),
+    AccountAlreadyInitialized(AccountAlreadyInitialized),
+    AccountInvalidBatchLength(AccountInvalidBatchLength),
+    AccountEntryPointCallFailed(AccountEntryPointCallFailed),
+}
+
+// Internal functions
+impl<T: ERC4337AccountParams> ERC4337Account<T> {
+    /// Initializes the owner of the account.
+    ///
+    /// Requirements:
+    /// - The account must not have been initialized.
+    ///
+    /// Emits a {AccountInitialized} event.
+    pub fn _initialize_owner(&mut self, owner: Address) -> Result<(), ERC4337AccountError> {
+        if !self.owner.get().is_zero() {
+            return Err(ERC4337AccountError::AccountAlreadyInitialized(
+                AccountAlreadyInitialized {},
+            ));
+        }
+        self.owner.set(owner);
+        evm::log(AccountInitialized {
+            entryPoint: T::ENTRY_POINT,
+            owner,
+        });
+        Ok(())
+    }
+
+    /// Throws if the sender is not the EntryPoint.
+    pub fn only_entry_point(&self) -> Result<(), ERC4337AccountError> {
+        if msg::sender() != T::ENTRY_POINT {
+            return Err(ERC4337AccountError::AccountUnauthorized(
+                AccountUnauthorized {
+                    caller: msg::sender(),
+                },
+            ));
+        }
+        Ok(())
+    }
+
+    /// Throws if the sender is neither the EntryPoint nor the owner.
+    pub fn only_entry_point_or_owner(&self) -> Result<(), ERC4337AccountError> {
+        let sender = msg::sender();
+        if sender != T::ENTRY_POINT && sender != self.owner.get() {
+            return Err(ERC4337AccountError::AccountUnauthorized(
+                AccountUnauthorized { caller: sender },
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns whether `signature` is the owner's signature of the `eth_sign` hash of
+    /// `user_op_hash`.
+    pub fn _validate_signature(&self, user_op_hash: B256, signature: &[u8]) -> bool {
+        let mut message = [0_u8; 60];
+        message[..28].copy_from_slice(b"\x19Ethereum Signed Message:\n32");
+        message[28..].copy_from_slice(&user_op_hash.0);
+        is_valid_ecdsa_signature_now(self.owner.get(), keccak(message), signature)
+    }
+
+    /// Calls `target` with `value` and `data`, bubbling up the revert data on failure.
+    pub fn _call(target: Address, value: U256, data: &[u8]) -> Result<(), Vec<u8>> {
+        RawCall::new_with_value(value)
+            .skip_return_data()
+            .call(target, data)?;
+        Ok(())
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC4337AccountParams> ERC4337Account<T> {
+    /// Returns the EntryPoint trusted by the account.
+    pub fn entry_point(&self) -> Address {
+        T::ENTRY_POINT
+    }
+
+    /// Returns the owner of the account.
+    pub fn owner(&self) -> Address {
+        self.owner.get()
+    }
+
+    /// Returns the account's nonce for the default key, as tracked by the EntryPoint.
+    pub fn get_nonce<S: TopLevelStorage + Borrow<Self>>(
+        storage: &S,
+    ) -> Result<U256, ERC4337AccountError> {
+        IEntryPoint::new(T::ENTRY_POINT)
+            .get_nonce(storage, contract::address(), U256::ZERO)
+            .map_err(|_| {
+                ERC4337AccountError::AccountEntryPointCallFailed(AccountEntryPointCallFailed {})
+            })
+    }
+
+    /// Validates the signature of `user_op` and pays the EntryPoint the `missing_account_funds`
+    /// required to execute it. Returns `SIG_VALIDATION_SUCCESS` or `SIG_VALIDATION_FAILED`.
+    ///
+    /// Requirements:
+    /// - The caller must be the EntryPoint.
+    pub fn validate_user_op(
+        &mut self,
+        user_op: PackedUserOperation,
+        user_op_hash: B256,
+        missing_account_funds: U256,
+    ) -> Result<U256, ERC4337AccountError> {
+        self.only_entry_point()?;
+        let validation_data = if self._validate_signature(user_op_hash, &user_op.8 .0) {
+            SIG_VALIDATION_SUCCESS
+        } else {
+            SIG_VALIDATION_FAILED
+        };
+        if !missing_account_funds.is_zero() {
+            // Ignore failure, it is the EntryPoint's job to verify the prefund
+            let _ = transfer_eth(T::ENTRY_POINT, missing_account_funds);
+        }
+        Ok(validation_data)
+    }
+
+    /// Executes a call to `target` with `value` and `data`.
+    ///
+    /// Requirements:
+    /// - The caller must be the EntryPoint or the owner.
+    pub fn execute(&mut self, target: Address, value: U256, data: Bytes) -> Result<(), Vec<u8>> {
+        self.only_entry_point_or_owner()?;
+        Self::_call(target, value, &data.0)
+    }
+
+    /// Executes a batch of calls. `values` may be empty, in which case no value is sent.
+    ///
+    /// Requirements:
+    /// - The caller must be the EntryPoint or the owner.
+    /// - `targets` and `data` must have the same length, as well as `values` if it is not empty.
+    pub fn execute_batch(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        data: Vec<Bytes>,
+    ) -> Result<(), Vec<u8>> {
+        self.only_entry_point_or_owner()?;
+        if targets.len() != data.len() || (!values.is_empty() && values.len() != data.len()) {
+            return Err(ERC4337AccountError::AccountInvalidBatchLength(
+                AccountInvalidBatchLength {},
+            )
+            .into());
+        }
+        for (i, (target, data)) in targets.iter().zip(data.iter()).enumerate() {
+            let value = values.get(i).copied().unwrap_or_default();
+            Self::_call(*target, value, &data.0)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the account's deposit in the EntryPoint.
+    pub fn get_deposit<S: TopLevelStorage + Borrow<Self>>(
+        storage: &S,
+    ) -> Result<U256, ERC4337AccountError> {
+        IEntryPoint::new(T::ENTRY_POINT)
+            .balance_of(storage, contract::address())
+            .map_err(|_| {
+                ERC4337AccountError::AccountEntryPointCallFailed(AccountEntryPointCallFailed {})
+            })
+    }
+
+    /// Deposits the sent value to the account's deposit in the EntryPoint.
+    #[payable]
+    pub fn add_deposit<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+    ) -> Result<(), ERC4337AccountError> {
+        IEntryPoint::new(T::ENTRY_POINT)
+            .deposit_to(
+                Call::new_in(storage).value(msg::value()),
+                contract::address(),
+            )
+            .map_err(|_| {
+                ERC4337AccountError::AccountEntryPointCallFailed(AccountEntryPointCallFailed {})
+            })
+    }
+
+    /// Withdraws `amount` from the account's deposit in the EntryPoint to `withdraw_address`.
+    ///
+    /// Requirements:
+    /// - The caller must be the EntryPoint or the owner.
+    pub fn withdraw_deposit_to<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        withdraw_address: Address,
+        amount: U256,
+    ) -> Result<(), ERC4337AccountError> {
+        storage.borrow_mut().only_entry_point_or_owner()?;
+        IEntryPoint::new(T::ENTRY_POINT)
+            .withdraw_to(storage, withdraw_address, amount)
+            .map_err(|_| {
+                ERC4337AccountError::AccountEntryPointCallFailed(AccountEntryPointCallFailed {})
+            })
+    }
+}
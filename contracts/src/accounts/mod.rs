@@ -0,0 +1,4 @@
+//! Account abstraction contracts
+
+#[cfg(feature = "erc4337-account")]
+pub mod erc4337_account;
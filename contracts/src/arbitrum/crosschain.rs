@@ -0,0 +1,93 @@
+//! L1 to L2 address aliasing and L2 to L1 messaging helpers for Arbitrum chains.
+//! The logic was based off of: https://github.com/OffchainLabs/nitro-contracts/blob/main/src/libraries/AddressAliasHelper.sol
+//!
+//! L1 contracts call L2 contracts through retryable tickets, which are executed with the address
+//! of the L1 contract plus a constant offset as `msg::sender`. The aliasing prevents an L1 contract
+//! from impersonating the L2 contract deployed at the same address. Contracts controlled from L1
+//! (ex. by a DAO timelock) check that the caller is the alias of the L1 contract with
+//! `only_from_l1`. EOAs sending L1 to L2 transactions are not aliased.
+//!
+//! L2 contracts send messages to L1 through ArbSys with `send_message_to_l1`. The messages are
+//! executed by calling the Outbox on L1 once the assertion including them is confirmed.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{address, Address, U160, U256},
+    alloy_sol_types::sol,
+    msg,
+    prelude::*,
+};
+
+use crate::arbitrum::precompiles::send_tx_to_l1;
+
+/// The offset added to the address of L1 contracts calling L2 contracts.
+pub const L1_TO_L2_ALIAS_OFFSET: Address = address!("1111000000000000000000000000000000001111");
+
+// Define errors in the contract
+sol! {
+    /// The caller `actual` is not `expected`, the alias of the L1 contract allowed to call.
+    error InvalidCrossChainSender(address actual, address expected);
+}
+
+#[derive(SolidityError)]
+pub enum CrossChainError {
+    InvalidCrossChainSender(InvalidCrossChainSender),
+}
+
+/// Returns the address that the L1 contract `l1_address` calls L2 contracts with.
+pub fn apply_l1_to_l2_alias(l1_address: Address) -> Address {
+    let aliased = to_u160(l1_address).wrapping_add(to_u160(L1_TO_L2_ALIAS_OFFSET));
+    Address::from(aliased.to_be_bytes())
+}
+
+/// Returns the L1 contract calling L2 contracts with the address `l2_address`.
+pub fn undo_alias(l2_address: Address) -> Address {
+    let unaliased = to_u160(l2_address).wrapping_sub(to_u160(L1_TO_L2_ALIAS_OFFSET));
+    Address::from(unaliased.to_be_bytes())
+}
+
+/// Throws if the caller is not the L1 contract `l1_sender`, through a retryable ticket.
+pub fn only_from_l1(l1_sender: Address) -> Result<(), CrossChainError> {
+    let expected = apply_l1_to_l2_alias(l1_sender);
+    if msg::sender() != expected {
+        return Err(CrossChainError::InvalidCrossChainSender(
+            InvalidCrossChainSender {
+                actual: msg::sender(),
+                expected,
+            },
+        ));
+    }
+    Ok(())
+}
+
+/// Sends a message calling `destination` with `data` on L1, and returns its unique id.
+///
+/// Emits an {L2ToL1Tx} event from ArbSys.
+pub fn send_message_to_l1(destination: Address, data: &[u8]) -> Result<U256, Vec<u8>> {
+    send_tx_to_l1(destination, data, U256::ZERO)
+}
+
+fn to_u160(address: Address) -> U160 {
+    U160::from_be_bytes(address.0 .0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alias() {
+        assert_eq!(apply_l1_to_l2_alias(Address::ZERO), L1_TO_L2_ALIAS_OFFSET);
+
+        let l1 = address!("6B175474E89094C44Da98b954EedeAC495271d0F");
+        let l2 = address!("7C285474E89094C44Da98b954EedeAC495272e20");
+        assert_eq!(apply_l1_to_l2_alias(l1), l2);
+        assert_eq!(undo_alias(l2), l1);
+
+        // The offset wraps around the address space
+        let l1 = Address::repeat_byte(0xff);
+        let l2 = address!("1111000000000000000000000000000000001110");
+        assert_eq!(apply_l1_to_l2_alias(l1), l2);
+        assert_eq!(undo_alias(l2), l1);
+    }
+}
@@ -0,0 +1,7 @@
+//! Bindings and helpers specific to Arbitrum chains
+
+#[cfg(feature = "crosschain")]
+pub mod crosschain;
+
+#[cfg(feature = "precompiles")]
+pub mod precompiles;
@@ -0,0 +1,210 @@
+//! Typed wrappers of the ArbSys and ArbGasInfo precompiles of Arbitrum chains.
+//! The interfaces were based off of: https://github.com/OffchainLabs/nitro-contracts/tree/main/src/precompiles
+//!
+//! On Arbitrum, `block::number` is an estimate of the L1 block number, which only advances every
+//! few seconds and is shared by many L2 blocks. Logic that needs a number unique to each L2 block
+//! (ex. vote checkpoints or commit-reveal schemes) should use `arb_block_number` instead, and
+//! `arb_block_hash` for the hash of a recent L2 block.
+//!
+//! The wrappers return the revert data of the precompile if the call fails, or a
+//! {PrecompileUnavailable} error if the precompile returned no valid data, which is the case on
+//! chains other than Arbitrum chains. Errors can be propagated with `?`.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolCall, SolError},
+    call::RawCall,
+};
+
+/// The address of the ArbSys precompile.
+pub const ARB_SYS: Address = Address::with_last_byte(0x64);
+
+/// The address of the ArbGasInfo precompile.
+pub const ARB_GAS_INFO: Address = Address::with_last_byte(0x6c);
+
+// Define events, errors and functions of the precompiles
+sol! {
+    /// Emitted by ArbSys when an L2 to L1 message is sent.
+    event L2ToL1Tx(address caller, address indexed destination, uint256 indexed hash, uint256 indexed position, uint256 arbBlockNum, uint256 ethBlockNum, uint256 timestamp, uint256 callvalue, bytes data);
+
+    /// The requested L2 block number is not one of the 256 most recent blocks.
+    error InvalidBlockNumber(uint256 requested, uint256 current);
+    /// The `precompile` returned no valid data, as the chain is not an Arbitrum chain.
+    error PrecompileUnavailable(address precompile);
+
+    function arbBlockNumber() external view returns (uint256);
+    function arbBlockHash(uint256 arbBlockNum) external view returns (bytes32);
+    function arbChainID() external view returns (uint256);
+    function arbOSVersion() external view returns (uint256);
+    function sendTxToL1(address destination, bytes data) external payable returns (uint256);
+    function withdrawEth(address destination) external payable returns (uint256);
+    function wasMyCallersAddressAliased() external view returns (bool);
+    function myCallersAddressWithoutAliasing() external view returns (address);
+
+    function getPricesInWei() external view returns (uint256, uint256, uint256, uint256, uint256, uint256);
+    function getPricesInArbGas() external view returns (uint256, uint256, uint256);
+    function getL1BaseFeeEstimate() external view returns (uint256);
+    function getMinimumGasPrice() external view returns (uint256);
+    function getCurrentTxL1GasFees() external view returns (uint256);
+    function getGasBacklog() external view returns (uint64);
+}
+
+/// The prices of the resources of the current transaction, in wei
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PricesInWei {
+    /// The price of each L2 transaction
+    pub per_l2_tx: U256,
+    /// The price of each byte of L1 calldata
+    pub per_l1_calldata_byte: U256,
+    /// The price of each storage allocation
+    pub per_storage_allocation: U256,
+    /// The base price of each unit of ArbGas
+    pub per_arb_gas_base: U256,
+    /// The congestion price of each unit of ArbGas
+    pub per_arb_gas_congestion: U256,
+    /// The total price of each unit of ArbGas
+    pub per_arb_gas_total: U256,
+}
+
+/// The prices of the resources of the current transaction, in ArbGas
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PricesInArbGas {
+    /// The price of each L2 transaction
+    pub per_l2_tx: U256,
+    /// The price of each byte of L1 calldata
+    pub per_l1_calldata_byte: U256,
+    /// The price of each storage allocation
+    pub per_storage_allocation: U256,
+}
+
+/// Returns the number of the current L2 block.
+pub fn arb_block_number() -> Result<u64, Vec<u8>> {
+    let returns = static_call(ARB_SYS, &arbBlockNumberCall {})?;
+    Ok(returns._0.saturating_to())
+}
+
+/// Returns the hash of the L2 block `number`.
+///
+/// Requirements:
+/// - `number` must be one of the 256 most recent L2 blocks, excluding the current one.
+pub fn arb_block_hash(number: u64) -> Result<B256, Vec<u8>> {
+    let call = arbBlockHashCall {
+        arbBlockNum: U256::from(number),
+    };
+    Ok(B256::new(static_call(ARB_SYS, &call)?._0))
+}
+
+/// Returns the chain id of the Arbitrum chain.
+pub fn arb_chain_id() -> Result<u64, Vec<u8>> {
+    let returns = static_call(ARB_SYS, &arbChainIDCall {})?;
+    Ok(returns._0.saturating_to())
+}
+
+/// Returns the version of ArbOS, which is 55 plus the ArbOS version number.
+pub fn arb_os_version() -> Result<u64, Vec<u8>> {
+    let returns = static_call(ARB_SYS, &arbOSVersionCall {})?;
+    Ok(returns._0.saturating_to())
+}
+
+/// Sends a message calling `destination` with `data` and `value` wei on L1, which can be executed
+/// through the Outbox once the assertion including it is confirmed. Returns the unique id of the
+/// message.
+///
+/// Requirements:
+/// - The contract must have a balance of at least `value`.
+///
+/// Emits an {L2ToL1Tx} event from ArbSys.
+pub fn send_tx_to_l1(destination: Address, data: &[u8], value: U256) -> Result<U256, Vec<u8>> {
+    let call = sendTxToL1Call {
+        destination,
+        data: data.to_vec(),
+    };
+    Ok(call_with_value(ARB_SYS, &call, value)?._0)
+}
+
+/// Withdraws `value` wei to `destination` on L1, which can be claimed through the Outbox once the
+/// assertion including it is confirmed. Returns the unique id of the withdrawal.
+///
+/// Requirements:
+/// - The contract must have a balance of at least `value`.
+///
+/// Emits an {L2ToL1Tx} event from ArbSys.
+pub fn withdraw_eth(destination: Address, value: U256) -> Result<U256, Vec<u8>> {
+    let call = withdrawEthCall { destination };
+    Ok(call_with_value(ARB_SYS, &call, value)?._0)
+}
+
+/// Returns whether the caller of the current contract was aliased, which is the case for calls
+/// made by L1 contracts through retryable tickets.
+pub fn was_my_callers_address_aliased() -> Result<bool, Vec<u8>> {
+    Ok(static_call(ARB_SYS, &wasMyCallersAddressAliasedCall {})?._0)
+}
+
+/// Returns the caller of the current contract, with the L1 to L2 aliasing undone if it was aliased.
+pub fn my_callers_address_without_aliasing() -> Result<Address, Vec<u8>> {
+    Ok(static_call(ARB_SYS, &myCallersAddressWithoutAliasingCall {})?._0)
+}
+
+/// Returns the prices of the resources of the current transaction, in wei.
+pub fn prices_in_wei() -> Result<PricesInWei, Vec<u8>> {
+    let returns = static_call(ARB_GAS_INFO, &getPricesInWeiCall {})?;
+    Ok(PricesInWei {
+        per_l2_tx: returns._0,
+        per_l1_calldata_byte: returns._1,
+        per_storage_allocation: returns._2,
+        per_arb_gas_base: returns._3,
+        per_arb_gas_congestion: returns._4,
+        per_arb_gas_total: returns._5,
+    })
+}
+
+/// Returns the prices of the resources of the current transaction, in ArbGas.
+pub fn prices_in_arb_gas() -> Result<PricesInArbGas, Vec<u8>> {
+    let returns = static_call(ARB_GAS_INFO, &getPricesInArbGasCall {})?;
+    Ok(PricesInArbGas {
+        per_l2_tx: returns._0,
+        per_l1_calldata_byte: returns._1,
+        per_storage_allocation: returns._2,
+    })
+}
+
+/// Returns the estimate of the L1 base fee used to price L1 calldata, in wei.
+pub fn l1_base_fee_estimate() -> Result<U256, Vec<u8>> {
+    Ok(static_call(ARB_GAS_INFO, &getL1BaseFeeEstimateCall {})?._0)
+}
+
+/// Returns the minimum gas price of L2 transactions, in wei.
+pub fn minimum_gas_price() -> Result<U256, Vec<u8>> {
+    Ok(static_call(ARB_GAS_INFO, &getMinimumGasPriceCall {})?._0)
+}
+
+/// Returns the fees paid by the current transaction for its L1 calldata, in wei.
+pub fn current_tx_l1_gas_fees() -> Result<U256, Vec<u8>> {
+    Ok(static_call(ARB_GAS_INFO, &getCurrentTxL1GasFeesCall {})?._0)
+}
+
+/// Returns the gas backlog of the L2 gas pricing, which raises the gas price when it grows.
+pub fn gas_backlog() -> Result<u64, Vec<u8>> {
+    Ok(static_call(ARB_GAS_INFO, &getGasBacklogCall {})?._0)
+}
+
+/// Static calls the `precompile` with `call`, and decodes its returned data.
+fn static_call<C: SolCall>(precompile: Address, call: &C) -> Result<C::Return, Vec<u8>> {
+    let data = RawCall::new_static().call(precompile, &call.encode())?;
+    decode_returns::<C>(precompile, &data)
+}
+
+/// Calls the `precompile` with `call` and `value` wei, and decodes its returned data.
+fn call_with_value<C: SolCall>(
+    precompile: Address,
+    call: &C,
+    value: U256,
+) -> Result<C::Return, Vec<u8>> {
+    let data = RawCall::new_with_value(value).call(precompile, &call.encode())?;
+    decode_returns::<C>(precompile, &data)
+}
+
+fn decode_returns<C: SolCall>(precompile: Address, data: &[u8]) -> Result<C::Return, Vec<u8>> {
+    C::decode_returns(data, true).map_err(|_| PrecompileUnavailable { precompile }.encode())
+}
@@ -0,0 +1,526 @@
+//! Constant product AMM pair of two ERC20 tokens, whose liquidity is an ERC20 LP token.
+//! The logic was based off of: https://github.com/Uniswap/v2-core/blob/master/contracts/UniswapV2Pair.sol
+//!
+//! Like Uniswap V2, the pair is meant to be called by a router in the same transaction as the
+//! tokens are sent to it: `mint` mints LP tokens for the tokens deposited since the last update of
+//! the reserves, `burn` burns the LP tokens sent to the pair and returns the underlying tokens, and
+//! `swap` sends the requested outputs once it has received enough input to keep
+//! `reserve0 * reserve1` from decreasing after a 0.3% fee. Tokens sent to the pair without calling
+//! it can be taken by anyone, so they must always be sent and used atomically.
+//!
+//! `swap` optimistically sends the outputs before checking the inputs, and calls `uniswapV2Call`
+//! on the recipient if `data` is not empty, which allows flash swaps. The first `MINIMUM_LIQUIDITY`
+//! LP tokens are locked at the zero address, so that the price of a share cannot be inflated to
+//! round later deposits down to nothing.
+//!
+//! Before the reserves change for the first time in a block, the price of each token in the other
+//! is added to `price0_cumulative_last` and `price1_cumulative_last` for every second since the
+//! last update, as UQ112x112 fixed point numbers. Oracles can observe them at two points in time
+//! to compute a time-weighted average price (see `twap_oracle`). They are meant to overflow.
+//!
+//! `AMMPair` wraps `ERC20`. Contracts must inherit both `AMMPair<T>` and `ERC20<T>`, and implement
+//! `Borrow<ERC20<T>>` and `BorrowMut<ERC20<T>>` by returning the `erc20` field of the `AMMPair`
+//! storage. The pair is set up with the tokens of the pair by `_initialize`, see
+//! [initialization](crate#initialization).
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, Uint, U256},
+    alloy_sol_types::{sol, SolCall},
+    block, contract, evm, msg,
+    prelude::*,
+};
+
+use crate::security::reentrancy_guard::ReentrancyGuard;
+use crate::tokens::erc20::{ERC20Params, ERC20};
+use crate::utils::address::{function_call, function_static_call};
+use crate::utils::fixed_point_math::{mul_div, sqrt};
+use crate::utils::safe_transfer::safe_transfer;
+use crate::utils::twap_oracle::accumulate;
+
+/// The amount of LP tokens locked forever by the first deposit.
+pub const MINIMUM_LIQUIDITY: U256 = U256::from_limbs([1000, 0, 0, 0]);
+
+/// The type of the reserves, which cannot exceed `2^112 - 1`.
+pub type U112 = Uint<112, 2>;
+
+sol_storage! {
+    /// AMMPair holds the reserves of the two tokens of the pair
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.AMMPair"))]
+    pub struct AMMPair<T: ERC20Params> {
+        /// The LP token
+        ERC20<T> erc20;
+        /// The lock of the functions that move tokens
+        ReentrancyGuard guard;
+        /// The token with the lowest address
+        address token0;
+        /// The token with the highest address
+        address token1;
+        /// The balance of `token0` at the last update
+        uint112 reserve0;
+        /// The balance of `token1` at the last update
+        uint112 reserve1;
+        /// The timestamp of the last update, modulo 2^32
+        uint32 block_timestamp_last;
+        /// The sum of the price of `token0` in `token1` for every second, as a UQ112x112
+        uint256 price0_cumulative_last;
+        /// The sum of the price of `token1` in `token0` for every second, as a UQ112x112
+        uint256 price1_cumulative_last;
+    }
+}
+
+// Define events and errors in the contract, and the functions of the tokens and callees
+sol! {
+    /// Emitted when `sender` deposits `amount0` and `amount1` tokens for LP tokens.
+    event Mint(address indexed sender, uint256 amount0, uint256 amount1);
+    /// Emitted when `sender` burns LP tokens for `amount0` and `amount1` tokens sent to `to`.
+    event Burn(address indexed sender, uint256 amount0, uint256 amount1, address indexed to);
+    /// Emitted when `sender` swaps input amounts for output amounts sent to `to`.
+    event Swap(address indexed sender, uint256 amount0In, uint256 amount1In, uint256 amount0Out, uint256 amount1Out, address indexed to);
+    /// Emitted when the reserves are updated to `reserve0` and `reserve1`.
+    event Sync(uint112 reserve0, uint112 reserve1);
+
+    /// The tokens of the pair have already been initialized.
+    error PairAlreadyInitialized();
+    /// The tokens `token0` and `token1` cannot be paired (ex. identical or zero).
+    error InvalidPairTokens(address token0, address token1);
+    /// The deposit is too small to mint LP tokens.
+    error InsufficientLiquidityMinted();
+    /// The LP tokens burned are too few to return tokens.
+    error InsufficientLiquidityBurned();
+    /// The swap has no output.
+    error InsufficientOutputAmount();
+    /// The swap has no input.
+    error InsufficientInputAmount();
+    /// The output of the swap exceeds the reserves.
+    error InsufficientLiquidity();
+    /// The recipient `to` of the swap cannot be a token of the pair.
+    error InvalidTo(address to);
+    /// The swap decreases the product of the reserves.
+    error KInvariant();
+    /// The balances of the pair exceed the maximum reserves.
+    error ReserveOverflow();
+    /// The `token` returned an invalid balance.
+    error InvalidTokenBalance(address token);
+
+    function balanceOf(address account) external view returns (uint256);
+    function uniswapV2Call(address sender, uint256 amount0, uint256 amount1, bytes data) external;
+}
+
+#[derive(SolidityError)]
+pub enum AMMPairError {
+    PairAlreadyInitialized(PairAlreadyInitialized),
+    InvalidPairTokens(InvalidPairTokens),
+    InsufficientLiquidityMinted(InsufficientLiquidityMinted),
+    InsufficientLiquidityBurned(InsufficientLiquidityBurned),
+    InsufficientOutputAmount(InsufficientOutputAmount),
+    InsufficientInputAmount(InsufficientInputAmount),
+    InsufficientLiquidity(InsufficientLiquidity),
+    InvalidTo(InvalidTo),
+    KInvariant(KInvariant),
+    ReserveOverflow(ReserveOverflow),
+    InvalidTokenBalance(InvalidTokenBalance),
+}
+
+// Internal functions
+impl<T: ERC20Params> AMMPair<T> {
+    /// Sets the tokens of the pair, sorted by address.
+    ///
+    /// Requirements:
+    /// - The tokens must not have been initialized.
+    /// - `token_a` and `token_b` must be different, and not be the zero address.
+    pub fn _initialize(&mut self, token_a: Address, token_b: Address) -> Result<(), AMMPairError> {
+        if !self.token0.get().is_zero() {
+            return Err(AMMPairError::PairAlreadyInitialized(
+                PairAlreadyInitialized {},
+            ));
+        }
+        let (token0, token1) = if token_a < token_b {
+            (token_a, token_b)
+        } else {
+            (token_b, token_a)
+        };
+        if token0 == token1 || token0.is_zero() {
+            return Err(AMMPairError::InvalidPairTokens(InvalidPairTokens {
+                token0,
+                token1,
+            }));
+        }
+        self.token0.set(token0);
+        self.token1.set(token1);
+        Ok(())
+    }
+
+    /// Returns the reserves of the pair.
+    fn _reserves(&self) -> (U256, U256) {
+        (
+            U256::from(self.reserve0.get()),
+            U256::from(self.reserve1.get()),
+        )
+    }
+
+    /// Returns the balances of the tokens held by the pair.
+    fn _balances(&self) -> Result<(U256, U256), Vec<u8>> {
+        Ok((
+            balance_of(self.token0.get())?,
+            balance_of(self.token1.get())?,
+        ))
+    }
+
+    /// Accumulates the prices of the previous reserves for the time elapsed since their last
+    /// update, then sets the reserves to the `balance0` and `balance1` of the pair.
+    ///
+    /// Requirements:
+    /// - The balances must not exceed `2^112 - 1`.
+    ///
+    /// Emits a {Sync} event.
+    fn _update(&mut self, balance0: U256, balance1: U256) -> Result<(), AMMPairError> {
+        let max_reserve = U256::from(U112::MAX);
+        if balance0 > max_reserve || balance1 > max_reserve {
+            return Err(AMMPairError::ReserveOverflow(ReserveOverflow {}));
+        }
+        let block_timestamp = block::timestamp() as u32;
+        let time_elapsed = block_timestamp.wrapping_sub(self.block_timestamp_last.get().to());
+        let (last0, last1) = self._reserves();
+        if time_elapsed > 0 && !last0.is_zero() && !last1.is_zero() {
+            // Prices are below 2^224, as the reserves are below 2^112
+            let price0 = (last1 << 112) / last0;
+            let price1 = (last0 << 112) / last1;
+            let elapsed = time_elapsed as u64;
+            let cumulative0 = accumulate(self.price0_cumulative_last.get(), price0, elapsed);
+            let cumulative1 = accumulate(self.price1_cumulative_last.get(), price1, elapsed);
+            self.price0_cumulative_last.set(cumulative0);
+            self.price1_cumulative_last.set(cumulative1);
+        }
+
+        let (reserve0, reserve1) = (balance0.to::<U112>(), balance1.to::<U112>());
+        self.reserve0.set(reserve0);
+        self.reserve1.set(reserve1);
+        self.block_timestamp_last.set(Uint::from(block_timestamp));
+        evm::log(Sync {
+            reserve0: reserve0.to(),
+            reserve1: reserve1.to(),
+        });
+        Ok(())
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC20Params> AMMPair<T> {
+    /// Returns the token with the lowest address.
+    pub fn token0(&self) -> Address {
+        self.token0.get()
+    }
+
+    /// Returns the token with the highest address.
+    pub fn token1(&self) -> Address {
+        self.token1.get()
+    }
+
+    /// Returns the amount of LP tokens locked forever by the first deposit.
+    #[selector(name = "MINIMUM_LIQUIDITY")]
+    pub fn minimum_liquidity() -> U256 {
+        MINIMUM_LIQUIDITY
+    }
+
+    /// Returns the reserves of the pair and the timestamp of their last update, modulo 2^32.
+    pub fn get_reserves(&self) -> (U256, U256, u32) {
+        let (reserve0, reserve1) = self._reserves();
+        (reserve0, reserve1, self.block_timestamp_last.get().to())
+    }
+
+    /// Returns the sum of the price of `token0` in `token1` for every second until the last update
+    /// of the reserves, as a UQ112x112.
+    pub fn price0_cumulative_last(&self) -> U256 {
+        self.price0_cumulative_last.get()
+    }
+
+    /// Returns the sum of the price of `token1` in `token0` for every second until the last update
+    /// of the reserves, as a UQ112x112.
+    pub fn price1_cumulative_last(&self) -> U256 {
+        self.price1_cumulative_last.get()
+    }
+
+    /// Mints LP tokens to `to` for the tokens deposited since the last update, and returns the
+    /// amount minted. The first deposit mints the geometric mean of the amounts, minus
+    /// `MINIMUM_LIQUIDITY`. Later deposits mint in proportion to the smallest share of the
+    /// reserves deposited, so unbalanced deposits donate the excess to the pair.
+    ///
+    /// Requirements:
+    /// - The deposit must mint a non-zero amount of LP tokens.
+    ///
+    /// Emits a {Mint} event, {Transfer} events and a {Sync} event.
+    pub fn mint(&mut self, to: Address) -> Result<U256, Vec<u8>> {
+        self.guard.non_reentrant_enter()?;
+        let (reserve0, reserve1) = self._reserves();
+        let (balance0, balance1) = self._balances()?;
+        let amount0 = balance0.saturating_sub(reserve0);
+        let amount1 = balance1.saturating_sub(reserve1);
+        let overflow = || AMMPairError::ReserveOverflow(ReserveOverflow {});
+
+        let total_supply = self.erc20.total_supply();
+        let liquidity = if total_supply.is_zero() {
+            let product = amount0.checked_mul(amount1).ok_or_else(overflow)?;
+            let liquidity = sqrt(product).saturating_sub(MINIMUM_LIQUIDITY);
+            if !liquidity.is_zero() {
+                ERC20::_mint(&mut self.erc20, Address::ZERO, MINIMUM_LIQUIDITY)?;
+            }
+            liquidity
+        } else {
+            let liquidity0 = mul_div(amount0, total_supply, reserve0).ok_or_else(overflow)?;
+            let liquidity1 = mul_div(amount1, total_supply, reserve1).ok_or_else(overflow)?;
+            liquidity0.min(liquidity1)
+        };
+        if liquidity.is_zero() {
+            return Err(
+                AMMPairError::InsufficientLiquidityMinted(InsufficientLiquidityMinted {}).into(),
+            );
+        }
+        ERC20::_mint(&mut self.erc20, to, liquidity)?;
+
+        self._update(balance0, balance1)?;
+        evm::log(Mint {
+            sender: msg::sender(),
+            amount0,
+            amount1,
+        });
+        self.guard.non_reentrant_exit();
+        Ok(liquidity)
+    }
+
+    /// Burns the LP tokens held by the pair, sends their share of the balances to `to`, and
+    /// returns the amounts sent.
+    ///
+    /// Requirements:
+    /// - The LP tokens must be worth a non-zero amount of both tokens.
+    ///
+    /// Emits a {Burn} event, {Transfer} events and a {Sync} event.
+    pub fn burn(&mut self, to: Address) -> Result<(U256, U256), Vec<u8>> {
+        self.guard.non_reentrant_enter()?;
+        let (token0, token1) = (self.token0.get(), self.token1.get());
+        let (balance0, balance1) = self._balances()?;
+        let liquidity = self.erc20.balance_of(contract::address());
+
+        // The total supply is at least `MINIMUM_LIQUIDITY` once the pair has been minted
+        let total_supply = self.erc20.total_supply();
+        let amount0 = mul_div(liquidity, balance0, total_supply).unwrap_or_default();
+        let amount1 = mul_div(liquidity, balance1, total_supply).unwrap_or_default();
+        if amount0.is_zero() || amount1.is_zero() {
+            return Err(
+                AMMPairError::InsufficientLiquidityBurned(InsufficientLiquidityBurned {}).into(),
+            );
+        }
+        ERC20::_burn(&mut self.erc20, contract::address(), liquidity)?;
+        safe_transfer(token0, to, amount0)?;
+        safe_transfer(token1, to, amount1)?;
+
+        let (balance0, balance1) = self._balances()?;
+        self._update(balance0, balance1)?;
+        evm::log(Burn {
+            sender: msg::sender(),
+            amount0,
+            amount1,
+            to,
+        });
+        self.guard.non_reentrant_exit();
+        Ok((amount0, amount1))
+    }
+
+    /// Sends `amount0_out` and `amount1_out` tokens to `to`, calling its `uniswapV2Call` callback
+    /// with `data` if it is not empty, and then checks that enough tokens were sent to the pair in
+    /// exchange. The inputs are charged a 0.3% fee, which stays in the reserves.
+    ///
+    /// Requirements:
+    /// - One of the outputs must be non-zero, and both must be below the reserves.
+    /// - `to` cannot be a token of the pair.
+    /// - The product of the balances net of the fee must not decrease.
+    ///
+    /// Emits a {Swap} event and a {Sync} event.
+    pub fn swap(
+        &mut self,
+        amount0_out: U256,
+        amount1_out: U256,
+        to: Address,
+        data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        self.guard.non_reentrant_enter()?;
+        if amount0_out.is_zero() && amount1_out.is_zero() {
+            return Err(AMMPairError::InsufficientOutputAmount(InsufficientOutputAmount {}).into());
+        }
+        let (reserve0, reserve1) = self._reserves();
+        if amount0_out >= reserve0 || amount1_out >= reserve1 {
+            return Err(AMMPairError::InsufficientLiquidity(InsufficientLiquidity {}).into());
+        }
+
+        let (token0, token1) = (self.token0.get(), self.token1.get());
+        if to == token0 || to == token1 {
+            return Err(AMMPairError::InvalidTo(InvalidTo { to }).into());
+        }
+        if !amount0_out.is_zero() {
+            safe_transfer(token0, to, amount0_out)?;
+        }
+        if !amount1_out.is_zero() {
+            safe_transfer(token1, to, amount1_out)?;
+        }
+        if !data.is_empty() {
+            let call = uniswapV2CallCall {
+                sender: msg::sender(),
+                amount0: amount0_out,
+                amount1: amount1_out,
+                data: data.0,
+            };
+            function_call(to, &call.encode())?;
+        }
+
+        let (balance0, balance1) = self._balances()?;
+        let amount0_in = balance0.saturating_sub(reserve0 - amount0_out);
+        let amount1_in = balance1.saturating_sub(reserve1 - amount1_out);
+        if amount0_in.is_zero() && amount1_in.is_zero() {
+            return Err(AMMPairError::InsufficientInputAmount(InsufficientInputAmount {}).into());
+        }
+        let overflow = || AMMPairError::ReserveOverflow(ReserveOverflow {});
+        let adjusted0 = fee_adjusted(balance0, amount0_in).ok_or_else(overflow)?;
+        let adjusted1 = fee_adjusted(balance1, amount1_in).ok_or_else(overflow)?;
+        let product = adjusted0.checked_mul(adjusted1).ok_or_else(overflow)?;
+        if product < reserve0 * reserve1 * U256::from(1_000_000) {
+            return Err(AMMPairError::KInvariant(KInvariant {}).into());
+        }
+
+        self._update(balance0, balance1)?;
+        evm::log(Swap {
+            sender: msg::sender(),
+            amount0In: amount0_in,
+            amount1In: amount1_in,
+            amount0Out: amount0_out,
+            amount1Out: amount1_out,
+            to,
+        });
+        self.guard.non_reentrant_exit();
+        Ok(())
+    }
+
+    /// Sends the balances of the pair in excess of the reserves to `to`.
+    pub fn skim(&mut self, to: Address) -> Result<(), Vec<u8>> {
+        self.guard.non_reentrant_enter()?;
+        let (reserve0, reserve1) = self._reserves();
+        let (balance0, balance1) = self._balances()?;
+        safe_transfer(self.token0.get(), to, balance0.saturating_sub(reserve0))?;
+        safe_transfer(self.token1.get(), to, balance1.saturating_sub(reserve1))?;
+        self.guard.non_reentrant_exit();
+        Ok(())
+    }
+
+    /// Sets the reserves to the balances of the pair (ex. after a donation or a rebase).
+    ///
+    /// Emits a {Sync} event.
+    pub fn sync(&mut self) -> Result<(), Vec<u8>> {
+        self.guard.non_reentrant_enter()?;
+        let (balance0, balance1) = self._balances()?;
+        self._update(balance0, balance1)?;
+        self.guard.non_reentrant_exit();
+        Ok(())
+    }
+}
+
+/// Returns the maximum output of a swap of `amount_in` tokens against `reserve_in` and
+/// `reserve_out`, after the 0.3% fee. Returns `None` if a reserve is empty or the math overflows.
+pub fn get_amount_out(amount_in: U256, reserve_in: U256, reserve_out: U256) -> Option<U256> {
+    if reserve_in.is_zero() || reserve_out.is_zero() {
+        return None;
+    }
+    let amount_in_with_fee = amount_in.checked_mul(U256::from(997))?;
+    let numerator = amount_in_with_fee.checked_mul(reserve_out)?;
+    let denominator = reserve_in
+        .checked_mul(U256::from(1000))?
+        .checked_add(amount_in_with_fee)?;
+    Some(numerator / denominator)
+}
+
+/// Returns the minimum input of a swap for `amount_out` tokens against `reserve_in` and
+/// `reserve_out`, after the 0.3% fee. Returns `None` if `amount_out` is not below `reserve_out`
+/// or the math overflows.
+pub fn get_amount_in(amount_out: U256, reserve_in: U256, reserve_out: U256) -> Option<U256> {
+    if reserve_in.is_zero() || amount_out >= reserve_out {
+        return None;
+    }
+    let numerator = reserve_in
+        .checked_mul(amount_out)?
+        .checked_mul(U256::from(1000))?;
+    let denominator = (reserve_out - amount_out) * U256::from(997);
+    (numerator / denominator).checked_add(U256::from(1))
+}
+
+/// Returns `balance * 1000 - amount_in * 3`, the balance net of the 0.3% fee on the input, scaled
+/// by 1000.
+fn fee_adjusted(balance: U256, amount_in: U256) -> Option<U256> {
+    balance
+        .checked_mul(U256::from(1000))?
+        .checked_sub(amount_in * U256::from(3))
+}
+
+/// Returns the balance of `token` held by the pair.
+fn balance_of(token: Address) -> Result<U256, Vec<u8>> {
+    let call = balanceOfCall {
+        account: contract::address(),
+    };
+    let data = function_static_call(token, &call.encode())?;
+    balanceOfCall::decode_returns(&data, true)
+        .map(|returns| returns._0)
+        .map_err(|_| AMMPairError::InvalidTokenBalance(InvalidTokenBalance { token }).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns whether swapping `amount_in` of token0 for `amount_out` of token1 passes the K check
+    /// of `swap`.
+    fn passes_k(reserve0: U256, reserve1: U256, amount_in: U256, amount_out: U256) -> bool {
+        let balance0 = reserve0 + amount_in;
+        let balance1 = reserve1 - amount_out;
+        let adjusted0 = fee_adjusted(balance0, amount_in).unwrap();
+        let adjusted1 = fee_adjusted(balance1, U256::ZERO).unwrap();
+        adjusted0 * adjusted1 >= reserve0 * reserve1 * U256::from(1_000_000)
+    }
+
+    #[test]
+    fn test_get_amount_out() {
+        let (reserve0, reserve1) = (U256::from(5_000_000u64), U256::from(10_000_000u64));
+        for amount_in in [1u64, 1_000, 123_456, 5_000_000, 50_000_000] {
+            let amount_in = U256::from(amount_in);
+            let amount_out = get_amount_out(amount_in, reserve0, reserve1).unwrap();
+            assert!(amount_out < reserve1);
+            assert!(passes_k(reserve0, reserve1, amount_in, amount_out));
+            // The output is the maximum passing the K check
+            assert!(!passes_k(
+                reserve0,
+                reserve1,
+                amount_in,
+                amount_out + U256::from(1)
+            ));
+        }
+
+        // 1 token in for 1000:1000 reserves is rounded down to nothing after the fee
+        let reserve = U256::from(1000);
+        assert_eq!(
+            get_amount_out(U256::from(1), reserve, reserve),
+            Some(U256::ZERO)
+        );
+        assert_eq!(get_amount_out(U256::from(1), U256::ZERO, reserve), None);
+    }
+
+    #[test]
+    fn test_get_amount_in() {
+        let (reserve0, reserve1) = (U256::from(5_000_000u64), U256::from(10_000_000u64));
+        for amount_out in [1u64, 1_000, 123_456, 5_000_000, 9_999_999] {
+            let amount_out = U256::from(amount_out);
+            let amount_in = get_amount_in(amount_out, reserve0, reserve1).unwrap();
+            assert!(passes_k(reserve0, reserve1, amount_in, amount_out));
+            assert!(get_amount_out(amount_in, reserve0, reserve1).unwrap() >= amount_out);
+        }
+        assert_eq!(get_amount_in(reserve1, reserve0, reserve1), None);
+    }
+}
@@ -0,0 +1,396 @@
+//! Router batching deposits, withdrawals and migrations across ERC-4626 vaults with slippage checks.
+//! The logic was based off of: https://github.com/fei-protocol/ERC4626/blob/main/src/ERC4626Router.sol
+//!
+//! ERC-4626 functions do not take a minimum output, so a deposit can be sandwiched or made at a
+//! manipulated share price. The router wraps each vault operation with a bound on the amount
+//! received or spent, and reverts with {MinSharesError}, {MaxSharesError}, {MinAmountError} or
+//! {MaxAmountError} if it is not met.
+//!
+//! `deposit_to_vault`, `deposit_max`, `redeem_max` and the migrations between vaults act on the
+//! tokens of the caller. The base operations (`deposit`, `mint`, `withdraw` and `redeem`) deposit
+//! the assets held by the router, and are meant to be batched after `pull_token` or `self_permit`
+//! through `multicall`. For instance, a permit and deposit is
+//! `multicall([selfPermit(asset, ...), depositToVault(vault, to, amount, minSharesOut)])`. The
+//! router must not hold tokens between transactions, as anyone can deposit or sweep them.
+//!
+//! Contracts must inherit `ERC4626Router` and `Multicall<Self>`. Withdrawals and redemptions
+//! burn the shares of the caller, who must first approve the router to spend them.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolCall},
+    contract, msg,
+    prelude::*,
+};
+
+use crate::utils::address::function_call;
+use crate::utils::safe_transfer::{safe_approve, safe_transfer, safe_transfer_from};
+
+sol_storage! {
+    /// ERC4626Router routes deposits and withdrawals of ERC-4626 vaults, and holds no state
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC4626Router"))]
+    pub struct ERC4626Router {}
+}
+
+// Define errors in the contract, and the functions of the vaults and tokens
+sol! {
+    /// The amount of assets received is below the minimum.
+    error MinAmountError();
+    /// The amount of shares received is below the minimum.
+    error MinSharesError();
+    /// The amount of assets spent is above the maximum.
+    error MaxAmountError();
+    /// The amount of shares burned is above the maximum.
+    error MaxSharesError();
+    /// The vault or token `target` returned invalid data.
+    error InvalidResponse(address target);
+
+    function asset() external view returns (address);
+    function balanceOf(address account) external view returns (uint256);
+    function maxDeposit(address receiver) external view returns (uint256);
+    function maxRedeem(address owner) external view returns (uint256);
+    function deposit(uint256 assets, address receiver) external returns (uint256);
+    function mint(uint256 shares, address receiver) external returns (uint256);
+    function withdraw(uint256 assets, address receiver, address owner) external returns (uint256);
+    function redeem(uint256 shares, address receiver, address owner) external returns (uint256);
+    function permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external;
+}
+
+#[derive(SolidityError)]
+pub enum ERC4626RouterError {
+    MinAmountError(MinAmountError),
+    MinSharesError(MinSharesError),
+    MaxAmountError(MaxAmountError),
+    MaxSharesError(MaxSharesError),
+    InvalidResponse(InvalidResponse),
+}
+
+// Internal functions
+impl ERC4626Router {
+    /// Deposits `amount` assets held by the router into `vault` for `to`, and returns the shares
+    /// minted.
+    ///
+    /// Requirements:
+    /// - At least `min_shares_out` shares must be minted.
+    pub fn _deposit(
+        vault: Address,
+        to: Address,
+        amount: U256,
+        min_shares_out: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let asset = decoded_call(vault, &assetCall {})?._0;
+        safe_approve(asset, vault, amount)?;
+        let call = depositCall {
+            assets: amount,
+            receiver: to,
+        };
+        let shares_out = decoded_call(vault, &call)?._0;
+        if shares_out < min_shares_out {
+            return Err(ERC4626RouterError::MinSharesError(MinSharesError {}).into());
+        }
+        Ok(shares_out)
+    }
+
+    /// Withdraws `amount` assets of the caller from `vault` to `to`, and returns the shares burned.
+    ///
+    /// Requirements:
+    /// - At most `max_shares_out` shares must be burned.
+    pub fn _withdraw(
+        vault: Address,
+        to: Address,
+        amount: U256,
+        max_shares_out: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let call = withdrawCall {
+            assets: amount,
+            receiver: to,
+            owner: msg::sender(),
+        };
+        let shares_out = decoded_call(vault, &call)?._0;
+        if shares_out > max_shares_out {
+            return Err(ERC4626RouterError::MaxSharesError(MaxSharesError {}).into());
+        }
+        Ok(shares_out)
+    }
+
+    /// Redeems `shares` of the caller from `vault` to `to`, and returns the assets received.
+    ///
+    /// Requirements:
+    /// - At least `min_amount_out` assets must be received.
+    pub fn _redeem(
+        vault: Address,
+        to: Address,
+        shares: U256,
+        min_amount_out: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let call = redeemCall {
+            shares,
+            receiver: to,
+            owner: msg::sender(),
+        };
+        let amount_out = decoded_call(vault, &call)?._0;
+        if amount_out < min_amount_out {
+            return Err(ERC4626RouterError::MinAmountError(MinAmountError {}).into());
+        }
+        Ok(amount_out)
+    }
+}
+
+// External functions
+#[external]
+impl ERC4626Router {
+    /// Deposits `amount` assets held by the router into `vault` for `to`, and returns the shares
+    /// minted.
+    ///
+    /// Requirements:
+    /// - The router must hold at least `amount` assets.
+    /// - At least `min_shares_out` shares must be minted.
+    pub fn deposit(
+        &mut self,
+        vault: Address,
+        to: Address,
+        amount: U256,
+        min_shares_out: U256,
+    ) -> Result<U256, Vec<u8>> {
+        Self::_deposit(vault, to, amount, min_shares_out)
+    }
+
+    /// Mints `shares` of `vault` to `to` with the assets held by the router, and returns the
+    /// assets spent.
+    ///
+    /// Requirements:
+    /// - The router must hold enough assets to mint `shares`.
+    /// - At most `max_amount_in` assets must be spent.
+    pub fn mint(
+        &mut self,
+        vault: Address,
+        to: Address,
+        shares: U256,
+        max_amount_in: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let asset = decoded_call(vault, &assetCall {})?._0;
+        safe_approve(asset, vault, max_amount_in)?;
+        let amount_in = decoded_call(
+            vault,
+            &mintCall {
+                shares,
+                receiver: to,
+            },
+        )?
+        ._0;
+        if amount_in > max_amount_in {
+            return Err(ERC4626RouterError::MaxAmountError(MaxAmountError {}).into());
+        }
+        // Clear the allowance left by spending less than the maximum
+        if amount_in < max_amount_in {
+            safe_approve(asset, vault, U256::ZERO)?;
+        }
+        Ok(amount_in)
+    }
+
+    /// Withdraws `amount` assets of the caller from `vault` to `to`, and returns the shares burned.
+    ///
+    /// Requirements:
+    /// - The caller must have approved the router for the shares.
+    /// - At most `max_shares_out` shares must be burned.
+    pub fn withdraw(
+        &mut self,
+        vault: Address,
+        to: Address,
+        amount: U256,
+        max_shares_out: U256,
+    ) -> Result<U256, Vec<u8>> {
+        Self::_withdraw(vault, to, amount, max_shares_out)
+    }
+
+    /// Redeems `shares` of the caller from `vault` to `to`, and returns the assets received.
+    ///
+    /// Requirements:
+    /// - The caller must have approved the router for the shares.
+    /// - At least `min_amount_out` assets must be received.
+    pub fn redeem(
+        &mut self,
+        vault: Address,
+        to: Address,
+        shares: U256,
+        min_amount_out: U256,
+    ) -> Result<U256, Vec<u8>> {
+        Self::_redeem(vault, to, shares, min_amount_out)
+    }
+
+    /// Deposits `amount` assets of the caller into `vault` for `to`, and returns the shares minted.
+    ///
+    /// Requirements:
+    /// - The caller must have approved the router for at least `amount` assets.
+    /// - At least `min_shares_out` shares must be minted.
+    pub fn deposit_to_vault(
+        &mut self,
+        vault: Address,
+        to: Address,
+        amount: U256,
+        min_shares_out: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let asset = decoded_call(vault, &assetCall {})?._0;
+        safe_transfer_from(asset, msg::sender(), contract::address(), amount)?;
+        Self::_deposit(vault, to, amount, min_shares_out)
+    }
+
+    /// Deposits all the assets of the caller that `vault` accepts for `to`, and returns the shares
+    /// minted.
+    ///
+    /// Requirements:
+    /// - The caller must have approved the router for its assets.
+    /// - At least `min_shares_out` shares must be minted.
+    pub fn deposit_max(
+        &mut self,
+        vault: Address,
+        to: Address,
+        min_shares_out: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let asset = decoded_call(vault, &assetCall {})?._0;
+        let balance = decoded_call(
+            asset,
+            &balanceOfCall {
+                account: msg::sender(),
+            },
+        )?
+        ._0;
+        let max_deposit = decoded_call(vault, &maxDepositCall { receiver: to })?._0;
+        let amount = balance.min(max_deposit);
+        safe_transfer_from(asset, msg::sender(), contract::address(), amount)?;
+        Self::_deposit(vault, to, amount, min_shares_out)
+    }
+
+    /// Redeems all the shares of the caller that `vault` allows to `to`, and returns the assets
+    /// received.
+    ///
+    /// Requirements:
+    /// - The caller must have approved the router for its shares.
+    /// - At least `min_amount_out` assets must be received.
+    pub fn redeem_max(
+        &mut self,
+        vault: Address,
+        to: Address,
+        min_amount_out: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let owner = msg::sender();
+        let balance = decoded_call(vault, &balanceOfCall { account: owner })?._0;
+        let max_redeem = decoded_call(vault, &maxRedeemCall { owner })?._0;
+        Self::_redeem(vault, to, balance.min(max_redeem), min_amount_out)
+    }
+
+    /// Withdraws `amount` assets of the caller from `from_vault` and deposits them into
+    /// `to_vault` for `to`, and returns the shares of `to_vault` minted.
+    ///
+    /// Requirements:
+    /// - The vaults must have the same asset.
+    /// - The caller must have approved the router for its shares of `from_vault`.
+    /// - At most `max_shares_in` shares of `from_vault` must be burned.
+    /// - At least `min_shares_out` shares of `to_vault` must be minted.
+    pub fn withdraw_to_deposit(
+        &mut self,
+        from_vault: Address,
+        to_vault: Address,
+        to: Address,
+        amount: U256,
+        max_shares_in: U256,
+        min_shares_out: U256,
+    ) -> Result<U256, Vec<u8>> {
+        Self::_withdraw(from_vault, contract::address(), amount, max_shares_in)?;
+        Self::_deposit(to_vault, to, amount, min_shares_out)
+    }
+
+    /// Redeems `shares` of the caller from `from_vault` and deposits the assets into `to_vault`
+    /// for `to`, and returns the shares of `to_vault` minted.
+    ///
+    /// Requirements:
+    /// - The vaults must have the same asset.
+    /// - The caller must have approved the router for its shares of `from_vault`.
+    /// - At least `min_shares_out` shares of `to_vault` must be minted.
+    pub fn redeem_to_deposit(
+        &mut self,
+        from_vault: Address,
+        to_vault: Address,
+        to: Address,
+        shares: U256,
+        min_shares_out: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let amount = Self::_redeem(from_vault, contract::address(), shares, U256::ZERO)?;
+        Self::_deposit(to_vault, to, amount, min_shares_out)
+    }
+
+    /// Transfers `amount` of `token` from the caller to `recipient` (ex. the router itself).
+    ///
+    /// Requirements:
+    /// - The caller must have approved the router for at least `amount` tokens.
+    pub fn pull_token(
+        &mut self,
+        token: Address,
+        amount: U256,
+        recipient: Address,
+    ) -> Result<(), Vec<u8>> {
+        safe_transfer_from(token, msg::sender(), recipient, amount)
+    }
+
+    /// Transfers all the `token` held by the router to `recipient`.
+    ///
+    /// Requirements:
+    /// - The router must hold at least `amount_minimum` tokens.
+    pub fn sweep_token(
+        &mut self,
+        token: Address,
+        amount_minimum: U256,
+        recipient: Address,
+    ) -> Result<(), Vec<u8>> {
+        let balance = decoded_call(
+            token,
+            &balanceOfCall {
+                account: contract::address(),
+            },
+        )?
+        ._0;
+        if balance < amount_minimum {
+            return Err(ERC4626RouterError::MinAmountError(MinAmountError {}).into());
+        }
+        if !balance.is_zero() {
+            safe_transfer(token, recipient, balance)?;
+        }
+        Ok(())
+    }
+
+    /// Approves the router to spend `value` of the EIP-2612 `token` of the caller, with the
+    /// caller's signature.
+    ///
+    /// Requirements:
+    /// - The signature must be valid for the current nonce of the caller, and not be expired.
+    pub fn self_permit(
+        &mut self,
+        token: Address,
+        value: U256,
+        deadline: U256,
+        v: u8,
+        r: B256,
+        s: B256,
+    ) -> Result<(), Vec<u8>> {
+        let call = permitCall {
+            owner: msg::sender(),
+            spender: contract::address(),
+            value,
+            deadline,
+            v,
+            r: r.0,
+            s: s.0,
+        };
+        function_call(token, &call.encode())?;
+        Ok(())
+    }
+}
+
+/// Calls `target` with `call`, and decodes its returned data.
+fn decoded_call<C: SolCall>(target: Address, call: &C) -> Result<C::Return, Vec<u8>> {
+    let data = function_call(target, &call.encode())?;
+    C::decode_returns(&data, true)
+        .map_err(|_| ERC4626RouterError::InvalidResponse(InvalidResponse { target }).into())
+}
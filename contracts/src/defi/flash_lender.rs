@@ -0,0 +1,313 @@
+//! ERC-3156 flash loans of the ERC20 tokens held by a contract, and a base for flash borrowers.
+//! The logic was based off of: https://github.com/alcueca/ERC3156/blob/main/contracts/FlashLender.sol
+//!
+//! Unlike `ERC20FlashMint`, which mints the token it implements, `FlashLender` lends any ERC20
+//! token held by the contract (ex. the assets of a vault or the treasury of a protocol), up to its
+//! balance. The tokens are sent to the receiver, its `onFlashLoan` callback must return
+//! `ON_FLASH_LOAN_RETURN`, and `amount + fee` is then pulled back through its allowance. The fee
+//! is set by `FlashLenderParams`, and is kept by the lender unless it has a fee receiver. Loans
+//! cannot be nested: the lender rejects a new loan while one is in progress.
+//!
+//! `FlashBorrower` is the other side of the loan. The inheriting contract starts a loan with
+//! `_flash_borrow`, which approves the repayment, and exposes its own `on_flash_loan` function
+//! (exported as `onFlashLoan`) that calls `_verify_flash_loan` before using the tokens, so that
+//! only trusted lenders can call it and only for loans it initiated. As the callback reenters the
+//! borrower, it must enable the `reentrant` feature of `stylus-sdk`.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolCall},
+    contract, evm, msg,
+    prelude::*,
+};
+
+use crate::security::reentrancy_guard::ReentrancyGuard;
+use crate::utils::address::{function_call, function_static_call};
+use crate::utils::safe_transfer::{safe_approve, safe_transfer, safe_transfer_from};
+
+/// keccak256("ERC3156FlashBorrower.onFlashLoan"), which must be returned by flash borrowers.
+pub const ON_FLASH_LOAN_RETURN: B256 = crate::const_keccak!("ERC3156FlashBorrower.onFlashLoan");
+
+/// Flash loan parameters, which can be overridden to restrict the tokens lent or charge a fee.
+pub trait FlashLenderParams {
+    /// Returns whether `token` can be lent.
+    /// By default, every token held by the contract can be lent.
+    fn is_supported(_token: Address) -> bool {
+        true
+    }
+
+    /// Returns the fee applied when doing flash loans of `amount` of `token`.
+    /// By default, no fee is charged.
+    fn flash_fee(_token: Address, _amount: U256) -> U256 {
+        U256::ZERO
+    }
+
+    /// Returns the receiver address of the flash fee.
+    /// By default, the fee is kept by the lender.
+    fn flash_fee_receiver() -> Address {
+        Address::ZERO
+    }
+}
+
+sol_storage! {
+    /// FlashLender lends the tokens held by the contract through ERC-3156 flash loans
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.FlashLender"))]
+    pub struct FlashLender<T> {
+        /// The lock held during a loan
+        ReentrancyGuard guard;
+        PhantomData<T> phantom;
+    }
+
+    /// FlashBorrower stores the lenders trusted to call back the contract
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.FlashBorrower"))]
+    pub struct FlashBorrower {
+        /// Maps lender to whether it is trusted
+        mapping(address => bool) trusted_lenders;
+    }
+}
+
+// Define events and errors in the contract, and the functions of the tokens, lenders and borrowers
+sol! {
+    /// Emitted when `receiver` is lent `amount` of `token` for a `fee`.
+    event FlashLoan(address indexed receiver, address indexed token, uint256 amount, uint256 fee);
+    /// Emitted when `lender` is trusted or untrusted by the borrower.
+    event TrustedLenderSet(address indexed lender, bool trusted);
+
+    /// The loan token is not valid.
+    error ERC3156UnsupportedToken(address token);
+    /// The requested loan exceeds the max loan value for `token`.
+    error ERC3156ExceededMaxLoan(uint256 maxLoan);
+    /// The receiver of a flashloan is not a valid `onFlashLoan` implementer.
+    error ERC3156InvalidReceiver(address receiver);
+    /// The `token` returned an invalid balance.
+    error InvalidTokenBalance(address token);
+    /// The flash loan callback was not called by a trusted lender.
+    error UntrustedLender(address lender);
+    /// The flash loan was not initiated by the borrower.
+    error UntrustedLoanInitiator(address initiator);
+    /// The `lender` returned an invalid response.
+    error InvalidLenderResponse(address lender);
+
+    function balanceOf(address account) external view returns (uint256);
+    function allowance(address owner, address spender) external view returns (uint256);
+    function flashFee(address token, uint256 amount) external view returns (uint256);
+    function flashLoan(address receiver, address token, uint256 amount, bytes data) external returns (bool);
+    function onFlashLoan(address initiator, address token, uint256 amount, uint256 fee, bytes data) external returns (bytes32);
+}
+
+#[derive(SolidityError)]
+pub enum FlashLenderError {
+    ERC3156UnsupportedToken(ERC3156UnsupportedToken),
+    ERC3156ExceededMaxLoan(ERC3156ExceededMaxLoan),
+    ERC3156InvalidReceiver(ERC3156InvalidReceiver),
+    InvalidTokenBalance(InvalidTokenBalance),
+}
+
+#[derive(SolidityError)]
+pub enum FlashBorrowerError {
+    UntrustedLender(UntrustedLender),
+    UntrustedLoanInitiator(UntrustedLoanInitiator),
+    InvalidLenderResponse(InvalidLenderResponse),
+}
+
+// External functions
+#[external]
+impl<T: FlashLenderParams> FlashLender<T> {
+    /// Returns the maximum amount of `token` available to be lent, which is the balance of the
+    /// contract, or 0 if `token` is not supported.
+    pub fn max_flash_loan(&self, token: Address) -> Result<U256, Vec<u8>> {
+        if !T::is_supported(token) {
+            return Ok(U256::ZERO);
+        }
+        let call = balanceOfCall {
+            account: contract::address(),
+        };
+        let data = function_static_call(token, &call.encode())?;
+        balanceOfCall::decode_returns(&data, true)
+            .map(|returns| returns._0)
+            .map_err(|_| {
+                FlashLenderError::InvalidTokenBalance(InvalidTokenBalance { token }).into()
+            })
+    }
+
+    /// Returns the fee applied when doing flash loans of `amount` of `token`.
+    ///
+    /// Requirements:
+    /// - `token` must be supported.
+    pub fn flash_fee(&self, token: Address, amount: U256) -> Result<U256, FlashLenderError> {
+        if !T::is_supported(token) {
+            return Err(FlashLenderError::ERC3156UnsupportedToken(
+                ERC3156UnsupportedToken { token },
+            ));
+        }
+        Ok(T::flash_fee(token, amount))
+    }
+
+    /// Performs a flash loan of `amount` of `token` to `receiver`, calling its `onFlashLoan`
+    /// callback. The tokens are sent to `receiver`, and `amount + fee` is pulled back from
+    /// `receiver` through its allowance once the callback returns.
+    ///
+    /// Requirements:
+    /// - No other loan can be in progress.
+    /// - `token` must be supported.
+    /// - `amount` must not exceed `max_flash_loan`.
+    /// - `receiver` must return `ON_FLASH_LOAN_RETURN` from its callback.
+    /// - `receiver` must approve this contract for at least `amount + fee`.
+    ///
+    /// Emits a {FlashLoan} event.
+    pub fn flash_loan(
+        &mut self,
+        receiver: Address,
+        token: Address,
+        amount: U256,
+        data: Bytes,
+    ) -> Result<bool, Vec<u8>> {
+        self.guard.non_reentrant_enter()?;
+        let fee = self.flash_fee(token, amount)?;
+        let max_loan = self.max_flash_loan(token)?;
+        if amount > max_loan {
+            return Err(
+                FlashLenderError::ERC3156ExceededMaxLoan(ERC3156ExceededMaxLoan {
+                    maxLoan: max_loan,
+                })
+                .into(),
+            );
+        }
+        safe_transfer(token, receiver, amount)?;
+
+        let call = onFlashLoanCall {
+            initiator: msg::sender(),
+            token,
+            amount,
+            fee,
+            data: data.0,
+        };
+        let returned = function_call(receiver, &call.encode())?;
+        let returned = onFlashLoanCall::decode_returns(&returned, true).map(|returns| returns._0);
+        if returned != Ok(ON_FLASH_LOAN_RETURN.0) {
+            return Err(
+                FlashLenderError::ERC3156InvalidReceiver(ERC3156InvalidReceiver { receiver })
+                    .into(),
+            );
+        }
+
+        safe_transfer_from(token, receiver, contract::address(), amount + fee)?;
+        let fee_receiver = T::flash_fee_receiver();
+        if !fee.is_zero() && !fee_receiver.is_zero() {
+            safe_transfer(token, fee_receiver, fee)?;
+        }
+        evm::log(FlashLoan {
+            receiver,
+            token,
+            amount,
+            fee,
+        });
+        self.guard.non_reentrant_exit();
+        Ok(true)
+    }
+}
+
+// Internal functions
+impl FlashBorrower {
+    /// Sets whether `lender` is trusted to call back the contract.
+    ///
+    /// Emits a {TrustedLenderSet} event.
+    pub fn _set_trusted_lender(&mut self, lender: Address, trusted: bool) {
+        self.trusted_lenders.insert(lender, trusted);
+        evm::log(TrustedLenderSet { lender, trusted });
+    }
+
+    /// Borrows `amount` of `token` from `lender`, which calls back the contract with `data`.
+    /// Approves `lender` to pull back the loan and its fee on top of its current allowance.
+    ///
+    /// Requirements:
+    /// - `lender` must be trusted.
+    pub fn _flash_borrow(
+        &mut self,
+        lender: Address,
+        token: Address,
+        amount: U256,
+        data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        if !self.trusted_lenders.get(lender) {
+            return Err(FlashBorrowerError::UntrustedLender(UntrustedLender { lender }).into());
+        }
+        let invalid_response =
+            || FlashBorrowerError::InvalidLenderResponse(InvalidLenderResponse { lender });
+        let fee = function_static_call(lender, &flashFeeCall { token, amount }.encode())?;
+        let fee = flashFeeCall::decode_returns(&fee, true)
+            .map_err(|_| invalid_response())?
+            ._0;
+        let allowance = allowanceCall {
+            owner: contract::address(),
+            spender: lender,
+        };
+        let allowance = function_static_call(token, &allowance.encode())?;
+        let allowance = allowanceCall::decode_returns(&allowance, true)
+            .map_err(|_| invalid_response())?
+            ._0;
+        let repayment = amount
+            .checked_add(fee)
+            .and_then(|repayment| repayment.checked_add(allowance))
+            .ok_or_else(invalid_response)?;
+        safe_approve(token, lender, repayment)?;
+
+        let call = flashLoanCall {
+            receiver: contract::address(),
+            token,
+            amount,
+            data: data.0,
+        };
+        function_call(lender, &call.encode())?;
+        Ok(())
+    }
+
+    /// Checks that the `onFlashLoan` callback is called by a trusted lender, for a loan that the
+    /// contract initiated.
+    ///
+    /// Requirements:
+    /// - The caller must be a trusted lender.
+    /// - `initiator` must be this contract.
+    pub fn _verify_flash_loan(&self, initiator: Address) -> Result<(), FlashBorrowerError> {
+        let lender = msg::sender();
+        if !self.trusted_lenders.get(lender) {
+            return Err(FlashBorrowerError::UntrustedLender(UntrustedLender {
+                lender,
+            }));
+        }
+        if initiator != contract::address() {
+            return Err(FlashBorrowerError::UntrustedLoanInitiator(
+                UntrustedLoanInitiator { initiator },
+            ));
+        }
+        Ok(())
+    }
+}
+
+// External functions
+#[external]
+impl FlashBorrower {
+    /// Returns whether `lender` is trusted to call back the contract.
+    pub fn is_trusted_lender(&self, lender: Address) -> bool {
+        self.trusted_lenders.get(lender)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stylus_sdk::alloy_primitives::b256;
+
+    #[test]
+    fn test_on_flash_loan_return() {
+        assert_eq!(
+            ON_FLASH_LOAN_RETURN,
+            b256!("439148f0bbc682ca079e46d6e2c2f0c1e3b820f1a291b069d8882abf8cf18dd9")
+        );
+    }
+}
@@ -0,0 +1,329 @@
+//! Jump rate interest model and index based interest accrual for lending markets.
+//! The logic was based off of: https://github.com/compound-finance/compound-protocol/blob/master/contracts/JumpRateModelV2.sol
+//!
+//! Rates are per second and utilizations are fractions, both as WAD (18 decimal fixed point)
+//! numbers. The borrow rate of a `JumpRateModel` grows linearly with the utilization of the
+//! market, `borrows / (cash + borrows - reserves)`, and grows `jump_multiplier / multiplier` times
+//! faster above the `kink` utilization, which pushes borrowers to repay before the market runs out
+//! of cash. Suppliers earn the interest paid by borrowers, minus the `reserve_factor` share kept
+//! as reserves.
+//!
+//! Balances are tracked against indexes, as in Compound's `CToken.accrueInterest`. The borrow index
+//! starts at 1 (`WAD`) and compounds by `borrow_rate * elapsed` at every accrual, so a debt of
+//! `principal` taken when the index was `principal_index` is worth
+//! `principal * index / principal_index` later (see `balance_at`). The supply index compounds by
+//! the supply rate in the same way. `InterestAccrual` stores the indexes and the totals of a market,
+//! and the inheriting contract calls `_accrue` with the cash of the market before any operation
+//! that reads or changes borrows, supplies or reserves, then updates the totals itself.
+
+use core::marker::PhantomData;
+use stylus_sdk::{
+    alloy_primitives::{U256, U64},
+    alloy_sol_types::sol,
+    block, evm,
+    prelude::*,
+};
+
+use crate::utils::fixed_point_math::{div_wad, mul_div, mul_wad, WAD};
+
+/// The number of seconds per year used to convert yearly rates, ignoring leap years.
+pub const SECONDS_PER_YEAR: u64 = 31_536_000;
+
+/// A borrow rate that is linear in the utilization, with a steeper slope above the kink.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JumpRateModel {
+    /// The borrow rate per second at 0 utilization
+    pub base_rate: U256,
+    /// The increase of the borrow rate per second for a utilization of 1, below the kink
+    pub multiplier: U256,
+    /// The increase of the borrow rate per second for a utilization of 1, above the kink
+    pub jump_multiplier: U256,
+    /// The utilization above which the jump multiplier applies
+    pub kink: U256,
+}
+
+impl JumpRateModel {
+    /// Returns the model with yearly rates converted to rates per second.
+    pub fn from_yearly(
+        base_rate: U256,
+        multiplier: U256,
+        jump_multiplier: U256,
+        kink: U256,
+    ) -> Self {
+        let seconds = U256::from(SECONDS_PER_YEAR);
+        Self {
+            base_rate: base_rate / seconds,
+            multiplier: multiplier / seconds,
+            jump_multiplier: jump_multiplier / seconds,
+            kink,
+        }
+    }
+
+    /// Returns the borrow rate per second of a market with `cash`, `borrows` and `reserves`, or
+    /// `None` on overflow or if `reserves` exceed `cash + borrows`.
+    pub fn borrow_rate(&self, cash: U256, borrows: U256, reserves: U256) -> Option<U256> {
+        let utilization = utilization(cash, borrows, reserves)?;
+        let normal =
+            |utilization| mul_wad(utilization, self.multiplier)?.checked_add(self.base_rate);
+        if utilization <= self.kink {
+            return normal(utilization);
+        }
+        let excess = mul_wad(utilization - self.kink, self.jump_multiplier)?;
+        normal(self.kink)?.checked_add(excess)
+    }
+
+    /// Returns the supply rate per second of a market with `cash`, `borrows` and `reserves`, once
+    /// the `reserve_factor` share of the interest is kept as reserves, or `None` on overflow, if
+    /// `reserves` exceed `cash + borrows`, or if `reserve_factor` exceeds `WAD`.
+    pub fn supply_rate(
+        &self,
+        cash: U256,
+        borrows: U256,
+        reserves: U256,
+        reserve_factor: U256,
+    ) -> Option<U256> {
+        let borrow_rate = self.borrow_rate(cash, borrows, reserves)?;
+        let rate_to_pool = mul_wad(borrow_rate, WAD.checked_sub(reserve_factor)?)?;
+        mul_wad(utilization(cash, borrows, reserves)?, rate_to_pool)
+    }
+}
+
+/// Returns the share of the funds of a market that is borrowed, `borrows / (cash + borrows -
+/// reserves)`, or `None` on overflow or if `reserves` exceed `cash + borrows`.
+pub fn utilization(cash: U256, borrows: U256, reserves: U256) -> Option<U256> {
+    if borrows.is_zero() {
+        return Some(U256::ZERO);
+    }
+    let total = cash.checked_add(borrows)?.checked_sub(reserves)?;
+    div_wad(borrows, total)
+}
+
+/// Returns `index` compounded by `rate` per second for `elapsed` seconds, or `None` on overflow.
+pub fn accrue_index(index: U256, rate: U256, elapsed: u64) -> Option<U256> {
+    let factor = rate.checked_mul(U256::from(elapsed))?;
+    index.checked_add(mul_wad(index, factor)?)
+}
+
+/// Returns the balance of `principal` recorded when the index was `principal_index`, now that it
+/// is `index`, or `None` on overflow or if `principal_index` is 0.
+pub fn balance_at(principal: U256, principal_index: U256, index: U256) -> Option<U256> {
+    mul_div(principal, index, principal_index)
+}
+
+/// Immutable definitions of the market
+pub trait InterestRateParams {
+    /// Returns the interest rate model of the market.
+    fn interest_rate_model() -> JumpRateModel;
+
+    /// Returns the share of the interest kept as reserves, as a WAD.
+    /// By default, all the interest goes to suppliers.
+    fn reserve_factor() -> U256 {
+        U256::ZERO
+    }
+}
+
+sol_storage! {
+    /// InterestAccrual stores the indexes and the totals of a lending market
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.InterestAccrual"))]
+    pub struct InterestAccrual<T> {
+        /// The borrow index at the last accrual, or 0 before the first one
+        uint256 borrow_index;
+        /// The supply index at the last accrual, or 0 before the first one
+        uint256 supply_index;
+        /// The amount borrowed, including the interest accrued
+        uint256 total_borrows;
+        /// The interest kept as reserves
+        uint256 total_reserves;
+        /// The timestamp of the last accrual
+        uint64 accrual_timestamp;
+        PhantomData<T> phantom;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when `interestAccumulated` is accrued on the borrows of a market holding `cashPrior`.
+    event AccrueInterest(uint256 cashPrior, uint256 interestAccumulated, uint256 borrowIndex, uint256 supplyIndex, uint256 totalBorrows);
+
+    /// The interest accrual has overflowed.
+    error InterestAccrualOverflow();
+}
+
+#[derive(SolidityError)]
+pub enum InterestError {
+    InterestAccrualOverflow(InterestAccrualOverflow),
+}
+
+// Internal functions
+impl<T: InterestRateParams> InterestAccrual<T> {
+    /// Accrues the interest of the market holding `cash` since the last accrual: compounds the
+    /// indexes, adds the interest to the borrows, and the `reserve_factor` share of it to the
+    /// reserves. Initializes the indexes to `WAD` the first time.
+    ///
+    /// Requirements:
+    /// - The rates, indexes and totals must not overflow.
+    ///
+    /// Emits an {AccrueInterest} event if time has passed since the last accrual.
+    pub fn _accrue(&mut self, cash: U256) -> Result<(), InterestError> {
+        let now = block::timestamp();
+        let elapsed = now.saturating_sub(self.accrual_timestamp.get().to());
+        if self.borrow_index.get().is_zero() {
+            self.borrow_index.set(WAD);
+            self.supply_index.set(WAD);
+            self.accrual_timestamp.set(U64::from(now));
+            return Ok(());
+        }
+        if elapsed == 0 {
+            return Ok(());
+        }
+
+        let overflow = || InterestError::InterestAccrualOverflow(InterestAccrualOverflow {});
+        let model = T::interest_rate_model();
+        let reserve_factor = T::reserve_factor();
+        let borrows = self.total_borrows.get();
+        let reserves = self.total_reserves.get();
+        let borrow_rate = model
+            .borrow_rate(cash, borrows, reserves)
+            .ok_or_else(overflow)?;
+        let supply_rate = model
+            .supply_rate(cash, borrows, reserves, reserve_factor)
+            .ok_or_else(overflow)?;
+
+        let interest = borrow_rate
+            .checked_mul(U256::from(elapsed))
+            .and_then(|factor| mul_wad(factor, borrows))
+            .ok_or_else(overflow)?;
+        let total_borrows = borrows.checked_add(interest).ok_or_else(overflow)?;
+        let total_reserves = mul_wad(interest, reserve_factor)
+            .and_then(|kept| reserves.checked_add(kept))
+            .ok_or_else(overflow)?;
+        let borrow_index =
+            accrue_index(self.borrow_index.get(), borrow_rate, elapsed).ok_or_else(overflow)?;
+        let supply_index =
+            accrue_index(self.supply_index.get(), supply_rate, elapsed).ok_or_else(overflow)?;
+
+        self.borrow_index.set(borrow_index);
+        self.supply_index.set(supply_index);
+        self.total_borrows.set(total_borrows);
+        self.total_reserves.set(total_reserves);
+        self.accrual_timestamp.set(U64::from(now));
+        evm::log(AccrueInterest {
+            cashPrior: cash,
+            interestAccumulated: interest,
+            borrowIndex: borrow_index,
+            supplyIndex: supply_index,
+            totalBorrows: total_borrows,
+        });
+        Ok(())
+    }
+}
+
+// External functions
+#[external]
+impl<T: InterestRateParams> InterestAccrual<T> {
+    /// Returns the borrow index, the supply index, the total borrows and the total reserves at the
+    /// last accrual, and its timestamp.
+    pub fn interest_accrual(&self) -> (U256, U256, U256, U256, u64) {
+        (
+            self.borrow_index.get(),
+            self.supply_index.get(),
+            self.total_borrows.get(),
+            self.total_reserves.get(),
+            self.accrual_timestamp.get().to(),
+        )
+    }
+
+    /// Returns the borrow rate per second of the market holding `cash`, as of the last accrual.
+    pub fn borrow_rate_per_second(&self, cash: U256) -> Result<U256, InterestError> {
+        T::interest_rate_model()
+            .borrow_rate(cash, self.total_borrows.get(), self.total_reserves.get())
+            .ok_or(InterestError::InterestAccrualOverflow(
+                InterestAccrualOverflow {},
+            ))
+    }
+
+    /// Returns the supply rate per second of the market holding `cash`, as of the last accrual.
+    pub fn supply_rate_per_second(&self, cash: U256) -> Result<U256, InterestError> {
+        T::interest_rate_model()
+            .supply_rate(
+                cash,
+                self.total_borrows.get(),
+                self.total_reserves.get(),
+                T::reserve_factor(),
+            )
+            .ok_or(InterestError::InterestAccrualOverflow(
+                InterestAccrualOverflow {},
+            ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wad(percent: u64) -> U256 {
+        WAD * U256::from(percent) / U256::from(100)
+    }
+
+    fn model() -> JumpRateModel {
+        JumpRateModel {
+            base_rate: wad(2),
+            multiplier: wad(10),
+            jump_multiplier: wad(100),
+            kink: wad(80),
+        }
+    }
+
+    #[test]
+    fn test_utilization() {
+        assert_eq!(
+            utilization(wad(100), U256::ZERO, U256::ZERO),
+            Some(U256::ZERO)
+        );
+        assert_eq!(utilization(wad(50), wad(50), U256::ZERO), Some(wad(50)));
+        assert_eq!(utilization(wad(60), wad(50), wad(10)), Some(wad(50)));
+        assert_eq!(utilization(wad(10), wad(50), wad(70)), None);
+    }
+
+    #[test]
+    fn test_borrow_rate() {
+        let model = model();
+        // 2% + 50% * 10%
+        assert_eq!(
+            model.borrow_rate(wad(50), wad(50), U256::ZERO),
+            Some(wad(7))
+        );
+        // 2% + 80% * 10% at the kink
+        assert_eq!(
+            model.borrow_rate(wad(20), wad(80), U256::ZERO),
+            Some(wad(10))
+        );
+        // 2% + 80% * 10% + 10% * 100% above the kink
+        assert_eq!(
+            model.borrow_rate(wad(10), wad(90), U256::ZERO),
+            Some(wad(20))
+        );
+    }
+
+    #[test]
+    fn test_supply_rate() {
+        let model = model();
+        // 50% * 7% * (1 - 20%)
+        let rate = model.supply_rate(wad(50), wad(50), U256::ZERO, wad(20));
+        assert_eq!(rate, Some(WAD * U256::from(28) / U256::from(1000)));
+        assert_eq!(
+            model.supply_rate(wad(50), wad(50), U256::ZERO, wad(101)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_accrue_index() {
+        let index = accrue_index(WAD, wad(1), 10).unwrap();
+        assert_eq!(index, wad(110));
+        assert_eq!(balance_at(wad(200), WAD, index), Some(wad(220)));
+        assert_eq!(balance_at(wad(200), U256::ZERO, index), None);
+        assert_eq!(accrue_index(WAD, U256::MAX, 2), None);
+    }
+}
@@ -0,0 +1,13 @@
+//! DeFi building blocks such as AMM pairs, vault routers, flash lenders and interest accrual
+
+#[cfg(feature = "amm-pair")]
+pub mod amm_pair;
+
+#[cfg(feature = "erc4626-router")]
+pub mod erc4626_router;
+
+#[cfg(feature = "flash-lender")]
+pub mod flash_lender;
+
+#[cfg(feature = "interest")]
+pub mod interest;
@@ -0,0 +1,581 @@
+//! Exports the ABI of a contract, as its Solidity interface or as JSON artifacts.
+//!
+//! The `main` of an ABI export binary can call [`main`] with its entrypoint:
+//! - without arguments, it prints the Solidity interface like `cargo stylus export-abi`
+//! - with `--json <DIR>`, it writes the Solidity JSON ABI to `<DIR>/<NAME>.json`
+//! - with `--foundry <DIR>`, it writes a Foundry-style artifact to `<DIR>/<NAME>.sol/<NAME>.json`
+//!
+//! The SDK only prints the interfaces of a contract, so they are parsed back into ABI items. Events
+//! are not part of the printed interfaces, and only appear in the JSON ABI if a hand-written
+//! `GenerateAbi` implementation declares them.
+//!
+//! The export fails if two functions of the contract have different signatures but the same
+//! selector, and warns about the inherited methods shadowed by another method, which is an error
+//! with `--deny-shadowing`.
+
+use std::{fmt, marker::PhantomData, path::PathBuf, process};
+
+use serde_json::{json, Map, Value};
+use stylus_sdk::abi::{export::print_abi, GenerateAbi};
+
+use super::{selectors, write_json};
+
+/// Runs the ABI export of the contract `T` with the command line arguments, and exits with an
+/// error if they are invalid or the artifacts cannot be written.
+pub fn main<T: GenerateAbi>() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(err) = run::<T>(&args) {
+        eprintln!("error: {err}");
+        process::exit(1);
+    }
+}
+
+/// Runs the ABI export of the contract `T` with `args` (see the module documentation).
+pub fn run<T: GenerateAbi>(args: &[String]) -> Result<(), String> {
+    let mut json_dir = None;
+    let mut foundry_dir = None;
+    let mut deny_shadowing = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let dir = match arg.as_str() {
+            "--json" => &mut json_dir,
+            "--foundry" => &mut foundry_dir,
+            "--deny-shadowing" => {
+                deny_shadowing = true;
+                continue;
+            }
+            _ => {
+                return Err(format!(
+                    "unknown argument `{arg}`, expected --json, --foundry or --deny-shadowing"
+                ))
+            }
+        };
+        let path = args.next().ok_or(format!("{arg} takes a directory"))?;
+        *dir = Some(PathBuf::from(path));
+    }
+
+    let source = interfaces::<T>();
+    check_selectors(&parse_interfaces(&source)?, deny_shadowing)?;
+    if json_dir.is_none() && foundry_dir.is_none() {
+        print_abi::<T>();
+        return Ok(());
+    }
+
+    let items = parse(&source)?;
+    if let Some(dir) = json_dir {
+        write_json(&dir.join(format!("{}.json", T::NAME)), &to_json(&items))?;
+    }
+    if let Some(dir) = foundry_dir {
+        let path = dir
+            .join(format!("{}.sol", T::NAME))
+            .join(format!("{}.json", T::NAME));
+        write_json(&path, &foundry_artifact(&items))?;
+    }
+    Ok(())
+}
+
+/// Fails if functions of `interfaces` have different signatures but the same selector, or if
+/// `deny_shadowing` and a method shadows another. Warns about the shadowed methods otherwise.
+fn check_selectors(interfaces: &[Interface], deny_shadowing: bool) -> Result<(), String> {
+    let (errors, warnings): (Vec<_>, Vec<_>) = selectors::collisions(interfaces)
+        .into_iter()
+        .partition(|collision| deny_shadowing || collision.is_clash());
+    for collision in warnings {
+        eprintln!("warning: {collision}");
+    }
+    if errors.is_empty() {
+        return Ok(());
+    }
+    let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+    Err(format!(
+        "functions sharing a selector:\n{}",
+        errors.join("\n")
+    ))
+}
+
+/// Returns the Solidity interfaces of the contract `T`.
+pub fn interfaces<T: GenerateAbi>() -> String {
+    struct Interfaces<T>(PhantomData<T>);
+
+    impl<T: GenerateAbi> fmt::Display for Interfaces<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            T::fmt_abi(f)
+        }
+    }
+
+    Interfaces::<T>(PhantomData).to_string()
+}
+
+/// The kind of an ABI item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemKind {
+    Function,
+    Error,
+    Event,
+}
+
+impl ItemKind {
+    fn keyword(self) -> &'static str {
+        match self {
+            ItemKind::Function => "function",
+            ItemKind::Error => "error",
+            ItemKind::Event => "event",
+        }
+    }
+}
+
+/// A parameter of an ABI item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Param {
+    /// The name of the parameter, empty if unnamed
+    pub name: String,
+    /// The Solidity type of the parameter, with tuples written as `(type1,type2)`
+    pub ty: String,
+    /// Whether the parameter is an indexed event topic
+    pub indexed: bool,
+}
+
+/// A function, error or event of an interface
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiItem {
+    pub kind: ItemKind,
+    pub name: String,
+    pub inputs: Vec<Param>,
+    pub outputs: Vec<Param>,
+    /// `pure`, `view`, `payable` or `nonpayable` for functions
+    pub state_mutability: &'static str,
+}
+
+impl AbiItem {
+    /// Returns the canonical signature of the item (ex. `transfer(address,uint256)`).
+    pub fn signature(&self) -> String {
+        let types: Vec<&str> = self.inputs.iter().map(|param| param.ty.as_str()).collect();
+        format!("{}({})", self.name, types.join(","))
+    }
+
+    /// Returns the 4-byte selector of the item, or the topic of the event truncated to 4 bytes.
+    pub fn selector(&self) -> [u8; 4] {
+        // The native keccak hostio is not available outside of the VM
+        let hash = stylus_sdk::keccak_const::Keccak256::new()
+            .update(self.signature().as_bytes())
+            .finalize();
+        [hash[0], hash[1], hash[2], hash[3]]
+    }
+
+    /// Returns the item in the Solidity JSON ABI format.
+    pub fn to_json(&self) -> Value {
+        let mut item = Map::new();
+        item.insert("type".into(), self.kind.keyword().into());
+        item.insert("name".into(), self.name.clone().into());
+        item.insert("inputs".into(), params_json(&self.inputs, self.kind));
+        match self.kind {
+            ItemKind::Function => {
+                item.insert("outputs".into(), params_json(&self.outputs, self.kind));
+                item.insert("stateMutability".into(), self.state_mutability.into());
+            }
+            ItemKind::Event => {
+                item.insert("anonymous".into(), false.into());
+            }
+            ItemKind::Error => {}
+        }
+        Value::Object(item)
+    }
+}
+
+/// An interface printed by the SDK
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interface {
+    /// The name of the interface (ex. `IERC20`), empty for items outside of an interface
+    pub name: String,
+    /// The interfaces of the `is` clause, in the order their contracts are `#[inherit]`ed
+    pub inherits: Vec<String>,
+    pub items: Vec<AbiItem>,
+}
+
+/// Parses the interfaces in `source`, in the order they are printed.
+pub fn parse_interfaces(source: &str) -> Result<Vec<Interface>, String> {
+    let mut interfaces: Vec<Interface> = Vec::new();
+    for line in source.lines().map(str::trim) {
+        if let Some(header) = line.strip_prefix("interface ") {
+            let header = header.split('{').next().unwrap_or_default().trim();
+            let (name, inherits) = match header.split_once(" is ") {
+                Some((name, inherits)) => (
+                    name,
+                    inherits.split(',').map(|name| name.trim().into()).collect(),
+                ),
+                None => (header, Vec::new()),
+            };
+            interfaces.push(Interface {
+                name: name.trim().into(),
+                inherits,
+                items: Vec::new(),
+            });
+            continue;
+        }
+
+        let (kind, rest) = if let Some(rest) = line.strip_prefix("function ") {
+            (ItemKind::Function, rest)
+        } else if let Some(rest) = line.strip_prefix("error ") {
+            (ItemKind::Error, rest)
+        } else if let Some(rest) = line.strip_prefix("event ") {
+            (ItemKind::Event, rest)
+        } else {
+            continue;
+        };
+        let item = parse_item(kind, rest.trim_end_matches(';'))
+            .map_err(|err| format!("invalid declaration `{line}`: {err}"))?;
+        if interfaces.is_empty() {
+            interfaces.push(Interface {
+                name: String::new(),
+                inherits: Vec::new(),
+                items: Vec::new(),
+            });
+        }
+        interfaces.last_mut().unwrap().items.push(item);
+    }
+    Ok(interfaces)
+}
+
+/// Returns `interfaces` in the order the router of the contract tries them: the interface of the
+/// contract, printed last, then the interfaces it inherits, depth first. Each interface is
+/// returned once, and the interfaces it does not inherit are returned last.
+pub fn dispatch_order(interfaces: &[Interface]) -> Vec<&Interface> {
+    fn visit<'a>(interfaces: &'a [Interface], index: usize, order: &mut Vec<&'a Interface>) {
+        let interface = &interfaces[index];
+        if order.iter().any(|visited| visited.name == interface.name) {
+            return;
+        }
+        order.push(interface);
+        for parent in &interface.inherits {
+            if let Some(index) = interfaces.iter().position(|other| &other.name == parent) {
+                visit(interfaces, index, order);
+            }
+        }
+    }
+
+    let mut order = Vec::new();
+    for index in (0..interfaces.len()).rev() {
+        visit(interfaces, index, &mut order);
+    }
+    order
+}
+
+/// Parses the functions, errors and events of the interfaces in `source`, in dispatch order. Items
+/// declared by several interfaces (ex. inherited overrides) are only kept once, as declared by the
+/// interface whose implementation is called.
+pub fn parse(source: &str) -> Result<Vec<AbiItem>, String> {
+    let interfaces = parse_interfaces(source)?;
+    let mut items: Vec<AbiItem> = Vec::new();
+    for item in dispatch_order(&interfaces)
+        .into_iter()
+        .flat_map(|interface| &interface.items)
+    {
+        if !items
+            .iter()
+            .any(|other| other.kind == item.kind && other.signature() == item.signature())
+        {
+            items.push(item.clone());
+        }
+    }
+    Ok(items)
+}
+
+/// Returns the JSON ABI of `items`.
+pub fn to_json(items: &[AbiItem]) -> Value {
+    Value::Array(items.iter().map(AbiItem::to_json).collect())
+}
+
+/// Returns a Foundry-style artifact of `items`, with the ABI and the selector of each function.
+pub fn foundry_artifact(items: &[AbiItem]) -> Value {
+    let mut identifiers = Map::new();
+    for item in items.iter().filter(|item| item.kind == ItemKind::Function) {
+        identifiers.insert(item.signature(), hex::encode(item.selector()).into());
+    }
+    json!({
+        "abi": to_json(items),
+        "methodIdentifiers": identifiers,
+    })
+}
+
+/// Parses the declaration `rest` of an item, following its keyword.
+fn parse_item(kind: ItemKind, rest: &str) -> Result<AbiItem, String> {
+    let open = rest.find('(').ok_or("missing parameters")?;
+    let name = rest[..open].trim().to_string();
+    let (inputs, modifiers) = split_group(&rest[open..])?;
+    let inputs = parse_params(inputs)?;
+
+    let mut outputs = Vec::new();
+    let mut state_mutability = "nonpayable";
+    let mut modifiers = modifiers.trim();
+    while !modifiers.is_empty() {
+        if let Some(returns) = modifiers.strip_prefix("returns") {
+            let (returns, rest) = split_group(returns.trim_start())?;
+            outputs = parse_params(returns)?;
+            modifiers = rest.trim();
+            continue;
+        }
+        let (word, rest) = modifiers
+            .split_once(char::is_whitespace)
+            .unwrap_or((modifiers, ""));
+        match word {
+            "pure" => state_mutability = "pure",
+            "view" => state_mutability = "view",
+            "payable" => state_mutability = "payable",
+            "external" | "public" | "anonymous" => {}
+            _ => return Err(format!("unsupported modifier `{word}`")),
+        }
+        modifiers = rest.trim();
+    }
+
+    Ok(AbiItem {
+        kind,
+        name,
+        inputs,
+        outputs,
+        state_mutability,
+    })
+}
+
+/// Splits `source`, which starts with `(`, into the contents of its parentheses and the rest.
+fn split_group(source: &str) -> Result<(&str, &str), String> {
+    if !source.starts_with('(') {
+        return Err("expected `(`".into());
+    }
+    let mut depth = 0;
+    for (index, c) in source.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok((&source[1..index], &source[index + 1..]));
+                }
+            }
+            _ => {}
+        }
+    }
+    Err("unbalanced parentheses".into())
+}
+
+/// Splits `source` on the commas that are not nested in parentheses.
+fn split_top_level(source: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, c) in source.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&source[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&source[start..]);
+    parts
+}
+
+/// Parses a list of parameters (ex. `address to, uint256 amount`).
+fn parse_params(source: &str) -> Result<Vec<Param>, String> {
+    if source.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    split_top_level(source)
+        .into_iter()
+        .map(|param| parse_param(param.trim()))
+        .collect()
+}
+
+/// Parses a parameter (ex. `bytes calldata data` or `(address, uint256)[] memory`).
+fn parse_param(source: &str) -> Result<Param, String> {
+    let (ty, rest) = if source.starts_with('(') {
+        let (components, rest) = split_group(source)?;
+        let components: Vec<String> = parse_params(components)?
+            .into_iter()
+            .map(|param| param.ty)
+            .collect();
+        let suffix_len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let (suffix, rest) = rest.split_at(suffix_len);
+        (format!("({}){suffix}", components.join(",")), rest)
+    } else {
+        let ty_len = source.find(char::is_whitespace).unwrap_or(source.len());
+        let (ty, rest) = source.split_at(ty_len);
+        if ty.is_empty() {
+            return Err("missing parameter type".into());
+        }
+        (ty.to_string(), rest)
+    };
+
+    let mut name = String::new();
+    let mut indexed = false;
+    for word in rest.split_whitespace() {
+        match word {
+            "memory" | "calldata" | "storage" => {}
+            "indexed" => indexed = true,
+            _ if name.is_empty() => name = word.to_string(),
+            _ => return Err(format!("unexpected `{word}` in parameter `{source}`")),
+        }
+    }
+    Ok(Param { name, ty, indexed })
+}
+
+/// Returns the JSON ABI parameters of `params`.
+fn params_json(params: &[Param], kind: ItemKind) -> Value {
+    Value::Array(
+        params
+            .iter()
+            .map(|param| {
+                let mut json = type_json(&param.ty);
+                json.insert("name".into(), param.name.clone().into());
+                if kind == ItemKind::Event {
+                    json.insert("indexed".into(), param.indexed.into());
+                }
+                Value::Object(json)
+            })
+            .collect(),
+    )
+}
+
+/// Returns the `type` of the Solidity type `ty`, with the `components` of tuples.
+fn type_json(ty: &str) -> Map<String, Value> {
+    let mut json = Map::new();
+    match split_group(ty) {
+        Ok((components, suffix)) => {
+            let components = split_top_level(components)
+                .into_iter()
+                .filter(|component| !component.is_empty())
+                .map(|component| {
+                    let mut json = type_json(component);
+                    json.insert("name".into(), "".into());
+                    Value::Object(json)
+                })
+                .collect();
+            json.insert("type".into(), format!("tuple{suffix}").into());
+            json.insert("components".into(), Value::Array(components));
+        }
+        Err(_) => {
+            json.insert("type".into(), ty.into());
+        }
+    }
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERFACE: &str = "
+        interface IToken {
+            function balanceOf(address _address) external view returns (uint256);
+
+            function transfer(address to, uint256 amount) external returns (bool);
+
+            function mint(uint256 quantity, bytes calldata data) external payable;
+
+            function getOrder(uint256 id) external pure returns ((address, uint256[]) memory, string memory);
+
+            error InsufficientBalance(address, uint256, uint256);
+
+            event Transfer(address indexed from, address indexed to, uint256 value);
+        }
+
+        interface ITokenMock is IToken {
+            function transfer(address recipient, uint256 amount) external returns (bool);
+        }
+    ";
+
+    #[test]
+    fn test_parse() {
+        let items = parse(INTERFACE).unwrap();
+        let signatures: Vec<String> = items.iter().map(AbiItem::signature).collect();
+        assert_eq!(
+            signatures,
+            [
+                "transfer(address,uint256)",
+                "balanceOf(address)",
+                "mint(uint256,bytes)",
+                "getOrder(uint256)",
+                "InsufficientBalance(address,uint256,uint256)",
+                "Transfer(address,address,uint256)",
+            ]
+        );
+        // The override of the contract is called rather than the inherited method
+        assert_eq!(items[0].inputs[0].name, "recipient");
+        assert_eq!(items[0].state_mutability, "nonpayable");
+        assert_eq!(items[1].state_mutability, "view");
+        assert_eq!(items[2].state_mutability, "payable");
+        assert_eq!(items[3].outputs[0].ty, "(address,uint256[])");
+        assert!(items[5].inputs[1].indexed && !items[5].inputs[2].indexed);
+    }
+
+    #[test]
+    fn test_dispatch_order() {
+        let source = "
+            interface IB {}
+            interface IC {}
+            interface IA is IB, IC {}
+            interface ID {}
+            interface IMock is IA, ID {}
+        ";
+        let interfaces = parse_interfaces(source).unwrap();
+        assert_eq!(interfaces[2].inherits, ["IB", "IC"]);
+        let order: Vec<&str> = dispatch_order(&interfaces)
+            .into_iter()
+            .map(|interface| interface.name.as_str())
+            .collect();
+        assert_eq!(order, ["IMock", "IA", "IB", "IC", "ID"]);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let items = parse(INTERFACE).unwrap();
+        assert_eq!(
+            items[0].to_json(),
+            json!({
+                "type": "function",
+                "name": "transfer",
+                "inputs": [
+                    { "type": "address", "name": "recipient" },
+                    { "type": "uint256", "name": "amount" },
+                ],
+                "outputs": [{ "type": "bool", "name": "" }],
+                "stateMutability": "nonpayable",
+            })
+        );
+        assert_eq!(
+            items[3].to_json()["outputs"][0],
+            json!({
+                "type": "tuple",
+                "components": [
+                    { "type": "address", "name": "" },
+                    { "type": "uint256[]", "name": "" },
+                ],
+                "name": "",
+            })
+        );
+        assert_eq!(items[4].to_json()["inputs"][0]["name"], "");
+        assert_eq!(items[5].to_json()["inputs"][0]["indexed"], true);
+    }
+
+    #[test]
+    fn test_foundry_artifact() {
+        let artifact = foundry_artifact(&parse(INTERFACE).unwrap());
+        assert_eq!(
+            artifact["methodIdentifiers"]["transfer(address,uint256)"],
+            "a9059cbb"
+        );
+        assert_eq!(
+            artifact["methodIdentifiers"]["balanceOf(address)"],
+            "70a08231"
+        );
+        assert_eq!(artifact["abi"].as_array().unwrap().len(), 6);
+    }
+
+    #[test]
+    fn test_invalid_declaration() {
+        assert!(parse("function transfer(address to external;").is_err());
+        assert!(parse("function transfer() external virtual;").is_err());
+    }
+}
@@ -0,0 +1,462 @@
+//! The storage layout of a contract, and the upgrade-compatibility check of two layouts.
+//!
+//! `#[storage_layout]` implements [`StorageLayout`] for a `sol_storage!` struct, placing its fields
+//! with the same rules as the SDK. Inkmate's structs are annotated with the `export-layout`
+//! feature, and contracts annotate theirs to export their layout:
+//!
+//! ```ignore
+//! sol_storage! {
+//!     #[entrypoint]
+//!     #[cfg_attr(feature = "export-layout", inkmate::export::layout::storage_layout)]
+//!     pub struct MyToken {
+//!         #[borrow]
+//!         ERC20<MyTokenParams> erc20;
+//!     }
+//! }
+//! ```
+//!
+//! The layout lists the leaf fields of the contract, with the path of nested structs in their label
+//! (ex. `erc20.balances`). Mappings and dynamic arrays are listed at their root slot.
+
+use std::{
+    any, fs,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    process,
+};
+
+#[doc(hidden)]
+pub use alloy_primitives::U256;
+use serde_json::{json, Value};
+use stylus_sdk::storage::{
+    StorageAddress, StorageArray, StorageBlockHash, StorageBlockNumber, StorageBool, StorageBytes,
+    StorageFixedBytes, StorageKey, StorageMap, StorageSigned, StorageString, StorageType,
+    StorageUint, StorageVec,
+};
+
+pub use inkmate_macros::storage_layout;
+
+/// A leaf field of the storage layout of a contract
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    /// The path of the field from the contract (ex. `erc20.balances`)
+    pub label: String,
+    /// The slot of the field, written in decimal like the storage layout of solc
+    pub slot: U256,
+    /// The offset of the field in its slot, in bytes from the right like Solidity
+    pub offset: u8,
+    /// The number of bytes of the field, which spans several slots if above 32
+    pub bytes: usize,
+    /// The type of the field, without module paths (ex. `StorageMap<Address, StorageUint<256, 4>>`)
+    pub ty: String,
+}
+
+impl Field {
+    /// Returns the storage bytes of the field, as a range of positions where the slot is the high
+    /// part and the byte from the left of the slot the low part.
+    fn span(&self) -> (U256, U256) {
+        let start = self.slot * U256::from(32) + U256::from(32 - self.offset as usize)
+            - U256::from(self.bytes.min(32));
+        (start, start + U256::from(self.bytes))
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "label": self.label,
+            "slot": self.slot.to_string(),
+            "offset": self.offset,
+            "bytes": self.bytes,
+            "type": self.ty,
+        })
+    }
+
+    fn from_json(value: &Value) -> Option<Self> {
+        Some(Self {
+            label: value["label"].as_str()?.into(),
+            slot: value["slot"].as_str()?.parse().ok()?,
+            offset: value["offset"].as_u64()?.try_into().ok()?,
+            bytes: value["bytes"].as_u64()?.try_into().ok()?,
+            ty: value["type"].as_str()?.into(),
+        })
+    }
+}
+
+/// A storage type whose layout can be exported.
+pub trait StorageLayout: StorageType {
+    /// Appends the leaf fields of the type stored at `slot` and SDK `offset` to `fields`, labelled
+    /// with the path `label`.
+    fn layout(label: &str, slot: U256, offset: u8, fields: &mut Vec<Field>);
+}
+
+/// Places the fields of a struct like the `StorageType::new` generated by `sol_storage!`.
+pub struct Cursor {
+    root: U256,
+    slot: usize,
+    space: usize,
+}
+
+impl Cursor {
+    /// Starts placing the fields of the struct stored at `root`.
+    pub fn new(root: U256) -> Self {
+        Self {
+            root,
+            slot: 0,
+            space: 32,
+        }
+    }
+
+    /// Places the next field `name` of type `S`, and appends its leaf fields to `fields`.
+    pub fn field<S: StorageLayout>(&mut self, label: &str, name: &str, fields: &mut Vec<Field>) {
+        let (bytes, words) = (S::SLOT_BYTES, S::REQUIRED_SLOTS);
+        if self.space < bytes {
+            self.space = 32;
+            self.slot += 1;
+        }
+        self.space -= bytes;
+
+        let label = match label {
+            "" => name.into(),
+            _ => format!("{label}.{name}"),
+        };
+        let slot = self.root + U256::from(self.slot);
+        S::layout(&label, slot, self.space as u8, fields);
+        if words > 0 {
+            self.slot += words;
+            self.space = 32;
+        }
+    }
+}
+
+/// Appends the field of the storage type `S` that has no nested fields.
+fn leaf<S: StorageType>(label: &str, slot: U256, offset: u8, fields: &mut Vec<Field>) {
+    let bytes = match S::REQUIRED_SLOTS {
+        0 => S::SLOT_BYTES,
+        words => words * 32,
+    };
+    fields.push(Field {
+        label: label.into(),
+        slot,
+        offset: (32 - offset as usize).saturating_sub(bytes.min(32)) as u8,
+        bytes,
+        ty: short_type_name::<S>(),
+    });
+}
+
+/// Implements `StorageLayout` for SDK storage types without nested fields.
+macro_rules! impl_leaf {
+    ($([$($generics:tt)*] $ty:ty;)*) => {
+        $(
+            impl<$($generics)*> StorageLayout for $ty where $ty: StorageType {
+                fn layout(label: &str, slot: U256, offset: u8, fields: &mut Vec<Field>) {
+                    leaf::<Self>(label, slot, offset, fields);
+                }
+            }
+        )*
+    };
+}
+
+impl_leaf! {
+    [const B: usize, const L: usize] StorageUint<B, L>;
+    [const B: usize, const L: usize] StorageSigned<B, L>;
+    [const N: usize] StorageFixedBytes<N>;
+    [] StorageBool;
+    [] StorageAddress;
+    [] StorageBlockNumber;
+    [] StorageBlockHash;
+    [] StorageBytes;
+    [] StorageString;
+    [K: StorageKey, V: StorageType] StorageMap<K, V>;
+    [S: StorageType] StorageVec<S>;
+    [S: StorageType, const N: usize] StorageArray<S, N>;
+}
+
+/// Phantom fields take no storage
+impl<T> StorageLayout for PhantomData<T> {
+    fn layout(_label: &str, _slot: U256, _offset: u8, _fields: &mut Vec<Field>) {}
+}
+
+/// Returns the storage layout of the contract `S`.
+pub fn layout<S: StorageLayout>() -> Vec<Field> {
+    let mut fields = Vec::new();
+    S::layout("", U256::ZERO, 0, &mut fields);
+    fields
+}
+
+/// Returns the layout as JSON.
+pub fn to_json(fields: &[Field]) -> Value {
+    json!({ "storage": fields.iter().map(Field::to_json).collect::<Vec<_>>() })
+}
+
+/// Parses a layout written by [`to_json`].
+pub fn from_json(value: &Value) -> Result<Vec<Field>, String> {
+    value["storage"]
+        .as_array()
+        .ok_or("missing `storage` array")?
+        .iter()
+        .map(|field| Field::from_json(field).ok_or(format!("invalid field {field}")))
+        .collect()
+}
+
+/// Returns the reasons why the layout `new` cannot upgrade the layout `old`: fields of `old` that
+/// were removed, renamed, moved or changed type, and fields of `new` overlapping fields of `old`.
+pub fn compare(old: &[Field], new: &[Field]) -> Vec<String> {
+    let mut errors = Vec::new();
+    for field in old {
+        match new.iter().find(|other| other.label == field.label) {
+            None => errors.push(format!("`{}` was removed or renamed", field.label)),
+            Some(other) if other.ty != field.ty => errors.push(format!(
+                "`{}` changed type from {} to {}",
+                field.label, field.ty, other.ty
+            )),
+            Some(other) if other.span() != field.span() => errors.push(format!(
+                "`{}` moved from slot {} offset {} to slot {} offset {}",
+                field.label, field.slot, field.offset, other.slot, other.offset
+            )),
+            Some(_) => {}
+        }
+    }
+    for field in new
+        .iter()
+        .filter(|field| !old.iter().any(|other| other.label == field.label))
+    {
+        let (start, end) = field.span();
+        for other in old {
+            let (other_start, other_end) = other.span();
+            if start < other_end && other_start < end {
+                errors.push(format!(
+                    "`{}` overlaps `{}` at slot {}",
+                    field.label, other.label, field.slot
+                ));
+            }
+        }
+    }
+    errors
+}
+
+/// Runs the layout export of the contract `S` with the command line arguments:
+/// - without arguments, it prints the layout as JSON
+/// - with `--json <DIR>`, it writes the layout to `<DIR>/<NAME>.layout.json`
+/// - with `--check <FILE>`, it fails if the layout cannot upgrade the layout written to `FILE`
+pub fn main<S: StorageLayout>() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(err) = run::<S>(&args) {
+        eprintln!("error: {err}");
+        process::exit(1);
+    }
+}
+
+/// Runs the layout export of the contract `S` with `args` (see [`main`]).
+pub fn run<S: StorageLayout>(args: &[String]) -> Result<(), String> {
+    let fields = layout::<S>();
+    let json = to_json(&fields);
+    let mut printed = true;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let path = PathBuf::from(args.next().ok_or(format!("{arg} takes a path"))?);
+        printed = false;
+        match arg.as_str() {
+            "--json" => {
+                let name = short_type_name::<S>();
+                let name = name.split('<').next().unwrap_or_default();
+                super::write_json(&path.join(format!("{name}.layout.json")), &json)?;
+            }
+            "--check" => check(&path, &fields)?,
+            _ => {
+                return Err(format!(
+                    "unknown argument `{arg}`, expected --json or --check"
+                ))
+            }
+        }
+    }
+    if printed {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json).map_err(|err| err.to_string())?
+        );
+    }
+    Ok(())
+}
+
+/// Fails if `fields` cannot upgrade the layout written to `path`.
+fn check(path: &Path, fields: &[Field]) -> Result<(), String> {
+    let old = fs::read_to_string(path).map_err(|err| format!("{}: {err}", path.display()))?;
+    let old = serde_json::from_str(&old).map_err(|err| format!("{}: {err}", path.display()))?;
+    let errors = compare(&from_json(&old)?, fields);
+    if errors.is_empty() {
+        println!("The layout is compatible with {}", path.display());
+        Ok(())
+    } else {
+        Err(format!(
+            "the layout is incompatible with {}:\n{}",
+            path.display(),
+            errors.join("\n")
+        ))
+    }
+}
+
+/// Returns the name of the type `T` without module paths.
+fn short_type_name<T: ?Sized>() -> String {
+    let mut short = String::new();
+    for (index, part) in any::type_name::<T>().split("::").enumerate() {
+        // The identifier preceding `::` is a module
+        if index > 0 {
+            let module = short
+                .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .map_or(0, |index| index + 1);
+            short.truncate(module);
+        }
+        short.push_str(part);
+    }
+    short
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(label: &str, slot: u64, offset: u8, bytes: usize, ty: &str) -> Field {
+        Field {
+            label: label.into(),
+            slot: U256::from(slot),
+            offset,
+            bytes,
+            ty: ty.into(),
+        }
+    }
+
+    #[test]
+    fn test_short_type_name() {
+        assert_eq!(
+            short_type_name::<StorageMap<alloy_primitives::Address, StorageUint<256, 4>>>(),
+            "StorageMap<Address, StorageUint<256, 4>>"
+        );
+    }
+
+    #[cfg(all(feature = "erc20", not(feature = "erc7201")))]
+    #[test]
+    fn test_layout() {
+        use crate::tokens::erc20::{ERC20Params, ERC20};
+
+        struct Params;
+        impl ERC20Params for Params {
+            const NAME: &'static str = "Token";
+            const SYMBOL: &'static str = "TKN";
+            const DECIMALS: u8 = 18;
+        }
+
+        let fields = layout::<ERC20<Params>>();
+        let summary: Vec<(&str, u64, u8, usize)> = fields
+            .iter()
+            .map(|field| {
+                (
+                    field.label.as_str(),
+                    field.slot.to(),
+                    field.offset,
+                    field.bytes,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("total_supply", 0, 0, 32),
+                ("balances", 1, 0, 32),
+                ("allowances", 2, 0, 32),
+                ("nonces.nonces", 3, 0, 32),
+                ("nonces.nonce_bitmap", 4, 0, 32),
+                ("eip712.cached_domain_separator", 5, 0, 32),
+                // The chain id and address are packed in the same slot
+                ("eip712.cached_chain_id", 6, 0, 8),
+                ("eip712.cached_this", 6, 8, 20),
+                ("eip712.hashed_name", 7, 0, 32),
+            ]
+        );
+        assert_eq!(fields[0].ty, "StorageUint<256, 4>");
+        assert_eq!(from_json(&to_json(&fields)).unwrap(), fields);
+    }
+
+    #[cfg(all(feature = "erc20", feature = "erc7201"))]
+    #[test]
+    fn test_namespaced_layout() {
+        use crate::tokens::erc20::{ERC20Params, ERC20};
+
+        struct Params;
+        impl ERC20Params for Params {
+            const NAME: &'static str = "Token";
+            const SYMBOL: &'static str = "TKN";
+            const DECIMALS: u8 = 18;
+        }
+
+        // The token is placed from its namespace, and its EIP-712 domain and nonces stay in it
+        let root = U256::from_be_bytes(crate::erc7201_slot!("inkmate.storage.ERC20").0);
+        let fields = layout::<ERC20<Params>>();
+        let summary: Vec<(&str, U256)> = fields
+            .iter()
+            .map(|field| (field.label.as_str(), field.slot - root))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("total_supply", U256::ZERO),
+                ("balances", U256::from(1)),
+                ("allowances", U256::from(2)),
+                ("nonces.nonces", U256::from(3)),
+                ("nonces.nonce_bitmap", U256::from(4)),
+                ("eip712.cached_domain_separator", U256::from(5)),
+                ("eip712.cached_chain_id", U256::from(6)),
+                ("eip712.cached_this", U256::from(6)),
+                ("eip712.hashed_name", U256::from(7)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare() {
+        let old = [
+            field("owner", 0, 0, 20, "StorageAddress"),
+            field("paused", 0, 20, 1, "StorageBool"),
+            field(
+                "balances",
+                1,
+                0,
+                32,
+                "StorageMap<Address, StorageUint<256, 4>>",
+            ),
+        ];
+
+        // Appending fields and packing them after the existing ones is compatible
+        let mut new = old.to_vec();
+        new.push(field("fee", 0, 21, 2, "StorageUint<16, 1>"));
+        new.push(field("total", 2, 0, 32, "StorageUint<256, 4>"));
+        assert!(compare(&old, &new).is_empty());
+
+        let new = [
+            field("paused", 0, 0, 1, "StorageBool"),
+            field("owner", 0, 1, 20, "StorageAddress"),
+            field(
+                "balances",
+                1,
+                0,
+                32,
+                "StorageMap<Address, StorageUint<128, 2>>",
+            ),
+            field("fee", 0, 19, 2, "StorageUint<16, 1>"),
+        ];
+        assert_eq!(
+            compare(&old, &new),
+            [
+                "`owner` moved from slot 0 offset 0 to slot 0 offset 1",
+                "`paused` moved from slot 0 offset 20 to slot 0 offset 0",
+                "`balances` changed type from StorageMap<Address, StorageUint<256, 4>> to \
+                 StorageMap<Address, StorageUint<128, 2>>",
+                "`fee` overlaps `owner` at slot 0",
+                "`fee` overlaps `paused` at slot 0",
+            ]
+        );
+
+        assert_eq!(
+            compare(&old, &old[..2]),
+            ["`balances` was removed or renamed"]
+        );
+    }
+}
@@ -0,0 +1,28 @@
+//! Exports the ABI and the storage layout of a contract.
+//!
+//! - with `export-abi`, [`abi::main`] prints the Solidity interface of a contract or writes its JSON
+//!   ABI, and checks that its functions do not share selectors
+//! - with `export-layout`, [`layout::main`] writes the storage layout of a contract, or checks that
+//!   it can upgrade a previous layout
+
+#[cfg(feature = "export-abi")]
+pub mod abi;
+#[cfg(feature = "export-layout")]
+pub mod layout;
+#[cfg(feature = "export-abi")]
+pub mod selectors;
+
+use std::{fs, path::Path};
+
+/// Writes `value` to `path` as pretty JSON, creating its parent directories.
+fn write_json(path: &Path, value: &serde_json::Value) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("cannot create {}: {err}", parent.display()))?;
+    }
+    let mut json = serde_json::to_string_pretty(value).map_err(|err| err.to_string())?;
+    json.push('\n');
+    fs::write(path, json).map_err(|err| format!("cannot write {}: {err}", path.display()))?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
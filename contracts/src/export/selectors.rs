@@ -0,0 +1,126 @@
+//! Detects the functions of a contract that share a selector.
+//!
+//! The router generated by `#[external]` tries the methods of the contract before the ones of the
+//! contracts it `#[inherit]`s, so a method with the selector of an inherited method silently
+//! shadows it. Shadowing a method with the same signature is how overrides are written, but it
+//! also hides accidental redefinitions. Two functions with different signatures but the same
+//! selector always make one of them unreachable.
+
+use core::fmt;
+
+use super::abi::{dispatch_order, Interface, ItemKind};
+
+/// Functions of a contract sharing a selector
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Collision {
+    pub selector: [u8; 4],
+    /// The interfaces and signatures of the functions, in dispatch order so that only the first
+    /// one is called
+    pub functions: Vec<(String, String)>,
+}
+
+impl Collision {
+    /// Returns whether the functions have different signatures, rather than the first one
+    /// overriding the others.
+    pub fn is_clash(&self) -> bool {
+        self.functions
+            .iter()
+            .any(|(_, signature)| *signature != self.functions[0].1)
+    }
+}
+
+impl fmt::Display for Collision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (called, shadowed) = self
+            .functions
+            .split_first()
+            .expect("a collision has functions");
+        let (interface, signature) = called;
+        write!(
+            f,
+            "0x{}: {signature} in {interface}",
+            hex::encode(self.selector)
+        )?;
+        for (interface, signature) in shadowed {
+            write!(f, " shadows {signature} in {interface}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the selectors shared by several functions of `interfaces`, in dispatch order.
+pub fn collisions(interfaces: &[Interface]) -> Vec<Collision> {
+    let mut collisions: Vec<Collision> = Vec::new();
+    let functions = dispatch_order(interfaces)
+        .into_iter()
+        .flat_map(|interface| {
+            interface
+                .items
+                .iter()
+                .filter(|item| item.kind == ItemKind::Function)
+                .map(move |item| (interface.name.clone(), item))
+        });
+    for (interface, item) in functions {
+        let selector = item.selector();
+        match collisions
+            .iter_mut()
+            .find(|other| other.selector == selector)
+        {
+            Some(collision) => collision.functions.push((interface, item.signature())),
+            None => collisions.push(Collision {
+                selector,
+                functions: vec![(interface, item.signature())],
+            }),
+        }
+    }
+    collisions.retain(|collision| collision.functions.len() > 1);
+    collisions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::abi::parse_interfaces;
+
+    #[test]
+    fn test_collisions() {
+        let source = "
+            interface IToken {
+                function balanceOf(address owner) external view returns (uint256);
+                function transferFrom(address from, address to, uint256 amount) external returns (bool);
+            }
+            interface ITokenMock is IToken {
+                function gasprice_bit_ether(int128 x) external;
+                function balanceOf(address owner) external view returns (uint256);
+            }
+        ";
+        let collisions = collisions(&parse_interfaces(source).unwrap());
+        assert_eq!(collisions.len(), 2);
+
+        // The override of the mock shadows the inherited method
+        assert!(!collisions[1].is_clash());
+        assert_eq!(
+            collisions[1].to_string(),
+            "0x70a08231: balanceOf(address) in ITokenMock shadows balanceOf(address) in IToken"
+        );
+
+        // Both signatures hash to 0x23b872dd, so transferFrom is unreachable
+        assert!(collisions[0].is_clash());
+        assert_eq!(
+            collisions[0].to_string(),
+            "0x23b872dd: gasprice_bit_ether(int128) in ITokenMock shadows \
+             transferFrom(address,address,uint256) in IToken"
+        );
+    }
+
+    #[test]
+    fn test_no_collisions() {
+        let source = "
+            interface IToken {
+                function balanceOf(address owner) external view returns (uint256);
+                function balanceOf(address owner, uint256 id) external view returns (uint256);
+            }
+        ";
+        assert!(collisions(&parse_interfaces(source).unwrap()).is_empty());
+    }
+}
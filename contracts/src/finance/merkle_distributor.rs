@@ -0,0 +1,138 @@
+//! Merkle airdrop distributor for ERC20 tokens.
+//! The logic was based off of: https://github.com/Uniswap/merkle-distributor/blob/master/contracts/MerkleDistributor.sol
+//!
+//! Each leaf of the merkle tree is `keccak256(abi.encodePacked(index, account, amount))`.
+//! Claimed indices are tracked in a bitmap, packing 256 claims per storage slot.
+//! The distributor must be funded with enough tokens to cover every claim.
+
+use alloc::vec::Vec;
+use core::{borrow::BorrowMut, marker::PhantomData};
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolType},
+    crypto::keccak,
+    evm,
+    prelude::*,
+};
+
+use crate::interfaces::ierc20::IERC20;
+use crate::utils::merkle_proof::{MerkleProof, MerkleProofTrait};
+
+pub trait MerkleDistributorParams {
+    /// The token distributed by the airdrop
+    const TOKEN: Address;
+    /// The merkle root of the airdrop claims
+    const MERKLE_ROOT: B256;
+}
+
+sol_storage! {
+    /// MerkleDistributor tracks which airdrop indices have been claimed
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.MerkleDistributor"))]
+    pub struct MerkleDistributor<T> {
+        /// Maps word index to a packed bitmap of claimed indices
+        mapping(uint256 => uint256) claimed_bitmap;
+        PhantomData<T> phantom;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when `amount` tokens are claimed for `account` at `index`.
+    event Claimed(uint256 index, address account, uint256 amount);
+
+    /// The claim at `index` was already made.
+    error AlreadyClaimed(uint256 index);
+    /// The merkle proof does not prove the claim.
+    error InvalidProof();
+    /// The token transfer failed.
+    error TransferFailed();
+}
+
+#[derive(SolidityError)]
+pub enum MerkleDistributorError {
+    AlreadyClaimed(AlreadyClaimed),
+    InvalidProof(InvalidProof),
+    TransferFailed(TransferFailed),
+}
+
+// Internal functions
+impl<T: MerkleDistributorParams> MerkleDistributor<T> {
+    /// Marks `index` as claimed.
+    fn _set_claimed(&mut self, index: U256) {
+        let word_index = index >> 8;
+        let bit = U256::from(1) << (index & U256::from(0xff)).to::<usize>();
+        let mut word = self.claimed_bitmap.setter(word_index);
+        let current = word.get();
+        word.set(current | bit);
+    }
+}
+
+// External functions
+#[external]
+impl<T: MerkleDistributorParams> MerkleDistributor<T> {
+    /// Returns the address of the token distributed by this contract.
+    pub fn token() -> Address {
+        T::TOKEN
+    }
+
+    /// Returns the merkle root of the claims.
+    pub fn merkle_root() -> B256 {
+        T::MERKLE_ROOT
+    }
+
+    /// Returns true if the claim at `index` has been made.
+    pub fn is_claimed(&self, index: U256) -> bool {
+        let word = self.claimed_bitmap.get(index >> 8);
+        let bit = U256::from(1) << (index & U256::from(0xff)).to::<usize>();
+        word & bit == bit
+    }
+
+    /// Claims `amount` tokens for `account` at `index`, given a merkle `proof`.
+    ///
+    /// Requirements:
+    /// - The claim at `index` must not have been made.
+    /// - `proof` must prove the claim is part of the merkle root.
+    ///
+    /// Emits a {Claimed} event.
+    pub fn claim<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        index: U256,
+        account: Address,
+        amount: U256,
+        proof: Vec<B256>,
+    ) -> Result<(), MerkleDistributorError> {
+        let distributor = storage.borrow_mut();
+        if distributor.is_claimed(index) {
+            return Err(MerkleDistributorError::AlreadyClaimed(AlreadyClaimed {
+                index,
+            }));
+        }
+
+        // Verify the merkle proof
+        let leaf = keccak(<sol! { (uint256, address, uint256) }>::encode_packed(&(
+            index, account, amount,
+        )));
+        let proof: Vec<[u8; 32]> = proof.iter().map(|node| node.0).collect();
+        if !MerkleProof::verify(&proof, T::MERKLE_ROOT.0, leaf.0) {
+            return Err(MerkleDistributorError::InvalidProof(InvalidProof {}));
+        }
+
+        // Mark it claimed and send the token
+        distributor._set_claimed(index);
+        let token = IERC20::new(T::TOKEN);
+        let success = token
+            .transfer(&mut *storage, account, amount)
+            .map_err(|_| MerkleDistributorError::TransferFailed(TransferFailed {}))?;
+        if !success {
+            return Err(MerkleDistributorError::TransferFailed(TransferFailed {}));
+        }
+
+        evm::log(Claimed {
+            index,
+            account,
+            amount,
+        });
+        Ok(())
+    }
+}
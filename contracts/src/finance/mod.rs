@@ -0,0 +1,13 @@
+//! Contracts for distributing and managing funds
+
+#[cfg(feature = "merkle-distributor")]
+pub mod merkle_distributor;
+
+#[cfg(feature = "payment-splitter")]
+pub mod payment_splitter;
+
+#[cfg(feature = "reward-distributor")]
+pub mod reward_distributor;
+
+#[cfg(feature = "streams")]
+pub mod streams;
@@ -0,0 +1,316 @@
+//! Splits ETH and ERC20 payments among a group of payees, proportionally to their shares.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/v4.9.0/contracts/finance/PaymentSplitter.sol
+//!
+//! Payments are not forwarded automatically. Each payee pulls what it is owed with `release`
+//! (for ETH) or `release_erc20` (for a given ERC20 token), which can be called by anyone.
+//! Tokens that rebase or charge fees on transfer are not supported.
+//!
+//! The payees are set by `_initialize_payees`, see [initialization](crate#initialization). Stylus
+//! contracts do not have a `receive` function, so ETH must be sent through the payable `deposit`
+//! method.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolCall},
+    call::RawCall,
+    contract, evm, msg,
+    prelude::*,
+};
+
+use crate::utils::fixed_point_math::full_mul_div;
+use crate::utils::safe_transfer::{safe_transfer, safe_transfer_eth};
+
+sol_storage! {
+    /// PaymentSplitter tracks the shares of each payee and the amounts released to them
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.PaymentSplitter"))]
+    pub struct PaymentSplitter {
+        /// Sum of the shares of all payees
+        uint256 total_shares;
+        /// Total amount of ETH released
+        uint256 total_released;
+        /// Maps account to its shares
+        mapping(address => uint256) shares;
+        /// Maps account to the amount of ETH released to it
+        mapping(address => uint256) released;
+        /// The payees, in the order they were added
+        address[] payees;
+        /// Maps token to the total amount released
+        mapping(address => uint256) erc20_total_released;
+        /// Maps token to the amount released to each account
+        mapping(address => mapping(address => uint256)) erc20_released;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when `account` is added as a payee with `shares`.
+    event PayeeAdded(address account, uint256 shares);
+    /// Emitted when `amount` of ETH is released to `to`.
+    event PaymentReleased(address to, uint256 amount);
+    /// Emitted when `amount` of ERC20 `token` is released to `to`.
+    event ERC20PaymentReleased(address indexed token, address to, uint256 amount);
+    /// Emitted when `amount` of ETH is received from `from`.
+    event PaymentReceived(address from, uint256 amount);
+
+    /// The payees have already been initialized.
+    error PaymentSplitterAlreadyInitialized();
+    /// The payees and shares have different lengths.
+    error PayeesLengthMismatch(uint256 payees, uint256 shares);
+    /// No payees were given.
+    error NoPayees();
+    /// The payee is the zero address.
+    error PayeeZeroAddress();
+    /// The payee was given zero shares.
+    error PayeeZeroShares(address account);
+    /// The payee has already been added.
+    error PayeeAlreadyHasShares(address account);
+    /// The account is not a payee.
+    error AccountHasNoShares(address account);
+    /// The account is not owed any payment.
+    error AccountNotDuePayment(address account);
+
+    function balanceOf(address account) external view returns (uint256);
+}
+
+#[derive(SolidityError)]
+pub enum PaymentSplitterError {
+    PaymentSplitterAlreadyInitialized(PaymentSplitterAlreadyInitialized),
+    PayeesLengthMismatch(PayeesLengthMismatch),
+    NoPayees(NoPayees),
+    PayeeZeroAddress(PayeeZeroAddress),
+    PayeeZeroShares(PayeeZeroShares),
+    PayeeAlreadyHasShares(PayeeAlreadyHasShares),
+    AccountHasNoShares(AccountHasNoShares),
+    AccountNotDuePayment(AccountNotDuePayment),
+}
+
+// Internal functions
+impl PaymentSplitter {
+    /// Adds each account of `payees` with the matching number of `shares`.
+    ///
+    /// Requirements:
+    /// - The payees must not have been initialized.
+    /// - `payees` and `shares` must be non-empty and have the same length.
+    /// - Payees must be unique, non-zero addresses with non-zero shares.
+    ///
+    /// Emits a {PayeeAdded} event for each payee.
+    pub fn _initialize_payees(
+        &mut self,
+        payees: &[Address],
+        shares: &[U256],
+    ) -> Result<(), PaymentSplitterError> {
+        if !self.payees.is_empty() {
+            return Err(PaymentSplitterError::PaymentSplitterAlreadyInitialized(
+                PaymentSplitterAlreadyInitialized {},
+            ));
+        }
+        if payees.len() != shares.len() {
+            return Err(PaymentSplitterError::PayeesLengthMismatch(
+                PayeesLengthMismatch {
+                    payees: U256::from(payees.len()),
+                    shares: U256::from(shares.len()),
+                },
+            ));
+        }
+        if payees.is_empty() {
+            return Err(PaymentSplitterError::NoPayees(NoPayees {}));
+        }
+        for (account, shares) in payees.iter().zip(shares) {
+            self._add_payee(*account, *shares)?;
+        }
+        Ok(())
+    }
+
+    /// Adds `account` as a payee with `shares`.
+    ///
+    /// Emits a {PayeeAdded} event.
+    fn _add_payee(&mut self, account: Address, shares: U256) -> Result<(), PaymentSplitterError> {
+        if account.is_zero() {
+            return Err(PaymentSplitterError::PayeeZeroAddress(PayeeZeroAddress {}));
+        }
+        if shares.is_zero() {
+            return Err(PaymentSplitterError::PayeeZeroShares(PayeeZeroShares {
+                account,
+            }));
+        }
+        if !self.shares.get(account).is_zero() {
+            return Err(PaymentSplitterError::PayeeAlreadyHasShares(
+                PayeeAlreadyHasShares { account },
+            ));
+        }
+
+        self.payees.push(account);
+        self.shares.insert(account, shares);
+        let total_shares = self.total_shares.get();
+        self.total_shares.set(total_shares + shares);
+        evm::log(PayeeAdded { account, shares });
+        Ok(())
+    }
+
+    /// Returns the amount owed to `account`, given the historical balance and the amount
+    /// already released to it. Nothing is owed before the payees are initialized.
+    fn _pending_payment(&self, account: Address, total_received: U256, released: U256) -> U256 {
+        // The share of a payee never exceeds `total_received`, so this only fails without shares
+        full_mul_div(
+            total_received,
+            self.shares.get(account),
+            self.total_shares.get(),
+        )
+        .unwrap_or_default()
+        .saturating_sub(released)
+    }
+
+    /// Requires that the `payment` owed to `account` is non-zero.
+    fn _require_due_payment(account: Address, payment: U256) -> Result<(), PaymentSplitterError> {
+        if payment.is_zero() {
+            return Err(PaymentSplitterError::AccountNotDuePayment(
+                AccountNotDuePayment { account },
+            ));
+        }
+        Ok(())
+    }
+
+    /// Requires that `account` is a payee.
+    fn _require_payee(&self, account: Address) -> Result<(), PaymentSplitterError> {
+        if self.shares.get(account).is_zero() {
+            return Err(PaymentSplitterError::AccountHasNoShares(
+                AccountHasNoShares { account },
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the balance of ERC20 `token` held by this contract.
+    fn _erc20_balance(token: Address) -> Result<U256, Vec<u8>> {
+        let call = balanceOfCall {
+            account: contract::address(),
+        };
+        let data = RawCall::new_static().call(token, &call.encode())?;
+        Ok(balanceOfCall::decode_returns(&data, true)
+            .map_err(|_| Vec::new())?
+            ._0)
+    }
+}
+
+// External functions
+#[external]
+impl PaymentSplitter {
+    /// Receives ETH to be split among the payees.
+    ///
+    /// Emits a {PaymentReceived} event.
+    #[payable]
+    pub fn deposit(&mut self) {
+        evm::log(PaymentReceived {
+            from: msg::sender(),
+            amount: msg::value(),
+        });
+    }
+
+    /// Returns the sum of the shares of all payees.
+    pub fn total_shares(&self) -> U256 {
+        self.total_shares.get()
+    }
+
+    /// Returns the total amount of ETH released.
+    pub fn total_released(&self) -> U256 {
+        self.total_released.get()
+    }
+
+    /// Returns the total amount of ERC20 `token` released.
+    #[selector(name = "totalReleased")]
+    pub fn total_released_erc20(&self, token: Address) -> U256 {
+        self.erc20_total_released.get(token)
+    }
+
+    /// Returns the shares of `account`.
+    pub fn shares(&self, account: Address) -> U256 {
+        self.shares.get(account)
+    }
+
+    /// Returns the amount of ETH released to `account`.
+    pub fn released(&self, account: Address) -> U256 {
+        self.released.get(account)
+    }
+
+    /// Returns the amount of ERC20 `token` released to `account`.
+    #[selector(name = "released")]
+    pub fn released_erc20(&self, token: Address, account: Address) -> U256 {
+        self.erc20_released.getter(token).get(account)
+    }
+
+    /// Returns the payee at `index`.
+    pub fn payee(&self, index: U256) -> Address {
+        self.payees.get(index).unwrap_or_default()
+    }
+
+    /// Returns the amount of ETH that can currently be released to `account`.
+    pub fn releasable(&self, account: Address) -> U256 {
+        let total_received = contract::balance() + self.total_released.get();
+        self._pending_payment(account, total_received, self.released.get(account))
+    }
+
+    /// Returns the amount of ERC20 `token` that can currently be released to `account`.
+    #[selector(name = "releasable")]
+    pub fn releasable_erc20(&self, token: Address, account: Address) -> Result<U256, Vec<u8>> {
+        let total_received = Self::_erc20_balance(token)? + self.erc20_total_released.get(token);
+        Ok(self._pending_payment(
+            account,
+            total_received,
+            self.erc20_released.getter(token).get(account),
+        ))
+    }
+
+    /// Releases the ETH owed to `account`.
+    ///
+    /// Requirements:
+    /// - `account` must be a payee that is owed a payment.
+    ///
+    /// Emits a {PaymentReleased} event.
+    pub fn release(&mut self, account: Address) -> Result<(), Vec<u8>> {
+        self._require_payee(account)?;
+        let payment = self.releasable(account);
+        Self::_require_due_payment(account, payment)?;
+
+        let total_released = self.total_released.get();
+        self.total_released.set(total_released + payment);
+        let released = self.released.get(account);
+        self.released.insert(account, released + payment);
+
+        safe_transfer_eth(account, payment)?;
+        evm::log(PaymentReleased {
+            to: account,
+            amount: payment,
+        });
+        Ok(())
+    }
+
+    /// Releases the ERC20 `token` owed to `account`.
+    ///
+    /// Requirements:
+    /// - `account` must be a payee that is owed a payment.
+    ///
+    /// Emits a {ERC20PaymentReleased} event.
+    #[selector(name = "release")]
+    pub fn release_erc20(&mut self, token: Address, account: Address) -> Result<(), Vec<u8>> {
+        self._require_payee(account)?;
+        let payment = self.releasable_erc20(token, account)?;
+        Self::_require_due_payment(account, payment)?;
+
+        let total_released = self.erc20_total_released.get(token);
+        self.erc20_total_released
+            .insert(token, total_released + payment);
+        let mut released = self.erc20_released.setter(token);
+        let account_released = released.get(account);
+        released.insert(account, account_released + payment);
+
+        safe_transfer(token, account, payment)?;
+        evm::log(ERC20PaymentReleased {
+            token,
+            to: account,
+            amount: payment,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,461 @@
+//! MasterChef-style distribution of a reward token to the stakers of multiple pools.
+//! The logic was based off of: https://github.com/sushiswap/masterchef/blob/master/contracts/MasterChefV2.sol
+//!
+//! `reward_per_second` reward tokens are emitted every second, and split between the pools in
+//! proportion to their allocation points. Within a pool, the rewards are split between stakers in
+//! proportion to their stake. Each pool tracks the rewards accrued per staked token since its
+//! creation (`acc_reward_per_share`), and each staker the part of it that was already accounted for
+//! (`reward_debt`), so that the pending reward of a staker is computed in constant time.
+//!
+//! The reward tokens are not minted: the inheriting contract must fund this contract with enough
+//! of them, and harvests revert when it runs out. `emergency_withdraw` always returns the stake,
+//! forfeiting the pending rewards. The inheriting contract exposes its own functions to add pools
+//! and change the emissions with `_add_pool`, `_set_pool` and `_set_reward_per_second`, gated by
+//! e.g. `Ownable::only_owner`. Staked tokens that charge fees on transfer are not supported.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256, U64},
+    alloy_sol_types::sol,
+    block, contract, evm, msg,
+    prelude::*,
+};
+
+use crate::utils::fixed_point_math::mul_div;
+use crate::utils::safe_transfer::{safe_transfer, safe_transfer_from};
+
+/// The precision of the rewards accrued per staked token.
+pub const ACC_REWARD_PRECISION: U256 = U256::from_limbs([1_000_000_000_000, 0, 0, 0]);
+
+/// Immutable definitions of the distributor
+pub trait RewardDistributorParams {
+    /// The ERC20 token distributed as rewards, which cannot be staked.
+    const REWARD_TOKEN: Address;
+}
+
+sol_storage! {
+    /// A pool of staked tokens and the rewards it has accrued
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct RewardPool {
+        address staked_token;
+        uint256 alloc_point;
+        uint256 total_staked;
+        uint256 acc_reward_per_share;
+        uint64 last_reward_time;
+    }
+
+    /// The stake of an account in a pool and the rewards already accounted for
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct StakerInfo {
+        uint256 amount;
+        uint256 reward_debt;
+    }
+
+    /// RewardDistributor stores the pools, the stakes and the emissions of the reward token
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.RewardDistributor"))]
+    pub struct RewardDistributor<T> {
+        /// The amount of reward tokens emitted per second
+        uint256 reward_per_second;
+        /// The sum of the allocation points of the pools
+        uint256 total_alloc_point;
+        /// The number of pools
+        uint256 pool_length;
+        /// Maps id to its pool
+        mapping(uint256 => RewardPool) pools;
+        /// Maps pool id and account to its stake
+        mapping(uint256 => mapping(address => StakerInfo)) stakers;
+        PhantomData<T> phantom;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when pool `pid` is added for `stakedToken` with `allocPoint` allocation points.
+    event PoolAdded(uint256 indexed pid, address indexed stakedToken, uint256 allocPoint);
+    /// Emitted when the allocation points of pool `pid` are set to `allocPoint`.
+    event PoolSet(uint256 indexed pid, uint256 allocPoint);
+    /// Emitted when the emissions are set to `rewardPerSecond` reward tokens per second.
+    event RewardPerSecondSet(uint256 rewardPerSecond);
+    /// Emitted when `user` stakes `amount` tokens in pool `pid`.
+    event Deposit(address indexed user, uint256 indexed pid, uint256 amount);
+    /// Emitted when `user` unstakes `amount` tokens from pool `pid`.
+    event Withdraw(address indexed user, uint256 indexed pid, uint256 amount);
+    /// Emitted when `user` unstakes `amount` tokens from pool `pid` without their rewards.
+    event EmergencyWithdraw(address indexed user, uint256 indexed pid, uint256 amount);
+    /// Emitted when `user` receives `amount` reward tokens from pool `pid`.
+    event Harvest(address indexed user, uint256 indexed pid, uint256 amount);
+
+    /// The pool `pid` does not exist.
+    error PoolDoesNotExist(uint256 pid);
+    /// The `token` cannot be staked (ex. the reward token).
+    error InvalidStakedToken(address token);
+    /// `amount` exceeds the `staked` amount of the caller in pool `pid`.
+    error WithdrawExceedsStake(uint256 pid, uint256 amount, uint256 staked);
+    /// The reward accounting has overflowed.
+    error RewardOverflow();
+}
+
+#[derive(SolidityError)]
+pub enum RewardDistributorError {
+    PoolDoesNotExist(PoolDoesNotExist),
+    InvalidStakedToken(InvalidStakedToken),
+    WithdrawExceedsStake(WithdrawExceedsStake),
+    RewardOverflow(RewardOverflow),
+}
+
+// Internal functions
+impl<T: RewardDistributorParams> RewardDistributor<T> {
+    /// Adds a pool of `staked_token` with `alloc_point` allocation points, and returns its id.
+    /// Updates every pool first, so that the change does not apply retroactively.
+    ///
+    /// Requirements:
+    /// - `staked_token` cannot be the zero address or the reward token.
+    ///
+    /// Emits a {PoolAdded} event.
+    pub fn _add_pool(
+        &mut self,
+        staked_token: Address,
+        alloc_point: U256,
+    ) -> Result<U256, RewardDistributorError> {
+        if staked_token.is_zero() || staked_token == T::REWARD_TOKEN {
+            return Err(RewardDistributorError::InvalidStakedToken(
+                InvalidStakedToken {
+                    token: staked_token,
+                },
+            ));
+        }
+        self.mass_update_pools()?;
+
+        let pid = self.pool_length.get();
+        self.pool_length.set(pid + U256::from(1));
+        self.total_alloc_point
+            .set(self.total_alloc_point.get() + alloc_point);
+        let mut pool = self.pools.setter(pid);
+        pool.staked_token.set(staked_token);
+        pool.alloc_point.set(alloc_point);
+        pool.last_reward_time.set(U64::from(block::timestamp()));
+        evm::log(PoolAdded {
+            pid,
+            stakedToken: staked_token,
+            allocPoint: alloc_point,
+        });
+        Ok(pid)
+    }
+
+    /// Sets the allocation points of pool `pid` to `alloc_point`.
+    /// Updates every pool first, so that the change does not apply retroactively.
+    ///
+    /// Requirements:
+    /// - The pool must exist.
+    ///
+    /// Emits a {PoolSet} event.
+    pub fn _set_pool(
+        &mut self,
+        pid: U256,
+        alloc_point: U256,
+    ) -> Result<(), RewardDistributorError> {
+        self._require_pool(pid)?;
+        self.mass_update_pools()?;
+
+        let previous = self.pools.getter(pid).alloc_point.get();
+        self.total_alloc_point
+            .set(self.total_alloc_point.get() - previous + alloc_point);
+        self.pools.setter(pid).alloc_point.set(alloc_point);
+        evm::log(PoolSet {
+            pid,
+            allocPoint: alloc_point,
+        });
+        Ok(())
+    }
+
+    /// Sets the emissions to `reward_per_second` reward tokens per second.
+    /// Updates every pool first, so that the change does not apply retroactively.
+    ///
+    /// Emits a {RewardPerSecondSet} event.
+    pub fn _set_reward_per_second(
+        &mut self,
+        reward_per_second: U256,
+    ) -> Result<(), RewardDistributorError> {
+        self.mass_update_pools()?;
+        self.reward_per_second.set(reward_per_second);
+        evm::log(RewardPerSecondSet {
+            rewardPerSecond: reward_per_second,
+        });
+        Ok(())
+    }
+
+    /// Requires that pool `pid` exists.
+    fn _require_pool(&self, pid: U256) -> Result<(), RewardDistributorError> {
+        if pid >= self.pool_length.get() {
+            return Err(RewardDistributorError::PoolDoesNotExist(PoolDoesNotExist {
+                pid,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Returns the rewards accrued per staked token by pool `pid` until now.
+    fn _acc_reward_per_share(&self, pid: U256) -> Result<U256, RewardDistributorError> {
+        let pool = self.pools.getter(pid);
+        let acc_reward_per_share = pool.acc_reward_per_share.get();
+        let total_staked = pool.total_staked.get();
+        let total_alloc_point = self.total_alloc_point.get();
+        let elapsed = block::timestamp().saturating_sub(pool.last_reward_time.get().to());
+        if elapsed == 0 || total_staked.is_zero() || total_alloc_point.is_zero() {
+            return Ok(acc_reward_per_share);
+        }
+
+        let overflow = || RewardDistributorError::RewardOverflow(RewardOverflow {});
+        let emitted = self
+            .reward_per_second
+            .get()
+            .checked_mul(U256::from(elapsed))
+            .ok_or_else(overflow)?;
+        let reward =
+            mul_div(emitted, pool.alloc_point.get(), total_alloc_point).ok_or_else(overflow)?;
+        let per_share = mul_div(reward, ACC_REWARD_PRECISION, total_staked).ok_or_else(overflow)?;
+        acc_reward_per_share
+            .checked_add(per_share)
+            .ok_or_else(overflow)
+    }
+
+    /// Returns the rewards accounted for a stake of `amount` at `acc_reward_per_share`.
+    fn _accumulated(
+        amount: U256,
+        acc_reward_per_share: U256,
+    ) -> Result<U256, RewardDistributorError> {
+        mul_div(amount, acc_reward_per_share, ACC_REWARD_PRECISION)
+            .ok_or(RewardDistributorError::RewardOverflow(RewardOverflow {}))
+    }
+
+    /// Updates pool `pid`, sets the stake of `account` to `amount`, and returns the pending
+    /// reward of `account` before the update.
+    fn _set_stake(
+        &mut self,
+        pid: U256,
+        account: Address,
+        amount: U256,
+    ) -> Result<U256, RewardDistributorError> {
+        let acc_reward_per_share = self.update_pool(pid)?;
+        let stakers = self.stakers.getter(pid);
+        let staker = stakers.getter(account);
+        let (staked, reward_debt) = (staker.amount.get(), staker.reward_debt.get());
+        let pending = Self::_accumulated(staked, acc_reward_per_share)? - reward_debt;
+
+        let mut pool = self.pools.setter(pid);
+        let total_staked = pool.total_staked.get();
+        pool.total_staked.set(total_staked - staked + amount);
+        let reward_debt = Self::_accumulated(amount, acc_reward_per_share)?;
+        let mut stakers = self.stakers.setter(pid);
+        let mut staker = stakers.setter(account);
+        staker.amount.set(amount);
+        staker.reward_debt.set(reward_debt);
+        Ok(pending)
+    }
+
+    /// Sends `pending` reward tokens of pool `pid` to `account`.
+    ///
+    /// Emits a {Harvest} event if `pending` is not 0.
+    fn _harvest(account: Address, pid: U256, pending: U256) -> Result<(), Vec<u8>> {
+        if pending.is_zero() {
+            return Ok(());
+        }
+        safe_transfer(T::REWARD_TOKEN, account, pending)?;
+        evm::log(Harvest {
+            user: account,
+            pid,
+            amount: pending,
+        });
+        Ok(())
+    }
+}
+
+// External functions
+#[external]
+impl<T: RewardDistributorParams> RewardDistributor<T> {
+    /// Returns the reward token.
+    pub fn reward_token(&self) -> Address {
+        T::REWARD_TOKEN
+    }
+
+    /// Returns the amount of reward tokens emitted per second.
+    pub fn reward_per_second(&self) -> U256 {
+        self.reward_per_second.get()
+    }
+
+    /// Returns the sum of the allocation points of the pools.
+    pub fn total_alloc_point(&self) -> U256 {
+        self.total_alloc_point.get()
+    }
+
+    /// Returns the number of pools.
+    pub fn pool_length(&self) -> U256 {
+        self.pool_length.get()
+    }
+
+    /// Returns pool `pid` as
+    /// `(staked_token, alloc_point, total_staked, acc_reward_per_share, last_reward_time)`.
+    ///
+    /// Requirements:
+    /// - The pool must exist.
+    pub fn pool_info(
+        &self,
+        pid: U256,
+    ) -> Result<(Address, U256, U256, U256, u64), RewardDistributorError> {
+        self._require_pool(pid)?;
+        let pool = self.pools.getter(pid);
+        Ok((
+            pool.staked_token.get(),
+            pool.alloc_point.get(),
+            pool.total_staked.get(),
+            pool.acc_reward_per_share.get(),
+            pool.last_reward_time.get().to(),
+        ))
+    }
+
+    /// Returns the stake of `account` in pool `pid` and its reward debt.
+    pub fn user_info(&self, pid: U256, account: Address) -> (U256, U256) {
+        let stakers = self.stakers.getter(pid);
+        let staker = stakers.getter(account);
+        (staker.amount.get(), staker.reward_debt.get())
+    }
+
+    /// Returns the reward tokens that `account` can harvest from pool `pid`.
+    ///
+    /// Requirements:
+    /// - The pool must exist.
+    pub fn pending_reward(
+        &self,
+        pid: U256,
+        account: Address,
+    ) -> Result<U256, RewardDistributorError> {
+        self._require_pool(pid)?;
+        let acc_reward_per_share = self._acc_reward_per_share(pid)?;
+        let stakers = self.stakers.getter(pid);
+        let staker = stakers.getter(account);
+        let accumulated = Self::_accumulated(staker.amount.get(), acc_reward_per_share)?;
+        Ok(accumulated - staker.reward_debt.get())
+    }
+
+    /// Accrues the rewards of pool `pid` until now, and returns its rewards per staked token.
+    ///
+    /// Requirements:
+    /// - The pool must exist.
+    pub fn update_pool(&mut self, pid: U256) -> Result<U256, RewardDistributorError> {
+        self._require_pool(pid)?;
+        let acc_reward_per_share = self._acc_reward_per_share(pid)?;
+        let mut pool = self.pools.setter(pid);
+        pool.acc_reward_per_share.set(acc_reward_per_share);
+        pool.last_reward_time.set(U64::from(block::timestamp()));
+        Ok(acc_reward_per_share)
+    }
+
+    /// Accrues the rewards of every pool until now. The gas cost grows with the number of pools.
+    pub fn mass_update_pools(&mut self) -> Result<(), RewardDistributorError> {
+        let mut pid = U256::ZERO;
+        while pid < self.pool_length.get() {
+            self.update_pool(pid)?;
+            pid += U256::from(1);
+        }
+        Ok(())
+    }
+
+    /// Stakes `amount` tokens of the caller in pool `pid`, and sends them their pending reward.
+    /// An `amount` of 0 only harvests.
+    ///
+    /// Requirements:
+    /// - The pool must exist.
+    /// - The caller must have approved this contract to spend `amount` staked tokens.
+    /// - This contract must hold enough reward tokens to pay the pending reward.
+    ///
+    /// Emits a {Deposit} event, and a {Harvest} event if the pending reward is not 0.
+    pub fn deposit(&mut self, pid: U256, amount: U256) -> Result<(), Vec<u8>> {
+        self._require_pool(pid)?;
+        let account = msg::sender();
+        let staked = self.stakers.getter(pid).getter(account).amount.get();
+        let pending = self._set_stake(pid, account, staked + amount)?;
+
+        if !amount.is_zero() {
+            let staked_token = self.pools.getter(pid).staked_token.get();
+            safe_transfer_from(staked_token, account, contract::address(), amount)?;
+        }
+        Self::_harvest(account, pid, pending)?;
+        evm::log(Deposit {
+            user: account,
+            pid,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Unstakes `amount` tokens of the caller from pool `pid`, and sends them their pending
+    /// reward. An `amount` of 0 only harvests.
+    ///
+    /// Requirements:
+    /// - The pool must exist.
+    /// - `amount` must not exceed the stake of the caller.
+    /// - This contract must hold enough reward tokens to pay the pending reward.
+    ///
+    /// Emits a {Withdraw} event, and a {Harvest} event if the pending reward is not 0.
+    pub fn withdraw(&mut self, pid: U256, amount: U256) -> Result<(), Vec<u8>> {
+        self._require_pool(pid)?;
+        let account = msg::sender();
+        let staked = self.stakers.getter(pid).getter(account).amount.get();
+        if amount > staked {
+            return Err(
+                RewardDistributorError::WithdrawExceedsStake(WithdrawExceedsStake {
+                    pid,
+                    amount,
+                    staked,
+                })
+                .into(),
+            );
+        }
+        let pending = self._set_stake(pid, account, staked - amount)?;
+
+        if !amount.is_zero() {
+            let staked_token = self.pools.getter(pid).staked_token.get();
+            safe_transfer(staked_token, account, amount)?;
+        }
+        Self::_harvest(account, pid, pending)?;
+        evm::log(Withdraw {
+            user: account,
+            pid,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Unstakes all the tokens of the caller from pool `pid` without accruing or paying their
+    /// rewards, which are forfeited. Only for emergencies, as it works even when the reward
+    /// accounting fails or the contract runs out of reward tokens.
+    ///
+    /// Requirements:
+    /// - The pool must exist.
+    ///
+    /// Emits an {EmergencyWithdraw} event.
+    pub fn emergency_withdraw(&mut self, pid: U256) -> Result<(), Vec<u8>> {
+        self._require_pool(pid)?;
+        let account = msg::sender();
+        let amount = self.stakers.getter(pid).getter(account).amount.get();
+
+        let mut pool = self.pools.setter(pid);
+        let total_staked = pool.total_staked.get();
+        pool.total_staked.set(total_staked - amount);
+        let mut stakers = self.stakers.setter(pid);
+        let mut staker = stakers.setter(account);
+        staker.amount.set(U256::ZERO);
+        staker.reward_debt.set(U256::ZERO);
+
+        let staked_token = self.pools.getter(pid).staked_token.get();
+        safe_transfer(staked_token, account, amount)?;
+        evm::log(EmergencyWithdraw {
+            user: account,
+            pid,
+            amount,
+        });
+        Ok(())
+    }
+}
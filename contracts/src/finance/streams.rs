@@ -0,0 +1,331 @@
+//! Continuous ERC20 payment streams, where the deposit is unlocked to the recipient every second.
+//! The logic was based off of: https://github.com/sablierhq/sablier/blob/develop/packages/protocol/contracts/Sablier.sol
+//!
+//! The sender deposits the full amount upfront, and the recipient can withdraw what has been
+//! streamed so far at any time. Either party can cancel the stream, which pays out the streamed
+//! amount to the recipient and refunds the rest to the sender.
+//!
+//! The deposit must be a multiple of the stream duration, so that it is streamed at a whole
+//! `rate_per_second` without leaving any dust. Tokens that charge fees on transfer are not supported.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    block, contract, evm, msg,
+    prelude::*,
+};
+
+use crate::utils::safe_transfer::{safe_transfer, safe_transfer_from};
+
+sol_storage! {
+    /// A stream of `deposit` tokens from `sender` to `recipient` between `start_time` and `stop_time`
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct Stream {
+        address sender;
+        address recipient;
+        address token;
+        uint256 deposit;
+        uint256 rate_per_second;
+        uint256 remaining_balance;
+        uint256 start_time;
+        uint256 stop_time;
+    }
+
+    /// Streams stores every stream by id
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.Streams"))]
+    pub struct Streams {
+        /// The id of the last created stream
+        uint256 last_stream_id;
+        /// Maps id to its stream
+        mapping(uint256 => Stream) streams;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when stream `streamId` is created.
+    event CreateStream(uint256 indexed streamId, address indexed sender, address indexed recipient, uint256 deposit, address tokenAddress, uint256 startTime, uint256 stopTime);
+    /// Emitted when `amount` is withdrawn from stream `streamId` to `recipient`.
+    event WithdrawFromStream(uint256 indexed streamId, address indexed recipient, uint256 amount);
+    /// Emitted when stream `streamId` is cancelled and the balances are paid out.
+    event CancelStream(uint256 indexed streamId, address indexed sender, address indexed recipient, uint256 senderBalance, uint256 recipientBalance);
+
+    /// The stream does not exist.
+    error StreamDoesNotExist(uint256 streamId);
+    /// The caller is neither the sender nor the recipient of the stream.
+    error StreamUnauthorized(uint256 streamId, address caller);
+    /// The recipient is the zero address, this contract or the sender.
+    error StreamInvalidRecipient(address recipient);
+    /// The start time is in the past or the stop time is not after the start time.
+    error StreamInvalidTimes(uint256 startTime, uint256 stopTime);
+    /// The deposit is zero or not a multiple of the stream duration.
+    error StreamInvalidDeposit(uint256 deposit, uint256 duration);
+    /// The amount to withdraw is zero.
+    error StreamZeroAmount();
+    /// The amount to withdraw exceeds the balance of the recipient.
+    error StreamInsufficientBalance(uint256 balance, uint256 amount);
+}
+
+#[derive(SolidityError)]
+pub enum StreamsError {
+    StreamDoesNotExist(StreamDoesNotExist),
+    StreamUnauthorized(StreamUnauthorized),
+    StreamInvalidRecipient(StreamInvalidRecipient),
+    StreamInvalidTimes(StreamInvalidTimes),
+    StreamInvalidDeposit(StreamInvalidDeposit),
+    StreamZeroAmount(StreamZeroAmount),
+    StreamInsufficientBalance(StreamInsufficientBalance),
+}
+
+// Internal functions
+impl Streams {
+    /// Requires that stream `stream_id` exists.
+    fn _require_stream(&self, stream_id: U256) -> Result<(), StreamsError> {
+        if self.streams.getter(stream_id).sender.get().is_zero() {
+            return Err(StreamsError::StreamDoesNotExist(StreamDoesNotExist {
+                streamId: stream_id,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Requires that the caller is the sender or the recipient of stream `stream_id`.
+    fn _require_sender_or_recipient(&self, stream_id: U256) -> Result<(), StreamsError> {
+        self._require_stream(stream_id)?;
+        let stream = self.streams.getter(stream_id);
+        let caller = msg::sender();
+        if caller != stream.sender.get() && caller != stream.recipient.get() {
+            return Err(StreamsError::StreamUnauthorized(StreamUnauthorized {
+                streamId: stream_id,
+                caller,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Deletes stream `stream_id`.
+    fn _delete_stream(&mut self, stream_id: U256) {
+        let mut stream = self.streams.setter(stream_id);
+        stream.sender.set(Address::ZERO);
+        stream.recipient.set(Address::ZERO);
+        stream.token.set(Address::ZERO);
+        stream.deposit.set(U256::ZERO);
+        stream.rate_per_second.set(U256::ZERO);
+        stream.remaining_balance.set(U256::ZERO);
+        stream.start_time.set(U256::ZERO);
+        stream.stop_time.set(U256::ZERO);
+    }
+}
+
+// External functions
+#[external]
+impl Streams {
+    /// Creates a stream of `deposit` tokens from the caller to `recipient`, and returns its id.
+    /// The deposit is transferred from the caller to this contract.
+    ///
+    /// Requirements:
+    /// - `recipient` cannot be the zero address, this contract or the caller.
+    /// - `start_time` cannot be in the past and `stop_time` must be after `start_time`.
+    /// - `deposit` must be a non-zero multiple of the stream duration.
+    /// - The caller must have approved this contract to spend `deposit` tokens.
+    ///
+    /// Emits a {CreateStream} event.
+    pub fn create_stream(
+        &mut self,
+        recipient: Address,
+        deposit: U256,
+        token_address: Address,
+        start_time: U256,
+        stop_time: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let sender = msg::sender();
+        if recipient.is_zero() || recipient == contract::address() || recipient == sender {
+            return Err(
+                StreamsError::StreamInvalidRecipient(StreamInvalidRecipient { recipient }).into(),
+            );
+        }
+        if start_time < U256::from(block::timestamp()) || stop_time <= start_time {
+            return Err(StreamsError::StreamInvalidTimes(StreamInvalidTimes {
+                startTime: start_time,
+                stopTime: stop_time,
+            })
+            .into());
+        }
+        let duration = stop_time - start_time;
+        if deposit.is_zero() || deposit < duration || !(deposit % duration).is_zero() {
+            return Err(StreamsError::StreamInvalidDeposit(StreamInvalidDeposit {
+                deposit,
+                duration,
+            })
+            .into());
+        }
+
+        let stream_id = self.last_stream_id.get() + U256::from(1);
+        self.last_stream_id.set(stream_id);
+        let mut stream = self.streams.setter(stream_id);
+        stream.sender.set(sender);
+        stream.recipient.set(recipient);
+        stream.token.set(token_address);
+        stream.deposit.set(deposit);
+        stream.rate_per_second.set(deposit / duration);
+        stream.remaining_balance.set(deposit);
+        stream.start_time.set(start_time);
+        stream.stop_time.set(stop_time);
+
+        safe_transfer_from(token_address, sender, contract::address(), deposit)?;
+        evm::log(CreateStream {
+            streamId: stream_id,
+            sender,
+            recipient,
+            deposit,
+            tokenAddress: token_address,
+            startTime: start_time,
+            stopTime: stop_time,
+        });
+        Ok(stream_id)
+    }
+
+    /// Returns the stream `stream_id` as
+    /// `(sender, recipient, deposit, token, start_time, stop_time, remaining_balance, rate_per_second)`.
+    ///
+    /// Requirements:
+    /// - The stream must exist.
+    #[allow(clippy::type_complexity)]
+    pub fn get_stream(
+        &self,
+        stream_id: U256,
+    ) -> Result<(Address, Address, U256, Address, U256, U256, U256, U256), StreamsError> {
+        self._require_stream(stream_id)?;
+        let stream = self.streams.getter(stream_id);
+        Ok((
+            stream.sender.get(),
+            stream.recipient.get(),
+            stream.deposit.get(),
+            stream.token.get(),
+            stream.start_time.get(),
+            stream.stop_time.get(),
+            stream.remaining_balance.get(),
+            stream.rate_per_second.get(),
+        ))
+    }
+
+    /// Returns the number of seconds of stream `stream_id` that have elapsed.
+    ///
+    /// Requirements:
+    /// - The stream must exist.
+    pub fn delta_of(&self, stream_id: U256) -> Result<U256, StreamsError> {
+        self._require_stream(stream_id)?;
+        let stream = self.streams.getter(stream_id);
+        let now = U256::from(block::timestamp());
+        let start_time = stream.start_time.get();
+        if now <= start_time {
+            return Ok(U256::ZERO);
+        }
+        Ok(now.min(stream.stop_time.get()) - start_time)
+    }
+
+    /// Returns the amount of stream `stream_id` that `who` can currently withdraw or be refunded.
+    /// The balance is zero for anyone other than the sender and the recipient.
+    ///
+    /// Requirements:
+    /// - The stream must exist.
+    pub fn balance_of(&self, stream_id: U256, who: Address) -> Result<U256, StreamsError> {
+        let delta = self.delta_of(stream_id)?;
+        let stream = self.streams.getter(stream_id);
+        let withdrawn = stream.deposit.get() - stream.remaining_balance.get();
+        let recipient_balance = delta * stream.rate_per_second.get() - withdrawn;
+
+        if who == stream.recipient.get() {
+            Ok(recipient_balance)
+        } else if who == stream.sender.get() {
+            Ok(stream.remaining_balance.get() - recipient_balance)
+        } else {
+            Ok(U256::ZERO)
+        }
+    }
+
+    /// Withdraws `amount` from stream `stream_id` to its recipient.
+    /// The stream is deleted once its balance has been fully withdrawn.
+    ///
+    /// Requirements:
+    /// - The stream must exist.
+    /// - The caller must be the sender or the recipient of the stream.
+    /// - `amount` must be non-zero and not exceed the balance of the recipient.
+    ///
+    /// Emits a {WithdrawFromStream} event.
+    pub fn withdraw_from_stream(&mut self, stream_id: U256, amount: U256) -> Result<(), Vec<u8>> {
+        self._require_sender_or_recipient(stream_id)?;
+        if amount.is_zero() {
+            return Err(StreamsError::StreamZeroAmount(StreamZeroAmount {}).into());
+        }
+        let (recipient, token) = {
+            let stream = self.streams.getter(stream_id);
+            (stream.recipient.get(), stream.token.get())
+        };
+        let balance = self.balance_of(stream_id, recipient)?;
+        if balance < amount {
+            return Err(
+                StreamsError::StreamInsufficientBalance(StreamInsufficientBalance {
+                    balance,
+                    amount,
+                })
+                .into(),
+            );
+        }
+
+        let mut stream = self.streams.setter(stream_id);
+        let remaining_balance = stream.remaining_balance.get() - amount;
+        stream.remaining_balance.set(remaining_balance);
+        if remaining_balance.is_zero() {
+            self._delete_stream(stream_id);
+        }
+
+        safe_transfer(token, recipient, amount)?;
+        evm::log(WithdrawFromStream {
+            streamId: stream_id,
+            recipient,
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Cancels stream `stream_id`, paying out the streamed amount to the recipient and refunding
+    /// the rest to the sender.
+    ///
+    /// Requirements:
+    /// - The stream must exist.
+    /// - The caller must be the sender or the recipient of the stream.
+    ///
+    /// Emits a {CancelStream} event.
+    pub fn cancel_stream(&mut self, stream_id: U256) -> Result<(), Vec<u8>> {
+        self._require_sender_or_recipient(stream_id)?;
+        let (sender, recipient, token) = {
+            let stream = self.streams.getter(stream_id);
+            (
+                stream.sender.get(),
+                stream.recipient.get(),
+                stream.token.get(),
+            )
+        };
+        let sender_balance = self.balance_of(stream_id, sender)?;
+        let recipient_balance = self.balance_of(stream_id, recipient)?;
+        self._delete_stream(stream_id);
+
+        if !recipient_balance.is_zero() {
+            safe_transfer(token, recipient, recipient_balance)?;
+        }
+        if !sender_balance.is_zero() {
+            safe_transfer(token, sender, sender_balance)?;
+        }
+        evm::log(CancelStream {
+            streamId: stream_id,
+            sender,
+            recipient,
+            senderBalance: sender_balance,
+            recipientBalance: recipient_balance,
+        });
+        Ok(())
+    }
+}
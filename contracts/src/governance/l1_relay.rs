@@ -0,0 +1,143 @@
+//! Receiver executing on Arbitrum the calls relayed by an L1 governor through retryable tickets.
+//! The logic was based off of: https://github.com/ArbitrumFoundation/governance/blob/main/src/UpgradeExecutor.sol
+//!
+//! An L1 DAO (ex. its timelock) creates retryable tickets calling `relay` on the `L1Relay`, which
+//! checks that the caller is the alias of the L1 governor, and then calls the L2 target. L2
+//! contracts controlled by the DAO make the `L1Relay` their owner or admin (ex. the owner of a
+//! `ProxyAdmin`), so that they can only be changed by L1 proposals. The value of the ticket is
+//! forwarded to the target.
+//!
+//! The governor can only be changed by relaying a `setL1Governor(address)` call to the relay
+//! itself, so that the DAO can migrate to a new governor. Stylus contracts revert on reentrant
+//! calls, so `relay` executes calls to the relay in-process instead of calling it. The first
+//! governor is set by `_initialize`, see [initialization](crate#initialization). Until then, anyone
+//! can set the governor.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolCall},
+    contract, evm,
+    prelude::*,
+};
+
+use crate::arbitrum::crosschain::only_from_l1;
+use crate::utils::address::function_call_with_value;
+
+sol_storage! {
+    /// L1Relay executes the calls of an L1 governor on L2
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.L1Relay"))]
+    pub struct L1Relay {
+        /// The L1 contract allowed to relay calls, which calls through its alias
+        address l1_governor;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when the L1 governor is changed from `previousGovernor` to `newGovernor`.
+    event L1GovernorChanged(address indexed previousGovernor, address indexed newGovernor);
+    /// Emitted when a call to `target` with `value` wei and `data` is relayed.
+    event CallRelayed(address indexed target, uint256 value, bytes data);
+
+    /// The L1 governor is invalid (ex. the zero address).
+    error InvalidL1Governor(address governor);
+    /// The L1 governor has already been initialized.
+    error L1RelayAlreadyInitialized();
+    /// The call relayed to the relay itself is not a `setL1Governor` call.
+    error L1RelayUnsupportedCall();
+
+    /// Relayed to the relay itself to change the L1 governor to `newGovernor`.
+    function setL1Governor(address newGovernor);
+}
+
+#[derive(SolidityError)]
+pub enum L1RelayError {
+    InvalidL1Governor(InvalidL1Governor),
+    L1RelayAlreadyInitialized(L1RelayAlreadyInitialized),
+    L1RelayUnsupportedCall(L1RelayUnsupportedCall),
+}
+
+// Internal functions
+impl L1Relay {
+    /// Sets `l1_governor` as the L1 contract allowed to relay calls.
+    ///
+    /// Requirements:
+    /// - The governor must not have been initialized.
+    /// - `l1_governor` cannot be the zero address.
+    ///
+    /// Emits an {L1GovernorChanged} event.
+    pub fn _initialize(&mut self, l1_governor: Address) -> Result<(), L1RelayError> {
+        if !self.l1_governor.get().is_zero() {
+            return Err(L1RelayError::L1RelayAlreadyInitialized(
+                L1RelayAlreadyInitialized {},
+            ));
+        }
+        self._set_l1_governor(l1_governor)
+    }
+
+    /// Sets `l1_governor` as the L1 contract allowed to relay calls, without authorization guard.
+    ///
+    /// Requirements:
+    /// - `l1_governor` cannot be the zero address.
+    ///
+    /// Emits an {L1GovernorChanged} event.
+    pub fn _set_l1_governor(&mut self, l1_governor: Address) -> Result<(), L1RelayError> {
+        if l1_governor.is_zero() {
+            return Err(L1RelayError::InvalidL1Governor(InvalidL1Governor {
+                governor: l1_governor,
+            }));
+        }
+        let previous_governor = self.l1_governor.get();
+        self.l1_governor.set(l1_governor);
+        evm::log(L1GovernorChanged {
+            previousGovernor: previous_governor,
+            newGovernor: l1_governor,
+        });
+        Ok(())
+    }
+
+    /// Executes `data` relayed to the relay itself, which must be a `setL1Governor` call.
+    fn _relay_to_self(&mut self, data: &[u8]) -> Result<(), L1RelayError> {
+        let call = setL1GovernorCall::decode(data, true)
+            .map_err(|_| L1RelayError::L1RelayUnsupportedCall(L1RelayUnsupportedCall {}))?;
+        self._set_l1_governor(call.newGovernor)
+    }
+}
+
+// External functions
+#[external]
+impl L1Relay {
+    /// Calls `target` with `value` wei and `data`. The revert data of the target is bubbled up if
+    /// the call fails. If `target` is the relay itself, `data` must be a `setL1Governor` call,
+    /// which is executed in-process, and `value` stays in the relay.
+    ///
+    /// Requirements:
+    /// - The caller must be the alias of the L1 governor, through a retryable ticket.
+    /// - The relay must have a balance of at least `value`.
+    ///
+    /// Emits a {CallRelayed} event, and an {L1GovernorChanged} event for calls to the relay.
+    #[payable]
+    pub fn relay(&mut self, target: Address, value: U256, data: Bytes) -> Result<(), Vec<u8>> {
+        only_from_l1(self.l1_governor.get())?;
+        if target == contract::address() {
+            self._relay_to_self(&data)?;
+        } else {
+            function_call_with_value(target, &data, value)?;
+        }
+        evm::log(CallRelayed {
+            target,
+            value,
+            data: data.0,
+        });
+        Ok(())
+    }
+
+    /// Returns the L1 contract allowed to relay calls.
+    #[selector(name = "l1Governor")]
+    pub fn l1_governor(&self) -> Address {
+        self.l1_governor.get()
+    }
+}
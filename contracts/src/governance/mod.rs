@@ -0,0 +1,10 @@
+//! Governance contracts for on-chain voting and cross-chain execution
+
+#[cfg(feature = "l1-relay")]
+pub mod l1_relay;
+
+#[cfg(feature = "vote-escrow")]
+pub mod vote_escrow;
+
+#[cfg(feature = "votes")]
+pub mod votes;
@@ -0,0 +1,790 @@
+//! Vote-escrowed tokens: ERC20 tokens locked for a duration in exchange for decaying voting power.
+//! The logic was based off of: https://github.com/curvefi/curve-dao-contracts/blob/master/contracts/VotingEscrow.vy
+//!
+//! Accounts lock `TOKEN` until an unlock time of at most `MAX_LOCK` seconds in the future, rounded
+//! down to a whole `WEEK`. The voting power of a lock is `amount * (end - now) / MAX_LOCK`, so it
+//! decays linearly to 0 at the unlock time, and the longest locks get the most votes. Locks can be
+//! topped up with `increase_amount` and extended with `increase_unlock_time`, and the tokens can
+//! only be withdrawn once the lock has expired. The voting power cannot be transferred.
+//!
+//! Voting power is stored as checkpoints of a bias (the power at the checkpoint) and a slope (the
+//! power lost per second), for each account and for the total supply. Lock ends are scheduled in
+//! `slope_changes`, so the total supply at any time is computed by walking the weeks since the last
+//! global checkpoint. The walk is capped at 255 weeks, so the global checkpoint must be updated
+//! (ex. with `checkpoint`) at least every ~5 years.
+//!
+//! Checkpoints are keyed by timestamp, as `block::number` is an approximation of the L1 block
+//! number on Arbitrum chains. `get_votes`, `get_past_votes`, `get_past_total_supply` and the
+//! EIP-6372 clock make the contract usable as the voting token of a governor.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256, U64},
+    alloy_sol_types::sol,
+    block, contract, evm, msg,
+    prelude::*,
+};
+
+use crate::utils::safe_transfer::{safe_transfer, safe_transfer_from};
+
+/// The number of seconds in a week, to which unlock times are rounded down.
+pub const WEEK: u64 = 7 * 86_400;
+
+/// Immutable definitions of the vote escrow
+pub trait VoteEscrowParams {
+    /// The ERC20 token that is locked.
+    const TOKEN: Address;
+    /// The maximum duration of a lock in seconds, which gets 1 vote per token locked.
+    const MAX_LOCK: u64 = 4 * 365 * 86_400;
+}
+
+sol_storage! {
+    /// The voting power at a point in time, and the power lost per second after it
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct Point {
+        uint256 bias;
+        uint256 slope;
+        uint64 timestamp;
+    }
+
+    /// The amount locked by an account and its unlock time
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct LockedBalance {
+        uint256 amount;
+        uint64 end;
+    }
+
+    /// VoteEscrow stores the locks and the voting power checkpoints
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.VoteEscrow"))]
+    pub struct VoteEscrow<T> {
+        /// The total amount of tokens locked
+        uint256 supply;
+        /// Maps account to its lock
+        mapping(address => LockedBalance) locked;
+        /// The index of the latest global checkpoint
+        uint256 epoch;
+        /// Maps index to global checkpoint
+        mapping(uint256 => Point) point_history;
+        /// Maps account to the index of its latest checkpoint
+        mapping(address => uint256) user_point_epoch;
+        /// Maps account to its checkpoints, starting at index 1
+        mapping(address => mapping(uint256 => Point)) user_point_history;
+        /// Maps timestamp to the slope of the locks ending at that time
+        mapping(uint256 => uint256) slope_changes;
+        PhantomData<T> phantom;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when `provider` locks `value` more tokens, until `lockTime`.
+    event Deposit(address indexed provider, uint256 value, uint64 indexed lockTime, uint64 timestamp);
+    /// Emitted when `provider` withdraws `value` tokens from their expired lock.
+    event Withdraw(address indexed provider, uint256 value, uint64 timestamp);
+    /// Emitted when the total amount of tokens locked changes from `prevSupply` to `supply`.
+    event Supply(uint256 prevSupply, uint256 supply);
+
+    /// The amount locked cannot be 0.
+    error ZeroLockAmount();
+    /// `account` already has a lock, which must be withdrawn first.
+    error LockAlreadyExists(address account);
+    /// `account` has no lock.
+    error NoLock(address account);
+    /// The lock of `account` expired at `end`.
+    error LockExpired(address account, uint64 end);
+    /// The lock of `account` only expires at `end`.
+    error LockNotExpired(address account, uint64 end);
+    /// `unlockTime` must be in the future, after the current unlock time, and at most
+    /// `maxUnlockTime`, once rounded down to a whole week.
+    error InvalidUnlockTime(uint64 unlockTime, uint64 maxUnlockTime);
+    /// Lookup to future votes is not available.
+    error ERC5805FutureLookup(uint256 timepoint, uint256 clock);
+}
+
+#[derive(SolidityError)]
+pub enum VoteEscrowError {
+    ZeroLockAmount(ZeroLockAmount),
+    LockAlreadyExists(LockAlreadyExists),
+    NoLock(NoLock),
+    LockExpired(LockExpired),
+    LockNotExpired(LockNotExpired),
+    InvalidUnlockTime(InvalidUnlockTime),
+    ERC5805FutureLookup(ERC5805FutureLookup),
+}
+
+/// A copy of a `Point` in memory.
+#[derive(Clone, Copy, Default)]
+struct PointData {
+    bias: U256,
+    slope: U256,
+    timestamp: u64,
+}
+
+impl PointData {
+    /// Returns the voting power of the point at `timestamp`, which is not before the point.
+    fn value_at(&self, timestamp: u64) -> U256 {
+        let elapsed = U256::from(timestamp.saturating_sub(self.timestamp));
+        self.bias.saturating_sub(self.slope.saturating_mul(elapsed))
+    }
+}
+
+impl Point {
+    /// Returns a copy of the point in memory.
+    fn snapshot(&self) -> PointData {
+        PointData {
+            bias: self.bias.get(),
+            slope: self.slope.get(),
+            timestamp: self.timestamp.get().to(),
+        }
+    }
+
+    /// Overwrites the point with `point`.
+    fn record(&mut self, point: PointData) {
+        self.bias.set(point.bias);
+        self.slope.set(point.slope);
+        self.timestamp.set(U64::from(point.timestamp));
+    }
+}
+
+// Internal functions
+impl<T: VoteEscrowParams> VoteEscrow<T> {
+    /// Returns the checkpoint of a lock of `amount` ending at `end`, as of now.
+    fn lock_point(amount: U256, end: u64) -> PointData {
+        let now = block::timestamp();
+        let mut point = PointData {
+            timestamp: now,
+            ..Default::default()
+        };
+        if end > now && !amount.is_zero() {
+            point.slope = amount / U256::from(T::MAX_LOCK);
+            point.bias = point.slope * U256::from(end - now);
+        }
+        point
+    }
+
+    /// Records a global checkpoint, and a checkpoint of `account` unless it is the zero address,
+    /// whose lock changed from `old_lock` to `new_lock` (amount and unlock time).
+    fn _checkpoint(&mut self, account: Address, old_lock: (U256, u64), new_lock: (U256, u64)) {
+        let now = block::timestamp();
+        let (old_end, new_end) = (old_lock.1, new_lock.1);
+        let old_point = Self::lock_point(old_lock.0, old_end);
+        let new_point = Self::lock_point(new_lock.0, new_end);
+
+        // Walk the weeks since the last global checkpoint, applying the scheduled slope changes
+        let mut epoch = self.epoch.get();
+        let mut last_point = if epoch.is_zero() {
+            PointData {
+                timestamp: now,
+                ..Default::default()
+            }
+        } else {
+            self.point_history.getter(epoch).snapshot()
+        };
+        let mut t_i = last_point.timestamp / WEEK * WEEK;
+        for _ in 0..255 {
+            t_i += WEEK;
+            let mut d_slope = U256::ZERO;
+            if t_i > now {
+                t_i = now;
+            } else {
+                d_slope = self.slope_changes.get(U256::from(t_i));
+            }
+            last_point.bias = last_point.value_at(t_i);
+            last_point.slope = last_point.slope.saturating_sub(d_slope);
+            last_point.timestamp = t_i;
+            epoch += U256::from(1);
+            if t_i == now {
+                break;
+            }
+            self.point_history.setter(epoch).record(last_point);
+        }
+        self.epoch.set(epoch);
+
+        if account.is_zero() {
+            self.point_history.setter(epoch).record(last_point);
+            return;
+        }
+        last_point.slope = (last_point.slope + new_point.slope).saturating_sub(old_point.slope);
+        last_point.bias = (last_point.bias + new_point.bias).saturating_sub(old_point.bias);
+        self.point_history.setter(epoch).record(last_point);
+
+        // Reschedule the end of the lock
+        if old_end > now {
+            let key = U256::from(old_end);
+            let mut old_d_slope = self.slope_changes.get(key).saturating_sub(old_point.slope);
+            if new_end == old_end {
+                old_d_slope += new_point.slope;
+            }
+            self.slope_changes.insert(key, old_d_slope);
+        }
+        if new_end > now && new_end > old_end {
+            let key = U256::from(new_end);
+            let new_d_slope = self.slope_changes.get(key) + new_point.slope;
+            self.slope_changes.insert(key, new_d_slope);
+        }
+
+        let user_epoch = self.user_point_epoch.get(account) + U256::from(1);
+        self.user_point_epoch.insert(account, user_epoch);
+        self.user_point_history
+            .setter(account)
+            .setter(user_epoch)
+            .record(new_point);
+    }
+
+    /// Adds `value` tokens from `account` to its lock, and sets its unlock time to `unlock_time`.
+    ///
+    /// Emits a {Deposit} event and a {Supply} event.
+    fn _deposit_for(
+        &mut self,
+        account: Address,
+        value: U256,
+        unlock_time: u64,
+    ) -> Result<(), Vec<u8>> {
+        let lock = self.locked.getter(account);
+        let (old_amount, old_end) = (lock.amount.get(), lock.end.get().to::<u64>());
+        let new_amount = old_amount + value;
+        let new_end = if unlock_time == 0 {
+            old_end
+        } else {
+            unlock_time
+        };
+
+        let supply = self.supply.get();
+        self.supply.set(supply + value);
+        let mut lock = self.locked.setter(account);
+        lock.amount.set(new_amount);
+        lock.end.set(U64::from(new_end));
+        self._checkpoint(account, (old_amount, old_end), (new_amount, new_end));
+
+        if !value.is_zero() {
+            safe_transfer_from(T::TOKEN, account, contract::address(), value)?;
+        }
+        let now = block::timestamp();
+        evm::log(Deposit {
+            provider: account,
+            value,
+            lockTime: new_end,
+            timestamp: now,
+        });
+        evm::log(Supply {
+            prevSupply: supply,
+            supply: supply + value,
+        });
+        Ok(())
+    }
+
+    /// Returns `unlock_time` rounded down to a whole week.
+    ///
+    /// Requirements:
+    /// - The rounded unlock time must be after `current_end` and now.
+    /// - The rounded unlock time must be at most `MAX_LOCK` seconds from now.
+    fn _unlock_time(unlock_time: u64, current_end: u64) -> Result<u64, VoteEscrowError> {
+        let now = block::timestamp();
+        let rounded = unlock_time / WEEK * WEEK;
+        let max_unlock_time = now + T::MAX_LOCK;
+        if rounded <= now || rounded <= current_end || rounded > max_unlock_time {
+            return Err(VoteEscrowError::InvalidUnlockTime(InvalidUnlockTime {
+                unlockTime: unlock_time,
+                maxUnlockTime: max_unlock_time,
+            }));
+        }
+        Ok(rounded)
+    }
+
+    /// Returns the voting power of `account` at `timestamp`.
+    fn _balance_at(&self, account: Address, timestamp: u64) -> U256 {
+        let user_epoch = self.user_point_epoch.get(account);
+        let history = self.user_point_history.getter(account);
+        let timestamp_of = |epoch| history.getter(epoch).timestamp.get().to::<u64>();
+        match find_epoch(U256::from(1), user_epoch, timestamp, timestamp_of) {
+            Some(epoch) => history.getter(epoch).snapshot().value_at(timestamp),
+            None => U256::ZERO,
+        }
+    }
+
+    /// Returns the total voting power at `timestamp`.
+    fn _supply_at(&self, timestamp: u64) -> U256 {
+        let timestamp_of = |epoch| self.point_history.getter(epoch).timestamp.get().to::<u64>();
+        let Some(epoch) = find_epoch(U256::from(1), self.epoch.get(), timestamp, timestamp_of)
+        else {
+            return U256::ZERO;
+        };
+
+        // Walk the weeks from the checkpoint, applying the scheduled slope changes
+        let mut point = self.point_history.getter(epoch).snapshot();
+        let mut t_i = point.timestamp / WEEK * WEEK;
+        for _ in 0..255 {
+            t_i += WEEK;
+            let mut d_slope = U256::ZERO;
+            if t_i > timestamp {
+                t_i = timestamp;
+            } else {
+                d_slope = self.slope_changes.get(U256::from(t_i));
+            }
+            point.bias = point.value_at(t_i);
+            if t_i == timestamp {
+                break;
+            }
+            point.slope = point.slope.saturating_sub(d_slope);
+            point.timestamp = t_i;
+        }
+        point.bias
+    }
+
+    /// Returns the unlock time of the lock of `account`.
+    ///
+    /// Requirements:
+    /// - `account` must have a lock that has not expired.
+    fn _check_active_lock(&self, account: Address) -> Result<u64, VoteEscrowError> {
+        let lock = self.locked.getter(account);
+        let end = lock.end.get().to::<u64>();
+        if lock.amount.get().is_zero() {
+            return Err(VoteEscrowError::NoLock(NoLock { account }));
+        }
+        if end <= block::timestamp() {
+            return Err(VoteEscrowError::LockExpired(LockExpired { account, end }));
+        }
+        Ok(end)
+    }
+
+    /// Returns `timepoint` as a timestamp.
+    ///
+    /// Requirements:
+    /// - `timepoint` must be in the past.
+    fn _validate_timepoint(timepoint: U256) -> Result<u64, VoteEscrowError> {
+        let now = block::timestamp();
+        if timepoint >= U256::from(now) {
+            return Err(VoteEscrowError::ERC5805FutureLookup(ERC5805FutureLookup {
+                timepoint,
+                clock: U256::from(now),
+            }));
+        }
+        Ok(timepoint.to())
+    }
+}
+
+/// Returns the highest epoch in `[min, max]` whose checkpoint is at or before `timestamp`, or
+/// `None` if there is none. Checkpoints are sorted by timestamp.
+fn find_epoch(
+    mut min: U256,
+    mut max: U256,
+    timestamp: u64,
+    timestamp_of: impl Fn(U256) -> u64,
+) -> Option<U256> {
+    if max < min || timestamp_of(min) > timestamp {
+        return None;
+    }
+    while min < max {
+        let mid = (min + max + U256::from(1)) / U256::from(2);
+        if timestamp_of(mid) <= timestamp {
+            min = mid;
+        } else {
+            max = mid - U256::from(1);
+        }
+    }
+    Some(min)
+}
+
+// External functions
+#[external]
+impl<T: VoteEscrowParams> VoteEscrow<T> {
+    /// Locks `value` tokens of the caller until `unlock_time`, rounded down to a whole week.
+    ///
+    /// Requirements:
+    /// - `value` cannot be 0.
+    /// - The caller cannot have a lock, even an expired one.
+    /// - The rounded unlock time must be in the future, and at most `MAX_LOCK` seconds from now.
+    /// - The caller must have approved this contract to spend `value` tokens.
+    ///
+    /// Emits a {Deposit} event and a {Supply} event.
+    pub fn create_lock(&mut self, value: U256, unlock_time: u64) -> Result<(), Vec<u8>> {
+        let account = msg::sender();
+        if value.is_zero() {
+            return Err(VoteEscrowError::ZeroLockAmount(ZeroLockAmount {}).into());
+        }
+        if !self.locked.getter(account).amount.get().is_zero() {
+            return Err(VoteEscrowError::LockAlreadyExists(LockAlreadyExists { account }).into());
+        }
+        let unlock_time = Self::_unlock_time(unlock_time, 0)?;
+        self._deposit_for(account, value, unlock_time)
+    }
+
+    /// Adds `value` tokens of the caller to their lock, without changing its unlock time.
+    ///
+    /// Requirements:
+    /// - `value` cannot be 0.
+    /// - The caller must have a lock that has not expired.
+    /// - The caller must have approved this contract to spend `value` tokens.
+    ///
+    /// Emits a {Deposit} event and a {Supply} event.
+    pub fn increase_amount(&mut self, value: U256) -> Result<(), Vec<u8>> {
+        let account = msg::sender();
+        if value.is_zero() {
+            return Err(VoteEscrowError::ZeroLockAmount(ZeroLockAmount {}).into());
+        }
+        self._check_active_lock(account)?;
+        self._deposit_for(account, value, 0)
+    }
+
+    /// Extends the lock of the caller until `unlock_time`, rounded down to a whole week.
+    ///
+    /// Requirements:
+    /// - The caller must have a lock that has not expired.
+    /// - The rounded unlock time must be after the current one, and at most `MAX_LOCK` seconds
+    ///   from now.
+    ///
+    /// Emits a {Deposit} event and a {Supply} event.
+    pub fn increase_unlock_time(&mut self, unlock_time: u64) -> Result<(), Vec<u8>> {
+        let account = msg::sender();
+        let end = self._check_active_lock(account)?;
+        let unlock_time = Self::_unlock_time(unlock_time, end)?;
+        self._deposit_for(account, U256::ZERO, unlock_time)
+    }
+
+    /// Withdraws all the tokens of the expired lock of the caller.
+    ///
+    /// Requirements:
+    /// - The caller must have a lock that has expired.
+    ///
+    /// Emits a {Withdraw} event and a {Supply} event.
+    pub fn withdraw(&mut self) -> Result<(), Vec<u8>> {
+        let account = msg::sender();
+        let lock = self.locked.getter(account);
+        let (amount, end) = (lock.amount.get(), lock.end.get().to::<u64>());
+        if amount.is_zero() {
+            return Err(VoteEscrowError::NoLock(NoLock { account }).into());
+        }
+        let now = block::timestamp();
+        if now < end {
+            return Err(VoteEscrowError::LockNotExpired(LockNotExpired { account, end }).into());
+        }
+
+        let supply = self.supply.get();
+        self.supply.set(supply - amount);
+        let mut lock = self.locked.setter(account);
+        lock.amount.set(U256::ZERO);
+        lock.end.set(U64::ZERO);
+        self._checkpoint(account, (amount, end), (U256::ZERO, 0));
+
+        safe_transfer(T::TOKEN, account, amount)?;
+        evm::log(Withdraw {
+            provider: account,
+            value: amount,
+            timestamp: now,
+        });
+        evm::log(Supply {
+            prevSupply: supply,
+            supply: supply - amount,
+        });
+        Ok(())
+    }
+
+    /// Records a global checkpoint, which keeps the total supply computations short.
+    pub fn checkpoint(&mut self) {
+        self._checkpoint(Address::ZERO, (U256::ZERO, 0), (U256::ZERO, 0));
+    }
+
+    /// Returns the amount locked by `account` and its unlock time.
+    pub fn locked(&self, account: Address) -> (U256, u64) {
+        let lock = self.locked.getter(account);
+        (lock.amount.get(), lock.end.get().to())
+    }
+
+    /// Returns the total amount of tokens locked.
+    pub fn supply(&self) -> U256 {
+        self.supply.get()
+    }
+
+    /// Returns the current voting power of `account`.
+    pub fn balance_of(&self, account: Address) -> U256 {
+        self._balance_at(account, block::timestamp())
+    }
+
+    /// Returns the current total voting power.
+    pub fn total_supply(&self) -> U256 {
+        self._supply_at(block::timestamp())
+    }
+
+    /// Returns the current timestamp, which is the clock of the voting power checkpoints.
+    pub fn clock() -> u64 {
+        block::timestamp()
+    }
+
+    /// Returns a machine-readable description of the clock, as specified in EIP-6372.
+    #[selector(name = "CLOCK_MODE")]
+    pub fn clock_mode() -> String {
+        "mode=timestamp".into()
+    }
+
+    /// Returns the current amount of votes that `account` has.
+    pub fn get_votes(&self, account: Address) -> U256 {
+        self.balance_of(account)
+    }
+
+    /// Returns the amount of votes that `account` had at `timepoint`.
+    ///
+    /// Requirements:
+    /// - `timepoint` must be in the past.
+    pub fn get_past_votes(
+        &self,
+        account: Address,
+        timepoint: U256,
+    ) -> Result<U256, VoteEscrowError> {
+        let timestamp = Self::_validate_timepoint(timepoint)?;
+        Ok(self._balance_at(account, timestamp))
+    }
+
+    /// Returns the total supply of votes available at `timepoint`.
+    ///
+    /// Requirements:
+    /// - `timepoint` must be in the past.
+    pub fn get_past_total_supply(&self, timepoint: U256) -> Result<U256, VoteEscrowError> {
+        let timestamp = Self::_validate_timepoint(timepoint)?;
+        Ok(self._supply_at(timestamp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use inkmate_test::TestHost;
+    use stylus_sdk::alloy_sol_types::SolCall;
+
+    /// A token that accepts every transfer
+    const TOKEN: Address = Address::new([0x70; 20]);
+
+    struct EscrowParams;
+
+    impl VoteEscrowParams for EscrowParams {
+        const TOKEN: Address = TOKEN;
+        const MAX_LOCK: u64 = 4 * WEEK;
+    }
+
+    sol_storage! {
+        struct Escrow {
+            #[borrow]
+            VoteEscrow<EscrowParams> escrow;
+        }
+    }
+
+    unsafe impl TopLevelStorage for Escrow {}
+
+    #[external]
+    #[inherit(VoteEscrow<EscrowParams>)]
+    impl Escrow {}
+
+    sol! {
+        function createLock(uint256 value, uint64 unlockTime);
+        function increaseAmount(uint256 value);
+        function increaseUnlockTime(uint64 unlockTime);
+        function withdraw();
+        function locked(address account) returns (uint256, uint64);
+        function balanceOf(address account) returns (uint256);
+        function totalSupply() returns (uint256);
+        function getPastVotes(address account, uint256 timepoint) returns (uint256);
+    }
+
+    /// The locks are created at the start of week 10
+    const START: u64 = 10 * WEEK;
+
+    /// A lock of `AMOUNT` loses 100 votes per second
+    const AMOUNT: u64 = 100 * EscrowParams::MAX_LOCK;
+
+    fn setup() -> (TestHost, Address, Address) {
+        let mut host = TestHost::new();
+        host.set_code(TOKEN, |_, _| Ok(Vec::new()));
+        let escrow = host.deploy::<Escrow>();
+        host.set_block_timestamp(START);
+        (host, escrow, TestHost::address("alice"))
+    }
+
+    fn create_lock(
+        host: &mut TestHost,
+        escrow: Address,
+        account: Address,
+        unlock_time: u64,
+    ) -> Result<Vec<u8>, Vec<u8>> {
+        let create = createLockCall {
+            value: U256::from(AMOUNT),
+            unlockTime: unlock_time,
+        };
+        host.call(account, escrow, &create.encode())
+    }
+
+    fn balance_of(host: &mut TestHost, escrow: Address, account: Address) -> U256 {
+        host.call_sol(account, escrow, &balanceOfCall { account })
+            .unwrap()
+            ._0
+    }
+
+    fn total_supply(host: &mut TestHost, escrow: Address) -> U256 {
+        host.call_sol(escrow, escrow, &totalSupplyCall {})
+            .unwrap()
+            ._0
+    }
+
+    #[test]
+    fn test_create_lock() {
+        let (mut host, escrow, alice) = setup();
+
+        // The unlock time is rounded down to a whole week
+        create_lock(&mut host, escrow, alice, START + 2 * WEEK + 100).unwrap();
+
+        let locked = host.call_sol(alice, escrow, &lockedCall { account: alice });
+        let locked = locked.unwrap();
+        assert_eq!(
+            (locked._0, locked._1),
+            (U256::from(AMOUNT), START + 2 * WEEK)
+        );
+        let votes = U256::from(100 * 2 * WEEK);
+        assert_eq!(balance_of(&mut host, escrow, alice), votes);
+        assert_eq!(total_supply(&mut host, escrow), votes);
+        let event = host.take_logs()[0].decode::<Deposit>().unwrap();
+        assert_eq!((event.provider, event.lockTime), (alice, START + 2 * WEEK));
+    }
+
+    #[test]
+    fn test_create_lock_invalid() {
+        let (mut host, escrow, alice) = setup();
+        let create = createLockCall {
+            value: U256::ZERO,
+            unlockTime: START + WEEK,
+        };
+        let revert = host.call(alice, escrow, &create.encode()).unwrap_err();
+        let error = VoteEscrowError::ZeroLockAmount(ZeroLockAmount {});
+        assert_eq!(revert, Vec::<u8>::from(error));
+
+        // The unlock time rounds down to now, or is beyond the maximum lock
+        let max_unlock_time = START + EscrowParams::MAX_LOCK;
+        for unlock_time in [START + WEEK - 1, max_unlock_time + WEEK] {
+            let revert = create_lock(&mut host, escrow, alice, unlock_time).unwrap_err();
+            let error = VoteEscrowError::InvalidUnlockTime(InvalidUnlockTime {
+                unlockTime: unlock_time,
+                maxUnlockTime: max_unlock_time,
+            });
+            assert_eq!(revert, Vec::<u8>::from(error));
+        }
+
+        create_lock(&mut host, escrow, alice, max_unlock_time).unwrap();
+        let revert = create_lock(&mut host, escrow, alice, START + WEEK).unwrap_err();
+        let error = VoteEscrowError::LockAlreadyExists(LockAlreadyExists { account: alice });
+        assert_eq!(revert, Vec::<u8>::from(error));
+    }
+
+    #[test]
+    fn test_extend_lock() {
+        let (mut host, escrow, alice) = setup();
+        create_lock(&mut host, escrow, alice, START + WEEK).unwrap();
+
+        // The unlock time must be after the current one
+        let extend = increaseUnlockTimeCall {
+            unlockTime: START + 2 * WEEK - 1,
+        };
+        let revert = host.call(alice, escrow, &extend.encode()).unwrap_err();
+        let error = VoteEscrowError::InvalidUnlockTime(InvalidUnlockTime {
+            unlockTime: START + 2 * WEEK - 1,
+            maxUnlockTime: START + EscrowParams::MAX_LOCK,
+        });
+        assert_eq!(revert, Vec::<u8>::from(error));
+
+        let extend = increaseUnlockTimeCall {
+            unlockTime: START + 3 * WEEK,
+        };
+        host.call_sol(alice, escrow, &extend).unwrap();
+        assert_eq!(
+            balance_of(&mut host, escrow, alice),
+            U256::from(100 * 3 * WEEK)
+        );
+
+        // Topping up the lock doubles its slope
+        let increase = increaseAmountCall {
+            value: U256::from(AMOUNT),
+        };
+        host.call_sol(alice, escrow, &increase).unwrap();
+        let votes = U256::from(200 * 3 * WEEK);
+        assert_eq!(balance_of(&mut host, escrow, alice), votes);
+        assert_eq!(total_supply(&mut host, escrow), votes);
+    }
+
+    #[test]
+    fn test_lock_expiry() {
+        let (mut host, escrow, alice) = setup();
+        let bob = TestHost::address("bob");
+        create_lock(&mut host, escrow, alice, START + 2 * WEEK).unwrap();
+        create_lock(&mut host, escrow, bob, START + 4 * WEEK).unwrap();
+
+        // The votes decay linearly
+        host.set_block_timestamp(START + WEEK);
+        assert_eq!(balance_of(&mut host, escrow, alice), U256::from(100 * WEEK));
+        assert_eq!(
+            total_supply(&mut host, escrow),
+            U256::from(100 * WEEK + 100 * 3 * WEEK)
+        );
+        let revert = host.call(alice, escrow, &withdrawCall {}.encode());
+        let error = VoteEscrowError::LockNotExpired(LockNotExpired {
+            account: alice,
+            end: START + 2 * WEEK,
+        });
+        assert_eq!(revert.unwrap_err(), Vec::<u8>::from(error));
+
+        // The slope of the expired lock stops counting against the total supply
+        host.set_block_timestamp(START + 3 * WEEK);
+        assert_eq!(balance_of(&mut host, escrow, alice), U256::ZERO);
+        assert_eq!(total_supply(&mut host, escrow), U256::from(100 * WEEK));
+        let past_votes = getPastVotesCall {
+            account: alice,
+            timepoint: U256::from(START),
+        };
+        let past_votes = host.call_sol(alice, escrow, &past_votes).unwrap()._0;
+        assert_eq!(past_votes, U256::from(100 * 2 * WEEK));
+        let increase = increaseAmountCall {
+            value: U256::from(AMOUNT),
+        };
+        let revert = host.call(alice, escrow, &increase.encode()).unwrap_err();
+        let error = VoteEscrowError::LockExpired(LockExpired {
+            account: alice,
+            end: START + 2 * WEEK,
+        });
+        assert_eq!(revert, Vec::<u8>::from(error));
+
+        host.take_logs();
+        host.call_sol(alice, escrow, &withdrawCall {}).unwrap();
+        let locked = host.call_sol(alice, escrow, &lockedCall { account: alice });
+        let locked = locked.unwrap();
+        assert_eq!((locked._0, locked._1), (U256::ZERO, 0));
+        let event = host.take_logs()[0].decode::<Withdraw>().unwrap();
+        assert_eq!((event.provider, event.value), (alice, U256::from(AMOUNT)));
+        // The lock was withdrawn, so a new one can be created
+        create_lock(&mut host, escrow, alice, START + 4 * WEEK).unwrap();
+    }
+
+    #[test]
+    fn test_find_epoch() {
+        let timestamps = [0, 100, 200, 200, 300];
+        let timestamp_of = |epoch: U256| timestamps[epoch.to::<usize>()];
+        let find = |timestamp| find_epoch(U256::from(1), U256::from(4), timestamp, timestamp_of);
+        assert_eq!(find(99), None);
+        assert_eq!(find(100), Some(U256::from(1)));
+        assert_eq!(find(250), Some(U256::from(3)));
+        assert_eq!(find(1000), Some(U256::from(4)));
+        assert_eq!(
+            find_epoch(U256::from(1), U256::ZERO, 1000, timestamp_of),
+            None
+        );
+    }
+
+    #[test]
+    fn test_point_value_at() {
+        let point = PointData {
+            bias: U256::from(1000),
+            slope: U256::from(10),
+            timestamp: 50,
+        };
+        assert_eq!(point.value_at(50), U256::from(1000));
+        assert_eq!(point.value_at(80), U256::from(700));
+        assert_eq!(point.value_at(200), U256::ZERO);
+    }
+}
@@ -0,0 +1,355 @@
+//! Vote delegation and historical vote checkpoints shared by voting tokens, with an EIP-6372 clock.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/governance/utils/Votes.sol
+//!
+//! `Votes` keeps track of the delegate of each account, the voting power of each delegate and
+//! the total supply of voting units over time. It does not know how many voting units an account
+//! holds, so tokens (ex. `ERC20Votes` and `ERC721Votes`) must call `_transfer_voting_units` on
+//! every mint, transfer and burn, and pass the delegator's units to `_delegate`.
+//!
+//! Checkpoints are keyed by the clock configured in `VotesParams`, which defaults to the block
+//! number. On Arbitrum the block number is an approximation of the L1 block number, which is only
+//! updated every few seconds, so timestamp based clocks or the L2 block number of ArbSys (with the
+//! `precompiles` feature) are usually preferable.
+//!
+//! Accounts do not have voting power until they delegate, which can be to themselves.
+
+use alloc::string::String;
+use core::marker::PhantomData;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolType},
+    block,
+    crypto::keccak,
+    evm,
+    prelude::*,
+};
+
+#[cfg(feature = "precompiles")]
+use crate::arbitrum::precompiles::arb_block_number;
+use crate::utils::checkpoints::{
+    CheckpointUnorderedInsertion, CheckpointsError, Trace208, U208, U48,
+};
+use crate::utils::ecrecover::{EcRecoverTrait, PrecompileEcRecover};
+pub use crate::utils::nonces::InvalidAccountNonce;
+pub use crate::utils::nonces::InvalidUnorderedNonce;
+use crate::utils::nonces::NoncesError;
+use crate::utils::safe_cast::{
+    to_uint, SafeCastError, SafeCastOverflowedIntDowncast, SafeCastOverflowedIntToUint,
+    SafeCastOverflowedUintDowncast, SafeCastOverflowedUintToInt,
+};
+#[cfg(feature = "precompiles")]
+use crate::utils::strings::{Strings, StringsTrait};
+
+/// The clock used to key the vote checkpoints, as described in EIP-6372.
+pub enum Clock {
+    /// Checkpoints are keyed by block number.
+    BlockNumber,
+    /// Checkpoints are keyed by block timestamp.
+    Timestamp,
+    /// Checkpoints are keyed by the L2 block number of Arbitrum chains.
+    #[cfg(feature = "precompiles")]
+    ArbBlockNumber,
+}
+
+/// Immutable definitions of the vote tracking
+pub trait VotesParams {
+    /// The clock used to key the vote checkpoints.
+    const CLOCK: Clock = Clock::BlockNumber;
+}
+
+sol_storage! {
+    /// Votes tracks the delegated voting power of each account
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.Votes"))]
+    pub struct Votes<T> {
+        /// Maps account to its delegatee
+        mapping(address => address) delegatees;
+        /// Maps delegatee to its voting power history
+        mapping(address => Trace208) delegate_checkpoints;
+        /// History of the total supply of voting units
+        Trace208 total_checkpoints;
+        PhantomData<T> phantom;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when `delegator` changes their delegate from `fromDelegate` to `toDelegate`.
+    event DelegateChanged(address indexed delegator, address indexed fromDelegate, address indexed toDelegate);
+    /// Emitted when the voting power of `delegate` changes from `previousVotes` to `newVotes`.
+    event DelegateVotesChanged(address indexed delegate, uint256 previousVotes, uint256 newVotes);
+
+    /// The signature used has expired.
+    error VotesExpiredSignature(uint256 expiry);
+    /// The signature is invalid.
+    error ECDSAInvalidSignature();
+    /// Lookup to future votes is not available.
+    error ERC5805FutureLookup(uint256 timepoint, uint256 clock);
+    /// The current timepoint of the clock could not be read.
+    error VotesClockUnavailable();
+}
+
+#[derive(SolidityError)]
+pub enum VotesError {
+    CheckpointUnorderedInsertion(CheckpointUnorderedInsertion),
+    VotesExpiredSignature(VotesExpiredSignature),
+    ECDSAInvalidSignature(ECDSAInvalidSignature),
+    InvalidAccountNonce(InvalidAccountNonce),
+    ERC5805FutureLookup(ERC5805FutureLookup),
+    VotesClockUnavailable(VotesClockUnavailable),
+    InvalidUnorderedNonce(InvalidUnorderedNonce),
+    SafeCastOverflowedUintDowncast(SafeCastOverflowedUintDowncast),
+    SafeCastOverflowedIntToUint(SafeCastOverflowedIntToUint),
+    SafeCastOverflowedIntDowncast(SafeCastOverflowedIntDowncast),
+    SafeCastOverflowedUintToInt(SafeCastOverflowedUintToInt),
+}
+
+impl From<CheckpointsError> for VotesError {
+    fn from(err: CheckpointsError) -> Self {
+        match err {
+            CheckpointsError::CheckpointUnorderedInsertion(e) => {
+                Self::CheckpointUnorderedInsertion(e)
+            }
+        }
+    }
+}
+
+impl From<SafeCastError> for VotesError {
+    fn from(err: SafeCastError) -> Self {
+        match err {
+            SafeCastError::SafeCastOverflowedUintDowncast(e) => {
+                Self::SafeCastOverflowedUintDowncast(e)
+            }
+            SafeCastError::SafeCastOverflowedIntToUint(e) => Self::SafeCastOverflowedIntToUint(e),
+            SafeCastError::SafeCastOverflowedIntDowncast(e) => {
+                Self::SafeCastOverflowedIntDowncast(e)
+            }
+            SafeCastError::SafeCastOverflowedUintToInt(e) => Self::SafeCastOverflowedUintToInt(e),
+        }
+    }
+}
+
+impl From<NoncesError> for VotesError {
+    fn from(err: NoncesError) -> Self {
+        match err {
+            NoncesError::InvalidAccountNonce(e) => Self::InvalidAccountNonce(e),
+            NoncesError::InvalidUnorderedNonce(e) => Self::InvalidUnorderedNonce(e),
+        }
+    }
+}
+
+pub const DELEGATION_TYPEHASH: B256 =
+    crate::const_keccak!("Delegation(address delegatee,uint256 nonce,uint256 expiry)");
+
+// Internal functions
+impl<T: VotesParams> Votes<T> {
+    /// Returns the current timepoint of the configured clock.
+    ///
+    /// Requirements:
+    /// - The ArbSys precompile must be available when the clock is `Clock::ArbBlockNumber`.
+    pub fn _clock() -> Result<U48, VotesError> {
+        let timepoint = match T::CLOCK {
+            Clock::BlockNumber => block::number(),
+            Clock::Timestamp => block::timestamp(),
+            #[cfg(feature = "precompiles")]
+            Clock::ArbBlockNumber => arb_block_number()
+                .map_err(|_| VotesError::VotesClockUnavailable(VotesClockUnavailable {}))?,
+        };
+        Ok(U48::from(timepoint))
+    }
+
+    /// Returns the maximum supply of voting units, as votes are stored in 208 bits.
+    pub fn _max_supply() -> U256 {
+        U256::from(U208::MAX)
+    }
+
+    /// Returns the current total supply of voting units.
+    pub fn _total_supply(&self) -> U256 {
+        U256::from(self.total_checkpoints.latest())
+    }
+
+    /// Delegates all of `account`'s voting `units` to `delegatee`.
+    ///
+    /// Emits {DelegateChanged} and {DelegateVotesChanged} events.
+    pub fn _delegate(
+        &mut self,
+        account: Address,
+        delegatee: Address,
+        units: U256,
+    ) -> Result<(), VotesError> {
+        let old_delegate = self.delegatees.replace(account, delegatee);
+        evm::log(DelegateChanged {
+            delegator: account,
+            fromDelegate: old_delegate,
+            toDelegate: delegatee,
+        });
+        self._move_delegate_votes(old_delegate, delegatee, units)
+    }
+
+    /// Transfers, mints, or burns voting units.
+    /// To register a mint, `from` should be zero. To register a burn, `to` should be zero.
+    pub fn _transfer_voting_units(
+        &mut self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<(), VotesError> {
+        let delta: U208 = to_uint(amount)?;
+        if from.is_zero() {
+            let total = self.total_checkpoints.latest();
+            self.total_checkpoints
+                .push(Self::_clock()?, total + delta)?;
+        }
+        if to.is_zero() {
+            let total = self.total_checkpoints.latest();
+            self.total_checkpoints
+                .push(Self::_clock()?, total - delta)?;
+        }
+        self._move_delegate_votes(self.delegatees.get(from), self.delegatees.get(to), amount)
+    }
+
+    /// Moves delegated votes from one delegate to another.
+    ///
+    /// Emits {DelegateVotesChanged} events.
+    fn _move_delegate_votes(
+        &mut self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<(), VotesError> {
+        if from == to || amount.is_zero() {
+            return Ok(());
+        }
+        let delta: U208 = to_uint(amount)?;
+        if !from.is_zero() {
+            let mut checkpoints = self.delegate_checkpoints.setter(from);
+            let latest = checkpoints.latest();
+            let (old_votes, new_votes) = checkpoints.push(Self::_clock()?, latest - delta)?;
+            evm::log(DelegateVotesChanged {
+                delegate: from,
+                previousVotes: U256::from(old_votes),
+                newVotes: U256::from(new_votes),
+            });
+        }
+        if !to.is_zero() {
+            let mut checkpoints = self.delegate_checkpoints.setter(to);
+            let latest = checkpoints.latest();
+            let (old_votes, new_votes) = checkpoints.push(Self::_clock()?, latest + delta)?;
+            evm::log(DelegateVotesChanged {
+                delegate: to,
+                previousVotes: U256::from(old_votes),
+                newVotes: U256::from(new_votes),
+            });
+        }
+        Ok(())
+    }
+
+    /// Validates that `timepoint` is in the past and returns it as a checkpoint key.
+    fn _validate_timepoint(timepoint: U256) -> Result<U48, VotesError> {
+        let clock = Self::_clock()?;
+        if timepoint >= U256::from(clock) {
+            return Err(VotesError::ERC5805FutureLookup(ERC5805FutureLookup {
+                timepoint,
+                clock: U256::from(clock),
+            }));
+        }
+        Ok(U48::from(timepoint))
+    }
+
+    /// Returns the EIP-712 struct hash of a delegation to `delegatee`.
+    pub fn _delegation_struct_hash(delegatee: Address, nonce: U256, expiry: U256) -> B256 {
+        keccak(<sol! { (bytes32, address, uint256, uint256) }>::encode(&(
+            DELEGATION_TYPEHASH.0,
+            delegatee,
+            nonce,
+            expiry,
+        )))
+    }
+
+    /// Returns the signer of the delegation signed as `signed_hash`.
+    ///
+    /// Requirements:
+    /// - `expiry` must not have passed.
+    /// - The signature must be valid.
+    pub fn _recover_delegation_signer(
+        signed_hash: B256,
+        expiry: U256,
+        v: u8,
+        r: B256,
+        s: B256,
+    ) -> Result<Address, VotesError> {
+        if U256::from(block::timestamp()) > expiry {
+            return Err(VotesError::VotesExpiredSignature(VotesExpiredSignature {
+                expiry,
+            }));
+        }
+        let signer = Address::from_slice(
+            &PrecompileEcRecover::ecrecover(&signed_hash.0, v, &r.0, &s.0)
+                .map_err(|_| VotesError::ECDSAInvalidSignature(ECDSAInvalidSignature {}))?,
+        );
+        if signer.is_zero() {
+            return Err(VotesError::ECDSAInvalidSignature(ECDSAInvalidSignature {}));
+        }
+        Ok(signer)
+    }
+}
+
+// External functions
+#[external]
+impl<T: VotesParams> Votes<T> {
+    /// Returns the current timepoint of the clock used for the vote checkpoints.
+    /// The timepoint fits in a `uint48`, which has the same ABI encoding as the returned `uint64`.
+    pub fn clock() -> Result<u64, VotesError> {
+        Ok(Self::_clock()?.to())
+    }
+
+    /// Returns a machine-readable description of the clock, as specified in EIP-6372.
+    #[selector(name = "CLOCK_MODE")]
+    pub fn clock_mode() -> String {
+        match T::CLOCK {
+            Clock::BlockNumber => "mode=blocknumber&from=default".into(),
+            Clock::Timestamp => "mode=timestamp".into(),
+            #[cfg(feature = "precompiles")]
+            Clock::ArbBlockNumber => {
+                let mut mode = String::from("mode=blocknumber&from=eip155:");
+                mode.push_str(&Strings::to_decimal_string(U256::from(block::chainid())));
+                mode
+            }
+        }
+    }
+
+    /// Returns the current amount of votes that `account` has.
+    pub fn get_votes(&self, account: Address) -> U256 {
+        U256::from(self.delegate_checkpoints.getter(account).latest())
+    }
+
+    /// Returns the amount of votes that `account` had at `timepoint`.
+    ///
+    /// Requirements:
+    /// - `timepoint` must be in the past.
+    pub fn get_past_votes(&self, account: Address, timepoint: U256) -> Result<U256, VotesError> {
+        let key = Self::_validate_timepoint(timepoint)?;
+        Ok(U256::from(
+            self.delegate_checkpoints.getter(account).upper_lookup(key),
+        ))
+    }
+
+    /// Returns the total supply of votes available at `timepoint`.
+    ///
+    /// Requirements:
+    /// - `timepoint` must be in the past.
+    pub fn get_past_total_supply(&self, timepoint: U256) -> Result<U256, VotesError> {
+        let key = Self::_validate_timepoint(timepoint)?;
+        Ok(U256::from(self.total_checkpoints.upper_lookup(key)))
+    }
+
+    /// Returns the number of checkpoints of `account`.
+    pub fn num_checkpoints(&self, account: Address) -> u32 {
+        self.delegate_checkpoints.getter(account).length() as u32
+    }
+
+    /// Returns the delegate that `account` has chosen.
+    pub fn delegates(&self, account: Address) -> Address {
+        self.delegatees.get(account)
+    }
+}
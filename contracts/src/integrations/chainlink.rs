@@ -0,0 +1,278 @@
+//! Safe reads of Chainlink price feeds through `AggregatorV3Interface`.
+//! The interface was based off of: https://github.com/smartcontractkit/chainlink/blob/develop/contracts/src/v0.8/shared/interfaces/AggregatorV3Interface.sol
+//!
+//! `latestRoundData` returns the last answer of a feed without any guarantee that it is recent or
+//! sane: the round may be incomplete, the feed may have stopped updating, and the answer may be
+//! clamped to the `minAnswer`/`maxAnswer` of the aggregator during a crash. `PriceFeed::price`
+//! checks the round, rejects answers older than `max_age` seconds or outside of the bounds set by
+//! the contract, and normalizes the answer to 18 decimals so that prices from feeds with different
+//! decimals can be combined.
+//!
+//! On Arbitrum, feeds are not updated while the sequencer is down, and prices can be stale right
+//! after it restarts. Contracts should also call `check_sequencer_up` with the L2 sequencer uptime
+//! feed of the chain before reading prices.
+//!
+//! The calls return a {FeedCallFailed} error if the feed reverts or returns invalid data.
+
+use alloc::{string::String, vec::Vec};
+use stylus_sdk::{
+    alloy_primitives::{Address, I256, U256},
+    alloy_sol_types::{sol, SolCall},
+    block,
+    call::RawCall,
+    prelude::*,
+};
+
+// Define the interface of the feeds and the errors
+sol! {
+    /// The interface of Chainlink price feeds (aggregator proxies).
+    interface AggregatorV3Interface {
+        function decimals() external view returns (uint8);
+        function description() external view returns (string);
+        function version() external view returns (uint256);
+        function getRoundData(uint80 _roundId) external view returns (uint80 roundId, int256 answer, uint256 startedAt, uint256 updatedAt, uint80 answeredInRound);
+        function latestRoundData() external view returns (uint80 roundId, int256 answer, uint256 startedAt, uint256 updatedAt, uint80 answeredInRound);
+    }
+
+    /// The call to `feed` reverted or returned invalid data.
+    error FeedCallFailed(address feed);
+    /// The `answer` of `feed` is not positive.
+    error InvalidPrice(address feed, int256 answer);
+    /// The round `roundId` of `feed` has not been answered.
+    error IncompleteRound(address feed, uint80 roundId);
+    /// The answer of `feed` was last updated at `updatedAt`, more than `maxAge` seconds ago.
+    error StalePrice(address feed, uint256 updatedAt, uint256 maxAge);
+    /// The normalized `price` of `feed` is outside of [`minPrice`, `maxPrice`].
+    error PriceOutOfBounds(address feed, uint256 price, uint256 minPrice, uint256 maxPrice);
+    /// The L2 sequencer is down.
+    error SequencerDown();
+    /// The L2 sequencer restarted at `startedAt`, less than `gracePeriod` seconds ago.
+    error GracePeriodNotOver(uint256 startedAt, uint256 gracePeriod);
+}
+
+#[derive(SolidityError)]
+pub enum ChainlinkError {
+    FeedCallFailed(FeedCallFailed),
+    InvalidPrice(InvalidPrice),
+    IncompleteRound(IncompleteRound),
+    StalePrice(StalePrice),
+    PriceOutOfBounds(PriceOutOfBounds),
+    SequencerDown(SequencerDown),
+    GracePeriodNotOver(GracePeriodNotOver),
+}
+
+/// The number of decimals of normalized prices.
+pub const PRICE_DECIMALS: u8 = 18;
+
+/// A round of a price feed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundData {
+    /// The id of the round, which encodes the phase of the proxy in its highest 16 bits
+    pub round_id: u128,
+    /// The answer of the round, with the decimals of the feed
+    pub answer: I256,
+    /// The timestamp at which the round started
+    pub started_at: u64,
+    /// The timestamp at which the answer was last updated
+    pub updated_at: u64,
+    /// Deprecated, the round in which the answer was computed
+    pub answered_in_round: u128,
+}
+
+/// A price feed and the checks applied to its answers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceFeed {
+    /// The address of the feed (the aggregator proxy)
+    pub feed: Address,
+    /// The maximum age of answers in seconds, usually slightly above the heartbeat of the feed
+    pub max_age: u64,
+    /// The minimum accepted price, with 18 decimals
+    pub min_price: U256,
+    /// The maximum accepted price, with 18 decimals
+    pub max_price: U256,
+}
+
+impl PriceFeed {
+    /// Returns a feed accepting answers of at most `max_age` seconds, without bounds on the price.
+    pub fn new(feed: Address, max_age: u64) -> Self {
+        Self {
+            feed,
+            max_age,
+            min_price: U256::from(1),
+            max_price: U256::MAX,
+        }
+    }
+
+    /// Sets the bounds of the accepted prices, with 18 decimals. The bounds should be within the
+    /// `minAnswer` and `maxAnswer` of the aggregator, so that clamped answers are rejected.
+    pub fn with_bounds(mut self, min_price: U256, max_price: U256) -> Self {
+        self.min_price = min_price;
+        self.max_price = max_price;
+        self
+    }
+
+    /// Returns the latest price of the feed, with 18 decimals.
+    ///
+    /// Requirements:
+    /// - The latest round must be complete, with a positive answer.
+    /// - The answer must have been updated at most `max_age` seconds ago.
+    /// - The price must be within [`min_price`, `max_price`].
+    pub fn price(&self) -> Result<U256, ChainlinkError> {
+        let round = latest_round_data(self.feed)?;
+        let answer = check_round(self.feed, &round, self.max_age)?;
+        let price = normalize(answer, decimals(self.feed)?).ok_or(ChainlinkError::InvalidPrice(
+            InvalidPrice {
+                feed: self.feed,
+                answer: round.answer,
+            },
+        ))?;
+        check_bounds(self.feed, price, self.min_price, self.max_price)?;
+        Ok(price)
+    }
+}
+
+/// Returns the latest round of `feed`.
+pub fn latest_round_data(feed: Address) -> Result<RoundData, ChainlinkError> {
+    let returns = static_call(feed, &AggregatorV3Interface::latestRoundDataCall {})?;
+    Ok(RoundData {
+        round_id: returns.roundId,
+        answer: returns.answer,
+        started_at: returns.startedAt.saturating_to(),
+        updated_at: returns.updatedAt.saturating_to(),
+        answered_in_round: returns.answeredInRound,
+    })
+}
+
+/// Returns the round `round_id` of `feed`.
+pub fn round_data(feed: Address, round_id: u128) -> Result<RoundData, ChainlinkError> {
+    let call = AggregatorV3Interface::getRoundDataCall { _roundId: round_id };
+    let returns = static_call(feed, &call)?;
+    Ok(RoundData {
+        round_id: returns.roundId,
+        answer: returns.answer,
+        started_at: returns.startedAt.saturating_to(),
+        updated_at: returns.updatedAt.saturating_to(),
+        answered_in_round: returns.answeredInRound,
+    })
+}
+
+/// Returns the number of decimals of the answers of `feed`.
+pub fn decimals(feed: Address) -> Result<u8, ChainlinkError> {
+    Ok(static_call(feed, &AggregatorV3Interface::decimalsCall {})?._0)
+}
+
+/// Returns the description of `feed` (ex. "ETH / USD").
+pub fn description(feed: Address) -> Result<String, ChainlinkError> {
+    Ok(static_call(feed, &AggregatorV3Interface::descriptionCall {})?._0)
+}
+
+/// Returns the version of the aggregator of `feed`.
+pub fn version(feed: Address) -> Result<U256, ChainlinkError> {
+    Ok(static_call(feed, &AggregatorV3Interface::versionCall {})?._0)
+}
+
+/// Checks that `round` of `feed` is complete and at most `max_age` seconds old, and returns its
+/// positive answer.
+pub fn check_round(feed: Address, round: &RoundData, max_age: u64) -> Result<U256, ChainlinkError> {
+    if round.answer <= I256::ZERO {
+        return Err(ChainlinkError::InvalidPrice(InvalidPrice {
+            feed,
+            answer: round.answer,
+        }));
+    }
+    if round.updated_at == 0 || round.answered_in_round < round.round_id {
+        return Err(ChainlinkError::IncompleteRound(IncompleteRound {
+            feed,
+            roundId: round.round_id,
+        }));
+    }
+    if block::timestamp().saturating_sub(round.updated_at) > max_age {
+        return Err(ChainlinkError::StalePrice(StalePrice {
+            feed,
+            updatedAt: U256::from(round.updated_at),
+            maxAge: U256::from(max_age),
+        }));
+    }
+    Ok(round.answer.into_raw())
+}
+
+/// Checks that the normalized `price` of `feed` is within [`min_price`, `max_price`].
+pub fn check_bounds(
+    feed: Address,
+    price: U256,
+    min_price: U256,
+    max_price: U256,
+) -> Result<(), ChainlinkError> {
+    if price < min_price || price > max_price {
+        return Err(ChainlinkError::PriceOutOfBounds(PriceOutOfBounds {
+            feed,
+            price,
+            minPrice: min_price,
+            maxPrice: max_price,
+        }));
+    }
+    Ok(())
+}
+
+/// Checks that the L2 sequencer reported by `uptime_feed` is up, and has been for more than
+/// `grace_period` seconds, so that the price feeds had time to be updated after a restart.
+pub fn check_sequencer_up(uptime_feed: Address, grace_period: u64) -> Result<(), ChainlinkError> {
+    let round = latest_round_data(uptime_feed)?;
+    // The answer is 0 while the sequencer is up, and 1 while it is down
+    if !round.answer.is_zero() {
+        return Err(ChainlinkError::SequencerDown(SequencerDown {}));
+    }
+    if block::timestamp().saturating_sub(round.started_at) <= grace_period {
+        return Err(ChainlinkError::GracePeriodNotOver(GracePeriodNotOver {
+            startedAt: U256::from(round.started_at),
+            gracePeriod: U256::from(grace_period),
+        }));
+    }
+    Ok(())
+}
+
+/// Returns `answer` with `decimals` decimals scaled to 18 decimals, rounding down if `decimals`
+/// is greater than 18. Returns `None` if the result overflows.
+pub fn normalize(answer: U256, decimals: u8) -> Option<U256> {
+    if decimals <= PRICE_DECIMALS {
+        let scale = U256::from(10).pow(U256::from(PRICE_DECIMALS - decimals));
+        answer.checked_mul(scale)
+    } else {
+        // Any answer rounds down to 0 if the scale overflows
+        let scale = U256::from(10).checked_pow(U256::from(decimals - PRICE_DECIMALS));
+        Some(scale.map_or(U256::ZERO, |scale| answer / scale))
+    }
+}
+
+/// Static calls `feed` with `call`, and decodes its returned data.
+fn static_call<C: SolCall>(feed: Address, call: &C) -> Result<C::Return, ChainlinkError> {
+    let failed = || ChainlinkError::FeedCallFailed(FeedCallFailed { feed });
+    let data: Vec<u8> = RawCall::new_static()
+        .call(feed, &call.encode())
+        .map_err(|_| failed())?;
+    C::decode_returns(&data, true).map_err(|_| failed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let wad = U256::from(10).pow(U256::from(18));
+
+        // 8 decimals (USD feeds)
+        let answer = U256::from(300_012_345_678u64);
+        assert_eq!(
+            normalize(answer, 8),
+            Some(answer * U256::from(10_000_000_000u64))
+        );
+        // 18 decimals (ETH feeds)
+        assert_eq!(normalize(wad, 18), Some(wad));
+        // More than 18 decimals round down
+        assert_eq!(normalize(U256::from(1_999), 21), Some(U256::from(1)));
+        assert_eq!(normalize(U256::from(1), 255), Some(U256::ZERO));
+
+        assert_eq!(normalize(U256::MAX, 0), None);
+    }
+}
@@ -0,0 +1,4 @@
+//! Integrations with external protocols such as oracles
+
+#[cfg(feature = "chainlink")]
+pub mod chainlink;
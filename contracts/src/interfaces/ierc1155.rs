@@ -0,0 +1,21 @@
+//! Interfaces of the ERC-1155 standard, as defined in: https://eips.ethereum.org/EIPS/eip-1155
+
+use stylus_sdk::prelude::*;
+
+sol_interface! {
+    /// Allows calls to contracts implementing `IERC1155`.
+    interface IERC1155 {
+        function balanceOf(address account, uint256 id) external view returns (uint256);
+        function balanceOfBatch(address[] accounts, uint256[] ids) external view returns (uint256[]);
+        function setApprovalForAll(address operator, bool approved) external;
+        function isApprovedForAll(address account, address operator) external view returns (bool);
+        function safeTransferFrom(address from, address to, uint256 id, uint256 value, bytes data) external;
+        function safeBatchTransferFrom(address from, address to, uint256[] ids, uint256[] values, bytes data) external;
+    }
+
+    /// Allows calls to the receiver hooks of contracts implementing `IERC1155Receiver`.
+    interface IERC1155Receiver {
+        function onERC1155Received(address operator, address from, uint256 id, uint256 value, bytes data) external returns (bytes4);
+        function onERC1155BatchReceived(address operator, address from, uint256[] ids, uint256[] values, bytes data) external returns (bytes4);
+    }
+}
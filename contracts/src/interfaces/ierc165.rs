@@ -0,0 +1,10 @@
+//! Interface of the ERC-165 standard, as defined in: https://eips.ethereum.org/EIPS/eip-165
+
+use stylus_sdk::prelude::*;
+
+sol_interface! {
+    /// Allows querying the interfaces supported by contracts implementing `IERC165`.
+    interface IERC165 {
+        function supportsInterface(bytes4 interface_id) external view returns (bool);
+    }
+}
@@ -0,0 +1,26 @@
+//! Interfaces of the ERC-20 standard and the ERC-2612 permit extension, as defined in:
+//! https://eips.ethereum.org/EIPS/eip-20 and https://eips.ethereum.org/EIPS/eip-2612
+
+// The generated `permit` binding takes the call context and all 7 arguments of the method
+#![allow(clippy::too_many_arguments)]
+
+use stylus_sdk::prelude::*;
+
+sol_interface! {
+    /// Allows calls to contracts implementing `IERC20`.
+    interface IERC20 {
+        function totalSupply() external view returns (uint256);
+        function balanceOf(address account) external view returns (uint256);
+        function transfer(address to, uint256 value) external returns (bool);
+        function allowance(address owner, address spender) external view returns (uint256);
+        function approve(address spender, uint256 value) external returns (bool);
+        function transferFrom(address from, address to, uint256 value) external returns (bool);
+    }
+
+    /// Allows calls to the permit methods of contracts implementing `IERC20Permit`.
+    interface IERC20Permit {
+        function permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external;
+        function nonces(address owner) external view returns (uint256);
+        function DOMAIN_SEPARATOR() external view returns (bytes32);
+    }
+}
@@ -0,0 +1,27 @@
+//! Interface of the ERC-4626 tokenized vault standard, as defined in:
+//! https://eips.ethereum.org/EIPS/eip-4626
+
+use stylus_sdk::prelude::*;
+
+sol_interface! {
+    /// Allows calls to the vault methods of contracts implementing `IERC4626`.
+    /// The ERC-20 methods of the vault shares can be called through `IERC20`.
+    interface IERC4626 {
+        function asset() external view returns (address);
+        function totalAssets() external view returns (uint256);
+        function convertToShares(uint256 assets) external view returns (uint256);
+        function convertToAssets(uint256 shares) external view returns (uint256);
+        function maxDeposit(address receiver) external view returns (uint256);
+        function previewDeposit(uint256 assets) external view returns (uint256);
+        function deposit(uint256 assets, address receiver) external returns (uint256);
+        function maxMint(address receiver) external view returns (uint256);
+        function previewMint(uint256 shares) external view returns (uint256);
+        function mint(uint256 shares, address receiver) external returns (uint256);
+        function maxWithdraw(address owner) external view returns (uint256);
+        function previewWithdraw(uint256 assets) external view returns (uint256);
+        function withdraw(uint256 assets, address receiver, address owner) external returns (uint256);
+        function maxRedeem(address owner) external view returns (uint256);
+        function previewRedeem(uint256 shares) external view returns (uint256);
+        function redeem(uint256 shares, address receiver, address owner) external returns (uint256);
+    }
+}
@@ -0,0 +1,22 @@
+//! Interfaces of the ERC-721 standard, as defined in: https://eips.ethereum.org/EIPS/eip-721
+
+use stylus_sdk::prelude::*;
+
+sol_interface! {
+    /// Allows calls to contracts implementing `IERC721`.
+    interface IERC721 {
+        function balanceOf(address owner) external view returns (uint256);
+        function ownerOf(uint256 token_id) external view returns (address);
+        function safeTransferFrom(address from, address to, uint256 token_id, bytes data) external;
+        function transferFrom(address from, address to, uint256 token_id) external;
+        function approve(address to, uint256 token_id) external;
+        function setApprovalForAll(address operator, bool approved) external;
+        function getApproved(uint256 token_id) external view returns (address);
+        function isApprovedForAll(address owner, address operator) external view returns (bool);
+    }
+
+    /// Allows calls to the `onERC721Received` method of contracts implementing `IERC721Receiver`.
+    interface IERC721Receiver {
+        function onERC721Received(address operator, address from, uint256 token_id, bytes data) external returns (bytes4);
+    }
+}
@@ -0,0 +1,10 @@
+//! Canonical `sol_interface!` bindings for calling standard contracts
+//!
+//! `sol_interface!` cannot bind overloaded functions, so only the most general overload is
+//! included (ex. `safeTransferFrom` with a `data` argument, which can be empty).
+
+pub mod ierc1155;
+pub mod ierc165;
+pub mod ierc20;
+pub mod ierc4626;
+pub mod ierc721;
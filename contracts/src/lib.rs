@@ -1,21 +1,176 @@
 //! Building block contracts for Stylus
+//!
+//! # Initialization
+//!
+//! Stylus contracts do not have constructors. Modules that must be configured when the contract
+//! is deployed (ex. with an owner, an admin or the tokens of a pair) expose internal initialization
+//! functions instead, and the inheriting contract is responsible for calling them exactly once,
+//! right after the deployment, usually from an `init` or `initialize` method. Until then, the
+//! state they set is empty, so whoever calls an unguarded `initialize` method first takes control
+//! of the contract. Contracts deployed behind proxies or as clones can guard their `initialize`
+//! method with `proxy::initializable::Initializable`.
 
 // Conditional compilation attributes for no_std compatibility and ABI export features
-#![cfg_attr(not(feature = "export-abi"), no_main, no_std)]
+#![cfg_attr(
+    not(any(test, feature = "export-abi", feature = "export-layout")),
+    no_main,
+    no_std
+)]
 extern crate alloc;
 
+// Lets the code generated by `#[storage_layout]` and `#[namespace]` refer to `::inkmate` within
+// the crate
+#[cfg(any(feature = "export-layout", feature = "erc7201"))]
+extern crate self as inkmate;
+
 // Custom global allocator for the wasm32 target
 #[cfg(target_arch = "wasm32")]
 #[global_allocator]
 static ALLOC: mini_alloc::MiniAlloc = mini_alloc::MiniAlloc::INIT;
 
+// Minimal panic handler for `no_std` contracts, which traps without formatting the panic message so
+// that the `core::fmt` machinery is not linked into the WASM. Contracts linking `std` (or built with
+// `-Z build-std-features=panic_immediate_abort`) already have a panic handler and must not enable `tiny`.
+#[cfg(all(
+    feature = "tiny",
+    target_arch = "wasm32",
+    not(any(test, feature = "export-abi", feature = "export-layout"))
+))]
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    core::arch::wasm32::unreachable()
+}
+
 // Common utility contracts from the workspace
 extern crate inkmate_common;
 
+// Compile-time constant macros such as `const_keccak!`
+#[doc(hidden)]
+pub mod macros;
+
+// Account abstraction modules
+#[cfg(feature = "erc4337-account")]
+pub mod accounts;
+
+// Access control modules for privileged functions
+#[cfg(any(feature = "ownable", feature = "access-control"))]
+pub mod access;
+
+// Arbitrum precompile bindings and cross-chain helpers
+#[cfg(any(feature = "crosschain", feature = "precompiles"))]
+pub mod arbitrum;
+
+// DeFi building blocks such as AMM pairs and vault routers
+#[cfg(any(
+    feature = "amm-pair",
+    feature = "erc4626-router",
+    feature = "flash-lender",
+    feature = "interest"
+))]
+pub mod defi;
+
+// Governance building blocks such as vote tracking and L1 relays
+#[cfg(any(feature = "l1-relay", feature = "vote-escrow", feature = "votes"))]
+pub mod governance;
+
+// Integrations with external protocols such as Chainlink price feeds
+#[cfg(feature = "chainlink")]
+pub mod integrations;
+
+// Canonical interfaces for calling standard contracts
+#[cfg(feature = "interfaces")]
+pub mod interfaces;
+
+// Meta-transaction context and forwarder
+#[cfg(any(feature = "erc2771-context", feature = "forwarder"))]
+pub mod metatx;
+
+// Upgradeability contracts for use behind proxies
+#[cfg(any(feature = "initializable", feature = "proxy-admin", feature = "uups"))]
+pub mod proxy;
+
+// Security mixins such as emergency stops and rate limits
+#[cfg(any(
+    feature = "pausable",
+    feature = "rate-limit",
+    feature = "reentrancy-guard"
+))]
+pub mod security;
+
 // Conditional compilation of the ERC20 token module
-#[cfg(any(feature = "erc20", feature = "erc721"))]
+#[cfg(any(
+    feature = "dn404",
+    feature = "erc20",
+    feature = "erc20-rebasing",
+    feature = "erc721",
+    feature = "erc1155",
+    feature = "erc721-holder",
+    feature = "erc1155-holder",
+    feature = "erc2981"
+))]
 pub mod tokens;
 
+// Ready-to-deploy contracts combining the building blocks
+#[cfg(any(
+    feature = "erc20-preset",
+    feature = "erc721-drop",
+    feature = "erc1155-game-items"
+))]
+pub mod presets;
+
+// Funds distribution contracts
+#[cfg(any(
+    feature = "merkle-distributor",
+    feature = "payment-splitter",
+    feature = "reward-distributor",
+    feature = "streams"
+))]
+pub mod finance;
+
+// NFT sale and marketplace contracts
+#[cfg(any(
+    feature = "dutch-auction-mint",
+    feature = "fractional-vault",
+    feature = "listings"
+))]
+pub mod market;
+
+// Smart contract wallet building blocks
+#[cfg(feature = "erc1271")]
+pub mod wallet;
+
 // Utility functions and helpers used across the library
-#[cfg(any(feature = "erc20", feature = "ecrecover"))]
+#[cfg(any(
+    feature = "erc20",
+    feature = "ecrecover",
+    feature = "address",
+    feature = "base64",
+    feature = "bitmaps",
+    feature = "checkpoints",
+    feature = "clones",
+    feature = "commit-reveal",
+    feature = "create",
+    feature = "eip712",
+    feature = "eip712-derive",
+    feature = "excessively-safe-call",
+    feature = "fixed-point-math",
+    feature = "introspection",
+    feature = "merkle-proof",
+    feature = "metadata",
+    feature = "multicall",
+    feature = "nonces",
+    feature = "randomness",
+    feature = "safe-cast",
+    feature = "safe-transfer",
+    feature = "signature-checker",
+    feature = "sstore2",
+    feature = "strings",
+    feature = "structs",
+    feature = "svg",
+    feature = "twap-oracle"
+))]
 pub mod utils;
+
+// ABI and storage layout export of the contracts
+#[cfg(any(feature = "export-abi", feature = "export-layout"))]
+pub mod export;
@@ -0,0 +1,77 @@
+//! Macros computing constants at compile time, exported at the root of the crate.
+
+#[doc(hidden)]
+pub use stylus_sdk::{alloy_primitives::B256, keccak_const};
+
+/// Computes the keccak256 hash of the concatenation of the string `inputs` at compile time, as a
+/// `B256`. Used for EIP-712 typehashes, role identifiers and magic values, which would otherwise be
+/// hardcoded hashes that can silently get out of sync with their preimage.
+///
+/// ```ignore
+/// const MINTER_ROLE: B256 = const_keccak!("MINTER_ROLE");
+/// // Typehashes of structs referencing other structs append the referenced types
+/// const MAIL_TYPEHASH: B256 = const_keccak!(
+///     "Mail(Person from,Person to,string contents)",
+///     "Person(string name,address wallet)",
+/// );
+/// ```
+#[macro_export]
+macro_rules! const_keccak {
+    ($($input:expr),+ $(,)?) => {
+        $crate::macros::B256::new(
+            $crate::macros::keccak_const::Keccak256::new()
+                $(.update($input.as_bytes()))+
+                .finalize(),
+        )
+    };
+}
+
+/// Computes the ERC-7201 storage slot of the namespace `id` at compile time, as a `B256`. It is
+/// `keccak256(abi.encode(uint256(keccak256(id)) - 1)) & ~bytes32(uint256(0xff))`.
+///
+/// ```ignore
+/// const ERC20_SLOT: B256 = erc7201_slot!("inkmate.storage.ERC20");
+/// ```
+#[macro_export]
+macro_rules! erc7201_slot {
+    ($id:expr) => {
+        $crate::macros::B256::new($crate::macros::erc7201($id))
+    };
+}
+
+#[doc(hidden)]
+pub const fn erc7201(id: &str) -> [u8; 32] {
+    let mut hash = keccak_const::Keccak256::new()
+        .update(id.as_bytes())
+        .finalize();
+    let mut i = hash.len();
+    while i > 0 {
+        i -= 1;
+        let (byte, borrow) = hash[i].overflowing_sub(1);
+        hash[i] = byte;
+        if !borrow {
+            break;
+        }
+    }
+    let mut slot = keccak_const::Keccak256::new().update(&hash).finalize();
+    slot[31] = 0;
+    slot
+}
+
+#[cfg(test)]
+mod tests {
+    use stylus_sdk::alloy_primitives::b256;
+
+    #[test]
+    fn test_erc7201_slot() {
+        // The example of the ERC, and the namespace of OpenZeppelin's ERC20
+        assert_eq!(
+            erc7201_slot!("example.main"),
+            b256!("183a6125c38840424c4a85fa12bab2ab606c4b6d0e7cc73c0c06ba5300eab500")
+        );
+        assert_eq!(
+            erc7201_slot!("openzeppelin.storage.ERC20"),
+            b256!("52c63247e1f47db19d5ce0460030c497f067ca4cebf71ba98eeadabe20bace00")
+        );
+    }
+}
@@ -0,0 +1,329 @@
+//! Dutch auction mint for ERC721 collections, with an optional rebate at the clearing price.
+//! The logic was based off of: https://etherscan.io/address/0xed5af388653567af2f388e6224dc7c4b3241c544#code
+//!
+//! The mint price starts at `START_PRICE` and decays to `RESTING_PRICE` over `DURATION` seconds,
+//! either linearly or in steps of `DROP_INTERVAL` seconds. Minters pay the current price, and any
+//! excess value is refunded immediately.
+//!
+//! When `REBATE` is enabled, every minter ends up paying the clearing price, which is the price of
+//! the last mint if the collection sells out, or the resting price otherwise. Once the auction is
+//! settled, minters claim the difference with `claim_rebate` and the owner can withdraw the
+//! proceeds. Without rebates, the owner can withdraw the proceeds at any time.
+//!
+//! `DutchAuctionMint` wraps `ERC721` and `Ownable`. Contracts must inherit `DutchAuctionMint<T>`,
+//! `ERC721<T>` and `Ownable`, and implement `Borrow`/`BorrowMut` of `ERC721<T>` and `Ownable` by
+//! returning the `erc721` and `ownable` fields of the `DutchAuctionMint` storage. The owner is set
+//! during the [initialization](crate#initialization) of the contract.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256, U64},
+    alloy_sol_types::sol,
+    block, contract, evm, msg,
+    prelude::*,
+};
+
+use crate::access::ownable::Ownable;
+use crate::tokens::erc721::{ERC721Params, ERC721};
+use crate::utils::safe_transfer::safe_transfer_eth;
+
+/// Immutable definitions of the auction
+pub trait DutchAuctionParams {
+    /// The price of a token when the auction starts.
+    const START_PRICE: U256;
+    /// The price of a token once the auction has fully decayed.
+    const RESTING_PRICE: U256;
+    /// The number of seconds it takes for the price to decay to the resting price.
+    const DURATION: u64;
+    /// The number of seconds between price drops, or 0 for a linear decay.
+    const DROP_INTERVAL: u64 = 0;
+    /// The maximum number of tokens that can be minted.
+    const MAX_SUPPLY: u64;
+    /// The maximum number of tokens that a single wallet can mint.
+    const MAX_PER_WALLET: u64;
+    /// Whether minters are refunded the difference with the clearing price.
+    const REBATE: bool = false;
+}
+
+sol_storage! {
+    /// DutchAuctionMint sells the tokens of a collection at a decaying price
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.DutchAuctionMint"))]
+    pub struct DutchAuctionMint<T: ERC721Params> {
+        /// The collection being minted
+        ERC721<T> erc721;
+        /// The owner, who configures the auction and withdraws the proceeds
+        Ownable ownable;
+        /// The timestamp at which the auction starts, or 0 if it has not been scheduled
+        uint64 start_time;
+        /// The number of tokens minted
+        uint256 total_minted;
+        /// The price paid for the last mint
+        uint256 last_price;
+        /// The amount of proceeds withdrawn by the owner
+        uint256 withdrawn;
+        /// Maps account to the number of tokens it minted
+        mapping(address => uint256) minted;
+        /// Maps account to the total price it paid
+        mapping(address => uint256) paid;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when the auction is scheduled to start at `startTime`.
+    event AuctionScheduled(uint256 startTime);
+    /// Emitted when `minter` mints `quantity` tokens at `price` each.
+    event AuctionMint(address indexed minter, uint256 quantity, uint256 price);
+    /// Emitted when `minter` is refunded `amount` after the auction is settled.
+    event RebateClaimed(address indexed minter, uint256 amount);
+
+    /// The auction has not started.
+    error AuctionNotStarted();
+    /// The auction has already started and cannot be rescheduled.
+    error AuctionAlreadyStarted();
+    /// The auction has not sold out or reached its resting price.
+    error AuctionNotSettled();
+    /// The quantity to mint is zero.
+    error AuctionZeroQuantity();
+    /// The mint would exceed the maximum supply.
+    error AuctionExceedsMaxSupply(uint256 maxSupply);
+    /// The mint would exceed the maximum number of tokens per wallet.
+    error AuctionExceedsWalletLimit(uint256 maxPerWallet);
+    /// The value sent does not cover the price of the mint.
+    error AuctionInsufficientPayment(uint256 required, uint256 sent);
+    /// There is nothing to refund or withdraw.
+    error AuctionNothingToClaim();
+}
+
+#[derive(SolidityError)]
+pub enum DutchAuctionMintError {
+    AuctionNotStarted(AuctionNotStarted),
+    AuctionAlreadyStarted(AuctionAlreadyStarted),
+    AuctionNotSettled(AuctionNotSettled),
+    AuctionZeroQuantity(AuctionZeroQuantity),
+    AuctionExceedsMaxSupply(AuctionExceedsMaxSupply),
+    AuctionExceedsWalletLimit(AuctionExceedsWalletLimit),
+    AuctionInsufficientPayment(AuctionInsufficientPayment),
+    AuctionNothingToClaim(AuctionNothingToClaim),
+}
+
+// Internal functions
+impl<T: ERC721Params + DutchAuctionParams> DutchAuctionMint<T> {
+    /// Returns whether the auction has started.
+    fn _started(&self) -> bool {
+        let start_time = self.start_time.get().to::<u64>();
+        start_time != 0 && block::timestamp() >= start_time
+    }
+
+    /// Returns whether the auction has sold out or reached its resting price.
+    fn _settled(&self) -> bool {
+        if self.total_minted.get() >= U256::from(T::MAX_SUPPLY) {
+            return true;
+        }
+        self._started() && block::timestamp() - self.start_time.get().to::<u64>() >= T::DURATION
+    }
+
+    /// Returns the price every minter ends up paying once the auction is settled.
+    fn _clearing_price(&self) -> U256 {
+        if self.total_minted.get() >= U256::from(T::MAX_SUPPLY) {
+            return self.last_price.get();
+        }
+        T::RESTING_PRICE
+    }
+
+    /// Returns the proceeds the owner is entitled to, excluding what has been withdrawn.
+    fn _withdrawable(&self) -> Result<U256, DutchAuctionMintError> {
+        let balance = contract::balance();
+        if !T::REBATE {
+            return Ok(balance);
+        }
+        if !self._settled() {
+            return Err(DutchAuctionMintError::AuctionNotSettled(
+                AuctionNotSettled {},
+            ));
+        }
+        Ok(self.total_minted.get() * self._clearing_price() - self.withdrawn.get())
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC721Params + DutchAuctionParams> DutchAuctionMint<T> {
+    /// Returns the current price of a token.
+    pub fn current_price(&self) -> U256 {
+        if !self._started() {
+            return T::START_PRICE;
+        }
+        let mut elapsed = block::timestamp() - self.start_time.get().to::<u64>();
+        if elapsed >= T::DURATION {
+            return T::RESTING_PRICE;
+        }
+        if T::DROP_INTERVAL != 0 {
+            elapsed -= elapsed % T::DROP_INTERVAL;
+        }
+        let decay = (T::START_PRICE - T::RESTING_PRICE) * U256::from(elapsed);
+        T::START_PRICE - decay / U256::from(T::DURATION)
+    }
+
+    /// Returns the timestamp at which the auction starts, or 0 if it has not been scheduled.
+    pub fn auction_start_time(&self) -> u64 {
+        self.start_time.get().to()
+    }
+
+    /// Returns the number of tokens minted.
+    pub fn total_minted(&self) -> U256 {
+        self.total_minted.get()
+    }
+
+    /// Returns the number of tokens minted by `account`.
+    pub fn minted(&self, account: Address) -> U256 {
+        self.minted.get(account)
+    }
+
+    /// Returns the total price paid by `account`.
+    pub fn paid(&self, account: Address) -> U256 {
+        self.paid.get(account)
+    }
+
+    /// Schedules the auction to start at `start_time`.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    /// - The auction must not have started.
+    ///
+    /// Emits an {AuctionScheduled} event.
+    pub fn set_auction_start_time(&mut self, start_time: u64) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        if self._started() {
+            return Err(
+                DutchAuctionMintError::AuctionAlreadyStarted(AuctionAlreadyStarted {}).into(),
+            );
+        }
+        self.start_time.set(U64::from(start_time));
+        evm::log(AuctionScheduled {
+            startTime: U256::from(start_time),
+        });
+        Ok(())
+    }
+
+    /// Mints `quantity` tokens to the caller at the current price.
+    /// Any value sent above the price is refunded.
+    ///
+    /// Requirements:
+    /// - The auction must have started.
+    /// - `quantity` must be non-zero.
+    /// - The mint must not exceed the maximum supply or the caller's wallet limit.
+    /// - The value sent must cover the price of the mint.
+    ///
+    /// Emits a {Transfer} event for each token and an {AuctionMint} event.
+    #[payable]
+    pub fn auction_mint(&mut self, quantity: U256) -> Result<(), Vec<u8>> {
+        if !self._started() {
+            return Err(DutchAuctionMintError::AuctionNotStarted(AuctionNotStarted {}).into());
+        }
+        if quantity.is_zero() {
+            return Err(DutchAuctionMintError::AuctionZeroQuantity(AuctionZeroQuantity {}).into());
+        }
+        let first_id = self.total_minted.get();
+        if first_id + quantity > U256::from(T::MAX_SUPPLY) {
+            return Err(
+                DutchAuctionMintError::AuctionExceedsMaxSupply(AuctionExceedsMaxSupply {
+                    maxSupply: U256::from(T::MAX_SUPPLY),
+                })
+                .into(),
+            );
+        }
+        let minter = msg::sender();
+        let minted = self.minted.get(minter) + quantity;
+        if minted > U256::from(T::MAX_PER_WALLET) {
+            return Err(DutchAuctionMintError::AuctionExceedsWalletLimit(
+                AuctionExceedsWalletLimit {
+                    maxPerWallet: U256::from(T::MAX_PER_WALLET),
+                },
+            )
+            .into());
+        }
+        let price = self.current_price();
+        let cost = price * quantity;
+        if msg::value() < cost {
+            return Err(DutchAuctionMintError::AuctionInsufficientPayment(
+                AuctionInsufficientPayment {
+                    required: cost,
+                    sent: msg::value(),
+                },
+            )
+            .into());
+        }
+
+        self.total_minted.set(first_id + quantity);
+        self.last_price.set(price);
+        self.minted.insert(minter, minted);
+        let paid = self.paid.get(minter);
+        self.paid.insert(minter, paid + cost);
+
+        let mut id = first_id;
+        while id < first_id + quantity {
+            ERC721::_mint(&mut self.erc721, minter, id)?;
+            id += U256::from(1);
+        }
+        evm::log(AuctionMint {
+            minter,
+            quantity,
+            price,
+        });
+
+        if msg::value() > cost {
+            safe_transfer_eth(minter, msg::value() - cost)?;
+        }
+        Ok(())
+    }
+
+    /// Refunds the caller the difference between what it paid and the clearing price.
+    ///
+    /// Requirements:
+    /// - Rebates must be enabled and the auction must be settled.
+    /// - The caller must be owed a refund.
+    ///
+    /// Emits a {RebateClaimed} event.
+    pub fn claim_rebate(&mut self) -> Result<(), Vec<u8>> {
+        if !T::REBATE || !self._settled() {
+            return Err(DutchAuctionMintError::AuctionNotSettled(AuctionNotSettled {}).into());
+        }
+        let minter = msg::sender();
+        let paid = self.paid.get(minter);
+        let owed = self.minted.get(minter) * self._clearing_price();
+        if paid <= owed {
+            return Err(
+                DutchAuctionMintError::AuctionNothingToClaim(AuctionNothingToClaim {}).into(),
+            );
+        }
+        self.paid.insert(minter, owed);
+
+        safe_transfer_eth(minter, paid - owed)?;
+        evm::log(RebateClaimed {
+            minter,
+            amount: paid - owed,
+        });
+        Ok(())
+    }
+
+    /// Sends the auction proceeds to `to`.
+    /// With rebates enabled, the proceeds are only available once the auction is settled.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    /// - There must be proceeds to withdraw.
+    pub fn withdraw(&mut self, to: Address) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        let amount = self._withdrawable()?;
+        if amount.is_zero() {
+            return Err(
+                DutchAuctionMintError::AuctionNothingToClaim(AuctionNothingToClaim {}).into(),
+            );
+        }
+        let withdrawn = self.withdrawn.get();
+        self.withdrawn.set(withdrawn + amount);
+        safe_transfer_eth(to, amount)
+    }
+}
@@ -0,0 +1,395 @@
+//! Fractional ownership of an ERC721 token through ERC20 shares, with a reserve price buyout.
+//! The logic was based off of: https://github.com/fractional-company/contracts/blob/master/src/ERC721TokenVault.sol
+//!
+//! The curator locks an ERC721 token in the vault with `_initialize`, and receives the whole
+//! supply of shares, which can then be traded like any ERC20 token. Whoever holds every share can
+//! `redeem` them for the token. Otherwise, anyone can buy the token out by starting an auction
+//! with a bid of at least the reserve price. Bids must outbid the previous one by
+//! `MIN_BID_INCREASE_BPS`, and bids in the last `TIME_EXTENSION` seconds extend the auction, so
+//! that it cannot be sniped. Once the auction has ended, the winner receives the token, and share
+//! holders burn their shares with `cash` for their part of the winning bid, in ETH.
+//!
+//! Outbid bidders are not refunded in the same call, as a bidder that rejects ETH could otherwise
+//! block the auction. Their bid is credited instead, and withdrawn with `withdraw_refund`. The
+//! reserve price is set with `_set_reserve_price` by the inheriting contract, which decides who
+//! may change it (ex. the curator, or a vote of the share holders).
+//!
+//! `FractionalVault` wraps `ERC20`. Contracts must inherit both `FractionalVault<T>` and
+//! `ERC20<T>`, and implement `Borrow<ERC20<T>>` and `BorrowMut<ERC20<T>>` by returning the `erc20`
+//! field of the `FractionalVault` storage. The token is locked by `_initialize`, see
+//! [initialization](crate#initialization).
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{aliases::U8, Address, U256, U64},
+    alloy_sol_types::{sol, SolCall},
+    block, contract, evm, msg,
+    prelude::*,
+};
+
+use crate::tokens::erc20::{ERC20Params, ERC20};
+use crate::utils::address::function_call;
+use crate::utils::safe_transfer::safe_transfer_eth;
+
+/// The token is locked in the vault, and no auction has started.
+pub const AUCTION_INACTIVE: u8 = 0;
+/// The auction is accepting bids.
+pub const AUCTION_LIVE: u8 = 1;
+/// The auction has ended, and share holders can cash out.
+pub const AUCTION_ENDED: u8 = 2;
+/// The token was redeemed by the holder of every share.
+pub const AUCTION_REDEEMED: u8 = 3;
+
+/// Immutable definitions of the vault
+pub trait FractionalVaultParams {
+    /// The duration of the auction in seconds.
+    const AUCTION_LENGTH: u64 = 7 * 86_400;
+    /// The number of seconds by which a late bid extends the auction.
+    const TIME_EXTENSION: u64 = 15 * 60;
+    /// The minimum increase of a bid over the previous one, in basis points.
+    const MIN_BID_INCREASE_BPS: u64 = 500;
+}
+
+sol_storage! {
+    /// FractionalVault locks an ERC721 token and tracks its buyout auction
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.FractionalVault"))]
+    pub struct FractionalVault<T: ERC20Params> {
+        /// The shares of the token
+        ERC20<T> erc20;
+        /// The collection of the locked token
+        address token;
+        /// The id of the locked token
+        uint256 id;
+        /// The account that locked the token
+        address curator;
+        /// The minimum bid to start an auction
+        uint256 reserve_price;
+        /// The state of the auction
+        uint8 auction_state;
+        /// The timestamp at which the auction ends
+        uint64 auction_end;
+        /// The highest bid
+        uint256 live_price;
+        /// The highest bidder
+        address winning;
+        /// The winning bid that share holders have not cashed out yet
+        uint256 proceeds;
+        /// Maps outbid bidder to the amount they can withdraw
+        mapping(address => uint256) refunds;
+    }
+}
+
+// Define events and errors in the contract, and the functions of the collection
+sol! {
+    /// Emitted when the reserve price is set to `price`.
+    event ReservePriceSet(uint256 price);
+    /// Emitted when `buyer` starts the auction with a bid of `price`.
+    event AuctionStarted(address indexed buyer, uint256 price);
+    /// Emitted when `buyer` bids `price`.
+    event Bid(address indexed buyer, uint256 price);
+    /// Emitted when `buyer` wins the auction with a bid of `price`.
+    event Won(address indexed buyer, uint256 price);
+    /// Emitted when `redeemer` burns every share for the token.
+    event Redeem(address indexed redeemer);
+    /// Emitted when `owner` burns their shares for `amount` of the winning bid.
+    event Cash(address indexed owner, uint256 amount);
+
+    /// The vault has already been initialized.
+    error VaultAlreadyInitialized();
+    /// The supply of shares cannot be 0.
+    error InvalidShareSupply();
+    /// The action is not allowed in the current `state` of the auction.
+    error InvalidAuctionState(uint8 state);
+    /// The bid of `price` is below the `minimum`.
+    error BidTooLow(uint256 price, uint256 minimum);
+    /// The auction ended at `end`.
+    error AuctionClosed(uint64 end);
+    /// The auction only ends at `end`.
+    error AuctionNotOver(uint64 end);
+    /// The caller holds `balance` of the `totalSupply` shares, but must hold them all.
+    error NotAllShares(uint256 balance, uint256 totalSupply);
+    /// The caller has nothing to cash out or withdraw.
+    error NothingToClaim();
+
+    function transferFrom(address from, address to, uint256 id) external;
+}
+
+#[derive(SolidityError)]
+pub enum FractionalVaultError {
+    VaultAlreadyInitialized(VaultAlreadyInitialized),
+    InvalidShareSupply(InvalidShareSupply),
+    InvalidAuctionState(InvalidAuctionState),
+    BidTooLow(BidTooLow),
+    AuctionClosed(AuctionClosed),
+    AuctionNotOver(AuctionNotOver),
+    NotAllShares(NotAllShares),
+    NothingToClaim(NothingToClaim),
+}
+
+// Internal functions
+impl<T: ERC20Params + FractionalVaultParams> FractionalVault<T> {
+    /// Locks token `id` of `token` from `curator`, mints `supply` shares to `curator`, and sets
+    /// the reserve price to `reserve_price`.
+    ///
+    /// Requirements:
+    /// - The vault must not have been initialized.
+    /// - `supply` cannot be 0.
+    /// - `curator` must have approved this contract to transfer the token.
+    ///
+    /// Emits a {Transfer} event and a {ReservePriceSet} event.
+    pub fn _initialize(
+        &mut self,
+        token: Address,
+        id: U256,
+        curator: Address,
+        supply: U256,
+        reserve_price: U256,
+    ) -> Result<(), Vec<u8>> {
+        if !self.token.get().is_zero() {
+            return Err(
+                FractionalVaultError::VaultAlreadyInitialized(VaultAlreadyInitialized {}).into(),
+            );
+        }
+        if supply.is_zero() {
+            return Err(FractionalVaultError::InvalidShareSupply(InvalidShareSupply {}).into());
+        }
+        self.token.set(token);
+        self.id.set(id);
+        self.curator.set(curator);
+        ERC20::_mint(&mut self.erc20, curator, supply)?;
+        self._set_reserve_price(reserve_price)?;
+        Self::_transfer_token(token, curator, contract::address(), id)
+    }
+
+    /// Sets the minimum bid to start an auction to `price`.
+    ///
+    /// Requirements:
+    /// - No auction has started.
+    ///
+    /// Emits a {ReservePriceSet} event.
+    pub fn _set_reserve_price(&mut self, price: U256) -> Result<(), FractionalVaultError> {
+        self._require_state(AUCTION_INACTIVE)?;
+        self.reserve_price.set(price);
+        evm::log(ReservePriceSet { price });
+        Ok(())
+    }
+
+    /// Requires that the auction is in `state`.
+    fn _require_state(&self, state: u8) -> Result<(), FractionalVaultError> {
+        let current = self.auction_state.get().to::<u8>();
+        if current != state {
+            return Err(FractionalVaultError::InvalidAuctionState(
+                InvalidAuctionState { state: current },
+            ));
+        }
+        Ok(())
+    }
+
+    /// Transfers token `id` of `token` from `from` to `to`.
+    fn _transfer_token(
+        token: Address,
+        from: Address,
+        to: Address,
+        id: U256,
+    ) -> Result<(), Vec<u8>> {
+        function_call(token, &transferFromCall { from, to, id }.encode())?;
+        Ok(())
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC20Params + FractionalVaultParams> FractionalVault<T> {
+    /// Returns the collection and the id of the locked token.
+    pub fn token(&self) -> (Address, U256) {
+        (self.token.get(), self.id.get())
+    }
+
+    /// Returns the account that locked the token.
+    pub fn curator(&self) -> Address {
+        self.curator.get()
+    }
+
+    /// Returns the minimum bid to start an auction.
+    pub fn reserve_price(&self) -> U256 {
+        self.reserve_price.get()
+    }
+
+    /// Returns the state of the auction, its end, the highest bid and the highest bidder.
+    pub fn auction(&self) -> (u8, u64, U256, Address) {
+        (
+            self.auction_state.get().to(),
+            self.auction_end.get().to(),
+            self.live_price.get(),
+            self.winning.get(),
+        )
+    }
+
+    /// Returns the amount that `bidder` can withdraw after being outbid.
+    pub fn refund_of(&self, bidder: Address) -> U256 {
+        self.refunds.get(bidder)
+    }
+
+    /// Starts the auction with a bid of the value sent.
+    ///
+    /// Requirements:
+    /// - No auction has started, and the token has not been redeemed.
+    /// - The value sent must be at least the reserve price, and not 0.
+    ///
+    /// Emits an {AuctionStarted} event.
+    #[payable]
+    pub fn start(&mut self) -> Result<(), FractionalVaultError> {
+        self._require_state(AUCTION_INACTIVE)?;
+        let price = msg::value();
+        let reserve_price = self.reserve_price.get();
+        if price < reserve_price || price.is_zero() {
+            return Err(FractionalVaultError::BidTooLow(BidTooLow {
+                price,
+                minimum: reserve_price,
+            }));
+        }
+
+        let buyer = msg::sender();
+        self.auction_state.set(U8::from(AUCTION_LIVE));
+        self.auction_end
+            .set(U64::from(block::timestamp() + T::AUCTION_LENGTH));
+        self.live_price.set(price);
+        self.winning.set(buyer);
+        evm::log(AuctionStarted { buyer, price });
+        Ok(())
+    }
+
+    /// Outbids the highest bid with the value sent, and credits the previous bid to its bidder.
+    /// Extends the auction to `TIME_EXTENSION` seconds from now if it ends sooner.
+    ///
+    /// Requirements:
+    /// - The auction must be live, and not over.
+    /// - The value sent must exceed the highest bid by `MIN_BID_INCREASE_BPS`.
+    ///
+    /// Emits a {Bid} event.
+    #[payable]
+    pub fn bid(&mut self) -> Result<(), FractionalVaultError> {
+        self._require_state(AUCTION_LIVE)?;
+        let now = block::timestamp();
+        let end = self.auction_end.get().to::<u64>();
+        if now >= end {
+            return Err(FractionalVaultError::AuctionClosed(AuctionClosed { end }));
+        }
+        let price = msg::value();
+        let live_price = self.live_price.get();
+        let minimum =
+            live_price + live_price * U256::from(T::MIN_BID_INCREASE_BPS) / U256::from(10_000);
+        if price < minimum || price == live_price {
+            return Err(FractionalVaultError::BidTooLow(BidTooLow {
+                price,
+                minimum,
+            }));
+        }
+
+        let previous = self.winning.get();
+        let refund = self.refunds.get(previous) + live_price;
+        self.refunds.insert(previous, refund);
+        if end - now <= T::TIME_EXTENSION {
+            self.auction_end.set(U64::from(now + T::TIME_EXTENSION));
+        }
+        let buyer = msg::sender();
+        self.live_price.set(price);
+        self.winning.set(buyer);
+        evm::log(Bid { buyer, price });
+        Ok(())
+    }
+
+    /// Ends the auction, and sends the token to the highest bidder. The winning bid is then
+    /// cashed out by the share holders.
+    ///
+    /// Requirements:
+    /// - The auction must be live, and over.
+    ///
+    /// Emits a {Won} event.
+    pub fn end(&mut self) -> Result<(), Vec<u8>> {
+        self._require_state(AUCTION_LIVE)?;
+        let end = self.auction_end.get().to::<u64>();
+        if block::timestamp() < end {
+            return Err(FractionalVaultError::AuctionNotOver(AuctionNotOver { end }).into());
+        }
+
+        let (buyer, price) = (self.winning.get(), self.live_price.get());
+        self.auction_state.set(U8::from(AUCTION_ENDED));
+        self.proceeds.set(price);
+        Self::_transfer_token(self.token.get(), contract::address(), buyer, self.id.get())?;
+        evm::log(Won { buyer, price });
+        Ok(())
+    }
+
+    /// Burns every share for the token, which is sent to the caller.
+    ///
+    /// Requirements:
+    /// - No auction has started.
+    /// - The caller must hold every share.
+    ///
+    /// Emits a {Transfer} event and a {Redeem} event.
+    pub fn redeem(&mut self) -> Result<(), Vec<u8>> {
+        self._require_state(AUCTION_INACTIVE)?;
+        let redeemer = msg::sender();
+        let balance = self.erc20.balance_of(redeemer);
+        let total_supply = self.erc20.total_supply();
+        if balance != total_supply {
+            return Err(FractionalVaultError::NotAllShares(NotAllShares {
+                balance,
+                totalSupply: total_supply,
+            })
+            .into());
+        }
+
+        ERC20::_burn(&mut self.erc20, redeemer, balance)?;
+        self.auction_state.set(U8::from(AUCTION_REDEEMED));
+        Self::_transfer_token(
+            self.token.get(),
+            contract::address(),
+            redeemer,
+            self.id.get(),
+        )?;
+        evm::log(Redeem { redeemer });
+        Ok(())
+    }
+
+    /// Burns the shares of the caller for their part of the winning bid, sent in ETH.
+    ///
+    /// Requirements:
+    /// - The auction must have ended.
+    /// - The caller must hold shares.
+    ///
+    /// Emits a {Transfer} event and a {Cash} event.
+    pub fn cash(&mut self) -> Result<(), Vec<u8>> {
+        self._require_state(AUCTION_ENDED)?;
+        let owner = msg::sender();
+        let balance = self.erc20.balance_of(owner);
+        if balance.is_zero() {
+            return Err(FractionalVaultError::NothingToClaim(NothingToClaim {}).into());
+        }
+
+        // The last share holder receives the remaining proceeds, including rounding dust
+        let proceeds = self.proceeds.get();
+        let amount = proceeds * balance / self.erc20.total_supply();
+        ERC20::_burn(&mut self.erc20, owner, balance)?;
+        self.proceeds.set(proceeds - amount);
+        safe_transfer_eth(owner, amount)?;
+        evm::log(Cash { owner, amount });
+        Ok(())
+    }
+
+    /// Withdraws the bids of the caller that were outbid.
+    ///
+    /// Requirements:
+    /// - The caller must have been outbid.
+    pub fn withdraw_refund(&mut self) -> Result<(), Vec<u8>> {
+        let bidder = msg::sender();
+        let refund = self.refunds.get(bidder);
+        if refund.is_zero() {
+            return Err(FractionalVaultError::NothingToClaim(NothingToClaim {}).into());
+        }
+        self.refunds.insert(bidder, U256::ZERO);
+        safe_transfer_eth(bidder, refund)
+    }
+}
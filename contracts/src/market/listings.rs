@@ -0,0 +1,244 @@
+//! Fixed-price NFT listings backed by EIP-712 signed sell orders.
+//! The logic was based off of: https://github.com/0xProject/protocol/blob/development/contracts/zero-ex/contracts/src/features/nft_orders/ERC721OrdersFeature.sol
+//!
+//! Makers list a token off-chain by signing a `SellOrder`, and anyone can fill it on-chain by
+//! paying the price, either in ETH (`currency` is the zero address) or in an ERC20 token. The
+//! payment is sent to the maker and the token is transferred to the buyer in the same call, so
+//! the maker must have approved this contract to manage the listed token.
+//!
+//! Every order has a maker-chosen nonce, which is marked as used once the order is filled or
+//! cancelled. Nonces are tracked in a bitmap, packing 256 nonces per storage slot, so makers can
+//! cancel many orders at once by choosing nonces that share a word.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolCall, SolType},
+    block,
+    call::RawCall,
+    crypto::keccak,
+    evm, msg,
+    prelude::*,
+};
+
+use crate::utils::eip712::{to_typed_data_hash, EIP712Params, EIP712};
+use crate::utils::safe_transfer::{safe_transfer_eth, safe_transfer_from};
+use crate::utils::signature_checker::is_valid_signature_now;
+
+/// The EIP-712 type hash of a sell order.
+pub const SELL_ORDER_TYPEHASH: B256 = crate::const_keccak!("SellOrder(address maker,address token,uint256 id,address currency,uint256 price,uint256 expiry,uint256 nonce)");
+
+sol_storage! {
+    /// Listings tracks the used order nonces of each maker
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.Listings"))]
+    pub struct Listings<T> {
+        /// The EIP-712 domain the orders are signed for
+        EIP712<T> eip712;
+        /// Maps maker to word index to a packed bitmap of used nonces
+        mapping(address => mapping(uint256 => uint256)) nonce_bitmaps;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when the order `orderHash` of `maker` is filled by `taker`.
+    event OrderFilled(bytes32 orderHash, address indexed maker, address indexed taker, address indexed token, uint256 id, address currency, uint256 price);
+    /// Emitted when `maker` cancels the order with `nonce`.
+    event OrderCancelled(address indexed maker, uint256 nonce);
+
+    /// The order has expired.
+    error OrderExpired(uint256 expiry);
+    /// The order nonce has already been used, because it was filled or cancelled.
+    error OrderNonceUsed(address maker, uint256 nonce);
+    /// The order signature is not a valid signature of the maker.
+    error InvalidOrderSignature();
+    /// The value sent does not match the price of the order.
+    error InvalidOrderPayment(uint256 expected, uint256 sent);
+
+    function transferFrom(address from, address to, uint256 id) external;
+}
+
+#[derive(SolidityError)]
+pub enum ListingsError {
+    OrderExpired(OrderExpired),
+    OrderNonceUsed(OrderNonceUsed),
+    InvalidOrderSignature(InvalidOrderSignature),
+    InvalidOrderPayment(InvalidOrderPayment),
+}
+
+// Internal functions
+impl<T: EIP712Params> Listings<T> {
+    /// Returns the EIP-712 struct hash of a sell order.
+    #[allow(clippy::too_many_arguments)]
+    pub fn _order_struct_hash(
+        maker: Address,
+        token: Address,
+        id: U256,
+        currency: Address,
+        price: U256,
+        expiry: U256,
+        nonce: U256,
+    ) -> B256 {
+        keccak(
+            <sol! { (bytes32, address, address, uint256, address, uint256, uint256, uint256) }>::encode(&(
+                SELL_ORDER_TYPEHASH.0,
+                maker,
+                token,
+                id,
+                currency,
+                price,
+                expiry,
+                nonce,
+            )),
+        )
+    }
+
+    /// Marks `nonce` of `maker` as used.
+    ///
+    /// Requirements:
+    /// - The nonce must not have been used.
+    fn _use_nonce(&mut self, maker: Address, nonce: U256) -> Result<(), ListingsError> {
+        let bit = U256::from(1) << (nonce & U256::from(0xff)).to::<usize>();
+        let mut bitmaps = self.nonce_bitmaps.setter(maker);
+        let mut word = bitmaps.setter(nonce >> 8);
+        let current = word.get();
+        if current & bit == bit {
+            return Err(ListingsError::OrderNonceUsed(OrderNonceUsed {
+                maker,
+                nonce,
+            }));
+        }
+        word.set(current | bit);
+        Ok(())
+    }
+}
+
+// External functions
+#[external]
+impl<T: EIP712Params> Listings<T> {
+    /// Returns the EIP-712 domain separator the orders are signed for.
+    #[selector(name = "DOMAIN_SEPARATOR")]
+    pub fn domain_separator(&self) -> B256 {
+        self.eip712._domain_separator()
+    }
+
+    /// Returns the EIP-712 hash of a sell order, which the maker signs.
+    #[allow(clippy::too_many_arguments)]
+    pub fn hash_order(
+        &self,
+        maker: Address,
+        token: Address,
+        id: U256,
+        currency: Address,
+        price: U256,
+        expiry: U256,
+        nonce: U256,
+    ) -> B256 {
+        let struct_hash =
+            Self::_order_struct_hash(maker, token, id, currency, price, expiry, nonce);
+        to_typed_data_hash(self.eip712._domain_separator(), struct_hash)
+    }
+
+    /// Returns whether `nonce` of `maker` has been used.
+    pub fn is_nonce_used(&self, maker: Address, nonce: U256) -> bool {
+        let word = self.nonce_bitmaps.getter(maker).get(nonce >> 8);
+        let bit = U256::from(1) << (nonce & U256::from(0xff)).to::<usize>();
+        word & bit == bit
+    }
+
+    /// Cancels the orders of the caller with `nonces`.
+    ///
+    /// Requirements:
+    /// - None of the nonces must have been used.
+    ///
+    /// Emits an {OrderCancelled} event for each nonce.
+    pub fn cancel_orders(&mut self, nonces: Vec<U256>) -> Result<(), ListingsError> {
+        let maker = msg::sender();
+        for nonce in nonces {
+            self._use_nonce(maker, nonce)?;
+            evm::log(OrderCancelled { maker, nonce });
+        }
+        Ok(())
+    }
+
+    /// Fills the sell order of `maker`, paying `price` of `currency` to the maker and
+    /// transferring token `id` of `token` to the caller.
+    ///
+    /// Requirements:
+    /// - The order must not have expired, been filled or been cancelled.
+    /// - `signature` must be a valid signature of the order by `maker`.
+    /// - For ETH orders, the value sent must be exactly `price`.
+    ///   For ERC20 orders, no value must be sent and the caller must have approved this contract
+    ///   to spend `price` of `currency`.
+    /// - The maker must own the token and have approved this contract to manage it.
+    ///
+    /// Emits an {OrderFilled} event.
+    #[payable]
+    #[allow(clippy::too_many_arguments)]
+    pub fn fill_order(
+        &mut self,
+        maker: Address,
+        token: Address,
+        id: U256,
+        currency: Address,
+        price: U256,
+        expiry: U256,
+        nonce: U256,
+        signature: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        if U256::from(block::timestamp()) > expiry {
+            return Err(ListingsError::OrderExpired(OrderExpired { expiry }).into());
+        }
+        let struct_hash =
+            Self::_order_struct_hash(maker, token, id, currency, price, expiry, nonce);
+        let order_hash = self.eip712._hash_typed_data(struct_hash);
+        if !is_valid_signature_now(maker, order_hash, &signature) {
+            return Err(ListingsError::InvalidOrderSignature(InvalidOrderSignature {}).into());
+        }
+        self._use_nonce(maker, nonce)?;
+
+        // Pay the maker
+        let taker = msg::sender();
+        let expected = if currency.is_zero() {
+            price
+        } else {
+            U256::ZERO
+        };
+        if msg::value() != expected {
+            return Err(ListingsError::InvalidOrderPayment(InvalidOrderPayment {
+                expected,
+                sent: msg::value(),
+            })
+            .into());
+        }
+        if currency.is_zero() {
+            safe_transfer_eth(maker, price)?;
+        } else {
+            safe_transfer_from(currency, taker, maker, price)?;
+        }
+
+        // Deliver the token
+        RawCall::new().call(
+            token,
+            &transferFromCall {
+                from: maker,
+                to: taker,
+                id,
+            }
+            .encode(),
+        )?;
+
+        evm::log(OrderFilled {
+            orderHash: order_hash.0,
+            maker,
+            taker,
+            token,
+            id,
+            currency,
+            price,
+        });
+        Ok(())
+    }
+}
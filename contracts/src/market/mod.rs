@@ -0,0 +1,10 @@
+//! Primary sale and marketplace contracts for NFT collections
+
+#[cfg(feature = "dutch-auction-mint")]
+pub mod dutch_auction_mint;
+
+#[cfg(feature = "fractional-vault")]
+pub mod fractional_vault;
+
+#[cfg(feature = "listings")]
+pub mod listings;
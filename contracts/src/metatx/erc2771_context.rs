@@ -0,0 +1,176 @@
+//! ERC-2771 context for receiving meta-transactions from a trusted forwarder.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/metatx/ERC2771Context.sol
+//!
+//! A trusted forwarder verifies the signature of a request and calls the contract with the
+//! address of the signer appended to the calldata. Contracts must then use `msg_sender()` and
+//! `msg_data()` of this module instead of `msg::sender()` wherever the caller is authorized, and
+//! pass the result to the internal functions of the token bases (ex. `_transfer` or
+//! `_spend_allowance`).
+//!
+//! Stylus methods cannot read the raw calldata, so `ERC2771Context` implements its router by hand
+//! to capture the sender and the calldata of the current call. It must be listed first in
+//! `#[inherit]`, and methods defined directly on the entrypoint type, which are routed before any
+//! inherited router, see `msg::sender()` as the sender:
+//!
+//! ```ignore
+//! #[external]
+//! #[inherit(ERC2771Context, MyToken)]
+//! impl Contract {}
+//! ```
+
+use alloc::vec::Vec;
+use core::{borrow::BorrowMut, cell::UnsafeCell};
+use stylus_sdk::{
+    abi::{internal, internal::EncodableReturnType, AbiType, Router},
+    alloy_primitives::Address,
+    alloy_sol_types::SolType,
+    function_selector, msg,
+    prelude::*,
+    storage::TopLevelStorage,
+    ArbResult,
+};
+
+/// The byte length of the sender address appended to the calldata by the forwarder.
+pub const CONTEXT_SUFFIX_LEN: usize = 20;
+
+sol_storage! {
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC2771Context"))]
+    pub struct ERC2771Context {
+        /// The forwarder trusted to append the original sender to the calldata
+        address trusted_forwarder;
+    }
+}
+
+/// The sender and the calldata of the current call, as captured by the router of `ERC2771Context`.
+struct CallContext {
+    sender: Address,
+    data: Vec<u8>,
+}
+
+/// Holds the context of the current call. Stylus programs are single threaded, and every call
+/// runs in a fresh instance of the program, so the context never outlives its call.
+struct CurrentContext(UnsafeCell<Option<CallContext>>);
+
+unsafe impl Sync for CurrentContext {}
+
+static CURRENT_CONTEXT: CurrentContext = CurrentContext(UnsafeCell::new(None));
+
+/// Returns the sender of the current call, which is the address appended to the calldata if the
+/// call comes from the trusted forwarder, and `msg::sender()` otherwise.
+pub fn msg_sender() -> Address {
+    match unsafe { &*CURRENT_CONTEXT.0.get() } {
+        Some(context) => context.sender,
+        None => msg::sender(),
+    }
+}
+
+/// Returns the calldata of the current call, without the appended sender if the call comes from
+/// the trusted forwarder. Returns empty data if the call was not routed through `ERC2771Context`.
+pub fn msg_data() -> Vec<u8> {
+    match unsafe { &*CURRENT_CONTEXT.0.get() } {
+        Some(context) => context.data.clone(),
+        None => Vec::new(),
+    }
+}
+
+// Internal functions
+impl ERC2771Context {
+    /// Sets `forwarder` as the trusted forwarder, or disables meta-transactions if it is the zero
+    /// address.
+    pub fn _set_trusted_forwarder(&mut self, forwarder: Address) {
+        self.trusted_forwarder.set(forwarder);
+    }
+
+    /// Captures the sender and the calldata of the call with `selector` and `input`, stripping the
+    /// appended sender if the call comes from the trusted forwarder.
+    fn _capture_context(&self, selector: u32, input: &[u8]) {
+        let forwarded =
+            input.len() >= CONTEXT_SUFFIX_LEN && self.is_trusted_forwarder(msg::sender());
+        let (sender, args) = if forwarded {
+            let (args, suffix) = input.split_at(input.len() - CONTEXT_SUFFIX_LEN);
+            (Address::from_slice(suffix), args)
+        } else {
+            (msg::sender(), input)
+        };
+        let mut data = Vec::with_capacity(4 + args.len());
+        data.extend_from_slice(&selector.to_be_bytes());
+        data.extend_from_slice(args);
+        unsafe { *CURRENT_CONTEXT.0.get() = Some(CallContext { sender, data }) };
+    }
+}
+
+// External functions, routed by the `Router` implementation below
+impl ERC2771Context {
+    /// Returns the trusted forwarder.
+    pub fn trusted_forwarder(&self) -> Address {
+        self.trusted_forwarder.get()
+    }
+
+    /// Returns whether `forwarder` is the trusted forwarder.
+    pub fn is_trusted_forwarder(&self, forwarder: Address) -> bool {
+        !forwarder.is_zero() && forwarder == self.trusted_forwarder.get()
+    }
+}
+
+const SELECTOR_TRUSTED_FORWARDER: u32 = u32::from_be_bytes(function_selector!("trustedForwarder"));
+const SELECTOR_IS_TRUSTED_FORWARDER: u32 =
+    u32::from_be_bytes(function_selector!("isTrustedForwarder", Address));
+
+impl<S> Router<S> for ERC2771Context
+where
+    S: TopLevelStorage + BorrowMut<Self>,
+{
+    type Storage = Self;
+
+    fn route(storage: &mut S, selector: u32, input: &[u8]) -> Option<ArbResult> {
+        let context: &mut Self = storage.borrow_mut();
+        // Capture the context for the inherited routers that come after this one
+        context._capture_context(selector, input);
+
+        match selector {
+            SELECTOR_TRUSTED_FORWARDER => {
+                if let Err(err) = internal::deny_value("trusted_forwarder") {
+                    return Some(Err(err));
+                }
+                Some(EncodableReturnType::encode(context.trusted_forwarder()))
+            }
+            SELECTOR_IS_TRUSTED_FORWARDER => {
+                if let Err(err) = internal::deny_value("is_trusted_forwarder") {
+                    return Some(Err(err));
+                }
+                let args = match <<(Address,) as AbiType>::SolType as SolType>::decode(input, true)
+                {
+                    Ok(args) => args,
+                    Err(err) => {
+                        internal::failed_to_decode_arguments(err);
+                        return Some(Err(Vec::new()));
+                    }
+                };
+                Some(EncodableReturnType::encode(
+                    context.is_trusted_forwarder(args.0),
+                ))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "export-abi")]
+impl stylus_sdk::abi::GenerateAbi for ERC2771Context {
+    const NAME: &'static str = "ERC2771Context";
+
+    fn fmt_abi(f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "interface IERC2771Context {{")?;
+        write!(
+            f,
+            "\n    function trustedForwarder() external view returns (address);"
+        )?;
+        write!(
+            f,
+            "\n    function isTrustedForwarder(address forwarder) external view returns (bool);"
+        )?;
+        writeln!(f, "\n}}")?;
+        Ok(())
+    }
+}
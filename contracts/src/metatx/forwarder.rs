@@ -0,0 +1,165 @@
+//! Minimal ERC-2771 forwarder that verifies and executes EIP-712 signed meta-transactions.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/release-v4.9/contracts/metatx/MinimalForwarder.sol
+//!
+//! A relayer submits a `ForwardRequest` signed by `from` and pays for its gas. The forwarder calls
+//! `to` with the request data and `from` appended, which contracts using `ERC2771Context` read as
+//! the sender of the call. Requests use the sequential nonces of their signer, and smart contract
+//! wallets can sign requests through ERC-1271.
+//!
+//! Unlike the Solidity version, which returns `(bool, bytes)`, `execute` bubbles up the revert
+//! data of a failed call, like the `ERC2771Forwarder` of OpenZeppelin 5.x. A failed call therefore
+//! does not consume the nonce, and the same request can be executed again later.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolType},
+    call::RawCall,
+    crypto::keccak,
+    evm, msg,
+    prelude::*,
+};
+
+use crate::utils::eip712::{to_typed_data_hash, EIP712Params, EIP712};
+use crate::utils::nonces::Nonces;
+use crate::utils::signature_checker::is_valid_signature_now;
+
+/// The EIP-712 type hash of a forward request.
+pub const FORWARD_REQUEST_TYPEHASH: B256 = crate::const_keccak!(
+    "ForwardRequest(address from,address to,uint256 value,uint256 gas,uint256 nonce,bytes data)"
+);
+
+/// A forward request as `(from, to, value, gas, nonce, data)`, which is ABI encoded like the
+/// `ForwardRequest` struct of the Solidity forwarder.
+pub type ForwardRequest = (Address, Address, U256, U256, U256, Bytes);
+
+/// The EIP-712 domain of the forwarder, which matches the one of the Solidity forwarder
+pub struct MinimalForwarderDomain;
+
+impl EIP712Params for MinimalForwarderDomain {
+    const NAME: &'static str = "MinimalForwarder";
+    const VERSION: &'static str = "0.0.1";
+}
+
+sol_storage! {
+    /// MinimalForwarder relays signed requests to contracts trusting it
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.MinimalForwarder"))]
+    pub struct MinimalForwarder {
+        /// The EIP-712 domain the requests are signed for
+        EIP712<MinimalForwarderDomain> eip712;
+        /// The request nonces of the signers
+        Nonces nonces;
+    }
+}
+
+sol! {
+    /// The signature or the nonce of the request of `from` is invalid.
+    error MinimalForwarderInvalidRequest(address from);
+    /// The `msgValue` sent does not match the `requestedValue` of the request.
+    error MinimalForwarderMismatchedValue(uint256 requestedValue, uint256 msgValue);
+    /// The relayer did not provide enough gas for the call to receive the `requestedGas`.
+    error MinimalForwarderInsufficientGas(uint256 requestedGas);
+}
+
+#[derive(SolidityError)]
+pub enum MinimalForwarderError {
+    MinimalForwarderInvalidRequest(MinimalForwarderInvalidRequest),
+    MinimalForwarderMismatchedValue(MinimalForwarderMismatchedValue),
+    MinimalForwarderInsufficientGas(MinimalForwarderInsufficientGas),
+}
+
+// Internal functions
+impl MinimalForwarder {
+    /// Returns the EIP-712 struct hash of `req`.
+    pub fn _request_struct_hash(req: &ForwardRequest) -> B256 {
+        let (from, to, value, gas, nonce, data) = req;
+        keccak(
+            <sol! { (bytes32, address, address, uint256, uint256, uint256, bytes32) }>::encode(&(
+                FORWARD_REQUEST_TYPEHASH.0,
+                *from,
+                *to,
+                *value,
+                *gas,
+                *nonce,
+                keccak(&data.0).0,
+            )),
+        )
+    }
+}
+
+// External functions
+#[external]
+impl MinimalForwarder {
+    /// Returns the current nonce of `from`, which its next request must use.
+    pub fn get_nonce(&self, from: Address) -> U256 {
+        self.nonces.nonces(from)
+    }
+
+    /// Returns the EIP-712 domain separator for forward requests.
+    #[selector(name = "DOMAIN_SEPARATOR")]
+    pub fn domain_separator(&self) -> B256 {
+        self.eip712._domain_separator()
+    }
+
+    /// Returns whether `signature` is a valid signature of `req` by its `from` address, and `req`
+    /// uses the current nonce of `from`.
+    pub fn verify(&self, req: ForwardRequest, signature: Bytes) -> bool {
+        let (from, _, _, _, nonce, _) = req;
+        let signed_hash = to_typed_data_hash(
+            self.eip712._domain_separator(),
+            Self::_request_struct_hash(&req),
+        );
+        nonce == self.nonces.nonces(from) && is_valid_signature_now(from, signed_hash, &signature)
+    }
+
+    /// Calls `to` with the `data` of `req` and its `from` address appended, sending `value` wei and
+    /// forwarding `gas` gas.
+    ///
+    /// Requirements:
+    /// - `req` must be signed by `from` with its current nonce.
+    /// - The value sent must be the `value` of `req`.
+    /// - The call must not revert. Its revert data is bubbled up.
+    /// - The relayer must provide enough gas for the call to receive `gas`.
+    #[payable]
+    pub fn execute(&mut self, req: ForwardRequest, signature: Bytes) -> Result<(), Vec<u8>> {
+        let (from, to, value, gas, _, data) = req.clone();
+        if msg::value() != value {
+            return Err(MinimalForwarderError::MinimalForwarderMismatchedValue(
+                MinimalForwarderMismatchedValue {
+                    requestedValue: value,
+                    msgValue: msg::value(),
+                },
+            )
+            .into());
+        }
+        if !self.verify(req, signature) {
+            return Err(MinimalForwarderError::MinimalForwarderInvalidRequest(
+                MinimalForwarderInvalidRequest { from },
+            )
+            .into());
+        }
+        self.nonces._use_nonce(from);
+        self.eip712._cache_domain_separator();
+
+        let mut calldata = data.0;
+        calldata.extend_from_slice(from.as_slice());
+        let gas_limit = u64::try_from(gas).unwrap_or(u64::MAX);
+        let result = RawCall::new_with_value(value)
+            .gas(gas_limit)
+            .call(to, &calldata);
+
+        // The call only receives 63/64 of the remaining gas, so a relayer could make it fail by
+        // providing too little gas. Fewer than `gas / 63` gas left means the call got less than
+        // `gas` and the request must be retried.
+        if evm::gas_left() <= gas_limit / 63 {
+            return Err(MinimalForwarderError::MinimalForwarderInsufficientGas(
+                MinimalForwarderInsufficientGas { requestedGas: gas },
+            )
+            .into());
+        }
+        result?;
+        Ok(())
+    }
+}
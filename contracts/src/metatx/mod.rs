@@ -0,0 +1,7 @@
+//! Meta-transaction support for gasless calls relayed by a trusted forwarder
+
+#[cfg(feature = "erc2771-context")]
+pub mod erc2771_context;
+
+#[cfg(feature = "forwarder")]
+pub mod forwarder;
@@ -0,0 +1,323 @@
+//! Ready-to-deploy ERC1155 collection of game items with role-gated minting and per-id supply caps.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/release-v4.9/contracts/token/ERC1155/presets/ERC1155PresetMinterPauser.sol
+//!
+//! Accounts with the {MINTER_ROLE} mint items, and accounts with the {URI_SETTER_ROLE} manage the
+//! URI of all items and the URIs of individual items, which take precedence. The admin sets the
+//! maximum supply of each item, where zero means the supply is uncapped. Holders and their approved
+//! operators can burn items, which frees up supply.
+//!
+//! `ERC1155GameItems` wraps `ERC1155` and `AccessControl`. Contracts must inherit
+//! `ERC1155GameItems`, `ERC1155` and `AccessControl` (in that order) so the overrides take
+//! precedence, and implement `Borrow`/`BorrowMut` of the wrapped types by returning the fields of
+//! the `ERC1155GameItems` storage. See `samples/src/erc1155_game_items_mock.rs` for a complete
+//! contract.
+//!
+//! The inheriting contract must expose an `initialize` method calling `_initialize`, which grants
+//! every role to the admin, see [initialization](crate#initialization). Until then, anyone can
+//! initialize the collection.
+
+use alloc::{string::String, vec::Vec};
+use core::borrow::BorrowMut;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::sol,
+    evm,
+    prelude::*,
+};
+
+use crate::access::access_control::{AccessControl, DEFAULT_ADMIN_ROLE};
+use crate::proxy::initializable::{Initializable, InitializableError};
+use crate::tokens::erc1155::{ERC1155Error, ERC1155InvalidReceiver, ERC1155, URI};
+
+/// The role allowed to mint items.
+pub const MINTER_ROLE: B256 = crate::const_keccak!("MINTER_ROLE");
+
+/// The role allowed to set the URIs of the items.
+pub const URI_SETTER_ROLE: B256 = crate::const_keccak!("URI_SETTER_ROLE");
+
+sol_storage! {
+    /// ERC1155GameItems is a collection of items with capped supplies and per-item URIs
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC1155GameItems"))]
+    pub struct ERC1155GameItems {
+        /// The underlying collection
+        ERC1155 erc1155;
+        /// The roles of the collection
+        AccessControl access;
+        /// Maps id to the amount of the item in existence
+        mapping(uint256 => uint256) total_supply;
+        /// Maps id to the maximum supply of the item, or zero if it is uncapped
+        mapping(uint256 => uint256) max_supply;
+        /// Maps id to the URI of the item, which overrides the URI of all items if not empty
+        mapping(uint256 => string) token_uris;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when the maximum supply of item `id` is set to `maxSupply`.
+    event MaxSupplySet(uint256 indexed id, uint256 maxSupply);
+
+    /// Minting `amount` of item `id` would exceed its `maxSupply`.
+    error ERC1155ExceededMaxSupply(uint256 id, uint256 amount, uint256 maxSupply);
+    /// The `maxSupply` of item `id` is lower than its `totalSupply`.
+    error ERC1155InvalidMaxSupply(uint256 id, uint256 maxSupply, uint256 totalSupply);
+}
+
+#[derive(SolidityError)]
+pub enum ERC1155GameItemsError {
+    ERC1155ExceededMaxSupply(ERC1155ExceededMaxSupply),
+    ERC1155InvalidMaxSupply(ERC1155InvalidMaxSupply),
+}
+
+// Internal functions
+impl ERC1155GameItems {
+    /// Grants the admin, minter and URI setter roles to `admin`, and sets the URI of all items to
+    /// `uri`.
+    ///
+    /// Requirements:
+    /// - The collection must not have been initialized.
+    pub fn _initialize(&mut self, admin: Address, uri: &str) -> Result<(), InitializableError> {
+        Initializable::_initializer_enter()?;
+        for role in [DEFAULT_ADMIN_ROLE, MINTER_ROLE, URI_SETTER_ROLE] {
+            self.access._grant_role(role, admin);
+        }
+        self.erc1155._set_uri(uri);
+        Initializable::_initializer_exit();
+        Ok(())
+    }
+
+    /// Records `amounts` of items `ids` minted.
+    ///
+    /// Requirements:
+    /// - The mint must not exceed the maximum supply of any item.
+    pub fn _use_supply(
+        &mut self,
+        ids: &[U256],
+        amounts: &[U256],
+    ) -> Result<(), ERC1155GameItemsError> {
+        for (&id, &amount) in ids.iter().zip(amounts) {
+            let max_supply = self.max_supply.get(id);
+            let total_supply = self.total_supply.get(id).saturating_add(amount);
+            if !max_supply.is_zero() && total_supply > max_supply {
+                return Err(ERC1155GameItemsError::ERC1155ExceededMaxSupply(
+                    ERC1155ExceededMaxSupply {
+                        id,
+                        amount,
+                        maxSupply: max_supply,
+                    },
+                ));
+            }
+            self.total_supply.insert(id, total_supply);
+        }
+        Ok(())
+    }
+
+    /// Mints `amounts` of items `ids` to `to`.
+    ///
+    /// Requirements:
+    /// - `to` cannot be the zero address.
+    /// - `ids` and `amounts` must have the same length.
+    /// - The mint must not exceed the maximum supply of any item.
+    /// - If `to` refers to a smart contract, it must implement the `IERC1155Receiver` hooks.
+    ///
+    /// Emits a {TransferSingle} or a {TransferBatch} event.
+    pub fn _mint_batch<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        to: Address,
+        ids: &[U256],
+        amounts: &[U256],
+        data: Vec<u8>,
+    ) -> Result<(), Vec<u8>> {
+        if to.is_zero() {
+            return Err(
+                ERC1155Error::ERC1155InvalidReceiver(ERC1155InvalidReceiver { receiver: to })
+                    .into(),
+            );
+        }
+        let items = storage.borrow_mut();
+        items._use_supply(ids, amounts)?;
+        items.erc1155._update(Address::ZERO, to, ids, amounts)?;
+        ERC1155::_call_receiver(storage, Address::ZERO, to, ids, amounts, data)?;
+        Ok(())
+    }
+
+    /// Burns `amounts` of items `ids` from `account`, freeing up their supply.
+    ///
+    /// Requirements:
+    /// - `ids` and `amounts` must have the same length.
+    /// - `account` must have at least `amounts` of each of the items.
+    ///
+    /// Emits a {TransferSingle} or a {TransferBatch} event.
+    pub fn _burn_batch(
+        &mut self,
+        account: Address,
+        ids: &[U256],
+        amounts: &[U256],
+    ) -> Result<(), ERC1155Error> {
+        self.erc1155._burn_batch(account, ids, amounts)?;
+        for (&id, &amount) in ids.iter().zip(amounts) {
+            // Cannot underflow, as the burned amount was held by `account`
+            let total_supply = self.total_supply.get(id) - amount;
+            self.total_supply.insert(id, total_supply);
+        }
+        Ok(())
+    }
+}
+
+// External functions
+#[external]
+impl ERC1155GameItems {
+    /// Returns the URI of item `id`, or the URI of all items if it has no URI of its own.
+    pub fn uri(&self, id: U256) -> String {
+        let token_uri = self.token_uris.getter(id).get_string();
+        if token_uri.is_empty() {
+            return self.erc1155.uri(id);
+        }
+        token_uri
+    }
+
+    /// Returns the amount of item `id` in existence.
+    pub fn total_supply(&self, id: U256) -> U256 {
+        self.total_supply.get(id)
+    }
+
+    /// Returns the maximum supply of item `id`, or zero if it is uncapped.
+    pub fn max_supply(&self, id: U256) -> U256 {
+        self.max_supply.get(id)
+    }
+
+    /// Returns whether any amount of item `id` exists.
+    pub fn exists(&self, id: U256) -> bool {
+        !self.total_supply.get(id).is_zero()
+    }
+
+    /// Mints `amount` of item `id` to `to`.
+    ///
+    /// Requirements:
+    /// - The caller must have the {MINTER_ROLE}.
+    /// - `to` cannot be the zero address.
+    /// - The mint must not exceed the maximum supply of the item.
+    /// - If `to` refers to a smart contract, it must implement
+    ///   {IERC1155Receiver-onERC1155Received}.
+    ///
+    /// Emits a {TransferSingle} event.
+    pub fn mint<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        to: Address,
+        id: U256,
+        amount: U256,
+        data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        storage.borrow_mut().access.only_role(MINTER_ROLE)?;
+        Self::_mint_batch(storage, to, &[id], &[amount], data.0)
+    }
+
+    /// Mints `amounts` of items `ids` to `to`.
+    ///
+    /// Requirements:
+    /// - The caller must have the {MINTER_ROLE}.
+    /// - `to` cannot be the zero address.
+    /// - `ids` and `amounts` must have the same length.
+    /// - The mint must not exceed the maximum supply of any item.
+    /// - If `to` refers to a smart contract, it must implement the `IERC1155Receiver` hooks.
+    ///
+    /// Emits a {TransferSingle} or a {TransferBatch} event.
+    pub fn mint_batch<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        to: Address,
+        ids: Vec<U256>,
+        amounts: Vec<U256>,
+        data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        storage.borrow_mut().access.only_role(MINTER_ROLE)?;
+        Self::_mint_batch(storage, to, &ids, &amounts, data.0)
+    }
+
+    /// Burns `amount` of item `id` from `account`.
+    ///
+    /// Requirements:
+    /// - The caller must be `account` or an approved operator of `account`.
+    /// - `account` must have at least `amount` of the item.
+    ///
+    /// Emits a {TransferSingle} event.
+    pub fn burn(&mut self, account: Address, id: U256, amount: U256) -> Result<(), ERC1155Error> {
+        self.erc1155._require_owner_or_approved(account)?;
+        self._burn_batch(account, &[id], &[amount])
+    }
+
+    /// Burns `amounts` of items `ids` from `account`.
+    ///
+    /// Requirements:
+    /// - The caller must be `account` or an approved operator of `account`.
+    /// - `ids` and `amounts` must have the same length.
+    /// - `account` must have at least `amounts` of each of the items.
+    ///
+    /// Emits a {TransferSingle} or a {TransferBatch} event.
+    pub fn burn_batch(
+        &mut self,
+        account: Address,
+        ids: Vec<U256>,
+        amounts: Vec<U256>,
+    ) -> Result<(), ERC1155Error> {
+        self.erc1155._require_owner_or_approved(account)?;
+        self._burn_batch(account, &ids, &amounts)
+    }
+
+    /// Sets the maximum supply of item `id` to `max_supply`, or uncaps it if `max_supply` is zero.
+    ///
+    /// Requirements:
+    /// - The caller must have the {DEFAULT_ADMIN_ROLE}.
+    /// - `max_supply` must be zero or at least the amount of the item in existence.
+    ///
+    /// Emits a {MaxSupplySet} event.
+    pub fn set_max_supply(&mut self, id: U256, max_supply: U256) -> Result<(), Vec<u8>> {
+        self.access.only_role(DEFAULT_ADMIN_ROLE)?;
+        let total_supply = self.total_supply.get(id);
+        if !max_supply.is_zero() && max_supply < total_supply {
+            return Err(
+                ERC1155GameItemsError::ERC1155InvalidMaxSupply(ERC1155InvalidMaxSupply {
+                    id,
+                    maxSupply: max_supply,
+                    totalSupply: total_supply,
+                })
+                .into(),
+            );
+        }
+        self.max_supply.insert(id, max_supply);
+        evm::log(MaxSupplySet {
+            id,
+            maxSupply: max_supply,
+        });
+        Ok(())
+    }
+
+    /// Sets the URI of all items to `new_uri`, in which clients replace `{id}` by the id of the
+    /// item.
+    ///
+    /// Requirements:
+    /// - The caller must have the {URI_SETTER_ROLE}.
+    pub fn set_uri(&mut self, new_uri: String) -> Result<(), Vec<u8>> {
+        self.access.only_role(URI_SETTER_ROLE)?;
+        self.erc1155._set_uri(&new_uri);
+        Ok(())
+    }
+
+    /// Sets the URI of item `id` to `token_uri`, or resets it to the URI of all items if
+    /// `token_uri` is empty.
+    ///
+    /// Requirements:
+    /// - The caller must have the {URI_SETTER_ROLE}.
+    ///
+    /// Emits a {URI} event.
+    pub fn set_token_uri(&mut self, id: U256, token_uri: String) -> Result<(), Vec<u8>> {
+        self.access.only_role(URI_SETTER_ROLE)?;
+        self.token_uris.setter(id).set_str(&token_uri);
+        evm::log(URI {
+            value: self.uri(id),
+            id,
+        });
+        Ok(())
+    }
+}
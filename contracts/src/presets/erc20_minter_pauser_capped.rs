@@ -0,0 +1,227 @@
+//! Ready-to-deploy ERC20 token with role-gated minting and burning, pausing and a capped supply.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/release-v4.9/contracts/token/ERC20/presets/ERC20PresetMinterPauser.sol
+//!
+//! The token supports EIP-2612 permits through `ERC20`. Accounts with the {MINTER_ROLE} can mint
+//! tokens up to `CAP`, accounts with the {BURNER_ROLE} can burn their own tokens or the tokens they
+//! are approved for, and accounts with the {PAUSER_ROLE} can pause every transfer, mint and burn.
+//! Approvals and permits still work while the token is paused.
+//!
+//! `ERC20MinterPauserCapped` wraps `ERC20`, `AccessControl` and `Pausable`. Contracts must inherit
+//! `ERC20MinterPauserCapped<T>`, `ERC20<T>`, `AccessControl` and `Pausable` (in that order) so the
+//! overrides take precedence, and implement `Borrow`/`BorrowMut` of the wrapped types by returning
+//! the fields of the `ERC20MinterPauserCapped` storage. See `samples/src/erc20_preset_mock.rs` for
+//! a complete contract.
+//!
+//! The inheriting contract must expose an `initialize` method calling `_initialize`, which grants
+//! every role to the admin, see [initialization](crate#initialization). Until then, anyone can
+//! initialize the token.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::sol,
+    msg,
+    prelude::*,
+};
+
+use crate::access::access_control::{AccessControl, DEFAULT_ADMIN_ROLE};
+use crate::proxy::initializable::{Initializable, InitializableError};
+use crate::security::pausable::{EnforcedPause, ExpectedPause, Pausable, PausableError};
+use crate::tokens::erc20::{ERC20Params, ERC20};
+
+pub trait ERC20PresetParams: ERC20Params {
+    /// The maximum total supply of the token.
+    const CAP: U256;
+}
+
+/// The role allowed to mint tokens.
+pub const MINTER_ROLE: B256 = crate::const_keccak!("MINTER_ROLE");
+
+/// The role allowed to burn tokens.
+pub const BURNER_ROLE: B256 = crate::const_keccak!("BURNER_ROLE");
+
+/// The role allowed to pause and unpause the token.
+pub const PAUSER_ROLE: B256 = crate::const_keccak!("PAUSER_ROLE");
+
+sol_storage! {
+    /// ERC20MinterPauserCapped is a capped and pausable token with minter, burner and pauser roles
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC20MinterPauserCapped"))]
+    pub struct ERC20MinterPauserCapped<T> {
+        /// The underlying token
+        ERC20<T> erc20;
+        /// The roles of the token
+        AccessControl access;
+        /// The pause state, which stops every balance change
+        Pausable pausable;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Minting would increase the total supply to `increasedSupply`, above the `cap`.
+    error ERC20ExceededCap(uint256 increasedSupply, uint256 cap);
+}
+
+#[derive(SolidityError)]
+pub enum ERC20PresetError {
+    ERC20ExceededCap(ERC20ExceededCap),
+    EnforcedPause(EnforcedPause),
+    ExpectedPause(ExpectedPause),
+}
+
+impl From<PausableError> for ERC20PresetError {
+    fn from(err: PausableError) -> Self {
+        match err {
+            PausableError::EnforcedPause(e) => Self::EnforcedPause(e),
+            PausableError::ExpectedPause(e) => Self::ExpectedPause(e),
+        }
+    }
+}
+
+// Internal functions
+impl<T: ERC20PresetParams> ERC20MinterPauserCapped<T> {
+    /// Grants the admin, minter, burner and pauser roles to `admin`.
+    ///
+    /// Requirements:
+    /// - The token must not have been initialized.
+    pub fn _initialize(&mut self, admin: Address) -> Result<(), InitializableError> {
+        Initializable::_initializer_enter()?;
+        for role in [DEFAULT_ADMIN_ROLE, MINTER_ROLE, BURNER_ROLE, PAUSER_ROLE] {
+            self.access._grant_role(role, admin);
+        }
+        Initializable::_initializer_exit();
+        Ok(())
+    }
+
+    /// Mints `amount` tokens to `to`.
+    ///
+    /// Requirements:
+    /// - The token must not be paused.
+    /// - The total supply must not exceed `CAP`.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn _mint(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self.pausable.require_not_paused()?;
+        let increased_supply = self.erc20.total_supply().saturating_add(amount);
+        if increased_supply > T::CAP {
+            return Err(ERC20PresetError::ERC20ExceededCap(ERC20ExceededCap {
+                increasedSupply: increased_supply,
+                cap: T::CAP,
+            })
+            .into());
+        }
+        ERC20::_mint(&mut self.erc20, to, amount)
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC20PresetParams> ERC20MinterPauserCapped<T> {
+    /// Returns the maximum total supply of the token.
+    pub fn cap(&self) -> U256 {
+        T::CAP
+    }
+
+    /// Transfer `amount` tokens from the caller to `to`.
+    ///
+    /// Requirements:
+    /// - The token must not be paused.
+    /// - The caller must at least have `amount`.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn transfer(&mut self, to: Address, amount: U256) -> Result<bool, Vec<u8>> {
+        self.pausable.require_not_paused()?;
+        self.erc20.transfer(to, amount)
+    }
+
+    /// Transfers `amount` tokens from `from` to `to`.
+    ///
+    /// Requirements:
+    /// - The token must not be paused.
+    /// - `from` must at least have `amount`.
+    /// - The caller must have at least `amount` of allowance to transfer the tokens of `from`.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<bool, Vec<u8>> {
+        self.pausable.require_not_paused()?;
+        self.erc20.transfer_from(from, to, amount)
+    }
+
+    /// Mints `amount` tokens to `to`.
+    ///
+    /// Requirements:
+    /// - The caller must have the {MINTER_ROLE}.
+    /// - The token must not be paused.
+    /// - The total supply must not exceed the cap.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn mint(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self.access.only_role(MINTER_ROLE)?;
+        self._mint(to, amount)
+    }
+
+    /// Burns `amount` tokens of the caller.
+    ///
+    /// Requirements:
+    /// - The caller must have the {BURNER_ROLE}.
+    /// - The token must not be paused.
+    /// - The caller must at least have `amount`.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn burn(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        self.access.only_role(BURNER_ROLE)?;
+        self.pausable.require_not_paused()?;
+        ERC20::_burn(&mut self.erc20, msg::sender(), amount)?;
+        Ok(())
+    }
+
+    /// Burns `amount` tokens of `account`, spending the allowance of the caller.
+    ///
+    /// Requirements:
+    /// - The caller must have the {BURNER_ROLE}.
+    /// - The token must not be paused.
+    /// - `account` must at least have `amount`.
+    /// - The caller must have at least `amount` of allowance to burn the tokens of `account`.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn burn_from(&mut self, account: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self.access.only_role(BURNER_ROLE)?;
+        self.pausable.require_not_paused()?;
+        self.erc20
+            ._spend_allowance(account, msg::sender(), amount)?;
+        ERC20::_burn(&mut self.erc20, account, amount)?;
+        Ok(())
+    }
+
+    /// Pauses every transfer, mint and burn.
+    ///
+    /// Requirements:
+    /// - The caller must have the {PAUSER_ROLE}.
+    /// - The token must not be paused.
+    ///
+    /// Emits a {Paused} event.
+    pub fn pause(&mut self) -> Result<(), Vec<u8>> {
+        self.access.only_role(PAUSER_ROLE)?;
+        self.pausable._pause()?;
+        Ok(())
+    }
+
+    /// Unpauses the token.
+    ///
+    /// Requirements:
+    /// - The caller must have the {PAUSER_ROLE}.
+    /// - The token must be paused.
+    ///
+    /// Emits an {Unpaused} event.
+    pub fn unpause(&mut self) -> Result<(), Vec<u8>> {
+        self.access.only_role(PAUSER_ROLE)?;
+        self.pausable._unpause()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+//! Ready-to-deploy NFT drop with a max supply, sale phases, ERC2981 royalties and withdrawals.
+//! The logic was based off of: https://github.com/thirdweb-dev/contracts/blob/main/contracts/prebuilts/drop/DropERC721.sol
+//!
+//! The collection is sold through the phases of `MintPhases` (public, merkle allowlist and
+//! signature allowlist), which the owner configures and whose proceeds the owner withdraws. The
+//! mint methods are overridden to cap the number of tokens minted at `MAX_SUPPLY`, and the owner
+//! manages the default and per-token royalties.
+//!
+//! The library does not ship an ERC721A base yet, so the drop is built on `ERC721` and mints tokens
+//! one by one with sequential ids starting from 0.
+//!
+//! `ERC721Drop` wraps `MintPhases` (which wraps `ERC721` and `Ownable`) and `ERC2981`. Contracts
+//! must inherit `ERC721Drop<T>`, `MintPhases<T>`, `ERC721<T>`, `Ownable` and `ERC2981` (in that
+//! order) so the overrides take precedence, and implement `Borrow`/`BorrowMut` of the wrapped types
+//! by returning the fields of the `ERC721Drop` storage. See `samples/src/erc721_drop_mock.rs` for
+//! a complete contract.
+//!
+//! The inheriting contract must expose an `initialize` method calling `_initialize`, which sets the
+//! owner and the default royalty, see [initialization](crate#initialization). Until then, anyone
+//! can initialize the drop.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+};
+
+use crate::tokens::erc721::{ERC721Params, ERC721};
+use crate::tokens::extensions::erc2981::ERC2981;
+use crate::tokens::extensions::mint_phases::MintPhases;
+use crate::utils::introspection::{self, InterfaceIds};
+
+pub trait ERC721DropParams: ERC721Params {
+    /// The maximum number of tokens that can be minted.
+    const MAX_SUPPLY: u64;
+}
+
+sol_storage! {
+    /// ERC721Drop sells a capped collection through sale phases, and pays royalties on resales
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC721Drop"))]
+    pub struct ERC721Drop<T: ERC721Params> {
+        /// The sale phases, which wrap the collection and its owner
+        MintPhases<T> phases;
+        /// The royalties of the collection
+        ERC2981 royalties;
+        /// The number of tokens minted
+        uint256 total_minted;
+    }
+}
+
+// Declare Solidity error types
+sol! {
+    /// The mint would exceed the maximum supply.
+    error ExceedsMaxSupply(uint256 maxSupply);
+}
+
+#[derive(SolidityError)]
+pub enum ERC721DropError {
+    ExceedsMaxSupply(ExceedsMaxSupply),
+}
+
+// Internal functions
+impl<T: ERC721DropParams> ERC721Drop<T> {
+    /// Sets `owner` as the owner and the default royalty to `royalty_fee` basis points paid to
+    /// `royalty_receiver`.
+    ///
+    /// Requirements:
+    /// - The owner must not have been initialized.
+    /// - `royalty_receiver` must not be the zero address.
+    /// - `royalty_fee` must not be greater than the fee denominator.
+    pub fn _initialize(
+        &mut self,
+        owner: Address,
+        royalty_receiver: Address,
+        royalty_fee: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.phases.ownable._initialize_owner(owner)?;
+        self.royalties
+            ._set_default_royalty(royalty_receiver, royalty_fee)?;
+        Ok(())
+    }
+
+    /// Records `quantity` tokens minted.
+    ///
+    /// Requirements:
+    /// - The mint must not exceed the maximum supply.
+    pub fn _use_supply(&mut self, quantity: U256) -> Result<(), ERC721DropError> {
+        let total_minted = self.total_minted.get().saturating_add(quantity);
+        if total_minted > U256::from(T::MAX_SUPPLY) {
+            return Err(ERC721DropError::ExceedsMaxSupply(ExceedsMaxSupply {
+                maxSupply: U256::from(T::MAX_SUPPLY),
+            }));
+        }
+        self.total_minted.set(total_minted);
+        Ok(())
+    }
+}
+
+impl<T: ERC721Params> InterfaceIds for ERC721Drop<T> {
+    fn implements_interface(id: u32) -> bool {
+        <(ERC721<T>, ERC2981)>::implements_interface(id)
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC721DropParams> ERC721Drop<T> {
+    /// Returns the maximum number of tokens that can be minted.
+    pub fn max_supply(&self) -> U256 {
+        U256::from(T::MAX_SUPPLY)
+    }
+
+    /// Returns the number of tokens minted.
+    pub fn total_minted(&self) -> U256 {
+        self.total_minted.get()
+    }
+
+    /// Mints `quantity` tokens to the caller in the public phase `phase_id`.
+    /// See `MintPhases::mint_public`.
+    ///
+    /// Requirements:
+    /// - The mint must not exceed the maximum supply.
+    #[payable]
+    pub fn mint_public(&mut self, phase_id: U256, quantity: U256) -> Result<(), Vec<u8>> {
+        self._use_supply(quantity)?;
+        self.phases.mint_public(phase_id, quantity)
+    }
+
+    /// Mints `quantity` tokens to the caller in the merkle allowlist phase `phase_id`.
+    /// See `MintPhases::mint_allowlist`.
+    ///
+    /// Requirements:
+    /// - The mint must not exceed the maximum supply.
+    #[payable]
+    pub fn mint_allowlist(
+        &mut self,
+        phase_id: U256,
+        quantity: U256,
+        proof: Vec<B256>,
+    ) -> Result<(), Vec<u8>> {
+        self._use_supply(quantity)?;
+        self.phases.mint_allowlist(phase_id, quantity, proof)
+    }
+
+    /// Mints `quantity` tokens to the caller in the signature allowlist phase `phase_id`.
+    /// See `MintPhases::mint_with_signature`.
+    ///
+    /// Requirements:
+    /// - The mint must not exceed the maximum supply.
+    #[payable]
+    pub fn mint_with_signature(
+        &mut self,
+        phase_id: U256,
+        quantity: U256,
+        signature: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        self._use_supply(quantity)?;
+        self.phases
+            .mint_with_signature(phase_id, quantity, signature)
+    }
+
+    /// Sets the default royalty to `fee_numerator` basis points paid to `receiver`.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    /// - `receiver` must not be the zero address.
+    /// - `fee_numerator` must not be greater than the fee denominator.
+    pub fn set_default_royalty(
+        &mut self,
+        receiver: Address,
+        fee_numerator: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.phases.ownable.only_owner()?;
+        self.royalties
+            ._set_default_royalty(receiver, fee_numerator)?;
+        Ok(())
+    }
+
+    /// Sets the royalty of token `id` to `fee_numerator` basis points paid to `receiver`.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    /// - `receiver` must not be the zero address.
+    /// - `fee_numerator` must not be greater than the fee denominator.
+    pub fn set_token_royalty(
+        &mut self,
+        id: U256,
+        receiver: Address,
+        fee_numerator: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.phases.ownable.only_owner()?;
+        self.royalties
+            ._set_token_royalty(id, receiver, fee_numerator)?;
+        Ok(())
+    }
+
+    /// Resets the royalty of token `id` back to the default royalty.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    pub fn reset_token_royalty(&mut self, id: U256) -> Result<(), Vec<u8>> {
+        self.phases.ownable.only_owner()?;
+        self.royalties._reset_token_royalty(id);
+        Ok(())
+    }
+
+    /// Returns true if this contract implements the interface defined by `interfaceId`.
+    /// See: https://eips.ethereum.org/EIPS/eip-165
+    pub fn supports_interface(interface: [u8; 4]) -> bool {
+        introspection::supports_interface::<Self>(interface)
+    }
+}
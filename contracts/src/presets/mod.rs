@@ -0,0 +1,10 @@
+//! Ready-to-deploy contracts combining the building blocks
+
+#[cfg(feature = "erc20-preset")]
+pub mod erc20_minter_pauser_capped;
+
+#[cfg(feature = "erc721-drop")]
+pub mod erc721_drop;
+
+#[cfg(feature = "erc1155-game-items")]
+pub mod erc1155_game_items;
@@ -0,0 +1,140 @@
+//! Initializer guards for contracts deployed without a constructor (ex. behind proxies or clones).
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/proxy/utils/Initializable.sol
+//!
+//! Contracts deployed behind proxies or as clones are configured through an `initialize` method
+//! (see [initialization](crate#initialization)). The guards below make sure such methods run at
+//! most once per version, and that internal initialization steps of base contracts can only be
+//! called while an initializer is running.
+//!
+//! Stylus does not have modifiers, so an initializer must call `_initializer_enter` (or
+//! `_reinitializer_enter`) before its logic and `_initializer_exit` after it. Nested initializers
+//! are not supported; base contracts should guard their initialization steps with
+//! `only_initializing` instead.
+//!
+//! The state is kept in the ERC-7201 namespaced slot used by OpenZeppelin, so it never collides
+//! with `sol_storage!` fields and does not need to be part of the contract's storage struct.
+
+use stylus_sdk::{
+    alloy_primitives::{fixed_bytes, B256, U256, U64},
+    alloy_sol_types::sol,
+    evm,
+    prelude::*,
+    storage::{StorageBool, StorageU64},
+};
+
+/// The storage slot of the initializable state,
+/// `keccak256(abi.encode(uint256(keccak256("openzeppelin.storage.Initializable")) - 1)) & ~bytes32(uint256(0xff))`.
+pub const INITIALIZABLE_SLOT: B256 =
+    fixed_bytes!("f0c57e16840df040f15088dc2f81fe391c3923bec73e23a9662efc9c229c6a00");
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when the contract has been initialized or reinitialized to `version`.
+    event Initialized(uint64 version);
+
+    /// The contract is already initialized.
+    error InvalidInitialization();
+    /// The contract is not initializing.
+    error NotInitializing();
+}
+
+#[derive(SolidityError)]
+pub enum InitializableError {
+    InvalidInitialization(InvalidInitialization),
+    NotInitializing(NotInitializing),
+}
+
+/// Initializer guards stored in a dedicated slot.
+pub struct Initializable;
+
+impl Initializable {
+    /// Starts the initializer of the contract, which can only run once.
+    /// Every successful call must be paired with a call to `_initializer_exit`.
+    ///
+    /// Requirements:
+    /// - The contract must not have been initialized.
+    pub fn _initializer_enter() -> Result<(), InitializableError> {
+        Self::_reinitializer_enter(U64::from(1))
+    }
+
+    /// Starts a reinitializer of the contract to `version`, which allows a new initialization
+    /// step to run after an upgrade. Every successful call must be paired with a call to
+    /// `_initializer_exit`.
+    ///
+    /// Requirements:
+    /// - No initializer must be running.
+    /// - The contract must not have been initialized to `version` or a later version.
+    pub fn _reinitializer_enter(version: U64) -> Result<(), InitializableError> {
+        let mut initialized = Self::_initialized();
+        let mut initializing = Self::_initializing();
+        if initializing.get() || initialized.get() >= version {
+            return Err(InitializableError::InvalidInitialization(
+                InvalidInitialization {},
+            ));
+        }
+        initialized.set(version);
+        initializing.set(true);
+        Ok(())
+    }
+
+    /// Ends the running initializer.
+    ///
+    /// Emits an {Initialized} event.
+    pub fn _initializer_exit() {
+        Self::_initializing().set(false);
+        evm::log(Initialized {
+            version: Self::_initialized().get().to(),
+        });
+    }
+
+    /// Throws if no initializer is running.
+    pub fn only_initializing() -> Result<(), InitializableError> {
+        if !Self::_initializing().get() {
+            return Err(InitializableError::NotInitializing(NotInitializing {}));
+        }
+        Ok(())
+    }
+
+    /// Locks the contract, preventing any future (re)initialization. This is recommended for
+    /// implementation contracts, so that they cannot be taken over by initializing them directly.
+    ///
+    /// Requirements:
+    /// - No initializer must be running.
+    ///
+    /// Emits an {Initialized} event the first time it is successfully called.
+    pub fn _disable_initializers() -> Result<(), InitializableError> {
+        if Self::_initializing().get() {
+            return Err(InitializableError::InvalidInitialization(
+                InvalidInitialization {},
+            ));
+        }
+        let mut initialized = Self::_initialized();
+        if initialized.get() != U64::MAX {
+            initialized.set(U64::MAX);
+            evm::log(Initialized { version: u64::MAX });
+        }
+        Ok(())
+    }
+
+    /// Returns the highest version that has been initialized.
+    pub fn _get_initialized_version() -> U64 {
+        Self::_initialized().get()
+    }
+
+    /// Returns whether an initializer is running.
+    pub fn _is_initializing() -> bool {
+        Self::_initializing().get()
+    }
+
+    /// Returns the accessor of the initialized version, packed first in the namespaced slot.
+    fn _initialized() -> StorageU64 {
+        // SAFETY: the namespaced slot is never allocated by `sol_storage!` structs
+        unsafe { StorageU64::new(U256::from_be_bytes(INITIALIZABLE_SLOT.0), 0) }
+    }
+
+    /// Returns the accessor of the initializing flag, packed after the initialized version.
+    fn _initializing() -> StorageBool {
+        // SAFETY: the namespaced slot is never allocated by `sol_storage!` structs
+        unsafe { StorageBool::new(U256::from_be_bytes(INITIALIZABLE_SLOT.0), 8) }
+    }
+}
@@ -0,0 +1,10 @@
+//! Upgradeability contracts for use behind proxies
+
+#[cfg(feature = "initializable")]
+pub mod initializable;
+
+#[cfg(feature = "proxy-admin")]
+pub mod proxy_admin;
+
+#[cfg(feature = "uups")]
+pub mod uups;
@@ -0,0 +1,113 @@
+//! Owner contract holding the upgrade rights of transparent and UUPS proxies.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/proxy/transparent/ProxyAdmin.sol
+//!
+//! Rather than an EOA, the `ProxyAdmin` is set as the admin of transparent proxies, or as the
+//! account authorized to upgrade UUPS proxies (ex. the owner of `UUPSUpgradeable` contracts guarded
+//! by `Ownable::only_owner`). Its owner then upgrades the proxies through it, which keeps the
+//! upgrade rights separate from the accounts using the proxies: the admin of a transparent proxy
+//! cannot call its implementation. The ownership of the `ProxyAdmin` can be transferred, or given to
+//! a timelock or a multisig, to move the upgrade rights of every proxy at once.
+//!
+//! `ProxyAdmin` wraps `Ownable`. Contracts must inherit `ProxyAdmin` and `Ownable`, and implement
+//! `Borrow`/`BorrowMut` of `Ownable` by returning the `ownable` field of the `ProxyAdmin` storage.
+//! The owner is set by `_initialize`, see [initialization](crate#initialization). Until then,
+//! anyone can become the owner.
+
+use alloc::{string::String, vec::Vec};
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::Address,
+    alloy_sol_types::{sol, SolCall},
+    call::RawCall,
+    msg,
+    prelude::*,
+};
+
+use crate::access::ownable::{Ownable, OwnableError};
+
+/// The version of the upgrade interface of the contract.
+pub const UPGRADE_INTERFACE_VERSION: &str = "5.0.0";
+
+sol_storage! {
+    /// ProxyAdmin upgrades the proxies it administers on behalf of its owner
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ProxyAdmin"))]
+    pub struct ProxyAdmin {
+        /// The owner, who upgrades the proxies and changes their admins
+        Ownable ownable;
+    }
+}
+
+// Define the functions of the proxies
+sol! {
+    function upgradeToAndCall(address newImplementation, bytes data) external payable;
+    function changeAdmin(address newAdmin) external;
+}
+
+// Internal functions
+impl ProxyAdmin {
+    /// Sets `owner` as the owner of the admin.
+    ///
+    /// Requirements:
+    /// - The owner must not have been initialized.
+    ///
+    /// Emits an {OwnershipTransferred} event.
+    pub fn _initialize(&mut self, owner: Address) -> Result<(), OwnableError> {
+        self.ownable._initialize_owner(owner)
+    }
+}
+
+// External functions
+#[external]
+impl ProxyAdmin {
+    /// Returns the version of the upgrade interface of the contract. If it is `5.0.0`, the
+    /// upgrades go through `upgradeAndCall` only, which upgrades the proxies with
+    /// `upgradeToAndCall` even if `data` is empty.
+    #[selector(name = "UPGRADE_INTERFACE_VERSION")]
+    pub fn upgrade_interface_version() -> String {
+        UPGRADE_INTERFACE_VERSION.into()
+    }
+
+    /// Upgrades `proxy` to `implementation`, and calls `implementation` with `data` through the
+    /// proxy if it is not empty (ex. to reinitialize the contract). The value sent is forwarded
+    /// to the proxy. Reverts with the error of the proxy if the upgrade fails.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    /// - The admin must be the admin of `proxy`, or authorized to upgrade it.
+    #[payable]
+    pub fn upgrade_and_call(
+        &mut self,
+        proxy: Address,
+        implementation: Address,
+        data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        let call = upgradeToAndCallCall {
+            newImplementation: implementation,
+            data: data.0,
+        };
+        RawCall::new_with_value(msg::value()).call(proxy, &call.encode())?;
+        Ok(())
+    }
+
+    /// Changes the admin of `proxy` to `new_admin`, for transparent proxies implementing
+    /// `changeAdmin` (ex. OpenZeppelin's before v5). Reverts with the error of the proxy if the
+    /// change fails.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    /// - The admin must be the admin of `proxy`.
+    pub fn change_proxy_admin(
+        &mut self,
+        proxy: Address,
+        new_admin: Address,
+    ) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        let call = changeAdminCall {
+            newAdmin: new_admin,
+        };
+        RawCall::new().call(proxy, &call.encode())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+//! UUPS (ERC-1822) upgradeability mixin for implementations behind an ERC-1967 proxy.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/proxy/utils/UUPSUpgradeable.sol
+//!
+//! The upgrade logic lives in the implementation rather than in the proxy. Stylus does not have
+//! modifiers, so the inheriting contract must expose its own `upgrade_to_and_call` method which
+//! authorizes the caller (ex. with `Ownable::only_owner`) before calling `_upgrade_to_and_call`.
+//!
+//! Stylus does not have immutables, so an implementation cannot know its own address to detect
+//! whether it is being delegated to. Instead, a call is considered to come through a proxy if the
+//! ERC-1967 implementation slot is set in the current storage, which is never the case for the
+//! implementation's own storage.
+//!
+//! Upgradeable contracts are initialized through an `initialize` method guarded by
+//! [`Initializable`](crate::proxy::initializable::Initializable), see
+//! [initialization](crate#initialization).
+
+use alloc::{string::String, vec::Vec};
+use stylus_sdk::{
+    alloy_primitives::{fixed_bytes, Address, B256, U256},
+    alloy_sol_types::{sol, SolCall},
+    call::RawCall,
+    evm,
+    prelude::*,
+    storage::{StorageAddress, StorageCache},
+};
+
+/// The ERC-1967 storage slot of the implementation address,
+/// `keccak256("eip1967.proxy.implementation") - 1`.
+pub const IMPLEMENTATION_SLOT: B256 =
+    fixed_bytes!("360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc");
+
+/// The ERC-1967 storage slot of the proxy admin address, `keccak256("eip1967.proxy.admin") - 1`.
+pub const ADMIN_SLOT: B256 =
+    fixed_bytes!("b53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103");
+
+/// The ERC-1967 storage slot of the beacon address, `keccak256("eip1967.proxy.beacon") - 1`.
+pub const BEACON_SLOT: B256 =
+    fixed_bytes!("a3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50");
+
+/// The version of the upgrade interface of the contract.
+pub const UPGRADE_INTERFACE_VERSION: &str = "5.0.0";
+
+sol_storage! {
+    /// UUPSUpgradeable keeps all of its state in the ERC-1967 slots
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.UUPSUpgradeable"))]
+    pub struct UUPSUpgradeable {}
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when the implementation is upgraded.
+    event Upgraded(address indexed implementation);
+
+    /// The `implementation` of the proxy is invalid.
+    error ERC1967InvalidImplementation(address implementation);
+    /// The call is from an unauthorized context.
+    error UUPSUnauthorizedCallContext();
+    /// The storage `slot` is unsupported as a UUID.
+    error UUPSUnsupportedProxiableUUID(bytes32 slot);
+
+    function proxiableUUID() external view returns (bytes32);
+}
+
+#[derive(SolidityError)]
+pub enum UUPSError {
+    ERC1967InvalidImplementation(ERC1967InvalidImplementation),
+    UUPSUnauthorizedCallContext(UUPSUnauthorizedCallContext),
+    UUPSUnsupportedProxiableUUID(UUPSUnsupportedProxiableUUID),
+}
+
+// Internal functions
+impl UUPSUpgradeable {
+    /// Throws if the call is not made through an ERC-1967 proxy.
+    pub fn only_proxy(&self) -> Result<(), UUPSError> {
+        if Self::_get_implementation().is_zero() {
+            return Err(UUPSError::UUPSUnauthorizedCallContext(
+                UUPSUnauthorizedCallContext {},
+            ));
+        }
+        Ok(())
+    }
+
+    /// Throws if the call is made through a proxy.
+    pub fn not_delegated(&self) -> Result<(), UUPSError> {
+        if !Self::_get_implementation().is_zero() {
+            return Err(UUPSError::UUPSUnauthorizedCallContext(
+                UUPSUnauthorizedCallContext {},
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the current implementation address stored in the ERC-1967 slot.
+    pub fn _get_implementation() -> Address {
+        Self::_implementation_slot().get()
+    }
+
+    /// Upgrades the implementation of the proxy to `new_implementation`, and subsequently
+    /// delegates a call to it with `data` if it is not empty (ex. to reinitialize the contract).
+    /// The caller must be authorized beforehand.
+    ///
+    /// Requirements:
+    /// - The call must be made through a proxy.
+    /// - `new_implementation` must be a UUPS implementation with a matching `proxiableUUID`,
+    ///   which prevents upgrading to a contract that cannot be upgraded further.
+    ///
+    /// Emits an {Upgraded} event.
+    pub fn _upgrade_to_and_call(
+        &mut self,
+        new_implementation: Address,
+        data: &[u8],
+    ) -> Result<(), Vec<u8>> {
+        self.only_proxy()?;
+
+        let invalid_implementation = || {
+            UUPSError::ERC1967InvalidImplementation(ERC1967InvalidImplementation {
+                implementation: new_implementation,
+            })
+        };
+        let uuid = RawCall::new_static()
+            .call(new_implementation, &proxiableUUIDCall {}.encode())
+            .map_err(|_| invalid_implementation())?;
+        if uuid.len() != 32 {
+            return Err(invalid_implementation().into());
+        }
+        if uuid.as_slice() != IMPLEMENTATION_SLOT.as_slice() {
+            return Err(
+                UUPSError::UUPSUnsupportedProxiableUUID(UUPSUnsupportedProxiableUUID {
+                    slot: B256::from_slice(&uuid).0,
+                })
+                .into(),
+            );
+        }
+
+        Self::_implementation_slot().set(new_implementation);
+        evm::log(Upgraded {
+            implementation: new_implementation,
+        });
+
+        if !data.is_empty() {
+            // Persist and drop cached storage, as the new implementation may modify it
+            StorageCache::clear();
+            RawCall::new_delegate().call(new_implementation, data)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the accessor of the ERC-1967 implementation slot.
+    fn _implementation_slot() -> StorageAddress {
+        // SAFETY: the ERC-1967 slot is never allocated by `sol_storage!` structs
+        unsafe { StorageAddress::new(U256::from_be_bytes(IMPLEMENTATION_SLOT.0), 0) }
+    }
+}
+
+// External functions
+#[external]
+impl UUPSUpgradeable {
+    /// Returns the storage slot that the proxiable contract assumes is being used to store the
+    /// implementation address, as defined by ERC-1822.
+    ///
+    /// Requirements:
+    /// - The call must not be made through a proxy, so that a proxy pointing to a proxy
+    ///   cannot be considered valid.
+    #[selector(name = "proxiableUUID")]
+    pub fn proxiable_uuid(&self) -> Result<B256, UUPSError> {
+        self.not_delegated()?;
+        Ok(IMPLEMENTATION_SLOT)
+    }
+
+    /// Returns the version of the upgrade interface of the contract.
+    #[selector(name = "UPGRADE_INTERFACE_VERSION")]
+    pub fn upgrade_interface_version() -> String {
+        UPGRADE_INTERFACE_VERSION.into()
+    }
+}
@@ -0,0 +1,10 @@
+//! Security mixins used to protect contracts from misuse
+
+#[cfg(feature = "pausable")]
+pub mod pausable;
+
+#[cfg(feature = "rate-limit")]
+pub mod rate_limit;
+
+#[cfg(feature = "reentrancy-guard")]
+pub mod reentrancy_guard;
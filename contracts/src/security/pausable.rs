@@ -0,0 +1,93 @@
+//! Emergency stop mechanism that can be triggered by an authorized account.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/utils/Pausable.sol
+//!
+//! `_pause` and `_unpause` do not perform any authorization checks. The inheriting contract is
+//! responsible for exposing its own `pause`/`unpause` methods gated by e.g. `Ownable::only_owner`.
+
+use stylus_sdk::{alloy_sol_types::sol, evm, msg, prelude::*};
+
+sol_storage! {
+    /// Pausable stores whether the contract is currently paused
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.Pausable"))]
+    pub struct Pausable {
+        bool paused;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when the pause is triggered by `account`.
+    event Paused(address account);
+    /// Emitted when the pause is lifted by `account`.
+    event Unpaused(address account);
+
+    /// The operation failed because the contract is paused.
+    error EnforcedPause();
+    /// The operation failed because the contract is not paused.
+    error ExpectedPause();
+}
+
+#[derive(SolidityError)]
+pub enum PausableError {
+    EnforcedPause(EnforcedPause),
+    ExpectedPause(ExpectedPause),
+}
+
+// Internal functions
+impl Pausable {
+    /// Throws if the contract is paused.
+    pub fn require_not_paused(&self) -> Result<(), PausableError> {
+        if self.paused.get() {
+            return Err(PausableError::EnforcedPause(EnforcedPause {}));
+        }
+        Ok(())
+    }
+
+    /// Throws if the contract is not paused.
+    pub fn require_paused(&self) -> Result<(), PausableError> {
+        if !self.paused.get() {
+            return Err(PausableError::ExpectedPause(ExpectedPause {}));
+        }
+        Ok(())
+    }
+
+    /// Triggers the stopped state.
+    ///
+    /// Requirements:
+    /// - The contract must not be paused.
+    ///
+    /// Emits a {Paused} event.
+    pub fn _pause(&mut self) -> Result<(), PausableError> {
+        self.require_not_paused()?;
+        self.paused.set(true);
+        evm::log(Paused {
+            account: msg::sender(),
+        });
+        Ok(())
+    }
+
+    /// Returns to the normal state.
+    ///
+    /// Requirements:
+    /// - The contract must be paused.
+    ///
+    /// Emits an {Unpaused} event.
+    pub fn _unpause(&mut self) -> Result<(), PausableError> {
+        self.require_paused()?;
+        self.paused.set(false);
+        evm::log(Unpaused {
+            account: msg::sender(),
+        });
+        Ok(())
+    }
+}
+
+// External functions
+#[external]
+impl Pausable {
+    /// Returns true if the contract is paused, and false otherwise.
+    pub fn paused(&self) -> bool {
+        self.paused.get()
+    }
+}
@@ -0,0 +1,189 @@
+//! Per-account and global rate limits on amounts moved out of a contract (ex. withdrawals).
+//! The logic was based off of: https://github.com/smartcontractkit/ccip/blob/ccip-develop/contracts/src/v0.8/ccip/libraries/RateLimiter.sol
+//!
+//! Each limit is a token bucket holding up to `limit` units, refilled continuously at a rate of
+//! `limit` per `window` seconds. A limit of 1000 tokens per 24 hours therefore allows 1000 tokens
+//! to be withdrawn at once, then about 41 more every hour, and never more than 2000 over any 24
+//! hours. Buckets track the amount consumed, so the bucket of an account that has never been
+//! limited is full.
+//!
+//! `_consume` checks and consumes `amount` from the global bucket and the bucket of the account,
+//! and returns an {AccountRateLimited} or {GlobalRateLimited} error if either is exhausted. The
+//! inheriting contract calls it in the functions it limits, and exposes its own function to change
+//! the limits with `_set_limits`, gated by e.g. `Ownable::only_owner`. A limit of 0 disables it.
+
+use stylus_sdk::{
+    alloy_primitives::{Address, U256, U64},
+    alloy_sol_types::sol,
+    block, evm,
+    prelude::*,
+};
+
+sol_storage! {
+    /// The amount consumed from a bucket when it was last updated
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct RateLimitBucket {
+        uint256 consumed;
+        uint64 updated_at;
+    }
+
+    /// RateLimit stores the limits and the buckets of the contract and of each account
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.RateLimit"))]
+    pub struct RateLimit {
+        /// The capacity of the global bucket, or 0 if there is no global limit
+        uint256 global_limit;
+        /// The capacity of the bucket of each account, or 0 if there is no limit per account
+        uint256 account_limit;
+        /// The number of seconds it takes to refill an empty bucket
+        uint64 window;
+        /// The bucket shared by all accounts
+        RateLimitBucket global;
+        /// Maps account to its bucket
+        mapping(address => RateLimitBucket) accounts;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when the limits are set to `globalLimit` and `accountLimit` per `window` seconds.
+    event RateLimitsSet(uint256 globalLimit, uint256 accountLimit, uint64 window);
+
+    /// `requested` exceeds the `available` amount of the global bucket.
+    error GlobalRateLimited(uint256 requested, uint256 available);
+    /// `requested` exceeds the `available` amount of the bucket of `account`.
+    error AccountRateLimited(address account, uint256 requested, uint256 available);
+    /// The refill window cannot be 0.
+    error InvalidRateLimitWindow();
+}
+
+#[derive(SolidityError)]
+pub enum RateLimitError {
+    GlobalRateLimited(GlobalRateLimited),
+    AccountRateLimited(AccountRateLimited),
+    InvalidRateLimitWindow(InvalidRateLimitWindow),
+}
+
+impl RateLimitBucket {
+    /// Returns the amount consumed from the bucket, net of what was refilled since its last update.
+    fn consumed_now(&self, limit: U256, window: u64) -> U256 {
+        let elapsed = block::timestamp().saturating_sub(self.updated_at.get().to());
+        let refilled = U256::from(elapsed).saturating_mul(limit) / U256::from(window);
+        self.consumed.get().saturating_sub(refilled)
+    }
+
+    /// Returns the amount available in the bucket, or `U256::MAX` if it has no limit.
+    fn available(&self, limit: U256, window: u64) -> U256 {
+        if limit.is_zero() {
+            return U256::MAX;
+        }
+        limit.saturating_sub(self.consumed_now(limit, window))
+    }
+
+    /// Consumes `amount` from the bucket, whose availability has been checked.
+    fn consume(&mut self, amount: U256, limit: U256, window: u64) {
+        if limit.is_zero() {
+            return;
+        }
+        let consumed = self.consumed_now(limit, window) + amount;
+        self.consumed.set(consumed);
+        self.updated_at.set(U64::from(block::timestamp()));
+    }
+}
+
+// Internal functions
+impl RateLimit {
+    /// Sets the capacity of the global bucket to `global_limit` and of the bucket of each account
+    /// to `account_limit`, refilled over `window` seconds. A limit of 0 disables it.
+    ///
+    /// Requirements:
+    /// - `window` cannot be 0.
+    ///
+    /// Emits a {RateLimitsSet} event.
+    pub fn _set_limits(
+        &mut self,
+        global_limit: U256,
+        account_limit: U256,
+        window: u64,
+    ) -> Result<(), RateLimitError> {
+        if window == 0 {
+            return Err(RateLimitError::InvalidRateLimitWindow(
+                InvalidRateLimitWindow {},
+            ));
+        }
+        self.global_limit.set(global_limit);
+        self.account_limit.set(account_limit);
+        self.window.set(U64::from(window));
+        evm::log(RateLimitsSet {
+            globalLimit: global_limit,
+            accountLimit: account_limit,
+            window,
+        });
+        Ok(())
+    }
+
+    /// Consumes `amount` from the global bucket and from the bucket of `account`.
+    ///
+    /// Requirements:
+    /// - `amount` must not exceed the amount available in the bucket of `account`.
+    /// - `amount` must not exceed the amount available in the global bucket.
+    pub fn _consume(&mut self, account: Address, amount: U256) -> Result<(), RateLimitError> {
+        let window = self.window.get().to::<u64>();
+        let account_limit = self.account_limit.get();
+        let global_limit = self.global_limit.get();
+
+        let available = self
+            .accounts
+            .getter(account)
+            .available(account_limit, window);
+        if amount > available {
+            return Err(RateLimitError::AccountRateLimited(AccountRateLimited {
+                account,
+                requested: amount,
+                available,
+            }));
+        }
+        let available = self.global.available(global_limit, window);
+        if amount > available {
+            return Err(RateLimitError::GlobalRateLimited(GlobalRateLimited {
+                requested: amount,
+                available,
+            }));
+        }
+
+        self.accounts
+            .setter(account)
+            .consume(amount, account_limit, window);
+        self.global.consume(amount, global_limit, window);
+        Ok(())
+    }
+}
+
+// External functions
+#[external]
+impl RateLimit {
+    /// Returns the capacity of the global bucket, of the bucket of each account, and the number
+    /// of seconds it takes to refill them.
+    pub fn rate_limits(&self) -> (U256, U256, u64) {
+        (
+            self.global_limit.get(),
+            self.account_limit.get(),
+            self.window.get().to(),
+        )
+    }
+
+    /// Returns the amount that can currently be consumed by all accounts, or `U256::MAX` if there
+    /// is no global limit.
+    pub fn global_available(&self) -> U256 {
+        self.global
+            .available(self.global_limit.get(), self.window.get().to())
+    }
+
+    /// Returns the amount that can currently be consumed by `account`, ignoring the global limit,
+    /// or `U256::MAX` if there is no limit per account.
+    pub fn available_of(&self, account: Address) -> U256 {
+        self.accounts
+            .getter(account)
+            .available(self.account_limit.get(), self.window.get().to())
+    }
+}
@@ -0,0 +1,84 @@
+//! Helpers to prevent reentrant calls to a function.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/utils/ReentrancyGuard.sol
+//!
+//! Note: Stylus contracts reject reentrant calls at the entrypoint unless the `reentrant` feature
+//! of `stylus-sdk` is enabled. These guards are only needed by contracts that opt into reentrancy
+//! but still want to protect specific functions.
+//!
+//! Two variants are provided:
+//! - `ReentrancyGuard`, which tracks the lock in a storage slot (the classic pattern).
+//! - `TransientReentrancyGuard`, which avoids any SSTORE by asking the host whether the current
+//!   call is reentrant. `stylus-sdk` does not expose EIP-1153 `TLOAD`/`TSTORE` hostios, so the
+//!   `msg_reentrant` hostio is used to the same effect.
+
+use stylus_sdk::{alloy_primitives::U256, alloy_sol_types::sol, msg, prelude::*};
+
+/// The lock is not held. The zero value is also considered unlocked.
+const NOT_ENTERED: U256 = U256::from_limbs([1, 0, 0, 0]);
+
+/// The lock is held.
+const ENTERED: U256 = U256::from_limbs([2, 0, 0, 0]);
+
+sol_storage! {
+    /// ReentrancyGuard stores the status of the lock
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ReentrancyGuard"))]
+    pub struct ReentrancyGuard {
+        uint256 status;
+    }
+}
+
+// Define errors in the contract
+sol! {
+    /// Unauthorized reentrant call.
+    error ReentrancyGuardReentrantCall();
+}
+
+#[derive(SolidityError)]
+pub enum ReentrancyGuardError {
+    ReentrancyGuardReentrantCall(ReentrancyGuardReentrantCall),
+}
+
+impl ReentrancyGuard {
+    /// Acquires the lock, throwing if it is already held.
+    /// Every successful call must be paired with a call to `non_reentrant_exit`.
+    pub fn non_reentrant_enter(&mut self) -> Result<(), ReentrancyGuardError> {
+        if self.status.get() == ENTERED {
+            return Err(ReentrancyGuardError::ReentrancyGuardReentrantCall(
+                ReentrancyGuardReentrantCall {},
+            ));
+        }
+        self.status.set(ENTERED);
+        Ok(())
+    }
+
+    /// Releases the lock.
+    /// Resets to `NOT_ENTERED` rather than zero to keep the refund-friendly nonzero slot.
+    pub fn non_reentrant_exit(&mut self) {
+        self.status.set(NOT_ENTERED);
+    }
+
+    /// Returns true if the lock is currently held.
+    pub fn _reentrancy_guard_entered(&self) -> bool {
+        self.status.get() == ENTERED
+    }
+}
+
+/// Storage-free reentrancy guard backed by the `msg_reentrant` hostio.
+pub struct TransientReentrancyGuard;
+
+impl TransientReentrancyGuard {
+    /// Throws if the current call reentered this contract.
+    /// No `exit` is required since the host tracks the call stack.
+    pub fn non_reentrant_enter() -> Result<(), ReentrancyGuardError> {
+        if msg::reentrant() {
+            return Err(ReentrancyGuardError::ReentrancyGuardReentrantCall(
+                ReentrancyGuardReentrantCall {},
+            ));
+        }
+        Ok(())
+    }
+
+    /// No-op kept for symmetry with `ReentrancyGuard::non_reentrant_exit`.
+    pub fn non_reentrant_exit() {}
+}
@@ -0,0 +1,664 @@
+//! Experimental DN404 hybrid token, pairing an ERC20 base with a mirror ERC721 contract.
+//! The logic was based off of: https://github.com/Vectorized/dn404/blob/main/src/DN404.sol
+//!
+//! Every `UNIT` of ERC20 tokens held by an account is backed by an NFT, which is automatically
+//! minted, transferred or burned as the fungible tokens move. The NFTs are exposed through a
+//! separately deployed `DN404Mirror` contract, which forwards its ERC721 calls to this base
+//! through the `nft_*` methods, and receives the NFT `Transfer` logs to emit from the base.
+//!
+//! Accounts can opt out of holding NFTs by setting their skip NFT flag. Contracts skip NFTs by
+//! default, since most of them (ex. AMM pools) don't need them and would pay for their minting.
+//!
+//! Stylus contracts cannot be reentered by default, so the mirror emits the `Transfer` logs of the
+//! NFT transfers it initiates itself, while the base only sends the logs of ERC20 initiated moves.
+//!
+//! The inheriting contract must call `_initialize_dn404` exactly once, see
+//! [initialization](crate#initialization).
+
+use alloc::{string::String, vec::Vec};
+use core::{cmp, marker::PhantomData};
+use stylus_sdk::{
+    alloy_primitives::{Address, U256, U8},
+    alloy_sol_types::{sol, SolCall},
+    call::RawCall,
+    evm, msg,
+    prelude::*,
+};
+
+pub trait DN404Params {
+    const NAME: &'static str;
+    const SYMBOL: &'static str;
+    const DECIMALS: u8 = 18;
+    /// The amount of ERC20 tokens backing a single NFT.
+    const UNIT: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+    fn token_uri(id: U256) -> String;
+}
+
+/// Flag marking that the skip NFT status of an address has been set explicitly.
+const SKIP_NFT_INITIALIZED: u8 = 1 << 0;
+/// Flag marking that an address does not hold NFTs.
+const SKIP_NFT: u8 = 1 << 1;
+
+sol_storage! {
+    /// DN404 implements the ERC20 side of the hybrid token and the NFT ownership records
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.DN404"))]
+    pub struct DN404<T> {
+        uint256 total_supply;
+        mapping(address => uint256) balances;
+        mapping(address => mapping(address => uint256)) allowances;
+        /// Maps address to its skip NFT flags
+        mapping(address => uint8) flags;
+        /// The mirror ERC721 contract
+        address mirror;
+        /// The id of the next NFT to mint, starting at 1
+        uint256 next_token_id;
+        /// The number of NFTs in existence
+        uint256 nft_total_supply;
+        /// Maps owner to the ids of the NFTs it holds
+        mapping(address => uint256[]) owned;
+        /// Maps id to the index of the NFT in its owner's `owned` list
+        mapping(uint256 => uint256) owned_index;
+        /// Maps id to owner
+        mapping(uint256 => address) nft_owners;
+        /// Maps id to the approved spender
+        mapping(uint256 => address) nft_approvals;
+        /// Maps the approved operators for a given address
+        mapping(address => mapping(address => bool)) operator_approvals;
+        PhantomData<T> phantom;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when `amount` tokens is transferred from `from` to `to`.
+    event Transfer(address indexed from, address indexed to, uint256 amount);
+    /// Emitted when `amount` tokens is approved by `owner` to be used by `spender`.
+    event Approval(address indexed owner, address indexed spender, uint256 amount);
+    /// Emitted when the skip NFT flag of `owner` is set to `status`.
+    event SkipNFTSet(address indexed owner, bool status);
+
+    /// The contract has already been initialized.
+    error DNAlreadyInitialized();
+    /// The contract has not been initialized.
+    error DNNotInitialized();
+    /// The mirror address is invalid.
+    error MirrorAddressIsZero();
+    /// The function can only be called by the mirror contract.
+    error SenderNotMirror();
+    /// The call to the mirror contract failed.
+    error MirrorCallFailed();
+    /// Insufficient balance.
+    error InsufficientBalance(address from, uint256 have, uint256 want);
+    /// Insufficient allowance.
+    error InsufficientAllowance(address owner, address spender, uint256 have, uint256 want);
+    /// Cannot transfer or mint to the zero address.
+    error TransferToZeroAddress();
+    /// The total supply or a balance has overflowed.
+    error TotalSupplyOverflow();
+    /// The token does not exist.
+    error TokenDoesNotExist();
+    /// The token must be owned by `from`.
+    error TransferFromIncorrectOwner();
+    /// Only the token owner or an approved account can transfer the token.
+    error TransferCallerNotOwnerNorApproved();
+    /// Only the token owner or an approved operator can approve an account for the token.
+    error ApprovalCallerNotOwnerNorApproved();
+}
+
+#[derive(SolidityError)]
+pub enum DN404Error {
+    DNAlreadyInitialized(DNAlreadyInitialized),
+    DNNotInitialized(DNNotInitialized),
+    MirrorAddressIsZero(MirrorAddressIsZero),
+    SenderNotMirror(SenderNotMirror),
+    MirrorCallFailed(MirrorCallFailed),
+    InsufficientBalance(InsufficientBalance),
+    InsufficientAllowance(InsufficientAllowance),
+    TransferToZeroAddress(TransferToZeroAddress),
+    TotalSupplyOverflow(TotalSupplyOverflow),
+    TokenDoesNotExist(TokenDoesNotExist),
+    TransferFromIncorrectOwner(TransferFromIncorrectOwner),
+    TransferCallerNotOwnerNorApproved(TransferCallerNotOwnerNorApproved),
+    ApprovalCallerNotOwnerNorApproved(ApprovalCallerNotOwnerNorApproved),
+}
+
+sol! {
+    /// Emits the NFT `Transfer` logs `(from[i], to[i], ids[i])` from the mirror.
+    function logTransfers(address[] from, address[] to, uint256[] ids);
+}
+
+/// NFT transfers to be emitted by the mirror, batched into a single call
+#[derive(Default)]
+struct NFTTransferLogs {
+    from: Vec<Address>,
+    to: Vec<Address>,
+    ids: Vec<U256>,
+}
+
+impl NFTTransferLogs {
+    fn push(&mut self, from: Address, to: Address, id: U256) {
+        self.from.push(from);
+        self.to.push(to);
+        self.ids.push(id);
+    }
+}
+
+// Internal functions
+impl<T: DN404Params> DN404<T> {
+    /// Initializes the mirror and mints `initial_supply` tokens to `initial_owner`.
+    /// `initial_owner` skips NFTs, so the initial supply doesn't mint any NFT.
+    ///
+    /// Requirements:
+    /// - The contract must not have been initialized.
+    /// - `mirror` cannot be the zero address.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn _initialize_dn404(
+        &mut self,
+        initial_supply: U256,
+        initial_owner: Address,
+        mirror: Address,
+    ) -> Result<(), DN404Error> {
+        if !self.mirror.get().is_zero() {
+            return Err(DN404Error::DNAlreadyInitialized(DNAlreadyInitialized {}));
+        }
+        if mirror.is_zero() {
+            return Err(DN404Error::MirrorAddressIsZero(MirrorAddressIsZero {}));
+        }
+        self.mirror.set(mirror);
+        self.next_token_id.set(U256::from(1));
+
+        if !initial_supply.is_zero() {
+            self._set_skip_nft(initial_owner, true);
+            self._mint(initial_owner, initial_supply)?;
+        }
+        Ok(())
+    }
+
+    /// Sets the skip NFT flag of `owner` to `status`.
+    ///
+    /// Emits a {SkipNFTSet} event.
+    pub fn _set_skip_nft(&mut self, owner: Address, status: bool) {
+        let flags = if status {
+            SKIP_NFT_INITIALIZED | SKIP_NFT
+        } else {
+            SKIP_NFT_INITIALIZED
+        };
+        self.flags.setter(owner).set(U8::from(flags));
+        evm::log(SkipNFTSet { owner, status });
+    }
+
+    /// Mints `amount` tokens to `to`, minting the NFTs backed by the new balance.
+    ///
+    /// Requirements:
+    /// - The total supply and the balance of `to` must not overflow.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn _mint(&mut self, to: Address, amount: U256) -> Result<(), DN404Error> {
+        self._require_initialized()?;
+        if to.is_zero() {
+            return Err(DN404Error::TransferToZeroAddress(TransferToZeroAddress {}));
+        }
+        let total_supply = Self::_checked_add(self.total_supply.get(), amount)?;
+        self.total_supply.set(total_supply);
+        let to_balance = Self::_checked_add(self.balances.get(to), amount)?;
+        self.balances.setter(to).set(to_balance);
+        evm::log(Transfer {
+            from: Address::ZERO,
+            to,
+            amount,
+        });
+
+        let mut logs = NFTTransferLogs::default();
+        for _ in 0..self._nfts_needed(to, to_balance) {
+            self._mint_nft(to, &mut logs);
+        }
+        self._log_nft_transfers(logs)
+    }
+
+    /// Burns `amount` tokens from `from`, burning the NFTs no longer backed by its balance.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn _burn(&mut self, from: Address, amount: U256) -> Result<(), DN404Error> {
+        self._require_initialized()?;
+        let from_balance = self._debit(from, amount)?;
+        self.total_supply.set(self.total_supply.get() - amount);
+        evm::log(Transfer {
+            from,
+            to: Address::ZERO,
+            amount,
+        });
+
+        let mut logs = NFTTransferLogs::default();
+        for _ in 0..self._nfts_in_excess(from, from_balance) {
+            self._burn_last_nft(from, &mut logs);
+        }
+        self._log_nft_transfers(logs)
+    }
+
+    /// Moves `amount` of tokens from `from` to `to`, moving the NFTs backed by the tokens when
+    /// possible, and burning or minting the remainder.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn _transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<(), DN404Error> {
+        self._require_initialized()?;
+        if to.is_zero() {
+            return Err(DN404Error::TransferToZeroAddress(TransferToZeroAddress {}));
+        }
+        let from_balance = self._debit(from, amount)?;
+        let to_balance = Self::_checked_add(self.balances.get(to), amount)?;
+        self.balances.setter(to).set(to_balance);
+        evm::log(Transfer { from, to, amount });
+        if from == to {
+            return Ok(());
+        }
+
+        let excess = self._nfts_in_excess(from, from_balance);
+        let needed = self._nfts_needed(to, to_balance);
+        let moved = cmp::min(excess, needed);
+
+        let mut logs = NFTTransferLogs::default();
+        for _ in 0..moved {
+            let id = self._pop_owned(from);
+            self._push_owned(to, id);
+            self.nft_approvals.delete(id);
+            logs.push(from, to, id);
+        }
+        for _ in moved..excess {
+            self._burn_last_nft(from, &mut logs);
+        }
+        for _ in moved..needed {
+            self._mint_nft(to, &mut logs);
+        }
+        self._log_nft_transfers(logs)
+    }
+
+    /// Spends `amount` of the allowance of `owner` for `spender`.
+    /// Does not update the allowance if it is the maximum `uint256` value.
+    pub fn _spend_allowance(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        amount: U256,
+    ) -> Result<(), DN404Error> {
+        let mut allowance = self.allowances.setter(owner);
+        let mut allowance = allowance.setter(spender);
+        let old_allowance = allowance.get();
+        if old_allowance < amount {
+            return Err(DN404Error::InsufficientAllowance(InsufficientAllowance {
+                owner,
+                spender,
+                have: old_allowance,
+                want: amount,
+            }));
+        }
+        if old_allowance != U256::MAX {
+            allowance.set(old_allowance - amount);
+        }
+        Ok(())
+    }
+
+    /// Returns whether `owner` skips NFTs. Contracts skip NFTs unless they opt in.
+    pub fn _get_skip_nft(&self, owner: Address) -> bool {
+        let flags = self.flags.get(owner).to::<u8>();
+        if flags & SKIP_NFT_INITIALIZED == 0 {
+            return owner.has_code();
+        }
+        flags & SKIP_NFT != 0
+    }
+
+    /// Throws if the contract has not been initialized.
+    fn _require_initialized(&self) -> Result<(), DN404Error> {
+        if self.mirror.get().is_zero() {
+            return Err(DN404Error::DNNotInitialized(DNNotInitialized {}));
+        }
+        Ok(())
+    }
+
+    /// Throws if the sender is not the mirror contract.
+    fn _only_mirror(&self) -> Result<(), DN404Error> {
+        if msg::sender() != self.mirror.get() {
+            return Err(DN404Error::SenderNotMirror(SenderNotMirror {}));
+        }
+        Ok(())
+    }
+
+    /// Returns `a + b`, or a {TotalSupplyOverflow} error if the addition overflows.
+    fn _checked_add(a: U256, b: U256) -> Result<U256, DN404Error> {
+        a.checked_add(b)
+            .ok_or(DN404Error::TotalSupplyOverflow(TotalSupplyOverflow {}))
+    }
+
+    /// Decreases the balance of `from` by `amount` and returns the new balance.
+    fn _debit(&mut self, from: Address, amount: U256) -> Result<U256, DN404Error> {
+        let mut balance = self.balances.setter(from);
+        let old_balance = balance.get();
+        if old_balance < amount {
+            return Err(DN404Error::InsufficientBalance(InsufficientBalance {
+                from,
+                have: old_balance,
+                want: amount,
+            }));
+        }
+        balance.set(old_balance - amount);
+        Ok(old_balance - amount)
+    }
+
+    /// Returns the number of NFTs `owner` holds beyond what `balance` backs.
+    fn _nfts_in_excess(&self, owner: Address, balance: U256) -> usize {
+        let backed = (balance / T::UNIT).saturating_to::<usize>();
+        self.owned.get(owner).len().saturating_sub(backed)
+    }
+
+    /// Returns the number of NFTs `owner` should receive for `balance`, or zero if it skips NFTs.
+    fn _nfts_needed(&self, owner: Address, balance: U256) -> usize {
+        if self._get_skip_nft(owner) {
+            return 0;
+        }
+        let backed = (balance / T::UNIT).saturating_to::<usize>();
+        backed.saturating_sub(self.owned.get(owner).len())
+    }
+
+    /// Mints the next NFT to `to`.
+    fn _mint_nft(&mut self, to: Address, logs: &mut NFTTransferLogs) {
+        let id = self.next_token_id.get();
+        self.next_token_id.set(id + U256::from(1));
+        self.nft_total_supply
+            .set(self.nft_total_supply.get() + U256::from(1));
+        self._push_owned(to, id);
+        logs.push(Address::ZERO, to, id);
+    }
+
+    /// Burns the last NFT held by `from`.
+    fn _burn_last_nft(&mut self, from: Address, logs: &mut NFTTransferLogs) {
+        let id = self._pop_owned(from);
+        self.nft_owners.delete(id);
+        self.owned_index.delete(id);
+        self.nft_approvals.delete(id);
+        self.nft_total_supply
+            .set(self.nft_total_supply.get() - U256::from(1));
+        logs.push(from, Address::ZERO, id);
+    }
+
+    /// Appends NFT `id` to the owned list of `to`, and makes `to` its owner.
+    fn _push_owned(&mut self, to: Address, id: U256) {
+        let mut owned = self.owned.setter(to);
+        self.owned_index.insert(id, U256::from(owned.len()));
+        owned.push(id);
+        self.nft_owners.insert(id, to);
+    }
+
+    /// Removes and returns the last NFT of the owned list of `from`.
+    fn _pop_owned(&mut self, from: Address) -> U256 {
+        // Callers only pop NFTs in excess of the backed balance, so the list is never empty
+        self.owned.setter(from).pop().unwrap_or_default()
+    }
+
+    /// Removes NFT `id` from the owned list of `from` by swapping it with the last NFT.
+    fn _remove_owned(&mut self, from: Address, id: U256) {
+        let index = self.owned_index.get(id);
+        let last = self._pop_owned(from);
+        if last != id {
+            if let Some(mut slot) = self.owned.setter(from).setter(index) {
+                slot.set(last);
+            }
+            self.owned_index.insert(last, index);
+        }
+    }
+
+    /// Sends the NFT transfer logs to the mirror, which emits them.
+    fn _log_nft_transfers(&mut self, logs: NFTTransferLogs) -> Result<(), DN404Error> {
+        if logs.ids.is_empty() {
+            return Ok(());
+        }
+        let calldata = logTransfersCall {
+            from: logs.from,
+            to: logs.to,
+            ids: logs.ids,
+        }
+        .encode();
+        RawCall::new()
+            .call(self.mirror.get(), &calldata)
+            .map_err(|_| DN404Error::MirrorCallFailed(MirrorCallFailed {}))?;
+        Ok(())
+    }
+}
+
+// External functions
+#[external]
+impl<T: DN404Params> DN404<T> {
+    /// Returns the name of the token.
+    pub fn name() -> String {
+        T::NAME.into()
+    }
+
+    /// Returns the symbol of the token.
+    pub fn symbol() -> String {
+        T::SYMBOL.into()
+    }
+
+    /// Returns the decimals places of the token.
+    pub fn decimals() -> u8 {
+        T::DECIMALS
+    }
+
+    /// Returns the amount of tokens in existence.
+    pub fn total_supply(&self) -> U256 {
+        self.total_supply.get()
+    }
+
+    /// Returns the amount of tokens owned by `owner`.
+    pub fn balance_of(&self, owner: Address) -> U256 {
+        self.balances.get(owner)
+    }
+
+    /// Returns the amount of tokens that `spender` can spend on behalf of `owner`.
+    pub fn allowance(&self, owner: Address, spender: Address) -> U256 {
+        self.allowances.getter(owner).get(spender)
+    }
+
+    /// Sets `amount` as the allowance of `spender` over the caller's tokens.
+    ///
+    /// Emits a {Approval} event.
+    pub fn approve(&mut self, spender: Address, amount: U256) -> bool {
+        self.allowances
+            .setter(msg::sender())
+            .insert(spender, amount);
+        evm::log(Approval {
+            owner: msg::sender(),
+            spender,
+            amount,
+        });
+        true
+    }
+
+    /// Transfer `amount` tokens from the caller to `to`.
+    ///
+    /// Requirements:
+    /// - `from` must at least have `amount`.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn transfer(&mut self, to: Address, amount: U256) -> Result<bool, DN404Error> {
+        self._transfer(msg::sender(), to, amount)?;
+        Ok(true)
+    }
+
+    /// Transfers `amount` tokens from `from` to `to`.
+    ///
+    /// Requirements:
+    /// - `from` must at least have `amount`.
+    /// - The caller must have at least `amount` of allowance to transfer the tokens of `from`.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<bool, DN404Error> {
+        self._spend_allowance(from, msg::sender(), amount)?;
+        self._transfer(from, to, amount)?;
+        Ok(true)
+    }
+
+    /// Returns the address of the mirror ERC721 contract.
+    #[selector(name = "mirrorERC721")]
+    pub fn mirror_erc721(&self) -> Address {
+        self.mirror.get()
+    }
+
+    /// Returns whether `owner` skips NFTs.
+    #[selector(name = "getSkipNFT")]
+    pub fn get_skip_nft(&self, owner: Address) -> bool {
+        self._get_skip_nft(owner)
+    }
+
+    /// Sets whether the caller skips NFTs. NFTs already held are not affected.
+    ///
+    /// Emits a {SkipNFTSet} event.
+    #[selector(name = "setSkipNFT")]
+    pub fn set_skip_nft(&mut self, skip_nft: bool) -> bool {
+        self._set_skip_nft(msg::sender(), skip_nft);
+        true
+    }
+
+    /// Returns the URI of NFT `id`, for the mirror.
+    ///
+    /// Requirements:
+    /// - NFT `id` must exist.
+    #[selector(name = "nftTokenURI")]
+    pub fn nft_token_uri(&self, id: U256) -> Result<String, DN404Error> {
+        self.nft_owner_of(id)?;
+        Ok(T::token_uri(id))
+    }
+
+    /// Returns the number of NFTs in existence, for the mirror.
+    pub fn nft_total_supply(&self) -> U256 {
+        self.nft_total_supply.get()
+    }
+
+    /// Returns the number of NFTs held by `owner`, for the mirror.
+    pub fn nft_balance_of(&self, owner: Address) -> U256 {
+        U256::from(self.owned.get(owner).len())
+    }
+
+    /// Returns the owner of NFT `id`, for the mirror.
+    ///
+    /// Requirements:
+    /// - NFT `id` must exist.
+    pub fn nft_owner_of(&self, id: U256) -> Result<Address, DN404Error> {
+        let owner = self.nft_owners.get(id);
+        if owner.is_zero() {
+            return Err(DN404Error::TokenDoesNotExist(TokenDoesNotExist {}));
+        }
+        Ok(owner)
+    }
+
+    /// Returns the account approved to manage NFT `id`, for the mirror.
+    ///
+    /// Requirements:
+    /// - NFT `id` must exist.
+    pub fn nft_get_approved(&self, id: U256) -> Result<Address, DN404Error> {
+        self.nft_owner_of(id)?;
+        Ok(self.nft_approvals.get(id))
+    }
+
+    /// Returns whether `operator` manages all of the NFTs of `owner`, for the mirror.
+    pub fn nft_is_approved_for_all(&self, owner: Address, operator: Address) -> bool {
+        self.operator_approvals.getter(owner).get(operator)
+    }
+
+    /// Sets `spender` as the approved account to manage NFT `id` on behalf of `msg_sender`,
+    /// and returns the owner of the NFT. Only callable by the mirror.
+    ///
+    /// Requirements:
+    /// - NFT `id` must exist.
+    /// - `msg_sender` must be the owner of the NFT, or an approved operator for the owner.
+    pub fn nft_approve(
+        &mut self,
+        spender: Address,
+        id: U256,
+        msg_sender: Address,
+    ) -> Result<Address, DN404Error> {
+        self._only_mirror()?;
+        let owner = self.nft_owner_of(id)?;
+        if msg_sender != owner && !self.operator_approvals.getter(owner).get(msg_sender) {
+            return Err(DN404Error::ApprovalCallerNotOwnerNorApproved(
+                ApprovalCallerNotOwnerNorApproved {},
+            ));
+        }
+        self.nft_approvals.insert(id, spender);
+        Ok(owner)
+    }
+
+    /// Sets whether `operator` manages all of the NFTs of `msg_sender`.
+    /// Only callable by the mirror.
+    pub fn nft_set_approval_for_all(
+        &mut self,
+        operator: Address,
+        approved: bool,
+        msg_sender: Address,
+    ) -> Result<(), DN404Error> {
+        self._only_mirror()?;
+        self.operator_approvals
+            .setter(msg_sender)
+            .insert(operator, approved);
+        Ok(())
+    }
+
+    /// Transfers NFT `id` from `from` to `to` on behalf of `msg_sender`, along with the `UNIT`
+    /// of tokens backing it. Only callable by the mirror, which emits the NFT `Transfer` log.
+    ///
+    /// Requirements:
+    /// - `to` cannot be the zero address.
+    /// - NFT `id` must be owned by `from`.
+    /// - `msg_sender` must be the owner of the NFT, or be approved to manage the NFT.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn nft_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        id: U256,
+        msg_sender: Address,
+    ) -> Result<(), DN404Error> {
+        self._only_mirror()?;
+        if to.is_zero() {
+            return Err(DN404Error::TransferToZeroAddress(TransferToZeroAddress {}));
+        }
+        let owner = self.nft_owner_of(id)?;
+        if owner != from {
+            return Err(DN404Error::TransferFromIncorrectOwner(
+                TransferFromIncorrectOwner {},
+            ));
+        }
+        if msg_sender != from
+            && !self.operator_approvals.getter(from).get(msg_sender)
+            && self.nft_approvals.get(id) != msg_sender
+        {
+            return Err(DN404Error::TransferCallerNotOwnerNorApproved(
+                TransferCallerNotOwnerNorApproved {},
+            ));
+        }
+
+        self._debit(from, T::UNIT)?;
+        let to_balance = self.balances.get(to) + T::UNIT;
+        self.balances.setter(to).set(to_balance);
+        evm::log(Transfer {
+            from,
+            to,
+            amount: T::UNIT,
+        });
+
+        self._remove_owned(from, id);
+        self._push_owned(to, id);
+        self.nft_approvals.delete(id);
+        Ok(())
+    }
+}
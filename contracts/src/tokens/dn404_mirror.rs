@@ -0,0 +1,345 @@
+//! Experimental ERC721 mirror of a `DN404` base.
+//! The logic was based off of: https://github.com/Vectorized/dn404/blob/main/src/DN404Mirror.sol
+//!
+//! The mirror holds no NFT state. Every ERC721 method is forwarded to the base through its
+//! `nft_*` methods, and the mirror emits the resulting ERC721 events. NFT transfers caused by
+//! ERC20 transfers on the base are logged through `log_transfers`, which only the base can call.
+//!
+//! The inheriting contract must call `_initialize_mirror` exactly once, see
+//! [initialization](crate#initialization).
+
+use alloc::{string::String, vec::Vec};
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolCall},
+    call::RawCall,
+    evm, msg,
+    prelude::*,
+};
+
+use crate::utils::excessively_safe_call::{excessively_safe_call, ALL_GAS};
+use crate::utils::introspection::{self, InterfaceIds};
+
+sol_storage! {
+    /// DN404Mirror exposes the NFTs of a DN404 base as an ERC721 collection
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.DN404Mirror"))]
+    pub struct DN404Mirror {
+        /// The DN404 base contract
+        address base_erc20;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when token `id` is transferred from `from` to `to`.
+    event Transfer(address indexed from, address indexed to, uint256 indexed id);
+    /// Emitted when `owner` enables `account` to manage the `id` token.
+    event Approval(address indexed owner, address indexed account, uint256 indexed id);
+    /// Emitted when `owner` enables or disables `operator` to manage all of their tokens.
+    event ApprovalForAll(address indexed owner, address indexed operator, bool approved);
+
+    /// The mirror has already been initialized.
+    error MirrorAlreadyInitialized();
+    /// The mirror has not been initialized.
+    error MirrorNotInitialized();
+    /// The function can only be called by the base contract.
+    error SenderNotBase();
+    /// Cannot safely transfer to a contract that does not implement the ERC721Receiver interface.
+    error TransferToNonERC721ReceiverImplementer();
+}
+
+#[derive(SolidityError)]
+pub enum DN404MirrorError {
+    MirrorAlreadyInitialized(MirrorAlreadyInitialized),
+    MirrorNotInitialized(MirrorNotInitialized),
+    SenderNotBase(SenderNotBase),
+    TransferToNonERC721ReceiverImplementer(TransferToNonERC721ReceiverImplementer),
+}
+
+// The methods of the DN404 base and ERC721 receivers called by the mirror
+sol! {
+    function name() returns (string);
+    function symbol() returns (string);
+    function nftTokenURI(uint256 id) returns (string);
+    function nftTotalSupply() returns (uint256);
+    function nftBalanceOf(address owner) returns (uint256);
+    function nftOwnerOf(uint256 id) returns (address);
+    function nftGetApproved(uint256 id) returns (address);
+    function nftIsApprovedForAll(address owner, address operator) returns (bool);
+    function nftApprove(address spender, uint256 id, address msgSender) returns (address);
+    function nftSetApprovalForAll(address operator, bool approved, address msgSender);
+    function nftTransferFrom(address from, address to, uint256 id, address msgSender);
+    function onERC721Received(address operator, address from, uint256 id, bytes data) returns (bytes4);
+}
+
+/// The ERC165 interface ids of ERC721 and ERC721Metadata.
+const IERC721: u32 = crate::interface_id!(
+    "balanceOf(address)",
+    "ownerOf(uint256)",
+    "safeTransferFrom(address,address,uint256,bytes)",
+    "safeTransferFrom(address,address,uint256)",
+    "transferFrom(address,address,uint256)",
+    "approve(address,uint256)",
+    "setApprovalForAll(address,bool)",
+    "getApproved(uint256)",
+    "isApprovedForAll(address,address)",
+);
+const IERC721METADATA: u32 = crate::interface_id!("name()", "symbol()", "tokenURI(uint256)");
+
+impl InterfaceIds for DN404Mirror {
+    fn implements_interface(id: u32) -> bool {
+        matches!(id, IERC721 | IERC721METADATA)
+    }
+}
+
+// Internal functions
+impl DN404Mirror {
+    /// Sets the DN404 base contract whose NFTs are mirrored.
+    ///
+    /// Requirements:
+    /// - The mirror must not have been initialized.
+    pub fn _initialize_mirror(&mut self, base: Address) -> Result<(), DN404MirrorError> {
+        if !self.base_erc20.get().is_zero() {
+            return Err(DN404MirrorError::MirrorAlreadyInitialized(
+                MirrorAlreadyInitialized {},
+            ));
+        }
+        self.base_erc20.set(base);
+        Ok(())
+    }
+
+    /// Returns the base contract, throwing if the mirror has not been initialized.
+    fn _base(&self) -> Result<Address, Vec<u8>> {
+        let base = self.base_erc20.get();
+        if base.is_zero() {
+            return Err(DN404MirrorError::MirrorNotInitialized(MirrorNotInitialized {}).into());
+        }
+        Ok(base)
+    }
+
+    /// Calls the base with `call`, bubbling up its revert data.
+    fn _call_base<C: SolCall>(&self, call: C) -> Result<C::Return, Vec<u8>> {
+        let data = RawCall::new().call(self._base()?, &call.encode())?;
+        C::decode_returns(&data, true).map_err(|_| Vec::new())
+    }
+
+    /// Static calls the base with `call`, bubbling up its revert data.
+    fn _static_call_base<C: SolCall>(&self, call: C) -> Result<C::Return, Vec<u8>> {
+        let data = RawCall::new_static().call(self._base()?, &call.encode())?;
+        C::decode_returns(&data, true).map_err(|_| Vec::new())
+    }
+
+    /// Calls the onERC721Received callback function if the receiver is not an EOA (code size > 0).
+    fn _call_receiver(
+        from: Address,
+        to: Address,
+        id: U256,
+        data: Vec<u8>,
+    ) -> Result<(), DN404MirrorError> {
+        if !to.has_code() {
+            return Ok(());
+        }
+        let refused = || {
+            DN404MirrorError::TransferToNonERC721ReceiverImplementer(
+                TransferToNonERC721ReceiverImplementer {},
+            )
+        };
+        let calldata = onERC721ReceivedCall {
+            operator: msg::sender(),
+            from,
+            id,
+            data,
+        }
+        .encode();
+        // Only copy the first word of the returned data to prevent return bombs
+        let result = excessively_safe_call(to, ALL_GAS, U256::ZERO, 32, &calldata);
+        if !result.is_success()
+            || result.data().get(..4) != Some(&onERC721ReceivedCall::SELECTOR[..])
+        {
+            return Err(refused());
+        }
+        Ok(())
+    }
+}
+
+// External functions
+#[external]
+impl DN404Mirror {
+    /// Returns the token collection name.
+    pub fn name(&self) -> Result<String, Vec<u8>> {
+        Ok(self._static_call_base(nameCall {})?._0)
+    }
+
+    /// Returns the token collection symbol.
+    pub fn symbol(&self) -> Result<String, Vec<u8>> {
+        Ok(self._static_call_base(symbolCall {})?._0)
+    }
+
+    /// Returns the Uniform Resource Identifier (URI) for token `id`.
+    #[selector(name = "tokenURI")]
+    pub fn token_uri(&self, id: U256) -> Result<String, Vec<u8>> {
+        Ok(self._static_call_base(nftTokenURICall { id })?._0)
+    }
+
+    /// Returns the number of tokens in existence.
+    pub fn total_supply(&self) -> Result<U256, Vec<u8>> {
+        Ok(self._static_call_base(nftTotalSupplyCall {})?._0)
+    }
+
+    /// Returns the number of tokens owned by `owner`.
+    pub fn balance_of(&self, owner: Address) -> Result<U256, Vec<u8>> {
+        Ok(self._static_call_base(nftBalanceOfCall { owner })?._0)
+    }
+
+    /// Returns the owner of token `id`.
+    ///
+    /// Requirements:
+    /// - Token `id` must exist.
+    pub fn owner_of(&self, id: U256) -> Result<Address, Vec<u8>> {
+        Ok(self._static_call_base(nftOwnerOfCall { id })?._0)
+    }
+
+    /// Returns the account approved to manage token `id`.
+    ///
+    /// Requirements:
+    /// - Token `id` must exist.
+    pub fn get_approved(&self, id: U256) -> Result<Address, Vec<u8>> {
+        Ok(self._static_call_base(nftGetApprovedCall { id })?._0)
+    }
+
+    /// Returns whether `operator` is approved to manage the tokens of `owner`.
+    pub fn is_approved_for_all(&self, owner: Address, operator: Address) -> Result<bool, Vec<u8>> {
+        Ok(self
+            ._static_call_base(nftIsApprovedForAllCall { owner, operator })?
+            ._0)
+    }
+
+    /// Sets `account` as the approved account to manage token `id`.
+    ///
+    /// Requirements:
+    /// - Token `id` must exist.
+    /// - The caller must be the owner of the token,
+    ///   or an approved operator for the token owner.
+    ///
+    /// Emits an {Approval} event.
+    pub fn approve(&mut self, account: Address, id: U256) -> Result<(), Vec<u8>> {
+        let owner = self
+            ._call_base(nftApproveCall {
+                spender: account,
+                id,
+                msgSender: msg::sender(),
+            })?
+            ._0;
+        evm::log(Approval { owner, account, id });
+        Ok(())
+    }
+
+    /// Sets whether `operator` is approved to manage the tokens of the caller.
+    ///
+    /// Emits a {ApprovalForAll} event.
+    pub fn set_approval_for_all(
+        &mut self,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), Vec<u8>> {
+        self._call_base(nftSetApprovalForAllCall {
+            operator,
+            approved,
+            msgSender: msg::sender(),
+        })?;
+        evm::log(ApprovalForAll {
+            owner: msg::sender(),
+            operator,
+            approved,
+        });
+        Ok(())
+    }
+
+    /// Transfers token `id` from `from` to `to`, along with the base tokens backing it.
+    ///
+    /// Requirements:
+    ///
+    /// - Token `id` must exist.
+    /// - `from` must be the owner of the token.
+    /// - `to` cannot be the zero address.
+    /// - The caller must be the owner of the token, or be approved to manage the token.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn transfer_from(&mut self, from: Address, to: Address, id: U256) -> Result<(), Vec<u8>> {
+        self._call_base(nftTransferFromCall {
+            from,
+            to,
+            id,
+            msgSender: msg::sender(),
+        })?;
+        evm::log(Transfer { from, to, id });
+        Ok(())
+    }
+
+    /// Equivalent to `safe_transfer_from_with_data(from, to, id, "")`.
+    pub fn safe_transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        id: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.safe_transfer_from_with_data(from, to, id, Bytes(Vec::new()))
+    }
+
+    /// Transfers token `id` from `from` to `to`, along with the base tokens backing it.
+    ///
+    /// Requirements:
+    ///
+    /// - Token `id` must exist.
+    /// - `from` must be the owner of the token.
+    /// - `to` cannot be the zero address.
+    /// - The caller must be the owner of the token, or be approved to manage the token.
+    /// - If `to` refers to a smart contract, it must implement
+    ///   {IERC721Receiver-onERC721Received}, which is called upon a safe transfer.
+    ///
+    /// Emits a {Transfer} event.
+    #[selector(name = "safeTransferFrom")]
+    pub fn safe_transfer_from_with_data(
+        &mut self,
+        from: Address,
+        to: Address,
+        id: U256,
+        data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        self.transfer_from(from, to, id)?;
+        Ok(Self::_call_receiver(from, to, id, data.0)?)
+    }
+
+    /// Returns true if this contract implements the interface defined by `interfaceId`.
+    /// See: https://eips.ethereum.org/EIPS/eip-165
+    pub fn supports_interface(interface: [u8; 4]) -> bool {
+        introspection::supports_interface::<Self>(interface)
+    }
+
+    /// Returns the address of the DN404 base contract.
+    #[selector(name = "baseERC20")]
+    pub fn base_erc20(&self) -> Address {
+        self.base_erc20.get()
+    }
+
+    /// Emits the `Transfer` logs `(from[i], to[i], ids[i])` of the NFTs moved by the base.
+    ///
+    /// Requirements:
+    /// - The caller must be the base contract.
+    pub fn log_transfers(
+        &mut self,
+        from: Vec<Address>,
+        to: Vec<Address>,
+        ids: Vec<U256>,
+    ) -> Result<(), DN404MirrorError> {
+        if self.base_erc20.get().is_zero() || msg::sender() != self.base_erc20.get() {
+            return Err(DN404MirrorError::SenderNotBase(SenderNotBase {}));
+        }
+        for ((from, to), id) in from.into_iter().zip(to).zip(ids) {
+            evm::log(Transfer { from, to, id });
+        }
+        Ok(())
+    }
+}
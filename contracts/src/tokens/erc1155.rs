@@ -0,0 +1,491 @@
+//! ERC1155 multi token base contract.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/token/ERC1155/ERC1155.sol
+//! Doc comments are forked from: https://github.com/Vectorized/solady/blob/main/src/tokens/ERC1155.sol
+//!
+//! All balance changes go through `_update`, which emits a {TransferSingle} event for a single id
+//! and a {TransferBatch} event otherwise. Mints and transfers to contracts call the
+//! `IERC1155Receiver` hooks of the recipient through `_call_receiver`, which must run after the
+//! balances are updated. Extensions that need to update balances and call the receiver from a
+//! method borrowing a wrapper type can call both functions directly.
+//!
+//! The URI of every token is the same string, in which clients replace `{id}` by the hex encoded
+//! id of the token (see `_set_uri`).
+//!
+//! Reverts use the ERC-6093 custom errors (ex. `ERC1155InsufficientBalance`).
+
+use alloc::{string::String, vec::Vec};
+use core::borrow::BorrowMut;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolCall},
+    evm, msg,
+    prelude::*,
+};
+
+use crate::utils::excessively_safe_call::{excessively_safe_call, ALL_GAS};
+use crate::utils::introspection::{self, InterfaceIds};
+
+/// The ERC165 interface id of ERC1155.
+pub const IERC1155: u32 = crate::interface_id!(
+    "safeTransferFrom(address,address,uint256,uint256,bytes)",
+    "safeBatchTransferFrom(address,address,uint256[],uint256[],bytes)",
+    "balanceOf(address,uint256)",
+    "balanceOfBatch(address[],uint256[])",
+    "setApprovalForAll(address,bool)",
+    "isApprovedForAll(address,address)",
+);
+
+/// The ERC165 interface id of the ERC1155 metadata URI extension.
+pub const IERC1155_METADATA_URI: u32 = crate::interface_id!("uri(uint256)");
+
+sol_storage! {
+    /// ERC1155 implements all ERC-1155 methods
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC1155"))]
+    pub struct ERC1155 {
+        /// Maps id to account to balance
+        mapping(uint256 => mapping(address => uint256)) balances;
+        /// Maps the approved operators for a given account
+        mapping(address => mapping(address => bool)) operator_approvals;
+        /// The URI of all tokens, where `{id}` is replaced by clients
+        string uri;
+    }
+}
+
+// Declare events and Solidity error types
+sol! {
+    /// Emitted when `value` tokens of token `id` are transferred from `from` to `to` by `operator`.
+    event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value);
+    /// Emitted when `values` tokens of tokens `ids` are transferred from `from` to `to` by `operator`.
+    event TransferBatch(address indexed operator, address indexed from, address indexed to, uint256[] ids, uint256[] values);
+    /// Emitted when `account` enables or disables `operator` to manage all of their tokens.
+    event ApprovalForAll(address indexed account, address indexed operator, bool approved);
+    /// Emitted when the URI of token `id` changes to `value`.
+    event URI(string value, uint256 indexed id);
+
+    /// Indicates an error related to the current `balance` of a `sender`. Used in transfers.
+    error ERC1155InsufficientBalance(address sender, uint256 balance, uint256 needed, uint256 tokenId);
+    /// Indicates a failure with the token `sender`. Used in transfers.
+    error ERC1155InvalidSender(address sender);
+    /// Indicates a failure with the token `receiver`. Used in transfers.
+    error ERC1155InvalidReceiver(address receiver);
+    /// Indicates a failure with the `operator`'s approval. Used in transfers.
+    error ERC1155MissingApprovalForAll(address operator, address owner);
+    /// Indicates a failure with the `operator` to be approved. Used in approvals.
+    error ERC1155InvalidOperator(address operator);
+    /// Indicates an array length mismatch between ids and values in a batch operation.
+    error ERC1155InvalidArrayLength(uint256 idsLength, uint256 valuesLength);
+}
+
+#[derive(SolidityError)]
+pub enum ERC1155Error {
+    ERC1155InsufficientBalance(ERC1155InsufficientBalance),
+    ERC1155InvalidSender(ERC1155InvalidSender),
+    ERC1155InvalidReceiver(ERC1155InvalidReceiver),
+    ERC1155MissingApprovalForAll(ERC1155MissingApprovalForAll),
+    ERC1155InvalidOperator(ERC1155InvalidOperator),
+    ERC1155InvalidArrayLength(ERC1155InvalidArrayLength),
+}
+
+/// Selector for `onERC1155Received`, which is returned by contracts implementing `IERC1155Receiver`.
+const ERC1155_RECEIVED_SELECTOR: u32 =
+    crate::interface_id!("onERC1155Received(address,address,uint256,uint256,bytes)");
+
+/// Selector for `onERC1155BatchReceived`, which is returned by contracts implementing
+/// `IERC1155Receiver`.
+const ERC1155_BATCH_RECEIVED_SELECTOR: u32 =
+    crate::interface_id!("onERC1155BatchReceived(address,address,uint256[],uint256[],bytes)");
+
+// The callbacks of contracts implementing `IERC1155Receiver`
+sol! {
+    function onERC1155Received(address operator, address from, uint256 id, uint256 value, bytes data) returns (bytes4);
+    function onERC1155BatchReceived(address operator, address from, uint256[] ids, uint256[] values, bytes data) returns (bytes4);
+}
+
+// Internal functions
+impl ERC1155 {
+    /// Transfers `values` tokens of tokens `ids` from `from` to `to`, minting if `from` is the
+    /// zero address and burning if `to` is the zero address. Does not call the receiver.
+    ///
+    /// Requirements:
+    /// - `ids` and `values` must have the same length.
+    /// - `from` must have at least `values` tokens of each of the `ids`.
+    ///
+    /// Emits a {TransferSingle} event if `ids` has a single id, and a {TransferBatch} event
+    /// otherwise.
+    pub fn _update(
+        &mut self,
+        from: Address,
+        to: Address,
+        ids: &[U256],
+        values: &[U256],
+    ) -> Result<(), ERC1155Error> {
+        if ids.len() != values.len() {
+            return Err(ERC1155Error::ERC1155InvalidArrayLength(
+                ERC1155InvalidArrayLength {
+                    idsLength: U256::from(ids.len()),
+                    valuesLength: U256::from(values.len()),
+                },
+            ));
+        }
+        for (&id, &value) in ids.iter().zip(values) {
+            let mut balances = self.balances.setter(id);
+            if !from.is_zero() {
+                let mut from_balance = balances.setter(from);
+                let balance = from_balance.get();
+                if balance < value {
+                    return Err(ERC1155Error::ERC1155InsufficientBalance(
+                        ERC1155InsufficientBalance {
+                            sender: from,
+                            balance,
+                            needed: value,
+                            tokenId: id,
+                        },
+                    ));
+                }
+                from_balance.set(balance - value);
+            }
+            if !to.is_zero() {
+                let mut to_balance = balances.setter(to);
+                let balance = to_balance.get() + value;
+                to_balance.set(balance);
+            }
+        }
+
+        let operator = msg::sender();
+        if ids.len() == 1 {
+            evm::log(TransferSingle {
+                operator,
+                from,
+                to,
+                id: ids[0],
+                value: values[0],
+            });
+        } else {
+            evm::log(TransferBatch {
+                operator,
+                from,
+                to,
+                ids: ids.to_vec(),
+                values: values.to_vec(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Calls `onERC1155Received` (for a single id) or `onERC1155BatchReceived` on `to` if it is a
+    /// contract. Throws if the receiver cannot be called or does not return the expected selector.
+    /// Only the first word of the returned data is copied, so that the receiver cannot grief the
+    /// transfer with a large return payload.
+    ///
+    /// `storage` is mutably borrowed so that no storage references are held across the call.
+    pub fn _call_receiver<S: TopLevelStorage>(
+        _storage: &mut S,
+        from: Address,
+        to: Address,
+        ids: &[U256],
+        values: &[U256],
+        data: Vec<u8>,
+    ) -> Result<(), ERC1155Error> {
+        if !to.has_code() {
+            return Ok(());
+        }
+        let (calldata, expected) = if ids.len() == 1 {
+            let call = onERC1155ReceivedCall {
+                operator: msg::sender(),
+                from,
+                id: ids[0],
+                value: values[0],
+                data,
+            };
+            (call.encode(), ERC1155_RECEIVED_SELECTOR)
+        } else {
+            let call = onERC1155BatchReceivedCall {
+                operator: msg::sender(),
+                from,
+                ids: ids.to_vec(),
+                values: values.to_vec(),
+                data,
+            };
+            (call.encode(), ERC1155_BATCH_RECEIVED_SELECTOR)
+        };
+        let result = excessively_safe_call(to, ALL_GAS, U256::ZERO, 32, &calldata);
+        let received = result.data().get(..4);
+        if !result.is_success() || received != Some(&expected.to_be_bytes()[..]) {
+            return Err(ERC1155Error::ERC1155InvalidReceiver(
+                ERC1155InvalidReceiver { receiver: to },
+            ));
+        }
+        Ok(())
+    }
+
+    /// Transfers `values` tokens of tokens `ids` from `from` to `to`, and calls the receiver.
+    ///
+    /// Requirements:
+    /// - `from` and `to` cannot be the zero address.
+    /// - `ids` and `values` must have the same length.
+    /// - `from` must have at least `values` tokens of each of the `ids`.
+    /// - If `to` refers to a smart contract, it must implement the `IERC1155Receiver` hooks.
+    ///
+    /// Emits a {TransferSingle} or a {TransferBatch} event.
+    pub fn _safe_transfer<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        from: Address,
+        to: Address,
+        ids: &[U256],
+        values: &[U256],
+        data: Vec<u8>,
+    ) -> Result<(), ERC1155Error> {
+        if from.is_zero() {
+            return Err(ERC1155Error::ERC1155InvalidSender(ERC1155InvalidSender {
+                sender: from,
+            }));
+        }
+        if to.is_zero() {
+            return Err(ERC1155Error::ERC1155InvalidReceiver(
+                ERC1155InvalidReceiver { receiver: to },
+            ));
+        }
+        storage.borrow_mut()._update(from, to, ids, values)?;
+        Self::_call_receiver(storage, from, to, ids, values, data)
+    }
+
+    /// Mints `value` tokens of token `id` to `to`.
+    ///
+    /// Requirements:
+    /// - `to` cannot be the zero address.
+    /// - If `to` refers to a smart contract, it must implement
+    ///   {IERC1155Receiver-onERC1155Received}.
+    ///
+    /// Emits a {TransferSingle} event.
+    pub fn _mint<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        to: Address,
+        id: U256,
+        value: U256,
+        data: Vec<u8>,
+    ) -> Result<(), ERC1155Error> {
+        Self::_mint_batch(storage, to, &[id], &[value], data)
+    }
+
+    /// Mints `values` tokens of tokens `ids` to `to`.
+    ///
+    /// Requirements:
+    /// - `to` cannot be the zero address.
+    /// - `ids` and `values` must have the same length.
+    /// - If `to` refers to a smart contract, it must implement the `IERC1155Receiver` hooks.
+    ///
+    /// Emits a {TransferSingle} or a {TransferBatch} event.
+    pub fn _mint_batch<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        to: Address,
+        ids: &[U256],
+        values: &[U256],
+        data: Vec<u8>,
+    ) -> Result<(), ERC1155Error> {
+        if to.is_zero() {
+            return Err(ERC1155Error::ERC1155InvalidReceiver(
+                ERC1155InvalidReceiver { receiver: to },
+            ));
+        }
+        storage
+            .borrow_mut()
+            ._update(Address::ZERO, to, ids, values)?;
+        Self::_call_receiver(storage, Address::ZERO, to, ids, values, data)
+    }
+
+    /// Burns `values` tokens of tokens `ids` from `from`.
+    ///
+    /// Requirements:
+    /// - `from` cannot be the zero address.
+    /// - `ids` and `values` must have the same length.
+    /// - `from` must have at least `values` tokens of each of the `ids`.
+    ///
+    /// Emits a {TransferSingle} or a {TransferBatch} event.
+    pub fn _burn_batch(
+        &mut self,
+        from: Address,
+        ids: &[U256],
+        values: &[U256],
+    ) -> Result<(), ERC1155Error> {
+        if from.is_zero() {
+            return Err(ERC1155Error::ERC1155InvalidSender(ERC1155InvalidSender {
+                sender: from,
+            }));
+        }
+        self._update(from, Address::ZERO, ids, values)
+    }
+
+    /// Burns `value` tokens of token `id` from `from`.
+    ///
+    /// Requirements:
+    /// - `from` cannot be the zero address.
+    /// - `from` must have at least `value` tokens of token `id`.
+    ///
+    /// Emits a {TransferSingle} event.
+    pub fn _burn(&mut self, from: Address, id: U256, value: U256) -> Result<(), ERC1155Error> {
+        self._burn_batch(from, &[id], &[value])
+    }
+
+    /// Sets whether `operator` is approved to manage the tokens of `owner`.
+    ///
+    /// Requirements:
+    /// - `operator` cannot be the zero address.
+    ///
+    /// Emits an {ApprovalForAll} event.
+    pub fn _set_approval_for_all(
+        &mut self,
+        owner: Address,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), ERC1155Error> {
+        if operator.is_zero() {
+            return Err(ERC1155Error::ERC1155InvalidOperator(
+                ERC1155InvalidOperator { operator },
+            ));
+        }
+        self.operator_approvals
+            .setter(owner)
+            .insert(operator, approved);
+        evm::log(ApprovalForAll {
+            account: owner,
+            operator,
+            approved,
+        });
+        Ok(())
+    }
+
+    /// Requires that the caller is `owner` or an approved operator of `owner`.
+    pub fn _require_owner_or_approved(&self, owner: Address) -> Result<(), ERC1155Error> {
+        let operator = msg::sender();
+        if operator != owner && !self.is_approved_for_all(owner, operator) {
+            return Err(ERC1155Error::ERC1155MissingApprovalForAll(
+                ERC1155MissingApprovalForAll { operator, owner },
+            ));
+        }
+        Ok(())
+    }
+
+    /// Sets the URI of all tokens to `new_uri`. Clients replace `{id}` in the URI by the lowercase
+    /// hex encoded id of the token, padded with zeros to 64 characters.
+    ///
+    /// Does not emit a {URI} event, since the standard defines it per token id.
+    pub fn _set_uri(&mut self, new_uri: &str) {
+        self.uri.set_str(new_uri);
+    }
+}
+
+impl InterfaceIds for ERC1155 {
+    fn implements_interface(id: u32) -> bool {
+        matches!(id, IERC1155 | IERC1155_METADATA_URI)
+    }
+}
+
+// External functions
+#[external]
+impl ERC1155 {
+    /// Returns the URI of token `id`, in which clients replace `{id}` by the id of the token.
+    pub fn uri(&self, _id: U256) -> String {
+        self.uri.get_string()
+    }
+
+    /// Returns true if this contract implements the interface defined by `interfaceId`.
+    /// See: https://eips.ethereum.org/EIPS/eip-165
+    pub fn supports_interface(interface: [u8; 4]) -> bool {
+        introspection::supports_interface::<Self>(interface)
+    }
+
+    /// Returns the amount of token `id` owned by `account`.
+    pub fn balance_of(&self, account: Address, id: U256) -> U256 {
+        self.balances.getter(id).get(account)
+    }
+
+    /// Returns the amounts of tokens `ids` owned by `accounts`, pairwise.
+    ///
+    /// Requirements:
+    /// - `accounts` and `ids` must have the same length.
+    pub fn balance_of_batch(
+        &self,
+        accounts: Vec<Address>,
+        ids: Vec<U256>,
+    ) -> Result<Vec<U256>, ERC1155Error> {
+        if accounts.len() != ids.len() {
+            return Err(ERC1155Error::ERC1155InvalidArrayLength(
+                ERC1155InvalidArrayLength {
+                    idsLength: U256::from(ids.len()),
+                    valuesLength: U256::from(accounts.len()),
+                },
+            ));
+        }
+        Ok(accounts
+            .iter()
+            .zip(&ids)
+            .map(|(&account, &id)| self.balance_of(account, id))
+            .collect())
+    }
+
+    /// Sets whether `operator` is approved to manage the tokens of the caller.
+    ///
+    /// Requirements:
+    /// - `operator` cannot be the zero address.
+    ///
+    /// Emits an {ApprovalForAll} event.
+    pub fn set_approval_for_all(
+        &mut self,
+        operator: Address,
+        approved: bool,
+    ) -> Result<(), ERC1155Error> {
+        self._set_approval_for_all(msg::sender(), operator, approved)
+    }
+
+    /// Returns whether `operator` is approved to manage the tokens of `account`.
+    pub fn is_approved_for_all(&self, account: Address, operator: Address) -> bool {
+        self.operator_approvals.getter(account).get(operator)
+    }
+
+    /// Transfers `value` tokens of token `id` from `from` to `to`.
+    ///
+    /// Requirements:
+    /// - `to` cannot be the zero address.
+    /// - `from` must have at least `value` tokens of token `id`.
+    /// - The caller must be `from` or an approved operator of `from`.
+    /// - If `to` refers to a smart contract, it must implement
+    ///   {IERC1155Receiver-onERC1155Received}.
+    ///
+    /// Emits a {TransferSingle} event.
+    pub fn safe_transfer_from<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        from: Address,
+        to: Address,
+        id: U256,
+        value: U256,
+        data: Bytes,
+    ) -> Result<(), ERC1155Error> {
+        storage.borrow_mut()._require_owner_or_approved(from)?;
+        Self::_safe_transfer(storage, from, to, &[id], &[value], data.0)
+    }
+
+    /// Transfers `values` tokens of tokens `ids` from `from` to `to`.
+    ///
+    /// Requirements:
+    /// - `to` cannot be the zero address.
+    /// - `ids` and `values` must have the same length.
+    /// - `from` must have at least `values` tokens of each of the `ids`.
+    /// - The caller must be `from` or an approved operator of `from`.
+    /// - If `to` refers to a smart contract, it must implement
+    ///   {IERC1155Receiver-onERC1155BatchReceived}.
+    ///
+    /// Emits a {TransferSingle} or a {TransferBatch} event.
+    pub fn safe_batch_transfer_from<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        from: Address,
+        to: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: Bytes,
+    ) -> Result<(), ERC1155Error> {
+        storage.borrow_mut()._require_owner_or_approved(from)?;
+        Self::_safe_transfer(storage, from, to, &ids, &values, data.0)
+    }
+}
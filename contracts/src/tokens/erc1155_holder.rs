@@ -0,0 +1,81 @@
+//! ERC1155 receiver base that accepts all safe transfers of ERC1155 tokens.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/token/ERC1155/utils/ERC1155Holder.sol
+//!
+//! Contracts custodying ERC1155 tokens (ex. vaults, staking or escrow contracts) must implement
+//! the `IERC1155Receiver` hooks, or transfers and mints to them revert. Inheriting
+//! `ERC1155Holder` accepts every token. Contracts that only accept some tokens should define their
+//! own hooks instead, returning `ERC1155_RECEIVED` or `ERC1155_BATCH_RECEIVED` for accepted tokens.
+//!
+//! `ERC1155Holder` also implements `supports_interface`, which reports `IERC1155Receiver`. A
+//! contract inheriting another base with its own `supports_interface` must define a
+//! `supports_interface` combining both.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, FixedBytes, U256},
+    prelude::*,
+};
+
+use crate::utils::introspection::{self, InterfaceIds};
+
+/// The value returned by `onERC1155Received` to accept a transfer, which is its selector.
+pub const ERC1155_RECEIVED: u32 =
+    crate::interface_id!("onERC1155Received(address,address,uint256,uint256,bytes)");
+
+/// The value returned by `onERC1155BatchReceived` to accept a batch transfer, which is its
+/// selector.
+pub const ERC1155_BATCH_RECEIVED: u32 =
+    crate::interface_id!("onERC1155BatchReceived(address,address,uint256[],uint256[],bytes)");
+
+/// The ERC165 interface id of IERC1155Receiver.
+pub const IERC1155_RECEIVER: u32 = ERC1155_RECEIVED ^ ERC1155_BATCH_RECEIVED;
+
+sol_storage! {
+    /// ERC1155Holder implements the `IERC1155Receiver` hooks
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC1155Holder"))]
+    pub struct ERC1155Holder {}
+}
+
+impl InterfaceIds for ERC1155Holder {
+    fn implements_interface(id: u32) -> bool {
+        id == IERC1155_RECEIVER
+    }
+}
+
+// External functions
+#[external]
+impl ERC1155Holder {
+    /// Accepts the transfer of `value` tokens of token `id` from `from` by `operator`, by returning
+    /// `ERC1155_RECEIVED`.
+    #[selector(name = "onERC1155Received")]
+    pub fn on_erc1155_received(
+        _operator: Address,
+        _from: Address,
+        _id: U256,
+        _value: U256,
+        _data: Bytes,
+    ) -> FixedBytes<4> {
+        FixedBytes(ERC1155_RECEIVED.to_be_bytes())
+    }
+
+    /// Accepts the transfer of `values` tokens of tokens `ids` from `from` by `operator`, by
+    /// returning `ERC1155_BATCH_RECEIVED`.
+    #[selector(name = "onERC1155BatchReceived")]
+    pub fn on_erc1155_batch_received(
+        _operator: Address,
+        _from: Address,
+        _ids: Vec<U256>,
+        _values: Vec<U256>,
+        _data: Bytes,
+    ) -> FixedBytes<4> {
+        FixedBytes(ERC1155_BATCH_RECEIVED.to_be_bytes())
+    }
+
+    /// Returns true if this contract implements the interface defined by `interfaceId`.
+    /// See: https://eips.ethereum.org/EIPS/eip-165
+    pub fn supports_interface(interface: [u8; 4]) -> bool {
+        introspection::supports_interface::<Self>(interface)
+    }
+}
@@ -1,19 +1,57 @@
 //! ERC20 base contract with EIP2612 (permit) support.
 //! The logic was based off of: https://github.com/OffchainLabs/stylus-sdk-rs/blob/stylus/examples/erc20/src/erc20.rs
 //! Doc comments are forked from: https://github.com/Vectorized/solady/blob/main/src/tokens/ERC20.sol
-
-use alloc::string::{String, ToString};
-use core::marker::PhantomData;
+//!
+//! Logic can be run on every mint, transfer and burn by implementing `ERC20Hooks` on the storage
+//! holding the token (ex. the top-level storage of the contract), and moving tokens through it:
+//! `_transfer`, `_mint` and `_burn` take that storage and run its hooks, ex.
+//! `ERC20::_mint(&mut self.erc20, to, amount)` for a token without hooks. The external `transfer`
+//! and `transfer_from` of `ERC20` run the no-op hooks of `ERC20` itself, so contracts with hooks
+//! must override them to call `_transfer` with their own storage:
+//!
+//! ```ignore
+//! impl ERC20Hooks<MyParams> for MyToken {
+//!     fn after_token_transfer(&mut self, from: Address, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+//!         Ok(self.votes._after_token_transfer(from, to, amount)?)
+//!     }
+//! }
+//!
+//! #[external]
+//! #[inherit(ERC20<MyParams>)]
+//! impl MyToken {
+//!     pub fn transfer(&mut self, to: Address, amount: U256) -> Result<bool, Vec<u8>> {
+//!         ERC20::_transfer(self, msg::sender(), to, amount)?;
+//!         Ok(true)
+//!     }
+//!     // ... and `transfer_from`, through `ERC20::_transfer_from`
+//! }
+//! ```
+//!
+//! Hooks can revert with any error, so the functions that move tokens return raw revert data
+//! instead of an `ERC20Error`.
+//!
+//! Increases of the total supply and of the recipient balances are checked, and return an
+//! {ArithmeticOverflow} error instead of wrapping or panicking. Gas sensitive tokens whose supply
+//! cannot realistically overflow can enable the `erc20-unchecked` feature to skip these checks.
+//!
+//! Reverts use the ERC-6093 custom errors (ex. `ERC20InsufficientBalance`), like OpenZeppelin 5.x.
+//! The errors of previous versions (ex. `InsufficientBalance`) can be restored with the
+//! `legacy-errors` feature.
+
+use alloc::{string::String, vec::Vec};
+use core::{borrow::BorrowMut, marker::PhantomData};
 use stylus_sdk::{
-    alloy_primitives::{fixed_bytes, Address, B256, U256},
+    alloy_primitives::{Address, B256, U256},
     alloy_sol_types::{sol, SolType},
-    block, contract,
+    block,
     crypto::keccak,
     evm, msg,
     prelude::*,
 };
 
-use crate::utils::ecrecover::{EcRecoverTrait, PrecompileEcRecover};
+use crate::utils::eip712::{EIP712Params, EIP712};
+use crate::utils::nonces::Nonces;
+use crate::utils::signature_checker::{is_valid_signature_now, ECDSA_SIGNATURE_LEN};
 
 pub trait ERC20Params {
     const NAME: &'static str;
@@ -21,13 +59,54 @@ pub trait ERC20Params {
     const DECIMALS: u8;
 }
 
+/// Transfer hooks of the token, implemented by the storage holding the token.
+/// For mints `from` is the zero address, and for burns `to` is the zero address.
+pub trait ERC20Hooks<T: ERC20Params>: BorrowMut<ERC20<T>> {
+    /// Hook that is called before any transfer of tokens, including minting and burning.
+    /// Returning an error reverts the transfer.
+    fn before_token_transfer(
+        &mut self,
+        _from: Address,
+        _to: Address,
+        _amount: U256,
+    ) -> Result<(), Vec<u8>> {
+        Ok(())
+    }
+
+    /// Hook that is called after any transfer of tokens, including minting and burning.
+    /// Returning an error reverts the transfer.
+    fn after_token_transfer(
+        &mut self,
+        _from: Address,
+        _to: Address,
+        _amount: U256,
+    ) -> Result<(), Vec<u8>> {
+        Ok(())
+    }
+}
+
+impl<T: ERC20Params> ERC20Hooks<T> for ERC20<T> {}
+
+/// The EIP-712 domain of the token, which uses the token name and version "1"
+pub struct ERC20Domain<T>(PhantomData<T>);
+
+impl<T: ERC20Params> EIP712Params for ERC20Domain<T> {
+    const NAME: &'static str = T::NAME;
+    const VERSION: &'static str = "1";
+}
+
 sol_storage! {
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC20"))]
     pub struct ERC20<T> {
         uint256 total_supply;
         mapping(address => uint256) balances;
         mapping(address => mapping(address => uint256)) allowances;
-        mapping(address => uint256) nonces;
+        /// The permit nonces
+        Nonces nonces;
         PhantomData<T> phantom;
+        /// The EIP-712 domain used for permits
+        EIP712<ERC20Domain<T>> eip712;
     }
 }
 
@@ -38,115 +117,214 @@ sol! {
     /// Emitted when `amount` tokens is approved by `owner` to be used by `spender`.
     event Approval(address indexed owner, address indexed spender, uint256 value);
 
-    /// Insufficient balance.
-    error InsufficientBalance(address from, uint256 have, uint256 want);
-    /// Insufficient allowance.
-    error InsufficientAllowance(address owner, address spender, uint256 have, uint256 want);
+    /// Indicates an error related to the current `balance` of a `sender`. Used in transfers.
+    error ERC20InsufficientBalance(address sender, uint256 balance, uint256 needed);
+    /// Indicates a failure with the `spender`'s `allowance`. Used in transfers.
+    error ERC20InsufficientAllowance(address spender, uint256 allowance, uint256 needed);
     /// The permit has expired.
     error PermitExpired();
     /// The permit is invalid.
     error InvalidPermit();
+    /// The total supply or a balance has overflowed.
+    error ArithmeticOverflow();
+
+    // Legacy errors, only returned when the `legacy-errors` feature is enabled.
+
+    /// Insufficient balance.
+    error InsufficientBalance(address from, uint256 have, uint256 want);
+    /// Insufficient allowance.
+    error InsufficientAllowance(address owner, address spender, uint256 have, uint256 want);
 }
 
 #[derive(SolidityError)]
 pub enum ERC20Error {
-    InsufficientBalance(InsufficientBalance),
-    InsufficientAllowance(InsufficientAllowance),
+    ERC20InsufficientBalance(ERC20InsufficientBalance),
+    ERC20InsufficientAllowance(ERC20InsufficientAllowance),
     PermitExpired(PermitExpired),
     InvalidPermit(InvalidPermit),
+    ArithmeticOverflow(ArithmeticOverflow),
+    InsufficientBalance(InsufficientBalance),
+    InsufficientAllowance(InsufficientAllowance),
 }
 
-// keccak256("1")
-const VERSION_HASH: B256 =
-    fixed_bytes!("c89efdaa54c0f20c7adf612882df0950f5a951637e0307cdcb4c672f298b8bc6");
+// Constructors of the errors that differ between ERC-6093 and the legacy errors
+impl ERC20Error {
+    /// `sender` has a `balance` lower than the `needed` amount.
+    pub fn insufficient_balance(sender: Address, balance: U256, needed: U256) -> Self {
+        if cfg!(feature = "legacy-errors") {
+            Self::InsufficientBalance(InsufficientBalance {
+                from: sender,
+                have: balance,
+                want: needed,
+            })
+        } else {
+            Self::ERC20InsufficientBalance(ERC20InsufficientBalance {
+                sender,
+                balance,
+                needed,
+            })
+        }
+    }
 
-// keccack256("EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)")
-const EIP_712_DOMAIN_HASH: B256 =
-    fixed_bytes!("8b73c3c69bb8fe3d512ecc4cf759cc79239f7b179b0ffacaa9a75d522b39400f");
+    /// `spender` has an `allowance` over the tokens of `owner` lower than the `needed` amount.
+    pub fn insufficient_allowance(
+        owner: Address,
+        spender: Address,
+        allowance: U256,
+        needed: U256,
+    ) -> Self {
+        if cfg!(feature = "legacy-errors") {
+            Self::InsufficientAllowance(InsufficientAllowance {
+                owner,
+                spender,
+                have: allowance,
+                want: needed,
+            })
+        } else {
+            Self::ERC20InsufficientAllowance(ERC20InsufficientAllowance {
+                spender,
+                allowance,
+                needed,
+            })
+        }
+    }
+}
 
-// keccak256("Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)")
-const PERMIT_TYPEHASH: B256 =
-    fixed_bytes!("6e71edae12b1b97f4d1f60370fef10105fa2faae0126114a169c64845d6126c9");
+const PERMIT_TYPEHASH: B256 = crate::const_keccak!(
+    "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)"
+);
 
 // Internal functions
 impl<T: ERC20Params> ERC20<T> {
-    /// Moves `amount` of tokens from `from` to `to`.
-    pub fn _transfer(
-        &mut self,
+    /// Returns `a + b`, or an {ArithmeticOverflow} error if the addition overflows.
+    /// The addition is not checked when the `erc20-unchecked` feature is enabled.
+    #[cfg(not(feature = "erc20-unchecked"))]
+    fn _checked_add(a: U256, b: U256) -> Result<U256, ERC20Error> {
+        a.checked_add(b)
+            .ok_or(ERC20Error::ArithmeticOverflow(ArithmeticOverflow {}))
+    }
+
+    /// Returns `a + b` without checking for overflows.
+    #[cfg(feature = "erc20-unchecked")]
+    fn _checked_add(a: U256, b: U256) -> Result<U256, ERC20Error> {
+        Ok(a + b)
+    }
+
+    /// Moves `amount` of tokens from `from` to `to`, running the hooks of `storage`.
+    pub fn _transfer<H: ERC20Hooks<T>>(
+        storage: &mut H,
         from: Address,
         to: Address,
         amount: U256,
-    ) -> Result<(), ERC20Error> {
-        let mut sender_balance = self.balances.setter(from);
+    ) -> Result<(), Vec<u8>> {
+        storage.before_token_transfer(from, to, amount)?;
+        let this: &mut Self = storage.borrow_mut();
+        let mut sender_balance = this.balances.setter(from);
         let old_sender_balance = sender_balance.get();
         if old_sender_balance < amount {
-            return Err(ERC20Error::InsufficientBalance(InsufficientBalance {
-                from,
-                have: old_sender_balance,
-                want: amount,
-            }));
+            return Err(ERC20Error::insufficient_balance(from, old_sender_balance, amount).into());
         }
         sender_balance.set(old_sender_balance - amount);
-        let mut to_balance = self.balances.setter(to);
-        let new_to_balance = to_balance.get() + amount;
+        let mut to_balance = this.balances.setter(to);
+        let new_to_balance = Self::_checked_add(to_balance.get(), amount)?;
         to_balance.set(new_to_balance);
         evm::log(Transfer {
             from,
             to,
             value: amount,
         });
-        Ok(())
+        storage.after_token_transfer(from, to, amount)
+    }
+
+    /// Moves `amount` of tokens from `from` to `to` on behalf of the caller, spending their
+    /// allowance, and running the hooks of `storage`. This is the logic of `transfer_from`.
+    pub fn _transfer_from<H: ERC20Hooks<T>>(
+        storage: &mut H,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<(), Vec<u8>> {
+        let this: &mut Self = storage.borrow_mut();
+        this._spend_allowance(from, msg::sender(), amount)?;
+        Self::_transfer(storage, from, to, amount)
     }
 
-    /// Mints `amount` tokens to `to`, increasing the total supply.
+    /// Mints `amount` tokens to `to`, increasing the total supply, and running the hooks of
+    /// `storage`.
+    ///
+    /// Requirements:
+    /// - The total supply and the balance of `to` must not overflow.
     ///
     /// Emits a {Transfer} event.
-    pub fn _mint(&mut self, address: Address, amount: U256) {
-        let mut balance = self.balances.setter(address);
-        let new_balance = balance.get() + amount;
+    pub fn _mint<H: ERC20Hooks<T>>(
+        storage: &mut H,
+        address: Address,
+        amount: U256,
+    ) -> Result<(), Vec<u8>> {
+        storage.before_token_transfer(Address::ZERO, address, amount)?;
+        let this: &mut Self = storage.borrow_mut();
+        let new_supply = Self::_checked_add(this.total_supply.get(), amount)?;
+        let mut balance = this.balances.setter(address);
+        let new_balance = Self::_checked_add(balance.get(), amount)?;
         balance.set(new_balance);
-        self.total_supply.set(self.total_supply.get() + amount);
+        this.total_supply.set(new_supply);
         evm::log(Transfer {
             from: Address::ZERO,
             to: address,
             value: amount,
         });
+        storage.after_token_transfer(Address::ZERO, address, amount)
     }
 
-    /// Burns `amount` tokens from `from`, reducing the total supply.
+    /// Burns `amount` tokens from `from`, reducing the total supply, and running the hooks of
+    /// `storage`.
     ///
     /// Emits a {Transfer} event.
-    pub fn _burn(&mut self, address: Address, amount: U256) -> Result<(), ERC20Error> {
-        let mut balance = self.balances.setter(address);
+    pub fn _burn<H: ERC20Hooks<T>>(
+        storage: &mut H,
+        address: Address,
+        amount: U256,
+    ) -> Result<(), Vec<u8>> {
+        storage.before_token_transfer(address, Address::ZERO, amount)?;
+        let this: &mut Self = storage.borrow_mut();
+        let mut balance = this.balances.setter(address);
         let old_balance = balance.get();
         if old_balance < amount {
-            return Err(ERC20Error::InsufficientBalance(InsufficientBalance {
-                from: address,
-                have: old_balance,
-                want: amount,
-            }));
+            return Err(ERC20Error::insufficient_balance(address, old_balance, amount).into());
         }
         balance.set(old_balance - amount);
-        self.total_supply.set(self.total_supply.get() - amount);
+        this.total_supply.set(this.total_supply.get() - amount);
         evm::log(Transfer {
             from: address,
             to: Address::ZERO,
             value: amount,
         });
-        Ok(())
+        storage.after_token_transfer(address, Address::ZERO, amount)
     }
 
-    /// Computes the domain separator for the current contract and chain
-    pub fn _compute_domain_separator(&self) -> B256 {
-        keccak(
-            <sol! { (bytes32, bytes32, bytes32, uint256, address) }>::encode(&(
-                EIP_712_DOMAIN_HASH.0,
-                keccak(T::NAME.as_bytes()).0,
-                VERSION_HASH.0,
-                U256::from(block::chainid()),
-                contract::address(),
-            )),
-        )
+    /// Updates the allowance of `owner` for `spender` based on spent `amount`.
+    /// Does not update the allowance if it is the maximum `uint256` value.
+    pub fn _spend_allowance(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        amount: U256,
+    ) -> Result<(), ERC20Error> {
+        let mut owner_allowances = self.allowances.setter(owner);
+        let mut allowance = owner_allowances.setter(spender);
+        let old_allowance = allowance.get();
+        if old_allowance < amount {
+            return Err(ERC20Error::insufficient_allowance(
+                owner,
+                spender,
+                old_allowance,
+                amount,
+            ));
+        }
+        if old_allowance != U256::MAX {
+            allowance.set(old_allowance - amount);
+        }
+        Ok(())
     }
 }
 
@@ -186,8 +364,8 @@ impl<T: ERC20Params> ERC20<T> {
     /// - `from` must at least have `amount`.
     ///
     /// Emits a {Transfer} event.
-    pub fn transfer(&mut self, to: Address, amount: U256) -> Result<bool, ERC20Error> {
-        self._transfer(msg::sender(), to, amount)?;
+    pub fn transfer(&mut self, to: Address, amount: U256) -> Result<bool, Vec<u8>> {
+        Self::_transfer(self, msg::sender(), to, amount)?;
         Ok(true)
     }
 
@@ -218,35 +396,29 @@ impl<T: ERC20Params> ERC20<T> {
         from: Address,
         to: Address,
         amount: U256,
-    ) -> Result<bool, ERC20Error> {
-        let mut sender_allowances = self.allowances.setter(from);
-        let mut allowance = sender_allowances.setter(msg::sender());
-        let old_allowance = allowance.get();
-        if old_allowance < amount {
-            return Err(ERC20Error::InsufficientAllowance(InsufficientAllowance {
-                owner: from,
-                spender: msg::sender(),
-                have: old_allowance,
-                want: amount,
-            }));
-        }
-        if old_allowance != U256::MAX {
-            allowance.set(old_allowance - amount);
-        }
-        self._transfer(from, to, amount)?;
+    ) -> Result<bool, Vec<u8>> {
+        Self::_transfer_from(self, from, to, amount)?;
         Ok(true)
     }
 
+    /// @dev Returns the current nonce for `owner`.
+    /// This value is used to compute the signature for EIP-2612 permit.
+    pub fn nonces(&self, owner: Address) -> U256 {
+        self.nonces.nonces(owner)
+    }
+
     /// @dev Returns the EIP-712 domain separator for the EIP-2612 permit.
     #[selector(name = "DOMAIN_SEPARATOR")]
     pub fn domain_separator(&self) -> B256 {
-        self._compute_domain_separator()
+        self.eip712._domain_separator()
     }
 
     /// @dev Sets `value` as the allowance of `spender` over the tokens of `owner`,
     /// authorized by a signed approval by `owner`.
+    /// If `owner` is a contract, the signature is validated through ERC-1271.
     ///
     /// Emits a {Approval} event.
+    #[allow(clippy::too_many_arguments)]
     pub fn permit(
         &mut self,
         owner: Address,
@@ -261,8 +433,7 @@ impl<T: ERC20Params> ERC20<T> {
             return Err(ERC20Error::PermitExpired(PermitExpired {}));
         }
 
-        let nonce = self.nonces.get(owner);
-        self.nonces.setter(owner).set(nonce + U256::from(1));
+        let nonce = self.nonces._use_nonce(owner);
 
         let struct_hash = keccak(
             <sol! { (bytes32, address, address, uint256, uint256, uint256) }>::encode(&(
@@ -275,25 +446,18 @@ impl<T: ERC20Params> ERC20<T> {
             )),
         );
 
-        let signed_hash = keccak(<sol! { (string, bytes32, bytes32) }>::encode_packed(&(
-            "\x19\x01".to_string(),
-            self._compute_domain_separator().0,
-            struct_hash.0,
-        )));
+        let signed_hash = self.eip712._hash_typed_data(struct_hash);
 
-        let recovered_address = Address::from_slice(
-            &PrecompileEcRecover::ecrecover(&signed_hash.0, v, &r.0, &s.0)
-                .map_err(|_| ERC20Error::InvalidPermit(InvalidPermit {}))?,
-        );
-
-        if recovered_address.is_zero() || recovered_address != owner {
+        // Smart contract wallets receive the signature packed as `r ‖ s ‖ v`
+        let mut signature = [0_u8; ECDSA_SIGNATURE_LEN];
+        signature[..32].copy_from_slice(&r.0);
+        signature[32..64].copy_from_slice(&s.0);
+        signature[64] = v;
+        if !is_valid_signature_now(owner, signed_hash, &signature) {
             return Err(ERC20Error::InvalidPermit(InvalidPermit {}));
         }
 
-        self.allowances
-            .setter(recovered_address)
-            .setter(spender)
-            .set(value);
+        self.allowances.setter(owner).setter(spender).set(value);
 
         evm::log(Approval {
             owner,
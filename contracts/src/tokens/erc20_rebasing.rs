@@ -0,0 +1,497 @@
+//! Rebasing ERC20 base contract where balances are shares of a total supply that can change.
+//! The logic was based off of: https://github.com/lidofinance/lido-dao/blob/master/contracts/0.4.24/StETH.sol
+//!
+//! Accounts hold shares instead of tokens, and the balance of an account is its portion of the
+//! total supply: `shares * total_supply / total_shares`. Rebasing sets a new total supply without
+//! moving any shares, so every balance grows or shrinks in proportion (ex. to distribute staking
+//! rewards or to apply slashing). Only accounts with the {REBASER_ROLE} can rebase.
+//!
+//! Token amounts are converted to shares rounding down, so a transfer of `amount` tokens can move
+//! slightly less than `amount`, and the balance of an account can be lower than the sum of the
+//! amounts it received. Integrations that need exact accounting should use the share functions
+//! (ex. `transfer_shares`) instead.
+//!
+//! `ERC20Rebasing` wraps `AccessControl` to restrict rebases. Contracts must inherit
+//! `ERC20Rebasing<T>` and `AccessControl`, and implement `Borrow`/`BorrowMut` of `AccessControl`
+//! by returning the `access` field of the `ERC20Rebasing` storage. Stylus contracts do not have
+//! constructors, so the inheriting contract must grant the admin and rebaser roles. Like stETH, it
+//! should also mint a small amount of shares to a dead address first, so that the share rate
+//! cannot be manipulated while the total supply is tiny.
+
+use alloc::{string::String, vec::Vec};
+use core::marker::PhantomData;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::sol,
+    evm, msg,
+    prelude::*,
+};
+
+use crate::access::access_control::AccessControl;
+use crate::utils::fixed_point_math::{full_mul_div, full_mul_div_up};
+
+pub trait ERC20RebasingParams {
+    const NAME: &'static str;
+    const SYMBOL: &'static str;
+    const DECIMALS: u8;
+}
+
+/// The role allowed to rebase the token.
+pub const REBASER_ROLE: B256 = crate::const_keccak!("REBASER_ROLE");
+
+sol_storage! {
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC20Rebasing"))]
+    pub struct ERC20Rebasing<T> {
+        /// The amount of tokens in existence, which is shared between the shareholders
+        uint256 total_supply;
+        /// The amount of shares in existence
+        uint256 total_shares;
+        /// Maps account to its amount of shares
+        mapping(address => uint256) shares;
+        /// Allowances are in tokens, not in shares
+        mapping(address => mapping(address => uint256)) allowances;
+        /// The roles, where the {REBASER_ROLE} can rebase the token
+        AccessControl access;
+        PhantomData<T> phantom;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when `amount` tokens is transferred from `from` to `to`.
+    event Transfer(address indexed from, address indexed to, uint256 value);
+    /// Emitted when `amount` tokens is approved by `owner` to be used by `spender`.
+    event Approval(address indexed owner, address indexed spender, uint256 value);
+    /// Emitted when `sharesValue` shares are transferred from `from` to `to`, along with {Transfer}.
+    event TransferShares(address indexed from, address indexed to, uint256 sharesValue);
+    /// Emitted when the total supply is rebased from `preTotalSupply` to `postTotalSupply`.
+    event Rebase(uint256 preTotalSupply, uint256 postTotalSupply, uint256 totalShares);
+
+    /// Indicates an error related to the current `balance` of a `sender`. Used in transfers.
+    error ERC20InsufficientBalance(address sender, uint256 balance, uint256 needed);
+    /// Indicates a failure with the `spender`'s `allowance`. Used in transfers.
+    error ERC20InsufficientAllowance(address spender, uint256 allowance, uint256 needed);
+    /// The `sender` has `shares` shares, fewer than the `needed` shares.
+    error ERC20RebasingInsufficientShares(address sender, uint256 shares, uint256 needed);
+    /// The total supply cannot be rebased to zero while shares exist.
+    error ERC20RebasingInvalidTotalSupply();
+    /// The total supply cannot be rebased before any shares exist.
+    error ERC20RebasingNoShares();
+    /// The total supply, the total shares or a conversion has overflowed.
+    error ArithmeticOverflow();
+}
+
+#[derive(SolidityError)]
+pub enum ERC20RebasingError {
+    ERC20InsufficientBalance(ERC20InsufficientBalance),
+    ERC20InsufficientAllowance(ERC20InsufficientAllowance),
+    ERC20RebasingInsufficientShares(ERC20RebasingInsufficientShares),
+    ERC20RebasingInvalidTotalSupply(ERC20RebasingInvalidTotalSupply),
+    ERC20RebasingNoShares(ERC20RebasingNoShares),
+    ArithmeticOverflow(ArithmeticOverflow),
+}
+
+impl ERC20RebasingError {
+    fn overflow() -> Self {
+        Self::ArithmeticOverflow(ArithmeticOverflow {})
+    }
+}
+
+// Internal functions
+impl<T: ERC20RebasingParams> ERC20Rebasing<T> {
+    /// Returns the amount of shares worth `amount` tokens rounded down, or `None` on overflow.
+    /// Shares and tokens are exchanged 1:1 while no shares exist.
+    pub fn _shares_by_amount(&self, amount: U256) -> Option<U256> {
+        let total_shares = self.total_shares.get();
+        let total_supply = self.total_supply.get();
+        if total_shares.is_zero() || total_supply.is_zero() {
+            return Some(amount);
+        }
+        full_mul_div(amount, total_shares, total_supply)
+    }
+
+    /// Returns the amount of shares worth `amount` tokens rounded up, or `None` on overflow.
+    pub fn _shares_by_amount_up(&self, amount: U256) -> Option<U256> {
+        let total_shares = self.total_shares.get();
+        let total_supply = self.total_supply.get();
+        if total_shares.is_zero() || total_supply.is_zero() {
+            return Some(amount);
+        }
+        full_mul_div_up(amount, total_shares, total_supply)
+    }
+
+    /// Returns the amount of tokens `shares` are worth rounded down, or `None` on overflow.
+    pub fn _amount_by_shares(&self, shares: U256) -> Option<U256> {
+        let total_shares = self.total_shares.get();
+        if total_shares.is_zero() {
+            return Some(shares);
+        }
+        full_mul_div(shares, self.total_supply.get(), total_shares)
+    }
+
+    /// Returns the amount of tokens `shares` are worth rounded up, or `None` on overflow.
+    pub fn _amount_by_shares_up(&self, shares: U256) -> Option<U256> {
+        let total_shares = self.total_shares.get();
+        if total_shares.is_zero() {
+            return Some(shares);
+        }
+        full_mul_div_up(shares, self.total_supply.get(), total_shares)
+    }
+
+    /// Moves `shares` shares from `from` to `to`.
+    ///
+    /// Emits a {Transfer} event with the amount of tokens worth `amount` and a {TransferShares} event.
+    pub fn _transfer_shares(
+        &mut self,
+        from: Address,
+        to: Address,
+        shares: U256,
+        amount: U256,
+    ) -> Result<(), ERC20RebasingError> {
+        let mut sender_shares = self.shares.setter(from);
+        let old_sender_shares = sender_shares.get();
+        if old_sender_shares < shares {
+            return Err(ERC20RebasingError::ERC20RebasingInsufficientShares(
+                ERC20RebasingInsufficientShares {
+                    sender: from,
+                    shares: old_sender_shares,
+                    needed: shares,
+                },
+            ));
+        }
+        sender_shares.set(old_sender_shares - shares);
+        // Cannot overflow, as the shares of an account are at most the total shares
+        let mut to_shares = self.shares.setter(to);
+        let new_to_shares = to_shares.get() + shares;
+        to_shares.set(new_to_shares);
+        evm::log(Transfer {
+            from,
+            to,
+            value: amount,
+        });
+        evm::log(TransferShares {
+            from,
+            to,
+            sharesValue: shares,
+        });
+        Ok(())
+    }
+
+    /// Moves `amount` tokens from `from` to `to`, as the shares worth `amount` rounded down.
+    pub fn _transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<(), ERC20RebasingError> {
+        let balance = self.balance_of(from);
+        if balance < amount {
+            return Err(ERC20RebasingError::ERC20InsufficientBalance(
+                ERC20InsufficientBalance {
+                    sender: from,
+                    balance,
+                    needed: amount,
+                },
+            ));
+        }
+        let shares = self
+            ._shares_by_amount(amount)
+            .ok_or_else(ERC20RebasingError::overflow)?;
+        self._transfer_shares(from, to, shares, amount)
+    }
+
+    /// Mints `amount` tokens to `to`, increasing the total supply and issuing the shares worth
+    /// `amount` at the current rate.
+    ///
+    /// Requirements:
+    /// - The total supply and the total shares must not overflow.
+    ///
+    /// Emits a {Transfer} and a {TransferShares} event.
+    pub fn _mint(&mut self, to: Address, amount: U256) -> Result<(), ERC20RebasingError> {
+        let shares = self
+            ._shares_by_amount(amount)
+            .ok_or_else(ERC20RebasingError::overflow)?;
+        let new_supply = self
+            .total_supply
+            .get()
+            .checked_add(amount)
+            .ok_or_else(ERC20RebasingError::overflow)?;
+        let new_total_shares = self
+            .total_shares
+            .get()
+            .checked_add(shares)
+            .ok_or_else(ERC20RebasingError::overflow)?;
+        self.total_supply.set(new_supply);
+        self.total_shares.set(new_total_shares);
+        let mut to_shares = self.shares.setter(to);
+        let new_to_shares = to_shares.get() + shares;
+        to_shares.set(new_to_shares);
+        evm::log(Transfer {
+            from: Address::ZERO,
+            to,
+            value: amount,
+        });
+        evm::log(TransferShares {
+            from: Address::ZERO,
+            to,
+            sharesValue: shares,
+        });
+        Ok(())
+    }
+
+    /// Burns `amount` tokens from `from`, reducing the total supply and redeeming the shares worth
+    /// `amount` rounded up, so that the remaining holders are not diluted.
+    ///
+    /// Emits a {Transfer} and a {TransferShares} event.
+    pub fn _burn(&mut self, from: Address, amount: U256) -> Result<(), ERC20RebasingError> {
+        let balance = self.balance_of(from);
+        if balance < amount {
+            return Err(ERC20RebasingError::ERC20InsufficientBalance(
+                ERC20InsufficientBalance {
+                    sender: from,
+                    balance,
+                    needed: amount,
+                },
+            ));
+        }
+        let shares = self
+            ._shares_by_amount_up(amount)
+            .ok_or_else(ERC20RebasingError::overflow)?;
+        let mut from_shares = self.shares.setter(from);
+        let old_from_shares = from_shares.get();
+        if old_from_shares < shares {
+            return Err(ERC20RebasingError::ERC20RebasingInsufficientShares(
+                ERC20RebasingInsufficientShares {
+                    sender: from,
+                    shares: old_from_shares,
+                    needed: shares,
+                },
+            ));
+        }
+        from_shares.set(old_from_shares - shares);
+        self.total_shares.set(self.total_shares.get() - shares);
+        self.total_supply.set(self.total_supply.get() - amount);
+        evm::log(Transfer {
+            from,
+            to: Address::ZERO,
+            value: amount,
+        });
+        evm::log(TransferShares {
+            from,
+            to: Address::ZERO,
+            sharesValue: shares,
+        });
+        Ok(())
+    }
+
+    /// Sets the total supply to `new_total_supply`, changing every balance in proportion.
+    ///
+    /// Requirements:
+    /// - Shares must exist. Otherwise the first mint would issue shares 1:1 against the rebased
+    ///   supply, and the minter would own all of it.
+    /// - The total supply cannot be zero while shares exist.
+    ///
+    /// Emits a {Rebase} event.
+    pub fn _rebase(&mut self, new_total_supply: U256) -> Result<(), ERC20RebasingError> {
+        let total_shares = self.total_shares.get();
+        if total_shares.is_zero() {
+            return Err(ERC20RebasingError::ERC20RebasingNoShares(
+                ERC20RebasingNoShares {},
+            ));
+        }
+        if new_total_supply.is_zero() {
+            return Err(ERC20RebasingError::ERC20RebasingInvalidTotalSupply(
+                ERC20RebasingInvalidTotalSupply {},
+            ));
+        }
+        let pre_total_supply = self.total_supply.get();
+        self.total_supply.set(new_total_supply);
+        evm::log(Rebase {
+            preTotalSupply: pre_total_supply,
+            postTotalSupply: new_total_supply,
+            totalShares: total_shares,
+        });
+        Ok(())
+    }
+
+    /// Updates the allowance of `owner` for `spender` based on spent `amount`.
+    /// Does not update the allowance if it is the maximum `uint256` value.
+    pub fn _spend_allowance(
+        &mut self,
+        owner: Address,
+        spender: Address,
+        amount: U256,
+    ) -> Result<(), ERC20RebasingError> {
+        let mut owner_allowances = self.allowances.setter(owner);
+        let mut allowance = owner_allowances.setter(spender);
+        let old_allowance = allowance.get();
+        if old_allowance < amount {
+            return Err(ERC20RebasingError::ERC20InsufficientAllowance(
+                ERC20InsufficientAllowance {
+                    spender,
+                    allowance: old_allowance,
+                    needed: amount,
+                },
+            ));
+        }
+        if old_allowance != U256::MAX {
+            allowance.set(old_allowance - amount);
+        }
+        Ok(())
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC20RebasingParams> ERC20Rebasing<T> {
+    pub fn name() -> String {
+        T::NAME.into()
+    }
+
+    pub fn symbol() -> String {
+        T::SYMBOL.into()
+    }
+
+    pub fn decimals() -> u8 {
+        T::DECIMALS
+    }
+
+    /// Returns the amount of tokens in existence.
+    pub fn total_supply(&self) -> U256 {
+        self.total_supply.get()
+    }
+
+    /// Returns the amount of shares in existence.
+    pub fn total_shares(&self) -> U256 {
+        self.total_shares.get()
+    }
+
+    /// Returns the amount of tokens owned by `owner`, which is the value of its shares.
+    pub fn balance_of(&self, owner: Address) -> U256 {
+        // Cannot overflow, as the shares of an account are at most the total shares
+        self._amount_by_shares(self.shares.get(owner))
+            .unwrap_or_default()
+    }
+
+    /// Returns the amount of shares owned by `owner`.
+    pub fn shares_of(&self, owner: Address) -> U256 {
+        self.shares.get(owner)
+    }
+
+    /// Returns the amount of shares worth `amount` tokens, rounded down.
+    pub fn get_shares_by_amount(&self, amount: U256) -> Result<U256, ERC20RebasingError> {
+        self._shares_by_amount(amount)
+            .ok_or_else(ERC20RebasingError::overflow)
+    }
+
+    /// Returns the amount of tokens `shares` are worth, rounded down.
+    pub fn get_amount_by_shares(&self, shares: U256) -> Result<U256, ERC20RebasingError> {
+        self._amount_by_shares(shares)
+            .ok_or_else(ERC20RebasingError::overflow)
+    }
+
+    /// Returns the amount of tokens that `spender` can spend on behalf of `owner`.
+    pub fn allowance(&self, owner: Address, spender: Address) -> U256 {
+        self.allowances.getter(owner).get(spender)
+    }
+
+    /// Sets `amount` as the allowance of `spender` over the caller's tokens.
+    ///
+    /// Emits a {Approval} event.
+    pub fn approve(&mut self, spender: Address, amount: U256) -> bool {
+        self.allowances
+            .setter(msg::sender())
+            .insert(spender, amount);
+        evm::log(Approval {
+            owner: msg::sender(),
+            spender,
+            value: amount,
+        });
+        true
+    }
+
+    /// Transfer `amount` tokens from the caller to `to`.
+    ///
+    /// Requirements:
+    /// - The caller must at least have `amount`.
+    ///
+    /// Emits a {Transfer} and a {TransferShares} event.
+    pub fn transfer(&mut self, to: Address, amount: U256) -> Result<bool, ERC20RebasingError> {
+        self._transfer(msg::sender(), to, amount)?;
+        Ok(true)
+    }
+
+    /// Transfers `amount` tokens from `from` to `to`.
+    ///
+    /// Requirements:
+    /// - `from` must at least have `amount`.
+    /// - The caller must have at least `amount` of allowance to transfer the tokens of `from`.
+    ///
+    /// Emits a {Transfer} and a {TransferShares} event.
+    pub fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<bool, ERC20RebasingError> {
+        self._spend_allowance(from, msg::sender(), amount)?;
+        self._transfer(from, to, amount)?;
+        Ok(true)
+    }
+
+    /// Transfers exactly `shares` shares from the caller to `to`, and returns the amount of tokens
+    /// they are worth.
+    ///
+    /// Requirements:
+    /// - The caller must at least have `shares`.
+    ///
+    /// Emits a {Transfer} and a {TransferShares} event.
+    pub fn transfer_shares(
+        &mut self,
+        to: Address,
+        shares: U256,
+    ) -> Result<U256, ERC20RebasingError> {
+        let amount = self.get_amount_by_shares(shares)?;
+        self._transfer_shares(msg::sender(), to, shares, amount)?;
+        Ok(amount)
+    }
+
+    /// Transfers exactly `shares` shares from `from` to `to`, and returns the amount of tokens
+    /// they are worth.
+    ///
+    /// Requirements:
+    /// - `from` must at least have `shares`.
+    /// - The caller must have an allowance of at least the amount of tokens `shares` are worth,
+    ///   rounded up.
+    ///
+    /// Emits a {Transfer} and a {TransferShares} event.
+    pub fn transfer_shares_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        shares: U256,
+    ) -> Result<U256, ERC20RebasingError> {
+        let allowance_needed = self
+            ._amount_by_shares_up(shares)
+            .ok_or_else(ERC20RebasingError::overflow)?;
+        self._spend_allowance(from, msg::sender(), allowance_needed)?;
+        let amount = self.get_amount_by_shares(shares)?;
+        self._transfer_shares(from, to, shares, amount)?;
+        Ok(amount)
+    }
+
+    /// Sets the total supply to `new_total_supply`, changing every balance in proportion.
+    ///
+    /// Requirements:
+    /// - The caller must have the {REBASER_ROLE}.
+    /// - Shares must exist.
+    /// - The total supply cannot be zero while shares exist.
+    ///
+    /// Emits a {Rebase} event.
+    pub fn rebase(&mut self, new_total_supply: U256) -> Result<(), Vec<u8>> {
+        self.access.only_role(REBASER_ROLE)?;
+        self._rebase(new_total_supply)?;
+        Ok(())
+    }
+}
@@ -1,25 +1,100 @@
 //! ERC721 base contract.
 //! The logic was based off of: https://github.com/cygaar/ERC721Stylus/blob/main/src/erc721.rs
 //! Doc comments are forked from: https://github.com/Vectorized/solady/blob/main/src/tokens/ERC721.sol
+//!
+//! Logic can be run on every mint, transfer and burn by implementing `ERC721Hooks` on the storage
+//! holding the collection, and moving tokens through it: `_transfer`, `_mint`, `_burn` and their
+//! variants take that storage and run its hooks, ex. `ERC721::_mint(&mut self.erc721, to, id)` for
+//! a collection without hooks. The external transfer functions of `ERC721` run the no-op hooks of
+//! `ERC721` itself, so contracts with hooks must override `transfer_from` and both
+//! `safe_transfer_from` to call `_transfer_from` and `_safe_transfer` with their own storage (see
+//! `ERC4907` for an example). Hooks can revert with any error, so the functions that transfer
+//! tokens return raw revert data instead of an `ERC721Error`.
+//!
+//! Large initial supplies can be minted in batches with `_mint_consecutive` (ERC-2309). Batches
+//! only record their last id and owner, and the owner of each token is written to storage on its
+//! first transfer or burn.
+//!
+//! Reverts use the ERC-6093 custom errors (ex. `ERC721NonexistentToken`), like OpenZeppelin 5.x.
+//! The errors of previous versions (ex. `InvalidTokenId`) can be restored with the `legacy-errors`
+//! feature. Extensions should build these errors with the constructors of `ERC721Error` (ex.
+//! `ERC721Error::nonexistent_token`) so that they follow the feature.
 
 use alloc::{string::String, vec, vec::Vec};
 use core::{borrow::BorrowMut, marker::PhantomData};
 use stylus_sdk::{
     abi::Bytes,
     alloy_primitives::{Address, U256},
-    alloy_sol_types::sol,
+    alloy_sol_types::{sol, SolCall},
     evm, msg,
     prelude::*,
 };
 
+use crate::utils::excessively_safe_call::{excessively_safe_call, ALL_GAS};
+use crate::utils::introspection::{self, InterfaceIds};
+
+/// The ERC165 interface id of ERC721.
+pub const IERC721: u32 = crate::interface_id!(
+    "balanceOf(address)",
+    "ownerOf(uint256)",
+    "safeTransferFrom(address,address,uint256,bytes)",
+    "safeTransferFrom(address,address,uint256)",
+    "transferFrom(address,address,uint256)",
+    "approve(address,uint256)",
+    "setApprovalForAll(address,bool)",
+    "getApproved(uint256)",
+    "isApprovedForAll(address,address)",
+);
+
+/// The ERC165 interface id of the ERC721 metadata extension.
+pub const IERC721_METADATA: u32 = crate::interface_id!("name()", "symbol()", "tokenURI(uint256)");
+
 pub trait ERC721Params {
     const NAME: &'static str;
     const SYMBOL: &'static str;
     fn token_uri(id: U256) -> String;
+
+    /// The maximum number of tokens minted by a single `_mint_consecutive` call.
+    /// ERC-2309 recommends keeping batches small enough for indexers to process.
+    const MAX_BATCH_SIZE: u64 = 5000;
+}
+
+/// Transfer hooks of the collection, implemented by the storage holding the collection.
+/// The hooks are called with the `batch_size` consecutive ids starting from `first_id`, which is a
+/// single token except for the batches of `_mint_consecutive`, so that batches run them once.
+/// For mints `from` is the zero address, and for burns `to` is the zero address.
+pub trait ERC721Hooks<T: ERC721Params>: BorrowMut<ERC721<T>> {
+    /// Hook that is called before any token transfer, including minting and burning.
+    /// Returning an error reverts the transfer.
+    fn before_token_transfer(
+        &mut self,
+        _from: Address,
+        _to: Address,
+        _first_id: U256,
+        _batch_size: U256,
+    ) -> Result<(), Vec<u8>> {
+        Ok(())
+    }
+
+    /// Hook that is called after any token transfer, including minting and burning.
+    /// Returning an error reverts the transfer.
+    fn after_token_transfer(
+        &mut self,
+        _from: Address,
+        _to: Address,
+        _first_id: U256,
+        _batch_size: U256,
+    ) -> Result<(), Vec<u8>> {
+        Ok(())
+    }
 }
 
+impl<T: ERC721Params> ERC721Hooks<T> for ERC721<T> {}
+
 sol_storage! {
     /// ERC721 implements all ERC-721 methods
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC721"))]
     pub struct ERC721<T: ERC721Params> {
         /// Maps id to owner
         mapping(uint256 => address) owners;
@@ -29,6 +104,16 @@ sol_storage! {
         mapping(address => uint256) balance;
         /// Maps the approved spenders for a given address
         mapping(address => mapping(address => bool)) approved_for_all;
+        /// Number of tokens minted in consecutive batches, which own ids `0..next_consecutive_id`
+        uint256 next_consecutive_id;
+        /// Exclusive end id of each consecutive batch, in increasing order
+        uint256[] batch_ends;
+        /// Owner of each consecutive batch
+        address[] batch_owners;
+        /// Maps id to whether a token minted in a consecutive batch has been burned
+        mapping(uint256 => bool) consecutive_burned;
+        /// Whether a token has been minted with `_mint` above the consecutive batches
+        bool minted_individually;
         PhantomData<T> phantom;
     }
 }
@@ -41,6 +126,27 @@ sol! {
     event Approval(address indexed owner, address indexed approved, uint256 indexed id);
     /// Emitted when `owner` enables or disables `operator` to manage all of their tokens.
     event ApprovalForAll(address indexed owner, address indexed operator, bool approved);
+    /// Emitted when tokens `fromTokenId` to `toTokenId` (inclusive) are transferred from `fromAddress` to `toAddress`.
+    event ConsecutiveTransfer(uint256 indexed fromTokenId, uint256 toTokenId, address indexed fromAddress, address indexed toAddress);
+
+    /// Indicates a `tokenId` whose `owner` is the zero address.
+    error ERC721NonexistentToken(uint256 tokenId);
+    /// Indicates an error related to the ownership over a particular token. Used in transfers.
+    error ERC721IncorrectOwner(address sender, uint256 tokenId, address owner);
+    /// Indicates a failure with the token `sender`. Used in transfers.
+    error ERC721InvalidSender(address sender);
+    /// Indicates a failure with the token `receiver`. Used in transfers.
+    error ERC721InvalidReceiver(address receiver);
+    /// Indicates a failure with the `operator`'s approval. Used in transfers.
+    error ERC721InsufficientApproval(address operator, uint256 tokenId);
+    /// The token is locked and cannot be transferred.
+    error TokenLocked(uint256 id);
+    /// The batch is larger than the maximum batch size.
+    error ERC721ExceededMaxBatchMint(uint256 batchSize, uint256 maxBatch);
+    /// Batch mint is not allowed once tokens have been minted individually.
+    error ERC721ForbiddenBatchMint();
+
+    // Legacy errors, only returned when the `legacy-errors` feature is enabled.
 
     /// Token already minted.
     error AlreadyMinted();
@@ -58,6 +164,14 @@ sol! {
 
 #[derive(SolidityError)]
 pub enum ERC721Error {
+    ERC721NonexistentToken(ERC721NonexistentToken),
+    ERC721IncorrectOwner(ERC721IncorrectOwner),
+    ERC721InvalidSender(ERC721InvalidSender),
+    ERC721InvalidReceiver(ERC721InvalidReceiver),
+    ERC721InsufficientApproval(ERC721InsufficientApproval),
+    TokenLocked(TokenLocked),
+    ERC721ExceededMaxBatchMint(ERC721ExceededMaxBatchMint),
+    ERC721ForbiddenBatchMint(ERC721ForbiddenBatchMint),
     AlreadyMinted(AlreadyMinted),
     InvalidTokenId(InvalidTokenId),
     NotOwner(NotOwner),
@@ -66,16 +180,112 @@ pub enum ERC721Error {
     ReceiverRefused(ReceiverRefused),
 }
 
+// Constructors of the errors that differ between ERC-6093 and the legacy errors
+impl ERC721Error {
+    /// Token `id` does not exist.
+    pub fn nonexistent_token(id: U256) -> Self {
+        if cfg!(feature = "legacy-errors") {
+            Self::InvalidTokenId(InvalidTokenId { id })
+        } else {
+            Self::ERC721NonexistentToken(ERC721NonexistentToken { tokenId: id })
+        }
+    }
+
+    /// Token `id` is owned by `owner` instead of `sender`.
+    pub fn incorrect_owner(sender: Address, id: U256, owner: Address) -> Self {
+        if cfg!(feature = "legacy-errors") {
+            Self::NotOwner(NotOwner {
+                from: sender,
+                id,
+                real_owner: owner,
+            })
+        } else {
+            Self::ERC721IncorrectOwner(ERC721IncorrectOwner {
+                sender,
+                tokenId: id,
+                owner,
+            })
+        }
+    }
+
+    /// `operator` is not approved to manage token `id` of `owner`.
+    pub fn insufficient_approval(operator: Address, id: U256, owner: Address) -> Self {
+        if cfg!(feature = "legacy-errors") {
+            Self::NotApproved(NotApproved {
+                id,
+                owner,
+                spender: operator,
+            })
+        } else {
+            Self::ERC721InsufficientApproval(ERC721InsufficientApproval {
+                operator,
+                tokenId: id,
+            })
+        }
+    }
+
+    /// The token cannot be minted as it already exists.
+    pub fn already_minted() -> Self {
+        if cfg!(feature = "legacy-errors") {
+            Self::AlreadyMinted(AlreadyMinted {})
+        } else {
+            Self::ERC721InvalidSender(ERC721InvalidSender {
+                sender: Address::ZERO,
+            })
+        }
+    }
+
+    /// Token `id` cannot be transferred to the zero address.
+    pub fn transfer_to_zero(id: U256) -> Self {
+        if cfg!(feature = "legacy-errors") {
+            Self::TransferToZero(TransferToZero { id })
+        } else {
+            Self::ERC721InvalidReceiver(ERC721InvalidReceiver {
+                receiver: Address::ZERO,
+            })
+        }
+    }
+
+    /// `receiver` did not accept the safe transfer of token `id`.
+    pub fn receiver_refused(receiver: Address, id: U256) -> Self {
+        if cfg!(feature = "legacy-errors") {
+            Self::ReceiverRefused(ReceiverRefused { receiver, id })
+        } else {
+            Self::ERC721InvalidReceiver(ERC721InvalidReceiver { receiver })
+        }
+    }
+}
+
 impl<T: ERC721Params> ERC721<T> {
+    /// Returns the owner of token `id`, or the zero address if it does not exist.
+    /// Falls back to the owner of its consecutive batch if the owner has not been written yet.
+    pub fn _owner_of(&self, id: U256) -> Address {
+        let owner = self.owners.get(id);
+        if !owner.is_zero()
+            || id >= self.next_consecutive_id.get()
+            || self.consecutive_burned.get(id)
+        {
+            return owner;
+        }
+
+        // Binary search for the first batch ending after `id`
+        let (mut low, mut high) = (0, self.batch_ends.len());
+        while low < high {
+            let mid = (low + high) / 2;
+            if self.batch_ends.get(mid).unwrap_or_default() > id {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        self.batch_owners.get(low).unwrap_or_default()
+    }
+
     /// Requires that msg::sender() is authorized to spend a given token
     fn _require_authorized_to_spend(&self, from: Address, id: U256) -> Result<(), ERC721Error> {
         let owner = self.owner_of(id)?;
         if from != owner {
-            return Err(ERC721Error::NotOwner(NotOwner {
-                from,
-                id,
-                real_owner: owner,
-            }));
+            return Err(ERC721Error::incorrect_owner(from, id, owner));
         }
 
         if msg::sender() == owner {
@@ -87,72 +297,96 @@ impl<T: ERC721Params> ERC721<T> {
         if msg::sender() == self.approved.get(id) {
             return Ok(());
         }
-        Err(ERC721Error::NotApproved(NotApproved {
-            owner,
-            spender: msg::sender(),
-            id,
-        }))
+        Err(ERC721Error::insufficient_approval(msg::sender(), id, owner))
     }
 
-    /// Internal transfer function
-    pub fn _transfer(&mut self, id: U256, from: Address, to: Address) -> Result<(), ERC721Error> {
-        let mut owner = self.owners.setter(id);
-        let previous_owner = owner.get();
+    /// Internal transfer function, running the hooks of `storage`
+    pub fn _transfer<H: ERC721Hooks<T>>(
+        storage: &mut H,
+        id: U256,
+        from: Address,
+        to: Address,
+    ) -> Result<(), Vec<u8>> {
+        let previous_owner = storage.borrow()._owner_of(id);
         if previous_owner != from {
-            return Err(ERC721Error::NotOwner(NotOwner {
-                from,
-                id,
-                real_owner: previous_owner,
-            }));
+            return Err(ERC721Error::incorrect_owner(from, id, previous_owner).into());
         }
-        owner.set(to);
+        storage.before_token_transfer(from, to, id, U256::from(1))?;
+        let this: &mut Self = storage.borrow_mut();
+        this.owners.setter(id).set(to);
 
         // right now working with storage can be verbose, but this will change upcoming version of the Stylus SDK
-        let mut from_balance = self.balance.setter(from);
+        let mut from_balance = this.balance.setter(from);
         let balance = from_balance.get() - U256::from(1);
         from_balance.set(balance);
 
-        let mut to_balance = self.balance.setter(to);
+        let mut to_balance = this.balance.setter(to);
         let balance = to_balance.get() + U256::from(1);
         to_balance.set(balance);
 
-        self.approved.delete(id);
+        this.approved.delete(id);
         evm::log(Transfer { from, to, id });
-        Ok(())
+        storage.after_token_transfer(from, to, id, U256::from(1))
+    }
+
+    /// Transfers token `id` from `from` to `to` on behalf of the caller, running the hooks of
+    /// `storage`. This is the logic of `transfer_from`.
+    ///
+    /// Requirements:
+    ///
+    /// - Token `id` must exist.
+    /// - `from` must be the owner of the token.
+    /// - `to` cannot be the zero address.
+    /// - The caller must be the owner of the token, or be approved to manage the token.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn _transfer_from<H: ERC721Hooks<T>>(
+        storage: &mut H,
+        from: Address,
+        to: Address,
+        id: U256,
+    ) -> Result<(), Vec<u8>> {
+        if to.is_zero() {
+            return Err(ERC721Error::transfer_to_zero(id).into());
+        }
+        let this: &mut Self = storage.borrow_mut();
+        this._require_authorized_to_spend(from, id)?;
+        Self::_transfer(storage, id, from, to)
     }
 
     /// Calls the onERC721Received callback function if the receiver is not an EOA (code size > 0).
     /// Throws an error if the receiver cannot be called or the returned value is not ERC721_RECEIVED_SELECTOR.
-    fn _call_receiver<S: TopLevelStorage>(
-        storage: &mut S,
+    /// Only the first word of the returned data is copied, so that the receiver cannot grief the
+    /// transfer with a large return payload.
+    ///
+    /// `storage` is mutably borrowed so that no storage references are held across the call.
+    pub fn _call_receiver<S: TopLevelStorage>(
+        _storage: &mut S,
         id: U256,
         from: Address,
         to: Address,
         data: Vec<u8>,
     ) -> Result<(), ERC721Error> {
         if to.has_code() {
-            let receiver = IERC721TokenReceiver::new(to);
-            let received = receiver
-                .on_erc_721_received(storage, msg::sender(), from, id, data)
-                .map_err(|_| {
-                    ERC721Error::ReceiverRefused(ReceiverRefused {
-                        receiver: receiver.address,
-                        id,
-                    })
-                })?
-                .0;
-
-            if u32::from_be_bytes(received) != ERC721_RECEIVED_SELECTOR {
-                return Err(ERC721Error::ReceiverRefused(ReceiverRefused {
-                    receiver: receiver.address,
-                    id,
-                }));
+            let calldata = onERC721ReceivedCall {
+                operator: msg::sender(),
+                from,
+                id,
+                data,
+            }
+            .encode();
+            let result = excessively_safe_call(to, ALL_GAS, U256::ZERO, 32, &calldata);
+            let received = result.data().get(..4);
+            if !result.is_success() || received != Some(&ERC721_RECEIVED_SELECTOR.to_be_bytes()[..])
+            {
+                return Err(ERC721Error::receiver_refused(to, id));
             }
         }
         Ok(())
     }
 
-    /// Transfers token `id` from `from` to `to`.
+    /// Transfers token `id` from `from` to `to` on behalf of the caller, running the hooks of the
+    /// holder `H` of the collection. This is the logic of `safe_transfer_from`.
     ///
     /// Requirements:
     ///
@@ -164,18 +398,19 @@ impl<T: ERC721Params> ERC721<T> {
     ///   {IERC721Receiver-onERC721Received}, which is called upon a safe transfer.
     ///
     /// Emits a {Transfer} event.
-    pub fn _safe_transfer<S: TopLevelStorage + BorrowMut<Self>>(
+    pub fn _safe_transfer<S: TopLevelStorage + BorrowMut<H>, H: ERC721Hooks<T>>(
         storage: &mut S,
         id: U256,
         from: Address,
         to: Address,
         data: Vec<u8>,
-    ) -> Result<(), ERC721Error> {
-        storage.borrow_mut().transfer_from(from, to, id)?;
-        Self::_call_receiver(storage, id, from, to, data)
+    ) -> Result<(), Vec<u8>> {
+        let holder: &mut H = storage.borrow_mut();
+        Self::_transfer_from(holder, from, to, id)?;
+        Ok(Self::_call_receiver(storage, id, from, to, data)?)
     }
 
-    /// Mints token `id` to `to`.
+    /// Mints token `id` to `to`, running the hooks of `storage`.
     ///
     /// Requirements:
     ///
@@ -183,17 +418,21 @@ impl<T: ERC721Params> ERC721<T> {
     /// - `to` cannot be the zero address.
     ///
     /// Emits a {Transfer} event.
-    pub fn _mint(&mut self, to: Address, id: U256) -> Result<(), ERC721Error> {
+    pub fn _mint<H: ERC721Hooks<T>>(storage: &mut H, to: Address, id: U256) -> Result<(), Vec<u8>> {
         if to.is_zero() {
-            return Err(ERC721Error::TransferToZero(TransferToZero { id }));
+            return Err(ERC721Error::transfer_to_zero(id).into());
         }
-        let mut owner = self.owners.setter(id);
-        if !owner.is_zero() {
-            return Err(ERC721Error::AlreadyMinted(AlreadyMinted {}));
+        if !storage.borrow()._owner_of(id).is_zero() {
+            return Err(ERC721Error::already_minted().into());
+        }
+        storage.before_token_transfer(Address::default(), to, id, U256::from(1))?;
+        let this: &mut Self = storage.borrow_mut();
+        this.owners.setter(id).set(to);
+        if id >= this.next_consecutive_id.get() && !this.minted_individually.get() {
+            this.minted_individually.set(true);
         }
-        owner.set(to);
 
-        let mut to_balance = self.balance.setter(to);
+        let mut to_balance = this.balance.setter(to);
         let balance = to_balance.get() + U256::from(1);
         to_balance.set(balance);
 
@@ -202,10 +441,12 @@ impl<T: ERC721Params> ERC721<T> {
             to,
             id,
         });
+        storage.after_token_transfer(Address::default(), to, id, U256::from(1))?;
         Ok(())
     }
 
-    /// Mints token `id` to `to`.
+    /// Mints token `id` to `to`, running the hooks of the holder `H` of the collection (ex.
+    /// `ERC721::_safe_mint::<_, ERC721<T>>(self, to, id, data)` for a collection without hooks).
     ///
     /// Requirements:
     ///
@@ -215,17 +456,76 @@ impl<T: ERC721Params> ERC721<T> {
     ///   {IERC721Receiver-onERC721Received}, which is called upon a safe transfer.
     ///
     /// Emits a {Transfer} event.
-    pub fn _safe_mint<S: TopLevelStorage + BorrowMut<Self>>(
+    pub fn _safe_mint<S: TopLevelStorage + BorrowMut<H>, H: ERC721Hooks<T>>(
         storage: &mut S,
         to: Address,
         id: U256,
         data: Vec<u8>,
-    ) -> Result<(), ERC721Error> {
-        storage.borrow_mut()._mint(to, id)?;
+    ) -> Result<(), Vec<u8>> {
+        let holder: &mut H = storage.borrow_mut();
+        Self::_mint(holder, to, id)?;
         Self::_call_receiver(storage, id, Address::default(), to, data)?;
         Ok(())
     }
 
+    /// Mints `batch_size` tokens with consecutive ids to `to`, and returns the first id.
+    /// The hooks of `storage` are run once for the whole batch. The ids of consecutive batches start from 0, so batches can only be minted before
+    /// any token is minted with `_mint` (ex. in an `init` method).
+    ///
+    /// Requirements:
+    ///
+    /// - `to` cannot be the zero address.
+    /// - `batch_size` must not exceed `ERC721Params::MAX_BATCH_SIZE`.
+    /// - No token can have been minted with `_mint`, other than burned tokens of previous batches.
+    ///
+    /// Emits a {ConsecutiveTransfer} event.
+    pub fn _mint_consecutive<H: ERC721Hooks<T>>(
+        storage: &mut H,
+        to: Address,
+        batch_size: U256,
+    ) -> Result<U256, Vec<u8>> {
+        let this: &mut Self = storage.borrow_mut();
+        let first = this.next_consecutive_id.get();
+        if batch_size.is_zero() {
+            return Ok(first);
+        }
+        if to.is_zero() {
+            return Err(ERC721Error::transfer_to_zero(first).into());
+        }
+        if this.minted_individually.get() {
+            return Err(ERC721Error::ERC721ForbiddenBatchMint(ERC721ForbiddenBatchMint {}).into());
+        }
+        if batch_size > U256::from(T::MAX_BATCH_SIZE) {
+            return Err(
+                ERC721Error::ERC721ExceededMaxBatchMint(ERC721ExceededMaxBatchMint {
+                    batchSize: batch_size,
+                    maxBatch: U256::from(T::MAX_BATCH_SIZE),
+                })
+                .into(),
+            );
+        }
+        storage.before_token_transfer(Address::default(), to, first, batch_size)?;
+        let end = first + batch_size;
+
+        let this: &mut Self = storage.borrow_mut();
+        this.batch_ends.push(end);
+        this.batch_owners.push(to);
+        this.next_consecutive_id.set(end);
+
+        let mut to_balance = this.balance.setter(to);
+        let balance = to_balance.get() + batch_size;
+        to_balance.set(balance);
+
+        evm::log(ConsecutiveTransfer {
+            fromTokenId: first,
+            toTokenId: end - U256::from(1),
+            fromAddress: Address::default(),
+            toAddress: to,
+        });
+        storage.after_token_transfer(Address::default(), to, first, batch_size)?;
+        Ok(first)
+    }
+
     /// Destroys token `id`, using `by`.
     ///
     /// Requirements:
@@ -235,49 +535,56 @@ impl<T: ERC721Params> ERC721<T> {
     ///   it must be the owner of the token, or be approved to manage the token.
     ///
     /// Emits a {Transfer} event.
-    pub fn _burn(&mut self, id: U256) -> Result<(), ERC721Error> {
-        let mut owner_setter = self.owners.setter(id);
-        if owner_setter.is_zero() {
-            return Err(ERC721Error::InvalidTokenId(InvalidTokenId { id }));
+    pub fn _burn<H: ERC721Hooks<T>>(storage: &mut H, id: U256) -> Result<(), Vec<u8>> {
+        let this: &mut Self = storage.borrow_mut();
+        let owner = this._owner_of(id);
+        if owner.is_zero() {
+            return Err(ERC721Error::nonexistent_token(id).into());
         }
-        let owner = owner_setter.get();
 
         if msg::sender() != owner
-            && !self.approved_for_all.getter(owner).get(msg::sender())
-            && msg::sender() != self.approved.get(id)
+            && !this.approved_for_all.getter(owner).get(msg::sender())
+            && msg::sender() != this.approved.get(id)
         {
-            return Err(ERC721Error::NotApproved(NotApproved {
-                owner,
-                spender: msg::sender(),
-                id,
-            }));
+            return Err(ERC721Error::insufficient_approval(msg::sender(), id, owner).into());
         }
+        storage.before_token_transfer(owner, Address::default(), id, U256::from(1))?;
 
-        let mut owner_balance = self.balance.setter(owner);
+        let this: &mut Self = storage.borrow_mut();
+        let mut owner_balance = this.balance.setter(owner);
         let balance = owner_balance.get() - U256::from(1);
         owner_balance.set(balance);
 
-        owner_setter.set(Address::default());
-        self.approved.delete(id);
+        this.owners.setter(id).set(Address::default());
+        this.approved.delete(id);
+        if id < this.next_consecutive_id.get() {
+            this.consecutive_burned.insert(id, true);
+        }
 
         evm::log(Transfer {
             from: owner,
             to: Address::default(),
             id,
         });
+        storage.after_token_transfer(owner, Address::default(), id, U256::from(1))?;
         Ok(())
     }
 }
 
-sol_interface! {
-    /// Allows calls to the `onERC721Received` method of other contracts implementing `IERC721TokenReceiver`.
-    interface IERC721TokenReceiver {
-        function onERC721Received(address operator, address from, uint256 id, bytes data) external returns(bytes4);
+impl<T: ERC721Params> InterfaceIds for ERC721<T> {
+    fn implements_interface(id: u32) -> bool {
+        matches!(id, IERC721 | IERC721_METADATA)
     }
 }
 
-/// Selector for `onERC721Received`, which is returned by contracts implementing `IERC721TokenReceiver`.
-const ERC721_RECEIVED_SELECTOR: u32 = 0x150b7a02;
+/// Selector for `onERC721Received`, which is returned by contracts implementing `IERC721Receiver`.
+const ERC721_RECEIVED_SELECTOR: u32 =
+    crate::interface_id!("onERC721Received(address,address,uint256,bytes)");
+
+// The callback of contracts implementing `IERC721Receiver`
+sol! {
+    function onERC721Received(address operator, address from, uint256 id, bytes data) returns (bytes4);
+}
 
 #[external]
 impl<T: ERC721Params> ERC721<T> {
@@ -301,19 +608,7 @@ impl<T: ERC721Params> ERC721<T> {
     /// Returns true if this contract implements the interface defined by `interfaceId`.
     /// See: https://eips.ethereum.org/EIPS/eip-165
     pub fn supports_interface(interface: [u8; 4]) -> bool {
-        if interface == [0xff; 4] {
-            // special cased in the ERC165 standard
-            return false;
-        }
-
-        const IERC165: u32 = 0x01ffc9a7;
-        const IERC721: u32 = 0x80ac58cd;
-        const IERC721METADATA: u32 = 0x5b5e139f;
-
-        matches!(
-            u32::from_be_bytes(interface),
-            IERC165 | IERC721 | IERC721METADATA
-        )
+        introspection::supports_interface::<Self>(interface)
     }
 
     /// Returns the number of tokens owned by `owner`.
@@ -329,9 +624,9 @@ impl<T: ERC721Params> ERC721<T> {
     /// Requirements:
     /// - Token `id` must exist.
     pub fn owner_of(&self, id: U256) -> Result<Address, ERC721Error> {
-        let owner = self.owners.get(id);
+        let owner = self._owner_of(id);
         if owner.is_zero() {
-            return Err(ERC721Error::InvalidTokenId(InvalidTokenId { id }));
+            return Err(ERC721Error::nonexistent_token(id));
         }
         Ok(owner)
     }
@@ -353,7 +648,7 @@ impl<T: ERC721Params> ERC721<T> {
         from: Address,
         to: Address,
         id: U256,
-    ) -> Result<(), ERC721Error> {
+    ) -> Result<(), Vec<u8>> {
         Self::safe_transfer_from_with_data(storage, from, to, id, Bytes(vec![]))
     }
 
@@ -368,15 +663,8 @@ impl<T: ERC721Params> ERC721<T> {
         to: Address,
         id: U256,
         data: Bytes,
-    ) -> Result<(), ERC721Error> {
-        if to.is_zero() {
-            return Err(ERC721Error::TransferToZero(TransferToZero { id }));
-        }
-        storage
-            .borrow_mut()
-            ._require_authorized_to_spend(from, id)?;
-
-        Self::_safe_transfer(storage, id, from, to, data.0)
+    ) -> Result<(), Vec<u8>> {
+        Self::_safe_transfer::<S, Self>(storage, id, from, to, data.0)
     }
 
     /// Transfers token `id` from `from` to `to`.
@@ -389,18 +677,8 @@ impl<T: ERC721Params> ERC721<T> {
     /// - The caller must be the owner of the token, or be approved to manage the token.
     ///
     /// Emits a {Transfer} event.
-    pub fn transfer_from(
-        &mut self,
-        from: Address,
-        to: Address,
-        id: U256,
-    ) -> Result<(), ERC721Error> {
-        if to.is_zero() {
-            return Err(ERC721Error::TransferToZero(TransferToZero { id }));
-        }
-        self._require_authorized_to_spend(from, id)?;
-        self._transfer(id, from, to)?;
-        Ok(())
+    pub fn transfer_from(&mut self, from: Address, to: Address, id: U256) -> Result<(), Vec<u8>> {
+        Self::_transfer_from(self, from, to, id)
     }
 
     /// Sets `account` as the approved account to manage token `id`.
@@ -416,11 +694,7 @@ impl<T: ERC721Params> ERC721<T> {
 
         // require authorization
         if msg::sender() != owner && !self.approved_for_all.getter(owner).get(msg::sender()) {
-            return Err(ERC721Error::NotApproved(NotApproved {
-                owner,
-                spender: msg::sender(),
-                id,
-            }));
+            return Err(ERC721Error::insufficient_approval(msg::sender(), id, owner));
         }
         self.approved.insert(id, approved);
 
@@ -0,0 +1,43 @@
+//! ERC721 receiver base that accepts all safe transfers of ERC721 tokens.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/token/ERC721/utils/ERC721Holder.sol
+//!
+//! Contracts custodying NFTs (ex. vaults, staking or escrow contracts) must implement
+//! `onERC721Received`, or safe transfers and safe mints to them revert. Inheriting `ERC721Holder`
+//! accepts every token. Contracts that only accept some collections should define their own
+//! `on_erc721_received` instead, returning `ERC721_RECEIVED` for accepted tokens.
+//!
+//! Tokens sent with `transferFrom` do not call the receiver, so a contract must also expose a way
+//! to move its tokens out, or they may be stuck.
+
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, FixedBytes, U256},
+    prelude::*,
+};
+
+/// The value returned by `onERC721Received` to accept a transfer, which is its selector.
+pub const ERC721_RECEIVED: u32 =
+    crate::interface_id!("onERC721Received(address,address,uint256,bytes)");
+
+sol_storage! {
+    /// ERC721Holder implements the `onERC721Received` hook
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC721Holder"))]
+    pub struct ERC721Holder {}
+}
+
+// External functions
+#[external]
+impl ERC721Holder {
+    /// Accepts the transfer of token `token_id` from `from` by `operator`, by returning
+    /// `ERC721_RECEIVED`.
+    #[selector(name = "onERC721Received")]
+    pub fn on_erc721_received(
+        _operator: Address,
+        _from: Address,
+        _token_id: U256,
+        _data: Bytes,
+    ) -> FixedBytes<4> {
+        FixedBytes(ERC721_RECEIVED.to_be_bytes())
+    }
+}
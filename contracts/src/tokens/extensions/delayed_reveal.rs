@@ -0,0 +1,241 @@
+//! ERC721 extension for delayed reveals with a provenance hash.
+//! The logic was based off of: https://etherscan.io/address/0xbc4ca0eda7647a8ab7c2061c2e118a18a936f13d#code
+//!
+//! Before the reveal, every token URI is the pre-reveal URI. The owner commits to the final
+//! metadata by setting a provenance hash (ex. the hash of the concatenated hashes of all images),
+//! then reveals the collection by setting the final base URI, which can no longer be changed.
+//! Every reveal and pre-reveal URI change emits an ERC-4906 {BatchMetadataUpdate} event so that
+//! marketplaces refresh the metadata.
+//!
+//! The reveal can optionally shift token ids by a random offset, so that the owner cannot choose
+//! which metadata each minter gets. Token `id` then uses the metadata at index
+//! `(id + offset) % COLLECTION_SIZE`. Arbitrum chains do not expose `prevrandao`, so the offset is
+//! derived from the provenance hash and block data. This is good enough to prevent the owner from
+//! picking the order ahead of time, but the sequencer could still influence it.
+//!
+//! `DelayedReveal` wraps `ERC721` and `Ownable` to override `tokenURI`. Contracts must inherit
+//! `DelayedReveal<T>`, `ERC721<T>` and `Ownable` (in that order), and implement
+//! `Borrow`/`BorrowMut` of `ERC721<T>` and `Ownable` by returning the `erc721` and `ownable` fields
+//! of the `DelayedReveal` storage. The owner and the pre-reveal URI are set during the
+//! [initialization](crate#initialization) of the contract.
+
+use alloc::{string::String, vec::Vec};
+use stylus_sdk::{
+    alloy_primitives::{B256, U256},
+    alloy_sol_types::{sol, SolType},
+    block,
+    crypto::keccak,
+    evm,
+    prelude::*,
+};
+
+use crate::access::ownable::Ownable;
+use crate::tokens::erc721::{ERC721Error, ERC721Params, ERC721};
+use crate::utils::introspection::{self, InterfaceIds};
+use crate::utils::strings::{Strings, StringsTrait};
+
+/// The ERC165 interface id of ERC4906, which only defines events and is therefore fixed by the
+/// standard instead of computed from selectors.
+pub const IERC4906: u32 = 0x49064906;
+
+/// Immutable definitions of the reveal
+pub trait DelayedRevealParams {
+    /// The number of tokens in the collection, which the random offset wraps around.
+    const COLLECTION_SIZE: u64;
+}
+
+sol_storage! {
+    /// DelayedReveal hides the metadata of the collection until the owner reveals it
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.DelayedReveal"))]
+    pub struct DelayedReveal<T: ERC721Params> {
+        /// The underlying collection
+        ERC721<T> erc721;
+        /// The owner, who sets the provenance hash and reveals the collection
+        Ownable ownable;
+        /// The commitment to the final metadata
+        bytes32 provenance_hash;
+        /// The URI of every token before the reveal
+        string pre_reveal_uri;
+        /// The prefix of all token URIs after the reveal
+        string base_uri;
+        /// Whether the collection has been revealed
+        bool revealed;
+        /// The amount token ids are shifted by to find their metadata
+        uint256 offset;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when the provenance hash is set to `provenanceHash`.
+    event ProvenanceHashSet(bytes32 provenanceHash);
+    /// Emitted when the collection is revealed at `baseURI`, with token ids shifted by `offset`.
+    event Revealed(string baseURI, uint256 offset);
+    /// Emitted when the metadata of tokens `_fromTokenId` to `_toTokenId` (inclusive) is changed.
+    event BatchMetadataUpdate(uint256 _fromTokenId, uint256 _toTokenId);
+
+    /// The collection has already been revealed.
+    error AlreadyRevealed();
+}
+
+#[derive(SolidityError)]
+pub enum DelayedRevealError {
+    AlreadyRevealed(AlreadyRevealed),
+}
+
+// Internal functions
+impl<T: ERC721Params + DelayedRevealParams> DelayedReveal<T> {
+    /// Requires that the collection has not been revealed.
+    fn _require_not_revealed(&self) -> Result<(), DelayedRevealError> {
+        if self.revealed.get() {
+            return Err(DelayedRevealError::AlreadyRevealed(AlreadyRevealed {}));
+        }
+        Ok(())
+    }
+
+    /// Sets the URI of every token before the reveal.
+    ///
+    /// Emits a {BatchMetadataUpdate} event.
+    pub fn _set_pre_reveal_uri(&mut self, uri: &str) {
+        self.pre_reveal_uri.set_str(uri);
+        evm::log(BatchMetadataUpdate {
+            _fromTokenId: U256::ZERO,
+            _toTokenId: U256::MAX,
+        });
+    }
+
+    /// Returns a random offset in `1..COLLECTION_SIZE`, or 0 if the collection has a single token.
+    fn _random_offset(&self) -> U256 {
+        let size = U256::from(T::COLLECTION_SIZE);
+        if size <= U256::from(1) {
+            return U256::ZERO;
+        }
+        let seed = keccak(<sol! { (bytes32, uint256, uint256, uint256) }>::encode(&(
+            self.provenance_hash.get().0,
+            U256::from(block::number()),
+            U256::from(block::timestamp()),
+            block::basefee(),
+        )));
+        let offset = U256::from_be_bytes(seed.0) % size;
+        // Shift by at least 1 so that the offset is never the identity
+        if offset.is_zero() {
+            return U256::from(1);
+        }
+        offset
+    }
+}
+
+impl<T: ERC721Params> InterfaceIds for DelayedReveal<T> {
+    fn implements_interface(id: u32) -> bool {
+        id == IERC4906 || ERC721::<T>::implements_interface(id)
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC721Params + DelayedRevealParams> DelayedReveal<T> {
+    /// Returns the Uniform Resource Identifier (URI) for token `id`.
+    /// Before the reveal, this is the pre-reveal URI. After the reveal, this is the base URI
+    /// followed by the shifted decimal `id`.
+    ///
+    /// Requirements:
+    /// - Token `id` must exist.
+    #[selector(name = "tokenURI")]
+    pub fn token_uri(&self, id: U256) -> Result<String, ERC721Error> {
+        self.erc721.owner_of(id)?;
+        if !self.revealed.get() {
+            return Ok(self.pre_reveal_uri.get_string());
+        }
+        let mut index = id + self.offset.get();
+        if T::COLLECTION_SIZE != 0 {
+            index %= U256::from(T::COLLECTION_SIZE);
+        }
+        let mut uri = self.base_uri.get_string();
+        uri.push_str(&Strings::to_decimal_string(index));
+        Ok(uri)
+    }
+
+    /// Returns the commitment to the final metadata.
+    pub fn provenance_hash(&self) -> B256 {
+        self.provenance_hash.get()
+    }
+
+    /// Returns whether the collection has been revealed.
+    pub fn revealed(&self) -> bool {
+        self.revealed.get()
+    }
+
+    /// Returns the amount token ids are shifted by to find their metadata.
+    pub fn reveal_offset(&self) -> U256 {
+        self.offset.get()
+    }
+
+    /// Returns true if this contract implements the interface defined by `interfaceId`.
+    /// See: https://eips.ethereum.org/EIPS/eip-165
+    pub fn supports_interface(interface: [u8; 4]) -> bool {
+        introspection::supports_interface::<Self>(interface)
+    }
+
+    /// Sets the commitment to the final metadata.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    /// - The collection must not have been revealed.
+    ///
+    /// Emits a {ProvenanceHashSet} event.
+    pub fn set_provenance_hash(&mut self, provenance_hash: B256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self._require_not_revealed()?;
+        self.provenance_hash.set(provenance_hash);
+        evm::log(ProvenanceHashSet {
+            provenanceHash: provenance_hash.0,
+        });
+        Ok(())
+    }
+
+    /// Sets the URI of every token before the reveal.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    /// - The collection must not have been revealed.
+    ///
+    /// Emits a {BatchMetadataUpdate} event.
+    pub fn set_pre_reveal_uri(&mut self, uri: String) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self._require_not_revealed()?;
+        self._set_pre_reveal_uri(&uri);
+        Ok(())
+    }
+
+    /// Reveals the collection by setting the final `base_uri`, shifting token ids by a random
+    /// offset if `randomize` is true.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    /// - The collection must not have been revealed.
+    ///
+    /// Emits a {Revealed} event and a {BatchMetadataUpdate} event.
+    pub fn reveal(&mut self, base_uri: String, randomize: bool) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self._require_not_revealed()?;
+        let offset = if randomize {
+            self._random_offset()
+        } else {
+            U256::ZERO
+        };
+        self.revealed.set(true);
+        self.offset.set(offset);
+        self.base_uri.set_str(&base_uri);
+
+        evm::log(Revealed {
+            baseURI: base_uri,
+            offset,
+        });
+        evm::log(BatchMetadataUpdate {
+            _fromTokenId: U256::ZERO,
+            _toTokenId: U256::MAX,
+        });
+        Ok(())
+    }
+}
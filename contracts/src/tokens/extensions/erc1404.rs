@@ -0,0 +1,333 @@
+//! ERC20 extension implementing ERC-1404 restricted transfers for permissioned tokens.
+//! The logic was based off of: https://github.com/simple-restricted-token/reference-implementation/blob/master/contracts/token/ERC1404/ERC1404.sol
+//!
+//! Every transfer is checked against restriction rules, and reverts with a {TransferRestricted}
+//! error holding the restriction code if any rule fails. Wallets can check a transfer beforehand
+//! with `detectTransferRestriction`, and show the reason with `messageForTransferRestriction`.
+//!
+//! The built-in rules are managed by the owner, and are evaluated in order:
+//! - Whitelist: when enabled, both the sender and the recipient must be whitelisted.
+//! - Lockup: the sender cannot transfer until its lockup timestamp has passed.
+//! - Jurisdictions: every account has jurisdiction flags, and when the allowed jurisdictions are
+//!   set, the flags of the recipient must include one of them.
+//!
+//! Tokens can add their own rules and codes (ex. holding limits) through `ERC1404Params`, which
+//! are evaluated after the built-in rules. Custom codes should start at `CUSTOM_CODE_START`.
+//!
+//! `ERC1404` wraps `ERC20` and `Ownable` to override the transfer methods. Contracts must inherit
+//! `ERC1404<T>`, `ERC20<T>` and `Ownable` (in that order) so the overrides take precedence, and
+//! implement `Borrow`/`BorrowMut` of `ERC20<T>` and `Ownable` by returning the `erc20` and
+//! `ownable` fields of the `ERC1404` storage. Tokens must be minted through `ERC1404::_mint`, which
+//! applies the recipient rules. The owner is set during the
+//! [initialization](crate#initialization) of the contract.
+
+use alloc::{string::String, vec::Vec};
+use stylus_sdk::{
+    alloy_primitives::{Address, U256, U64},
+    alloy_sol_types::sol,
+    block, evm, msg,
+    prelude::*,
+};
+
+use crate::access::ownable::Ownable;
+use crate::tokens::erc20::{ERC20Params, ERC20};
+
+/// The transfer is not restricted.
+pub const SUCCESS_CODE: u8 = 0;
+/// The whitelist is enabled and the sender is not whitelisted.
+pub const SENDER_NOT_WHITELISTED_CODE: u8 = 1;
+/// The whitelist is enabled and the recipient is not whitelisted.
+pub const RECIPIENT_NOT_WHITELISTED_CODE: u8 = 2;
+/// The tokens of the sender are locked up.
+pub const SENDER_LOCKED_CODE: u8 = 3;
+/// The recipient is not in an allowed jurisdiction.
+pub const JURISDICTION_NOT_ALLOWED_CODE: u8 = 4;
+/// The first code available to the custom rules of `ERC1404Params`.
+pub const CUSTOM_CODE_START: u8 = 16;
+
+/// Custom restriction rules of the token, which default to no restrictions.
+pub trait ERC1404Params: ERC20Params {
+    /// Returns the restriction code of a transfer of `value` tokens from `from` to `to`, or
+    /// `SUCCESS_CODE` if it is not restricted. `from` is the zero address for mints.
+    fn detect_restriction(_from: Address, _to: Address, _value: U256) -> u8 {
+        SUCCESS_CODE
+    }
+
+    /// Returns the message of the custom restriction `code`, or `None` if the code is unknown.
+    fn message_for_restriction(_code: u8) -> Option<&'static str> {
+        None
+    }
+}
+
+sol_storage! {
+    /// ERC1404 restricts transfers of the underlying token with owner managed rules
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC1404"))]
+    pub struct ERC1404<T> {
+        /// The underlying token
+        ERC20<T> erc20;
+        /// The owner, who manages the restriction rules
+        Ownable ownable;
+        /// Whether only whitelisted accounts can send and receive tokens
+        bool whitelist_enabled;
+        /// Maps account to whether it is whitelisted
+        mapping(address => bool) whitelisted;
+        /// Maps account to the timestamp until which it cannot send tokens
+        mapping(address => uint64) lockups;
+        /// Maps account to its jurisdiction flags
+        mapping(address => uint256) jurisdictions;
+        /// The jurisdiction flags recipients must have one of, or zero to allow all
+        uint256 allowed_jurisdictions;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when the whitelist rule is enabled or disabled.
+    event WhitelistEnabledSet(bool enabled);
+    /// Emitted when `account` is added to or removed from the whitelist.
+    event WhitelistedSet(address indexed account, bool whitelisted);
+    /// Emitted when the tokens of `account` are locked up until `until`.
+    event LockupSet(address indexed account, uint64 until);
+    /// Emitted when the jurisdiction flags of `account` are set.
+    event JurisdictionsSet(address indexed account, uint256 flags);
+    /// Emitted when the allowed jurisdiction flags are set.
+    event AllowedJurisdictionsSet(uint256 flags);
+
+    /// The transfer is restricted with the restriction `code`.
+    error TransferRestricted(uint8 code);
+}
+
+#[derive(SolidityError)]
+pub enum ERC1404Error {
+    TransferRestricted(TransferRestricted),
+}
+
+// Internal functions
+impl<T: ERC1404Params> ERC1404<T> {
+    /// Reverts with the restriction code if a transfer of `value` tokens from `from` to `to` is
+    /// restricted.
+    pub fn _require_not_restricted(
+        &self,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<(), ERC1404Error> {
+        let code = self.detect_transfer_restriction(from, to, value);
+        if code != SUCCESS_CODE {
+            return Err(ERC1404Error::TransferRestricted(TransferRestricted {
+                code,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Mints `amount` tokens to `to`.
+    ///
+    /// Requirements:
+    /// - The recipient rules must allow `to` to receive tokens.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn _mint(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self._require_not_restricted(Address::ZERO, to, amount)?;
+        ERC20::_mint(&mut self.erc20, to, amount)
+    }
+
+    /// Enables or disables the whitelist rule.
+    ///
+    /// Emits a {WhitelistEnabledSet} event.
+    pub fn _set_whitelist_enabled(&mut self, enabled: bool) {
+        self.whitelist_enabled.set(enabled);
+        evm::log(WhitelistEnabledSet { enabled });
+    }
+
+    /// Adds `account` to or removes it from the whitelist.
+    ///
+    /// Emits a {WhitelistedSet} event.
+    pub fn _set_whitelisted(&mut self, account: Address, whitelisted: bool) {
+        self.whitelisted.insert(account, whitelisted);
+        evm::log(WhitelistedSet {
+            account,
+            whitelisted,
+        });
+    }
+
+    /// Locks up the tokens of `account` until the `until` timestamp.
+    ///
+    /// Emits a {LockupSet} event.
+    pub fn _set_lockup(&mut self, account: Address, until: u64) {
+        self.lockups.insert(account, U64::from(until));
+        evm::log(LockupSet { account, until });
+    }
+
+    /// Sets the jurisdiction `flags` of `account`.
+    ///
+    /// Emits a {JurisdictionsSet} event.
+    pub fn _set_jurisdictions(&mut self, account: Address, flags: U256) {
+        self.jurisdictions.insert(account, flags);
+        evm::log(JurisdictionsSet { account, flags });
+    }
+
+    /// Sets the jurisdiction `flags` recipients must have one of, or allows all if zero.
+    ///
+    /// Emits an {AllowedJurisdictionsSet} event.
+    pub fn _set_allowed_jurisdictions(&mut self, flags: U256) {
+        self.allowed_jurisdictions.set(flags);
+        evm::log(AllowedJurisdictionsSet { flags });
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC1404Params> ERC1404<T> {
+    /// Transfer `amount` tokens from the caller to `to`.
+    ///
+    /// Requirements:
+    /// - The caller must at least have `amount`.
+    /// - The transfer must not be restricted.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn transfer(&mut self, to: Address, amount: U256) -> Result<bool, Vec<u8>> {
+        self._require_not_restricted(msg::sender(), to, amount)?;
+        self.erc20.transfer(to, amount)
+    }
+
+    /// Transfers `amount` tokens from `from` to `to`.
+    ///
+    /// Requirements:
+    /// - `from` must at least have `amount`.
+    /// - The caller must have at least `amount` of allowance to transfer the tokens of `from`.
+    /// - The transfer must not be restricted.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<bool, Vec<u8>> {
+        self._require_not_restricted(from, to, amount)?;
+        self.erc20.transfer_from(from, to, amount)
+    }
+
+    /// Returns the restriction code of a transfer of `value` tokens from `from` to `to`, or
+    /// `SUCCESS_CODE` (0) if it is not restricted.
+    pub fn detect_transfer_restriction(&self, from: Address, to: Address, value: U256) -> u8 {
+        // Mints only apply the recipient rules
+        let is_mint = from.is_zero();
+        if self.whitelist_enabled.get() {
+            if !is_mint && !self.whitelisted.get(from) {
+                return SENDER_NOT_WHITELISTED_CODE;
+            }
+            if !self.whitelisted.get(to) {
+                return RECIPIENT_NOT_WHITELISTED_CODE;
+            }
+        }
+        if !is_mint && U64::from(block::timestamp()) < self.lockups.get(from) {
+            return SENDER_LOCKED_CODE;
+        }
+        let allowed = self.allowed_jurisdictions.get();
+        if allowed != U256::ZERO && self.jurisdictions.get(to) & allowed == U256::ZERO {
+            return JURISDICTION_NOT_ALLOWED_CODE;
+        }
+        T::detect_restriction(from, to, value)
+    }
+
+    /// Returns a human readable message for the restriction `code`.
+    pub fn message_for_transfer_restriction(&self, code: u8) -> String {
+        let message = match code {
+            SUCCESS_CODE => "SUCCESS",
+            SENDER_NOT_WHITELISTED_CODE => "Sender is not whitelisted",
+            RECIPIENT_NOT_WHITELISTED_CODE => "Recipient is not whitelisted",
+            SENDER_LOCKED_CODE => "Sender tokens are locked up",
+            JURISDICTION_NOT_ALLOWED_CODE => "Recipient jurisdiction is not allowed",
+            _ => T::message_for_restriction(code).unwrap_or("Unknown restriction code"),
+        };
+        message.into()
+    }
+
+    /// Returns whether the whitelist rule is enabled.
+    pub fn whitelist_enabled(&self) -> bool {
+        self.whitelist_enabled.get()
+    }
+
+    /// Returns whether `account` is whitelisted.
+    pub fn is_whitelisted(&self, account: Address) -> bool {
+        self.whitelisted.get(account)
+    }
+
+    /// Returns the timestamp until which `account` cannot send tokens.
+    pub fn lockup_of(&self, account: Address) -> u64 {
+        self.lockups.get(account).to()
+    }
+
+    /// Returns the jurisdiction flags of `account`.
+    pub fn jurisdictions_of(&self, account: Address) -> U256 {
+        self.jurisdictions.get(account)
+    }
+
+    /// Returns the jurisdiction flags recipients must have one of, or zero if all are allowed.
+    pub fn allowed_jurisdictions(&self) -> U256 {
+        self.allowed_jurisdictions.get()
+    }
+
+    /// Enables or disables the whitelist rule.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    ///
+    /// Emits a {WhitelistEnabledSet} event.
+    pub fn set_whitelist_enabled(&mut self, enabled: bool) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self._set_whitelist_enabled(enabled);
+        Ok(())
+    }
+
+    /// Adds `account` to or removes it from the whitelist.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    ///
+    /// Emits a {WhitelistedSet} event.
+    pub fn set_whitelisted(&mut self, account: Address, whitelisted: bool) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self._set_whitelisted(account, whitelisted);
+        Ok(())
+    }
+
+    /// Locks up the tokens of `account` until the `until` timestamp.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    ///
+    /// Emits a {LockupSet} event.
+    pub fn set_lockup(&mut self, account: Address, until: u64) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self._set_lockup(account, until);
+        Ok(())
+    }
+
+    /// Sets the jurisdiction `flags` of `account`.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    ///
+    /// Emits a {JurisdictionsSet} event.
+    pub fn set_jurisdictions(&mut self, account: Address, flags: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self._set_jurisdictions(account, flags);
+        Ok(())
+    }
+
+    /// Sets the jurisdiction `flags` recipients must have one of, or allows all if zero.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    ///
+    /// Emits an {AllowedJurisdictionsSet} event.
+    pub fn set_allowed_jurisdictions(&mut self, flags: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self._set_allowed_jurisdictions(flags);
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+//! ERC20 extension that blocks accounts from sending and receiving tokens, for compliance.
+//! The logic was based off of: https://github.com/circlefin/stablecoin-evm/blob/master/contracts/v1/Blacklistable.sol
+//!
+//! The owner manages a blocklist of accounts, and can optionally set a sanctions oracle (ex. the
+//! Chainalysis sanctions oracle) implementing `isSanctioned(address)`. Accounts on the blocklist or
+//! sanctioned by the oracle cannot send, receive or spend tokens, and a failed oracle call blocks
+//! the transfer. Allowances can still be approved, but cannot be spent while either account is
+//! blocked. Burns are not checked, so that the issuer can destroy blocked funds.
+//!
+//! `ERC20Blocklist` wraps `ERC20` and `Ownable`. Contracts must inherit `ERC20Blocklist<T>`,
+//! `ERC20<T>` and `Ownable`, and implement `Borrow`/`BorrowMut` of `ERC20<T>` and `Ownable` by
+//! returning the `erc20` and `ownable` fields of the `ERC20Blocklist` storage. Transfers are
+//! checked by the `ERC20Hooks` of `ERC20Blocklist`, which overrides `transfer` and `transfer_from`
+//! to run them, and tokens must be minted through the `ERC20Blocklist` storage (ex.
+//! `ERC20::_mint(&mut self.token, to, amount)`). The owner is set during the
+//! [initialization](crate#initialization) of the contract.
+
+use alloc::vec::Vec;
+use core::borrow::{Borrow, BorrowMut};
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolCall},
+    call::RawCall,
+    evm, msg,
+    prelude::*,
+};
+
+use crate::access::ownable::Ownable;
+use crate::tokens::erc20::{ERC20Hooks, ERC20Params, ERC20};
+
+sol_storage! {
+    /// ERC20Blocklist prevents blocked and sanctioned accounts from moving tokens
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC20Blocklist"))]
+    pub struct ERC20Blocklist<T> {
+        /// The underlying token
+        ERC20<T> erc20;
+        /// The owner, who manages the blocklist and the sanctions oracle
+        Ownable ownable;
+        /// Maps account to whether it is blocked
+        mapping(address => bool) blocked;
+        /// The optional sanctions oracle, disabled if zero
+        address sanctions_oracle;
+    }
+}
+
+impl<T: ERC20Params> Borrow<ERC20<T>> for ERC20Blocklist<T> {
+    fn borrow(&self) -> &ERC20<T> {
+        &self.erc20
+    }
+}
+
+impl<T: ERC20Params> BorrowMut<ERC20<T>> for ERC20Blocklist<T> {
+    fn borrow_mut(&mut self) -> &mut ERC20<T> {
+        &mut self.erc20
+    }
+}
+
+/// Checks the blocklist on every mint and transfer
+impl<T: ERC20Params> ERC20Hooks<T> for ERC20Blocklist<T> {
+    fn before_token_transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<(), Vec<u8>> {
+        Ok(self._before_token_transfer(from, to, amount)?)
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when `account` is added to the blocklist.
+    event Blocked(address indexed account);
+    /// Emitted when `account` is removed from the blocklist.
+    event Unblocked(address indexed account);
+    /// Emitted when the sanctions oracle is set to `oracle`.
+    event SanctionsOracleSet(address indexed oracle);
+
+    /// The `account` is blocked or sanctioned.
+    error AddressBlocked(address account);
+
+    function isSanctioned(address addr) external view returns (bool);
+}
+
+#[derive(SolidityError)]
+pub enum ERC20BlocklistError {
+    AddressBlocked(AddressBlocked),
+}
+
+// Internal functions
+impl<T: ERC20Params> ERC20Blocklist<T> {
+    /// Adds `account` to the blocklist.
+    ///
+    /// Emits a {Blocked} event.
+    pub fn _block(&mut self, account: Address) {
+        self.blocked.insert(account, true);
+        evm::log(Blocked { account });
+    }
+
+    /// Removes `account` from the blocklist.
+    ///
+    /// Emits an {Unblocked} event.
+    pub fn _unblock(&mut self, account: Address) {
+        self.blocked.insert(account, false);
+        evm::log(Unblocked { account });
+    }
+
+    /// Sets the sanctions oracle to `oracle`, or disables it if `oracle` is the zero address.
+    ///
+    /// Emits a {SanctionsOracleSet} event.
+    pub fn _set_sanctions_oracle(&mut self, oracle: Address) {
+        self.sanctions_oracle.set(oracle);
+        evm::log(SanctionsOracleSet { oracle });
+    }
+
+    /// Returns whether the sanctions oracle sanctions `account`.
+    /// A failed oracle call is treated as a sanction, so that transfers fail closed.
+    fn _is_sanctioned(&self, account: Address) -> bool {
+        let oracle = self.sanctions_oracle.get();
+        if oracle.is_zero() {
+            return false;
+        }
+        let call = isSanctionedCall { addr: account };
+        match RawCall::new_static().call(oracle, &call.encode()) {
+            Ok(data) => isSanctionedCall::decode_returns(&data, true)
+                .map(|returns| returns._0)
+                .unwrap_or(true),
+            Err(_) => true,
+        }
+    }
+
+    /// Reverts if any of the `accounts` is blocked or sanctioned.
+    pub fn _require_not_blocked(&self, accounts: &[Address]) -> Result<(), ERC20BlocklistError> {
+        for &account in accounts {
+            if self.is_blocked(account) {
+                return Err(ERC20BlocklistError::AddressBlocked(AddressBlocked {
+                    account,
+                }));
+            }
+        }
+        Ok(())
+    }
+
+    /// Hook that is called before `amount` tokens are minted, transferred or burned.
+    ///
+    /// Requirements:
+    /// - Neither the caller, `from` nor `to` can be blocked or sanctioned, except for burns,
+    ///   and for the caller of mints.
+    pub fn _before_token_transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        _amount: U256,
+    ) -> Result<(), ERC20BlocklistError> {
+        if to.is_zero() {
+            return Ok(());
+        }
+        let sender = msg::sender();
+        if from.is_zero() {
+            self._require_not_blocked(&[to])
+        } else if sender == from {
+            self._require_not_blocked(&[from, to])
+        } else {
+            self._require_not_blocked(&[sender, from, to])
+        }
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC20Params> ERC20Blocklist<T> {
+    /// Returns whether `account` is on the blocklist or sanctioned by the sanctions oracle.
+    pub fn is_blocked(&self, account: Address) -> bool {
+        self.blocked.get(account) || self._is_sanctioned(account)
+    }
+
+    /// Returns the sanctions oracle, or the zero address if it is disabled.
+    pub fn sanctions_oracle(&self) -> Address {
+        self.sanctions_oracle.get()
+    }
+
+    /// Transfer `amount` tokens from the caller to `to`.
+    ///
+    /// Requirements:
+    /// - Neither the caller nor `to` can be blocked or sanctioned.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn transfer(&mut self, to: Address, amount: U256) -> Result<bool, Vec<u8>> {
+        ERC20::_transfer(self, msg::sender(), to, amount)?;
+        Ok(true)
+    }
+
+    /// Transfers `amount` tokens from `from` to `to` using the allowance of the caller.
+    ///
+    /// Requirements:
+    /// - Neither the caller, `from` nor `to` can be blocked or sanctioned.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<bool, Vec<u8>> {
+        ERC20::_transfer_from(self, from, to, amount)?;
+        Ok(true)
+    }
+
+    /// Adds `account` to the blocklist.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    ///
+    /// Emits a {Blocked} event.
+    pub fn block(&mut self, account: Address) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self._block(account);
+        Ok(())
+    }
+
+    /// Removes `account` from the blocklist.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    ///
+    /// Emits an {Unblocked} event.
+    pub fn unblock(&mut self, account: Address) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self._unblock(account);
+        Ok(())
+    }
+
+    /// Sets the sanctions oracle to `oracle`, or disables it if `oracle` is the zero address.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    ///
+    /// Emits a {SanctionsOracleSet} event.
+    pub fn set_sanctions_oracle(&mut self, oracle: Address) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self._set_sanctions_oracle(oracle);
+        Ok(())
+    }
+}
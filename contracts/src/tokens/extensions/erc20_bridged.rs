@@ -0,0 +1,131 @@
+//! ERC20 extension implementing `IArbToken`, so that the token can be registered as the L2 custom
+//! token of an L1 token with the canonical Arbitrum bridge.
+//! The logic was based off of: https://github.com/OffchainLabs/token-bridge-contracts/blob/main/contracts/tokenbridge/arbitrum/StandardArbERC20.sol
+//!
+//! Deposits of the L1 token are minted on L2 by the L2 gateway of the token through `bridgeMint`,
+//! and withdrawals are burned by the gateway through `bridgeBurn` before being released on L1. Only
+//! the gateway can mint and burn through the bridge, and it does not need an allowance to burn.
+//! The L1 token must also be registered with the L1 gateway and router for deposits to work.
+//!
+//! `ERC20Bridged` wraps `ERC20`. Contracts must inherit both `ERC20Bridged<T>` and `ERC20<T>`, and
+//! implement `Borrow<ERC20<T>>` and `BorrowMut<ERC20<T>>` by returning the `erc20` field of the
+//! `ERC20Bridged` storage. The L2 gateway and the L1 token are set by `_initialize`, see
+//! [initialization](crate#initialization).
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    msg,
+    prelude::*,
+};
+
+use crate::tokens::erc20::{ERC20Params, ERC20};
+
+sol_storage! {
+    /// ERC20Bridged lets the L2 gateway of the Arbitrum bridge mint and burn tokens
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC20Bridged"))]
+    pub struct ERC20Bridged<T> {
+        /// The underlying token
+        ERC20<T> erc20;
+        /// The L2 gateway of the token, which mints deposits and burns withdrawals
+        address l2_gateway;
+        /// The address of the token on L1
+        address l1_address;
+    }
+}
+
+// Define errors in the contract
+sol! {
+    /// The caller `account` is not the L2 gateway.
+    error NotGateway(address account);
+    /// The L2 gateway is invalid (ex. the zero address).
+    error InvalidGateway(address gateway);
+    /// The gateway and L1 token have already been initialized.
+    error BridgeAlreadyInitialized();
+}
+
+#[derive(SolidityError)]
+pub enum ERC20BridgedError {
+    NotGateway(NotGateway),
+    InvalidGateway(InvalidGateway),
+    BridgeAlreadyInitialized(BridgeAlreadyInitialized),
+}
+
+// Internal functions
+impl<T: ERC20Params> ERC20Bridged<T> {
+    /// Sets the L2 gateway allowed to mint and burn tokens, and the address of the token on L1.
+    ///
+    /// Requirements:
+    /// - The gateway must not have been initialized.
+    /// - `l2_gateway` cannot be the zero address.
+    pub fn _initialize(
+        &mut self,
+        l2_gateway: Address,
+        l1_address: Address,
+    ) -> Result<(), ERC20BridgedError> {
+        if !self.l2_gateway.get().is_zero() {
+            return Err(ERC20BridgedError::BridgeAlreadyInitialized(
+                BridgeAlreadyInitialized {},
+            ));
+        }
+        if l2_gateway.is_zero() {
+            return Err(ERC20BridgedError::InvalidGateway(InvalidGateway {
+                gateway: l2_gateway,
+            }));
+        }
+        self.l2_gateway.set(l2_gateway);
+        self.l1_address.set(l1_address);
+        Ok(())
+    }
+
+    /// Throws if the caller is not the L2 gateway.
+    pub fn only_gateway(&self) -> Result<(), ERC20BridgedError> {
+        if msg::sender() != self.l2_gateway.get() {
+            return Err(ERC20BridgedError::NotGateway(NotGateway {
+                account: msg::sender(),
+            }));
+        }
+        Ok(())
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC20Params> ERC20Bridged<T> {
+    /// Mints `amount` tokens deposited from L1 to `account`.
+    ///
+    /// Requirements:
+    /// - The caller must be the L2 gateway.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn bridge_mint(&mut self, account: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self.only_gateway()?;
+        ERC20::_mint(&mut self.erc20, account, amount)
+    }
+
+    /// Burns `amount` tokens of `account` withdrawn to L1.
+    ///
+    /// Requirements:
+    /// - The caller must be the L2 gateway.
+    /// - `account` must have at least `amount` tokens.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn bridge_burn(&mut self, account: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self.only_gateway()?;
+        ERC20::_burn(&mut self.erc20, account, amount)
+    }
+
+    /// Returns the address of the token on L1.
+    #[selector(name = "l1Address")]
+    pub fn l1_address(&self) -> Address {
+        self.l1_address.get()
+    }
+
+    /// Returns the L2 gateway of the token.
+    #[selector(name = "l2Gateway")]
+    pub fn l2_gateway(&self) -> Address {
+        self.l2_gateway.get()
+    }
+}
@@ -0,0 +1,174 @@
+//! ERC20 extension implementing the ERC-3156 flash loan standard by minting and burning tokens.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/token/ERC20/extensions/ERC20FlashMint.sol
+//!
+//! `ERC20FlashMint` wraps `ERC20` and only lends the token itself. The borrowed tokens are minted
+//! to the receiver and burned (along with the fee) once the `onFlashLoan` callback returns, so the
+//! total supply is unchanged after a loan unless a fee is sent to `flash_fee_receiver`.
+//!
+//! Contracts must inherit both `ERC20FlashMint<T>` and `ERC20<T>`, and implement
+//! `Borrow<ERC20<T>>` and `BorrowMut<ERC20<T>>` by returning the `erc20` field of the
+//! `ERC20FlashMint` storage.
+
+use alloc::vec::Vec;
+use core::borrow::BorrowMut;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::sol,
+    contract, msg,
+    prelude::*,
+};
+
+use crate::tokens::erc20::{ERC20Params, ERC20};
+
+/// keccak256("ERC3156FlashBorrower.onFlashLoan"), which must be returned by flash borrowers.
+pub const ON_FLASH_LOAN_RETURN: B256 = crate::const_keccak!("ERC3156FlashBorrower.onFlashLoan");
+
+/// Flash loan parameters, which can be overridden to charge a fee on loans.
+pub trait ERC20FlashMintParams {
+    /// Returns the fee applied when doing flash loans of `amount` tokens.
+    /// By default, no fee is charged.
+    fn flash_fee(_amount: U256) -> U256 {
+        U256::ZERO
+    }
+
+    /// Returns the receiver address of the flash fee.
+    /// By default, the fee is burned.
+    fn flash_fee_receiver() -> Address {
+        Address::ZERO
+    }
+}
+
+/// Implemented by contracts that receive ERC-3156 flash loans.
+///
+/// Stylus does not route calls to trait methods, so implementors must expose `on_flash_loan`
+/// through an `#[external]` method with the same signature, which is exported as `onFlashLoan`.
+/// Stylus rejects reentrant calls, so the borrower cannot call back into the lender from
+/// `on_flash_loan`. It must approve the lender for `amount + fee` before the loan is taken.
+pub trait ERC3156FlashBorrower {
+    /// Receives a flash loan of `amount` of `token` initiated by `initiator`.
+    /// Must return [`ON_FLASH_LOAN_RETURN`] for the loan to succeed.
+    fn on_flash_loan(
+        &mut self,
+        initiator: Address,
+        token: Address,
+        amount: U256,
+        fee: U256,
+        data: Bytes,
+    ) -> Result<B256, Vec<u8>>;
+}
+
+sol_storage! {
+    /// ERC20FlashMint lends the underlying token through ERC-3156 flash loans
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC20FlashMint"))]
+    pub struct ERC20FlashMint<T> {
+        /// The underlying token
+        ERC20<T> erc20;
+    }
+}
+
+sol_interface! {
+    /// Allows calls to the `onFlashLoan` method of contracts implementing `IERC3156FlashBorrower`.
+    interface IERC3156FlashBorrower {
+        function onFlashLoan(address initiator, address token, uint256 amount, uint256 fee, bytes data) external returns (bytes32);
+    }
+}
+
+// Define errors in the contract
+sol! {
+    /// The loan token is not valid.
+    error ERC3156UnsupportedToken(address token);
+    /// The requested loan exceeds the max loan value for `token`.
+    error ERC3156ExceededMaxLoan(uint256 maxLoan);
+    /// The receiver of a flashloan is not a valid `onFlashLoan` implementer.
+    error ERC3156InvalidReceiver(address receiver);
+}
+
+#[derive(SolidityError)]
+pub enum ERC20FlashMintError {
+    ERC3156UnsupportedToken(ERC3156UnsupportedToken),
+    ERC3156ExceededMaxLoan(ERC3156ExceededMaxLoan),
+    ERC3156InvalidReceiver(ERC3156InvalidReceiver),
+}
+
+// External functions
+#[external]
+impl<T: ERC20Params + ERC20FlashMintParams> ERC20FlashMint<T> {
+    /// Returns the maximum amount of `token` available to be lent.
+    /// Only the token itself can be lent, up to the remaining `uint256` supply.
+    pub fn max_flash_loan(&self, token: Address) -> U256 {
+        if token == contract::address() {
+            U256::MAX - self.erc20.total_supply.get()
+        } else {
+            U256::ZERO
+        }
+    }
+
+    /// Returns the fee applied when doing flash loans of `amount` of `token`.
+    ///
+    /// Requirements:
+    /// - `token` must be this contract.
+    pub fn flash_fee(&self, token: Address, amount: U256) -> Result<U256, ERC20FlashMintError> {
+        if token != contract::address() {
+            return Err(ERC20FlashMintError::ERC3156UnsupportedToken(
+                ERC3156UnsupportedToken { token },
+            ));
+        }
+        Ok(T::flash_fee(amount))
+    }
+
+    /// Performs a flash loan of `amount` tokens to `receiver`, calling its `onFlashLoan` callback.
+    /// The tokens are minted to `receiver`, and `amount + fee` is pulled back from `receiver`
+    /// through its allowance once the callback returns.
+    ///
+    /// Requirements:
+    /// - `token` must be this contract.
+    /// - `amount` must not exceed `max_flash_loan`.
+    /// - `receiver` must return `ON_FLASH_LOAN_RETURN` from its callback.
+    /// - `receiver` must have approved this contract for at least `amount + fee` before the loan,
+    ///   since it cannot call back into this contract from its callback.
+    ///
+    /// Emits {Transfer} events.
+    pub fn flash_loan<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        receiver: Address,
+        token: Address,
+        amount: U256,
+        data: Bytes,
+    ) -> Result<bool, Vec<u8>> {
+        let this = storage.borrow_mut();
+        let max_loan = this.max_flash_loan(token);
+        if amount > max_loan {
+            return Err(
+                ERC20FlashMintError::ERC3156ExceededMaxLoan(ERC3156ExceededMaxLoan {
+                    maxLoan: max_loan,
+                })
+                .into(),
+            );
+        }
+        let fee = this.flash_fee(token, amount)?;
+        ERC20::_mint(&mut this.erc20, receiver, amount)?;
+
+        let invalid_receiver =
+            || ERC20FlashMintError::ERC3156InvalidReceiver(ERC3156InvalidReceiver { receiver });
+        let returned = IERC3156FlashBorrower::new(receiver)
+            .on_flash_loan(&mut *storage, msg::sender(), token, amount, fee, data.0)
+            .map_err(|_| invalid_receiver())?;
+        if returned != ON_FLASH_LOAN_RETURN {
+            return Err(invalid_receiver().into());
+        }
+
+        let this = storage.borrow_mut();
+        let fee_receiver = T::flash_fee_receiver();
+        this.erc20
+            ._spend_allowance(receiver, contract::address(), amount + fee)?;
+        if fee.is_zero() || fee_receiver.is_zero() {
+            ERC20::_burn(&mut this.erc20, receiver, amount + fee)?;
+        } else {
+            ERC20::_burn(&mut this.erc20, receiver, amount)?;
+            ERC20::_transfer(&mut this.erc20, receiver, fee_receiver, fee)?;
+        }
+        Ok(true)
+    }
+}
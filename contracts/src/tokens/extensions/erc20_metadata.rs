@@ -0,0 +1,82 @@
+//! ERC20 extension storing the token name, symbol and decimals in storage.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts-upgradeable/blob/master/contracts/token/ERC20/ERC20Upgradeable.sol
+//!
+//! The base `ERC20` reads its metadata from `ERC20Params` constants, so every deployment of the
+//! same code shares the same name and symbol. This is a problem for clones and proxies, which
+//! reuse a single implementation for many tokens. `ERC20Metadata` instead stores the metadata,
+//! which is set once through `_initialize_metadata` (ex. in an `init` method).
+//!
+//! Contracts must inherit both `ERC20Metadata` and `ERC20<T>` (in that order) so that `name`,
+//! `symbol` and `decimals` are read from storage. The `ERC20Params` constants are then unused,
+//! except for `NAME` being the default EIP-712 domain name of permits. To sign permits with the
+//! runtime name, also call `ERC20::eip712._set_name` upon initialization.
+
+use alloc::string::String;
+use stylus_sdk::{alloy_primitives::U8, alloy_sol_types::sol, prelude::*};
+
+sol_storage! {
+    /// ERC20Metadata stores the token metadata set upon initialization
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC20Metadata"))]
+    pub struct ERC20Metadata {
+        string name;
+        string symbol;
+        uint8 decimals;
+        bool initialized;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// The metadata has already been initialized.
+    error MetadataAlreadyInitialized();
+}
+
+#[derive(SolidityError)]
+pub enum ERC20MetadataError {
+    MetadataAlreadyInitialized(MetadataAlreadyInitialized),
+}
+
+// Internal functions
+impl ERC20Metadata {
+    /// Sets the token `name`, `symbol` and `decimals`.
+    ///
+    /// Requirements:
+    /// - The metadata must not have been initialized.
+    pub fn _initialize_metadata(
+        &mut self,
+        name: &str,
+        symbol: &str,
+        decimals: u8,
+    ) -> Result<(), ERC20MetadataError> {
+        if self.initialized.get() {
+            return Err(ERC20MetadataError::MetadataAlreadyInitialized(
+                MetadataAlreadyInitialized {},
+            ));
+        }
+        self.initialized.set(true);
+        self.name.set_str(name);
+        self.symbol.set_str(symbol);
+        self.decimals.set(U8::from(decimals));
+        Ok(())
+    }
+}
+
+// External functions
+#[external]
+impl ERC20Metadata {
+    /// Returns the name of the token.
+    pub fn name(&self) -> String {
+        self.name.get_string()
+    }
+
+    /// Returns the symbol of the token.
+    pub fn symbol(&self) -> String {
+        self.symbol.get_string()
+    }
+
+    /// Returns the decimals places of the token.
+    pub fn decimals(&self) -> u8 {
+        self.decimals.get().to()
+    }
+}
@@ -0,0 +1,220 @@
+//! ERC20 extension with vote delegation and historical vote checkpoints, compatible with
+//! Compound's governance token and EIP-5805.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/token/ERC20/extensions/ERC20Votes.sol
+//!
+//! `ERC20Votes` wraps `ERC20` and moves voting units along with the tokens, while the delegation
+//! logic lives in the shared `Votes` core. Contracts must inherit `ERC20Votes<T>`, `Votes<T>` and
+//! `ERC20<T>`, and implement `Borrow`/`BorrowMut` of `Votes<T>` and `ERC20<T>` by returning the
+//! `votes` and `erc20` fields of the `ERC20Votes` storage.
+//!
+//! Voting units are moved by the `ERC20Hooks` of `ERC20Votes`, which overrides `transfer` and
+//! `transfer_from` to run them. Tokens must be minted and burned through the `ERC20Votes` storage
+//! (ex. `ERC20::_mint(&mut self.votes, to, amount)`) for their voting units to move. Accounts do
+//! not have voting power until they delegate, which can be to themselves.
+
+use alloc::vec::Vec;
+use core::borrow::{Borrow, BorrowMut};
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::sol,
+    msg,
+    prelude::*,
+};
+
+use crate::governance::votes::{
+    ECDSAInvalidSignature, ERC5805FutureLookup, InvalidAccountNonce, InvalidUnorderedNonce, Votes,
+    VotesClockUnavailable, VotesError, VotesExpiredSignature, VotesParams,
+};
+use crate::tokens::erc20::{ERC20Hooks, ERC20Params, ERC20};
+use crate::utils::checkpoints::CheckpointUnorderedInsertion;
+use crate::utils::safe_cast::{
+    SafeCastOverflowedIntDowncast, SafeCastOverflowedIntToUint, SafeCastOverflowedUintDowncast,
+    SafeCastOverflowedUintToInt,
+};
+
+sol_storage! {
+    /// ERC20Votes moves the delegated voting power of token holders along with their tokens
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC20Votes"))]
+    pub struct ERC20Votes<T> {
+        /// The underlying token
+        ERC20<T> erc20;
+        /// The delegations and vote checkpoints
+        Votes<T> votes;
+    }
+}
+
+impl<T: ERC20Params> Borrow<ERC20<T>> for ERC20Votes<T> {
+    fn borrow(&self) -> &ERC20<T> {
+        &self.erc20
+    }
+}
+
+impl<T: ERC20Params> BorrowMut<ERC20<T>> for ERC20Votes<T> {
+    fn borrow_mut(&mut self) -> &mut ERC20<T> {
+        &mut self.erc20
+    }
+}
+
+/// Moves the voting units of every mint, transfer and burn
+impl<T: ERC20Params + VotesParams> ERC20Hooks<T> for ERC20Votes<T> {
+    fn after_token_transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<(), Vec<u8>> {
+        Ok(self._after_token_transfer(from, to, amount)?)
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Total supply cap has been exceeded, introducing a risk of votes overflowing.
+    error ERC20ExceededSafeSupply(uint256 increasedSupply, uint256 cap);
+}
+
+#[derive(SolidityError)]
+pub enum ERC20VotesError {
+    CheckpointUnorderedInsertion(CheckpointUnorderedInsertion),
+    VotesExpiredSignature(VotesExpiredSignature),
+    ECDSAInvalidSignature(ECDSAInvalidSignature),
+    InvalidAccountNonce(InvalidAccountNonce),
+    ERC5805FutureLookup(ERC5805FutureLookup),
+    VotesClockUnavailable(VotesClockUnavailable),
+    InvalidUnorderedNonce(InvalidUnorderedNonce),
+    SafeCastOverflowedUintDowncast(SafeCastOverflowedUintDowncast),
+    SafeCastOverflowedIntToUint(SafeCastOverflowedIntToUint),
+    SafeCastOverflowedIntDowncast(SafeCastOverflowedIntDowncast),
+    SafeCastOverflowedUintToInt(SafeCastOverflowedUintToInt),
+    ERC20ExceededSafeSupply(ERC20ExceededSafeSupply),
+}
+
+impl From<VotesError> for ERC20VotesError {
+    fn from(err: VotesError) -> Self {
+        match err {
+            VotesError::CheckpointUnorderedInsertion(e) => Self::CheckpointUnorderedInsertion(e),
+            VotesError::VotesExpiredSignature(e) => Self::VotesExpiredSignature(e),
+            VotesError::ECDSAInvalidSignature(e) => Self::ECDSAInvalidSignature(e),
+            VotesError::InvalidAccountNonce(e) => Self::InvalidAccountNonce(e),
+            VotesError::ERC5805FutureLookup(e) => Self::ERC5805FutureLookup(e),
+            VotesError::VotesClockUnavailable(e) => Self::VotesClockUnavailable(e),
+            VotesError::InvalidUnorderedNonce(e) => Self::InvalidUnorderedNonce(e),
+            VotesError::SafeCastOverflowedUintDowncast(e) => {
+                Self::SafeCastOverflowedUintDowncast(e)
+            }
+            VotesError::SafeCastOverflowedIntToUint(e) => Self::SafeCastOverflowedIntToUint(e),
+            VotesError::SafeCastOverflowedIntDowncast(e) => Self::SafeCastOverflowedIntDowncast(e),
+            VotesError::SafeCastOverflowedUintToInt(e) => Self::SafeCastOverflowedUintToInt(e),
+        }
+    }
+}
+
+// Internal functions
+impl<T: ERC20Params + VotesParams> ERC20Votes<T> {
+    /// Hook that is called after `amount` tokens are minted, transferred or burned.
+    /// Moves the voting units from the delegate of `from` to the delegate of `to`.
+    ///
+    /// Requirements:
+    /// - The total supply must not exceed `Votes::_max_supply` after a mint.
+    ///
+    /// Emits {DelegateVotesChanged} events.
+    pub fn _after_token_transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<(), ERC20VotesError> {
+        if from.is_zero() {
+            let supply = self.erc20.total_supply.get();
+            let cap = Votes::<T>::_max_supply();
+            if supply > cap {
+                return Err(ERC20VotesError::ERC20ExceededSafeSupply(
+                    ERC20ExceededSafeSupply {
+                        increasedSupply: supply,
+                        cap,
+                    },
+                ));
+            }
+        }
+        Ok(self.votes._transfer_voting_units(from, to, amount)?)
+    }
+
+    /// Delegates all of `account`'s voting units, its token balance, to `delegatee`.
+    ///
+    /// Emits {DelegateChanged} and {DelegateVotesChanged} events.
+    pub fn _delegate(
+        &mut self,
+        account: Address,
+        delegatee: Address,
+    ) -> Result<(), ERC20VotesError> {
+        let units = self.erc20.balances.get(account);
+        Ok(self.votes._delegate(account, delegatee, units)?)
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC20Params + VotesParams> ERC20Votes<T> {
+    /// Returns the current nonce of `owner`, shared between permits and delegations.
+    pub fn nonces(&self, owner: Address) -> U256 {
+        self.erc20.nonces.nonces(owner)
+    }
+
+    /// Transfer `amount` tokens from the caller to `to`, moving their voting units.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn transfer(&mut self, to: Address, amount: U256) -> Result<bool, Vec<u8>> {
+        ERC20::_transfer(self, msg::sender(), to, amount)?;
+        Ok(true)
+    }
+
+    /// Transfers `amount` tokens from `from` to `to` using the allowance of the caller, moving
+    /// their voting units.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<bool, Vec<u8>> {
+        ERC20::_transfer_from(self, from, to, amount)?;
+        Ok(true)
+    }
+
+    /// Delegates votes from the caller to `delegatee`.
+    ///
+    /// Emits {DelegateChanged} and {DelegateVotesChanged} events.
+    pub fn delegate(&mut self, delegatee: Address) -> Result<(), ERC20VotesError> {
+        self._delegate(msg::sender(), delegatee)
+    }
+
+    /// Delegates votes from the signer to `delegatee`.
+    ///
+    /// Requirements:
+    /// - `expiry` must not have passed.
+    /// - `nonce` must be the signer's current nonce.
+    ///
+    /// Emits {DelegateChanged} and {DelegateVotesChanged} events.
+    pub fn delegate_by_sig(
+        &mut self,
+        delegatee: Address,
+        nonce: U256,
+        expiry: U256,
+        v: u8,
+        r: B256,
+        s: B256,
+    ) -> Result<(), ERC20VotesError> {
+        let struct_hash = Votes::<T>::_delegation_struct_hash(delegatee, nonce, expiry);
+        let signed_hash = self.erc20.eip712._hash_typed_data(struct_hash);
+        let signer = Votes::<T>::_recover_delegation_signer(signed_hash, expiry, v, r, s)?;
+
+        self.erc20
+            .nonces
+            ._use_checked_nonce(signer, nonce)
+            .map_err(VotesError::from)?;
+
+        self._delegate(signer, delegatee)
+    }
+}
@@ -0,0 +1,176 @@
+//! ERC2981 NFT royalty standard extension.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/token/common/ERC2981.sol
+//! Doc comments are forked from: https://github.com/Vectorized/solady/blob/main/src/tokens/ERC2981.sol
+//!
+//! Royalty is specified as a fraction of the sale price in basis points (see `FEE_DENOMINATOR`).
+//! Royalty information can be set globally for all token ids and/or individually per token id,
+//! with the per-token royalty taking precedence.
+//!
+//! Because Stylus routes a selector to the first inherited contract that defines it, a contract
+//! inheriting both `ERC721` and `ERC2981` must define its own `supports_interface`, ex. with
+//! `introspection::supports_interface::<(ERC721<T>, ERC2981)>`.
+
+use stylus_sdk::{
+    alloy_primitives::{Address, Uint, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+};
+
+use crate::utils::introspection::{self, InterfaceIds};
+
+/// The denominator with which to interpret the royalty fraction, in basis points.
+pub const FEE_DENOMINATOR: u64 = 10_000;
+
+/// The ERC165 interface id of ERC2981.
+pub const IERC2981: u32 = crate::interface_id!("royaltyInfo(uint256,uint256)");
+
+sol_storage! {
+    /// Royalty receiver and fraction packed into a single slot
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct RoyaltyInfo {
+        address receiver;
+        uint96 royalty_fraction;
+    }
+
+    /// ERC2981 implements the `royaltyInfo` method
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC2981"))]
+    pub struct ERC2981 {
+        /// Royalty used for token ids without a specific royalty
+        RoyaltyInfo default_royalty;
+        /// Maps id to its royalty
+        mapping(uint256 => RoyaltyInfo) token_royalties;
+    }
+}
+
+// Declare Solidity error types
+sol! {
+    /// The default royalty set is invalid (ex. fraction greater than the denominator).
+    error ERC2981InvalidDefaultRoyalty(uint256 numerator, uint256 denominator);
+    /// The default royalty receiver is invalid.
+    error ERC2981InvalidDefaultRoyaltyReceiver(address receiver);
+    /// The royalty set for token `id` is invalid (ex. fraction greater than the denominator).
+    error ERC2981InvalidTokenRoyalty(uint256 id, uint256 numerator, uint256 denominator);
+    /// The royalty receiver for token `id` is invalid.
+    error ERC2981InvalidTokenRoyaltyReceiver(uint256 id, address receiver);
+}
+
+#[derive(SolidityError)]
+pub enum ERC2981Error {
+    ERC2981InvalidDefaultRoyalty(ERC2981InvalidDefaultRoyalty),
+    ERC2981InvalidDefaultRoyaltyReceiver(ERC2981InvalidDefaultRoyaltyReceiver),
+    ERC2981InvalidTokenRoyalty(ERC2981InvalidTokenRoyalty),
+    ERC2981InvalidTokenRoyaltyReceiver(ERC2981InvalidTokenRoyaltyReceiver),
+}
+
+// Internal functions
+impl ERC2981 {
+    /// Sets the default royalty `receiver` and `fee_numerator`.
+    ///
+    /// Requirements:
+    /// - `receiver` must not be the zero address.
+    /// - `fee_numerator` must not be greater than the fee denominator.
+    pub fn _set_default_royalty(
+        &mut self,
+        receiver: Address,
+        fee_numerator: U256,
+    ) -> Result<(), ERC2981Error> {
+        let denominator = U256::from(FEE_DENOMINATOR);
+        if fee_numerator > denominator {
+            return Err(ERC2981Error::ERC2981InvalidDefaultRoyalty(
+                ERC2981InvalidDefaultRoyalty {
+                    numerator: fee_numerator,
+                    denominator,
+                },
+            ));
+        }
+        if receiver.is_zero() {
+            return Err(ERC2981Error::ERC2981InvalidDefaultRoyaltyReceiver(
+                ERC2981InvalidDefaultRoyaltyReceiver { receiver },
+            ));
+        }
+        self.default_royalty.receiver.set(receiver);
+        self.default_royalty
+            .royalty_fraction
+            .set(Uint::from(fee_numerator.to::<u64>()));
+        Ok(())
+    }
+
+    /// Sets the default royalty receiver and fraction to zero.
+    pub fn _delete_default_royalty(&mut self) {
+        self.default_royalty.receiver.set(Address::ZERO);
+        self.default_royalty.royalty_fraction.set(Uint::ZERO);
+    }
+
+    /// Sets the royalty `receiver` and `fee_numerator` for token `id`.
+    ///
+    /// Requirements:
+    /// - `receiver` must not be the zero address.
+    /// - `fee_numerator` must not be greater than the fee denominator.
+    pub fn _set_token_royalty(
+        &mut self,
+        id: U256,
+        receiver: Address,
+        fee_numerator: U256,
+    ) -> Result<(), ERC2981Error> {
+        let denominator = U256::from(FEE_DENOMINATOR);
+        if fee_numerator > denominator {
+            return Err(ERC2981Error::ERC2981InvalidTokenRoyalty(
+                ERC2981InvalidTokenRoyalty {
+                    id,
+                    numerator: fee_numerator,
+                    denominator,
+                },
+            ));
+        }
+        if receiver.is_zero() {
+            return Err(ERC2981Error::ERC2981InvalidTokenRoyaltyReceiver(
+                ERC2981InvalidTokenRoyaltyReceiver { id, receiver },
+            ));
+        }
+        let mut royalty = self.token_royalties.setter(id);
+        royalty.receiver.set(receiver);
+        royalty
+            .royalty_fraction
+            .set(Uint::from(fee_numerator.to::<u64>()));
+        Ok(())
+    }
+
+    /// Resets the royalty for token `id` back to the default royalty.
+    pub fn _reset_token_royalty(&mut self, id: U256) {
+        let mut royalty = self.token_royalties.setter(id);
+        royalty.receiver.set(Address::ZERO);
+        royalty.royalty_fraction.set(Uint::ZERO);
+    }
+}
+
+impl InterfaceIds for ERC2981 {
+    fn implements_interface(id: u32) -> bool {
+        id == IERC2981
+    }
+}
+
+// External functions
+#[external]
+impl ERC2981 {
+    /// Returns the `receiver` and `royalty_amount` for token `id` sold at `sale_price`.
+    pub fn royalty_info(&self, id: U256, sale_price: U256) -> (Address, U256) {
+        let royalty = self.token_royalties.getter(id);
+        let (receiver, fraction) = if royalty.receiver.get().is_zero() {
+            (
+                self.default_royalty.receiver.get(),
+                self.default_royalty.royalty_fraction.get(),
+            )
+        } else {
+            (royalty.receiver.get(), royalty.royalty_fraction.get())
+        };
+        let royalty_amount = sale_price * U256::from(fraction) / U256::from(FEE_DENOMINATOR);
+        (receiver, royalty_amount)
+    }
+
+    /// Returns true if this contract implements the interface defined by `interfaceId`.
+    /// See: https://eips.ethereum.org/EIPS/eip-165
+    pub fn supports_interface(interface: [u8; 4]) -> bool {
+        introspection::supports_interface::<Self>(interface)
+    }
+}
@@ -0,0 +1,194 @@
+//! ERC4907 rentable NFT extension, which adds a time-limited user role to tokens.
+//! The logic was based off of: https://github.com/ethereum/ERCs/blob/master/assets/erc-4907/contracts/ERC4907.sol
+//!
+//! The owner (or an approved account) of a token can assign a user until an expiry timestamp.
+//! The user loses its role once the expiry has passed, or as soon as the token is transferred.
+//!
+//! `ERC4907` wraps `ERC721`. Contracts must inherit `ERC4907<T>` and `ERC721<T>` (in that order),
+//! and implement `Borrow`/`BorrowMut` of `ERC721<T>` by returning the `erc721` field of the
+//! `ERC4907` storage. The user is cleared by the `ERC721Hooks` of `ERC4907`, which overrides
+//! `transfer_from` and `safe_transfer_from` to run them, so tokens must be burned through the
+//! `ERC4907` storage (ex. `ERC721::_burn(&mut self.rentable, id)`).
+
+use alloc::vec::Vec;
+use core::borrow::{Borrow, BorrowMut};
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, U256, U64},
+    alloy_sol_types::sol,
+    block, evm, msg,
+    prelude::*,
+};
+
+use crate::tokens::erc721::{ERC721Error, ERC721Hooks, ERC721Params, ERC721};
+use crate::utils::introspection::{self, InterfaceIds};
+
+/// The ERC165 interface id of ERC4907.
+pub const IERC4907: u32 = crate::interface_id!(
+    "setUser(uint256,address,uint64)",
+    "userOf(uint256)",
+    "userExpires(uint256)",
+);
+
+sol_storage! {
+    /// User and expiry packed into a single slot
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct UserInfo {
+        address user;
+        uint64 expires;
+    }
+
+    /// ERC4907 tracks the user of each token of the underlying collection
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC4907"))]
+    pub struct ERC4907<T: ERC721Params> {
+        /// The underlying collection
+        ERC721<T> erc721;
+        /// Maps id to its user
+        mapping(uint256 => UserInfo) users;
+    }
+}
+
+// Declare events
+sol! {
+    /// Emitted when the `user` of token `tokenId` is changed or `expires` is changed.
+    /// The zero address for `user` indicates that there is no user.
+    event UpdateUser(uint256 indexed tokenId, address indexed user, uint64 expires);
+}
+
+impl<T: ERC721Params> Borrow<ERC721<T>> for ERC4907<T> {
+    fn borrow(&self) -> &ERC721<T> {
+        &self.erc721
+    }
+}
+
+impl<T: ERC721Params> BorrowMut<ERC721<T>> for ERC4907<T> {
+    fn borrow_mut(&mut self) -> &mut ERC721<T> {
+        &mut self.erc721
+    }
+}
+
+/// Clears the user of every transferred and burned token
+impl<T: ERC721Params> ERC721Hooks<T> for ERC4907<T> {
+    fn after_token_transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        first_id: U256,
+        _batch_size: U256,
+    ) -> Result<(), Vec<u8>> {
+        // Only mints are batched, and minted tokens do not have a user yet
+        self._after_token_transfer(from, to, first_id);
+        Ok(())
+    }
+}
+
+// Internal functions
+impl<T: ERC721Params> ERC4907<T> {
+    /// Hook that is called after token `id` is transferred or burned.
+    /// Clears the user of the token, if any.
+    ///
+    /// Emits an {UpdateUser} event if the token had a user.
+    pub fn _after_token_transfer(&mut self, from: Address, to: Address, id: U256) {
+        if from == to || self.users.getter(id).user.get().is_zero() {
+            return;
+        }
+        let mut info = self.users.setter(id);
+        info.user.set(Address::ZERO);
+        info.expires.set(U64::ZERO);
+        evm::log(UpdateUser {
+            tokenId: id,
+            user: Address::ZERO,
+            expires: 0,
+        });
+    }
+}
+
+impl<T: ERC721Params> InterfaceIds for ERC4907<T> {
+    fn implements_interface(id: u32) -> bool {
+        id == IERC4907 || ERC721::<T>::implements_interface(id)
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC721Params> ERC4907<T> {
+    /// Transfers token `id` from `from` to `to`, running the hooks of `ERC4907`.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn transfer_from(&mut self, from: Address, to: Address, id: U256) -> Result<(), Vec<u8>> {
+        ERC721::_transfer_from(self, from, to, id)
+    }
+
+    /// Safely transfers token `id` from `from` to `to`, running the hooks of `ERC4907`.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn safe_transfer_from<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        from: Address,
+        to: Address,
+        id: U256,
+    ) -> Result<(), Vec<u8>> {
+        ERC721::<T>::_safe_transfer::<S, Self>(storage, id, from, to, Vec::new())
+    }
+
+    /// Equivalent to `safe_transfer_from`, but with additional data for the receiver.
+    #[selector(name = "safeTransferFrom")]
+    pub fn safe_transfer_from_with_data<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        from: Address,
+        to: Address,
+        id: U256,
+        data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        ERC721::<T>::_safe_transfer::<S, Self>(storage, id, from, to, data.0)
+    }
+
+    /// Sets `user` as the user of token `id` until the `expires` timestamp.
+    ///
+    /// Requirements:
+    /// - Token `id` must exist.
+    /// - The caller must be the owner of the token, or be approved to manage the token.
+    ///
+    /// Emits an {UpdateUser} event.
+    pub fn set_user(&mut self, id: U256, user: Address, expires: u64) -> Result<(), ERC721Error> {
+        let owner = self.erc721.owner_of(id)?;
+        let sender = msg::sender();
+        if sender != owner
+            && !self.erc721.approved_for_all.getter(owner).get(sender)
+            && sender != self.erc721.approved.get(id)
+        {
+            return Err(ERC721Error::insufficient_approval(sender, id, owner));
+        }
+
+        let mut info = self.users.setter(id);
+        info.user.set(user);
+        info.expires.set(U64::from(expires));
+        evm::log(UpdateUser {
+            tokenId: id,
+            user,
+            expires,
+        });
+        Ok(())
+    }
+
+    /// Returns the user of token `id`, or the zero address if there is none or it has expired.
+    pub fn user_of(&self, id: U256) -> Address {
+        let info = self.users.getter(id);
+        if info.expires.get() < U64::from(block::timestamp()) {
+            return Address::ZERO;
+        }
+        info.user.get()
+    }
+
+    /// Returns the timestamp at which the user of token `id` expires.
+    pub fn user_expires(&self, id: U256) -> U256 {
+        U256::from(self.users.getter(id).expires.get())
+    }
+
+    /// Returns true if this contract implements the interface defined by `interfaceId`.
+    /// See: https://eips.ethereum.org/EIPS/eip-165
+    pub fn supports_interface(interface: [u8; 4]) -> bool {
+        introspection::supports_interface::<Self>(interface)
+    }
+}
@@ -0,0 +1,164 @@
+//! ERC5192 minimal soulbound NFT extension.
+//! The logic was based off of: https://eips.ethereum.org/EIPS/eip-5192
+//!
+//! Every token is locked to the account it was minted to. Tokens can still be minted and burned,
+//! but any other transfer reverts with `TokenLocked`. Transfers are blocked by the `ERC721Hooks`
+//! of `ERC5192`, which overrides `transfer_from` and `safe_transfer_from` to run them, and tokens
+//! must be minted through the `ERC5192` storage (ex. `ERC721::_mint(&mut self.soulbound, to, id)`)
+//! for their {Locked} events to be emitted.
+//!
+//! `ERC5192` wraps `ERC721` to expose `locked` and an ERC165 `supports_interface` covering both
+//! standards. Contracts must inherit `ERC5192<T>` and `ERC721<T>` (in that order) and implement
+//! `Borrow`/`BorrowMut` of `ERC721<T>` by returning the `erc721` field of the `ERC5192` storage.
+
+use alloc::vec::Vec;
+use core::borrow::{Borrow, BorrowMut};
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    evm,
+    prelude::*,
+};
+
+use crate::tokens::erc721::{ERC721Error, ERC721Hooks, ERC721Params, TokenLocked, ERC721};
+use crate::utils::introspection::{self, InterfaceIds};
+
+/// The ERC165 interface id of ERC5192.
+pub const IERC5192: u32 = crate::interface_id!("locked(uint256)");
+
+sol_storage! {
+    /// ERC5192 locks every token of the underlying collection to its owner
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC5192"))]
+    pub struct ERC5192<T: ERC721Params> {
+        /// The underlying collection
+        ERC721<T> erc721;
+    }
+}
+
+// Declare events
+sol! {
+    /// Emitted when the locking status of token `tokenId` is changed to locked.
+    event Locked(uint256 tokenId);
+    /// Emitted when the locking status of token `tokenId` is changed to unlocked.
+    event Unlocked(uint256 tokenId);
+}
+
+/// Transfer hook that blocks every transfer other than mints and burns.
+/// Meant to be called from `ERC721Hooks::before_token_transfer`.
+pub fn before_token_transfer(
+    from: Address,
+    to: Address,
+    first_id: U256,
+    _batch_size: U256,
+) -> Result<(), ERC721Error> {
+    if !from.is_zero() && !to.is_zero() {
+        return Err(ERC721Error::TokenLocked(TokenLocked { id: first_id }));
+    }
+    Ok(())
+}
+
+/// Transfer hook that emits a {Locked} event for every minted token.
+/// Meant to be called from `ERC721Hooks::after_token_transfer`.
+pub fn after_token_transfer(from: Address, _to: Address, first_id: U256, batch_size: U256) {
+    if from.is_zero() {
+        let mut id = first_id;
+        while id < first_id + batch_size {
+            evm::log(Locked { tokenId: id });
+            id += U256::from(1);
+        }
+    }
+}
+
+impl<T: ERC721Params> Borrow<ERC721<T>> for ERC5192<T> {
+    fn borrow(&self) -> &ERC721<T> {
+        &self.erc721
+    }
+}
+
+impl<T: ERC721Params> BorrowMut<ERC721<T>> for ERC5192<T> {
+    fn borrow_mut(&mut self) -> &mut ERC721<T> {
+        &mut self.erc721
+    }
+}
+
+/// Only allows mints and burns, and emits a {Locked} event for every mint
+impl<T: ERC721Params> ERC721Hooks<T> for ERC5192<T> {
+    fn before_token_transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        first_id: U256,
+        batch_size: U256,
+    ) -> Result<(), Vec<u8>> {
+        Ok(before_token_transfer(from, to, first_id, batch_size)?)
+    }
+
+    fn after_token_transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        first_id: U256,
+        batch_size: U256,
+    ) -> Result<(), Vec<u8>> {
+        after_token_transfer(from, to, first_id, batch_size);
+        Ok(())
+    }
+}
+
+impl<T: ERC721Params> InterfaceIds for ERC5192<T> {
+    fn implements_interface(id: u32) -> bool {
+        id == IERC5192 || ERC721::<T>::implements_interface(id)
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC721Params> ERC5192<T> {
+    /// Transfers token `id` from `from` to `to`, running the hooks of `ERC5192`.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn transfer_from(&mut self, from: Address, to: Address, id: U256) -> Result<(), Vec<u8>> {
+        ERC721::_transfer_from(self, from, to, id)
+    }
+
+    /// Safely transfers token `id` from `from` to `to`, running the hooks of `ERC5192`.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn safe_transfer_from<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        from: Address,
+        to: Address,
+        id: U256,
+    ) -> Result<(), Vec<u8>> {
+        ERC721::<T>::_safe_transfer::<S, Self>(storage, id, from, to, Vec::new())
+    }
+
+    /// Equivalent to `safe_transfer_from`, but with additional data for the receiver.
+    #[selector(name = "safeTransferFrom")]
+    pub fn safe_transfer_from_with_data<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        from: Address,
+        to: Address,
+        id: U256,
+        data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        ERC721::<T>::_safe_transfer::<S, Self>(storage, id, from, to, data.0)
+    }
+
+    /// Returns whether token `id` is locked, which is always the case.
+    ///
+    /// Requirements:
+    /// - Token `id` must exist.
+    pub fn locked(&self, id: U256) -> Result<bool, ERC721Error> {
+        self.erc721.owner_of(id)?;
+        Ok(true)
+    }
+
+    /// Returns true if this contract implements the interface defined by `interfaceId`.
+    /// See: https://eips.ethereum.org/EIPS/eip-165
+    pub fn supports_interface(interface: [u8; 4]) -> bool {
+        introspection::supports_interface::<Self>(interface)
+    }
+}
@@ -0,0 +1,95 @@
+//! ERC721 extension storing the collection name, symbol and base URI in storage.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts-upgradeable/blob/master/contracts/token/ERC721/ERC721Upgradeable.sol
+//!
+//! The base `ERC721` reads its metadata from `ERC721Params`, so every deployment of the same code
+//! shares the same name, symbol and token URIs. This is a problem for clones and proxies, which
+//! reuse a single implementation for many collections. `ERC721Metadata` instead stores the
+//! metadata, which is set once through `_initialize_metadata` (ex. in an `init` method).
+//!
+//! Contracts must inherit both `ERC721Metadata` and `ERC721<T>` (in that order) so that `name`
+//! and `symbol` are read from storage. Since `tokenURI` must revert for nonexistent tokens,
+//! contracts should define their own `token_uri` that checks `ERC721::owner_of` before returning
+//! `ERC721Metadata::_token_uri`.
+
+use alloc::string::String;
+use stylus_sdk::{alloy_primitives::U256, alloy_sol_types::sol, prelude::*};
+
+use crate::utils::strings::{Strings, StringsTrait};
+
+sol_storage! {
+    /// ERC721Metadata stores the collection metadata set upon initialization
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC721Metadata"))]
+    pub struct ERC721Metadata {
+        string name;
+        string symbol;
+        /// The prefix of all token URIs
+        string base_uri;
+        bool initialized;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// The metadata has already been initialized.
+    error MetadataAlreadyInitialized();
+}
+
+#[derive(SolidityError)]
+pub enum ERC721MetadataError {
+    MetadataAlreadyInitialized(MetadataAlreadyInitialized),
+}
+
+// Internal functions
+impl ERC721Metadata {
+    /// Sets the collection `name`, `symbol` and `base_uri`.
+    ///
+    /// Requirements:
+    /// - The metadata must not have been initialized.
+    pub fn _initialize_metadata(
+        &mut self,
+        name: &str,
+        symbol: &str,
+        base_uri: &str,
+    ) -> Result<(), ERC721MetadataError> {
+        if self.initialized.get() {
+            return Err(ERC721MetadataError::MetadataAlreadyInitialized(
+                MetadataAlreadyInitialized {},
+            ));
+        }
+        self.initialized.set(true);
+        self.name.set_str(name);
+        self.symbol.set_str(symbol);
+        self.base_uri.set_str(base_uri);
+        Ok(())
+    }
+
+    /// Sets the prefix of all token URIs to `base_uri`.
+    pub fn _set_base_uri(&mut self, base_uri: &str) {
+        self.base_uri.set_str(base_uri);
+    }
+
+    /// Returns the URI of token `id`, which is the base URI followed by the decimal `id`.
+    /// Returns an empty string if the base URI is not set.
+    pub fn _token_uri(&self, id: U256) -> String {
+        let mut uri = self.base_uri.get_string();
+        if !uri.is_empty() {
+            uri.push_str(&Strings::to_decimal_string(id));
+        }
+        uri
+    }
+}
+
+// External functions
+#[external]
+impl ERC721Metadata {
+    /// Returns the token collection name.
+    pub fn name(&self) -> String {
+        self.name.get_string()
+    }
+
+    /// Returns the token collection symbol.
+    pub fn symbol(&self) -> String {
+        self.symbol.get_string()
+    }
+}
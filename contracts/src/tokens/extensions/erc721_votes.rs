@@ -0,0 +1,230 @@
+//! ERC721 extension with vote delegation and historical vote checkpoints, where each token
+//! counts as one voting unit.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/token/ERC721/extensions/ERC721Votes.sol
+//!
+//! `ERC721Votes` wraps `ERC721` and moves voting units along with the tokens, while the delegation
+//! logic lives in the shared `Votes` core. Contracts must inherit `ERC721Votes<T>`, `Votes<T>` and
+//! `ERC721<T>`, and implement `Borrow`/`BorrowMut` of `Votes<T>` and `ERC721<T>` by returning the
+//! `votes` and `erc721` fields of the `ERC721Votes` storage.
+//!
+//! Voting units are moved by the `ERC721Hooks` of `ERC721Votes`, which overrides `transfer_from`
+//! and `safe_transfer_from` to run them. Tokens must be minted and burned through the
+//! `ERC721Votes` storage (ex. `ERC721::_mint(&mut self.votes, to, id)`) for their voting units to
+//! move.
+
+use alloc::vec::Vec;
+use core::borrow::{Borrow, BorrowMut};
+use core::marker::PhantomData;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, B256, U256},
+    msg,
+    prelude::*,
+};
+
+use crate::governance::votes::{
+    ECDSAInvalidSignature, ERC5805FutureLookup, InvalidAccountNonce, InvalidUnorderedNonce, Votes,
+    VotesClockUnavailable, VotesError, VotesExpiredSignature, VotesParams,
+};
+use crate::tokens::erc721::{ERC721Hooks, ERC721Params, ERC721};
+use crate::utils::checkpoints::CheckpointUnorderedInsertion;
+use crate::utils::eip712::{EIP712Params, EIP712};
+use crate::utils::nonces::Nonces;
+use crate::utils::safe_cast::{
+    SafeCastOverflowedIntDowncast, SafeCastOverflowedIntToUint, SafeCastOverflowedUintDowncast,
+    SafeCastOverflowedUintToInt,
+};
+
+/// The EIP-712 domain of the collection, which uses the collection name and version "1"
+pub struct ERC721VotesDomain<T>(PhantomData<T>);
+
+impl<T: ERC721Params> EIP712Params for ERC721VotesDomain<T> {
+    const NAME: &'static str = T::NAME;
+    const VERSION: &'static str = "1";
+}
+
+sol_storage! {
+    /// ERC721Votes moves the delegated voting power of token holders along with their tokens
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC721Votes"))]
+    pub struct ERC721Votes<T: ERC721Params> {
+        /// The underlying collection
+        ERC721<T> erc721;
+        /// The delegations and vote checkpoints
+        Votes<T> votes;
+        /// The EIP-712 domain used for delegations by signature
+        EIP712<ERC721VotesDomain<T>> eip712;
+        /// The delegation nonces
+        Nonces nonces;
+    }
+}
+
+#[derive(SolidityError)]
+pub enum ERC721VotesError {
+    CheckpointUnorderedInsertion(CheckpointUnorderedInsertion),
+    VotesExpiredSignature(VotesExpiredSignature),
+    ECDSAInvalidSignature(ECDSAInvalidSignature),
+    InvalidAccountNonce(InvalidAccountNonce),
+    ERC5805FutureLookup(ERC5805FutureLookup),
+    VotesClockUnavailable(VotesClockUnavailable),
+    InvalidUnorderedNonce(InvalidUnorderedNonce),
+    SafeCastOverflowedUintDowncast(SafeCastOverflowedUintDowncast),
+    SafeCastOverflowedIntToUint(SafeCastOverflowedIntToUint),
+    SafeCastOverflowedIntDowncast(SafeCastOverflowedIntDowncast),
+    SafeCastOverflowedUintToInt(SafeCastOverflowedUintToInt),
+}
+
+impl From<VotesError> for ERC721VotesError {
+    fn from(err: VotesError) -> Self {
+        match err {
+            VotesError::CheckpointUnorderedInsertion(e) => Self::CheckpointUnorderedInsertion(e),
+            VotesError::VotesExpiredSignature(e) => Self::VotesExpiredSignature(e),
+            VotesError::ECDSAInvalidSignature(e) => Self::ECDSAInvalidSignature(e),
+            VotesError::InvalidAccountNonce(e) => Self::InvalidAccountNonce(e),
+            VotesError::ERC5805FutureLookup(e) => Self::ERC5805FutureLookup(e),
+            VotesError::VotesClockUnavailable(e) => Self::VotesClockUnavailable(e),
+            VotesError::InvalidUnorderedNonce(e) => Self::InvalidUnorderedNonce(e),
+            VotesError::SafeCastOverflowedUintDowncast(e) => {
+                Self::SafeCastOverflowedUintDowncast(e)
+            }
+            VotesError::SafeCastOverflowedIntToUint(e) => Self::SafeCastOverflowedIntToUint(e),
+            VotesError::SafeCastOverflowedIntDowncast(e) => Self::SafeCastOverflowedIntDowncast(e),
+            VotesError::SafeCastOverflowedUintToInt(e) => Self::SafeCastOverflowedUintToInt(e),
+        }
+    }
+}
+
+impl<T: ERC721Params> Borrow<ERC721<T>> for ERC721Votes<T> {
+    fn borrow(&self) -> &ERC721<T> {
+        &self.erc721
+    }
+}
+
+impl<T: ERC721Params> BorrowMut<ERC721<T>> for ERC721Votes<T> {
+    fn borrow_mut(&mut self) -> &mut ERC721<T> {
+        &mut self.erc721
+    }
+}
+
+/// Moves the voting unit of every minted, transferred and burned token
+impl<T: ERC721Params + VotesParams> ERC721Hooks<T> for ERC721Votes<T> {
+    fn after_token_transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        first_id: U256,
+        batch_size: U256,
+    ) -> Result<(), Vec<u8>> {
+        Ok(self._after_token_transfer(from, to, first_id, batch_size)?)
+    }
+}
+
+// Internal functions
+impl<T: ERC721Params + VotesParams> ERC721Votes<T> {
+    /// Hook that is called after `batch_size` tokens starting from `first_id` are minted,
+    /// transferred or burned. Moves their voting units from the delegate of `from` to the delegate
+    /// of `to`.
+    ///
+    /// Emits {DelegateVotesChanged} events.
+    pub fn _after_token_transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        _first_id: U256,
+        batch_size: U256,
+    ) -> Result<(), ERC721VotesError> {
+        Ok(self.votes._transfer_voting_units(from, to, batch_size)?)
+    }
+
+    /// Delegates all of `account`'s voting units, its token balance, to `delegatee`.
+    ///
+    /// Emits {DelegateChanged} and {DelegateVotesChanged} events.
+    pub fn _delegate(
+        &mut self,
+        account: Address,
+        delegatee: Address,
+    ) -> Result<(), ERC721VotesError> {
+        let units = self.erc721.balance_of(account);
+        Ok(self.votes._delegate(account, delegatee, units)?)
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC721Params + VotesParams> ERC721Votes<T> {
+    /// Transfers token `id` from `from` to `to`, running the hooks of `ERC721Votes`.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn transfer_from(&mut self, from: Address, to: Address, id: U256) -> Result<(), Vec<u8>> {
+        ERC721::_transfer_from(self, from, to, id)
+    }
+
+    /// Safely transfers token `id` from `from` to `to`, running the hooks of `ERC721Votes`.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn safe_transfer_from<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        from: Address,
+        to: Address,
+        id: U256,
+    ) -> Result<(), Vec<u8>> {
+        ERC721::<T>::_safe_transfer::<S, Self>(storage, id, from, to, Vec::new())
+    }
+
+    /// Equivalent to `safe_transfer_from`, but with additional data for the receiver.
+    #[selector(name = "safeTransferFrom")]
+    pub fn safe_transfer_from_with_data<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        from: Address,
+        to: Address,
+        id: U256,
+        data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        ERC721::<T>::_safe_transfer::<S, Self>(storage, id, from, to, data.0)
+    }
+
+    /// Returns the current delegation nonce of `owner`.
+    pub fn nonces(&self, owner: Address) -> U256 {
+        self.nonces.nonces(owner)
+    }
+
+    /// Returns the EIP-712 domain separator for delegations by signature.
+    #[selector(name = "DOMAIN_SEPARATOR")]
+    pub fn domain_separator(&self) -> B256 {
+        self.eip712._domain_separator()
+    }
+
+    /// Delegates votes from the caller to `delegatee`.
+    ///
+    /// Emits {DelegateChanged} and {DelegateVotesChanged} events.
+    pub fn delegate(&mut self, delegatee: Address) -> Result<(), ERC721VotesError> {
+        self._delegate(msg::sender(), delegatee)
+    }
+
+    /// Delegates votes from the signer to `delegatee`.
+    ///
+    /// Requirements:
+    /// - `expiry` must not have passed.
+    /// - `nonce` must be the signer's current nonce.
+    ///
+    /// Emits {DelegateChanged} and {DelegateVotesChanged} events.
+    pub fn delegate_by_sig(
+        &mut self,
+        delegatee: Address,
+        nonce: U256,
+        expiry: U256,
+        v: u8,
+        r: B256,
+        s: B256,
+    ) -> Result<(), ERC721VotesError> {
+        let struct_hash = Votes::<T>::_delegation_struct_hash(delegatee, nonce, expiry);
+        let signed_hash = self.eip712._hash_typed_data(struct_hash);
+        let signer = Votes::<T>::_recover_delegation_signer(signed_hash, expiry, v, r, s)?;
+
+        self.nonces
+            ._use_checked_nonce(signer, nonce)
+            .map_err(VotesError::from)?;
+
+        self._delegate(signer, delegatee)
+    }
+}
@@ -0,0 +1,189 @@
+//! ERC721 extension wrapping the tokens of an underlying ERC721 collection one for one.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/token/ERC721/extensions/ERC721Wrapper.sol
+//!
+//! Holders deposit tokens of the `UNDERLYING` collection with `deposit_for`, or by safely
+//! transferring them to this contract, and receive wrapper tokens with the same ids. Wrapper tokens
+//! are burned with `withdraw_to` to get the underlying tokens back. This is useful to migrate a
+//! collection, or to add functionality to a legacy one (ex. ERC2981 royalties or votes).
+//!
+//! The contract holds the underlying tokens, and only accepts safe transfers from the underlying
+//! collection: `on_erc721_received` overrides the one of `ERC721Holder`. Underlying tokens sent
+//! with `transferFrom` do not mint a wrapper token, and can be recovered with `_recover`.
+//!
+//! `ERC721Wrapper` wraps `ERC721`. Contracts must inherit `ERC721Wrapper<T>` and `ERC721<T>`, and
+//! implement `Borrow`/`BorrowMut` of `ERC721<T>` by returning the `erc721` field of the
+//! `ERC721Wrapper` storage.
+
+use alloc::{vec, vec::Vec};
+use core::borrow::BorrowMut;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, FixedBytes, U256},
+    alloy_sol_types::{sol, SolCall},
+    contract, msg,
+    prelude::*,
+};
+
+use crate::tokens::erc721::{ERC721Error, ERC721Params, ERC721};
+use crate::tokens::erc721_holder::ERC721_RECEIVED;
+use crate::utils::address::{function_call, function_static_call};
+
+/// Immutable definitions of the wrapper
+pub trait ERC721WrapperParams {
+    /// The underlying collection.
+    const UNDERLYING: Address;
+}
+
+sol_storage! {
+    /// ERC721Wrapper mints wrapper tokens for the underlying tokens it holds
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC721Wrapper"))]
+    pub struct ERC721Wrapper<T: ERC721Params> {
+        /// The wrapper collection
+        ERC721<T> erc721;
+    }
+}
+
+// Define errors in the contract, and the functions of the underlying collection
+sol! {
+    /// The received ERC721 `token` is not the underlying collection.
+    error ERC721UnsupportedToken(address token);
+    /// The underlying collection returned an invalid owner for token `tokenId`.
+    error InvalidUnderlyingOwner(uint256 tokenId);
+
+    function ownerOf(uint256 tokenId) external view returns (address);
+    function transferFrom(address from, address to, uint256 tokenId) external;
+    function safeTransferFrom(address from, address to, uint256 tokenId) external;
+}
+
+#[derive(SolidityError)]
+pub enum ERC721WrapperError {
+    ERC721UnsupportedToken(ERC721UnsupportedToken),
+    InvalidUnderlyingOwner(InvalidUnderlyingOwner),
+}
+
+// Internal functions
+impl<T: ERC721Params + ERC721WrapperParams> ERC721Wrapper<T> {
+    /// Mints wrapper token `id` to `to`, calling its `onERC721Received` callback if it is a
+    /// contract.
+    fn _safe_mint<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        to: Address,
+        id: U256,
+    ) -> Result<(), Vec<u8>> {
+        ERC721::_mint(&mut storage.borrow_mut().erc721, to, id)?;
+        Ok(ERC721::<T>::_call_receiver(
+            storage,
+            id,
+            Address::ZERO,
+            to,
+            vec![],
+        )?)
+    }
+
+    /// Mints a wrapper token to `account` for underlying token `id`, which was sent to this
+    /// contract without a safe transfer. The inheriting contract exposes it with its own access
+    /// control (ex. `Ownable::only_owner`).
+    ///
+    /// Requirements:
+    /// - This contract must own underlying token `id`.
+    /// - The wrapper token `id` must not exist.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn _recover(&mut self, account: Address, id: U256) -> Result<(), Vec<u8>> {
+        let owner = function_static_call(T::UNDERLYING, &ownerOfCall { tokenId: id }.encode())?;
+        let owner = ownerOfCall::decode_returns(&owner, true)
+            .map_err(|_| {
+                ERC721WrapperError::InvalidUnderlyingOwner(InvalidUnderlyingOwner { tokenId: id })
+            })?
+            ._0;
+        if owner != contract::address() {
+            return Err(ERC721Error::incorrect_owner(contract::address(), id, owner).into());
+        }
+        ERC721::_mint(&mut self.erc721, account, id)?;
+        Ok(())
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC721Params + ERC721WrapperParams> ERC721Wrapper<T> {
+    /// Returns the underlying collection.
+    pub fn underlying(&self) -> Address {
+        T::UNDERLYING
+    }
+
+    /// Deposits the underlying tokens `ids` of the caller, and mints the wrapper tokens with the
+    /// same ids to `account`.
+    ///
+    /// Requirements:
+    /// - The caller must own the underlying tokens, or be approved to manage them, and this
+    ///   contract must be approved to manage them.
+    /// - If `account` refers to a smart contract, it must implement
+    ///   {IERC721Receiver-onERC721Received}, which is called upon a safe transfer.
+    ///
+    /// Emits a {Transfer} event for each token.
+    pub fn deposit_for<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        account: Address,
+        ids: Vec<U256>,
+    ) -> Result<bool, Vec<u8>> {
+        for id in ids {
+            // The underlying tokens are not safely transferred, which would mint them again
+            let call = transferFromCall {
+                from: msg::sender(),
+                to: contract::address(),
+                tokenId: id,
+            };
+            function_call(T::UNDERLYING, &call.encode())?;
+            Self::_safe_mint(storage, account, id)?;
+        }
+        Ok(true)
+    }
+
+    /// Burns the wrapper tokens `ids`, and safely transfers the underlying tokens to `account`.
+    ///
+    /// Requirements:
+    /// - The caller must own the wrapper tokens, or be approved to manage them.
+    /// - If `account` refers to a smart contract, it must implement
+    ///   {IERC721Receiver-onERC721Received}, which is called upon a safe transfer.
+    ///
+    /// Emits a {Transfer} event for each token.
+    pub fn withdraw_to(&mut self, account: Address, ids: Vec<U256>) -> Result<bool, Vec<u8>> {
+        for id in ids {
+            ERC721::_burn(&mut self.erc721, id)?;
+            let call = safeTransferFromCall {
+                from: contract::address(),
+                to: account,
+                tokenId: id,
+            };
+            function_call(T::UNDERLYING, &call.encode())?;
+        }
+        Ok(true)
+    }
+
+    /// Mints the wrapper token `token_id` to `from` when it safely transfers the underlying token
+    /// to this contract, and accepts the transfer by returning `ERC721_RECEIVED`.
+    ///
+    /// Requirements:
+    /// - The caller must be the underlying collection.
+    ///
+    /// Emits a {Transfer} event.
+    #[selector(name = "onERC721Received")]
+    pub fn on_erc721_received<S: TopLevelStorage + BorrowMut<Self>>(
+        storage: &mut S,
+        _operator: Address,
+        from: Address,
+        token_id: U256,
+        _data: Bytes,
+    ) -> Result<FixedBytes<4>, Vec<u8>> {
+        let token = msg::sender();
+        if token != T::UNDERLYING {
+            return Err(
+                ERC721WrapperError::ERC721UnsupportedToken(ERC721UnsupportedToken { token }).into(),
+            );
+        }
+        Self::_safe_mint(storage, from, token_id)?;
+        Ok(FixedBytes(ERC721_RECEIVED.to_be_bytes()))
+    }
+}
@@ -0,0 +1,49 @@
+//! ERC7572 contract-level metadata extension.
+//! The logic was based off of: https://eips.ethereum.org/EIPS/eip-7572
+//!
+//! `contractURI` returns a URI pointing to a JSON document describing the contract itself (ex.
+//! the collection name, description and image shown by marketplaces), as opposed to the metadata
+//! of individual tokens. It can be composed with any token base.
+//!
+//! The URI is set through `_set_contract_uri`, which has no access control. Contracts should
+//! expose their own setter restricted to the owner (ex. with `Ownable::only_owner`).
+
+use alloc::string::String;
+use stylus_sdk::{alloy_sol_types::sol, evm, prelude::*};
+
+sol_storage! {
+    /// ERC7572 implements the `contractURI` method
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC7572"))]
+    pub struct ERC7572 {
+        /// The URI of the contract-level metadata
+        string contract_uri;
+    }
+}
+
+// Declare events
+sol! {
+    /// Emitted when the contract-level metadata is updated.
+    event ContractURIUpdated();
+}
+
+// Internal functions
+impl ERC7572 {
+    /// Sets the URI of the contract-level metadata to `uri`.
+    ///
+    /// Emits a {ContractURIUpdated} event.
+    pub fn _set_contract_uri(&mut self, uri: &str) {
+        self.contract_uri.set_str(uri);
+        evm::log(ContractURIUpdated {});
+    }
+}
+
+// External functions
+#[external]
+impl ERC7572 {
+    /// Returns the URI of the contract-level metadata.
+    #[selector(name = "contractURI")]
+    pub fn contract_uri(&self) -> String {
+        self.contract_uri.get_string()
+    }
+}
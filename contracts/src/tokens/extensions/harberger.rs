@@ -0,0 +1,435 @@
+//! Harberger tax ERC721 extension, where every token is always on sale at its self-assessed price.
+//! The logic was based off of: https://github.com/721labs/partial-common-ownership
+//!
+//! The owner of a token sets its price, and pays a continuous tax of `TAX_RATE_BPS` of that price
+//! per `TAX_PERIOD` to the `BENEFICIARY`. The tax is prepaid in ETH with `deposit`, and streamed out
+//! of the deposit whenever it is collected, which happens before any action on the token, or with
+//! `collect_tax`. Anyone can `buy` a token at any time by paying its price to the owner, and the
+//! buyer sets the new price. A higher price is safer from buyers, but costs more tax.
+//!
+//! A token whose deposit cannot cover the tax due is delinquent, and is foreclosed on the next
+//! collection: the remaining deposit goes to the beneficiary, and the token is transferred to this
+//! contract with a price of 0, where anyone can buy it. Sellers and the beneficiary are credited
+//! instead of paid in the same call, as an account that rejects ETH could otherwise block sales.
+//! Credits are withdrawn with `withdraw`.
+//!
+//! `Harberger` wraps `ERC721` and overrides the transfer methods, so that tokens only move through
+//! `buy` and foreclosure. Contracts must inherit `Harberger<T>` and `ERC721<T>` (in that order) so
+//! the overrides take precedence, and implement `Borrow`/`BorrowMut` of `ERC721<T>` by returning
+//! the `erc721` field of the `Harberger` storage. Tokens must be minted through `Harberger::_mint`.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, U256, U64},
+    alloy_sol_types::sol,
+    block, contract, evm, msg,
+    prelude::*,
+};
+
+use crate::tokens::erc721::{ERC721Error, ERC721Params, ERC721};
+use crate::utils::fixed_point_math::full_mul_div;
+use crate::utils::safe_transfer::safe_transfer_eth;
+
+/// Immutable definitions of the tax
+pub trait HarbergerParams {
+    /// The account receiving the tax.
+    const BENEFICIARY: Address;
+    /// The tax paid per `TAX_PERIOD`, in basis points of the price.
+    const TAX_RATE_BPS: u64;
+    /// The period over which `TAX_RATE_BPS` of the price is paid, in seconds.
+    const TAX_PERIOD: u64 = 365 * 86_400;
+}
+
+sol_storage! {
+    /// Self-assessed price and prepaid tax of a token
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct Assessment {
+        /// The price at which the token can be bought
+        uint256 price;
+        /// The ETH left to pay the tax
+        uint256 deposit;
+        /// The timestamp at which the tax was last collected
+        uint64 last_collected;
+    }
+
+    /// Harberger tracks the price and tax of each token of the underlying collection
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.Harberger"))]
+    pub struct Harberger<T: ERC721Params> {
+        /// The underlying collection
+        ERC721<T> erc721;
+        /// Maps id to its assessment
+        mapping(uint256 => Assessment) assessments;
+        /// Maps account to the ETH it can withdraw
+        mapping(address => uint256) credits;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when the price of token `tokenId` is set to `price`.
+    event PriceChanged(uint256 indexed tokenId, uint256 price);
+    /// Emitted when `amount` of tax is collected from the deposit of token `tokenId`.
+    event TaxCollected(uint256 indexed tokenId, uint256 amount);
+    /// Emitted when `buyer` buys token `tokenId` from `seller` for `price`.
+    event Bought(uint256 indexed tokenId, address indexed buyer, address indexed seller, uint256 price);
+    /// Emitted when token `tokenId` is foreclosed from `owner`.
+    event Foreclosed(uint256 indexed tokenId, address indexed owner);
+
+    /// Token `tokenId` is foreclosed, and can only be bought.
+    error TokenForeclosed(uint256 tokenId);
+    /// The price of the token is `price`, which is not the one the buyer expected.
+    error PriceChangedBeforeBuy(uint256 price);
+    /// The `value` sent does not cover the `price` of the token.
+    error InsufficientPayment(uint256 value, uint256 price);
+    /// The `amount` to withdraw exceeds the `deposit` of the token.
+    error InsufficientDeposit(uint256 deposit, uint256 amount);
+    /// Token `tokenId` can only be transferred by being bought.
+    error TransferDisabled(uint256 tokenId);
+    /// The caller has nothing to withdraw.
+    error NothingToWithdraw();
+}
+
+#[derive(SolidityError)]
+pub enum HarbergerError {
+    TokenForeclosed(TokenForeclosed),
+    PriceChangedBeforeBuy(PriceChangedBeforeBuy),
+    InsufficientPayment(InsufficientPayment),
+    InsufficientDeposit(InsufficientDeposit),
+    TransferDisabled(TransferDisabled),
+    NothingToWithdraw(NothingToWithdraw),
+}
+
+// Internal functions
+impl<T: ERC721Params + HarbergerParams> Harberger<T> {
+    /// Returns the tax due on `price` over `elapsed` seconds.
+    /// Saturates at `U256::MAX`, which no deposit can cover.
+    pub fn _tax_due(price: U256, elapsed: u64) -> U256 {
+        full_mul_div(
+            price,
+            U256::from(T::TAX_RATE_BPS) * U256::from(elapsed),
+            U256::from(10_000) * U256::from(T::TAX_PERIOD),
+        )
+        .unwrap_or(U256::MAX)
+    }
+
+    /// Mints token `id` to `to` at `price`, with `deposit` of prepaid tax. The inheriting contract
+    /// must have received the deposit, usually as `msg::value()` of its mint function.
+    ///
+    /// Requirements:
+    /// - Token `id` must not exist.
+    /// - `to` cannot be the zero address.
+    ///
+    /// Emits a {Transfer} event and a {PriceChanged} event.
+    pub fn _mint(
+        &mut self,
+        to: Address,
+        id: U256,
+        price: U256,
+        deposit: U256,
+    ) -> Result<(), Vec<u8>> {
+        ERC721::_mint(&mut self.erc721, to, id)?;
+        let mut assessment = self.assessments.setter(id);
+        assessment.price.set(price);
+        assessment.deposit.set(deposit);
+        assessment.last_collected.set(U64::from(block::timestamp()));
+        evm::log(PriceChanged { tokenId: id, price });
+        Ok(())
+    }
+
+    /// Collects the tax due on token `id` since the last collection, and forecloses the token if
+    /// its deposit does not cover it. Returns whether the token is foreclosed.
+    ///
+    /// Requirements:
+    /// - Token `id` must exist.
+    ///
+    /// Emits a {TaxCollected} event, and a {Foreclosed} event if the token is foreclosed.
+    pub fn _collect_tax(&mut self, id: U256) -> Result<bool, Vec<u8>> {
+        let owner = self.erc721.owner_of(id)?;
+        if owner == contract::address() {
+            return Ok(true);
+        }
+
+        let now = block::timestamp();
+        let mut assessment = self.assessments.setter(id);
+        let elapsed = now.saturating_sub(assessment.last_collected.get().to::<u64>());
+        let due = Self::_tax_due(assessment.price.get(), elapsed);
+        let deposit = assessment.deposit.get();
+        assessment.last_collected.set(U64::from(now));
+
+        // A token is delinquent once its deposit is exhausted
+        let foreclosed = due >= deposit && !due.is_zero();
+        let amount = if foreclosed { deposit } else { due };
+        if foreclosed {
+            assessment.price.set(U256::ZERO);
+        }
+        assessment.deposit.set(deposit - amount);
+        if !amount.is_zero() {
+            self._credit(T::BENEFICIARY, amount);
+            evm::log(TaxCollected {
+                tokenId: id,
+                amount,
+            });
+        }
+
+        if foreclosed {
+            ERC721::_transfer(&mut self.erc721, id, owner, contract::address())?;
+            evm::log(Foreclosed { tokenId: id, owner });
+        }
+        Ok(foreclosed)
+    }
+
+    /// Collects the tax due on token `id`, and returns its owner.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner of token `id`.
+    /// - The token must not be foreclosed.
+    fn _require_owner(&mut self, id: U256) -> Result<Address, Vec<u8>> {
+        let owner = self.erc721.owner_of(id)?;
+        let sender = msg::sender();
+        if sender != owner {
+            return Err(ERC721Error::incorrect_owner(sender, id, owner).into());
+        }
+        if self._collect_tax(id)? {
+            return Err(HarbergerError::TokenForeclosed(TokenForeclosed { tokenId: id }).into());
+        }
+        Ok(owner)
+    }
+
+    /// Credits `amount` to `account`, to be withdrawn with `withdraw`.
+    fn _credit(&mut self, account: Address, amount: U256) {
+        let credit = self.credits.get(account) + amount;
+        self.credits.insert(account, credit);
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC721Params + HarbergerParams> Harberger<T> {
+    /// Returns the account receiving the tax.
+    pub fn beneficiary(&self) -> Address {
+        T::BENEFICIARY
+    }
+
+    /// Returns the tax paid per period in basis points of the price, and the period in seconds.
+    pub fn tax_rate(&self) -> (u64, u64) {
+        (T::TAX_RATE_BPS, T::TAX_PERIOD)
+    }
+
+    /// Returns the price of token `id`.
+    pub fn price_of(&self, id: U256) -> U256 {
+        self.assessments.getter(id).price.get()
+    }
+
+    /// Returns the deposit of token `id`, minus the tax due since the last collection.
+    pub fn deposit_of(&self, id: U256) -> U256 {
+        let assessment = self.assessments.getter(id);
+        let elapsed = block::timestamp().saturating_sub(assessment.last_collected.get().to());
+        let due = Self::_tax_due(assessment.price.get(), elapsed);
+        assessment.deposit.get().saturating_sub(due)
+    }
+
+    /// Returns the tax due on token `id` since the last collection, which can exceed its deposit.
+    pub fn tax_due(&self, id: U256) -> U256 {
+        let assessment = self.assessments.getter(id);
+        let elapsed = block::timestamp().saturating_sub(assessment.last_collected.get().to());
+        Self::_tax_due(assessment.price.get(), elapsed)
+    }
+
+    /// Returns the timestamp at which the deposit of token `id` runs out at its current price,
+    /// or `U256::MAX` if the token is not taxed.
+    pub fn foreclosure_time(&self, id: U256) -> U256 {
+        let assessment = self.assessments.getter(id);
+        let rate = assessment.price.get() * U256::from(T::TAX_RATE_BPS);
+        if rate.is_zero() {
+            return U256::MAX;
+        }
+        full_mul_div(
+            assessment.deposit.get(),
+            U256::from(10_000) * U256::from(T::TAX_PERIOD),
+            rate,
+        )
+        .and_then(|funded| funded.checked_add(U256::from(assessment.last_collected.get())))
+        .unwrap_or(U256::MAX)
+    }
+
+    /// Returns whether token `id` is foreclosed, and can be bought for free.
+    pub fn foreclosed(&self, id: U256) -> Result<bool, ERC721Error> {
+        Ok(self.erc721.owner_of(id)? == contract::address())
+    }
+
+    /// Returns the ETH that `account` can withdraw from sales and taxes.
+    pub fn credit_of(&self, account: Address) -> U256 {
+        self.credits.get(account)
+    }
+
+    /// Collects the tax due on token `id`, and forecloses the token if it is delinquent.
+    /// Returns whether the token is foreclosed.
+    ///
+    /// Requirements:
+    /// - Token `id` must exist.
+    ///
+    /// Emits a {TaxCollected} event, and a {Foreclosed} event if the token is foreclosed.
+    pub fn collect_tax(&mut self, id: U256) -> Result<bool, Vec<u8>> {
+        self._collect_tax(id)
+    }
+
+    /// Sets the price of token `id` to `price`, after collecting the tax due at the old price.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner of the token.
+    /// - The token must not be foreclosed after the collection.
+    ///
+    /// Emits a {PriceChanged} event.
+    pub fn set_price(&mut self, id: U256, price: U256) -> Result<(), Vec<u8>> {
+        self._require_owner(id)?;
+        self.assessments.setter(id).price.set(price);
+        evm::log(PriceChanged { tokenId: id, price });
+        Ok(())
+    }
+
+    /// Adds the value sent to the deposit of token `id`.
+    ///
+    /// Requirements:
+    /// - Token `id` must exist.
+    /// - The token must not be foreclosed after the collection of the tax due.
+    #[payable]
+    pub fn deposit(&mut self, id: U256) -> Result<(), Vec<u8>> {
+        if self._collect_tax(id)? {
+            return Err(HarbergerError::TokenForeclosed(TokenForeclosed { tokenId: id }).into());
+        }
+        let mut assessment = self.assessments.setter(id);
+        let deposit = assessment.deposit.get() + msg::value();
+        assessment.deposit.set(deposit);
+        Ok(())
+    }
+
+    /// Withdraws `amount` from the deposit of token `id` to its owner.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner of the token.
+    /// - The token must not be foreclosed after the collection of the tax due.
+    /// - `amount` cannot exceed the deposit left after the collection.
+    pub fn withdraw_deposit(&mut self, id: U256, amount: U256) -> Result<(), Vec<u8>> {
+        let owner = self._require_owner(id)?;
+        let mut assessment = self.assessments.setter(id);
+        let deposit = assessment.deposit.get();
+        if amount > deposit {
+            return Err(HarbergerError::InsufficientDeposit(InsufficientDeposit {
+                deposit,
+                amount,
+            })
+            .into());
+        }
+        assessment.deposit.set(deposit - amount);
+        safe_transfer_eth(owner, amount)
+    }
+
+    /// Buys token `id` at its price, and sets its price to `new_price`. The price is credited to
+    /// the seller with what is left of their deposit, and the rest of the value sent becomes the
+    /// deposit of the buyer. The tax due is collected first, so a delinquent token is bought from
+    /// this contract for free.
+    ///
+    /// `current_price` must be the price the buyer expects to pay, so that the owner cannot raise
+    /// it right before the purchase.
+    ///
+    /// Requirements:
+    /// - Token `id` must exist.
+    /// - `current_price` must be the price of the token after the collection.
+    /// - The value sent must cover the price.
+    ///
+    /// Emits a {Transfer} event, a {Bought} event and a {PriceChanged} event.
+    #[payable]
+    pub fn buy(&mut self, id: U256, new_price: U256, current_price: U256) -> Result<(), Vec<u8>> {
+        self._collect_tax(id)?;
+        let seller = self.erc721.owner_of(id)?;
+        let buyer = msg::sender();
+        let value = msg::value();
+
+        let mut assessment = self.assessments.setter(id);
+        let price = assessment.price.get();
+        if price != current_price {
+            return Err(
+                HarbergerError::PriceChangedBeforeBuy(PriceChangedBeforeBuy { price }).into(),
+            );
+        }
+        if value < price {
+            return Err(
+                HarbergerError::InsufficientPayment(InsufficientPayment { value, price }).into(),
+            );
+        }
+        let proceeds = price + assessment.deposit.get();
+        assessment.price.set(new_price);
+        assessment.deposit.set(value - price);
+        assessment.last_collected.set(U64::from(block::timestamp()));
+        if !proceeds.is_zero() {
+            self._credit(seller, proceeds);
+        }
+
+        ERC721::_transfer(&mut self.erc721, id, seller, buyer)?;
+        evm::log(Bought {
+            tokenId: id,
+            buyer,
+            seller,
+            price,
+        });
+        evm::log(PriceChanged {
+            tokenId: id,
+            price: new_price,
+        });
+        Ok(())
+    }
+
+    /// Withdraws the ETH credited to the caller from sales and taxes.
+    ///
+    /// Requirements:
+    /// - The caller must have been credited.
+    pub fn withdraw(&mut self) -> Result<(), Vec<u8>> {
+        let account = msg::sender();
+        let credit = self.credits.get(account);
+        if credit.is_zero() {
+            return Err(HarbergerError::NothingToWithdraw(NothingToWithdraw {}).into());
+        }
+        self.credits.insert(account, U256::ZERO);
+        safe_transfer_eth(account, credit)
+    }
+
+    /// Reverts, as tokens can only be transferred by being bought.
+    pub fn transfer_from(
+        &mut self,
+        _from: Address,
+        _to: Address,
+        id: U256,
+    ) -> Result<(), HarbergerError> {
+        Err(HarbergerError::TransferDisabled(TransferDisabled {
+            tokenId: id,
+        }))
+    }
+
+    /// Reverts, as tokens can only be transferred by being bought.
+    pub fn safe_transfer_from(
+        &mut self,
+        _from: Address,
+        _to: Address,
+        id: U256,
+    ) -> Result<(), HarbergerError> {
+        Err(HarbergerError::TransferDisabled(TransferDisabled {
+            tokenId: id,
+        }))
+    }
+
+    /// Equivalent to [`safe_transfer_from`].
+    #[selector(name = "safeTransferFrom")]
+    pub fn safe_transfer_from_with_data(
+        &mut self,
+        _from: Address,
+        _to: Address,
+        id: U256,
+        _data: Bytes,
+    ) -> Result<(), HarbergerError> {
+        Err(HarbergerError::TransferDisabled(TransferDisabled {
+            tokenId: id,
+        }))
+    }
+}
@@ -0,0 +1,226 @@
+//! ERC721 extension for lazy minting, where tokens are only minted when a signed voucher is redeemed.
+//! The logic was based off of: https://github.com/yusefnapora/lazy-minting/blob/main/contracts/LazyNFT.sol
+//!
+//! An authorized signer issues EIP-712 `MintVoucher`s off-chain for tokens that do not exist yet.
+//! Anyone holding a voucher can redeem it on-chain by paying its price, which mints the token with
+//! the voucher's URI and forwards the payment to the payment receiver. Vouchers with a zero
+//! `recipient` mint to the redeemer, otherwise they mint to `recipient`. Each voucher has a nonce
+//! that can only be redeemed once.
+//!
+//! `LazyMint` wraps `ERC721` to override `tokenURI`. Contracts must inherit `LazyMint<T>` and
+//! `ERC721<T>` (in that order), and implement `Borrow`/`BorrowMut` of `ERC721<T>` by returning the
+//! `erc721` field of the `LazyMint` storage. The signer and the payment receiver are set during the
+//! [initialization](crate#initialization) of the contract.
+
+use alloc::{string::String, vec::Vec};
+use core::marker::PhantomData;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolType},
+    block,
+    crypto::keccak,
+    evm, msg,
+    prelude::*,
+};
+
+use crate::tokens::erc721::{ERC721Error, ERC721Params, ERC721};
+use crate::utils::eip712::{EIP712Params, EIP712};
+use crate::utils::safe_transfer::safe_transfer_eth;
+use crate::utils::signature_checker::is_valid_signature_now;
+
+/// The EIP-712 type hash of a mint voucher.
+pub const MINT_VOUCHER_TYPEHASH: B256 = crate::const_keccak!("MintVoucher(uint256 tokenId,string uri,uint256 price,address recipient,uint256 expiry,uint256 nonce)");
+
+/// The EIP-712 domain of the vouchers, which uses the collection name and version "1"
+pub struct LazyMintDomain<T>(PhantomData<T>);
+
+impl<T: ERC721Params> EIP712Params for LazyMintDomain<T> {
+    const NAME: &'static str = T::NAME;
+    const VERSION: &'static str = "1";
+}
+
+sol_storage! {
+    /// LazyMint mints tokens of the underlying collection from signed vouchers
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.LazyMint"))]
+    pub struct LazyMint<T: ERC721Params> {
+        /// The underlying collection
+        ERC721<T> erc721;
+        /// The EIP-712 domain the vouchers are signed for
+        EIP712<LazyMintDomain<T>> eip712;
+        /// The account authorized to sign vouchers
+        address signer;
+        /// The account receiving the voucher payments
+        address payment_receiver;
+        /// Maps voucher nonce to whether it has been redeemed
+        mapping(uint256 => bool) redeemed;
+        /// Maps id to the URI set by its voucher
+        mapping(uint256 => string) token_uris;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when the voucher with `nonce` is redeemed, minting token `tokenId` to `to`.
+    event VoucherRedeemed(uint256 indexed nonce, uint256 indexed tokenId, address indexed to, uint256 price);
+    /// Emitted when the voucher signer is changed to `signer`.
+    event VoucherSignerUpdated(address signer);
+
+    /// The voucher has expired.
+    error VoucherExpired(uint256 expiry);
+    /// The voucher has already been redeemed.
+    error VoucherAlreadyRedeemed(uint256 nonce);
+    /// The voucher signature is not a valid signature of the signer.
+    error InvalidVoucherSignature();
+    /// The value sent does not match the price of the voucher.
+    error InvalidVoucherPayment(uint256 expected, uint256 sent);
+}
+
+#[derive(SolidityError)]
+pub enum LazyMintError {
+    VoucherExpired(VoucherExpired),
+    VoucherAlreadyRedeemed(VoucherAlreadyRedeemed),
+    InvalidVoucherSignature(InvalidVoucherSignature),
+    InvalidVoucherPayment(InvalidVoucherPayment),
+}
+
+// Internal functions
+impl<T: ERC721Params> LazyMint<T> {
+    /// Sets the account authorized to sign vouchers.
+    /// Vouchers signed by the previous signer can no longer be redeemed.
+    ///
+    /// Emits a {VoucherSignerUpdated} event.
+    pub fn _set_signer(&mut self, signer: Address) {
+        self.signer.set(signer);
+        evm::log(VoucherSignerUpdated { signer });
+    }
+
+    /// Sets the account receiving the voucher payments.
+    pub fn _set_payment_receiver(&mut self, receiver: Address) {
+        self.payment_receiver.set(receiver);
+    }
+
+    /// Returns the EIP-712 struct hash of a mint voucher.
+    pub fn _voucher_struct_hash(
+        token_id: U256,
+        uri: &str,
+        price: U256,
+        recipient: Address,
+        expiry: U256,
+        nonce: U256,
+    ) -> B256 {
+        keccak(
+            <sol! { (bytes32, uint256, bytes32, uint256, address, uint256, uint256) }>::encode(&(
+                MINT_VOUCHER_TYPEHASH.0,
+                token_id,
+                keccak(uri.as_bytes()).0,
+                price,
+                recipient,
+                expiry,
+                nonce,
+            )),
+        )
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC721Params> LazyMint<T> {
+    /// Returns the Uniform Resource Identifier (URI) for token `id`.
+    /// Falls back to `ERC721Params::token_uri` for tokens minted without a voucher URI.
+    ///
+    /// Requirements:
+    /// - Token `id` must exist.
+    #[selector(name = "tokenURI")]
+    pub fn token_uri(&self, id: U256) -> Result<String, ERC721Error> {
+        self.erc721.owner_of(id)?;
+        let uri = self.token_uris.getter(id).get_string();
+        if uri.is_empty() {
+            return Ok(T::token_uri(id));
+        }
+        Ok(uri)
+    }
+
+    /// Returns the EIP-712 domain separator the vouchers are signed for.
+    #[selector(name = "DOMAIN_SEPARATOR")]
+    pub fn domain_separator(&self) -> B256 {
+        self.eip712._domain_separator()
+    }
+
+    /// Returns the account authorized to sign vouchers.
+    pub fn voucher_signer(&self) -> Address {
+        self.signer.get()
+    }
+
+    /// Returns whether the voucher with `nonce` has been redeemed.
+    pub fn is_voucher_redeemed(&self, nonce: U256) -> bool {
+        self.redeemed.get(nonce)
+    }
+
+    /// Redeems a mint voucher, minting token `token_id` and forwarding the payment.
+    ///
+    /// Requirements:
+    /// - The voucher must not have expired or been redeemed.
+    /// - `signature` must be a valid signature of the voucher by the signer.
+    /// - The value sent must be exactly `price`.
+    /// - Token `token_id` must not exist.
+    ///
+    /// Emits a {Transfer} event and a {VoucherRedeemed} event.
+    #[payable]
+    #[allow(clippy::too_many_arguments)]
+    pub fn redeem(
+        &mut self,
+        token_id: U256,
+        uri: String,
+        price: U256,
+        recipient: Address,
+        expiry: U256,
+        nonce: U256,
+        signature: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        if U256::from(block::timestamp()) > expiry {
+            return Err(LazyMintError::VoucherExpired(VoucherExpired { expiry }).into());
+        }
+        if self.redeemed.get(nonce) {
+            return Err(
+                LazyMintError::VoucherAlreadyRedeemed(VoucherAlreadyRedeemed { nonce }).into(),
+            );
+        }
+        let struct_hash =
+            Self::_voucher_struct_hash(token_id, &uri, price, recipient, expiry, nonce);
+        let signed_hash = self.eip712._hash_typed_data(struct_hash);
+        if !is_valid_signature_now(self.signer.get(), signed_hash, &signature) {
+            return Err(LazyMintError::InvalidVoucherSignature(InvalidVoucherSignature {}).into());
+        }
+        if msg::value() != price {
+            return Err(LazyMintError::InvalidVoucherPayment(InvalidVoucherPayment {
+                expected: price,
+                sent: msg::value(),
+            })
+            .into());
+        }
+        self.redeemed.insert(nonce, true);
+
+        let to = if recipient.is_zero() {
+            msg::sender()
+        } else {
+            recipient
+        };
+        ERC721::_mint(&mut self.erc721, to, token_id)?;
+        if !uri.is_empty() {
+            self.token_uris.setter(token_id).set_str(uri);
+        }
+        if !price.is_zero() {
+            safe_transfer_eth(self.payment_receiver.get(), price)?;
+        }
+
+        evm::log(VoucherRedeemed {
+            nonce,
+            tokenId: token_id,
+            to,
+            price,
+        });
+        Ok(())
+    }
+}
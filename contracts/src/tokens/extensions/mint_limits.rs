@@ -0,0 +1,160 @@
+//! ERC721 extension enforcing a maximum supply and a per-wallet mint cap.
+//! The logic was based off of: https://github.com/chiru-labs/ERC721A/blob/main/contracts/ERC721A.sol
+//!
+//! Every mint through this extension counts towards the total number of tokens minted and the
+//! number of tokens minted by the recipient. Like `_totalMinted` and `_numberMinted` of ERC721A,
+//! these counters never decrease, so burning tokens does not free up supply and transferring
+//! tokens away does not reset a wallet's cap.
+//!
+//! The `ERC721Params` transfer hooks cannot read storage, so the limits are enforced by the mint
+//! functions of `MintLimits` instead. Tokens minted directly through the wrapped `ERC721` bypass
+//! the limits, so all mints should go through `MintLimits`.
+//!
+//! `MintLimits` wraps `ERC721`. Contracts must inherit `MintLimits<T>` and `ERC721<T>`, and
+//! implement `Borrow`/`BorrowMut` of `ERC721<T>` by returning the `erc721` field of the
+//! `MintLimits` storage.
+
+use alloc::vec::Vec;
+use core::borrow::BorrowMut;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+};
+
+use crate::tokens::erc721::{ERC721Params, ERC721};
+
+/// Immutable definitions of the mint limits
+pub trait MintLimitsParams {
+    /// The maximum number of tokens that can be minted.
+    const MAX_SUPPLY: u64;
+    /// The maximum number of tokens that can be minted to a single wallet.
+    const MAX_PER_WALLET: u64 = u64::MAX;
+}
+
+sol_storage! {
+    /// MintLimits caps the number of tokens minted in total and per wallet
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.MintLimits"))]
+    pub struct MintLimits<T: ERC721Params> {
+        /// The underlying collection
+        ERC721<T> erc721;
+        /// The number of tokens minted
+        uint256 total_minted;
+        /// Maps account to the number of tokens minted to it
+        mapping(address => uint256) number_minted;
+    }
+}
+
+// Declare Solidity error types
+sol! {
+    /// The mint would exceed the maximum supply.
+    error ExceedsMaxSupply(uint256 maxSupply);
+    /// The mint would exceed the maximum number of tokens minted to `account`.
+    error ExceedsWalletLimit(address account, uint256 maxPerWallet);
+}
+
+#[derive(SolidityError)]
+pub enum MintLimitsError {
+    ExceedsMaxSupply(ExceedsMaxSupply),
+    ExceedsWalletLimit(ExceedsWalletLimit),
+}
+
+// Internal functions
+impl<T: ERC721Params + MintLimitsParams> MintLimits<T> {
+    /// Records `quantity` tokens minted to `to`.
+    ///
+    /// Requirements:
+    /// - The mint must not exceed the maximum supply or the wallet limit of `to`.
+    pub fn _use_mint_limits(&mut self, to: Address, quantity: U256) -> Result<(), MintLimitsError> {
+        let total_minted = self.total_minted.get() + quantity;
+        if total_minted > U256::from(T::MAX_SUPPLY) {
+            return Err(MintLimitsError::ExceedsMaxSupply(ExceedsMaxSupply {
+                maxSupply: U256::from(T::MAX_SUPPLY),
+            }));
+        }
+        let number_minted = self.number_minted.get(to) + quantity;
+        if number_minted > U256::from(T::MAX_PER_WALLET) {
+            return Err(MintLimitsError::ExceedsWalletLimit(ExceedsWalletLimit {
+                account: to,
+                maxPerWallet: U256::from(T::MAX_PER_WALLET),
+            }));
+        }
+        self.total_minted.set(total_minted);
+        self.number_minted.insert(to, number_minted);
+        Ok(())
+    }
+
+    /// Mints token `id` to `to`.
+    ///
+    /// Requirements:
+    /// - The mint must not exceed the maximum supply or the wallet limit of `to`.
+    /// - Token `id` must not exist.
+    /// - `to` cannot be the zero address.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn _mint(&mut self, to: Address, id: U256) -> Result<(), Vec<u8>> {
+        self._use_mint_limits(to, U256::from(1))?;
+        ERC721::_mint(&mut self.erc721, to, id)?;
+        Ok(())
+    }
+
+    /// Mints token `id` to `to`.
+    ///
+    /// Requirements:
+    /// - The mint must not exceed the maximum supply or the wallet limit of `to`.
+    /// - Token `id` must not exist.
+    /// - `to` cannot be the zero address.
+    /// - If `to` refers to a smart contract, it must implement
+    ///   {IERC721Receiver-onERC721Received}, which is called upon a safe transfer.
+    ///
+    /// Emits a {Transfer} event.
+    pub fn _safe_mint<S: TopLevelStorage + BorrowMut<Self> + BorrowMut<ERC721<T>>>(
+        storage: &mut S,
+        to: Address,
+        id: U256,
+        data: Vec<u8>,
+    ) -> Result<(), Vec<u8>> {
+        BorrowMut::<Self>::borrow_mut(storage)._use_mint_limits(to, U256::from(1))?;
+        ERC721::<T>::_safe_mint::<S, ERC721<T>>(storage, to, id, data)?;
+        Ok(())
+    }
+
+    /// Mints `quantity` tokens with consecutive ids to `to`, and returns the first id.
+    /// See `ERC721::_mint_consecutive`.
+    ///
+    /// Requirements:
+    /// - The mint must not exceed the maximum supply or the wallet limit of `to`.
+    /// - `to` cannot be the zero address.
+    /// - `quantity` must not exceed `ERC721Params::MAX_BATCH_SIZE`.
+    ///
+    /// Emits a {ConsecutiveTransfer} event.
+    pub fn _mint_consecutive(&mut self, to: Address, quantity: U256) -> Result<U256, Vec<u8>> {
+        self._use_mint_limits(to, quantity)?;
+        ERC721::_mint_consecutive(&mut self.erc721, to, quantity)
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC721Params + MintLimitsParams> MintLimits<T> {
+    /// Returns the maximum number of tokens that can be minted.
+    pub fn max_supply(&self) -> U256 {
+        U256::from(T::MAX_SUPPLY)
+    }
+
+    /// Returns the maximum number of tokens that can be minted to a single wallet.
+    pub fn max_per_wallet(&self) -> U256 {
+        U256::from(T::MAX_PER_WALLET)
+    }
+
+    /// Returns the number of tokens minted, including burned tokens.
+    pub fn total_minted(&self) -> U256 {
+        self.total_minted.get()
+    }
+
+    /// Returns the number of tokens minted to `account`, including transferred and burned tokens.
+    pub fn number_minted(&self, account: Address) -> U256 {
+        self.number_minted.get(account)
+    }
+}
@@ -0,0 +1,380 @@
+//! ERC721 extension managing the sale phases of a collection, such as allowlists and a public mint.
+//! The logic was based off of: https://github.com/thirdweb-dev/contracts/blob/main/contracts/extension/Drop.sol
+//!
+//! Each phase has a kind, a start and end time, a price and a per-wallet cap. Phases can be:
+//! - `PHASE_PUBLIC`: anyone can mint.
+//! - `PHASE_MERKLE_ALLOWLIST`: accounts prove their inclusion in a merkle tree whose leaves are
+//!   `keccak256(abi.encodePacked(account))`.
+//! - `PHASE_SIGNATURE_ALLOWLIST`: accounts present an EIP-712 `AllowlistMint` signature from the
+//!   signer of the phase.
+//!
+//! The number of tokens minted by each wallet is tracked per phase, so transferring tokens away
+//! does not reset the cap. Tokens are minted with sequential ids starting from 0.
+//!
+//! `MintPhases` wraps `ERC721` and `Ownable`. Contracts must inherit `MintPhases<T>`, `ERC721<T>`
+//! and `Ownable`, and implement `Borrow`/`BorrowMut` of `ERC721<T>` and `Ownable` by returning the
+//! `erc721` and `ownable` fields of the `MintPhases` storage. Stylus contracts do not have
+//! constructors, so the inheriting contract must initialize the owner.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, B256, U256, U32, U64, U8},
+    alloy_sol_types::{sol, SolType},
+    block, contract,
+    crypto::keccak,
+    evm, msg,
+    prelude::*,
+};
+
+use crate::access::ownable::Ownable;
+use crate::tokens::erc721::{ERC721Params, ERC721};
+use crate::utils::eip712::{EIP712Params, EIP712};
+use crate::utils::merkle_proof::{MerkleProof, MerkleProofTrait};
+use crate::utils::safe_transfer::safe_transfer_eth;
+use crate::utils::signature_checker::is_valid_signature_now;
+
+/// A phase open to anyone.
+pub const PHASE_PUBLIC: u8 = 0;
+/// A phase restricted to the accounts of a merkle allowlist.
+pub const PHASE_MERKLE_ALLOWLIST: u8 = 1;
+/// A phase restricted to the accounts with a signature from the phase signer.
+pub const PHASE_SIGNATURE_ALLOWLIST: u8 = 2;
+
+/// The EIP-712 type hash of a signature allowlist entry.
+pub const ALLOWLIST_MINT_TYPEHASH: B256 =
+    crate::const_keccak!("AllowlistMint(uint256 phaseId,address account)");
+
+/// The EIP-712 domain of the allowlist signatures, which uses the collection name and version "1"
+pub struct MintPhasesDomain<T>(PhantomData<T>);
+
+impl<T: ERC721Params> EIP712Params for MintPhasesDomain<T> {
+    const NAME: &'static str = T::NAME;
+    const VERSION: &'static str = "1";
+}
+
+sol_storage! {
+    /// The configuration of a sale phase
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct Phase {
+        uint8 kind;
+        uint64 start_time;
+        uint64 end_time;
+        uint32 max_per_wallet;
+        uint256 price;
+        bytes32 merkle_root;
+        address signer;
+    }
+
+    /// MintPhases sells the tokens of a collection through owner-configured phases
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.MintPhases"))]
+    pub struct MintPhases<T: ERC721Params> {
+        /// The collection being minted
+        ERC721<T> erc721;
+        /// The owner, who configures the phases and withdraws the proceeds
+        Ownable ownable;
+        /// The EIP-712 domain the allowlist signatures are signed for
+        EIP712<MintPhasesDomain<T>> eip712;
+        /// The number of phases
+        uint256 phase_count;
+        /// Maps phase id to its configuration
+        mapping(uint256 => Phase) phases;
+        /// Maps phase id to the number of tokens minted by each account
+        mapping(uint256 => mapping(address => uint256)) phase_minted;
+        /// The id of the next token to mint
+        uint256 next_token_id;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when phase `phaseId` is added or updated.
+    event PhaseUpdated(uint256 indexed phaseId, uint8 kind, uint64 startTime, uint64 endTime, uint256 price, uint32 maxPerWallet);
+    /// Emitted when `minter` mints `quantity` tokens in phase `phaseId`.
+    event PhaseMint(uint256 indexed phaseId, address indexed minter, uint256 quantity);
+
+    /// The phase does not exist.
+    error PhaseNotFound(uint256 phaseId);
+    /// The phase kind is not one of the supported kinds.
+    error PhaseInvalidKind(uint8 kind);
+    /// The phase is not active, or is not of the kind required by the mint method.
+    error PhaseNotActive(uint256 phaseId);
+    /// The quantity to mint is zero.
+    error PhaseZeroQuantity();
+    /// The mint would exceed the per-wallet cap of the phase.
+    error PhaseExceedsWalletLimit(uint256 phaseId, uint256 maxPerWallet);
+    /// The value sent does not match the price of the mint.
+    error PhaseInvalidPayment(uint256 expected, uint256 sent);
+    /// The merkle proof does not prove the caller is allowlisted.
+    error InvalidAllowlistProof();
+    /// The signature is not a valid allowlist signature for the caller.
+    error InvalidAllowlistSignature();
+}
+
+#[derive(SolidityError)]
+pub enum MintPhasesError {
+    PhaseNotFound(PhaseNotFound),
+    PhaseInvalidKind(PhaseInvalidKind),
+    PhaseNotActive(PhaseNotActive),
+    PhaseZeroQuantity(PhaseZeroQuantity),
+    PhaseExceedsWalletLimit(PhaseExceedsWalletLimit),
+    PhaseInvalidPayment(PhaseInvalidPayment),
+    InvalidAllowlistProof(InvalidAllowlistProof),
+    InvalidAllowlistSignature(InvalidAllowlistSignature),
+}
+
+// Internal functions
+impl<T: ERC721Params> MintPhases<T> {
+    /// Requires that phase `phase_id` is of `kind` and currently active.
+    fn _require_active(&self, phase_id: U256, kind: u8) -> Result<(), MintPhasesError> {
+        if phase_id >= self.phase_count.get() {
+            return Err(MintPhasesError::PhaseNotFound(PhaseNotFound {
+                phaseId: phase_id,
+            }));
+        }
+        let phase = self.phases.getter(phase_id);
+        let now = U64::from(block::timestamp());
+        if phase.kind.get() != U8::from(kind)
+            || now < phase.start_time.get()
+            || now >= phase.end_time.get()
+        {
+            return Err(MintPhasesError::PhaseNotActive(PhaseNotActive {
+                phaseId: phase_id,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Mints `quantity` tokens to the caller in phase `phase_id`, which must be active.
+    ///
+    /// Requirements:
+    /// - `quantity` must be non-zero and not exceed the caller's remaining cap for the phase.
+    /// - The value sent must be exactly the price of the mint.
+    ///
+    /// Emits a {Transfer} event for each token and a {PhaseMint} event.
+    fn _phase_mint(&mut self, phase_id: U256, quantity: U256) -> Result<(), Vec<u8>> {
+        if quantity.is_zero() {
+            return Err(MintPhasesError::PhaseZeroQuantity(PhaseZeroQuantity {}).into());
+        }
+        let (price, max_per_wallet) = {
+            let phase = self.phases.getter(phase_id);
+            (phase.price.get(), U256::from(phase.max_per_wallet.get()))
+        };
+        let minter = msg::sender();
+        let minted = self.phase_minted.getter(phase_id).get(minter) + quantity;
+        if minted > max_per_wallet {
+            return Err(
+                MintPhasesError::PhaseExceedsWalletLimit(PhaseExceedsWalletLimit {
+                    phaseId: phase_id,
+                    maxPerWallet: max_per_wallet,
+                })
+                .into(),
+            );
+        }
+        let cost = price * quantity;
+        if msg::value() != cost {
+            return Err(MintPhasesError::PhaseInvalidPayment(PhaseInvalidPayment {
+                expected: cost,
+                sent: msg::value(),
+            })
+            .into());
+        }
+        self.phase_minted.setter(phase_id).insert(minter, minted);
+
+        let first_id = self.next_token_id.get();
+        self.next_token_id.set(first_id + quantity);
+        let mut id = first_id;
+        while id < first_id + quantity {
+            ERC721::_mint(&mut self.erc721, minter, id)?;
+            id += U256::from(1);
+        }
+        evm::log(PhaseMint {
+            phaseId: phase_id,
+            minter,
+            quantity,
+        });
+        Ok(())
+    }
+}
+
+// External functions
+#[external]
+impl<T: ERC721Params> MintPhases<T> {
+    /// Returns the number of phases.
+    pub fn phase_count(&self) -> U256 {
+        self.phase_count.get()
+    }
+
+    /// Returns phase `phase_id` as
+    /// `(kind, start_time, end_time, price, max_per_wallet, merkle_root, signer)`.
+    ///
+    /// Requirements:
+    /// - The phase must exist.
+    #[allow(clippy::type_complexity)]
+    pub fn get_phase(
+        &self,
+        phase_id: U256,
+    ) -> Result<(u8, u64, u64, U256, u32, B256, Address), MintPhasesError> {
+        if phase_id >= self.phase_count.get() {
+            return Err(MintPhasesError::PhaseNotFound(PhaseNotFound {
+                phaseId: phase_id,
+            }));
+        }
+        let phase = self.phases.getter(phase_id);
+        Ok((
+            phase.kind.get().to(),
+            phase.start_time.get().to(),
+            phase.end_time.get().to(),
+            phase.price.get(),
+            phase.max_per_wallet.get().to(),
+            phase.merkle_root.get(),
+            phase.signer.get(),
+        ))
+    }
+
+    /// Returns the number of tokens minted by `account` in phase `phase_id`.
+    pub fn phase_minted(&self, phase_id: U256, account: Address) -> U256 {
+        self.phase_minted.getter(phase_id).get(account)
+    }
+
+    /// Returns the EIP-712 domain separator the allowlist signatures are signed for.
+    #[selector(name = "DOMAIN_SEPARATOR")]
+    pub fn domain_separator(&self) -> B256 {
+        self.eip712._domain_separator()
+    }
+
+    /// Updates phase `phase_id`, or adds a new phase if `phase_id` is the number of phases.
+    /// `merkle_root` and `signer` are only used by allowlist phases of the matching kind.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    /// - `phase_id` must not be greater than the number of phases.
+    /// - `kind` must be one of the supported phase kinds.
+    ///
+    /// Emits a {PhaseUpdated} event.
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_phase(
+        &mut self,
+        phase_id: U256,
+        kind: u8,
+        start_time: u64,
+        end_time: u64,
+        price: U256,
+        max_per_wallet: u32,
+        merkle_root: B256,
+        signer: Address,
+    ) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        let phase_count = self.phase_count.get();
+        if phase_id > phase_count {
+            return Err(MintPhasesError::PhaseNotFound(PhaseNotFound { phaseId: phase_id }).into());
+        }
+        if kind > PHASE_SIGNATURE_ALLOWLIST {
+            return Err(MintPhasesError::PhaseInvalidKind(PhaseInvalidKind { kind }).into());
+        }
+        if phase_id == phase_count {
+            self.phase_count.set(phase_count + U256::from(1));
+        }
+
+        let mut phase = self.phases.setter(phase_id);
+        phase.kind.set(U8::from(kind));
+        phase.start_time.set(U64::from(start_time));
+        phase.end_time.set(U64::from(end_time));
+        phase.price.set(price);
+        phase.max_per_wallet.set(U32::from(max_per_wallet));
+        phase.merkle_root.set(merkle_root);
+        phase.signer.set(signer);
+        evm::log(PhaseUpdated {
+            phaseId: phase_id,
+            kind,
+            startTime: start_time,
+            endTime: end_time,
+            price,
+            maxPerWallet: max_per_wallet,
+        });
+        Ok(())
+    }
+
+    /// Mints `quantity` tokens to the caller in the public phase `phase_id`.
+    ///
+    /// Requirements:
+    /// - The phase must be an active public phase.
+    /// - `quantity` must be non-zero and not exceed the caller's remaining cap for the phase.
+    /// - The value sent must be exactly the price of the mint.
+    ///
+    /// Emits a {Transfer} event for each token and a {PhaseMint} event.
+    #[payable]
+    pub fn mint_public(&mut self, phase_id: U256, quantity: U256) -> Result<(), Vec<u8>> {
+        self._require_active(phase_id, PHASE_PUBLIC)?;
+        self._phase_mint(phase_id, quantity)
+    }
+
+    /// Mints `quantity` tokens to the caller in the merkle allowlist phase `phase_id`.
+    ///
+    /// Requirements:
+    /// - The phase must be an active merkle allowlist phase.
+    /// - `proof` must prove the caller is part of the merkle root of the phase.
+    /// - `quantity` must be non-zero and not exceed the caller's remaining cap for the phase.
+    /// - The value sent must be exactly the price of the mint.
+    ///
+    /// Emits a {Transfer} event for each token and a {PhaseMint} event.
+    #[payable]
+    pub fn mint_allowlist(
+        &mut self,
+        phase_id: U256,
+        quantity: U256,
+        proof: Vec<B256>,
+    ) -> Result<(), Vec<u8>> {
+        self._require_active(phase_id, PHASE_MERKLE_ALLOWLIST)?;
+        let root = self.phases.getter(phase_id).merkle_root.get();
+        let leaf = keccak(msg::sender());
+        let proof: Vec<[u8; 32]> = proof.iter().map(|node| node.0).collect();
+        if !MerkleProof::verify(&proof, root.0, leaf.0) {
+            return Err(MintPhasesError::InvalidAllowlistProof(InvalidAllowlistProof {}).into());
+        }
+        self._phase_mint(phase_id, quantity)
+    }
+
+    /// Mints `quantity` tokens to the caller in the signature allowlist phase `phase_id`.
+    ///
+    /// Requirements:
+    /// - The phase must be an active signature allowlist phase.
+    /// - `signature` must be a valid `AllowlistMint` signature for the caller by the phase signer.
+    /// - `quantity` must be non-zero and not exceed the caller's remaining cap for the phase.
+    /// - The value sent must be exactly the price of the mint.
+    ///
+    /// Emits a {Transfer} event for each token and a {PhaseMint} event.
+    #[payable]
+    pub fn mint_with_signature(
+        &mut self,
+        phase_id: U256,
+        quantity: U256,
+        signature: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        self._require_active(phase_id, PHASE_SIGNATURE_ALLOWLIST)?;
+        let struct_hash = keccak(<sol! { (bytes32, uint256, address) }>::encode(&(
+            ALLOWLIST_MINT_TYPEHASH.0,
+            phase_id,
+            msg::sender(),
+        )));
+        let signed_hash = self.eip712._hash_typed_data(struct_hash);
+        let signer = self.phases.getter(phase_id).signer.get();
+        if !is_valid_signature_now(signer, signed_hash, &signature) {
+            return Err(
+                MintPhasesError::InvalidAllowlistSignature(InvalidAllowlistSignature {}).into(),
+            );
+        }
+        self._phase_mint(phase_id, quantity)
+    }
+
+    /// Sends the sale proceeds to `to`.
+    ///
+    /// Requirements:
+    /// - The caller must be the owner.
+    pub fn withdraw(&mut self, to: Address) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        safe_transfer_eth(to, contract::balance())
+    }
+}
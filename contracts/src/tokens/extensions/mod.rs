@@ -0,0 +1,55 @@
+//! Extensions that can be composed with the base token contracts
+
+#[cfg(feature = "delayed-reveal")]
+pub mod delayed_reveal;
+
+#[cfg(feature = "erc20-blocklist")]
+pub mod erc20_blocklist;
+
+#[cfg(feature = "erc20-bridged")]
+pub mod erc20_bridged;
+
+#[cfg(feature = "erc20-flashmint")]
+pub mod erc20_flashmint;
+
+#[cfg(feature = "erc20-metadata")]
+pub mod erc20_metadata;
+
+#[cfg(feature = "erc20-votes")]
+pub mod erc20_votes;
+
+#[cfg(feature = "erc1404")]
+pub mod erc1404;
+
+#[cfg(feature = "erc4907")]
+pub mod erc4907;
+
+#[cfg(feature = "erc5192")]
+pub mod erc5192;
+
+#[cfg(feature = "erc2981")]
+pub mod erc2981;
+
+#[cfg(feature = "erc7572")]
+pub mod erc7572;
+
+#[cfg(feature = "erc721-metadata")]
+pub mod erc721_metadata;
+
+#[cfg(feature = "erc721-votes")]
+pub mod erc721_votes;
+
+#[cfg(feature = "erc721-wrapper")]
+pub mod erc721_wrapper;
+
+#[cfg(feature = "harberger")]
+pub mod harberger;
+
+#[cfg(feature = "lazy-mint")]
+pub mod lazy_mint;
+
+#[cfg(feature = "mint-limits")]
+pub mod mint_limits;
+
+#[cfg(feature = "mint-phases")]
+pub mod mint_phases;
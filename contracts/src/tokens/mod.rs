@@ -1,7 +1,47 @@
 //! All the base Stylus token contracts
 
+#[cfg(feature = "dn404")]
+pub mod dn404;
+
+#[cfg(feature = "dn404")]
+pub mod dn404_mirror;
+
 #[cfg(feature = "erc20")]
 pub mod erc20;
 
+#[cfg(feature = "erc20-rebasing")]
+pub mod erc20_rebasing;
+
 #[cfg(feature = "erc721")]
 pub mod erc721;
+
+#[cfg(feature = "erc1155")]
+pub mod erc1155;
+
+#[cfg(feature = "erc721-holder")]
+pub mod erc721_holder;
+
+#[cfg(feature = "erc1155-holder")]
+pub mod erc1155_holder;
+
+#[cfg(any(
+    feature = "delayed-reveal",
+    feature = "erc2981",
+    feature = "erc4907",
+    feature = "erc5192",
+    feature = "erc7572",
+    feature = "erc20-blocklist",
+    feature = "erc20-bridged",
+    feature = "erc20-flashmint",
+    feature = "erc20-metadata",
+    feature = "erc20-votes",
+    feature = "erc1404",
+    feature = "erc721-metadata",
+    feature = "erc721-votes",
+    feature = "erc721-wrapper",
+    feature = "harberger",
+    feature = "lazy-mint",
+    feature = "mint-limits",
+    feature = "mint-phases"
+))]
+pub mod extensions;
@@ -0,0 +1,117 @@
+//! Address utilities for sending ETH and making low level calls that bubble up revert data.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/utils/Address.sol
+//!
+//! On failure, the revert data of the called contract is bubbled up if there is any. Otherwise, the
+//! ABI encoded error of this library is returned, so that callers can propagate it with `?`.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolError},
+    call::RawCall,
+    contract,
+    types::AddressVM,
+};
+
+sol! {
+    /// There's no code at `target` (it is not a contract).
+    error AddressEmptyCode(address target);
+    /// The ETH balance of the account is not enough to perform the operation.
+    error InsufficientBalance(uint256 balance, uint256 needed);
+    /// A call to an address target failed. The target may have reverted.
+    error FailedCall();
+}
+
+/// Returns true if `account` has deployed code.
+///
+/// This is false for EOAs, but also for contracts in construction, addresses where a contract will
+/// be created, and addresses where a contract lived but was destroyed. It should not be used to
+/// prevent calls from contracts.
+pub fn has_deployed_code(account: Address) -> bool {
+    account.has_code()
+}
+
+/// Sends `amount` (in wei) ETH to `recipient`, forwarding all gas.
+///
+/// Requirements:
+/// - The contract must have a balance of at least `amount`.
+pub fn send_value(recipient: Address, amount: U256) -> Result<(), Vec<u8>> {
+    let balance = contract::balance();
+    if balance < amount {
+        return Err(InsufficientBalance {
+            balance,
+            needed: amount,
+        }
+        .encode());
+    }
+    RawCall::new_with_value(amount)
+        .skip_return_data()
+        .call(recipient, &[])
+        .map_err(bubble_revert)?;
+    Ok(())
+}
+
+/// Calls `target` with `data`, and returns the returned data.
+///
+/// Requirements:
+/// - `target` must be a contract if the call returns no data.
+/// - The call must not revert.
+pub fn function_call(target: Address, data: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+    function_call_with_value(target, data, U256::ZERO)
+}
+
+/// Calls `target` with `data`, sending `value` wei, and returns the returned data.
+///
+/// Requirements:
+/// - The contract must have a balance of at least `value`.
+/// - `target` must be a contract if the call returns no data.
+/// - The call must not revert.
+pub fn function_call_with_value(
+    target: Address,
+    data: &[u8],
+    value: U256,
+) -> Result<Vec<u8>, Vec<u8>> {
+    let balance = contract::balance();
+    if balance < value {
+        return Err(InsufficientBalance {
+            balance,
+            needed: value,
+        }
+        .encode());
+    }
+    let result = RawCall::new_with_value(value).call(target, data);
+    verify_call_result_from_target(target, result)
+}
+
+/// Static calls `target` with `data`, and returns the returned data.
+///
+/// Requirements:
+/// - `target` must be a contract if the call returns no data.
+/// - The call must not revert.
+pub fn function_static_call(target: Address, data: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+    let result = RawCall::new_static().call(target, data);
+    verify_call_result_from_target(target, result)
+}
+
+/// Returns the data of a successful call to `target`, or the revert data of a failed call.
+/// A successful call without returned data fails if `target` has no code, as calls to accounts
+/// without code always succeed.
+pub fn verify_call_result_from_target(
+    target: Address,
+    result: Result<Vec<u8>, Vec<u8>>,
+) -> Result<Vec<u8>, Vec<u8>> {
+    let returndata = result.map_err(bubble_revert)?;
+    if returndata.is_empty() && !target.has_code() {
+        return Err(AddressEmptyCode { target }.encode());
+    }
+    Ok(returndata)
+}
+
+/// Returns the revert `data` if it is non-empty, otherwise the ABI encoded {FailedCall} error.
+fn bubble_revert(data: Vec<u8>) -> Vec<u8> {
+    if data.is_empty() {
+        FailedCall {}.encode()
+    } else {
+        data
+    }
+}
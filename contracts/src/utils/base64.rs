@@ -0,0 +1,6 @@
+//! Base64 encoding, used to build data URIs for fully on-chain metadata.
+//! The logic was based off of: https://github.com/Vectorized/solady/blob/main/src/utils/Base64.sol
+
+/// Import the base64 functions from inkmate_common publicly
+/// This permit projects to use them without importing `inkmate_common`
+pub use crate::inkmate_common::base64::{encode, encode_into, encode_with, encoded_len};
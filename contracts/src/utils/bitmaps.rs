@@ -0,0 +1,62 @@
+//! Bitmap storage library.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/utils/structs/BitMaps.sol
+//!
+//! A `BitMap` maps `U256` indices to booleans, packing 256 of them into each storage slot. Flags
+//! with sequential indices (ex. claimed airdrop indices, allowlist spots or nonces) share slots,
+//! which makes setting them much cheaper than a `mapping(uint256 => bool)`.
+
+use stylus_sdk::{alloy_primitives::U256, prelude::*};
+
+sol_storage! {
+    /// BitMap stores a packed boolean for every index
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct BitMap {
+        /// Maps word index to 256 packed bits
+        mapping(uint256 => uint256) data;
+    }
+}
+
+impl BitMap {
+    /// Returns the word index and the mask of the bit at `index`.
+    fn _position(index: U256) -> (U256, U256) {
+        let bit = (index & U256::from(0xff)).to::<usize>();
+        (index >> 8, U256::from(1) << bit)
+    }
+
+    /// Returns whether the bit at `index` is set.
+    pub fn get(&self, index: U256) -> bool {
+        let (word, mask) = Self::_position(index);
+        self.data.get(word) & mask != U256::ZERO
+    }
+
+    /// Sets the bit at `index` to `value`.
+    pub fn set_to(&mut self, index: U256, value: bool) {
+        if value {
+            self.set(index);
+        } else {
+            self.unset(index);
+        }
+    }
+
+    /// Sets the bit at `index`.
+    pub fn set(&mut self, index: U256) {
+        let (word, mask) = Self::_position(index);
+        let mut slot = self.data.setter(word);
+        let bits = slot.get();
+        slot.set(bits | mask);
+    }
+
+    /// Unsets the bit at `index`.
+    pub fn unset(&mut self, index: U256) {
+        let (word, mask) = Self::_position(index);
+        let mut slot = self.data.setter(word);
+        let bits = slot.get();
+        slot.set(bits & !mask);
+    }
+
+    /// Returns the 256 packed bits of the word at `word_index`, which holds the bits at indices
+    /// `word_index * 256` to `word_index * 256 + 255`.
+    pub fn get_word(&self, word_index: U256) -> U256 {
+        self.data.get(word_index)
+    }
+}
@@ -0,0 +1,409 @@
+//! Checkpoint history storage library.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/utils/structs/Checkpoints.sol
+//!
+//! A `Trace208` stores a sorted history of `(key, value)` checkpoints packed into a single slot each,
+//! where the key is usually a block number or timestamp. Values can be looked up at any past key.
+//! `Trace160` packs 96-bit keys with 160-bit values instead, which fits addresses.
+//!
+//! Both traces share the same implementation, which is generated by `impl_trace!`.
+
+use stylus_sdk::{alloy_primitives::Uint, alloy_sol_types::sol, prelude::*};
+
+/// A 48-bit unsigned integer, used for checkpoint keys.
+pub type U48 = Uint<48, 1>;
+
+/// A 208-bit unsigned integer, used for checkpoint values.
+pub type U208 = Uint<208, 4>;
+
+/// A 96-bit unsigned integer, used for `Trace160` checkpoint keys.
+pub type U96 = Uint<96, 2>;
+
+/// A 160-bit unsigned integer, used for `Trace160` checkpoint values.
+pub type U160 = Uint<160, 3>;
+
+sol_storage! {
+    /// A single checkpoint, packed into one slot
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct Checkpoint208 {
+        uint48 key;
+        uint208 value;
+    }
+
+    /// Trace208 stores checkpoints sorted by key
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct Trace208 {
+        Checkpoint208[] checkpoints;
+    }
+
+    /// A single checkpoint with a 160-bit value, packed into one slot
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct Checkpoint160 {
+        uint96 key;
+        uint160 value;
+    }
+
+    /// Trace160 stores checkpoints with 160-bit values sorted by key
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct Trace160 {
+        Checkpoint160[] checkpoints;
+    }
+}
+
+// Declare Solidity error types
+sol! {
+    /// A value was attempted to be inserted on a past checkpoint.
+    error CheckpointUnorderedInsertion();
+}
+
+#[derive(SolidityError)]
+pub enum CheckpointsError {
+    CheckpointUnorderedInsertion(CheckpointUnorderedInsertion),
+}
+
+/// Implements the checkpoint history methods for a trace with the given key and value types.
+macro_rules! impl_trace {
+    ($trace:ident, $key:ty, $value:ty) => {
+        impl $trace {
+            /// Pushes a `(key, value)` pair into the history so that it is stored as the checkpoint.
+            /// Returns the previous value and the new value.
+            ///
+            /// Requirements:
+            /// - `key` must be greater than or equal to the last inserted key.
+            pub fn push(
+                &mut self,
+                key: $key,
+                value: $value,
+            ) -> Result<($value, $value), CheckpointsError> {
+                let len = self.checkpoints.len();
+                if len == 0 {
+                    self._append(key, value);
+                    return Ok((<$value>::ZERO, value));
+                }
+
+                let mut last = self.checkpoints.setter(len - 1).unwrap();
+                let last_key = last.key.get();
+                let last_value = last.value.get();
+                if last_key > key {
+                    return Err(CheckpointsError::CheckpointUnorderedInsertion(
+                        CheckpointUnorderedInsertion {},
+                    ));
+                }
+
+                if last_key == key {
+                    // Update the existing checkpoint
+                    last.value.set(value);
+                } else {
+                    self._append(key, value);
+                }
+                Ok((last_value, value))
+            }
+
+            /// Returns the value in the first (oldest) checkpoint with a key greater or equal
+            /// than the search key, or zero if there is none.
+            pub fn lower_lookup(&self, key: $key) -> $value {
+                let len = self.checkpoints.len();
+                let pos = self._lower_binary_lookup(key, 0, len);
+                if pos == len {
+                    <$value>::ZERO
+                } else {
+                    self.checkpoints.getter(pos).unwrap().value.get()
+                }
+            }
+
+            /// Returns the value in the last (most recent) checkpoint with a key lower or equal
+            /// than the search key, or zero if there is none.
+            pub fn upper_lookup(&self, key: $key) -> $value {
+                let len = self.checkpoints.len();
+                let pos = self._upper_binary_lookup(key, 0, len);
+                if pos == 0 {
+                    <$value>::ZERO
+                } else {
+                    self.checkpoints.getter(pos - 1).unwrap().value.get()
+                }
+            }
+
+            /// Returns the value in the last (most recent) checkpoint with a key lower or equal
+            /// than the search key, or zero if there is none.
+            ///
+            /// This is a variant of `upper_lookup` optimised for finding recent checkpoints (ex.
+            /// the last `sqrt(length)` checkpoints), which it checks before searching the rest.
+            pub fn upper_lookup_recent(&self, key: $key) -> $value {
+                let len = self.checkpoints.len();
+                let mut low = 0;
+                let mut high = len;
+                if len > 5 {
+                    let mid = len - len.isqrt();
+                    if key < self.checkpoints.getter(mid).unwrap().key.get() {
+                        high = mid;
+                    } else {
+                        low = mid + 1;
+                    }
+                }
+
+                let pos = self._upper_binary_lookup(key, low, high);
+                if pos == 0 {
+                    <$value>::ZERO
+                } else {
+                    self.checkpoints.getter(pos - 1).unwrap().value.get()
+                }
+            }
+
+            /// Returns the value in the most recent checkpoint, or zero if there are no checkpoints.
+            pub fn latest(&self) -> $value {
+                let len = self.checkpoints.len();
+                if len == 0 {
+                    <$value>::ZERO
+                } else {
+                    self.checkpoints.getter(len - 1).unwrap().value.get()
+                }
+            }
+
+            /// Returns whether there is a checkpoint, and if so the key and value of the most
+            /// recent one.
+            pub fn latest_checkpoint(&self) -> (bool, $key, $value) {
+                let len = self.checkpoints.len();
+                if len == 0 {
+                    return (false, <$key>::ZERO, <$value>::ZERO);
+                }
+                let checkpoint = self.checkpoints.getter(len - 1).unwrap();
+                (true, checkpoint.key.get(), checkpoint.value.get())
+            }
+
+            /// Returns the number of checkpoints.
+            pub fn length(&self) -> usize {
+                self.checkpoints.len()
+            }
+
+            /// Returns the checkpoint at position `pos`, if it exists.
+            pub fn at(&self, pos: usize) -> Option<($key, $value)> {
+                self.checkpoints
+                    .getter(pos)
+                    .map(|checkpoint| (checkpoint.key.get(), checkpoint.value.get()))
+            }
+
+            /// Appends a new checkpoint to the end of the history.
+            fn _append(&mut self, key: $key, value: $value) {
+                let mut checkpoint = self.checkpoints.grow();
+                checkpoint.key.set(key);
+                checkpoint.value.set(value);
+            }
+
+            /// Returns the index of the first (oldest) checkpoint with a key strictly bigger than
+            /// the search key, or `high` if there is none. `low` and `high` define a section to
+            /// search in.
+            fn _upper_binary_lookup(&self, key: $key, mut low: usize, mut high: usize) -> usize {
+                while low < high {
+                    let mid = low + (high - low) / 2;
+                    if self.checkpoints.getter(mid).unwrap().key.get() > key {
+                        high = mid;
+                    } else {
+                        low = mid + 1;
+                    }
+                }
+                high
+            }
+
+            /// Returns the index of the first (oldest) checkpoint with a key greater or equal than
+            /// the search key, or `high` if there is none. `low` and `high` define a section to
+            /// search in.
+            fn _lower_binary_lookup(&self, key: $key, mut low: usize, mut high: usize) -> usize {
+                while low < high {
+                    let mid = low + (high - low) / 2;
+                    if self.checkpoints.getter(mid).unwrap().key.get() < key {
+                        low = mid + 1;
+                    } else {
+                        high = mid;
+                    }
+                }
+                high
+            }
+        }
+    };
+}
+
+impl_trace!(Trace208, U48, U208);
+impl_trace!(Trace160, U96, U160);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use inkmate_test::TestHost;
+    use stylus_sdk::alloy_primitives::{Address, U256};
+    use stylus_sdk::alloy_sol_types::SolCall;
+
+    sol_storage! {
+        struct History {
+            Trace208 trace;
+            Trace160 trace160;
+        }
+    }
+
+    unsafe impl TopLevelStorage for History {}
+
+    /// Exposes the traces, with the keys and values converted to types supported by the ABI
+    #[external]
+    impl History {
+        pub fn push(&mut self, key: u64, value: U256) -> Result<(U256, U256), Vec<u8>> {
+            let (previous, value) = self.trace.push(U48::from(key), U208::from(value))?;
+            Ok((U256::from(previous), U256::from(value)))
+        }
+
+        pub fn push160(
+            &mut self,
+            key: u128,
+            value: Address,
+        ) -> Result<(Address, Address), Vec<u8>> {
+            let value = U160::from_be_bytes(value.0 .0);
+            let (previous, value) = self.trace160.push(U96::from(key), value)?;
+            Ok((
+                Address::from(previous.to_be_bytes()),
+                Address::from(value.to_be_bytes()),
+            ))
+        }
+
+        pub fn lookups(&self, key: u64) -> (U256, U256, U256) {
+            let key = U48::from(key);
+            (
+                U256::from(self.trace.lower_lookup(key)),
+                U256::from(self.trace.upper_lookup(key)),
+                U256::from(self.trace.upper_lookup_recent(key)),
+            )
+        }
+
+        pub fn latest_checkpoint(&self) -> (bool, u64, U256) {
+            let (exists, key, value) = self.trace.latest_checkpoint();
+            (exists, key.to(), U256::from(value))
+        }
+
+        pub fn length(&self) -> U256 {
+            U256::from(self.trace.length())
+        }
+
+        pub fn latest160(&self) -> Address {
+            Address::from(self.trace160.latest().to_be_bytes())
+        }
+    }
+
+    sol! {
+        function push(uint64 key, uint256 value) returns (uint256, uint256);
+        function push160(uint128 key, address value) returns (address, address);
+        function lookups(uint64 key) returns (uint256, uint256, uint256);
+        function latestCheckpoint() returns (bool, uint64, uint256);
+        function length() returns (uint256);
+        function latest160() returns (address);
+    }
+
+    fn setup() -> (TestHost, Address, Address) {
+        let mut host = TestHost::new();
+        let history = host.deploy::<History>();
+        (host, history, TestHost::address("alice"))
+    }
+
+    /// Pushes `value` at `key`, and returns the previous and new values
+    fn push(host: &mut TestHost, history: Address, key: u64, value: u64) -> (U256, U256) {
+        let push = pushCall {
+            key,
+            value: U256::from(value),
+        };
+        let values = host.call_sol(Address::ZERO, history, &push).unwrap();
+        (values._0, values._1)
+    }
+
+    /// Returns the lower, upper and recent upper lookups of `key`
+    fn lookups(host: &mut TestHost, history: Address, key: u64) -> (u64, u64, u64) {
+        let lookups = lookupsCall { key };
+        let values = host.call_sol(Address::ZERO, history, &lookups).unwrap();
+        (values._0.to(), values._1.to(), values._2.to())
+    }
+
+    #[test]
+    fn test_push() {
+        let (mut host, history, _) = setup();
+
+        assert_eq!(
+            push(&mut host, history, 10, 100),
+            (U256::ZERO, U256::from(100))
+        );
+        assert_eq!(
+            push(&mut host, history, 20, 200),
+            (U256::from(100), U256::from(200))
+        );
+        // A push at the last key updates the checkpoint
+        assert_eq!(
+            push(&mut host, history, 20, 250),
+            (U256::from(200), U256::from(250))
+        );
+
+        let length = host.call_sol(Address::ZERO, history, &lengthCall {});
+        assert_eq!(length.unwrap()._0, U256::from(2));
+        let latest = host
+            .call_sol(Address::ZERO, history, &latestCheckpointCall {})
+            .unwrap();
+        assert_eq!(
+            (latest._0, latest._1, latest._2),
+            (true, 20, U256::from(250))
+        );
+    }
+
+    #[test]
+    fn test_push_unordered() {
+        let (mut host, history, alice) = setup();
+        push(&mut host, history, 10, 100);
+
+        let push = pushCall {
+            key: 9,
+            value: U256::from(90),
+        };
+        let revert = host.call(alice, history, &push.encode()).unwrap_err();
+        let error = CheckpointsError::CheckpointUnorderedInsertion(CheckpointUnorderedInsertion {});
+        assert_eq!(revert, Vec::<u8>::from(error));
+    }
+
+    #[test]
+    fn test_empty() {
+        let (mut host, history, _) = setup();
+
+        assert_eq!(lookups(&mut host, history, 0), (0, 0, 0));
+        assert_eq!(lookups(&mut host, history, U48::MAX.to()), (0, 0, 0));
+        let latest = host
+            .call_sol(Address::ZERO, history, &latestCheckpointCall {})
+            .unwrap();
+        assert_eq!((latest._0, latest._1, latest._2), (false, 0, U256::ZERO));
+    }
+
+    #[test]
+    fn test_lookups() {
+        let (mut host, history, _) = setup();
+        // Checkpoints at keys 10, 20, ..., 100, which is enough for the recent lookup to skip
+        // the oldest ones
+        for i in 1..=10 {
+            push(&mut host, history, i * 10, i);
+        }
+
+        assert_eq!(lookups(&mut host, history, 5), (1, 0, 0));
+        assert_eq!(lookups(&mut host, history, 10), (1, 1, 1));
+        assert_eq!(lookups(&mut host, history, 15), (2, 1, 1));
+        assert_eq!(lookups(&mut host, history, 75), (8, 7, 7));
+        assert_eq!(lookups(&mut host, history, 90), (9, 9, 9));
+        assert_eq!(lookups(&mut host, history, 100), (10, 10, 10));
+        assert_eq!(lookups(&mut host, history, 101), (0, 10, 10));
+    }
+
+    #[test]
+    fn test_trace160() {
+        let (mut host, history, _) = setup();
+        let alice = TestHost::address("alice");
+
+        // The largest key and an address fit in a single checkpoint
+        let push = push160Call {
+            key: U96::MAX.to(),
+            value: alice,
+        };
+        let values = host.call_sol(Address::ZERO, history, &push).unwrap();
+
+        assert_eq!((values._0, values._1), (Address::ZERO, alice));
+        let latest = host.call_sol(Address::ZERO, history, &latest160Call {});
+        assert_eq!(latest.unwrap()._0, alice);
+    }
+}
@@ -0,0 +1,84 @@
+//! Deploys EIP-1167 minimal proxies ("clones") that delegate all calls to an implementation.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/proxy/Clones.sol
+//!
+//! Clones are far cheaper to deploy than redeploying the implementation's WASM, which makes them
+//! a good fit for factories that deploy many instances of the same contract. Each clone must be
+//! initialized after deployment, see [initialization](crate#initialization).
+
+use crate::inkmate_common::clones::CLONE_INIT_CODE_LEN;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::sol,
+    contract,
+    crypto::keccak,
+    deploy::RawDeploy,
+    prelude::*,
+};
+
+/// Import ClonesTrait and CreateTrait from inkmate_common publicly
+/// This permit projects to use it without importing `inkmate_common`
+pub use crate::inkmate_common::{clones::ClonesTrait, create::CreateTrait};
+
+pub struct Clones;
+
+impl CreateTrait for Clones {
+    /// Hashes `data` using the native keccak hostio
+    fn keccak256(data: &[u8]) -> [u8; 32] {
+        keccak(data).0
+    }
+}
+
+impl ClonesTrait for Clones {}
+
+sol! {
+    /// The deployment of the clone has failed (ex. a clone already exists for the salt).
+    error FailedDeployment();
+}
+
+#[derive(SolidityError)]
+pub enum ClonesError {
+    FailedDeployment(FailedDeployment),
+}
+
+/// Deploys a clone of `implementation` using CREATE and returns its address.
+pub fn clone(implementation: Address) -> Result<Address, ClonesError> {
+    deploy(implementation, None)
+}
+
+/// Deploys a clone of `implementation` using CREATE2 with `salt` and returns its address.
+/// Using the same `implementation` and `salt` multiple times will fail, since the clones would
+/// be deployed to the same address.
+pub fn clone_deterministic(implementation: Address, salt: B256) -> Result<Address, ClonesError> {
+    deploy(implementation, Some(salt))
+}
+
+/// Returns the address of the clone of `implementation` deployed by this contract with `salt`.
+pub fn predict_deterministic_address(implementation: Address, salt: B256) -> Address {
+    predict_deterministic_address_from(implementation, salt, contract::address())
+}
+
+/// Returns the address of the clone of `implementation` deployed by `deployer` with `salt`.
+pub fn predict_deterministic_address_from(
+    implementation: Address,
+    salt: B256,
+    deployer: Address,
+) -> Address {
+    Address::from(Clones::predict_deterministic_address(
+        implementation.0 .0,
+        salt.0,
+        deployer.0 .0,
+    ))
+}
+
+/// Deploys the minimal proxy init code for `implementation`, with CREATE2 if `salt` is set.
+fn deploy(implementation: Address, salt: Option<B256>) -> Result<Address, ClonesError> {
+    let code: [u8; CLONE_INIT_CODE_LEN] = Clones::clone_init_code(implementation.0 .0);
+    // SAFETY: the minimal proxy init code only returns the runtime code and makes no calls,
+    // so the deployment cannot reenter this contract
+    unsafe {
+        RawDeploy::new()
+            .salt_option(salt)
+            .deploy(&code, U256::ZERO)
+            .map_err(|_| ClonesError::FailedDeployment(FailedDeployment {}))
+    }
+}
@@ -0,0 +1,186 @@
+//! Per-account commitments to hidden values, revealed in a later L2 block before they expire.
+//! The logic was based off of: https://github.com/ensdomains/ens-contracts/blob/master/contracts/ethregistrar/ETHRegistrarController.sol
+//!
+//! Accounts first commit to the hash of a value they want to keep hidden (ex. a sealed bid, a move
+//! in a game, or the choice of a mint), then reveal the value and the salt in a later L2 block,
+//! within `REVEAL_PERIOD` seconds of the commitment. Nobody can front-run the value while it is
+//! hidden, and the account cannot change it once committed without making a new commitment.
+//!
+//! Commitments are `commitment_hash(account, data, salt)`, which binds them to the account so that
+//! they cannot be copied by others. The salt must be random and kept secret until the reveal, as
+//! values with few possibilities (ex. a bid amount) can otherwise be brute forced.
+//!
+//! The inheriting contract calls `_reveal` in its own reveal function with the decoded value, and
+//! only acts on the value if it succeeds. Each commitment can only be revealed once. The block of
+//! the commitment is the L2 block number from ArbSys, as `block::number` is shared by many L2
+//! blocks on Arbitrum chains.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U64},
+    alloy_sol_types::sol,
+    block,
+    crypto::keccak,
+    evm, msg,
+    prelude::*,
+};
+
+use crate::arbitrum::precompiles::arb_block_number;
+
+/// Immutable definitions of the commitments
+pub trait CommitRevealParams {
+    /// The number of seconds after a commitment during which it can be revealed.
+    const REVEAL_PERIOD: u64;
+}
+
+sol_storage! {
+    /// A commitment, its L2 block and its expiry
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct Commitment {
+        bytes32 hash;
+        uint64 block_number;
+        uint64 expiry;
+    }
+
+    /// CommitReveal stores the pending commitment of each account
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.CommitReveal"))]
+    pub struct CommitReveal<T> {
+        /// Maps account to its pending commitment
+        mapping(address => Commitment) commitments;
+        PhantomData<T> phantom;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when `account` commits to `commitment`, which can be revealed until `expiry`.
+    event Committed(address indexed account, bytes32 commitment, uint64 blockNumber, uint64 expiry);
+    /// Emitted when `account` reveals the value of `commitment`.
+    event Revealed(address indexed account, bytes32 commitment);
+
+    /// `account` has no pending commitment.
+    error NoCommitment(address account);
+    /// The commitment of `account` cannot be revealed in the L2 block `blockNumber` it was made in.
+    error RevealTooEarly(address account, uint64 blockNumber);
+    /// The commitment of `account` expired at `expiry`.
+    error CommitmentExpired(address account, uint64 expiry);
+    /// The revealed value and salt do not match the commitment of `account`.
+    error InvalidReveal(address account);
+}
+
+#[derive(SolidityError)]
+pub enum CommitRevealError {
+    NoCommitment(NoCommitment),
+    RevealTooEarly(RevealTooEarly),
+    CommitmentExpired(CommitmentExpired),
+    InvalidReveal(InvalidReveal),
+}
+
+/// Returns the commitment of `account` to `data`, hidden by `salt`. It is
+/// `keccak256(abi.encodePacked(account, data, salt))`.
+pub fn commitment_hash(account: Address, data: &[u8], salt: B256) -> B256 {
+    let mut preimage = Vec::with_capacity(20 + data.len() + 32);
+    preimage.extend_from_slice(account.as_slice());
+    preimage.extend_from_slice(data);
+    preimage.extend_from_slice(salt.as_slice());
+    keccak(preimage)
+}
+
+// Internal functions
+impl<T: CommitRevealParams> CommitReveal<T> {
+    /// Sets `commitment` as the pending commitment of `account`, replacing its previous one.
+    ///
+    /// Emits a {Committed} event.
+    pub fn _commit(&mut self, account: Address, commitment: B256) -> Result<(), Vec<u8>> {
+        let block_number = arb_block_number()?;
+        let expiry = block::timestamp() + T::REVEAL_PERIOD;
+
+        let mut pending = self.commitments.setter(account);
+        pending.hash.set(commitment);
+        pending.block_number.set(U64::from(block_number));
+        pending.expiry.set(U64::from(expiry));
+        evm::log(Committed {
+            account,
+            commitment: commitment.0,
+            blockNumber: block_number,
+            expiry,
+        });
+        Ok(())
+    }
+
+    /// Reveals that the pending commitment of `account` is to `data` hidden by `salt`, and clears
+    /// it so that it cannot be revealed again.
+    ///
+    /// Requirements:
+    /// - `account` must have a pending commitment.
+    /// - The commitment must have been made in a previous L2 block, and not have expired.
+    /// - The commitment must be `commitment_hash(account, data, salt)`.
+    ///
+    /// Emits a {Revealed} event.
+    pub fn _reveal(&mut self, account: Address, data: &[u8], salt: B256) -> Result<(), Vec<u8>> {
+        let pending = self.commitments.getter(account);
+        let commitment = pending.hash.get();
+        if commitment.is_zero() {
+            return Err(CommitRevealError::NoCommitment(NoCommitment { account }).into());
+        }
+        let block_number = pending.block_number.get().to::<u64>();
+        if arb_block_number()? <= block_number {
+            return Err(CommitRevealError::RevealTooEarly(RevealTooEarly {
+                account,
+                blockNumber: block_number,
+            })
+            .into());
+        }
+        let expiry = pending.expiry.get().to::<u64>();
+        if block::timestamp() > expiry {
+            return Err(CommitRevealError::CommitmentExpired(CommitmentExpired {
+                account,
+                expiry,
+            })
+            .into());
+        }
+        if commitment_hash(account, data, salt) != commitment {
+            return Err(CommitRevealError::InvalidReveal(InvalidReveal { account }).into());
+        }
+
+        self._clear(account);
+        evm::log(Revealed {
+            account,
+            commitment: commitment.0,
+        });
+        Ok(())
+    }
+
+    /// Clears the pending commitment of `account` (ex. once an auction has settled).
+    pub fn _clear(&mut self, account: Address) {
+        let mut pending = self.commitments.setter(account);
+        pending.hash.set(B256::ZERO);
+        pending.block_number.set(U64::ZERO);
+        pending.expiry.set(U64::ZERO);
+    }
+}
+
+// External functions
+#[external]
+impl<T: CommitRevealParams> CommitReveal<T> {
+    /// Commits the caller to `commitment`, which must be `commitment_hash(caller, data, salt)`.
+    /// Replaces the pending commitment of the caller, if any.
+    ///
+    /// Emits a {Committed} event.
+    pub fn commit(&mut self, commitment: B256) -> Result<(), Vec<u8>> {
+        self._commit(msg::sender(), commitment)
+    }
+
+    /// Returns the pending commitment of `account`, its L2 block and its expiry, or zeros if it
+    /// has none.
+    pub fn commitment_of(&self, account: Address) -> (B256, u64, u64) {
+        let pending = self.commitments.getter(account);
+        (
+            pending.hash.get(),
+            pending.block_number.get().to(),
+            pending.expiry.get().to(),
+        )
+    }
+}
@@ -0,0 +1,122 @@
+//! Deterministic contract deployment through CREATE2 and CREATE3.
+//! The logic was based off of: https://github.com/Vectorized/solady/blob/main/src/utils/CREATE3.sol
+//!
+//! CREATE2 addresses depend on the deployer, the salt and the init code. CREATE3 deploys a small
+//! proxy through CREATE2 which then deploys the init code through CREATE, so the address only
+//! depends on the deployer and the salt. This makes it possible to deploy contracts to the same
+//! address on every chain, even if their init code differs (ex. constructor arguments).
+
+use crate::inkmate_common::create::CREATE3_PROXY_INIT_CODE;
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolError},
+    call::RawCall,
+    contract,
+    crypto::keccak,
+    deploy::RawDeploy,
+    prelude::*,
+};
+
+/// Import CreateTrait from inkmate_common publicly
+/// This permit projects to use it without importing `inkmate_common`
+pub use crate::inkmate_common::create::CreateTrait;
+
+pub struct Create;
+
+impl CreateTrait for Create {
+    /// Hashes `data` using the native keccak hostio
+    fn keccak256(data: &[u8]) -> [u8; 32] {
+        keccak(data).0
+    }
+}
+
+sol! {
+    /// A contract already exists at the deployment address.
+    error DeploymentAddressTaken(address deployed);
+    /// The deployment has failed without revert data.
+    error FailedDeployment();
+}
+
+/// Deploys `init_code` through CREATE2 with `salt`, sending `value` to the new contract.
+/// Returns the deployed address.
+///
+/// On failure, the revert data of the init code is bubbled up if there is any. Otherwise, the
+/// ABI encoded {DeploymentAddressTaken} or {FailedDeployment} error is returned.
+pub fn deploy_create2(init_code: &[u8], salt: B256, value: U256) -> Result<Address, Vec<u8>> {
+    let predicted = predict_create2_address(keccak(init_code), salt);
+    if predicted.has_code() {
+        return Err(DeploymentAddressTaken {
+            deployed: predicted,
+        }
+        .encode());
+    }
+    // SAFETY: reentrancy is disabled, so calls made by the init code cannot alias storage
+    unsafe {
+        RawDeploy::new()
+            .salt(salt)
+            .deploy(init_code, value)
+            .map_err(revert_data)
+    }
+}
+
+/// Returns the address of a contract deployed by this contract through CREATE2 with `salt` and
+/// the init code hashing to `init_code_hash`.
+pub fn predict_create2_address(init_code_hash: B256, salt: B256) -> Address {
+    Address::from(Create::compute_create2_address(
+        contract::address().0 .0,
+        salt.0,
+        init_code_hash.0,
+    ))
+}
+
+/// Deploys `init_code` through CREATE3 with `salt`, sending `value` to the new contract.
+/// Returns the deployed address, which does not depend on `init_code`.
+///
+/// On failure, the revert data of the init code is bubbled up if there is any. Otherwise, the
+/// ABI encoded {DeploymentAddressTaken} or {FailedDeployment} error is returned.
+pub fn deploy_create3(init_code: &[u8], salt: B256, value: U256) -> Result<Address, Vec<u8>> {
+    // SAFETY: the proxy init code only returns the proxy runtime code and makes no calls
+    let proxy = unsafe {
+        RawDeploy::new()
+            .salt(salt)
+            .deploy(&CREATE3_PROXY_INIT_CODE, U256::ZERO)
+            .map_err(|_| {
+                DeploymentAddressTaken {
+                    deployed: predict_create3_address(salt),
+                }
+                .encode()
+            })?
+    };
+
+    // The proxy deploys its calldata as init code
+    RawCall::new_with_value(value)
+        .skip_return_data()
+        .call(proxy, init_code)
+        .map_err(revert_data)?;
+
+    let deployed = predict_create3_address(salt);
+    if !deployed.has_code() {
+        return Err(FailedDeployment {}.encode());
+    }
+    Ok(deployed)
+}
+
+/// Returns the address of a contract deployed by this contract through CREATE3 with `salt`.
+pub fn predict_create3_address(salt: B256) -> Address {
+    predict_create3_address_from(salt, contract::address())
+}
+
+/// Returns the address of a contract deployed by `deployer` through CREATE3 with `salt`.
+pub fn predict_create3_address_from(salt: B256, deployer: Address) -> Address {
+    Address::from(Create::compute_create3_address(deployer.0 .0, salt.0))
+}
+
+/// Returns `data` if it is non-empty, otherwise the ABI encoded {FailedDeployment} error.
+fn revert_data(data: Vec<u8>) -> Vec<u8> {
+    if data.is_empty() {
+        FailedDeployment {}.encode()
+    } else {
+        data
+    }
+}
@@ -0,0 +1,293 @@
+//! EIP-712 typed structured data hashing and signing helpers.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/utils/cryptography/EIP712.sol
+//! Doc comments are forked from: https://github.com/Vectorized/solady/blob/main/src/utils/EIP712.sol
+//!
+//! The domain separator is cached in storage along with the chain id and contract address it was
+//! computed for, and is only recomputed if either of them changes (ex. after a fork, or when called
+//! through a proxy). The name and version hashes are computed at compile time, although the name
+//! can be overridden at runtime with `_set_name` for contracts whose name is only known after
+//! deployment (ex. clones and proxies).
+//!
+//! With the `eip712-derive` feature, `#[derive(EIP712)]` implements `EIP712Struct` for a struct,
+//! generating its typehash and struct hash from its fields:
+//!
+//! ```ignore
+//! #[derive(EIP712)]
+//! struct MintVoucher {
+//!     token_id: U256,
+//!     uri: String,
+//!     recipient: Address,
+//! }
+//!
+//! // keccak256("MintVoucher(uint256 tokenId,string uri,address recipient)")
+//! let typehash = MintVoucher::TYPEHASH;
+//! let signed_hash = voucher.sign_hash(&mut self.eip712);
+//! ```
+//!
+//! Field names are converted to camel case, and their Solidity types are inferred from their Rust
+//! types: `Address`, `bool`, `String`, `Bytes` (as `bytes`), `FixedBytes<N>` and `B256`, `Uint` and
+//! `Signed` with their aliases (ex. `U256`), primitive integers, and `Vec<T>` and `[T; N]` arrays
+//! of these types (`Vec<u8>` is therefore `uint8[]`). `#[eip712(name = "...")]` renames the struct
+//! or a field, and `#[eip712(type = "...")]` sets the Solidity type of a field whose Rust type
+//! implements `EIP712Encode`. Nested structs are not supported, since their encoded type must be
+//! appended to the encoded type of the parent.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, FixedBytes, Signed, Uint, B256, U256, U64},
+    alloy_sol_types::{sol, SolType},
+    block, contract,
+    crypto::keccak,
+    keccak_const,
+    prelude::*,
+};
+
+pub const EIP_712_DOMAIN_HASH: B256 = crate::const_keccak!(
+    "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
+);
+
+#[cfg(feature = "eip712-derive")]
+pub use inkmate_macros::EIP712;
+
+/// Immutable EIP-712 domain definitions
+pub trait EIP712Params {
+    /// The user readable name of the signing domain.
+    const NAME: &'static str;
+    /// The current major version of the signing domain.
+    const VERSION: &'static str;
+}
+
+sol_storage! {
+    /// EIP712 caches the domain separator for the chain and contract address it was computed for
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct EIP712<T> {
+        bytes32 cached_domain_separator;
+        uint64 cached_chain_id;
+        address cached_this;
+        PhantomData<T> phantom;
+        /// keccak256 of the name set at runtime, if any
+        bytes32 hashed_name;
+    }
+}
+
+/// Returns the hash of the fully encoded EIP-712 message for `domain_separator` and
+/// `struct_hash`, i.e. `keccak256("\x19\x01" ‖ domain_separator ‖ struct_hash)`.
+pub fn to_typed_data_hash(domain_separator: B256, struct_hash: B256) -> B256 {
+    let mut digest = [0_u8; 66];
+    digest[..2].copy_from_slice(b"\x19\x01");
+    digest[2..34].copy_from_slice(&domain_separator.0);
+    digest[34..].copy_from_slice(&struct_hash.0);
+    keccak(digest)
+}
+
+/// Returns the EIP-712 struct hash of a struct with `typehash` and the encoded values `fields`,
+/// i.e. `keccak256(typehash ‖ fields[0] ‖ ... ‖ fields[n])`.
+pub fn hash_struct(typehash: B256, fields: &[B256]) -> B256 {
+    let mut encoded = Vec::with_capacity(32 * (fields.len() + 1));
+    encoded.extend_from_slice(typehash.as_slice());
+    for field in fields {
+        encoded.extend_from_slice(field.as_slice());
+    }
+    keccak(encoded)
+}
+
+/// A struct that can be hashed and signed as EIP-712 typed data, usually implemented with
+/// `#[derive(EIP712)]`
+pub trait EIP712Struct {
+    /// The encoded type of the struct, ex. `Mail(address from,address to,string contents)`.
+    const ENCODED_TYPE: &'static str;
+    /// The hash of the encoded type.
+    const TYPEHASH: B256;
+
+    /// Returns the EIP-712 struct hash of the value.
+    fn struct_hash(&self) -> B256;
+
+    /// Returns the hash of the fully encoded EIP-712 message of the value for `domain`, which is
+    /// the hash signed by the signer. The domain separator is cached if it is stale.
+    fn sign_hash<T: EIP712Params>(&self, domain: &mut EIP712<T>) -> B256 {
+        domain._hash_typed_data(self.struct_hash())
+    }
+}
+
+/// A value that can be encoded as a field of an EIP-712 struct
+pub trait EIP712Encode {
+    /// Returns the 32 bytes encoding of the value: atomic values are ABI encoded, while dynamic
+    /// values (`bytes`, `string` and arrays) are hashed.
+    fn encode_eip712(&self) -> B256;
+}
+
+impl EIP712Encode for Address {
+    fn encode_eip712(&self) -> B256 {
+        self.into_word()
+    }
+}
+
+impl EIP712Encode for bool {
+    fn encode_eip712(&self) -> B256 {
+        U256::from(*self as u8).into()
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> EIP712Encode for Uint<BITS, LIMBS> {
+    fn encode_eip712(&self) -> B256 {
+        U256::from_limbs_slice(self.as_limbs()).into()
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> EIP712Encode for Signed<BITS, LIMBS> {
+    fn encode_eip712(&self) -> B256 {
+        let raw = U256::from_limbs_slice(self.into_raw().as_limbs());
+        if self.is_negative() {
+            // Sign extend to 256 bits
+            B256::from(raw | (U256::MAX << BITS))
+        } else {
+            raw.into()
+        }
+    }
+}
+
+macro_rules! impl_encode_for_primitive {
+    ($($uint:ty),+; $($int:ty),+) => {
+        $(
+            impl EIP712Encode for $uint {
+                fn encode_eip712(&self) -> B256 {
+                    U256::from(*self).into()
+                }
+            }
+        )+
+        $(
+            impl EIP712Encode for $int {
+                fn encode_eip712(&self) -> B256 {
+                    let raw = U256::from(*self as i128 as u128);
+                    if *self < 0 {
+                        // Sign extend to 256 bits
+                        B256::from(raw | (U256::MAX << 128))
+                    } else {
+                        B256::from(raw)
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_encode_for_primitive!(u8, u16, u32, u64, u128; i8, i16, i32, i64, i128);
+
+impl<const N: usize> EIP712Encode for FixedBytes<N> {
+    fn encode_eip712(&self) -> B256 {
+        let mut word = B256::ZERO;
+        word[..N].copy_from_slice(self.as_slice());
+        word
+    }
+}
+
+impl EIP712Encode for alloc::string::String {
+    fn encode_eip712(&self) -> B256 {
+        keccak(self.as_bytes())
+    }
+}
+
+impl EIP712Encode for Bytes {
+    fn encode_eip712(&self) -> B256 {
+        keccak(&self.0)
+    }
+}
+
+impl<T: EIP712Encode> EIP712Encode for [T] {
+    fn encode_eip712(&self) -> B256 {
+        let words: Vec<B256> = self.iter().map(EIP712Encode::encode_eip712).collect();
+        keccak(words.concat())
+    }
+}
+
+impl<T: EIP712Encode> EIP712Encode for Vec<T> {
+    fn encode_eip712(&self) -> B256 {
+        self.as_slice().encode_eip712()
+    }
+}
+
+impl<T: EIP712Encode, const N: usize> EIP712Encode for [T; N] {
+    fn encode_eip712(&self) -> B256 {
+        self.as_slice().encode_eip712()
+    }
+}
+
+// Internal functions
+impl<T: EIP712Params> EIP712<T> {
+    // keccak256(NAME), computed at compile time
+    const NAME_HASH: [u8; 32] = keccak_const::Keccak256::new()
+        .update(T::NAME.as_bytes())
+        .finalize();
+
+    // keccak256(VERSION), computed at compile time
+    const VERSION_HASH: [u8; 32] = keccak_const::Keccak256::new()
+        .update(T::VERSION.as_bytes())
+        .finalize();
+
+    /// Overrides the name of the signing domain, and invalidates the cached domain separator.
+    pub fn _set_name(&mut self, name: &str) {
+        self.hashed_name.set(keccak(name.as_bytes()));
+        self.cached_this.set(Address::ZERO);
+    }
+
+    /// Returns the hash of the name of the signing domain.
+    pub fn _hashed_name(&self) -> B256 {
+        let hashed_name = self.hashed_name.get();
+        if hashed_name.is_zero() {
+            B256::new(Self::NAME_HASH)
+        } else {
+            hashed_name
+        }
+    }
+
+    /// Computes the domain separator for the current contract and chain.
+    pub fn _compute_domain_separator(&self) -> B256 {
+        keccak(
+            <sol! { (bytes32, bytes32, bytes32, uint256, address) }>::encode(&(
+                EIP_712_DOMAIN_HASH.0,
+                self._hashed_name().0,
+                Self::VERSION_HASH,
+                U256::from(block::chainid()),
+                contract::address(),
+            )),
+        )
+    }
+
+    /// Returns the EIP-712 domain separator, using the cached value if it is still valid.
+    pub fn _domain_separator(&self) -> B256 {
+        if self._is_domain_separator_cached() {
+            self.cached_domain_separator.get()
+        } else {
+            self._compute_domain_separator()
+        }
+    }
+
+    /// Caches the domain separator for the current contract and chain if it is stale,
+    /// and returns it.
+    pub fn _cache_domain_separator(&mut self) -> B256 {
+        if self._is_domain_separator_cached() {
+            return self.cached_domain_separator.get();
+        }
+        let domain_separator = self._compute_domain_separator();
+        self.cached_domain_separator.set(domain_separator);
+        self.cached_chain_id.set(U64::from(block::chainid()));
+        self.cached_this.set(contract::address());
+        domain_separator
+    }
+
+    /// Returns the hash of the fully encoded EIP-712 message for this domain,
+    /// given `struct_hash`. The domain separator is cached if it is stale.
+    ///
+    /// The hash can be used together with `ecrecover` to obtain the signer of a message.
+    pub fn _hash_typed_data(&mut self, struct_hash: B256) -> B256 {
+        to_typed_data_hash(self._cache_domain_separator(), struct_hash)
+    }
+
+    /// Returns whether the cached domain separator is valid for the current contract and chain.
+    fn _is_domain_separator_cached(&self) -> bool {
+        self.cached_chain_id.get() == U64::from(block::chainid())
+            && self.cached_this.get() == contract::address()
+    }
+}
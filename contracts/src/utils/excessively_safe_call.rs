@@ -0,0 +1,87 @@
+//! Low level calls with an explicit gas limit that bound the amount of return data copied.
+//! The logic was based off of: https://github.com/nomad-xyz/ExcessivelySafeCall/blob/main/src/ExcessivelySafeCall.sol
+//!
+//! A called contract can return or revert with an arbitrarily large amount of data, which the
+//! caller pays to copy into its memory. Untrusted contracts (ex. the receiver of a safe transfer or
+//! the borrower of a flash loan) can use this to make the caller run out of gas, which is known as
+//! a return bomb. Calls made with this library only copy the first `max_copy` bytes of the return
+//! data, so the cost of a call is bounded by the gas given to the callee.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    call::RawCall,
+};
+
+/// The gas limit that forwards all remaining gas, minus the 1/64th kept by the caller.
+pub const ALL_GAS: u64 = u64::MAX;
+
+/// The outcome of an excessively safe call, which holds at most `max_copy` bytes of return data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SafeCallResult {
+    /// The call succeeded with the returned data
+    Success(Vec<u8>),
+    /// The call reverted with the revert data
+    Failure(Vec<u8>),
+}
+
+impl SafeCallResult {
+    /// Returns true if the call succeeded.
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Success(_))
+    }
+
+    /// Returns the copied return data of the call, whether it succeeded or not.
+    pub fn data(&self) -> &[u8] {
+        match self {
+            Self::Success(data) | Self::Failure(data) => data,
+        }
+    }
+
+    /// Returns the copied return data as `Ok` if the call succeeded, or the revert data as `Err`.
+    pub fn into_result(self) -> Result<Vec<u8>, Vec<u8>> {
+        match self {
+            Self::Success(data) => Ok(data),
+            Self::Failure(data) => Err(data),
+        }
+    }
+}
+
+/// Calls `target` with `calldata` and `value` wei, giving it at most `gas` gas (or `ALL_GAS`),
+/// and copies at most `max_copy` bytes of the return data.
+pub fn excessively_safe_call(
+    target: Address,
+    gas: u64,
+    value: U256,
+    max_copy: usize,
+    calldata: &[u8],
+) -> SafeCallResult {
+    let result = RawCall::new_with_value(value)
+        .gas(gas)
+        .limit_return_data(0, max_copy)
+        .call(target, calldata);
+    to_safe_call_result(result)
+}
+
+/// Static calls `target` with `calldata`, giving it at most `gas` gas (or `ALL_GAS`), and copies at
+/// most `max_copy` bytes of the return data.
+pub fn excessively_safe_static_call(
+    target: Address,
+    gas: u64,
+    max_copy: usize,
+    calldata: &[u8],
+) -> SafeCallResult {
+    let result = RawCall::new_static()
+        .gas(gas)
+        .limit_return_data(0, max_copy)
+        .call(target, calldata);
+    to_safe_call_result(result)
+}
+
+/// Converts the result of a raw call into a `SafeCallResult`.
+fn to_safe_call_result(result: Result<Vec<u8>, Vec<u8>>) -> SafeCallResult {
+    match result {
+        Ok(data) => SafeCallResult::Success(data),
+        Err(data) => SafeCallResult::Failure(data),
+    }
+}
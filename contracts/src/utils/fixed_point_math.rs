@@ -0,0 +1,12 @@
+//! Arithmetic on 18 decimal fixed point numbers (WAD), full precision `mul_div`, integer roots,
+//! and the natural exponential and logarithm.
+//!
+//! The functions return `None` where Solady's FixedPointMathLib would revert, so contracts can map
+//! failures to their own Solidity errors.
+
+/// Import the fixed point math functions from inkmate_common publicly
+/// This permit projects to use it without importing `inkmate_common`
+pub use crate::inkmate_common::fixed_point_math::{
+    cbrt, div_wad, div_wad_up, exp_wad, full_mul_div, full_mul_div_up, ln_wad, mul_div, mul_div_up,
+    mul_wad, mul_wad_up, pow_wad, sqrt, WAD,
+};
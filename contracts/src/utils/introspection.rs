@@ -0,0 +1,88 @@
+//! ERC165 interface registration shared by the contracts of the library.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/utils/introspection/ERC165.sol
+//!
+//! Stylus routes `supportsInterface` to the first inherited contract that defines it, so a contract
+//! combining several bases (ex. `ERC721` with `ERC2981` and `ERC5192`) only reports the interfaces
+//! of one of them unless it defines its own `supports_interface`. Each base of the library
+//! implements `InterfaceIds` to register its interfaces, and wrapper extensions also include the
+//! interfaces of the type they wrap. The final contract lists what it inherits as a tuple:
+//!
+//! ```ignore
+//! pub fn supports_interface(interface: [u8; 4]) -> bool {
+//!     introspection::supports_interface::<(ERC5192<MyParams>, ERC2981)>(interface)
+//! }
+//! ```
+//!
+//! Contracts defining a new standard implement `InterfaceIds` for their storage type, and can then
+//! be listed next to the bases of the library. Their interface ids can be computed at compile time
+//! from the function signatures with `interface_id!`.
+
+use stylus_sdk::keccak_const::Keccak256;
+
+/// Computes the ERC165 interface id of the functions `signatures` at compile time, which is the
+/// XOR of their selectors. Signatures must be in the canonical form used for selectors, without
+/// spaces or argument names (ex. `"transferFrom(address,address,uint256)"`). A single signature
+/// gives the selector of the function.
+///
+/// ```ignore
+/// const IERC5192: u32 = interface_id!("locked(uint256)");
+/// const IERC2981: u32 = interface_id!("royaltyInfo(uint256,uint256)");
+/// ```
+#[macro_export]
+macro_rules! interface_id {
+    ($($signature:literal),+ $(,)?) => {
+        0u32 $(^ $crate::utils::introspection::selector($signature))+
+    };
+}
+
+/// Returns the selector of the function `signature`, which is the first 4 bytes of its keccak256
+/// hash. Can be evaluated at compile time.
+pub const fn selector(signature: &str) -> u32 {
+    let hash = Keccak256::new().update(signature.as_bytes()).finalize();
+    u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]])
+}
+
+/// The ERC165 interface id of ERC165.
+pub const IERC165: u32 = interface_id!("supportsInterface(bytes4)");
+
+/// The interface id that ERC165 requires to never be supported.
+pub const INVALID_INTERFACE_ID: u32 = 0xffffffff;
+
+/// The ERC165 interfaces implemented by a contract
+pub trait InterfaceIds {
+    /// Returns true if the contract implements the interface `id`. `IERC165` itself is added by
+    /// `supports_interface`, so implementations only need to match their own interfaces.
+    fn implements_interface(id: u32) -> bool;
+}
+
+/// Implements `InterfaceIds` for tuples, which implement the union of the interfaces of their
+/// elements.
+macro_rules! impl_interface_ids_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: InterfaceIds),+> InterfaceIds for ($($name,)+) {
+            fn implements_interface(id: u32) -> bool {
+                $($name::implements_interface(id))||+
+            }
+        }
+    };
+}
+
+impl_interface_ids_for_tuple!(A);
+impl_interface_ids_for_tuple!(A, B);
+impl_interface_ids_for_tuple!(A, B, C);
+impl_interface_ids_for_tuple!(A, B, C, D);
+impl_interface_ids_for_tuple!(A, B, C, D, E);
+impl_interface_ids_for_tuple!(A, B, C, D, E, F);
+impl_interface_ids_for_tuple!(A, B, C, D, E, F, G);
+impl_interface_ids_for_tuple!(A, B, C, D, E, F, G, H);
+
+/// Returns true if `T` implements the interface defined by `interface`, which is the value
+/// `supportsInterface` must return.
+/// See: https://eips.ethereum.org/EIPS/eip-165
+pub fn supports_interface<T: InterfaceIds>(interface: [u8; 4]) -> bool {
+    match u32::from_be_bytes(interface) {
+        INVALID_INTERFACE_ID => false,
+        IERC165 => true,
+        id => T::implements_interface(id),
+    }
+}
@@ -0,0 +1,17 @@
+//! Verifies merkle proofs using the VM's native keccak
+
+use crate::inkmate_common::crypto::merkle_proof::NODE_SIZE;
+use stylus_sdk::crypto::keccak;
+
+/// Import MerkleProofTrait from inkmate_common publicly
+/// This permit projects to use it without importing `inkmate_common`
+pub use crate::inkmate_common::crypto::merkle_proof::{MerkleProofError, MerkleProofTrait};
+
+pub struct MerkleProof;
+
+impl MerkleProofTrait for MerkleProof {
+    /// Hashes `data` using the native keccak hostio
+    fn keccak256(data: &[u8]) -> [u8; NODE_SIZE] {
+        keccak(data).0
+    }
+}
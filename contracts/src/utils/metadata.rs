@@ -0,0 +1,11 @@
+//! Builds ERC721 metadata JSON and base64 data URIs for fully on-chain tokens.
+//!
+//! A `tokenURI` returning on-chain metadata chains the fields of a `MetadataBuilder` (ex. `name`,
+//! `image` and `attribute`) and returns `build_data_uri`. Numeric attributes can be written with
+//! `Strings::to_decimal_string` from the strings utility.
+
+/// Import the metadata builder from inkmate_common publicly
+/// This permit projects to use it without importing `inkmate_common`
+pub use crate::inkmate_common::metadata::{
+    json_data_uri, push_json_string, MetadataBuilder, JSON_DATA_URI_PREFIX,
+};
@@ -1,4 +1,76 @@
 //! Various utilities used throughout the contracts
 
+#[cfg(feature = "address")]
+pub mod address;
+
+#[cfg(feature = "base64")]
+pub mod base64;
+
+#[cfg(feature = "bitmaps")]
+pub mod bitmaps;
+
+#[cfg(feature = "checkpoints")]
+pub mod checkpoints;
+
+#[cfg(feature = "commit-reveal")]
+pub mod commit_reveal;
+
+#[cfg(feature = "create")]
+pub mod create;
+
 #[cfg(any(feature = "erc20", feature = "ecrecover"))]
 pub mod ecrecover;
+
+#[cfg(feature = "clones")]
+pub mod clones;
+
+#[cfg(feature = "eip712")]
+pub mod eip712;
+
+#[cfg(feature = "excessively-safe-call")]
+pub mod excessively_safe_call;
+
+#[cfg(feature = "fixed-point-math")]
+pub mod fixed_point_math;
+
+#[cfg(feature = "introspection")]
+pub mod introspection;
+
+#[cfg(feature = "merkle-proof")]
+pub mod merkle_proof;
+
+#[cfg(feature = "metadata")]
+pub mod metadata;
+
+#[cfg(feature = "multicall")]
+pub mod multicall;
+
+#[cfg(feature = "nonces")]
+pub mod nonces;
+
+#[cfg(feature = "randomness")]
+pub mod randomness;
+
+#[cfg(feature = "safe-cast")]
+pub mod safe_cast;
+
+#[cfg(feature = "safe-transfer")]
+pub mod safe_transfer;
+
+#[cfg(feature = "signature-checker")]
+pub mod signature_checker;
+
+#[cfg(feature = "sstore2")]
+pub mod sstore2;
+
+#[cfg(feature = "strings")]
+pub mod strings;
+
+#[cfg(feature = "structs")]
+pub mod structs;
+
+#[cfg(feature = "svg")]
+pub mod svg;
+
+#[cfg(feature = "twap-oracle")]
+pub mod twap_oracle;
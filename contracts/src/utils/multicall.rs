@@ -0,0 +1,135 @@
+//! Multicallable mixin that batches multiple calls to the contract in a single transaction.
+//! The logic was based off of: https://github.com/Vectorized/solady/blob/main/src/utils/Multicallable.sol
+//!
+//! Solidity implementations delegatecall into `address(this)` for each call, but Stylus contracts
+//! revert on reentrant calls unless the `reentrant` feature of the SDK is enabled. Instead, each
+//! call is dispatched in-process through the router of the top-level contract `T`, which preserves
+//! `msg::sender` and shares storage the same way a delegatecall would. Every call runs on the
+//! storage of the `multicall` call itself, so later calls see the writes of earlier ones.
+//!
+//! Add `Multicall<Self>` to the contract and inherit it like the token bases:
+//!
+//! ```ignore
+//! sol_storage! {
+//!     #[entrypoint]
+//!     struct MyToken {
+//!         #[borrow]
+//!         ERC20<MyParams> erc20;
+//!         #[borrow]
+//!         Multicall<MyToken> multicall;
+//!     }
+//! }
+//!
+//! #[external]
+//! #[inherit(ERC20<MyParams>, Multicall<MyToken>)]
+//! impl MyToken {}
+//! ```
+//!
+//! `multicall` is not payable, so that `msg::value` can't be counted more than once by payable
+//! methods in the batch. It returns the ABI encoded return data of each call, in order.
+//!
+//! `cargo stylus export-abi` can't export methods returning `bytes`, so `Multicall` implements its
+//! router and ABI by hand, like `ERC2771Context`.
+
+use alloc::vec::Vec;
+use core::{borrow::BorrowMut, marker::PhantomData};
+use stylus_sdk::{
+    abi::{internal, internal::EncodableReturnType, AbiType, Bytes, Router},
+    alloy_sol_types::SolType,
+    function_selector,
+    prelude::*,
+    storage::TopLevelStorage,
+    ArbResult,
+};
+
+sol_storage! {
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.Multicall"))]
+    pub struct Multicall<T> {
+        PhantomData<T> phantom;
+    }
+}
+
+// External functions, routed by the `Router` implementation below
+impl<T> Multicall<T>
+where
+    T: TopLevelStorage + Router<T, Storage = T>,
+{
+    /// Executes each of the encoded calls in `data` against the contract `storage`, in order, and
+    /// returns their results.
+    ///
+    /// Requirements:
+    /// - Each call must succeed. The revert data of the first failed call is bubbled up.
+    pub fn multicall<S: BorrowMut<T>>(
+        storage: &mut S,
+        data: Vec<Bytes>,
+    ) -> Result<Vec<Bytes>, Vec<u8>> {
+        let storage = storage.borrow_mut();
+        data.iter()
+            .map(|call| Self::_dispatch(storage, call).map(Bytes))
+            .collect()
+    }
+}
+
+// Internal functions
+impl<T> Multicall<T>
+where
+    T: TopLevelStorage + Router<T, Storage = T>,
+{
+    /// Routes the encoded `call` to the method of `storage` matching its selector.
+    /// Calls that are too short or have an unknown selector revert without data, like the entrypoint.
+    fn _dispatch(storage: &mut T, call: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+        if call.len() < 4 {
+            return Err(Vec::new());
+        }
+        let selector = u32::from_be_bytes([call[0], call[1], call[2], call[3]]);
+        T::route(storage, selector, &call[4..]).unwrap_or_else(|| Err(Vec::new()))
+    }
+}
+
+const SELECTOR_MULTICALL: u32 = u32::from_be_bytes(function_selector!("multicall", Vec<Bytes>));
+
+impl<S, T> Router<S> for Multicall<T>
+where
+    S: TopLevelStorage + BorrowMut<Self> + BorrowMut<T>,
+    T: TopLevelStorage + Router<T, Storage = T>,
+{
+    type Storage = Self;
+
+    fn route(storage: &mut S, selector: u32, input: &[u8]) -> Option<ArbResult> {
+        match selector {
+            SELECTOR_MULTICALL => {
+                if let Err(err) = internal::deny_value("multicall") {
+                    return Some(Err(err));
+                }
+                let args =
+                    match <<(Vec<Bytes>,) as AbiType>::SolType as SolType>::decode(input, true) {
+                        Ok(args) => args,
+                        Err(err) => {
+                            internal::failed_to_decode_arguments(err);
+                            return Some(Err(Vec::new()));
+                        }
+                    };
+                Some(EncodableReturnType::encode(Self::multicall(
+                    storage, args.0,
+                )))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "export-abi")]
+impl<T> stylus_sdk::abi::GenerateAbi for Multicall<T> {
+    const NAME: &'static str = "Multicall";
+
+    fn fmt_abi(f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "interface IMulticall {{")?;
+        write!(
+            f,
+            "\n    function multicall(bytes[] memory data) external returns (bytes[] memory);"
+        )?;
+        writeln!(f, "\n}}")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+//! Sequential and unordered nonces for signed messages (ex. permits, vouchers, orders and
+//! meta-transactions).
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/utils/Nonces.sol
+//! Unordered nonces are based off of: https://github.com/Uniswap/permit2/blob/main/src/SignatureTransfer.sol
+//!
+//! Sequential nonces must be consumed in order, which is what EIP-2612 permits and vote delegations
+//! expect. Unordered nonces can be consumed in any order, so that many signatures can be
+//! outstanding at once. They are stored in bitmaps of 256 nonces per word, and a whole word of
+//! nonces can be cancelled at once with `invalidate_unordered_nonces`.
+
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    evm, msg,
+    prelude::*,
+};
+
+sol_storage! {
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct Nonces {
+        /// The next sequential nonce of every account
+        mapping(address => uint256) nonces;
+        /// Maps account to word position to 256 packed used unordered nonces
+        mapping(address => mapping(uint256 => uint256)) nonce_bitmap;
+    }
+}
+
+sol! {
+    /// Emitted when `owner` invalidates the unordered nonces set in `mask` of the word at `word`.
+    event UnorderedNonceInvalidation(address indexed owner, uint256 word, uint256 mask);
+
+    /// The nonce used for an `account` is not the expected current nonce.
+    error InvalidAccountNonce(address account, uint256 currentNonce);
+    /// The unordered `nonce` of `account` has already been used or invalidated.
+    error InvalidUnorderedNonce(address account, uint256 nonce);
+}
+
+#[derive(SolidityError)]
+pub enum NoncesError {
+    InvalidAccountNonce(InvalidAccountNonce),
+    InvalidUnorderedNonce(InvalidUnorderedNonce),
+}
+
+// Internal functions
+impl Nonces {
+    /// Consumes the current sequential nonce of `owner`, and returns it.
+    pub fn _use_nonce(&mut self, owner: Address) -> U256 {
+        let mut slot = self.nonces.setter(owner);
+        let nonce = slot.get();
+        // The nonce cannot realistically overflow, as it is incremented by one per signature
+        slot.set(nonce.wrapping_add(U256::from(1)));
+        nonce
+    }
+
+    /// Consumes the sequential nonce of `owner`, which must be `nonce`.
+    ///
+    /// Requirements:
+    /// - `nonce` must be the current nonce of `owner`.
+    pub fn _use_checked_nonce(&mut self, owner: Address, nonce: U256) -> Result<(), NoncesError> {
+        let current = self._use_nonce(owner);
+        if nonce != current {
+            return Err(NoncesError::InvalidAccountNonce(InvalidAccountNonce {
+                account: owner,
+                currentNonce: current,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Returns the word position and the mask of the bit of the unordered `nonce`.
+    /// The first 248 bits of the nonce are the word position, and the last 8 bits are the bit.
+    fn _bitmap_position(nonce: U256) -> (U256, U256) {
+        let bit = (nonce & U256::from(0xff)).to::<usize>();
+        (nonce >> 8, U256::from(1) << bit)
+    }
+
+    /// Returns whether the unordered `nonce` of `owner` has been used or invalidated.
+    pub fn _is_unordered_nonce_used(&self, owner: Address, nonce: U256) -> bool {
+        let (word, mask) = Self::_bitmap_position(nonce);
+        self.nonce_bitmap.getter(owner).get(word) & mask != U256::ZERO
+    }
+
+    /// Consumes the unordered `nonce` of `owner`.
+    ///
+    /// Requirements:
+    /// - `nonce` must not have been used or invalidated.
+    pub fn _use_unordered_nonce(&mut self, owner: Address, nonce: U256) -> Result<(), NoncesError> {
+        let (word, mask) = Self::_bitmap_position(nonce);
+        let mut bitmaps = self.nonce_bitmap.setter(owner);
+        let mut slot = bitmaps.setter(word);
+        let bits = slot.get();
+        if bits & mask != U256::ZERO {
+            return Err(NoncesError::InvalidUnorderedNonce(InvalidUnorderedNonce {
+                account: owner,
+                nonce,
+            }));
+        }
+        slot.set(bits | mask);
+        Ok(())
+    }
+
+    /// Invalidates the unordered nonces of `owner` set in `mask`, in the word at `word_pos`.
+    ///
+    /// Emits a {UnorderedNonceInvalidation} event.
+    pub fn _invalidate_unordered_nonces(&mut self, owner: Address, word_pos: U256, mask: U256) {
+        let mut bitmaps = self.nonce_bitmap.setter(owner);
+        let mut slot = bitmaps.setter(word_pos);
+        let bits = slot.get();
+        slot.set(bits | mask);
+        evm::log(UnorderedNonceInvalidation {
+            owner,
+            word: word_pos,
+            mask,
+        });
+    }
+}
+
+// External functions
+#[external]
+impl Nonces {
+    /// Returns the next sequential nonce of `owner`.
+    pub fn nonces(&self, owner: Address) -> U256 {
+        self.nonces.get(owner)
+    }
+
+    /// Returns the 256 packed used unordered nonces of `owner` in the word at `word_pos`, which
+    /// holds the nonces `word_pos * 256` to `word_pos * 256 + 255`.
+    pub fn nonce_bitmap(&self, owner: Address, word_pos: U256) -> U256 {
+        self.nonce_bitmap.getter(owner).get(word_pos)
+    }
+
+    /// Invalidates the unordered nonces of the caller set in `mask`, in the word at `word_pos`.
+    ///
+    /// Emits a {UnorderedNonceInvalidation} event.
+    pub fn invalidate_unordered_nonces(&mut self, word_pos: U256, mask: U256) {
+        self._invalidate_unordered_nonces(msg::sender(), word_pos, mask);
+    }
+}
@@ -0,0 +1,296 @@
+//! Sources of randomness behind a common `RandomnessProvider` trait, so that reveals, raffles and
+//! games can swap the source without changing their logic.
+//! The logic was based off of: https://github.com/smartcontractkit/chainlink/blob/develop/contracts/src/v0.8/vrf/dev/VRFConsumerBaseV2Plus.sol
+//!
+//! Randomness is requested in one transaction and read in a later one: `request_randomness`
+//! returns the id of the request, and `randomness` returns its seed once it is available. Several
+//! random values can be drawn from a seed with `random_word`.
+//!
+//! `ChainlinkVRF` requests the seed from a Chainlink VRF v2.5 coordinator, which delivers it by
+//! calling `rawFulfillRandomWords` with a proof verified on-chain. The contract must be added as a
+//! consumer of the subscription, and must inherit `ChainlinkVRF<T>` so that the coordinator can
+//! call it.
+//!
+//! `BlockHashRandomness` derives the seed from the hash of the L2 block following the request.
+//! It is free and synchronous, but it is NOT safe for anything of value: the sequencer orders the
+//! transactions of the block and can predict or influence its hash. `prevrandao` is not an
+//! alternative, as it is a constant on Arbitrum chains. Only use it for cosmetic randomness, or in
+//! tests.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256, U64},
+    alloy_sol_types::{sol, SolCall, SolType},
+    call::RawCall,
+    crypto::keccak,
+    evm, msg,
+    prelude::*,
+};
+
+use crate::arbitrum::precompiles::{arb_block_hash, arb_block_number};
+
+/// The number of recent L2 blocks whose hash can be read.
+pub const BLOCK_HASH_WINDOW: u64 = 256;
+
+/// The tag of the v1 extra arguments of VRF v2.5 requests, `bytes4(keccak256("VRF ExtraArgsV1"))`.
+const EXTRA_ARGS_V1_TAG: [u8; 4] = [0x92, 0xfd, 0x13, 0x38];
+
+/// A source of random seeds, requested in one transaction and read in a later one.
+pub trait RandomnessProvider {
+    /// Requests a random seed, and returns the id of the request.
+    fn request_randomness(&mut self) -> Result<U256, Vec<u8>>;
+
+    /// Returns the random seed of the request `request_id`, or `None` if it is not available yet.
+    fn randomness(&self, request_id: U256) -> Result<Option<B256>, Vec<u8>>;
+}
+
+/// Returns the `index`-th random word drawn from `seed`. A value in `0..n` can be drawn with
+/// `random_word(seed, index) % n`, whose bias is negligible for `n` much smaller than 2^256.
+pub fn random_word(seed: B256, index: u64) -> U256 {
+    U256::from_be_bytes(
+        keccak(<sol! { (bytes32, uint256) }>::encode(&(
+            seed.0,
+            U256::from(index),
+        )))
+        .0,
+    )
+}
+
+// Define events, errors and functions of the coordinator
+sol! {
+    /// Emitted when the random seed of `requestId` is requested.
+    event RandomnessRequested(uint256 indexed requestId);
+    /// Emitted when the random seed of `requestId` is delivered.
+    event RandomnessFulfilled(uint256 indexed requestId, bytes32 seed);
+
+    /// The `coordinator` returned no valid request id.
+    error RandomnessRequestFailed(address coordinator);
+    /// The request `requestId` does not exist.
+    error UnknownRandomnessRequest(uint256 requestId);
+    /// The request `requestId` can no longer be fulfilled, and must be made again.
+    error RandomnessExpired(uint256 requestId);
+    /// The caller `have` is not the VRF coordinator `want`.
+    error OnlyCoordinatorCanFulfill(address have, address want);
+    /// The random seed of `requestId` has already been delivered.
+    error RandomnessAlreadyFulfilled(uint256 requestId);
+
+    struct RandomWordsRequest {
+        bytes32 keyHash;
+        uint256 subId;
+        uint16 requestConfirmations;
+        uint32 callbackGasLimit;
+        uint32 numWords;
+        bytes extraArgs;
+    }
+
+    function requestRandomWords(RandomWordsRequest req) external returns (uint256 requestId);
+}
+
+#[derive(SolidityError)]
+pub enum RandomnessError {
+    RandomnessRequestFailed(RandomnessRequestFailed),
+    UnknownRandomnessRequest(UnknownRandomnessRequest),
+    RandomnessExpired(RandomnessExpired),
+    OnlyCoordinatorCanFulfill(OnlyCoordinatorCanFulfill),
+    RandomnessAlreadyFulfilled(RandomnessAlreadyFulfilled),
+}
+
+/// Immutable definitions of the VRF requests
+pub trait ChainlinkVRFParams {
+    /// The VRF v2.5 coordinator of the chain.
+    const COORDINATOR: Address;
+    /// The key hash of the gas lane, which sets the maximum gas price of the fulfillments.
+    const KEY_HASH: B256;
+    /// The id of the subscription paying for the requests.
+    const SUBSCRIPTION_ID: U256;
+    /// The number of confirmations the coordinator waits for before fulfilling requests.
+    const REQUEST_CONFIRMATIONS: u16;
+    /// The gas limit of the `rawFulfillRandomWords` call.
+    const CALLBACK_GAS_LIMIT: u32;
+    /// Whether the requests are paid in the native token instead of LINK.
+    const NATIVE_PAYMENT: bool;
+}
+
+sol_storage! {
+    /// ChainlinkVRF requests random seeds from a Chainlink VRF v2.5 coordinator
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ChainlinkVRF"))]
+    pub struct ChainlinkVRF<T> {
+        /// Whether each request has been made by the contract
+        mapping(uint256 => bool) requested;
+        /// The seed of each fulfilled request
+        mapping(uint256 => bytes32) seeds;
+        PhantomData<T> phantom;
+    }
+}
+
+impl<T: ChainlinkVRFParams> RandomnessProvider for ChainlinkVRF<T> {
+    /// Requests a random seed from the coordinator. Reverts with the error of the coordinator if
+    /// the request fails (ex. the subscription is not funded).
+    ///
+    /// Emits a {RandomnessRequested} event.
+    fn request_randomness(&mut self) -> Result<U256, Vec<u8>> {
+        let mut extra_args = EXTRA_ARGS_V1_TAG.to_vec();
+        extra_args.extend(U256::from(T::NATIVE_PAYMENT as u8).to_be_bytes::<32>());
+        let call = requestRandomWordsCall {
+            req: RandomWordsRequest {
+                keyHash: T::KEY_HASH.0,
+                subId: T::SUBSCRIPTION_ID,
+                requestConfirmations: T::REQUEST_CONFIRMATIONS,
+                callbackGasLimit: T::CALLBACK_GAS_LIMIT,
+                numWords: 1,
+                extraArgs: extra_args,
+            },
+        };
+        let data = RawCall::new().call(T::COORDINATOR, &call.encode())?;
+        let request_id = requestRandomWordsCall::decode_returns(&data, true)
+            .map_err(|_| {
+                RandomnessError::RandomnessRequestFailed(RandomnessRequestFailed {
+                    coordinator: T::COORDINATOR,
+                })
+            })?
+            .requestId;
+
+        self.requested.insert(request_id, true);
+        evm::log(RandomnessRequested {
+            requestId: request_id,
+        });
+        Ok(request_id)
+    }
+
+    fn randomness(&self, request_id: U256) -> Result<Option<B256>, Vec<u8>> {
+        if !self.requested.get(request_id) {
+            return Err(
+                RandomnessError::UnknownRandomnessRequest(UnknownRandomnessRequest {
+                    requestId: request_id,
+                })
+                .into(),
+            );
+        }
+        let seed = self.seeds.get(request_id);
+        Ok((!seed.is_zero()).then_some(seed))
+    }
+}
+
+// External functions
+#[external]
+impl<T: ChainlinkVRFParams> ChainlinkVRF<T> {
+    /// Delivers the `random_words` of the request `request_id`, whose hash becomes its seed.
+    ///
+    /// Requirements:
+    /// - The caller must be the coordinator.
+    /// - The request must have been made by the contract, and not fulfilled yet.
+    ///
+    /// Emits a {RandomnessFulfilled} event.
+    #[selector(name = "rawFulfillRandomWords")]
+    pub fn raw_fulfill_random_words(
+        &mut self,
+        request_id: U256,
+        random_words: Vec<U256>,
+    ) -> Result<(), RandomnessError> {
+        if msg::sender() != T::COORDINATOR {
+            return Err(RandomnessError::OnlyCoordinatorCanFulfill(
+                OnlyCoordinatorCanFulfill {
+                    have: msg::sender(),
+                    want: T::COORDINATOR,
+                },
+            ));
+        }
+        if !self.requested.get(request_id) {
+            return Err(RandomnessError::UnknownRandomnessRequest(
+                UnknownRandomnessRequest {
+                    requestId: request_id,
+                },
+            ));
+        }
+        if !self.seeds.get(request_id).is_zero() {
+            return Err(RandomnessError::RandomnessAlreadyFulfilled(
+                RandomnessAlreadyFulfilled {
+                    requestId: request_id,
+                },
+            ));
+        }
+
+        let words: Vec<u8> = random_words
+            .iter()
+            .flat_map(|word| word.to_be_bytes::<32>())
+            .collect();
+        let seed = keccak(words);
+        self.seeds.insert(request_id, seed);
+        evm::log(RandomnessFulfilled {
+            requestId: request_id,
+            seed: seed.0,
+        });
+        Ok(())
+    }
+}
+
+sol_storage! {
+    /// BlockHashRandomness derives random seeds from L2 block hashes, NOT safe for value
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.BlockHashRandomness"))]
+    pub struct BlockHashRandomness {
+        /// The number of requests made
+        uint256 request_count;
+        /// The L2 block whose hash is the seed of each request
+        mapping(uint256 => uint64) target_blocks;
+    }
+}
+
+impl RandomnessProvider for BlockHashRandomness {
+    /// Requests the hash of the next L2 block as a random seed. The sequencer can predict or
+    /// influence it, so the seed must not decide anything of value.
+    ///
+    /// Emits a {RandomnessRequested} event.
+    fn request_randomness(&mut self) -> Result<U256, Vec<u8>> {
+        let request_id = self.request_count.get() + U256::from(1);
+        self.request_count.set(request_id);
+        let target = arb_block_number()? + 1;
+        self.target_blocks.insert(request_id, U64::from(target));
+
+        evm::log(RandomnessRequested {
+            requestId: request_id,
+        });
+        Ok(request_id)
+    }
+
+    /// Returns the seed of `request_id` once its L2 block has been mined, or a {RandomnessExpired}
+    /// error if the block is no longer one of the 256 most recent blocks.
+    fn randomness(&self, request_id: U256) -> Result<Option<B256>, Vec<u8>> {
+        let target: u64 = self.target_blocks.get(request_id).to();
+        if target == 0 {
+            return Err(
+                RandomnessError::UnknownRandomnessRequest(UnknownRandomnessRequest {
+                    requestId: request_id,
+                })
+                .into(),
+            );
+        }
+        let current = arb_block_number()?;
+        if current <= target {
+            return Ok(None);
+        }
+        if current - target > BLOCK_HASH_WINDOW {
+            return Err(RandomnessError::RandomnessExpired(RandomnessExpired {
+                requestId: request_id,
+            })
+            .into());
+        }
+        let hash = arb_block_hash(target)?;
+        Ok(Some(keccak(<sol! { (bytes32, uint256) }>::encode(&(
+            hash.0, request_id,
+        )))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extra_args_tag() {
+        let tag = crate::const_keccak!("VRF ExtraArgsV1");
+        assert_eq!(tag[..4], EXTRA_ARGS_V1_TAG);
+    }
+}
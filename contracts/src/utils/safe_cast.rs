@@ -0,0 +1,234 @@
+//! Downcasts between integer types that return Solidity errors instead of panicking or truncating.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/utils/math/SafeCast.sol
+//!
+//! Converting with `From` (ex. `U64::from(value)`) or `to` panics when the value does not fit,
+//! which reverts without any revert data, while `wrapping_to` silently truncates it. The functions
+//! of this library return a `SafeCastError` describing the failed cast, which callers can
+//! propagate with `?`.
+
+use core::mem::size_of;
+use stylus_sdk::{
+    alloy_primitives::{Uint, I256, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+};
+
+sol! {
+    /// The value doesn't fit in an uint of `bits` size.
+    error SafeCastOverflowedUintDowncast(uint8 bits, uint256 value);
+    /// An int value doesn't fit in an uint of `bits` size.
+    error SafeCastOverflowedIntToUint(int256 value);
+    /// The value doesn't fit in an int of `bits` size.
+    error SafeCastOverflowedIntDowncast(uint8 bits, int256 value);
+    /// An uint value doesn't fit in an int of `bits` size.
+    error SafeCastOverflowedUintToInt(uint256 value);
+}
+
+#[derive(SolidityError)]
+pub enum SafeCastError {
+    SafeCastOverflowedUintDowncast(SafeCastOverflowedUintDowncast),
+    SafeCastOverflowedIntToUint(SafeCastOverflowedIntToUint),
+    SafeCastOverflowedIntDowncast(SafeCastOverflowedIntDowncast),
+    SafeCastOverflowedUintToInt(SafeCastOverflowedUintToInt),
+}
+
+/// Returns the error of a failed downcast of `value` to an uint of `bits` size.
+fn uint_downcast_error(bits: usize, value: U256) -> SafeCastError {
+    SafeCastError::SafeCastOverflowedUintDowncast(SafeCastOverflowedUintDowncast {
+        bits: bits as u8,
+        value,
+    })
+}
+
+/// Returns `value` as an uint of `BITS` size (ex. `U48` or `U208`).
+///
+/// Requirements:
+/// - `value` must fit into `BITS` bits.
+pub fn to_uint<const BITS: usize, const LIMBS: usize>(
+    value: U256,
+) -> Result<Uint<BITS, LIMBS>, SafeCastError> {
+    Uint::<BITS, LIMBS>::checked_from_limbs_slice(value.as_limbs())
+        .ok_or_else(|| uint_downcast_error(BITS, value))
+}
+
+/// Returns `value` as a primitive unsigned integer (ex. `u64`).
+///
+/// Requirements:
+/// - `value` must fit into the primitive type.
+pub fn to_primitive<T: TryFrom<U256>>(value: U256) -> Result<T, SafeCastError> {
+    T::try_from(value).map_err(|_| uint_downcast_error(size_of::<T>() * 8, value))
+}
+
+/// Returns `value` as an `u128`.
+///
+/// Requirements:
+/// - `value` must fit into 128 bits.
+pub fn to_u128(value: U256) -> Result<u128, SafeCastError> {
+    to_primitive(value)
+}
+
+/// Returns `value` as an `u64`.
+///
+/// Requirements:
+/// - `value` must fit into 64 bits.
+pub fn to_u64(value: U256) -> Result<u64, SafeCastError> {
+    to_primitive(value)
+}
+
+/// Returns `value` as an `u32`.
+///
+/// Requirements:
+/// - `value` must fit into 32 bits.
+pub fn to_u32(value: U256) -> Result<u32, SafeCastError> {
+    to_primitive(value)
+}
+
+/// Returns `value` as an `u16`.
+///
+/// Requirements:
+/// - `value` must fit into 16 bits.
+pub fn to_u16(value: U256) -> Result<u16, SafeCastError> {
+    to_primitive(value)
+}
+
+/// Returns `value` as an `u8`.
+///
+/// Requirements:
+/// - `value` must fit into 8 bits.
+pub fn to_u8(value: U256) -> Result<u8, SafeCastError> {
+    to_primitive(value)
+}
+
+/// Returns `value` as an `usize`, ex. to index into a vector.
+///
+/// Requirements:
+/// - `value` must fit into the pointer width (32 bits on WASM).
+pub fn to_usize(value: U256) -> Result<usize, SafeCastError> {
+    to_primitive(value)
+}
+
+/// Returns the signed `value` as an `i64`.
+///
+/// Requirements:
+/// - `value` must fit into 64 bits.
+pub fn to_i64(value: I256) -> Result<i64, SafeCastError> {
+    i64::try_from(value).map_err(|_| {
+        SafeCastError::SafeCastOverflowedIntDowncast(SafeCastOverflowedIntDowncast {
+            bits: 64,
+            value,
+        })
+    })
+}
+
+/// Returns the signed `value` as an unsigned integer.
+///
+/// Requirements:
+/// - `value` must be non-negative.
+pub fn to_uint256(value: I256) -> Result<U256, SafeCastError> {
+    if value.is_negative() {
+        return Err(SafeCastError::SafeCastOverflowedIntToUint(
+            SafeCastOverflowedIntToUint { value },
+        ));
+    }
+    Ok(value.into_raw())
+}
+
+/// Returns the unsigned `value` as a signed integer.
+///
+/// Requirements:
+/// - `value` must fit into an `int256`, i.e. be at most `I256::MAX`.
+pub fn to_int256(value: U256) -> Result<I256, SafeCastError> {
+    let result = I256::from_raw(value);
+    if result.is_negative() {
+        return Err(SafeCastError::SafeCastOverflowedUintToInt(
+            SafeCastOverflowedUintToInt { value },
+        ));
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    type U48 = Uint<48, 1>;
+
+    /// Returns the revert data of a failed cast, to compare errors
+    fn revert<T: core::fmt::Debug>(result: Result<T, SafeCastError>) -> Vec<u8> {
+        match result {
+            Ok(value) => panic!("the cast succeeded with {value:?}"),
+            Err(error) => error.into(),
+        }
+    }
+
+    #[test]
+    fn test_to_uint() {
+        let max = U256::from(U48::MAX);
+        assert_eq!(to_uint::<48, 1>(max).ok(), Some(U48::MAX));
+        assert_eq!(to_uint::<48, 1>(U256::ZERO).ok(), Some(U48::ZERO));
+        assert_eq!(
+            revert(to_uint::<48, 1>(max + U256::from(1))),
+            Vec::<u8>::from(uint_downcast_error(48, max + U256::from(1)))
+        );
+        // The high limbs of the value are checked, not only the bits of the first one
+        let high = U256::from(1) << 128;
+        assert_eq!(
+            revert(to_uint::<48, 1>(high)),
+            Vec::<u8>::from(uint_downcast_error(48, high))
+        );
+    }
+
+    #[test]
+    fn test_to_primitive() {
+        assert_eq!(to_u64(U256::from(u64::MAX)).ok(), Some(u64::MAX));
+        assert_eq!(to_u128(U256::from(u128::MAX)).ok(), Some(u128::MAX));
+        assert_eq!(to_u8(U256::from(255)).ok(), Some(255));
+        assert_eq!(
+            revert(to_u8(U256::from(256))),
+            Vec::<u8>::from(uint_downcast_error(8, U256::from(256)))
+        );
+        assert_eq!(
+            revert(to_u32(U256::MAX)),
+            Vec::<u8>::from(uint_downcast_error(32, U256::MAX))
+        );
+        // The error reports the pointer width of the target
+        let value = U256::from(usize::MAX) + U256::from(1);
+        assert_eq!(
+            revert(to_usize(value)),
+            Vec::<u8>::from(uint_downcast_error(usize::BITS as usize, value))
+        );
+    }
+
+    #[test]
+    fn test_to_i64() {
+        let min = I256::try_from(i64::MIN).unwrap();
+        assert_eq!(to_i64(min).ok(), Some(i64::MIN));
+        let value = min - I256::ONE;
+        let error = SafeCastError::SafeCastOverflowedIntDowncast(SafeCastOverflowedIntDowncast {
+            bits: 64,
+            value,
+        });
+        assert_eq!(revert(to_i64(value)), Vec::<u8>::from(error));
+    }
+
+    #[test]
+    fn test_signed_conversions() {
+        assert_eq!(to_uint256(I256::MAX).ok(), Some(I256::MAX.into_raw()));
+        assert_eq!(to_uint256(I256::ZERO).ok(), Some(U256::ZERO));
+        let error = SafeCastError::SafeCastOverflowedIntToUint(SafeCastOverflowedIntToUint {
+            value: I256::MINUS_ONE,
+        });
+        assert_eq!(revert(to_uint256(I256::MINUS_ONE)), Vec::<u8>::from(error));
+
+        let max = I256::MAX.into_raw();
+        assert_eq!(to_int256(max).ok(), Some(I256::MAX));
+        let error = SafeCastError::SafeCastOverflowedUintToInt(SafeCastOverflowedUintToInt {
+            value: max + U256::from(1),
+        });
+        assert_eq!(
+            revert(to_int256(max + U256::from(1))),
+            Vec::<u8>::from(error)
+        );
+    }
+}
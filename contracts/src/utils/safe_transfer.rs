@@ -0,0 +1,110 @@
+//! Safe ETH and ERC20 transfer library that gracefully handles missing return values.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/token/ERC20/utils/SafeERC20.sol
+//! Doc comments are forked from: https://github.com/Vectorized/solady/blob/main/src/utils/SafeTransferLib.sol
+//!
+//! Tokens that return no data on success (ex. USDT) are supported, as long as the token address
+//! has code. Tokens that return `false` are treated as failed transfers.
+//!
+//! On failure, the revert data of the called contract is bubbled up if there is any. Otherwise, the
+//! ABI encoded error of this library is returned, so that callers can propagate it with `?`.
+
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolCall, SolError},
+    call::RawCall,
+    prelude::*,
+};
+
+sol! {
+    /// The ETH transfer has failed.
+    error ETHTransferFailed();
+    /// The ERC20 `transferFrom` has failed.
+    error TransferFromFailed();
+    /// The ERC20 `transfer` has failed.
+    error TransferFailed();
+    /// The ERC20 `approve` has failed.
+    error ApproveFailed();
+
+    function transfer(address to, uint256 amount) external returns (bool);
+    function transferFrom(address from, address to, uint256 amount) external returns (bool);
+    function approve(address spender, uint256 amount) external returns (bool);
+}
+
+/// Sends `amount` (in wei) ETH to `to`.
+/// All gas is forwarded, so the recipient may reenter or burn it.
+pub fn safe_transfer_eth(to: Address, amount: U256) -> Result<(), Vec<u8>> {
+    RawCall::new_with_value(amount)
+        .skip_return_data()
+        .call(to, &[])
+        .map_err(|data| revert_data(data, &ETHTransferFailed {}))?;
+    Ok(())
+}
+
+/// Sends `amount` of ERC20 `token` from the current contract to `to`.
+/// Reverts upon failure.
+pub fn safe_transfer(token: Address, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+    call_optional_return(
+        token,
+        &transferCall { to, amount }.encode(),
+        TransferFailed {},
+    )
+}
+
+/// Sends `amount` of ERC20 `token` from `from` to `to`.
+/// Reverts upon failure.
+///
+/// The `from` account must have at least `amount` approved for the current contract to manage.
+pub fn safe_transfer_from(
+    token: Address,
+    from: Address,
+    to: Address,
+    amount: U256,
+) -> Result<(), Vec<u8>> {
+    call_optional_return(
+        token,
+        &transferFromCall { from, to, amount }.encode(),
+        TransferFromFailed {},
+    )
+}
+
+/// Sets `amount` of ERC20 `token` for `spender` to manage on behalf of the current contract.
+/// Reverts upon failure.
+pub fn safe_approve(token: Address, spender: Address, amount: U256) -> Result<(), Vec<u8>> {
+    call_optional_return(
+        token,
+        &approveCall { spender, amount }.encode(),
+        ApproveFailed {},
+    )
+}
+
+/// Calls `token` with `calldata`, succeeding if the call returns `true` or returns no data at all
+/// while `token` has code.
+fn call_optional_return<E: SolError>(
+    token: Address,
+    calldata: &[u8],
+    error: E,
+) -> Result<(), Vec<u8>> {
+    let returndata = RawCall::new()
+        .call(token, calldata)
+        .map_err(|data| revert_data(data, &error))?;
+
+    let success = if returndata.is_empty() {
+        token.has_code()
+    } else {
+        returndata.len() >= 32 && U256::from_be_slice(&returndata[..32]) == U256::from(1)
+    };
+    if !success {
+        return Err(error.encode());
+    }
+    Ok(())
+}
+
+/// Returns `data` if it is non-empty, otherwise the ABI encoded `error`.
+fn revert_data<E: SolError>(data: Vec<u8>, error: &E) -> Vec<u8> {
+    if data.is_empty() {
+        error.encode()
+    } else {
+        data
+    }
+}
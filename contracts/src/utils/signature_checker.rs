@@ -0,0 +1,73 @@
+//! Signature verification helper that supports both ECDSA signatures from externally owned
+//! accounts and ERC-1271 signatures from smart contract wallets (ex. Safe, ERC-4337 accounts).
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/utils/cryptography/SignatureChecker.sol
+//!
+//! Signatures are not cached, so a signature that is valid now may become invalid later if the
+//! signing contract changes its validation logic.
+
+use stylus_sdk::{
+    alloy_primitives::{Address, B256},
+    alloy_sol_types::{sol, SolCall},
+    call::RawCall,
+    prelude::*,
+};
+
+use crate::utils::ecrecover::{EcRecoverTrait, PrecompileEcRecover};
+
+/// The magic value returned by `isValidSignature` for a valid ERC-1271 signature,
+/// which is the selector of `isValidSignature(bytes32,bytes)`.
+pub const ERC1271_MAGIC_VALUE: u32 = 0x1626ba7e;
+
+/// The byte length of an ECDSA signature encoded as `r ‖ s ‖ v`
+pub const ECDSA_SIGNATURE_LEN: usize = 65;
+
+sol! {
+    function isValidSignature(bytes32 hash, bytes signature) external view returns (bytes4);
+}
+
+/// Returns whether `signature` is a valid signature of `hash` by `signer`.
+/// If `signer` has code, the signature is validated by calling its ERC-1271 `isValidSignature`
+/// method. Otherwise, the signature must be an ECDSA signature encoded as `r ‖ s ‖ v`.
+pub fn is_valid_signature_now(signer: Address, hash: B256, signature: &[u8]) -> bool {
+    if signer.is_zero() {
+        return false;
+    }
+    if signer.has_code() {
+        is_valid_erc1271_signature_now(signer, hash, signature)
+    } else {
+        is_valid_ecdsa_signature_now(signer, hash, signature)
+    }
+}
+
+/// Returns whether `signature` is a valid ECDSA signature of `hash` by `signer`.
+/// The signature must be encoded as `r ‖ s ‖ v`.
+pub fn is_valid_ecdsa_signature_now(signer: Address, hash: B256, signature: &[u8]) -> bool {
+    if signer.is_zero() || signature.len() != ECDSA_SIGNATURE_LEN {
+        return false;
+    }
+    let r: [u8; 32] = signature[..32].try_into().unwrap();
+    let s: [u8; 32] = signature[32..64].try_into().unwrap();
+    let v = signature[64];
+    match PrecompileEcRecover::ecrecover(&hash.0, v, &r, &s) {
+        Ok(recovered) => Address::from(recovered) == signer,
+        Err(_) => false,
+    }
+}
+
+/// Returns whether `signature` is a valid ERC-1271 signature of `hash` by the `signer` contract.
+/// Reverts and malformed return data from `signer` are treated as invalid signatures.
+pub fn is_valid_erc1271_signature_now(signer: Address, hash: B256, signature: &[u8]) -> bool {
+    let calldata = isValidSignatureCall {
+        hash: hash.0,
+        signature: signature.to_vec(),
+    }
+    .encode();
+    match RawCall::new_static()
+        // Only get the first 4 bytes of the 32-byte return data
+        .limit_return_data(0, 4)
+        .call(signer, &calldata)
+    {
+        Ok(returned) => returned.as_slice() == ERC1271_MAGIC_VALUE.to_be_bytes(),
+        Err(_) => false,
+    }
+}
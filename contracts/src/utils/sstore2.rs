@@ -0,0 +1,121 @@
+//! Write-once storage of large byte blobs in contract code.
+//! The logic was based off of: https://github.com/Vectorized/solady/blob/main/src/utils/SSTORE2.sol
+//!
+//! `write` deploys a data contract whose code holds the blob, and returns its address, which is
+//! called a pointer. Deploying code costs far less per byte than writing storage slots, which
+//! makes this the cheapest way to store large on-chain assets such as images or fonts. The data
+//! cannot be modified once written.
+//!
+//! Stylus contracts cannot EXTCODECOPY, so data contracts return their data when called, and
+//! `read` and `read_range` are static calls to the pointer.
+
+use crate::inkmate_common::sstore2::{creation_code, MAX_DATA_SIZE};
+use alloc::vec::Vec;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolError},
+    call::RawCall,
+    contract,
+    crypto::keccak,
+    deploy::RawDeploy,
+    prelude::*,
+};
+
+use crate::utils::create::{Create, CreateTrait};
+
+sol! {
+    /// The data is larger than the maximum contract code size allows.
+    error SSTORE2DataTooLarge(uint256 size, uint256 maxSize);
+    /// The deployment of the data contract has failed.
+    error SSTORE2DeploymentFailed();
+    /// The pointer is not a data contract.
+    error SSTORE2InvalidPointer(address pointer);
+    /// The range to read is out of the bounds of the data.
+    error SSTORE2ReadOutOfBounds(uint256 start, uint256 end);
+}
+
+/// Returns the creation code of a data contract storing `data`.
+fn data_creation_code(data: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+    creation_code(data).ok_or_else(|| {
+        SSTORE2DataTooLarge {
+            size: U256::from(data.len()),
+            maxSize: U256::from(MAX_DATA_SIZE),
+        }
+        .encode()
+    })
+}
+
+/// Writes `data` into the code of a new data contract, and returns its address.
+pub fn write(data: &[u8]) -> Result<Address, Vec<u8>> {
+    let code = data_creation_code(data)?;
+    // SAFETY: the creation code only returns the runtime code and makes no calls
+    unsafe {
+        RawDeploy::new()
+            .deploy(&code, U256::ZERO)
+            .map_err(|_| SSTORE2DeploymentFailed {}.encode())
+    }
+}
+
+/// Writes `data` into the code of a new data contract deployed through CREATE2 with `salt`, and
+/// returns its address. See `predict_deterministic_address`.
+pub fn write_deterministic(data: &[u8], salt: B256) -> Result<Address, Vec<u8>> {
+    let code = data_creation_code(data)?;
+    // SAFETY: the creation code only returns the runtime code and makes no calls
+    unsafe {
+        RawDeploy::new()
+            .salt(salt)
+            .deploy(&code, U256::ZERO)
+            .map_err(|_| SSTORE2DeploymentFailed {}.encode())
+    }
+}
+
+/// Returns the address of the data contract storing `data` written by this contract with
+/// `write_deterministic` and `salt`.
+pub fn predict_deterministic_address(data: &[u8], salt: B256) -> Result<Address, Vec<u8>> {
+    let code = data_creation_code(data)?;
+    Ok(Address::from(Create::compute_create2_address(
+        contract::address().0 .0,
+        salt.0,
+        keccak(code).0,
+    )))
+}
+
+/// Returns the data stored at `pointer`.
+///
+/// Requirements:
+/// - `pointer` must be a data contract.
+pub fn read(pointer: Address) -> Result<Vec<u8>, Vec<u8>> {
+    if !pointer.has_code() {
+        return Err(SSTORE2InvalidPointer { pointer }.encode());
+    }
+    RawCall::new_static().call(pointer, &[])
+}
+
+/// Returns the bytes `start..end` of the data stored at `pointer`.
+/// Only the requested range is copied out of the data contract's return data.
+///
+/// Requirements:
+/// - `pointer` must be a data contract.
+/// - `start..end` must be within the bounds of the data.
+pub fn read_range(pointer: Address, start: usize, end: usize) -> Result<Vec<u8>, Vec<u8>> {
+    let out_of_bounds = || {
+        SSTORE2ReadOutOfBounds {
+            start: U256::from(start),
+            end: U256::from(end),
+        }
+        .encode()
+    };
+    if end < start {
+        return Err(out_of_bounds());
+    }
+    if !pointer.has_code() {
+        return Err(SSTORE2InvalidPointer { pointer }.encode());
+    }
+    let data = RawCall::new_static()
+        .limit_return_data(start, end - start)
+        .call(pointer, &[])?;
+    if data.len() != end - start {
+        return Err(out_of_bounds());
+    }
+    Ok(data)
+}
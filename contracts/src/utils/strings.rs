@@ -0,0 +1,16 @@
+//! Converts integers and addresses into strings using the VM's native keccak
+
+use stylus_sdk::crypto::keccak;
+
+/// Import StringsTrait from inkmate_common publicly
+/// This permit projects to use it without importing `inkmate_common`
+pub use crate::inkmate_common::strings::StringsTrait;
+
+pub struct Strings;
+
+impl StringsTrait for Strings {
+    /// Hashes `data` using the native keccak hostio
+    fn keccak256(data: &[u8]) -> [u8; 32] {
+        keccak(data).0
+    }
+}
@@ -0,0 +1,452 @@
+//! Storage data structures: a double-ended queue and a min-heap.
+//! The logic was based off of: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/utils/structs/DoubleEndedQueue.sol
+//! and: https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/utils/structs/Heap.sol
+//!
+//! A `Bytes32Deque` stores `bytes32` items that can be pushed and popped at both ends in constant
+//! time. Its begin and end indices are packed into a single slot and wrap around, so the queue
+//! never has to be moved in storage.
+//!
+//! A `MinHeap` stores `(key, value)` nodes ordered by their `U256` key, so that the node with the
+//! smallest key can be read in constant time and inserted or removed in logarithmic time (ex. for
+//! priority queues or order books sorted by price).
+
+use stylus_sdk::{
+    alloy_primitives::{B256, U128, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+};
+
+sol_storage! {
+    /// Bytes32Deque stores items at the indices `begin..end`, which wrap around
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct Bytes32Deque {
+        /// The index of the first item
+        uint128 begin;
+        /// The index after the last item
+        uint128 end;
+        /// Maps index to item
+        mapping(uint256 => bytes32) data;
+    }
+
+    /// MinHeap stores nodes as a binary tree in two parallel arrays, where the children of the
+    /// node at index `i` are at indices `2i + 1` and `2i + 2`
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct MinHeap {
+        /// The key of each node, which is never greater than the keys of its children
+        uint256[] keys;
+        /// The value of each node
+        bytes32[] values;
+    }
+}
+
+// Declare Solidity error types
+sol! {
+    /// The deque is empty.
+    error DequeEmpty();
+    /// The deque is full.
+    error DequeFull();
+    /// The index is out of the bounds of the deque.
+    error DequeOutOfBounds(uint256 index);
+    /// The heap is empty.
+    error HeapEmpty();
+}
+
+#[derive(SolidityError)]
+pub enum StructsError {
+    DequeEmpty(DequeEmpty),
+    DequeFull(DequeFull),
+    DequeOutOfBounds(DequeOutOfBounds),
+    HeapEmpty(HeapEmpty),
+}
+
+impl Bytes32Deque {
+    /// Inserts `value` at the end of the queue.
+    ///
+    /// Requirements:
+    /// - The queue must not be full.
+    pub fn push_back(&mut self, value: B256) -> Result<(), StructsError> {
+        let end = self.end.get();
+        if end.wrapping_add(U128::from(1)) == self.begin.get() {
+            return Err(StructsError::DequeFull(DequeFull {}));
+        }
+        self.data.insert(U256::from(end), value);
+        self.end.set(end.wrapping_add(U128::from(1)));
+        Ok(())
+    }
+
+    /// Removes the item at the end of the queue and returns it.
+    ///
+    /// Requirements:
+    /// - The queue must not be empty.
+    pub fn pop_back(&mut self) -> Result<B256, StructsError> {
+        if self.is_empty() {
+            return Err(StructsError::DequeEmpty(DequeEmpty {}));
+        }
+        let end = self.end.get().wrapping_sub(U128::from(1));
+        let value = self.data.get(U256::from(end));
+        self.data.delete(U256::from(end));
+        self.end.set(end);
+        Ok(value)
+    }
+
+    /// Inserts `value` at the beginning of the queue.
+    ///
+    /// Requirements:
+    /// - The queue must not be full.
+    pub fn push_front(&mut self, value: B256) -> Result<(), StructsError> {
+        let begin = self.begin.get().wrapping_sub(U128::from(1));
+        if begin == self.end.get() {
+            return Err(StructsError::DequeFull(DequeFull {}));
+        }
+        self.data.insert(U256::from(begin), value);
+        self.begin.set(begin);
+        Ok(())
+    }
+
+    /// Removes the item at the beginning of the queue and returns it.
+    ///
+    /// Requirements:
+    /// - The queue must not be empty.
+    pub fn pop_front(&mut self) -> Result<B256, StructsError> {
+        if self.is_empty() {
+            return Err(StructsError::DequeEmpty(DequeEmpty {}));
+        }
+        let begin = self.begin.get();
+        let value = self.data.get(U256::from(begin));
+        self.data.delete(U256::from(begin));
+        self.begin.set(begin.wrapping_add(U128::from(1)));
+        Ok(value)
+    }
+
+    /// Returns the item at the beginning of the queue.
+    ///
+    /// Requirements:
+    /// - The queue must not be empty.
+    pub fn front(&self) -> Result<B256, StructsError> {
+        if self.is_empty() {
+            return Err(StructsError::DequeEmpty(DequeEmpty {}));
+        }
+        Ok(self.data.get(U256::from(self.begin.get())))
+    }
+
+    /// Returns the item at the end of the queue.
+    ///
+    /// Requirements:
+    /// - The queue must not be empty.
+    pub fn back(&self) -> Result<B256, StructsError> {
+        if self.is_empty() {
+            return Err(StructsError::DequeEmpty(DequeEmpty {}));
+        }
+        let end = self.end.get().wrapping_sub(U128::from(1));
+        Ok(self.data.get(U256::from(end)))
+    }
+
+    /// Returns the item at position `index` in the queue, with the first item at index 0.
+    ///
+    /// Requirements:
+    /// - `index` must be lower than the length of the queue.
+    pub fn at(&self, index: U256) -> Result<B256, StructsError> {
+        if index >= self.length() {
+            return Err(StructsError::DequeOutOfBounds(DequeOutOfBounds { index }));
+        }
+        // The index is lower than the length, so it fits in 128 bits
+        let position = self.begin.get().wrapping_add(index.to::<U128>());
+        Ok(self.data.get(U256::from(position)))
+    }
+
+    /// Resets the queue back to being empty.
+    /// The items are not erased from storage, but they can no longer be read.
+    pub fn clear(&mut self) {
+        self.begin.set(U128::ZERO);
+        self.end.set(U128::ZERO);
+    }
+
+    /// Returns the number of items in the queue.
+    pub fn length(&self) -> U256 {
+        U256::from(self.end.get().wrapping_sub(self.begin.get()))
+    }
+
+    /// Returns whether the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.end.get() == self.begin.get()
+    }
+}
+
+impl MinHeap {
+    /// Returns the node with the smallest key, if the heap is not empty.
+    pub fn peek(&self) -> Option<(U256, B256)> {
+        Some((self.keys.get(0)?, self.values.get(0)?))
+    }
+
+    /// Inserts a node with `key` and `value`.
+    pub fn insert(&mut self, key: U256, value: B256) {
+        self.keys.push(key);
+        self.values.push(value);
+
+        // Move the parents down until the node is not smaller than its parent
+        let mut index = self.keys.len() - 1;
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            let parent_key = self.keys.get(parent).unwrap();
+            if parent_key <= key {
+                break;
+            }
+            let parent_value = self.values.get(parent).unwrap();
+            self._set(index, parent_key, parent_value);
+            index = parent;
+        }
+        self._set(index, key, value);
+    }
+
+    /// Removes the node with the smallest key and returns it.
+    ///
+    /// Requirements:
+    /// - The heap must not be empty.
+    pub fn pop(&mut self) -> Result<(U256, B256), StructsError> {
+        let root = self.peek().ok_or(StructsError::HeapEmpty(HeapEmpty {}))?;
+        let key = self.keys.pop().unwrap();
+        let value = self.values.pop().unwrap();
+        let len = self.keys.len();
+        if len == 0 {
+            return Ok(root);
+        }
+
+        // Move the last node to the root, then move the smallest children up until the node is
+        // not greater than its children
+        let mut index = 0;
+        loop {
+            let left = 2 * index + 1;
+            if left >= len {
+                break;
+            }
+            let right = left + 1;
+            let mut child = left;
+            let mut child_key = self.keys.get(left).unwrap();
+            if right < len {
+                let right_key = self.keys.get(right).unwrap();
+                if right_key < child_key {
+                    child = right;
+                    child_key = right_key;
+                }
+            }
+            if key <= child_key {
+                break;
+            }
+            let child_value = self.values.get(child).unwrap();
+            self._set(index, child_key, child_value);
+            index = child;
+        }
+        self._set(index, key, value);
+        Ok(root)
+    }
+
+    /// Returns the number of nodes in the heap.
+    pub fn length(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns whether the heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Writes the node at `index`.
+    fn _set(&mut self, index: usize, key: U256, value: B256) {
+        self.keys.setter(index).unwrap().set(key);
+        self.values.setter(index).unwrap().set(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use inkmate_test::TestHost;
+    use stylus_sdk::alloy_primitives::Address;
+    use stylus_sdk::alloy_sol_types::SolCall;
+
+    sol_storage! {
+        struct Structs {
+            Bytes32Deque deque;
+            MinHeap heap;
+        }
+    }
+
+    unsafe impl TopLevelStorage for Structs {}
+
+    #[external]
+    impl Structs {
+        pub fn push_back(&mut self, value: B256) -> Result<(), Vec<u8>> {
+            Ok(self.deque.push_back(value)?)
+        }
+
+        pub fn push_front(&mut self, value: B256) -> Result<(), Vec<u8>> {
+            Ok(self.deque.push_front(value)?)
+        }
+
+        pub fn pop_back(&mut self) -> Result<B256, Vec<u8>> {
+            Ok(self.deque.pop_back()?)
+        }
+
+        pub fn pop_front(&mut self) -> Result<B256, Vec<u8>> {
+            Ok(self.deque.pop_front()?)
+        }
+
+        pub fn ends(&self) -> Result<(B256, B256), Vec<u8>> {
+            Ok((self.deque.front()?, self.deque.back()?))
+        }
+
+        pub fn at(&self, index: U256) -> Result<B256, Vec<u8>> {
+            Ok(self.deque.at(index)?)
+        }
+
+        pub fn clear(&mut self) {
+            self.deque.clear();
+        }
+
+        pub fn length(&self) -> U256 {
+            self.deque.length()
+        }
+
+        pub fn insert(&mut self, key: U256, value: B256) {
+            self.heap.insert(key, value);
+        }
+
+        pub fn pop(&mut self) -> Result<(U256, B256), Vec<u8>> {
+            Ok(self.heap.pop()?)
+        }
+
+        pub fn peek(&self) -> (U256, B256) {
+            self.heap.peek().unwrap_or_default()
+        }
+    }
+
+    sol! {
+        function pushBack(bytes32 value);
+        function pushFront(bytes32 value);
+        function popBack() returns (bytes32);
+        function popFront() returns (bytes32);
+        function ends() returns (bytes32, bytes32);
+        function at(uint256 index) returns (bytes32);
+        function clear();
+        function length() returns (uint256);
+        function insert(uint256 key, bytes32 value);
+        function pop() returns (uint256, bytes32);
+        function peek() returns (uint256, bytes32);
+    }
+
+    fn item(n: u8) -> [u8; 32] {
+        B256::with_last_byte(n).0
+    }
+
+    fn setup() -> (TestHost, Address, Address) {
+        let mut host = TestHost::new();
+        let structs = host.deploy::<Structs>();
+        (host, structs, TestHost::address("alice"))
+    }
+
+    /// Returns the items of the deque, from the front to the back
+    fn items(host: &mut TestHost, structs: Address, alice: Address) -> Vec<[u8; 32]> {
+        let length = host.call_sol(alice, structs, &lengthCall {}).unwrap()._0;
+        (0..length.to::<u64>())
+            .map(|index| {
+                let at = atCall {
+                    index: U256::from(index),
+                };
+                host.call_sol(alice, structs, &at).unwrap()._0
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_deque_push_and_pop() {
+        let (mut host, structs, alice) = setup();
+
+        // The first push to the front wraps the begin index around
+        host.call_sol(alice, structs, &pushFrontCall { value: item(2) })
+            .unwrap();
+        host.call_sol(alice, structs, &pushBackCall { value: item(3) })
+            .unwrap();
+        host.call_sol(alice, structs, &pushFrontCall { value: item(1) })
+            .unwrap();
+
+        assert_eq!(
+            items(&mut host, structs, alice),
+            vec![item(1), item(2), item(3)]
+        );
+        let ends = host.call_sol(alice, structs, &endsCall {}).unwrap();
+        assert_eq!((ends._0, ends._1), (item(1), item(3)));
+        let back = host.call_sol(alice, structs, &popBackCall {}).unwrap();
+        assert_eq!(back._0, item(3));
+        let front = host.call_sol(alice, structs, &popFrontCall {}).unwrap();
+        assert_eq!(front._0, item(1));
+        assert_eq!(items(&mut host, structs, alice), vec![item(2)]);
+    }
+
+    #[test]
+    fn test_deque_empty() {
+        let (mut host, structs, alice) = setup();
+        let empty = Vec::<u8>::from(StructsError::DequeEmpty(DequeEmpty {}));
+
+        for call in [
+            popBackCall {}.encode(),
+            popFrontCall {}.encode(),
+            endsCall {}.encode(),
+        ] {
+            assert_eq!(host.call(alice, structs, &call).unwrap_err(), empty);
+        }
+        let at = atCall { index: U256::ZERO };
+        let revert = host.call(alice, structs, &at.encode()).unwrap_err();
+        let error = StructsError::DequeOutOfBounds(DequeOutOfBounds { index: U256::ZERO });
+        assert_eq!(revert, Vec::<u8>::from(error));
+    }
+
+    #[test]
+    fn test_deque_clear() {
+        let (mut host, structs, alice) = setup();
+        for n in 1..=3 {
+            host.call_sol(alice, structs, &pushBackCall { value: item(n) })
+                .unwrap();
+        }
+
+        host.call_sol(alice, structs, &clearCall {}).unwrap();
+
+        assert!(items(&mut host, structs, alice).is_empty());
+        let at = atCall {
+            index: U256::from(1),
+        };
+        assert!(host.call(alice, structs, &at.encode()).is_err());
+        host.call_sol(alice, structs, &pushBackCall { value: item(4) })
+            .unwrap();
+        assert_eq!(items(&mut host, structs, alice), vec![item(4)]);
+    }
+
+    #[test]
+    fn test_heap() {
+        let (mut host, structs, alice) = setup();
+        let peek = host.call_sol(alice, structs, &peekCall {}).unwrap();
+        assert_eq!((peek._0, peek._1), (U256::ZERO, [0; 32]));
+
+        // Nodes with the same key are popped in any order
+        for (key, n) in [(5, 1), (3, 2), (8, 3), (1, 4), (3, 5), (9, 6)] {
+            let insert = insertCall {
+                key: U256::from(key),
+                value: item(n),
+            };
+            host.call_sol(alice, structs, &insert).unwrap();
+        }
+
+        let peek = host.call_sol(alice, structs, &peekCall {}).unwrap();
+        assert_eq!((peek._0, peek._1), (U256::from(1), item(4)));
+        let mut nodes = vec![];
+        for _ in 0..6 {
+            let node = host.call_sol(alice, structs, &popCall {}).unwrap();
+            nodes.push((node._0.to::<u64>(), node._1[31]));
+        }
+        nodes[1..3].sort();
+        assert_eq!(nodes, vec![(1, 4), (3, 2), (3, 5), (5, 1), (8, 3), (9, 6)]);
+        let revert = host.call(alice, structs, &popCall {}.encode()).unwrap_err();
+        let error = StructsError::HeapEmpty(HeapEmpty {});
+        assert_eq!(revert, Vec::<u8>::from(error));
+    }
+}
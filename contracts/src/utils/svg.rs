@@ -0,0 +1,8 @@
+//! Composes SVG images into a string for generative on-chain art collections.
+//!
+//! Images built with `Svg` can be returned as base64 data URIs with `finish_data_uri`, and used as
+//! the image of on-chain metadata built with the metadata utility.
+
+/// Import the SVG builder from inkmate_common publicly
+/// This permit projects to use it without importing `inkmate_common`
+pub use crate::inkmate_common::svg::{push_escaped, push_u32, Svg, SVG_DATA_URI_PREFIX};
@@ -0,0 +1,215 @@
+//! Time-weighted average prices from cumulative price accumulators.
+//! The logic was based off of: https://github.com/Uniswap/v2-periphery/blob/master/contracts/examples/ExampleSlidingWindowOracle.sol
+//!
+//! A price accumulator is the sum of the price multiplied by the number of seconds it was valid
+//! for. The average price between two points in time is the difference of the accumulators
+//! divided by the seconds between them, so it cannot be moved much by a price manipulated for a
+//! single block. Accumulators are meant to overflow: they are added and subtracted with wrapping
+//! arithmetic, which gives the right difference as long as it fits in 256 bits.
+//!
+//! `TWAPOracle` accumulates a price set with `_update` whenever it changes (ex. when the reserves
+//! of a pool change), and records the accumulator in a ring buffer of `CARDINALITY` observations
+//! taken at least `MIN_INTERVAL` seconds apart. `consult(period)` returns the average price since
+//! the most recent observation at least `period` seconds old, so the buffer must cover the longest
+//! period consulted: `CARDINALITY * MIN_INTERVAL` should exceed it by one interval. Modules that
+//! keep their own accumulators (ex. `AMMPair`) can use `accumulate` and `average` directly.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use stylus_sdk::{
+    alloy_primitives::{U256, U64},
+    alloy_sol_types::sol,
+    block,
+    prelude::*,
+};
+
+/// Returns `cumulative` plus `price` valid for `elapsed` seconds, wrapping on overflow.
+pub fn accumulate(cumulative: U256, price: U256, elapsed: u64) -> U256 {
+    cumulative.wrapping_add(price.wrapping_mul(U256::from(elapsed)))
+}
+
+/// Returns the average price between the accumulators `start` and `end`, `elapsed` seconds
+/// apart, or `None` if `elapsed` is 0. The accumulators may have wrapped in between.
+pub fn average(start: U256, end: U256, elapsed: u64) -> Option<U256> {
+    end.wrapping_sub(start).checked_div(U256::from(elapsed))
+}
+
+/// Immutable definitions of the oracle
+pub trait TWAPOracleParams {
+    /// The number of observations kept in the ring buffer.
+    const CARDINALITY: u64;
+    /// The minimum number of seconds between two observations.
+    const MIN_INTERVAL: u64;
+}
+
+sol_storage! {
+    /// The price accumulator at a point in time
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    pub struct Observation {
+        uint64 timestamp;
+        uint256 price_cumulative;
+    }
+
+    /// TWAPOracle accumulates a price and records observations of its accumulator
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.TWAPOracle"))]
+    pub struct TWAPOracle<T> {
+        /// The current price
+        uint256 price;
+        /// The accumulator at the last update
+        uint256 price_cumulative;
+        /// The timestamp of the last update
+        uint64 updated_at;
+        /// The position of the most recent observation in the ring buffer
+        uint64 index;
+        /// The number of observations recorded, up to `CARDINALITY`
+        uint64 count;
+        /// The ring buffer of observations
+        mapping(uint256 => Observation) observations;
+        PhantomData<T> phantom;
+    }
+}
+
+// Define errors in the contract
+sol! {
+    /// The period cannot be 0.
+    error InvalidTWAPPeriod();
+    /// No observation is at least `period` seconds old.
+    error InsufficientObservations(uint64 period);
+}
+
+#[derive(SolidityError)]
+pub enum TWAPOracleError {
+    InvalidTWAPPeriod(InvalidTWAPPeriod),
+    InsufficientObservations(InsufficientObservations),
+}
+
+// Internal functions
+impl<T: TWAPOracleParams> TWAPOracle<T> {
+    /// Accumulates the previous price until now, and sets the current price to `price`. Records an
+    /// observation if the last one is at least `MIN_INTERVAL` seconds old.
+    pub fn _update(&mut self, price: U256) {
+        let now = block::timestamp();
+        let cumulative = self.cumulative_at(now);
+        self.price.set(price);
+        self.price_cumulative.set(cumulative);
+        self.updated_at.set(U64::from(now));
+
+        let count = self.count.get().to::<u64>();
+        let index = self.index.get().to::<u64>();
+        if count > 0 {
+            let last = self.observations.getter(U256::from(index));
+            if now - last.timestamp.get().to::<u64>() < T::MIN_INTERVAL {
+                return;
+            }
+        }
+        let index = if count == 0 {
+            0
+        } else {
+            (index + 1) % T::CARDINALITY
+        };
+        let mut observation = self.observations.setter(U256::from(index));
+        observation.timestamp.set(U64::from(now));
+        observation.price_cumulative.set(cumulative);
+        self.index.set(U64::from(index));
+        if count < T::CARDINALITY {
+            self.count.set(U64::from(count + 1));
+        }
+    }
+
+    /// Returns the accumulator extrapolated to `timestamp` with the current price.
+    fn cumulative_at(&self, timestamp: u64) -> U256 {
+        let updated_at = self.updated_at.get().to::<u64>();
+        accumulate(
+            self.price_cumulative.get(),
+            self.price.get(),
+            timestamp.saturating_sub(updated_at),
+        )
+    }
+}
+
+// External functions
+#[external]
+impl<T: TWAPOracleParams> TWAPOracle<T> {
+    /// Returns the average price since the most recent observation at least `period` seconds old.
+    /// The average covers slightly more than `period` seconds, up to `MIN_INTERVAL` more.
+    ///
+    /// Requirements:
+    /// - `period` cannot be 0.
+    /// - An observation must be at least `period` seconds old.
+    pub fn consult(&self, period: u64) -> Result<U256, TWAPOracleError> {
+        if period == 0 {
+            return Err(TWAPOracleError::InvalidTWAPPeriod(InvalidTWAPPeriod {}));
+        }
+        let now = block::timestamp();
+        let target = now.saturating_sub(period);
+        let count = self.count.get().to::<u64>();
+        let index = self.index.get().to::<u64>();
+
+        // Walk the ring buffer from the most recent observation to the oldest
+        for i in 0..count {
+            let position = (index + T::CARDINALITY - i) % T::CARDINALITY;
+            let observation = self.observations.getter(U256::from(position));
+            let timestamp = observation.timestamp.get().to::<u64>();
+            if timestamp <= target && timestamp < now {
+                let start = observation.price_cumulative.get();
+                let end = self.cumulative_at(now);
+                // The elapsed time is not zero, as the observation is in the past
+                return Ok(average(start, end, now - timestamp).unwrap_or_default());
+            }
+        }
+        Err(TWAPOracleError::InsufficientObservations(
+            InsufficientObservations { period },
+        ))
+    }
+
+    /// Returns the current price, its accumulator extrapolated to now, and the timestamp of the
+    /// last update.
+    pub fn current_cumulative_price(&self) -> (U256, U256, u64) {
+        let now = block::timestamp();
+        (
+            self.price.get(),
+            self.cumulative_at(now),
+            self.updated_at.get().to(),
+        )
+    }
+
+    /// Returns the timestamps and accumulators of the observations, from the most recent to the
+    /// oldest.
+    pub fn observations(&self) -> (Vec<u64>, Vec<U256>) {
+        let count = self.count.get().to::<u64>();
+        let index = self.index.get().to::<u64>();
+        (0..count)
+            .map(|i| {
+                let position = (index + T::CARDINALITY - i) % T::CARDINALITY;
+                let observation = self.observations.getter(U256::from(position));
+                (
+                    observation.timestamp.get().to::<u64>(),
+                    observation.price_cumulative.get(),
+                )
+            })
+            .unzip()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_average_wraps() {
+        let price = U256::from(3) << 200;
+        let start = U256::MAX - U256::from(10);
+        let end = accumulate(start, price, 100);
+        // The accumulator wrapped around, but the difference is still right
+        assert!(end < start);
+        assert_eq!(average(start, end, 100), Some(price));
+
+        let end = accumulate(end, price * U256::from(2), 100);
+        assert_eq!(
+            average(start, end, 200),
+            Some(price * U256::from(3) / U256::from(2))
+        );
+        assert_eq!(average(start, end, 0), None);
+    }
+}
@@ -0,0 +1,195 @@
+//! ERC-1271 signature validation for smart contract wallets, with a threshold of ECDSA signers.
+//! The logic was based off of: https://github.com/safe-global/safe-smart-account/blob/main/contracts/Safe.sol
+//!
+//! A signature is valid if it contains at least `threshold` ECDSA signatures of the hash from
+//! distinct signers. Signatures are packed as `r ‖ s ‖ v` and concatenated in strictly ascending
+//! order of their signer address, which prevents the same signer from being counted twice.
+//! A single owner wallet is a threshold of 1 with a single signer.
+//!
+//! The inheriting contract must add the initial signers and set the threshold during its
+//! [initialization](crate#initialization), and guard later changes (ex. with `Ownable`).
+
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, FixedBytes, B256, U256},
+    alloy_sol_types::sol,
+    evm,
+    prelude::*,
+};
+
+use crate::utils::ecrecover::{EcRecoverTrait, PrecompileEcRecover};
+
+/// The value returned by `isValidSignature` for a valid signature,
+/// which is the selector of `isValidSignature(bytes32,bytes)`.
+pub const ERC1271_MAGIC_VALUE: u32 = 0x1626ba7e;
+
+/// The value returned by `isValidSignature` for an invalid signature.
+pub const ERC1271_INVALID_VALUE: u32 = 0xffffffff;
+
+/// The byte length of a single ECDSA signature packed as `r ‖ s ‖ v`
+const SIGNATURE_LEN: usize = 65;
+
+sol_storage! {
+    /// ERC1271 stores the set of signers and the number of signatures required
+    #[cfg_attr(feature = "export-layout", inkmate_macros::storage_layout)]
+    #[cfg_attr(feature = "erc7201", inkmate_macros::namespace("inkmate.storage.ERC1271"))]
+    pub struct ERC1271 {
+        /// Maps address to whether it is a signer
+        mapping(address => bool) signers;
+        /// The number of signers
+        uint256 signer_count;
+        /// The number of signatures required for a signature to be valid
+        uint256 threshold;
+    }
+}
+
+// Define events and errors in the contract
+sol! {
+    /// Emitted when `signer` is added to the signers.
+    event SignerAdded(address indexed signer);
+    /// Emitted when `signer` is removed from the signers.
+    event SignerRemoved(address indexed signer);
+    /// Emitted when the number of required signatures is changed to `threshold`.
+    event ThresholdChanged(uint256 threshold);
+
+    /// The `signer` cannot be added or removed (ex. the zero address, or a duplicate).
+    error ERC1271InvalidSigner(address signer);
+    /// The `threshold` is zero or exceeds the number of signers.
+    error ERC1271InvalidThreshold(uint256 threshold, uint256 signerCount);
+}
+
+#[derive(SolidityError)]
+pub enum ERC1271Error {
+    ERC1271InvalidSigner(ERC1271InvalidSigner),
+    ERC1271InvalidThreshold(ERC1271InvalidThreshold),
+}
+
+// Internal functions
+impl ERC1271 {
+    /// Adds `signer` to the signers.
+    ///
+    /// Requirements:
+    /// - `signer` must not be the zero address or an existing signer.
+    ///
+    /// Emits a {SignerAdded} event.
+    pub fn _add_signer(&mut self, signer: Address) -> Result<(), ERC1271Error> {
+        if signer.is_zero() || self.signers.get(signer) {
+            return Err(ERC1271Error::ERC1271InvalidSigner(ERC1271InvalidSigner {
+                signer,
+            }));
+        }
+        self.signers.insert(signer, true);
+        self.signer_count
+            .set(self.signer_count.get() + U256::from(1));
+        evm::log(SignerAdded { signer });
+        Ok(())
+    }
+
+    /// Removes `signer` from the signers.
+    ///
+    /// Requirements:
+    /// - `signer` must be an existing signer.
+    /// - The remaining number of signers must not be lower than the threshold.
+    ///
+    /// Emits a {SignerRemoved} event.
+    pub fn _remove_signer(&mut self, signer: Address) -> Result<(), ERC1271Error> {
+        if !self.signers.get(signer) {
+            return Err(ERC1271Error::ERC1271InvalidSigner(ERC1271InvalidSigner {
+                signer,
+            }));
+        }
+        let signer_count = self.signer_count.get() - U256::from(1);
+        let threshold = self.threshold.get();
+        if threshold > signer_count {
+            return Err(ERC1271Error::ERC1271InvalidThreshold(
+                ERC1271InvalidThreshold {
+                    threshold,
+                    signerCount: signer_count,
+                },
+            ));
+        }
+        self.signers.delete(signer);
+        self.signer_count.set(signer_count);
+        evm::log(SignerRemoved { signer });
+        Ok(())
+    }
+
+    /// Sets the number of signatures required for a signature to be valid.
+    ///
+    /// Requirements:
+    /// - `threshold` must be non-zero and not exceed the number of signers.
+    ///
+    /// Emits a {ThresholdChanged} event.
+    pub fn _set_threshold(&mut self, threshold: U256) -> Result<(), ERC1271Error> {
+        let signer_count = self.signer_count.get();
+        if threshold.is_zero() || threshold > signer_count {
+            return Err(ERC1271Error::ERC1271InvalidThreshold(
+                ERC1271InvalidThreshold {
+                    threshold,
+                    signerCount: signer_count,
+                },
+            ));
+        }
+        self.threshold.set(threshold);
+        evm::log(ThresholdChanged { threshold });
+        Ok(())
+    }
+
+    /// Returns whether `signature` contains at least `threshold` valid signatures of `hash`
+    /// from distinct signers, ordered by ascending signer address.
+    pub fn _is_valid_signature(&self, hash: B256, signature: &[u8]) -> bool {
+        let threshold = self.threshold.get();
+        if threshold.is_zero()
+            || signature.is_empty()
+            || !signature.len().is_multiple_of(SIGNATURE_LEN)
+            || U256::from(signature.len() / SIGNATURE_LEN) < threshold
+        {
+            return false;
+        }
+
+        let mut last_signer = Address::ZERO;
+        for chunk in signature.chunks_exact(SIGNATURE_LEN) {
+            let r: [u8; 32] = chunk[..32].try_into().unwrap();
+            let s: [u8; 32] = chunk[32..64].try_into().unwrap();
+            let signer = match PrecompileEcRecover::ecrecover(&hash.0, chunk[64], &r, &s) {
+                Ok(signer) => Address::from(signer),
+                Err(_) => return false,
+            };
+            // Ascending order guarantees that the signers are distinct and non-zero
+            if signer <= last_signer || !self.signers.get(signer) {
+                return false;
+            }
+            last_signer = signer;
+        }
+        true
+    }
+}
+
+// External functions
+#[external]
+impl ERC1271 {
+    /// Returns `ERC1271_MAGIC_VALUE` if `signature` is valid for `hash`,
+    /// and `ERC1271_INVALID_VALUE` otherwise.
+    pub fn is_valid_signature(&self, hash: B256, signature: Bytes) -> FixedBytes<4> {
+        if self._is_valid_signature(hash, &signature.0) {
+            FixedBytes(ERC1271_MAGIC_VALUE.to_be_bytes())
+        } else {
+            FixedBytes(ERC1271_INVALID_VALUE.to_be_bytes())
+        }
+    }
+
+    /// Returns whether `account` is a signer.
+    pub fn is_signer(&self, account: Address) -> bool {
+        self.signers.get(account)
+    }
+
+    /// Returns the number of signers.
+    pub fn signer_count(&self) -> U256 {
+        self.signer_count.get()
+    }
+
+    /// Returns the number of signatures required for a signature to be valid.
+    pub fn threshold(&self) -> U256 {
+        self.threshold.get()
+    }
+}
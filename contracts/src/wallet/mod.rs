@@ -0,0 +1,4 @@
+//! Building blocks for smart contract wallets
+
+#[cfg(feature = "erc1271")]
+pub mod erc1271;
@@ -0,0 +1,348 @@
+//! Tests of the AMMPair, run on the in-memory host of `inkmate-test`.
+#![cfg(feature = "amm-pair")]
+extern crate alloc;
+
+use core::borrow::{Borrow, BorrowMut};
+use inkmate::defi::amm_pair::{
+    get_amount_out, AMMPair, AMMPairError, InsufficientLiquidityMinted, KInvariant, Mint, Swap,
+    Sync, MINIMUM_LIQUIDITY,
+};
+use inkmate::tokens::erc20::{ERC20Params, ERC20};
+use inkmate_test::TestHost;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolCall},
+    prelude::*,
+};
+
+struct TokenParams;
+
+impl ERC20Params for TokenParams {
+    const NAME: &'static str = "Token";
+    const SYMBOL: &'static str = "TKN";
+    const DECIMALS: u8 = 18;
+}
+
+sol_storage! {
+    struct Token {
+        #[borrow]
+        ERC20<TokenParams> erc20;
+    }
+}
+
+unsafe impl TopLevelStorage for Token {}
+
+#[external]
+#[inherit(ERC20<TokenParams>)]
+impl Token {
+    pub fn mint(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        ERC20::_mint(&mut self.erc20, to, amount)?;
+        Ok(())
+    }
+}
+
+struct LPParams;
+
+impl ERC20Params for LPParams {
+    const NAME: &'static str = "LP";
+    const SYMBOL: &'static str = "LP";
+    const DECIMALS: u8 = 18;
+}
+
+sol_storage! {
+    struct Pair {
+        #[borrow]
+        AMMPair<LPParams> pair;
+    }
+}
+
+unsafe impl TopLevelStorage for Pair {}
+
+impl Borrow<ERC20<LPParams>> for Pair {
+    fn borrow(&self) -> &ERC20<LPParams> {
+        &self.pair.erc20
+    }
+}
+
+impl BorrowMut<ERC20<LPParams>> for Pair {
+    fn borrow_mut(&mut self) -> &mut ERC20<LPParams> {
+        &mut self.pair.erc20
+    }
+}
+
+#[external]
+#[inherit(AMMPair<LPParams>, ERC20<LPParams>)]
+impl Pair {
+    pub fn initialize(&mut self, token_a: Address, token_b: Address) -> Result<(), Vec<u8>> {
+        self.pair._initialize(token_a, token_b)?;
+        Ok(())
+    }
+}
+
+sol! {
+    function mint(address to, uint256 amount);
+    function balanceOf(address account) returns (uint256);
+    function totalSupply() returns (uint256);
+    function transfer(address to, uint256 amount) returns (bool);
+    function initialize(address tokenA, address tokenB);
+    function token0() returns (address);
+    function getReserves() returns (uint256, uint256, uint32);
+    function burn(address to) returns (uint256, uint256);
+    function swap(uint256 amount0Out, uint256 amount1Out, address to, bytes data);
+    function skim(address to);
+    function sync();
+    function mint(address to) returns (uint256);
+}
+
+struct Setup {
+    host: TestHost,
+    pair: Address,
+    token0: Address,
+    token1: Address,
+    alice: Address,
+    bob: Address,
+}
+
+/// Deploys a pair whose first deposit by alice is 1,000,000 token0 and 4,000,000 token1
+fn setup() -> Setup {
+    let mut host = TestHost::new();
+    let token0 = host.deploy::<Token>();
+    let token1 = host.deploy::<Token>();
+    let pair = host.deploy::<Pair>();
+    let alice = TestHost::address("alice");
+    let bob = TestHost::address("bob");
+    let initialize = initializeCall {
+        tokenA: token1,
+        tokenB: token0,
+    };
+    host.call_sol(alice, pair, &initialize).unwrap();
+    for token in [token0, token1] {
+        for to in [alice, bob] {
+            let mint = mint_0Call {
+                to,
+                amount: U256::from(10_000_000),
+            };
+            host.call_sol(alice, token, &mint).unwrap();
+        }
+    }
+    let mut s = Setup {
+        host,
+        pair,
+        token0,
+        token1,
+        alice,
+        bob,
+    };
+    s.send(alice, token0, 1_000_000);
+    s.send(alice, token1, 4_000_000);
+    s.add_liquidity(alice).unwrap();
+    s.host.take_logs();
+    s
+}
+
+impl Setup {
+    /// Sends `amount` of `token` from `from` to the pair
+    fn send(&mut self, from: Address, token: Address, amount: u64) {
+        let transfer = transferCall {
+            to: self.pair,
+            amount: U256::from(amount),
+        };
+        self.host.call_sol(from, token, &transfer).unwrap();
+    }
+
+    fn add_liquidity(&mut self, to: Address) -> Result<U256, Vec<u8>> {
+        let mint = mint_1Call { to };
+        let output = self.host.call(to, self.pair, &mint.encode())?;
+        Ok(mint_1Call::decode_returns(&output, true).unwrap()._0)
+    }
+
+    fn swap(&mut self, amount0_out: U256, amount1_out: U256) -> Result<Vec<u8>, Vec<u8>> {
+        let swap = swapCall {
+            amount0Out: amount0_out,
+            amount1Out: amount1_out,
+            to: self.alice,
+            data: Vec::new(),
+        };
+        self.host.call(self.alice, self.pair, &swap.encode())
+    }
+
+    fn balance_of(&mut self, token: Address, account: Address) -> U256 {
+        self.host
+            .call_sol(account, token, &balanceOfCall { account })
+            .unwrap()
+            ._0
+    }
+
+    fn reserves(&mut self) -> (U256, U256) {
+        let reserves = self
+            .host
+            .call_sol(self.alice, self.pair, &getReservesCall {});
+        let reserves = reserves.unwrap();
+        (reserves._0, reserves._1)
+    }
+}
+
+#[test]
+fn test_mint_locks_minimum_liquidity() {
+    let mut s = setup();
+    let (pair, alice) = (s.pair, s.alice);
+
+    // The first deposit mints sqrt(1,000,000 * 4,000,000) LP tokens, minus the locked ones
+    assert_eq!(
+        s.balance_of(pair, alice),
+        U256::from(2_000_000) - MINIMUM_LIQUIDITY
+    );
+    assert_eq!(s.balance_of(pair, Address::ZERO), MINIMUM_LIQUIDITY);
+    let supply = s.host.call_sol(alice, pair, &totalSupplyCall {}).unwrap();
+    assert_eq!(supply._0, U256::from(2_000_000));
+    assert_eq!(s.reserves(), (U256::from(1_000_000), U256::from(4_000_000)));
+    let token0 = s.host.call_sol(alice, pair, &token0Call {}).unwrap()._0;
+    assert_eq!(token0, s.token0);
+}
+
+#[test]
+fn test_mint_below_minimum_liquidity() {
+    let mut host = TestHost::new();
+    let token0 = host.deploy::<Token>();
+    let token1 = host.deploy::<Token>();
+    let pair = host.deploy::<Pair>();
+    let alice = TestHost::address("alice");
+    let initialize = initializeCall {
+        tokenA: token0,
+        tokenB: token1,
+    };
+    host.call_sol(alice, pair, &initialize).unwrap();
+    // The geometric mean of the deposit does not exceed the locked liquidity
+    for token in [token0, token1] {
+        let mint = mint_0Call {
+            to: pair,
+            amount: MINIMUM_LIQUIDITY,
+        };
+        host.call_sol(alice, token, &mint).unwrap();
+    }
+
+    let revert = host
+        .call(alice, pair, &mint_1Call { to: alice }.encode())
+        .unwrap_err();
+    let error = AMMPairError::InsufficientLiquidityMinted(InsufficientLiquidityMinted {});
+    assert_eq!(revert, Vec::<u8>::from(error));
+}
+
+#[test]
+fn test_mint_unbalanced_deposit() {
+    let mut s = setup();
+    let (bob, token0, token1) = (s.bob, s.token0, s.token1);
+    s.send(bob, token0, 500_000);
+    s.send(bob, token1, 4_000_000);
+
+    // Bob deposits half of the reserves of token0, so he gets half of the supply
+    let minted = s.add_liquidity(bob).unwrap();
+    assert_eq!(minted, U256::from(1_000_000));
+    // The excess of token1 is donated to the pair
+    assert_eq!(s.reserves(), (U256::from(1_500_000), U256::from(8_000_000)));
+    let logs = s.host.take_logs();
+    let event = logs.iter().find_map(|log| log.decode::<Mint>()).unwrap();
+    assert_eq!(
+        (event.sender, event.amount0, event.amount1),
+        (bob, U256::from(500_000), U256::from(4_000_000))
+    );
+}
+
+#[test]
+fn test_burn() {
+    let mut s = setup();
+    let (pair, alice, token0, token1) = (s.pair, s.alice, s.token0, s.token1);
+    let liquidity = s.balance_of(pair, alice);
+    let transfer = transferCall {
+        to: pair,
+        amount: liquidity,
+    };
+    s.host.call_sol(alice, pair, &transfer).unwrap();
+
+    let amounts = s
+        .host
+        .call_sol(alice, pair, &burnCall { to: alice })
+        .unwrap();
+
+    // Everything but the share of the locked liquidity is returned
+    assert_eq!(
+        (amounts._0, amounts._1),
+        (U256::from(999_500), U256::from(3_998_000))
+    );
+    assert_eq!(s.balance_of(token0, alice), U256::from(9_999_500));
+    assert_eq!(s.balance_of(token1, alice), U256::from(9_998_000));
+    assert_eq!(s.balance_of(pair, pair), U256::ZERO);
+    assert_eq!(s.reserves(), (U256::from(500), U256::from(2_000)));
+}
+
+#[test]
+fn test_swap() {
+    let mut s = setup();
+    let (alice, token0, token1) = (s.alice, s.token0, s.token1);
+    let (reserve0, reserve1) = s.reserves();
+    let amount_out = get_amount_out(U256::from(10_000), reserve0, reserve1).unwrap();
+    s.send(alice, token0, 10_000);
+
+    s.swap(U256::ZERO, amount_out).unwrap();
+
+    assert_eq!(
+        s.balance_of(token1, alice),
+        U256::from(6_000_000) + amount_out
+    );
+    assert_eq!(
+        s.reserves(),
+        (reserve0 + U256::from(10_000), reserve1 - amount_out)
+    );
+    let logs = s.host.take_logs();
+    let event = logs.last().unwrap().decode::<Swap>().unwrap();
+    assert_eq!(
+        (
+            event.amount0In,
+            event.amount1In,
+            event.amount0Out,
+            event.amount1Out
+        ),
+        (U256::from(10_000), U256::ZERO, U256::ZERO, amount_out)
+    );
+}
+
+#[test]
+fn test_swap_k_invariant() {
+    let mut s = setup();
+    let (alice, token0, token1) = (s.alice, s.token0, s.token1);
+    let (reserve0, reserve1) = s.reserves();
+    let amount_out = get_amount_out(U256::from(10_000), reserve0, reserve1).unwrap();
+    s.send(alice, token0, 10_000);
+
+    // One more token than the fee allows decreases the product of the reserves
+    let revert = s.swap(U256::ZERO, amount_out + U256::from(1)).unwrap_err();
+
+    let error = AMMPairError::KInvariant(KInvariant {});
+    assert_eq!(revert, Vec::<u8>::from(error));
+    assert_eq!(s.balance_of(token1, alice), U256::from(6_000_000));
+}
+
+#[test]
+fn test_skim() {
+    let mut s = setup();
+    let (alice, bob, token0) = (s.alice, s.bob, s.token0);
+    s.send(alice, token0, 500);
+
+    s.host.call_sol(bob, s.pair, &skimCall { to: bob }).unwrap();
+
+    assert_eq!(s.balance_of(token0, bob), U256::from(10_000_500));
+    assert_eq!(s.reserves(), (U256::from(1_000_000), U256::from(4_000_000)));
+}
+
+#[test]
+fn test_sync() {
+    let mut s = setup();
+    let (alice, token0) = (s.alice, s.token0);
+    s.send(alice, token0, 500);
+
+    s.host.call_sol(alice, s.pair, &syncCall {}).unwrap();
+
+    assert_eq!(s.reserves(), (U256::from(1_000_500), U256::from(4_000_000)));
+    let event = s.host.take_logs()[1].decode::<Sync>().unwrap();
+    assert_eq!((event.reserve0, event.reserve1), (1_000_500, 4_000_000));
+}
@@ -0,0 +1,300 @@
+//! Tests of DN404 and its mirror, run on the in-memory host of `inkmate-test`.
+#![cfg(feature = "dn404")]
+extern crate alloc;
+
+use inkmate::tokens::dn404::{
+    DN404Error, DN404Params, SenderNotMirror, TotalSupplyOverflow,
+    TransferCallerNotOwnerNorApproved, DN404,
+};
+use inkmate::tokens::dn404_mirror::{DN404Mirror, DN404MirrorError, SenderNotBase, Transfer};
+use inkmate_test::{Log, TestHost};
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolCall},
+    msg,
+    prelude::*,
+};
+
+struct TokenParams;
+
+impl DN404Params for TokenParams {
+    const NAME: &'static str = "Token";
+    const SYMBOL: &'static str = "TKN";
+
+    fn token_uri(_id: U256) -> String {
+        String::new()
+    }
+}
+
+sol_storage! {
+    struct Token {
+        #[borrow]
+        DN404<TokenParams> dn404;
+    }
+}
+
+unsafe impl TopLevelStorage for Token {}
+
+#[external]
+#[inherit(DN404<TokenParams>)]
+impl Token {
+    pub fn init(&mut self, initial_supply: U256, mirror: Address) -> Result<(), Vec<u8>> {
+        Ok(self
+            .dn404
+            ._initialize_dn404(initial_supply, msg::sender(), mirror)?)
+    }
+
+    pub fn mint(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        Ok(self.dn404._mint(to, amount)?)
+    }
+}
+
+sol_storage! {
+    struct Mirror {
+        #[borrow]
+        DN404Mirror mirror;
+    }
+}
+
+unsafe impl TopLevelStorage for Mirror {}
+
+#[external]
+#[inherit(DN404Mirror)]
+impl Mirror {
+    pub fn init(&mut self, base: Address) -> Result<(), Vec<u8>> {
+        Ok(self.mirror._initialize_mirror(base)?)
+    }
+}
+
+sol! {
+    interface IToken {
+        function init(uint256 initialSupply, address mirror);
+        function mint(address to, uint256 amount);
+        function balanceOf(address owner) returns (uint256);
+        function transfer(address to, uint256 amount) returns (bool);
+        function getSkipNFT(address owner) returns (bool);
+        function setSkipNFT(bool skipNFT) returns (bool);
+        function nftTransferFrom(address from, address to, uint256 id, address msgSender);
+    }
+}
+
+sol! {
+    interface IMirror {
+        function init(address base);
+        function totalSupply() returns (uint256);
+        function balanceOf(address owner) returns (uint256);
+        function ownerOf(uint256 id) returns (address);
+        function transferFrom(address from, address to, uint256 id);
+        function logTransfers(address[] from, address[] to, uint256[] ids);
+    }
+}
+
+/// The amount of tokens backing a single NFT
+const UNIT: U256 = TokenParams::UNIT;
+
+struct Setup {
+    host: TestHost,
+    token: Address,
+    mirror: Address,
+    owner: Address,
+    alice: Address,
+    bob: Address,
+}
+
+/// Deploys the token with 10 units minted to `owner`, who skips NFTs.
+fn setup() -> Setup {
+    let mut host = TestHost::new();
+    let token = host.deploy::<Token>();
+    let mirror = host.deploy::<Mirror>();
+    let owner = TestHost::address("owner");
+    let init = IToken::initCall {
+        initialSupply: UNIT * U256::from(10),
+        mirror,
+    };
+    host.call_sol(owner, token, &init).unwrap();
+    host.call_sol(owner, mirror, &IMirror::initCall { base: token })
+        .unwrap();
+    host.take_logs();
+    Setup {
+        host,
+        token,
+        mirror,
+        owner,
+        alice: TestHost::address("alice"),
+        bob: TestHost::address("bob"),
+    }
+}
+
+impl Setup {
+    fn transfer(&mut self, from: Address, to: Address, amount: U256) {
+        let transfer = IToken::transferCall { to, amount };
+        self.host.call_sol(from, self.token, &transfer).unwrap();
+    }
+
+    fn nft_balance(&mut self, owner: Address) -> U256 {
+        let balance = IMirror::balanceOfCall { owner };
+        self.host.call_sol(owner, self.mirror, &balance).unwrap()._0
+    }
+
+    fn nft_owner(&mut self, id: u64) -> Address {
+        let owner_of = IMirror::ownerOfCall { id: U256::from(id) };
+        self.host
+            .call_sol(self.bob, self.mirror, &owner_of)
+            .unwrap()
+            ._0
+    }
+
+    /// Returns the NFT `Transfer` logs emitted by the mirror since the last call.
+    fn nft_transfers(&mut self) -> Vec<(Address, Address, U256)> {
+        let mirror = self.mirror;
+        self.host
+            .take_logs()
+            .iter()
+            .filter(|log| log.address == mirror)
+            .filter_map(Log::decode::<Transfer>)
+            .map(|log| (log.from, log.to, log.id))
+            .collect()
+    }
+}
+
+#[test]
+fn test_transfer_mints_and_burns_whole_units() {
+    let mut s = setup();
+    let (owner, alice, bob) = (s.owner, s.alice, s.bob);
+    let half = UNIT / U256::from(2);
+
+    // 2.5 units back 2 NFTs
+    s.transfer(owner, alice, UNIT * U256::from(2) + half);
+    assert_eq!(s.nft_balance(alice), U256::from(2));
+    assert_eq!(
+        s.nft_transfers(),
+        vec![
+            (Address::ZERO, alice, U256::from(1)),
+            (Address::ZERO, alice, U256::from(2)),
+        ]
+    );
+
+    // A whole unit moves the last NFT of the sender
+    s.transfer(alice, bob, UNIT);
+    assert_eq!(s.nft_owner(2), bob);
+    assert_eq!(s.nft_transfers(), vec![(alice, bob, U256::from(2))]);
+
+    // Fractions only move NFTs once a whole unit is crossed
+    s.transfer(alice, bob, half);
+    assert_eq!(s.nft_transfers(), vec![]);
+    assert_eq!(s.nft_balance(alice), U256::from(1));
+
+    s.transfer(alice, bob, half);
+    assert_eq!(s.nft_balance(alice), U256::ZERO);
+    assert_eq!(s.nft_balance(bob), U256::from(2));
+    assert_eq!(s.nft_owner(1), bob);
+    assert_eq!(s.nft_transfers(), vec![(alice, bob, U256::from(1))]);
+
+    // Sending the tokens back to the owner, who skips NFTs, burns them
+    s.transfer(bob, owner, UNIT * U256::from(2));
+    assert_eq!(
+        s.nft_transfers(),
+        vec![
+            (bob, Address::ZERO, U256::from(1)),
+            (bob, Address::ZERO, U256::from(2)),
+        ]
+    );
+    let total_supply = s
+        .host
+        .call_sol(bob, s.mirror, &IMirror::totalSupplyCall {})
+        .unwrap()
+        ._0;
+    assert_eq!(total_supply, U256::ZERO);
+}
+
+#[test]
+fn test_skip_nft() {
+    let mut s = setup();
+    let (owner, alice, bob) = (s.owner, s.alice, s.bob);
+
+    // The initial owner and contracts skip NFTs, while other accounts don't by default
+    for (account, skips) in [(owner, true), (s.mirror, true), (alice, false)] {
+        let skip_nft = IToken::getSkipNFTCall { owner: account };
+        let result = s.host.call_sol(alice, s.token, &skip_nft).unwrap()._0;
+        assert_eq!(result, skips);
+    }
+
+    let skip_nft = IToken::setSkipNFTCall { skipNFT: true };
+    s.host.call_sol(alice, s.token, &skip_nft).unwrap();
+    s.transfer(owner, alice, UNIT * U256::from(3));
+    assert_eq!(s.nft_balance(alice), U256::ZERO);
+    let balance = IToken::balanceOfCall { owner: alice };
+    let balance = s.host.call_sol(alice, s.token, &balance).unwrap()._0;
+    assert_eq!(balance, UNIT * U256::from(3));
+
+    // Accounts that skip NFTs still send whole units to accounts that don't
+    s.transfer(alice, bob, UNIT);
+    assert_eq!(s.nft_balance(bob), U256::from(1));
+}
+
+#[test]
+fn test_mirror_transfer() {
+    let mut s = setup();
+    let (owner, alice, bob) = (s.owner, s.alice, s.bob);
+    s.transfer(owner, alice, UNIT * U256::from(2));
+    s.host.take_logs();
+
+    let transfer = IMirror::transferFromCall {
+        from: alice,
+        to: bob,
+        id: U256::from(1),
+    };
+    let revert = s.host.call(bob, s.mirror, &transfer.encode()).unwrap_err();
+    let error = DN404Error::TransferCallerNotOwnerNorApproved(TransferCallerNotOwnerNorApproved {});
+    assert_eq!(revert, Vec::<u8>::from(error));
+
+    s.host.call_sol(alice, s.mirror, &transfer).unwrap();
+    assert_eq!(s.nft_owner(1), bob);
+    assert_eq!(s.nft_transfers(), vec![(alice, bob, U256::from(1))]);
+    for (account, balance) in [(alice, UNIT), (bob, UNIT)] {
+        let balance_of = IToken::balanceOfCall { owner: account };
+        let result = s.host.call_sol(account, s.token, &balance_of).unwrap()._0;
+        assert_eq!(result, balance);
+    }
+}
+
+#[test]
+fn test_only_paired_contracts() {
+    let mut s = setup();
+    let (owner, alice) = (s.owner, s.alice);
+    s.transfer(owner, alice, UNIT);
+
+    let transfer = IToken::nftTransferFromCall {
+        from: alice,
+        to: owner,
+        id: U256::from(1),
+        msgSender: alice,
+    };
+    let revert = s.host.call(alice, s.token, &transfer.encode()).unwrap_err();
+    let error = DN404Error::SenderNotMirror(SenderNotMirror {});
+    assert_eq!(revert, Vec::<u8>::from(error));
+
+    let log_transfers = IMirror::logTransfersCall {
+        from: vec![alice],
+        to: vec![owner],
+        ids: vec![U256::from(1)],
+    };
+    let revert = s
+        .host
+        .call(alice, s.mirror, &log_transfers.encode())
+        .unwrap_err();
+    let error = DN404MirrorError::SenderNotBase(SenderNotBase {});
+    assert_eq!(revert, Vec::<u8>::from(error));
+}
+
+#[test]
+fn test_mint_overflow() {
+    let mut s = setup();
+    let mint = IToken::mintCall {
+        to: s.alice,
+        amount: U256::MAX,
+    };
+    let revert = s.host.call(s.owner, s.token, &mint.encode()).unwrap_err();
+    let error = DN404Error::TotalSupplyOverflow(TotalSupplyOverflow {});
+    assert_eq!(revert, Vec::<u8>::from(error));
+}
@@ -0,0 +1,434 @@
+//! Tests of the ERC20 base, run on the in-memory host of `inkmate-test`.
+#![cfg(feature = "erc20")]
+extern crate alloc;
+
+use inkmate::tokens::erc20::{Approval, ERC20Error, ERC20Params, PermitExpired, Transfer, ERC20};
+use inkmate_test::{Signer, TestHost};
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+use stylus_sdk::{
+    alloy_primitives::{keccak256, Address, B256, U256},
+    alloy_sol_types::{sol, SolCall, SolError},
+    msg,
+    prelude::*,
+};
+
+struct TokenParams;
+
+impl ERC20Params for TokenParams {
+    const NAME: &'static str = "Token";
+    const SYMBOL: &'static str = "TKN";
+    const DECIMALS: u8 = 18;
+}
+
+sol_storage! {
+    struct Token {
+        #[borrow]
+        ERC20<TokenParams> erc20;
+    }
+}
+
+unsafe impl TopLevelStorage for Token {}
+
+#[external]
+#[inherit(ERC20<TokenParams>)]
+impl Token {
+    pub fn mint(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        ERC20::_mint(&mut self.erc20, msg::sender(), amount)?;
+        Ok(())
+    }
+
+    pub fn burn(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        ERC20::_burn(&mut self.erc20, msg::sender(), amount)?;
+        Ok(())
+    }
+}
+
+sol! {
+    function name() returns (string);
+    function decimals() returns (uint8);
+    function totalSupply() returns (uint256);
+    function balanceOf(address owner) returns (uint256);
+    function allowance(address owner, address spender) returns (uint256);
+    function transfer(address to, uint256 amount) returns (bool);
+    function approve(address spender, uint256 amount) returns (bool);
+    function transferFrom(address from, address to, uint256 amount) returns (bool);
+    function nonces(address owner) returns (uint256);
+    function DOMAIN_SEPARATOR() returns (bytes32);
+    function permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s);
+    function mint(uint256 amount);
+    function burn(uint256 amount);
+}
+
+fn setup() -> (TestHost, Address, Address, Address) {
+    let mut host = TestHost::new();
+    let token = host.deploy::<Token>();
+    let alice = TestHost::address("alice");
+    let bob = TestHost::address("bob");
+    host.call_sol(
+        alice,
+        token,
+        &mintCall {
+            amount: U256::from(100),
+        },
+    )
+    .unwrap();
+    host.take_logs();
+    (host, token, alice, bob)
+}
+
+fn balance_of(host: &mut TestHost, token: Address, owner: Address) -> U256 {
+    host.call_sol(owner, token, &balanceOfCall { owner })
+        .unwrap()
+        ._0
+}
+
+#[test]
+fn test_metadata() {
+    let (mut host, token, alice, _) = setup();
+    assert_eq!(
+        host.call_sol(alice, token, &nameCall {}).unwrap()._0,
+        "Token"
+    );
+    assert_eq!(
+        host.call_sol(alice, token, &decimalsCall {}).unwrap()._0,
+        18
+    );
+}
+
+#[test]
+fn test_mint_and_burn() {
+    let (mut host, token, alice, _) = setup();
+    let burn = burnCall {
+        amount: U256::from(40),
+    };
+    host.call_sol(alice, token, &burn).unwrap();
+
+    assert_eq!(balance_of(&mut host, token, alice), U256::from(60));
+    let supply = host.call_sol(alice, token, &totalSupplyCall {}).unwrap()._0;
+    assert_eq!(supply, U256::from(60));
+    let logs = host.take_logs();
+    let event = logs[0].decode::<Transfer>().unwrap();
+    assert_eq!(
+        (event.from, event.to, event.value),
+        (alice, Address::ZERO, U256::from(40))
+    );
+}
+
+#[test]
+fn test_storage_slots() {
+    let (host, token, _, _) = setup();
+    // The total supply is the first field of the token, at its namespace with `erc7201`
+    let slot = if cfg!(feature = "erc7201") {
+        inkmate::erc7201_slot!("inkmate.storage.ERC20")
+    } else {
+        B256::ZERO
+    };
+    assert_eq!(host.storage(token, slot), B256::from(U256::from(100)));
+}
+
+#[test]
+fn test_transfer() {
+    let (mut host, token, alice, bob) = setup();
+    let transfer = transferCall {
+        to: bob,
+        amount: U256::from(30),
+    };
+    assert!(host.call_sol(alice, token, &transfer).unwrap()._0);
+
+    assert_eq!(balance_of(&mut host, token, alice), U256::from(70));
+    assert_eq!(balance_of(&mut host, token, bob), U256::from(30));
+    let logs = host.take_logs();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].address, token);
+    let event = logs[0].decode::<Transfer>().unwrap();
+    assert_eq!(
+        (event.from, event.to, event.value),
+        (alice, bob, U256::from(30))
+    );
+}
+
+#[test]
+fn test_transfer_insufficient_balance() {
+    let (mut host, token, alice, bob) = setup();
+    let transfer = transferCall {
+        to: alice,
+        amount: U256::from(1),
+    };
+    let revert = host.call(bob, token, &transfer.encode()).unwrap_err();
+
+    let error = ERC20Error::insufficient_balance(bob, U256::ZERO, U256::from(1));
+    assert_eq!(revert, Vec::<u8>::from(error));
+    assert!(host.logs().is_empty());
+}
+
+#[test]
+fn test_transfer_from() {
+    let (mut host, token, alice, bob) = setup();
+    let approve = approveCall {
+        spender: bob,
+        amount: U256::from(50),
+    };
+    host.call_sol(alice, token, &approve).unwrap();
+    let transfer = transferFromCall {
+        from: alice,
+        to: bob,
+        amount: U256::from(20),
+    };
+    host.call_sol(bob, token, &transfer).unwrap();
+
+    let allowance = allowanceCall {
+        owner: alice,
+        spender: bob,
+    };
+    let remaining = host.call_sol(alice, token, &allowance).unwrap()._0;
+    assert_eq!(remaining, U256::from(30));
+    assert_eq!(balance_of(&mut host, token, bob), U256::from(20));
+    let logs = host.take_logs();
+    let event = logs[0].decode::<Approval>().unwrap();
+    assert_eq!(
+        (event.owner, event.spender, event.value),
+        (alice, bob, U256::from(50))
+    );
+    assert!(logs[1].decode::<Transfer>().is_some());
+}
+
+#[test]
+fn test_transfer_from_insufficient_allowance() {
+    let (mut host, token, alice, bob) = setup();
+    let transfer = transferFromCall {
+        from: alice,
+        to: bob,
+        amount: U256::from(1),
+    };
+    let revert = host.call(bob, token, &transfer.encode()).unwrap_err();
+
+    let error = ERC20Error::insufficient_allowance(alice, bob, U256::ZERO, U256::from(1));
+    assert_eq!(revert, Vec::<u8>::from(error));
+    assert_eq!(balance_of(&mut host, token, alice), U256::from(100));
+}
+
+#[test]
+fn test_permit() {
+    let (mut host, token, _, bob) = setup();
+    let owner = Signer::new("owner");
+    let deadline = U256::from(host.block_timestamp() + 60);
+
+    let domain = host
+        .call_sol(bob, token, &DOMAIN_SEPARATORCall {})
+        .unwrap()
+        ._0;
+    let typehash = inkmate::const_keccak!(
+        "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)"
+    );
+    let struct_hash = keccak256(
+        [
+            typehash.as_slice(),
+            owner.address().into_word().as_slice(),
+            bob.into_word().as_slice(),
+            &U256::from(25).to_be_bytes::<32>(),
+            &U256::ZERO.to_be_bytes::<32>(),
+            &deadline.to_be_bytes::<32>(),
+        ]
+        .concat(),
+    );
+    let digest = keccak256([&[0x19, 0x01], domain.as_slice(), struct_hash.as_slice()].concat());
+    let (v, r, s) = owner.sign(digest);
+    let permit = permitCall {
+        owner: owner.address(),
+        spender: bob,
+        value: U256::from(25),
+        deadline,
+        v,
+        r: r.0,
+        s: s.0,
+    };
+    host.call_sol(bob, token, &permit).unwrap();
+
+    let allowance = allowanceCall {
+        owner: owner.address(),
+        spender: bob,
+    };
+    assert_eq!(
+        host.call_sol(bob, token, &allowance).unwrap()._0,
+        U256::from(25)
+    );
+    let nonces = noncesCall {
+        owner: owner.address(),
+    };
+    assert_eq!(
+        host.call_sol(bob, token, &nonces).unwrap()._0,
+        U256::from(1)
+    );
+
+    // The signature cannot be replayed, and expires after the deadline
+    assert!(host.call_sol(bob, token, &permit).is_err());
+    host.advance(120);
+    let revert = host.call(bob, token, &permit.encode()).unwrap_err();
+    assert_eq!(revert, PermitExpired {}.encode());
+}
+
+// Invariants checked against a model of the token, over random sequences of operations
+
+/// The accounts used by the operations
+const USERS: usize = 3;
+
+#[derive(Debug, Clone)]
+enum Op {
+    Mint(usize, U256),
+    Burn(usize, U256),
+    Transfer(usize, usize, U256),
+    Approve(usize, usize, U256),
+    TransferFrom(usize, usize, usize, U256),
+}
+
+fn user() -> impl Strategy<Value = usize> {
+    0..USERS
+}
+
+fn amount() -> impl Strategy<Value = U256> {
+    (0u64..200).prop_map(U256::from)
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    let allowance = prop_oneof![4 => amount(), 1 => Just(U256::MAX)];
+    prop_oneof![
+        (user(), amount()).prop_map(|(user, amount)| Op::Mint(user, amount)),
+        (user(), amount()).prop_map(|(user, amount)| Op::Burn(user, amount)),
+        (user(), user(), amount()).prop_map(|(from, to, amount)| Op::Transfer(from, to, amount)),
+        (user(), user(), allowance)
+            .prop_map(|(owner, spender, amount)| Op::Approve(owner, spender, amount)),
+        (user(), user(), user(), amount()).prop_map(|(spender, from, to, amount)| {
+            Op::TransferFrom(spender, from, to, amount)
+        }),
+    ]
+}
+
+/// The expected state of the token
+#[derive(Default)]
+struct Model {
+    balances: [U256; USERS],
+    allowances: [[U256; USERS]; USERS],
+}
+
+impl Model {
+    /// Applies `op` to the model, and returns whether it succeeds.
+    fn apply(&mut self, op: &Op) -> bool {
+        match *op {
+            Op::Mint(user, amount) => self.balances[user] += amount,
+            Op::Burn(user, amount) => {
+                if self.balances[user] < amount {
+                    return false;
+                }
+                self.balances[user] -= amount;
+            }
+            Op::Transfer(from, to, amount) => return self.transfer(from, to, amount),
+            Op::Approve(owner, spender, amount) => self.allowances[owner][spender] = amount,
+            Op::TransferFrom(spender, from, to, amount) => {
+                let allowance = self.allowances[from][spender];
+                if allowance < amount || self.balances[from] < amount {
+                    return false;
+                }
+                if allowance != U256::MAX {
+                    self.allowances[from][spender] -= amount;
+                }
+                return self.transfer(from, to, amount);
+            }
+        }
+        true
+    }
+
+    fn transfer(&mut self, from: usize, to: usize, amount: U256) -> bool {
+        if self.balances[from] < amount {
+            return false;
+        }
+        self.balances[from] -= amount;
+        self.balances[to] += amount;
+        true
+    }
+}
+
+fn run_ops(ops: &[Op]) -> Result<(), TestCaseError> {
+    let mut host = TestHost::new();
+    let token = host.deploy::<Token>();
+    let users: Vec<Address> = (0..USERS)
+        .map(|i| TestHost::address(&format!("user{i}")))
+        .collect();
+    let mut model = Model::default();
+
+    for op in ops {
+        let allowance_before = match *op {
+            Op::TransferFrom(spender, from, ..) => {
+                Some(allowance_of(&mut host, token, users[from], users[spender]))
+            }
+            _ => None,
+        };
+        let result = match *op {
+            Op::Mint(user, amount) => host.call(users[user], token, &mintCall { amount }.encode()),
+            Op::Burn(user, amount) => host.call(users[user], token, &burnCall { amount }.encode()),
+            Op::Transfer(from, to, amount) => {
+                let to = users[to];
+                host.call(users[from], token, &transferCall { to, amount }.encode())
+            }
+            Op::Approve(owner, spender, amount) => {
+                let spender = users[spender];
+                host.call(
+                    users[owner],
+                    token,
+                    &approveCall { spender, amount }.encode(),
+                )
+            }
+            Op::TransferFrom(spender, from, to, amount) => {
+                let call = transferFromCall {
+                    from: users[from],
+                    to: users[to],
+                    amount,
+                };
+                host.call(users[spender], token, &call.encode())
+            }
+        };
+        let success = model.apply(op);
+        prop_assert_eq!(result.is_ok(), success, "{:?}", op);
+
+        // The sum of the balances is the total supply
+        let mut sum = U256::ZERO;
+        for (user, expected) in users.iter().zip(model.balances) {
+            let balance = balance_of(&mut host, token, *user);
+            prop_assert_eq!(balance, expected);
+            sum += balance;
+        }
+        let supply = host
+            .call_sol(users[0], token, &totalSupplyCall {})
+            .unwrap()
+            ._0;
+        prop_assert_eq!(supply, sum);
+
+        // Spending an allowance decreases it by the amount spent, unless it is infinite
+        if let (Op::TransferFrom(spender, from, _, amount), Some(before)) = (op, allowance_before) {
+            let after = allowance_of(&mut host, token, users[*from], users[*spender]);
+            let expected = if success && before != U256::MAX {
+                before - amount
+            } else {
+                before
+            };
+            prop_assert_eq!(after, expected);
+        }
+    }
+    Ok(())
+}
+
+fn allowance_of(host: &mut TestHost, token: Address, owner: Address, spender: Address) -> U256 {
+    host.call_sol(owner, token, &allowanceCall { owner, spender })
+        .unwrap()
+        ._0
+}
+
+proptest! {
+    // Each call forks a process, so fewer cases are run than the default
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn test_invariants(ops in prop::collection::vec(op(), 1..24)) {
+        run_ops(&ops)?;
+    }
+}
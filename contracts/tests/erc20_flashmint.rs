@@ -0,0 +1,314 @@
+//! Tests of the ERC20FlashMint extension, run on the in-memory host of `inkmate-test`.
+#![cfg(feature = "erc20-flashmint")]
+extern crate alloc;
+
+use core::borrow::{Borrow, BorrowMut};
+use inkmate::tokens::erc20::{ERC20Error, ERC20Params, Transfer, ERC20};
+use inkmate::tokens::extensions::erc20_flashmint::{
+    ERC20FlashMint, ERC20FlashMintError, ERC20FlashMintParams, ERC3156ExceededMaxLoan,
+    ERC3156InvalidReceiver, ON_FLASH_LOAN_RETURN,
+};
+use inkmate_test::TestHost;
+use stylus_sdk::{
+    abi::{Bytes, Router},
+    alloy_primitives::{Address, B256, U256},
+    alloy_sol_types::{sol, SolCall},
+    prelude::*,
+};
+
+struct FreeParams;
+
+impl ERC20Params for FreeParams {
+    const NAME: &'static str = "Free";
+    const SYMBOL: &'static str = "FREE";
+    const DECIMALS: u8 = 18;
+}
+
+impl ERC20FlashMintParams for FreeParams {}
+
+struct FeeParams;
+
+impl ERC20Params for FeeParams {
+    const NAME: &'static str = "Fee";
+    const SYMBOL: &'static str = "FEE";
+    const DECIMALS: u8 = 18;
+}
+
+/// Charges 1% of every loan, sent to the treasury
+impl ERC20FlashMintParams for FeeParams {
+    fn flash_fee(amount: U256) -> U256 {
+        amount / U256::from(100)
+    }
+
+    fn flash_fee_receiver() -> Address {
+        TestHost::address("treasury")
+    }
+}
+
+macro_rules! lender {
+    ($name:ident, $params:ty) => {
+        sol_storage! {
+            struct $name {
+                #[borrow]
+                ERC20FlashMint<$params> flashmint;
+            }
+        }
+
+        unsafe impl TopLevelStorage for $name {}
+
+        impl Borrow<ERC20<$params>> for $name {
+            fn borrow(&self) -> &ERC20<$params> {
+                &self.flashmint.erc20
+            }
+        }
+
+        impl BorrowMut<ERC20<$params>> for $name {
+            fn borrow_mut(&mut self) -> &mut ERC20<$params> {
+                &mut self.flashmint.erc20
+            }
+        }
+
+        #[external]
+        #[inherit(ERC20FlashMint<$params>, ERC20<$params>)]
+        impl $name {
+            pub fn mint(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+                ERC20::_mint(&mut self.flashmint.erc20, to, amount)?;
+                Ok(())
+            }
+        }
+    };
+}
+
+lender!(FreeLender, FreeParams);
+lender!(FeeLender, FeeParams);
+
+sol_interface! {
+    interface IToken {
+        function approve(address spender, uint256 amount) external returns (bool);
+    }
+}
+
+sol_storage! {
+    /// Records the last loan it received and answers with a configurable value
+    struct Borrower {
+        bytes32 reply;
+        address initiator;
+        uint256 amount;
+        uint256 fee;
+    }
+}
+
+unsafe impl TopLevelStorage for Borrower {}
+
+#[external]
+impl Borrower {
+    pub fn set_reply(&mut self, reply: B256) {
+        self.reply.set(reply);
+    }
+
+    pub fn approve_lender(&mut self, token: Address, amount: U256) -> Result<(), Vec<u8>> {
+        IToken::new(token).approve(&mut *self, token, amount)?;
+        Ok(())
+    }
+
+    pub fn last_loan(&self) -> (Address, U256, U256) {
+        (self.initiator.get(), self.amount.get(), self.fee.get())
+    }
+
+    pub fn on_flash_loan(
+        &mut self,
+        initiator: Address,
+        _token: Address,
+        amount: U256,
+        fee: U256,
+        _data: Bytes,
+    ) -> Result<B256, Vec<u8>> {
+        self.initiator.set(initiator);
+        self.amount.set(amount);
+        self.fee.set(fee);
+        Ok(self.reply.get())
+    }
+}
+
+sol! {
+    function totalSupply() returns (uint256);
+    function balanceOf(address owner) returns (uint256);
+    function mint(address to, uint256 amount);
+    function maxFlashLoan(address token) returns (uint256);
+    function flashFee(address token, uint256 amount) returns (uint256);
+    function flashLoan(address receiver, address token, uint256 amount, bytes data) returns (bool);
+    function setReply(bytes32 reply);
+    function approveLender(address token, uint256 amount);
+    function lastLoan() returns (address, uint256, uint256);
+}
+
+struct Setup {
+    host: TestHost,
+    token: Address,
+    borrower: Address,
+    alice: Address,
+}
+
+fn setup<S>() -> Setup
+where
+    S: StorageType + TopLevelStorage + Router<S> + BorrowMut<<S as Router<S>>::Storage>,
+{
+    let mut host = TestHost::new();
+    let token = host.deploy::<S>();
+    let borrower = host.deploy::<Borrower>();
+    let alice = TestHost::address("alice");
+    let reply = setReplyCall {
+        reply: ON_FLASH_LOAN_RETURN.0,
+    };
+    host.call_sol(alice, borrower, &reply).unwrap();
+    Setup {
+        host,
+        token,
+        borrower,
+        alice,
+    }
+}
+
+impl Setup {
+    fn approve(&mut self, amount: U256) {
+        let approve = approveLenderCall {
+            token: self.token,
+            amount,
+        };
+        self.host
+            .call_sol(self.alice, self.borrower, &approve)
+            .unwrap();
+    }
+
+    fn loan(&mut self, amount: U256) -> Result<Vec<u8>, Vec<u8>> {
+        let loan = flashLoanCall {
+            receiver: self.borrower,
+            token: self.token,
+            amount,
+            data: Default::default(),
+        };
+        self.host.call(self.alice, self.token, &loan.encode())
+    }
+
+    fn balance_of(&mut self, owner: Address) -> U256 {
+        self.host
+            .call_sol(owner, self.token, &balanceOfCall { owner })
+            .unwrap()
+            ._0
+    }
+
+    fn total_supply(&mut self) -> U256 {
+        self.host
+            .call_sol(self.alice, self.token, &totalSupplyCall {})
+            .unwrap()
+            ._0
+    }
+}
+
+#[test]
+fn test_flash_loan() {
+    let mut s = setup::<FreeLender>();
+    s.approve(U256::from(1000));
+    s.host.take_logs();
+
+    let returned = s.loan(U256::from(1000)).unwrap();
+    assert!(flashLoanCall::decode_returns(&returned, true).unwrap()._0);
+
+    // The loan reached the borrower, and was minted and burned in full
+    let (alice, borrower) = (s.alice, s.borrower);
+    let loan = s.host.call_sol(alice, borrower, &lastLoanCall {}).unwrap();
+    assert_eq!(
+        (loan._0, loan._1, loan._2),
+        (alice, U256::from(1000), U256::ZERO)
+    );
+    assert_eq!(s.balance_of(borrower), U256::ZERO);
+    assert_eq!(s.total_supply(), U256::ZERO);
+    let logs = s.host.take_logs();
+    let mint = logs[0].decode::<Transfer>().unwrap();
+    assert_eq!((mint.from, mint.to), (Address::ZERO, borrower));
+    let burn = logs[1].decode::<Transfer>().unwrap();
+    assert_eq!(
+        (burn.from, burn.to, burn.value),
+        (borrower, Address::ZERO, U256::from(1000))
+    );
+}
+
+#[test]
+fn test_flash_loan_requires_prior_approval() {
+    let mut s = setup::<FreeLender>();
+
+    let revert = s.loan(U256::from(1000)).unwrap_err();
+    let error =
+        ERC20Error::insufficient_allowance(s.borrower, s.token, U256::ZERO, U256::from(1000));
+    assert_eq!(revert, Vec::<u8>::from(error));
+    assert_eq!(s.total_supply(), U256::ZERO);
+}
+
+#[test]
+fn test_flash_loan_bad_return_value() {
+    let mut s = setup::<FreeLender>();
+    s.approve(U256::from(1000));
+    let reply = setReplyCall { reply: [0; 32] };
+    s.host.call_sol(s.alice, s.borrower, &reply).unwrap();
+
+    let revert = s.loan(U256::from(1000)).unwrap_err();
+    let error = ERC20FlashMintError::ERC3156InvalidReceiver(ERC3156InvalidReceiver {
+        receiver: s.borrower,
+    });
+    assert_eq!(revert, Vec::<u8>::from(error));
+    assert_eq!(s.balance_of(s.borrower), U256::ZERO);
+    assert_eq!(s.total_supply(), U256::ZERO);
+}
+
+#[test]
+fn test_flash_loan_exceeds_max() {
+    let mut s = setup::<FreeLender>();
+    let mint = mintCall {
+        to: s.alice,
+        amount: U256::from(100),
+    };
+    s.host.call_sol(s.alice, s.token, &mint).unwrap();
+    let max_loan = maxFlashLoanCall { token: s.token };
+    let max_loan = s.host.call_sol(s.alice, s.token, &max_loan).unwrap()._0;
+    assert_eq!(max_loan, U256::MAX - U256::from(100));
+
+    let revert = s.loan(max_loan + U256::from(1)).unwrap_err();
+    let error =
+        ERC20FlashMintError::ERC3156ExceededMaxLoan(ERC3156ExceededMaxLoan { maxLoan: max_loan });
+    assert_eq!(revert, Vec::<u8>::from(error));
+
+    // Other tokens cannot be lent at all
+    let other = maxFlashLoanCall { token: s.alice };
+    let other = s.host.call_sol(s.alice, s.token, &other).unwrap()._0;
+    assert_eq!(other, U256::ZERO);
+}
+
+#[test]
+fn test_flash_loan_fee_receiver() {
+    let mut s = setup::<FeeLender>();
+    let treasury = TestHost::address("treasury");
+    // The borrower holds the fee up front, since the loan itself must be repaid in full
+    let mint = mintCall {
+        to: s.borrower,
+        amount: U256::from(10),
+    };
+    s.host.call_sol(s.alice, s.token, &mint).unwrap();
+    let fee = flashFeeCall {
+        token: s.token,
+        amount: U256::from(1000),
+    };
+    let fee = s.host.call_sol(s.alice, s.token, &fee).unwrap()._0;
+    assert_eq!(fee, U256::from(10));
+    s.approve(U256::from(1010));
+
+    s.loan(U256::from(1000)).unwrap();
+
+    let loan = s
+        .host
+        .call_sol(s.alice, s.borrower, &lastLoanCall {})
+        .unwrap();
+    assert_eq!((loan._1, loan._2), (U256::from(1000), U256::from(10)));
+    assert_eq!(s.balance_of(s.borrower), U256::ZERO);
+    assert_eq!(s.balance_of(treasury), U256::from(10));
+    assert_eq!(s.total_supply(), U256::from(10));
+}
@@ -0,0 +1,277 @@
+//! Tests of the ERC20Rebasing base, run on the in-memory host of `inkmate-test`.
+#![cfg(feature = "erc20-rebasing")]
+extern crate alloc;
+
+use core::borrow::{Borrow, BorrowMut};
+use inkmate::access::access_control::AccessControl;
+use inkmate::tokens::erc20_rebasing::{
+    ERC20InsufficientAllowance, ERC20InsufficientBalance, ERC20Rebasing, ERC20RebasingError,
+    ERC20RebasingNoShares, ERC20RebasingParams, Rebase, Transfer, TransferShares, REBASER_ROLE,
+};
+use inkmate_test::TestHost;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolCall},
+    msg,
+    prelude::*,
+};
+
+struct TokenParams;
+
+impl ERC20RebasingParams for TokenParams {
+    const NAME: &'static str = "Staked";
+    const SYMBOL: &'static str = "STK";
+    const DECIMALS: u8 = 18;
+}
+
+sol_storage! {
+    struct Token {
+        #[borrow]
+        ERC20Rebasing<TokenParams> token;
+    }
+}
+
+unsafe impl TopLevelStorage for Token {}
+
+impl Borrow<AccessControl> for Token {
+    fn borrow(&self) -> &AccessControl {
+        &self.token.access
+    }
+}
+
+impl BorrowMut<AccessControl> for Token {
+    fn borrow_mut(&mut self) -> &mut AccessControl {
+        &mut self.token.access
+    }
+}
+
+#[external]
+#[inherit(ERC20Rebasing<TokenParams>, AccessControl)]
+impl Token {
+    pub fn initialize(&mut self) {
+        self.token.access._grant_role(REBASER_ROLE, msg::sender());
+    }
+
+    pub fn mint(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self.token._mint(to, amount)?;
+        Ok(())
+    }
+
+    pub fn burn(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        self.token._burn(msg::sender(), amount)?;
+        Ok(())
+    }
+}
+
+sol! {
+    function initialize();
+    function mint(address to, uint256 amount);
+    function burn(uint256 amount);
+    function rebase(uint256 newTotalSupply);
+    function totalSupply() returns (uint256);
+    function totalShares() returns (uint256);
+    function balanceOf(address owner) returns (uint256);
+    function sharesOf(address owner) returns (uint256);
+    function allowance(address owner, address spender) returns (uint256);
+    function approve(address spender, uint256 amount) returns (bool);
+    function transfer(address to, uint256 amount) returns (bool);
+    function transferSharesFrom(address from, address to, uint256 shares) returns (uint256);
+}
+
+struct Setup {
+    host: TestHost,
+    token: Address,
+    alice: Address,
+    bob: Address,
+}
+
+/// Mints `minted` tokens to each of alice and bob, then rebases the total supply to `supply`
+fn setup(minted: u64, supply: u64) -> Setup {
+    let mut host = TestHost::new();
+    let token = host.deploy::<Token>();
+    let alice = TestHost::address("alice");
+    let bob = TestHost::address("bob");
+    host.call_sol(alice, token, &initializeCall {}).unwrap();
+    for to in [alice, bob] {
+        let mint = mintCall {
+            to,
+            amount: U256::from(minted),
+        };
+        host.call_sol(alice, token, &mint).unwrap();
+    }
+    let rebase = rebaseCall {
+        newTotalSupply: U256::from(supply),
+    };
+    host.call_sol(alice, token, &rebase).unwrap();
+    host.take_logs();
+    Setup {
+        host,
+        token,
+        alice,
+        bob,
+    }
+}
+
+impl Setup {
+    fn balance_of(&mut self, owner: Address) -> U256 {
+        self.host
+            .call_sol(owner, self.token, &balanceOfCall { owner })
+            .unwrap()
+            ._0
+    }
+
+    fn shares_of(&mut self, owner: Address) -> U256 {
+        self.host
+            .call_sol(owner, self.token, &sharesOfCall { owner })
+            .unwrap()
+            ._0
+    }
+}
+
+#[test]
+fn test_rebase() {
+    let mut s = setup(100, 300);
+
+    assert_eq!(s.balance_of(s.alice), U256::from(150));
+    assert_eq!(s.balance_of(s.bob), U256::from(150));
+    let rebase = rebaseCall {
+        newTotalSupply: U256::from(100),
+    };
+    s.host.call_sol(s.alice, s.token, &rebase).unwrap();
+
+    assert_eq!(s.balance_of(s.alice), U256::from(50));
+    let event = s.host.take_logs()[0].decode::<Rebase>().unwrap();
+    assert_eq!(
+        (
+            event.preTotalSupply,
+            event.postTotalSupply,
+            event.totalShares
+        ),
+        (U256::from(300), U256::from(100), U256::from(200))
+    );
+}
+
+#[test]
+fn test_rebase_without_shares() {
+    let mut host = TestHost::new();
+    let token = host.deploy::<Token>();
+    let alice = TestHost::address("alice");
+    host.call_sol(alice, token, &initializeCall {}).unwrap();
+
+    let rebase = rebaseCall {
+        newTotalSupply: U256::from(100),
+    };
+    let revert = host.call(alice, token, &rebase.encode()).unwrap_err();
+    let error = ERC20RebasingError::ERC20RebasingNoShares(ERC20RebasingNoShares {});
+    assert_eq!(revert, Vec::<u8>::from(error));
+}
+
+#[test]
+fn test_transfer_rounds_down() {
+    // Each share is worth 1.5 tokens
+    let mut s = setup(100, 300);
+    let transfer = transferCall {
+        to: s.bob,
+        amount: U256::from(2),
+    };
+    s.host.call_sol(s.alice, s.token, &transfer).unwrap();
+
+    // 2 tokens are worth 1.33 shares, so only 1 share (1.5 tokens) moves
+    assert_eq!(s.shares_of(s.alice), U256::from(99));
+    assert_eq!(s.shares_of(s.bob), U256::from(101));
+    assert_eq!(s.balance_of(s.alice), U256::from(148));
+    assert_eq!(s.balance_of(s.bob), U256::from(151));
+    let logs = s.host.take_logs();
+    let event = logs[0].decode::<Transfer>().unwrap();
+    assert_eq!(event.value, U256::from(2));
+    let event = logs[1].decode::<TransferShares>().unwrap();
+    assert_eq!(event.sharesValue, U256::from(1));
+}
+
+#[test]
+fn test_transfer_shares_from_rounds_allowance_up() {
+    // Each share is worth 1.5 tokens
+    let mut s = setup(100, 300);
+    let approve = approveCall {
+        spender: s.bob,
+        amount: U256::from(1),
+    };
+    s.host.call_sol(s.alice, s.token, &approve).unwrap();
+    let transfer = transferSharesFromCall {
+        from: s.alice,
+        to: s.bob,
+        shares: U256::from(1),
+    };
+
+    let revert = s.host.call(s.bob, s.token, &transfer.encode()).unwrap_err();
+    let error = ERC20RebasingError::ERC20InsufficientAllowance(ERC20InsufficientAllowance {
+        spender: s.bob,
+        allowance: U256::from(1),
+        needed: U256::from(2),
+    });
+    assert_eq!(revert, Vec::<u8>::from(error));
+
+    let approve = approveCall {
+        spender: s.bob,
+        amount: U256::from(2),
+    };
+    s.host.call_sol(s.alice, s.token, &approve).unwrap();
+    let amount = s.host.call_sol(s.bob, s.token, &transfer).unwrap()._0;
+    assert_eq!(amount, U256::from(1));
+    assert_eq!(s.shares_of(s.bob), U256::from(101));
+    let allowance = allowanceCall {
+        owner: s.alice,
+        spender: s.bob,
+    };
+    let allowance = s.host.call_sol(s.bob, s.token, &allowance).unwrap()._0;
+    assert_eq!(allowance, U256::ZERO);
+}
+
+#[test]
+fn test_burn_after_rebase() {
+    let mut s = setup(100, 300);
+    let burn = burnCall {
+        amount: U256::from(150),
+    };
+    s.host.call_sol(s.alice, s.token, &burn).unwrap();
+
+    assert_eq!(s.shares_of(s.alice), U256::ZERO);
+    assert_eq!(s.balance_of(s.bob), U256::from(150));
+    let supply = s.host.call_sol(s.alice, s.token, &totalSupplyCall {});
+    assert_eq!(supply.unwrap()._0, U256::from(150));
+    let shares = s.host.call_sol(s.alice, s.token, &totalSharesCall {});
+    assert_eq!(shares.unwrap()._0, U256::from(100));
+}
+
+#[test]
+fn test_burn_rounds_shares_up() {
+    // Each share is worth 1.5 tokens
+    let mut s = setup(100, 300);
+    let burn = burnCall {
+        amount: U256::from(1),
+    };
+    s.host.call_sol(s.alice, s.token, &burn).unwrap();
+
+    // A whole share is redeemed for 1 token, so bob is not diluted
+    assert_eq!(s.shares_of(s.alice), U256::from(99));
+    assert_eq!(s.balance_of(s.bob), U256::from(150));
+    let supply = s.host.call_sol(s.alice, s.token, &totalSupplyCall {});
+    assert_eq!(supply.unwrap()._0, U256::from(299));
+}
+
+#[test]
+fn test_burn_exceeds_rebased_balance() {
+    // Each share is worth 0.5 tokens
+    let mut s = setup(100, 100);
+    let burn = burnCall {
+        amount: U256::from(100),
+    };
+
+    let revert = s.host.call(s.alice, s.token, &burn.encode()).unwrap_err();
+    let error = ERC20RebasingError::ERC20InsufficientBalance(ERC20InsufficientBalance {
+        sender: s.alice,
+        balance: U256::from(50),
+        needed: U256::from(100),
+    });
+    assert_eq!(revert, Vec::<u8>::from(error));
+}
@@ -0,0 +1,194 @@
+//! Tests of the ERC20Votes extension, run on the in-memory host of `inkmate-test`.
+#![cfg(feature = "erc20-votes")]
+extern crate alloc;
+
+use core::borrow::{Borrow, BorrowMut};
+use inkmate::governance::votes::{Votes, VotesParams};
+use inkmate::tokens::erc20::{ERC20Params, ERC20};
+use inkmate::tokens::extensions::erc20_votes::{
+    ERC20ExceededSafeSupply, ERC20Votes, ERC20VotesError,
+};
+use inkmate_test::TestHost;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolCall},
+    prelude::*,
+};
+
+struct TokenParams;
+
+impl ERC20Params for TokenParams {
+    const NAME: &'static str = "Token";
+    const SYMBOL: &'static str = "TKN";
+    const DECIMALS: u8 = 18;
+}
+
+impl VotesParams for TokenParams {}
+
+sol_storage! {
+    struct Token {
+        #[borrow]
+        ERC20Votes<TokenParams> votes;
+    }
+}
+
+unsafe impl TopLevelStorage for Token {}
+
+impl Borrow<Votes<TokenParams>> for Token {
+    fn borrow(&self) -> &Votes<TokenParams> {
+        &self.votes.votes
+    }
+}
+
+impl BorrowMut<Votes<TokenParams>> for Token {
+    fn borrow_mut(&mut self) -> &mut Votes<TokenParams> {
+        &mut self.votes.votes
+    }
+}
+
+impl Borrow<ERC20<TokenParams>> for Token {
+    fn borrow(&self) -> &ERC20<TokenParams> {
+        &self.votes.erc20
+    }
+}
+
+impl BorrowMut<ERC20<TokenParams>> for Token {
+    fn borrow_mut(&mut self) -> &mut ERC20<TokenParams> {
+        &mut self.votes.erc20
+    }
+}
+
+#[external]
+#[inherit(ERC20Votes<TokenParams>, Votes<TokenParams>, ERC20<TokenParams>)]
+impl Token {
+    pub fn mint(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        ERC20::_mint(&mut self.votes, to, amount)
+    }
+
+    pub fn burn(&mut self, from: Address, amount: U256) -> Result<(), Vec<u8>> {
+        ERC20::_burn(&mut self.votes, from, amount)
+    }
+
+    pub fn force_transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        amount: U256,
+    ) -> Result<(), Vec<u8>> {
+        ERC20::_transfer(&mut self.votes, from, to, amount)
+    }
+}
+
+sol! {
+    function balanceOf(address owner) returns (uint256);
+    function transfer(address to, uint256 amount) returns (bool);
+    function approve(address spender, uint256 amount) returns (bool);
+    function transferFrom(address from, address to, uint256 amount) returns (bool);
+    function delegate(address delegatee);
+    function getVotes(address account) returns (uint256);
+    function mint(address to, uint256 amount);
+    function burn(address from, uint256 amount);
+    function forceTransfer(address from, address to, uint256 amount);
+}
+
+fn setup() -> (TestHost, Address, Address, Address) {
+    let mut host = TestHost::new();
+    let token = host.deploy::<Token>();
+    let alice = TestHost::address("alice");
+    let bob = TestHost::address("bob");
+    for account in [alice, bob] {
+        host.call_sol(account, token, &delegateCall { delegatee: account })
+            .unwrap();
+    }
+    let mint = mintCall {
+        to: alice,
+        amount: U256::from(100),
+    };
+    host.call_sol(alice, token, &mint).unwrap();
+    host.take_logs();
+    (host, token, alice, bob)
+}
+
+fn votes_of(host: &mut TestHost, token: Address, account: Address) -> U256 {
+    host.call_sol(account, token, &getVotesCall { account })
+        .unwrap()
+        ._0
+}
+
+#[test]
+fn test_votes_follow_transfers() {
+    let (mut host, token, alice, bob) = setup();
+    assert_eq!(votes_of(&mut host, token, alice), U256::from(100));
+
+    let transfer = transferCall {
+        to: bob,
+        amount: U256::from(30),
+    };
+    host.call_sol(alice, token, &transfer).unwrap();
+    assert_eq!(votes_of(&mut host, token, alice), U256::from(70));
+    assert_eq!(votes_of(&mut host, token, bob), U256::from(30));
+
+    let burn = burnCall {
+        from: bob,
+        amount: U256::from(20),
+    };
+    host.call_sol(bob, token, &burn).unwrap();
+    assert_eq!(votes_of(&mut host, token, bob), U256::from(10));
+}
+
+#[test]
+fn test_votes_follow_transfers_from() {
+    let (mut host, token, alice, bob) = setup();
+    let approve = approveCall {
+        spender: bob,
+        amount: U256::from(50),
+    };
+    host.call_sol(alice, token, &approve).unwrap();
+
+    // Each transfer must see the checkpoints written by the previous one
+    let transfer = transferFromCall {
+        from: alice,
+        to: bob,
+        amount: U256::from(25),
+    };
+    for _ in 0..2 {
+        host.call_sol(bob, token, &transfer).unwrap();
+    }
+    assert_eq!(votes_of(&mut host, token, alice), U256::from(50));
+    assert_eq!(votes_of(&mut host, token, bob), U256::from(50));
+}
+
+#[test]
+fn test_internal_transfer_moves_votes() {
+    let (mut host, token, alice, bob) = setup();
+    let transfer = forceTransferCall {
+        from: alice,
+        to: bob,
+        amount: U256::from(40),
+    };
+    host.call_sol(bob, token, &transfer).unwrap();
+    assert_eq!(votes_of(&mut host, token, alice), U256::from(60));
+    assert_eq!(votes_of(&mut host, token, bob), U256::from(40));
+}
+
+#[test]
+fn test_mint_exceeding_safe_supply() {
+    let (mut host, token, alice, _) = setup();
+    let cap = Votes::<TokenParams>::_max_supply();
+    let mint = mintCall {
+        to: alice,
+        amount: cap,
+    };
+    let revert = host.call(alice, token, &mint.encode()).unwrap_err();
+
+    let error = ERC20VotesError::ERC20ExceededSafeSupply(ERC20ExceededSafeSupply {
+        increasedSupply: cap + U256::from(100),
+        cap,
+    });
+    assert_eq!(revert, Vec::<u8>::from(error));
+    let balance = host
+        .call_sol(alice, token, &balanceOfCall { owner: alice })
+        .unwrap()
+        ._0;
+    assert_eq!(balance, U256::from(100));
+}
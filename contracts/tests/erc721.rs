@@ -0,0 +1,621 @@
+//! Tests of the ERC721 base, run on the in-memory host of `inkmate-test`.
+#![cfg(feature = "erc721")]
+extern crate alloc;
+
+use inkmate::tokens::erc721::{
+    ConsecutiveTransfer, ERC721Error, ERC721ForbiddenBatchMint, ERC721Hooks, ERC721Params,
+    Transfer, ERC721,
+};
+use inkmate_test::TestHost;
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+use samples::receivers::{
+    ERC721ReceiverMock, Received, ReentrantReceiverMock, RevertingReceiverMock,
+    WrongSelectorReceiverMock,
+};
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolCall},
+    msg,
+    prelude::*,
+};
+
+struct NFTParams;
+
+impl ERC721Params for NFTParams {
+    const NAME: &'static str = "NFT";
+    const SYMBOL: &'static str = "NFT";
+
+    fn token_uri(_id: U256) -> String {
+        "ipfs://".into()
+    }
+}
+
+sol_storage! {
+    struct NFT {
+        #[borrow]
+        ERC721<NFTParams> erc721;
+    }
+}
+
+unsafe impl TopLevelStorage for NFT {}
+
+#[external]
+#[inherit(ERC721<NFTParams>)]
+impl NFT {
+    pub fn mint(&mut self, id: U256) -> Result<(), Vec<u8>> {
+        ERC721::_mint(&mut self.erc721, msg::sender(), id)?;
+        Ok(())
+    }
+
+    pub fn safe_mint(&mut self, to: Address, id: U256) -> Result<(), Vec<u8>> {
+        ERC721::<NFTParams>::_safe_mint::<_, ERC721<NFTParams>>(self, to, id, vec![])?;
+        Ok(())
+    }
+
+    pub fn mint_consecutive(&mut self, qty: U256) -> Result<(), Vec<u8>> {
+        ERC721::_mint_consecutive(&mut self.erc721, msg::sender(), qty)?;
+        Ok(())
+    }
+
+    pub fn burn(&mut self, id: U256) -> Result<(), Vec<u8>> {
+        ERC721::_burn(&mut self.erc721, id)?;
+        Ok(())
+    }
+}
+
+sol_storage! {
+    struct HookedNFT {
+        #[borrow]
+        ERC721<NFTParams> erc721;
+        uint256 before_calls;
+        uint256 after_calls;
+        uint256 first_id;
+        uint256 batch_size;
+    }
+}
+
+unsafe impl TopLevelStorage for HookedNFT {}
+
+/// Records the hook calls, and checks that the after hook sees the writes of the before hook
+impl ERC721Hooks<NFTParams> for HookedNFT {
+    fn before_token_transfer(
+        &mut self,
+        _from: Address,
+        _to: Address,
+        first_id: U256,
+        batch_size: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.before_calls
+            .set(self.before_calls.get() + U256::from(1));
+        self.first_id.set(first_id);
+        self.batch_size.set(batch_size);
+        Ok(())
+    }
+
+    fn after_token_transfer(
+        &mut self,
+        _from: Address,
+        _to: Address,
+        first_id: U256,
+        batch_size: U256,
+    ) -> Result<(), Vec<u8>> {
+        if (self.first_id.get(), self.batch_size.get()) != (first_id, batch_size) {
+            return Err(b"stale hook storage".to_vec());
+        }
+        self.after_calls.set(self.after_calls.get() + U256::from(1));
+        Ok(())
+    }
+}
+
+#[external]
+#[inherit(ERC721<NFTParams>)]
+impl HookedNFT {
+    pub fn mint_consecutive(&mut self, qty: U256) -> Result<(), Vec<u8>> {
+        ERC721::_mint_consecutive(self, msg::sender(), qty)?;
+        Ok(())
+    }
+
+    pub fn hook_calls(&self) -> (U256, U256, U256, U256) {
+        (
+            self.before_calls.get(),
+            self.after_calls.get(),
+            self.first_id.get(),
+            self.batch_size.get(),
+        )
+    }
+}
+
+sol! {
+    function hookCalls() returns (uint256, uint256, uint256, uint256);
+}
+
+sol! {
+    function balanceOf(address owner) returns (uint256);
+    function ownerOf(uint256 id) returns (address);
+    function getApproved(uint256 id) returns (address);
+    function approve(address approved, uint256 id);
+    function setApprovalForAll(address operator, bool approved);
+    function transferFrom(address from, address to, uint256 id);
+    function safeTransferFrom(address from, address to, uint256 id);
+    function onERC721Received(address operator, address from, uint256 id, bytes data) returns (bytes4);
+    function mint(uint256 id);
+    function safeMint(address to, uint256 id);
+    function received() returns (uint256);
+    function setReentryData(bytes data);
+    function mintConsecutive(uint256 qty);
+    function burn(uint256 id);
+}
+
+fn setup() -> (TestHost, Address, Address, Address) {
+    let mut host = TestHost::new();
+    let nft = host.deploy::<NFT>();
+    let alice = TestHost::address("alice");
+    let bob = TestHost::address("bob");
+    host.call_sol(alice, nft, &mintCall { id: U256::from(1) })
+        .unwrap();
+    host.take_logs();
+    (host, nft, alice, bob)
+}
+
+fn owner_of(host: &mut TestHost, nft: Address, id: u64) -> Address {
+    let id = U256::from(id);
+    host.call_sol(Address::ZERO, nft, &ownerOfCall { id })
+        .unwrap()
+        ._0
+}
+
+fn balance_of(host: &mut TestHost, nft: Address, owner: Address) -> U256 {
+    host.call_sol(owner, nft, &balanceOfCall { owner })
+        .unwrap()
+        ._0
+}
+
+#[test]
+fn test_mint_and_burn() {
+    let (mut host, nft, alice, bob) = setup();
+    assert_eq!(owner_of(&mut host, nft, 1), alice);
+    assert_eq!(balance_of(&mut host, nft, alice), U256::from(1));
+
+    let burn = burnCall { id: U256::from(1) };
+    let revert = host.call(bob, nft, &burn.encode()).unwrap_err();
+    let error = ERC721Error::insufficient_approval(bob, U256::from(1), alice);
+    assert_eq!(revert, Vec::<u8>::from(error));
+    host.call_sol(alice, nft, &burn).unwrap();
+
+    assert_eq!(balance_of(&mut host, nft, alice), U256::ZERO);
+    assert!(host
+        .call(alice, nft, &ownerOfCall { id: U256::from(1) }.encode())
+        .is_err());
+    let event = host.logs()[0].decode::<Transfer>().unwrap();
+    assert_eq!((event.from, event.to), (alice, Address::ZERO));
+}
+
+#[test]
+fn test_transfer_from() {
+    let (mut host, nft, alice, bob) = setup();
+    let transfer = transferFromCall {
+        from: alice,
+        to: bob,
+        id: U256::from(1),
+    };
+    host.call_sol(alice, nft, &transfer).unwrap();
+
+    assert_eq!(owner_of(&mut host, nft, 1), bob);
+    assert_eq!(balance_of(&mut host, nft, alice), U256::ZERO);
+    assert_eq!(balance_of(&mut host, nft, bob), U256::from(1));
+    let event = host.logs()[0].decode::<Transfer>().unwrap();
+    assert_eq!(
+        (event.from, event.to, event.id),
+        (alice, bob, U256::from(1))
+    );
+
+    // The previous owner cannot transfer the token anymore
+    let revert = host.call(alice, nft, &transfer.encode()).unwrap_err();
+    let error = ERC721Error::incorrect_owner(alice, U256::from(1), bob);
+    assert_eq!(revert, Vec::<u8>::from(error));
+}
+
+#[test]
+fn test_approvals() {
+    let (mut host, nft, alice, bob) = setup();
+    let carol = TestHost::address("carol");
+    let transfer = transferFromCall {
+        from: alice,
+        to: carol,
+        id: U256::from(1),
+    };
+    let revert = host.call(bob, nft, &transfer.encode()).unwrap_err();
+    let error = ERC721Error::insufficient_approval(bob, U256::from(1), alice);
+    assert_eq!(revert, Vec::<u8>::from(error));
+
+    let approve = approveCall {
+        approved: bob,
+        id: U256::from(1),
+    };
+    host.call_sol(alice, nft, &approve).unwrap();
+    let approved = getApprovedCall { id: U256::from(1) };
+    assert_eq!(host.call_sol(alice, nft, &approved).unwrap()._0, bob);
+    host.call_sol(bob, nft, &transfer).unwrap();
+
+    // Transfers clear the approval of the token
+    assert_eq!(owner_of(&mut host, nft, 1), carol);
+    assert_eq!(
+        host.call_sol(alice, nft, &approved).unwrap()._0,
+        Address::ZERO
+    );
+
+    let approve_all = setApprovalForAllCall {
+        operator: bob,
+        approved: true,
+    };
+    host.call_sol(carol, nft, &approve_all).unwrap();
+    let transfer = transferFromCall {
+        from: carol,
+        to: alice,
+        id: U256::from(1),
+    };
+    host.call_sol(bob, nft, &transfer).unwrap();
+    assert_eq!(owner_of(&mut host, nft, 1), alice);
+}
+
+#[test]
+fn test_safe_transfer_to_receiver() {
+    let (mut host, nft, alice, _) = setup();
+    let receiver = TestHost::address("receiver");
+    host.set_code(receiver, move |_, msg| {
+        let call = onERC721ReceivedCall::decode(&msg.data, true).unwrap();
+        assert_eq!((msg.sender, call.operator, call.from), (nft, alice, alice));
+        Ok(onERC721ReceivedCall::encode_returns(&(
+            onERC721ReceivedCall::SELECTOR,
+        )))
+    });
+
+    let transfer = safeTransferFromCall {
+        from: alice,
+        to: receiver,
+        id: U256::from(1),
+    };
+    host.call_sol(alice, nft, &transfer).unwrap();
+    assert_eq!(owner_of(&mut host, nft, 1), receiver);
+}
+
+#[test]
+fn test_safe_transfer_to_refusing_receiver() {
+    let (mut host, nft, alice, _) = setup();
+    let receiver = TestHost::address("receiver");
+    host.set_code(receiver, |_, _| Err(vec![]));
+
+    let transfer = safeTransferFromCall {
+        from: alice,
+        to: receiver,
+        id: U256::from(1),
+    };
+    let revert = host.call(alice, nft, &transfer.encode()).unwrap_err();
+    let error = ERC721Error::receiver_refused(receiver, U256::from(1));
+    assert_eq!(revert, Vec::<u8>::from(error));
+
+    // The transfer is reverted
+    assert_eq!(owner_of(&mut host, nft, 1), alice);
+    assert!(host.logs().is_empty());
+}
+
+#[test]
+fn test_safe_transfer_to_receiver_mock() {
+    let (mut host, nft, alice, _) = setup();
+    let receiver = host.deploy::<ERC721ReceiverMock>();
+    let transfer = safeTransferFromCall {
+        from: alice,
+        to: receiver,
+        id: U256::from(1),
+    };
+    host.call_sol(alice, nft, &transfer).unwrap();
+
+    assert_eq!(owner_of(&mut host, nft, 1), receiver);
+    let received = host.call_sol(alice, receiver, &receivedCall {}).unwrap()._0;
+    assert_eq!(received, U256::from(1));
+    let event = host.logs()[1].decode::<Received>().unwrap();
+    assert_eq!(
+        (event.operator, event.from, event.tokenId),
+        (alice, alice, U256::from(1))
+    );
+}
+
+#[test]
+fn test_safe_mint_to_receiver_mock() {
+    let (mut host, nft, alice, _) = setup();
+    let receiver = host.deploy::<ERC721ReceiverMock>();
+    let mint = safeMintCall {
+        to: receiver,
+        id: U256::from(2),
+    };
+    host.call_sol(alice, nft, &mint).unwrap();
+
+    assert_eq!(owner_of(&mut host, nft, 2), receiver);
+    let event = host.logs()[1].decode::<Received>().unwrap();
+    assert_eq!((event.operator, event.from), (alice, Address::ZERO));
+}
+
+#[test]
+fn test_safe_transfer_to_rejecting_receivers() {
+    let (mut host, nft, alice, _) = setup();
+    let reverting = host.deploy::<RevertingReceiverMock>();
+    let wrong_selector = host.deploy::<WrongSelectorReceiverMock>();
+
+    for receiver in [reverting, wrong_selector] {
+        let transfer = safeTransferFromCall {
+            from: alice,
+            to: receiver,
+            id: U256::from(1),
+        };
+        let revert = host.call(alice, nft, &transfer.encode()).unwrap_err();
+        let error = ERC721Error::receiver_refused(receiver, U256::from(1));
+        assert_eq!(revert, Vec::<u8>::from(error));
+
+        let mint = safeMintCall {
+            to: receiver,
+            id: U256::from(2),
+        };
+        let revert = host.call(alice, nft, &mint.encode()).unwrap_err();
+        let error = ERC721Error::receiver_refused(receiver, U256::from(2));
+        assert_eq!(revert, Vec::<u8>::from(error));
+    }
+
+    assert_eq!(owner_of(&mut host, nft, 1), alice);
+    assert!(host.logs().is_empty());
+}
+
+#[test]
+fn test_safe_mint_to_reentrant_receiver() {
+    let (mut host, nft, alice, _) = setup();
+    let receiver = host.deploy::<ReentrantReceiverMock>();
+    // The receiver mints another token when it receives one
+    let reentry = setReentryDataCall {
+        data: mintCall { id: U256::from(3) }.encode(),
+    };
+    host.call_sol(alice, receiver, &reentry).unwrap();
+
+    // The reentrant call reverts, so the receiver refuses the token
+    let mint = safeMintCall {
+        to: receiver,
+        id: U256::from(2),
+    };
+    let revert = host.call(alice, nft, &mint.encode()).unwrap_err();
+    let error = ERC721Error::receiver_refused(receiver, U256::from(2));
+    assert_eq!(revert, Vec::<u8>::from(error));
+
+    // Neither token is minted
+    for id in [2, 3] {
+        let call = ownerOfCall { id: U256::from(id) };
+        assert!(host.call(alice, nft, &call.encode()).is_err());
+    }
+    assert_eq!(balance_of(&mut host, nft, receiver), U256::ZERO);
+}
+
+#[test]
+fn test_batch_mint_after_individual_mint() {
+    // Token 1 is minted with `_mint` by the setup, so it could overlap a new batch
+    let (mut host, nft, alice, _) = setup();
+    let call = mintConsecutiveCall { qty: U256::from(2) };
+    let revert = host.call(alice, nft, &call.encode()).unwrap_err();
+
+    let error = ERC721Error::ERC721ForbiddenBatchMint(ERC721ForbiddenBatchMint {});
+    assert_eq!(revert, Vec::<u8>::from(error));
+    assert_eq!(owner_of(&mut host, nft, 1), alice);
+    assert_eq!(balance_of(&mut host, nft, alice), U256::from(1));
+}
+
+#[test]
+fn test_batch_mint_runs_hooks_once() {
+    let mut host = TestHost::new();
+    let nft = host.deploy::<HookedNFT>();
+    let alice = TestHost::address("alice");
+
+    let max_batch = U256::from(NFTParams::MAX_BATCH_SIZE);
+    for (first_id, qty) in [(U256::ZERO, max_batch), (max_batch, U256::from(3))] {
+        host.call_sol(alice, nft, &mintConsecutiveCall { qty })
+            .unwrap();
+        let logs = host.take_logs();
+        assert_eq!(logs.len(), 1);
+        let event = logs[0].decode::<ConsecutiveTransfer>().unwrap();
+        assert_eq!(event.fromTokenId, first_id);
+        assert_eq!(event.toTokenId, first_id + qty - U256::from(1));
+
+        let calls = host.call_sol(alice, nft, &hookCallsCall {}).unwrap();
+        assert_eq!(calls._2, first_id);
+        assert_eq!(calls._3, qty);
+    }
+    let calls = host.call_sol(alice, nft, &hookCallsCall {}).unwrap();
+    assert_eq!((calls._0, calls._1), (U256::from(2), U256::from(2)));
+    assert_eq!(balance_of(&mut host, nft, alice), max_batch + U256::from(3));
+    assert_eq!(
+        owner_of(&mut host, nft, NFTParams::MAX_BATCH_SIZE + 2),
+        alice
+    );
+}
+
+// Invariants checked against a model of the collection, over random sequences of operations
+
+/// The accounts used by the operations
+const USERS: usize = 3;
+/// The ids used by the operations, which include the consecutive batches minted first
+const IDS: u64 = 12;
+
+#[derive(Debug, Clone)]
+enum Op {
+    Mint(usize, u64),
+    Burn(usize, u64),
+    Transfer(usize, usize, usize, u64),
+    Approve(usize, usize, u64),
+    SetApprovalForAll(usize, usize, bool),
+}
+
+fn user() -> impl Strategy<Value = usize> {
+    0..USERS
+}
+
+fn id() -> impl Strategy<Value = u64> {
+    0..IDS
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        (user(), id()).prop_map(|(user, id)| Op::Mint(user, id)),
+        (user(), id()).prop_map(|(user, id)| Op::Burn(user, id)),
+        (user(), user(), user(), id())
+            .prop_map(|(caller, from, to, id)| Op::Transfer(caller, from, to, id)),
+        (user(), user(), id()).prop_map(|(caller, approved, id)| Op::Approve(caller, approved, id)),
+        (user(), user(), any::<bool>()).prop_map(|(owner, operator, approved)| {
+            Op::SetApprovalForAll(owner, operator, approved)
+        }),
+    ]
+}
+
+/// The expected state of the collection
+#[derive(Default)]
+struct Model {
+    owners: [Option<usize>; IDS as usize],
+    approved: [Option<usize>; IDS as usize],
+    operators: [[bool; USERS]; USERS],
+}
+
+impl Model {
+    /// Returns whether `caller` can manage token `id` owned by `owner`.
+    fn authorized(&self, caller: usize, owner: usize, id: usize) -> bool {
+        caller == owner || self.operators[owner][caller] || self.approved[id] == Some(caller)
+    }
+
+    /// Applies `op` to the model, and returns whether it succeeds.
+    fn apply(&mut self, op: &Op) -> bool {
+        match *op {
+            Op::Mint(user, id) => {
+                let id = id as usize;
+                if self.owners[id].is_some() {
+                    return false;
+                }
+                self.owners[id] = Some(user);
+            }
+            Op::Burn(caller, id) => {
+                let id = id as usize;
+                match self.owners[id] {
+                    Some(owner) if self.authorized(caller, owner, id) => {
+                        self.owners[id] = None;
+                        self.approved[id] = None;
+                    }
+                    _ => return false,
+                }
+            }
+            Op::Transfer(caller, from, to, id) => {
+                let id = id as usize;
+                match self.owners[id] {
+                    Some(owner) if owner == from && self.authorized(caller, owner, id) => {
+                        self.owners[id] = Some(to);
+                        self.approved[id] = None;
+                    }
+                    _ => return false,
+                }
+            }
+            Op::Approve(caller, approved, id) => {
+                let id = id as usize;
+                match self.owners[id] {
+                    Some(owner) if caller == owner || self.operators[owner][caller] => {
+                        self.approved[id] = Some(approved);
+                    }
+                    _ => return false,
+                }
+            }
+            Op::SetApprovalForAll(owner, operator, approved) => {
+                self.operators[owner][operator] = approved;
+            }
+        }
+        true
+    }
+}
+
+fn run_ops(batches: &[(usize, u64)], ops: &[Op]) -> Result<(), TestCaseError> {
+    let mut host = TestHost::new();
+    let nft = host.deploy::<NFT>();
+    let users: Vec<Address> = (0..USERS)
+        .map(|i| TestHost::address(&format!("user{i}")))
+        .collect();
+    let mut model = Model::default();
+
+    // Consecutive batches are minted before any other token
+    let mut next_id = 0;
+    for &(user, qty) in batches {
+        let call = mintConsecutiveCall {
+            qty: U256::from(qty),
+        };
+        host.call_sol(users[user], nft, &call).unwrap();
+        for id in next_id..next_id + qty {
+            model.owners[id as usize] = Some(user);
+        }
+        next_id += qty;
+    }
+
+    for op in ops {
+        let result = match *op {
+            Op::Mint(user, id) => {
+                let call = mintCall { id: U256::from(id) };
+                host.call(users[user], nft, &call.encode())
+            }
+            Op::Burn(caller, id) => {
+                let call = burnCall { id: U256::from(id) };
+                host.call(users[caller], nft, &call.encode())
+            }
+            Op::Transfer(caller, from, to, id) => {
+                let call = transferFromCall {
+                    from: users[from],
+                    to: users[to],
+                    id: U256::from(id),
+                };
+                host.call(users[caller], nft, &call.encode())
+            }
+            Op::Approve(caller, approved, id) => {
+                let call = approveCall {
+                    approved: users[approved],
+                    id: U256::from(id),
+                };
+                host.call(users[caller], nft, &call.encode())
+            }
+            Op::SetApprovalForAll(owner, operator, approved) => {
+                let call = setApprovalForAllCall {
+                    operator: users[operator],
+                    approved,
+                };
+                host.call(users[owner], nft, &call.encode())
+            }
+        };
+        let success = model.apply(op);
+        prop_assert_eq!(result.is_ok(), success, "{:?}", op);
+
+        // Each token is owned by its expected owner, and balances count the owned tokens
+        for (id, owner) in model.owners.iter().enumerate() {
+            let call = ownerOfCall { id: U256::from(id) };
+            let result = host.call_sol(users[0], nft, &call).map(|owner| owner._0);
+            prop_assert_eq!(result.ok(), owner.map(|owner| users[owner]), "token {}", id);
+        }
+        for (user, address) in users.iter().enumerate() {
+            let owned = model
+                .owners
+                .iter()
+                .filter(|owner| **owner == Some(user))
+                .count();
+            prop_assert_eq!(balance_of(&mut host, nft, *address), U256::from(owned));
+        }
+    }
+    Ok(())
+}
+
+proptest! {
+    // Each call forks a process, so fewer cases are run than the default
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn test_invariants(
+        batches in prop::collection::vec((user(), 0u64..4), 0..3),
+        ops in prop::collection::vec(op(), 1..24),
+    ) {
+        run_ops(&batches, &ops)?;
+    }
+}
@@ -0,0 +1,293 @@
+//! Tests of the FractionalVault, run on the in-memory host of `inkmate-test`.
+#![cfg(all(feature = "fractional-vault", feature = "erc721"))]
+extern crate alloc;
+
+use core::borrow::{Borrow, BorrowMut};
+use inkmate::market::fractional_vault::{
+    AuctionNotOver, BidTooLow, FractionalVault, FractionalVaultError, FractionalVaultParams,
+    InvalidAuctionState, NotAllShares, NothingToClaim, AUCTION_ENDED, AUCTION_LIVE,
+    AUCTION_REDEEMED,
+};
+use inkmate::tokens::erc20::{ERC20Params, ERC20};
+use inkmate::tokens::erc721::{ERC721Params, ERC721};
+use inkmate_test::TestHost;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolCall},
+    msg,
+    prelude::*,
+};
+
+struct NFTParams;
+
+impl ERC721Params for NFTParams {
+    const NAME: &'static str = "NFT";
+    const SYMBOL: &'static str = "NFT";
+
+    fn token_uri(_id: U256) -> String {
+        String::new()
+    }
+}
+
+sol_storage! {
+    struct NFT {
+        #[borrow]
+        ERC721<NFTParams> erc721;
+    }
+}
+
+unsafe impl TopLevelStorage for NFT {}
+
+#[external]
+#[inherit(ERC721<NFTParams>)]
+impl NFT {
+    pub fn mint(&mut self, id: U256) -> Result<(), Vec<u8>> {
+        ERC721::_mint(&mut self.erc721, msg::sender(), id)
+    }
+}
+
+struct SharesParams;
+
+impl ERC20Params for SharesParams {
+    const NAME: &'static str = "Shares";
+    const SYMBOL: &'static str = "SHR";
+    const DECIMALS: u8 = 18;
+}
+
+impl FractionalVaultParams for SharesParams {}
+
+sol_storage! {
+    struct Vault {
+        #[borrow]
+        FractionalVault<SharesParams> vault;
+    }
+}
+
+unsafe impl TopLevelStorage for Vault {}
+
+impl Borrow<ERC20<SharesParams>> for Vault {
+    fn borrow(&self) -> &ERC20<SharesParams> {
+        &self.vault.erc20
+    }
+}
+
+impl BorrowMut<ERC20<SharesParams>> for Vault {
+    fn borrow_mut(&mut self) -> &mut ERC20<SharesParams> {
+        &mut self.vault.erc20
+    }
+}
+
+#[external]
+#[inherit(FractionalVault<SharesParams>, ERC20<SharesParams>)]
+impl Vault {
+    pub fn init(
+        &mut self,
+        token: Address,
+        id: U256,
+        supply: U256,
+        reserve_price: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.vault
+            ._initialize(token, id, msg::sender(), supply, reserve_price)
+    }
+}
+
+sol! {
+    function mint(uint256 id);
+    function approve(address spender, uint256 id);
+    function ownerOf(uint256 id) returns (address);
+    function balanceOf(address owner) returns (uint256);
+    function totalSupply() returns (uint256);
+    function transfer(address to, uint256 amount) returns (bool);
+
+    function init(address token, uint256 id, uint256 supply, uint256 reservePrice);
+    function auction() returns (uint8, uint64, uint256, address);
+    function start();
+    function bid();
+    function end();
+    function redeem();
+    function cash();
+    function withdrawRefund();
+}
+
+/// The id of the locked token
+const ID: u64 = 7;
+/// The supply of shares minted to the curator
+const SUPPLY: u64 = 1000;
+/// The minimum bid to start an auction
+const RESERVE_PRICE: u64 = 100;
+
+struct Setup {
+    host: TestHost,
+    nft: Address,
+    vault: Address,
+    curator: Address,
+    alice: Address,
+}
+
+/// Locks token `ID` in a vault, and sends 400 of the shares of the curator to alice.
+fn setup() -> Setup {
+    let mut host = TestHost::new();
+    let nft = host.deploy::<NFT>();
+    let vault = host.deploy::<Vault>();
+    let curator = TestHost::address("curator");
+    let alice = TestHost::address("alice");
+
+    let id = U256::from(ID);
+    host.call_sol(curator, nft, &mintCall { id }).unwrap();
+    host.call_sol(curator, nft, &approveCall { spender: vault, id })
+        .unwrap();
+    let init = initCall {
+        token: nft,
+        id,
+        supply: U256::from(SUPPLY),
+        reservePrice: U256::from(RESERVE_PRICE),
+    };
+    host.call_sol(curator, vault, &init).unwrap();
+    let transfer = transferCall {
+        to: alice,
+        amount: U256::from(400),
+    };
+    host.call_sol(curator, vault, &transfer).unwrap();
+    Setup {
+        host,
+        nft,
+        vault,
+        curator,
+        alice,
+    }
+}
+
+impl Setup {
+    /// Calls `call` of the vault from `from` with `value` wei, funding `from` first.
+    fn send(&mut self, from: Address, value: u64, call: &impl SolCall) -> Result<(), Vec<u8>> {
+        let value = U256::from(value);
+        let balance = self.host.balance(from);
+        self.host.set_balance(from, balance + value);
+        self.host
+            .call_with_value(from, self.vault, value, &call.encode())?;
+        Ok(())
+    }
+
+    fn owner_of_token(&mut self) -> Address {
+        let owner_of = ownerOfCall { id: U256::from(ID) };
+        self.host
+            .call_sol(self.curator, self.nft, &owner_of)
+            .unwrap()
+            ._0
+    }
+
+    fn auction(&mut self) -> (u8, u64, U256, Address) {
+        let auction = self
+            .host
+            .call_sol(self.curator, self.vault, &auctionCall {})
+            .unwrap();
+        (auction._0, auction._1, auction._2, auction._3)
+    }
+}
+
+#[test]
+fn test_buyout() {
+    let mut s = setup();
+    let (curator, alice) = (s.curator, s.alice);
+    let bob = TestHost::address("bob");
+    let carol = TestHost::address("carol");
+    assert_eq!(s.owner_of_token(), s.vault);
+
+    // The auction starts at the reserve price, and bids must increase by 5%
+    let revert = s.send(bob, RESERVE_PRICE - 1, &startCall {}).unwrap_err();
+    let error = FractionalVaultError::BidTooLow(BidTooLow {
+        price: U256::from(RESERVE_PRICE - 1),
+        minimum: U256::from(RESERVE_PRICE),
+    });
+    assert_eq!(revert, Vec::<u8>::from(error));
+    s.send(bob, RESERVE_PRICE, &startCall {}).unwrap();
+    let (state, end, _, _) = s.auction();
+    assert_eq!(state, AUCTION_LIVE);
+
+    let revert = s.send(carol, 104, &bidCall {}).unwrap_err();
+    let error = FractionalVaultError::BidTooLow(BidTooLow {
+        price: U256::from(104),
+        minimum: U256::from(105),
+    });
+    assert_eq!(revert, Vec::<u8>::from(error));
+    s.send(carol, 105, &bidCall {}).unwrap();
+
+    // A bid in the last minutes extends the auction
+    s.host.set_block_timestamp(end - 60);
+    s.send(bob, 200, &bidCall {}).unwrap();
+    let (_, extended, price, winning) = s.auction();
+    assert_eq!(extended, end - 60 + SharesParams::TIME_EXTENSION);
+    assert_eq!((price, winning), (U256::from(200), bob));
+
+    s.host.set_block_timestamp(end);
+    let revert = s.host.call(bob, s.vault, &endCall {}.encode()).unwrap_err();
+    let error = FractionalVaultError::AuctionNotOver(AuctionNotOver { end: extended });
+    assert_eq!(revert, Vec::<u8>::from(error));
+    s.host.set_block_timestamp(extended);
+    s.host.call_sol(carol, s.vault, &endCall {}).unwrap();
+    assert_eq!(s.owner_of_token(), bob);
+    assert_eq!(s.auction().0, AUCTION_ENDED);
+
+    // Share holders cash out their part of the winning bid
+    s.host.call_sol(alice, s.vault, &cashCall {}).unwrap();
+    s.host.call_sol(curator, s.vault, &cashCall {}).unwrap();
+    assert_eq!(s.host.balance(alice), U256::from(80));
+    assert_eq!(s.host.balance(curator), U256::from(120));
+    let total_supply = s.host.call_sol(alice, s.vault, &totalSupplyCall {});
+    assert_eq!(total_supply.unwrap()._0, U256::ZERO);
+    let revert = s
+        .host
+        .call(alice, s.vault, &cashCall {}.encode())
+        .unwrap_err();
+    let error = FractionalVaultError::NothingToClaim(NothingToClaim {});
+    assert_eq!(revert, Vec::<u8>::from(error));
+
+    // Outbid bidders withdraw their bids
+    for (bidder, refund) in [(bob, RESERVE_PRICE), (carol, 105)] {
+        let balance = s.host.balance(bidder);
+        s.host
+            .call_sol(bidder, s.vault, &withdrawRefundCall {})
+            .unwrap();
+        assert_eq!(s.host.balance(bidder), balance + U256::from(refund));
+    }
+    assert_eq!(s.host.balance(s.vault), U256::ZERO);
+}
+
+#[test]
+fn test_redeem() {
+    let mut s = setup();
+    let (curator, alice) = (s.curator, s.alice);
+
+    let revert = s
+        .host
+        .call(curator, s.vault, &redeemCall {}.encode())
+        .unwrap_err();
+    let error = FractionalVaultError::NotAllShares(NotAllShares {
+        balance: U256::from(600),
+        totalSupply: U256::from(SUPPLY),
+    });
+    assert_eq!(revert, Vec::<u8>::from(error));
+
+    let transfer = transferCall {
+        to: curator,
+        amount: U256::from(400),
+    };
+    s.host.call_sol(alice, s.vault, &transfer).unwrap();
+    s.host.call_sol(curator, s.vault, &redeemCall {}).unwrap();
+    assert_eq!(s.owner_of_token(), curator);
+    assert_eq!(s.auction().0, AUCTION_REDEEMED);
+    let balance = s
+        .host
+        .call_sol(curator, s.vault, &balanceOfCall { owner: curator })
+        .unwrap()
+        ._0;
+    assert_eq!(balance, U256::ZERO);
+
+    // The token can no longer be bought out
+    let revert = s.send(alice, RESERVE_PRICE, &startCall {}).unwrap_err();
+    let error = FractionalVaultError::InvalidAuctionState(InvalidAuctionState {
+        state: AUCTION_REDEEMED,
+    });
+    assert_eq!(revert, Vec::<u8>::from(error));
+}
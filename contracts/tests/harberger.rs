@@ -0,0 +1,275 @@
+//! Tests of the Harberger tax extension, run on the in-memory host of `inkmate-test`.
+#![cfg(feature = "harberger")]
+extern crate alloc;
+
+use core::borrow::{Borrow, BorrowMut};
+use inkmate::tokens::erc721::{ERC721Params, ERC721};
+use inkmate::tokens::extensions::harberger::{
+    Harberger, HarbergerError, HarbergerParams, InsufficientDeposit, PriceChangedBeforeBuy,
+    TokenForeclosed, TransferDisabled,
+};
+use inkmate_test::TestHost;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolCall},
+    msg,
+    prelude::*,
+};
+
+/// The account receiving the tax
+const BENEFICIARY: Address = Address::new([0xbe; 20]);
+
+struct NFTParams;
+
+impl ERC721Params for NFTParams {
+    const NAME: &'static str = "NFT";
+    const SYMBOL: &'static str = "NFT";
+
+    fn token_uri(_id: U256) -> String {
+        String::new()
+    }
+}
+
+/// A tax of 10% of the price every 1000 seconds, which is 1 wei per second at a price of 10000
+impl HarbergerParams for NFTParams {
+    const BENEFICIARY: Address = BENEFICIARY;
+    const TAX_RATE_BPS: u64 = 1000;
+    const TAX_PERIOD: u64 = 1000;
+}
+
+sol_storage! {
+    struct NFT {
+        #[borrow]
+        Harberger<NFTParams> harberger;
+    }
+}
+
+unsafe impl TopLevelStorage for NFT {}
+
+impl Borrow<ERC721<NFTParams>> for NFT {
+    fn borrow(&self) -> &ERC721<NFTParams> {
+        &self.harberger.erc721
+    }
+}
+
+impl BorrowMut<ERC721<NFTParams>> for NFT {
+    fn borrow_mut(&mut self) -> &mut ERC721<NFTParams> {
+        &mut self.harberger.erc721
+    }
+}
+
+#[external]
+#[inherit(Harberger<NFTParams>, ERC721<NFTParams>)]
+impl NFT {
+    #[payable]
+    pub fn mint(&mut self, id: U256, price: U256) -> Result<(), Vec<u8>> {
+        self.harberger._mint(msg::sender(), id, price, msg::value())
+    }
+}
+
+sol! {
+    function mint(uint256 id, uint256 price);
+    function ownerOf(uint256 id) returns (address);
+    function transferFrom(address from, address to, uint256 id);
+    function priceOf(uint256 id) returns (uint256);
+    function depositOf(uint256 id) returns (uint256);
+    function taxDue(uint256 id) returns (uint256);
+    function foreclosed(uint256 id) returns (bool);
+    function creditOf(address account) returns (uint256);
+    function collectTax(uint256 id) returns (bool);
+    function deposit(uint256 id);
+    function withdrawDeposit(uint256 id, uint256 amount);
+    function buy(uint256 id, uint256 newPrice, uint256 currentPrice);
+    function withdraw();
+}
+
+/// The id of the minted token
+const ID: U256 = U256::from_limbs([1, 0, 0, 0]);
+/// The price of the minted token, taxed 1 wei per second
+const PRICE: u64 = 10_000;
+/// The deposit of the minted token, which lasts 500 seconds
+const DEPOSIT: u64 = 500;
+
+struct Setup {
+    host: TestHost,
+    nft: Address,
+    alice: Address,
+    bob: Address,
+}
+
+/// Mints token `ID` to alice at `PRICE`, with `DEPOSIT` of prepaid tax.
+fn setup() -> Setup {
+    let mut host = TestHost::new();
+    let nft = host.deploy::<NFT>();
+    let mut s = Setup {
+        host,
+        nft,
+        alice: TestHost::address("alice"),
+        bob: TestHost::address("bob"),
+    };
+    let mint = mintCall {
+        id: ID,
+        price: U256::from(PRICE),
+    };
+    s.send(s.alice, DEPOSIT, &mint).unwrap();
+    s
+}
+
+impl Setup {
+    /// Calls `call` of the collection from `from` with `value` wei, funding `from` first.
+    fn send(&mut self, from: Address, value: u64, call: &impl SolCall) -> Result<(), Vec<u8>> {
+        let value = U256::from(value);
+        let balance = self.host.balance(from);
+        self.host.set_balance(from, balance + value);
+        self.host
+            .call_with_value(from, self.nft, value, &call.encode())?;
+        Ok(())
+    }
+
+    fn view<C: SolCall>(&mut self, call: &C) -> C::Return {
+        self.host.call_sol(self.bob, self.nft, call).unwrap()
+    }
+
+    fn owner_of(&mut self) -> Address {
+        self.view(&ownerOfCall { id: ID })._0
+    }
+}
+
+#[test]
+fn test_tax_collection() {
+    let mut s = setup();
+    s.host.advance(100);
+    assert_eq!(s.view(&taxDueCall { id: ID })._0, U256::from(100));
+    assert_eq!(s.view(&depositOfCall { id: ID })._0, U256::from(400));
+
+    let foreclosed = s.host.call_sol(s.bob, s.nft, &collectTaxCall { id: ID });
+    assert!(!foreclosed.unwrap()._0);
+    assert_eq!(s.view(&taxDueCall { id: ID })._0, U256::ZERO);
+    let credit = creditOfCall {
+        account: BENEFICIARY,
+    };
+    assert_eq!(s.view(&credit)._0, U256::from(100));
+
+    // Topping up the deposit postpones the foreclosure
+    s.send(s.bob, 1000, &depositCall { id: ID }).unwrap();
+    s.host.advance(DEPOSIT);
+    assert!(!s.view(&foreclosedCall { id: ID })._0);
+    assert_eq!(s.view(&depositOfCall { id: ID })._0, U256::from(900));
+
+    s.host
+        .call_sol(BENEFICIARY, s.nft, &withdrawCall {})
+        .unwrap();
+    assert_eq!(s.host.balance(BENEFICIARY), U256::from(100));
+}
+
+#[test]
+fn test_withdraw_deposit() {
+    let mut s = setup();
+    let alice = s.alice;
+    s.host.advance(100);
+
+    let withdraw = withdrawDepositCall {
+        id: ID,
+        amount: U256::from(200),
+    };
+    let balance = s.host.balance(alice);
+    s.host.call_sol(alice, s.nft, &withdraw).unwrap();
+    assert_eq!(s.host.balance(alice), balance + U256::from(200));
+    assert_eq!(s.view(&depositOfCall { id: ID })._0, U256::from(200));
+
+    let withdraw = withdrawDepositCall {
+        id: ID,
+        amount: U256::from(201),
+    };
+    let revert = s.host.call(alice, s.nft, &withdraw.encode()).unwrap_err();
+    let error = HarbergerError::InsufficientDeposit(InsufficientDeposit {
+        deposit: U256::from(200),
+        amount: U256::from(201),
+    });
+    assert_eq!(revert, Vec::<u8>::from(error));
+}
+
+#[test]
+fn test_foreclosure() {
+    let mut s = setup();
+    let (alice, bob) = (s.alice, s.bob);
+    s.host.advance(DEPOSIT);
+
+    // The whole deposit is collected, and the token is taken back at a price of 0
+    let foreclosed = s.host.call_sol(bob, s.nft, &collectTaxCall { id: ID });
+    assert!(foreclosed.unwrap()._0);
+    assert_eq!(s.owner_of(), s.nft);
+    assert_eq!(s.view(&priceOfCall { id: ID })._0, U256::ZERO);
+    let credit = creditOfCall {
+        account: BENEFICIARY,
+    };
+    assert_eq!(s.view(&credit)._0, U256::from(DEPOSIT));
+
+    let revert = s.send(alice, 100, &depositCall { id: ID }).unwrap_err();
+    let error = HarbergerError::TokenForeclosed(TokenForeclosed { tokenId: ID });
+    assert_eq!(revert, Vec::<u8>::from(error));
+    let withdraw = withdrawDepositCall {
+        id: ID,
+        amount: U256::ZERO,
+    };
+    assert!(s.host.call(alice, s.nft, &withdraw.encode()).is_err());
+}
+
+#[test]
+fn test_buy() {
+    let mut s = setup();
+    let (alice, bob) = (s.alice, s.bob);
+    let carol = TestHost::address("carol");
+
+    // A foreclosed token is bought for free, and the value sent becomes the deposit
+    s.host.advance(DEPOSIT);
+    let buy = buyCall {
+        id: ID,
+        newPrice: U256::from(PRICE / 2),
+        currentPrice: U256::ZERO,
+    };
+    s.send(bob, 300, &buy).unwrap();
+    assert_eq!(s.owner_of(), bob);
+    assert_eq!(s.view(&depositOfCall { id: ID })._0, U256::from(300));
+    assert_eq!(s.view(&creditOfCall { account: s.nft })._0, U256::ZERO);
+    assert_eq!(s.view(&creditOfCall { account: alice })._0, U256::ZERO);
+
+    // Buyers must expect the current price
+    let buy = buyCall {
+        id: ID,
+        newPrice: U256::from(PRICE),
+        currentPrice: U256::ZERO,
+    };
+    let revert = s.send(carol, PRICE, &buy).unwrap_err();
+    let error = HarbergerError::PriceChangedBeforeBuy(PriceChangedBeforeBuy {
+        price: U256::from(PRICE / 2),
+    });
+    assert_eq!(revert, Vec::<u8>::from(error));
+
+    // The seller is credited the price and what is left of their deposit
+    s.host.advance(100);
+    let buy = buyCall {
+        id: ID,
+        newPrice: U256::from(PRICE),
+        currentPrice: U256::from(PRICE / 2),
+    };
+    s.send(carol, PRICE / 2 + 1000, &buy).unwrap();
+    assert_eq!(s.owner_of(), carol);
+    assert_eq!(s.view(&depositOfCall { id: ID })._0, U256::from(1000));
+    let credit = creditOfCall { account: bob };
+    assert_eq!(s.view(&credit)._0, U256::from(PRICE / 2 + 250));
+
+    let balance = s.host.balance(bob);
+    s.host.call_sol(bob, s.nft, &withdrawCall {}).unwrap();
+    assert_eq!(s.host.balance(bob), balance + U256::from(PRICE / 2 + 250));
+
+    // Tokens only move by being bought
+    let transfer = transferFromCall {
+        from: carol,
+        to: bob,
+        id: ID,
+    };
+    let revert = s.host.call(carol, s.nft, &transfer.encode()).unwrap_err();
+    let error = HarbergerError::TransferDisabled(TransferDisabled { tokenId: ID });
+    assert_eq!(revert, Vec::<u8>::from(error));
+}
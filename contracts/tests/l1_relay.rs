@@ -0,0 +1,172 @@
+//! Tests of the L1Relay, run on the in-memory host of `inkmate-test`.
+#![cfg(feature = "l1-relay")]
+extern crate alloc;
+
+use inkmate::arbitrum::crosschain::{
+    apply_l1_to_l2_alias, CrossChainError, InvalidCrossChainSender,
+};
+use inkmate::governance::l1_relay::{
+    setL1GovernorCall, L1GovernorChanged, L1Relay, L1RelayError, L1RelayUnsupportedCall,
+};
+use inkmate_test::TestHost;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolCall},
+    msg,
+    prelude::*,
+};
+
+sol_storage! {
+    struct Relay {
+        #[borrow]
+        L1Relay relay;
+    }
+}
+
+unsafe impl TopLevelStorage for Relay {}
+
+#[external]
+#[inherit(L1Relay)]
+impl Relay {
+    pub fn initialize(&mut self, l1_governor: Address) -> Result<(), Vec<u8>> {
+        self.relay._initialize(l1_governor)?;
+        Ok(())
+    }
+}
+
+sol_storage! {
+    /// A contract owned by the relay
+    struct Target {
+        address last_caller;
+    }
+}
+
+unsafe impl TopLevelStorage for Target {}
+
+#[external]
+impl Target {
+    pub fn ping(&mut self) {
+        self.last_caller.set(msg::sender());
+    }
+
+    pub fn last_caller(&self) -> Address {
+        self.last_caller.get()
+    }
+}
+
+sol! {
+    function initialize(address l1Governor);
+    function relay(address target, uint256 value, bytes data);
+    function l1Governor() returns (address);
+    function ping();
+    function lastCaller() returns (address);
+}
+
+struct Setup {
+    host: TestHost,
+    relay: Address,
+    target: Address,
+    governor: Address,
+}
+
+fn setup() -> Setup {
+    let mut host = TestHost::new();
+    let relay = host.deploy::<Relay>();
+    let target = host.deploy::<Target>();
+    let governor = TestHost::address("governor");
+    let initialize = initializeCall {
+        l1Governor: governor,
+    };
+    host.call_sol(governor, relay, &initialize).unwrap();
+    host.take_logs();
+    Setup {
+        host,
+        relay,
+        target,
+        governor,
+    }
+}
+
+impl Setup {
+    /// Relays `data` to `target` through a retryable ticket of the L1 contract `l1_sender`
+    fn relay(
+        &mut self,
+        l1_sender: Address,
+        target: Address,
+        data: Vec<u8>,
+    ) -> Result<Vec<u8>, Vec<u8>> {
+        let relay = relayCall {
+            target,
+            value: U256::ZERO,
+            data,
+        };
+        let alias = apply_l1_to_l2_alias(l1_sender);
+        self.host.call(alias, self.relay, &relay.encode())
+    }
+}
+
+#[test]
+fn test_relay() {
+    let mut s = setup();
+    let (governor, target) = (s.governor, s.target);
+
+    s.relay(governor, target, pingCall {}.encode()).unwrap();
+
+    let last_caller = s.host.call_sol(governor, target, &lastCallerCall {});
+    assert_eq!(last_caller.unwrap()._0, s.relay);
+}
+
+#[test]
+fn test_relay_unauthorized() {
+    let mut s = setup();
+    let (target, mallory) = (s.target, TestHost::address("mallory"));
+
+    let revert = s.relay(mallory, target, pingCall {}.encode()).unwrap_err();
+
+    let error = CrossChainError::InvalidCrossChainSender(InvalidCrossChainSender {
+        actual: apply_l1_to_l2_alias(mallory),
+        expected: apply_l1_to_l2_alias(s.governor),
+    });
+    assert_eq!(revert, Vec::<u8>::from(error));
+}
+
+#[test]
+fn test_relay_rotates_governor() {
+    let mut s = setup();
+    let (governor, relay) = (s.governor, s.relay);
+    let new_governor = TestHost::address("new governor");
+    let rotate = setL1GovernorCall {
+        newGovernor: new_governor,
+    };
+
+    s.relay(governor, relay, rotate.encode()).unwrap();
+
+    let current = s
+        .host
+        .call_sol(governor, relay, &l1GovernorCall {})
+        .unwrap();
+    assert_eq!(current._0, new_governor);
+    let event = s.host.take_logs()[0].decode::<L1GovernorChanged>().unwrap();
+    assert_eq!(
+        (event.previousGovernor, event.newGovernor),
+        (governor, new_governor)
+    );
+    // Only the new governor can relay calls from now on
+    let target = s.target;
+    assert!(s.relay(governor, target, pingCall {}.encode()).is_err());
+    s.relay(new_governor, target, pingCall {}.encode()).unwrap();
+}
+
+#[test]
+fn test_relay_unsupported_self_call() {
+    let mut s = setup();
+    let (governor, relay) = (s.governor, s.relay);
+    let initialize = initializeCall {
+        l1Governor: TestHost::address("mallory"),
+    };
+
+    let revert = s.relay(governor, relay, initialize.encode()).unwrap_err();
+
+    let error = L1RelayError::L1RelayUnsupportedCall(L1RelayUnsupportedCall {});
+    assert_eq!(revert, Vec::<u8>::from(error));
+}
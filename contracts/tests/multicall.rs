@@ -0,0 +1,141 @@
+//! Tests of the Multicall mixin, run on the in-memory host of `inkmate-test`.
+#![cfg(all(feature = "multicall", feature = "erc20"))]
+extern crate alloc;
+
+use inkmate::tokens::erc20::{ERC20Error, ERC20Params, ERC20};
+use inkmate::utils::multicall::Multicall;
+use inkmate_test::TestHost;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolCall},
+    msg,
+    prelude::*,
+};
+
+struct TokenParams;
+
+impl ERC20Params for TokenParams {
+    const NAME: &'static str = "Token";
+    const SYMBOL: &'static str = "TKN";
+    const DECIMALS: u8 = 18;
+}
+
+sol_storage! {
+    struct Token {
+        #[borrow]
+        ERC20<TokenParams> erc20;
+        #[borrow]
+        Multicall<Token> multicall;
+        uint256 counter;
+    }
+}
+
+unsafe impl TopLevelStorage for Token {}
+
+#[external]
+#[inherit(ERC20<TokenParams>, Multicall<Token>)]
+impl Token {
+    pub fn mint(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        ERC20::_mint(&mut self.erc20, msg::sender(), amount)?;
+        Ok(())
+    }
+
+    pub fn increment(&mut self) {
+        let counter = self.counter.get();
+        self.counter.set(counter + U256::from(1));
+    }
+
+    pub fn counter(&self) -> U256 {
+        self.counter.get()
+    }
+}
+
+sol! {
+    function balanceOf(address owner) returns (uint256);
+    function transfer(address to, uint256 amount) returns (bool);
+    function mint(uint256 amount);
+    function increment();
+    function counter() returns (uint256);
+    function multicall(bytes[] data) returns (bytes[]);
+}
+
+fn setup() -> (TestHost, Address, Address, Address) {
+    let mut host = TestHost::new();
+    let token = host.deploy::<Token>();
+    let alice = TestHost::address("alice");
+    let bob = TestHost::address("bob");
+    (host, token, alice, bob)
+}
+
+fn balance_of(host: &mut TestHost, token: Address, owner: Address) -> U256 {
+    host.call_sol(owner, token, &balanceOfCall { owner })
+        .unwrap()
+        ._0
+}
+
+#[test]
+fn test_multicall_sees_earlier_calls() {
+    let (mut host, token, alice, bob) = setup();
+    let mint = mintCall {
+        amount: U256::from(100),
+    };
+    let transfer = transferCall {
+        to: bob,
+        amount: U256::from(60),
+    };
+    let batch = multicallCall {
+        data: vec![mint.encode(), transfer.encode()],
+    };
+
+    let results = host.call_sol(alice, token, &batch).unwrap()._0;
+
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_empty());
+    assert!(transferCall::decode_returns(&results[1], true).unwrap()._0);
+    assert_eq!(balance_of(&mut host, token, alice), U256::from(40));
+    assert_eq!(balance_of(&mut host, token, bob), U256::from(60));
+}
+
+#[test]
+fn test_multicall_reads_its_own_writes() {
+    let (mut host, token, alice, _) = setup();
+    let batch = multicallCall {
+        data: vec![
+            counterCall {}.encode(),
+            incrementCall {}.encode(),
+            incrementCall {}.encode(),
+            counterCall {}.encode(),
+        ],
+    };
+
+    let results = host.call_sol(alice, token, &batch).unwrap()._0;
+
+    // The counter was read before the increments, and must not be served from a stale cache
+    let before = counterCall::decode_returns(&results[0], true).unwrap()._0;
+    let after = counterCall::decode_returns(&results[3], true).unwrap()._0;
+    assert_eq!((before, after), (U256::ZERO, U256::from(2)));
+    let counter = host.call_sol(alice, token, &counterCall {}).unwrap()._0;
+    assert_eq!(counter, U256::from(2));
+}
+
+#[test]
+fn test_multicall_bubbles_up_reverts() {
+    let (mut host, token, alice, bob) = setup();
+    let mint = mintCall {
+        amount: U256::from(100),
+    };
+    let transfer = transferCall {
+        to: bob,
+        amount: U256::from(150),
+    };
+    let batch = multicallCall {
+        data: vec![mint.encode(), transfer.encode()],
+    };
+
+    let revert = host.call(alice, token, &batch.encode()).unwrap_err();
+
+    let error = ERC20Error::insufficient_balance(alice, U256::from(100), U256::from(150));
+    assert_eq!(revert, Vec::<u8>::from(error));
+    // The mint of the batch is reverted along with the transfer
+    assert_eq!(balance_of(&mut host, token, alice), U256::ZERO);
+}
@@ -0,0 +1,245 @@
+//! Tests of the PaymentSplitter, run on the in-memory host of `inkmate-test`.
+#![cfg(all(feature = "payment-splitter", feature = "erc20"))]
+extern crate alloc;
+
+use inkmate::finance::payment_splitter::{
+    AccountHasNoShares, AccountNotDuePayment, ERC20PaymentReleased, PaymentReleased,
+    PaymentSplitter, PaymentSplitterError,
+};
+use inkmate::tokens::erc20::{ERC20Params, ERC20};
+use inkmate_test::TestHost;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolCall},
+    prelude::*,
+};
+
+struct TokenParams;
+
+impl ERC20Params for TokenParams {
+    const NAME: &'static str = "Token";
+    const SYMBOL: &'static str = "TKN";
+    const DECIMALS: u8 = 18;
+}
+
+sol_storage! {
+    struct Token {
+        #[borrow]
+        ERC20<TokenParams> erc20;
+    }
+}
+
+unsafe impl TopLevelStorage for Token {}
+
+#[external]
+#[inherit(ERC20<TokenParams>)]
+impl Token {
+    pub fn mint(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        ERC20::_mint(&mut self.erc20, to, amount)?;
+        Ok(())
+    }
+}
+
+sol_storage! {
+    struct Splitter {
+        #[borrow]
+        PaymentSplitter splitter;
+    }
+}
+
+unsafe impl TopLevelStorage for Splitter {}
+
+#[external]
+#[inherit(PaymentSplitter)]
+impl Splitter {
+    pub fn initialize(&mut self, payees: Vec<Address>, shares: Vec<U256>) -> Result<(), Vec<u8>> {
+        self.splitter._initialize_payees(&payees, &shares)?;
+        Ok(())
+    }
+}
+
+sol! {
+    function balanceOf(address owner) returns (uint256);
+    function mint(address to, uint256 amount);
+    function initialize(address[] payees, uint256[] shares);
+    function deposit();
+    function totalReleased() returns (uint256);
+    function totalReleased(address token) returns (uint256);
+    function releasable(address account) returns (uint256);
+    function releasable(address token, address account) returns (uint256);
+    function release(address account);
+    function release(address token, address account);
+}
+
+struct Setup {
+    host: TestHost,
+    splitter: Address,
+    alice: Address,
+    bob: Address,
+    carol: Address,
+}
+
+/// Deploys a splitter paying 1 share to alice and 2 shares to bob
+fn setup() -> Setup {
+    let mut host = TestHost::new();
+    let splitter = host.deploy::<Splitter>();
+    let alice = TestHost::address("alice");
+    let bob = TestHost::address("bob");
+    let carol = TestHost::address("carol");
+    let initialize = initializeCall {
+        payees: vec![alice, bob],
+        shares: vec![U256::from(1), U256::from(2)],
+    };
+    host.call_sol(alice, splitter, &initialize).unwrap();
+    host.set_balance(carol, U256::from(1000));
+    host.take_logs();
+    Setup {
+        host,
+        splitter,
+        alice,
+        bob,
+        carol,
+    }
+}
+
+impl Setup {
+    fn deposit(&mut self, amount: u64) {
+        let deposit = depositCall {}.encode();
+        self.host
+            .call_with_value(self.carol, self.splitter, U256::from(amount), &deposit)
+            .unwrap();
+    }
+
+    fn release(&mut self, account: Address) -> Result<Vec<u8>, Vec<u8>> {
+        let release = release_0Call { account };
+        self.host.call(self.carol, self.splitter, &release.encode())
+    }
+}
+
+#[test]
+fn test_release() {
+    let mut s = setup();
+    s.deposit(300);
+
+    s.release(s.alice).unwrap();
+    s.release(s.bob).unwrap();
+
+    assert_eq!(s.host.balance(s.alice), U256::from(100));
+    assert_eq!(s.host.balance(s.bob), U256::from(200));
+    assert_eq!(s.host.balance(s.splitter), U256::ZERO);
+    let total = s
+        .host
+        .call_sol(s.carol, s.splitter, &totalReleased_0Call {});
+    assert_eq!(total.unwrap()._0, U256::from(300));
+    let logs = s.host.take_logs();
+    let event = logs[1].decode::<PaymentReleased>().unwrap();
+    assert_eq!((event.to, event.amount), (s.alice, U256::from(100)));
+
+    // Later deposits are split on top of what was already released
+    s.deposit(30);
+    let releasable = releasable_0Call { account: s.alice };
+    let releasable = s.host.call_sol(s.carol, s.splitter, &releasable).unwrap();
+    assert_eq!(releasable._0, U256::from(10));
+    s.release(s.alice).unwrap();
+    assert_eq!(s.host.balance(s.alice), U256::from(110));
+}
+
+#[test]
+fn test_release_nothing_due() {
+    let mut s = setup();
+    s.deposit(300);
+    s.release(s.alice).unwrap();
+
+    let revert = s.release(s.alice).unwrap_err();
+    let error =
+        PaymentSplitterError::AccountNotDuePayment(AccountNotDuePayment { account: s.alice });
+    assert_eq!(revert, Vec::<u8>::from(error));
+}
+
+#[test]
+fn test_release_not_payee() {
+    let mut s = setup();
+    s.deposit(300);
+
+    let revert = s.release(s.carol).unwrap_err();
+    let error = PaymentSplitterError::AccountHasNoShares(AccountHasNoShares { account: s.carol });
+    assert_eq!(revert, Vec::<u8>::from(error));
+}
+
+#[test]
+fn test_releasable_without_payees() {
+    let mut host = TestHost::new();
+    let splitter = host.deploy::<Splitter>();
+    let alice = TestHost::address("alice");
+    host.set_balance(alice, U256::from(300));
+    host.call_with_value(alice, splitter, U256::from(300), &depositCall {}.encode())
+        .unwrap();
+
+    let releasable = releasable_0Call { account: alice };
+    let releasable = host.call_sol(alice, splitter, &releasable).unwrap();
+    assert_eq!(releasable._0, U256::ZERO);
+}
+
+#[test]
+fn test_release_erc20() {
+    let mut s = setup();
+    let token = s.host.deploy::<Token>();
+    let mint = mintCall {
+        to: s.splitter,
+        amount: U256::from(300),
+    };
+    s.host.call_sol(s.carol, token, &mint).unwrap();
+    s.host.take_logs();
+
+    let release = release_1Call {
+        token,
+        account: s.bob,
+    };
+    s.host.call_sol(s.carol, s.splitter, &release).unwrap();
+
+    let balance = balanceOfCall { owner: s.bob };
+    let balance = s.host.call_sol(s.carol, token, &balance).unwrap()._0;
+    assert_eq!(balance, U256::from(200));
+    let total = totalReleased_1Call { token };
+    let total = s.host.call_sol(s.carol, s.splitter, &total).unwrap()._0;
+    assert_eq!(total, U256::from(200));
+    let logs = s.host.take_logs();
+    let event = logs[1].decode::<ERC20PaymentReleased>().unwrap();
+    assert_eq!(
+        (event.token, event.to, event.amount),
+        (token, s.bob, U256::from(200))
+    );
+    // ETH payments are tracked separately
+    let total = s
+        .host
+        .call_sol(s.carol, s.splitter, &totalReleased_0Call {});
+    assert_eq!(total.unwrap()._0, U256::ZERO);
+}
+
+#[test]
+fn test_release_erc20_large_balance() {
+    let mut s = setup();
+    let token = s.host.deploy::<Token>();
+    let mint = mintCall {
+        to: s.splitter,
+        amount: U256::MAX,
+    };
+    s.host.call_sol(s.carol, token, &mint).unwrap();
+
+    // The balance times the shares overflows 256 bits
+    let releasable = releasable_1Call {
+        token,
+        account: s.bob,
+    };
+    let releasable = s.host.call_sol(s.carol, s.splitter, &releasable).unwrap();
+    assert_eq!(releasable._0, U256::MAX / U256::from(3) * U256::from(2));
+    let release = release_1Call {
+        token,
+        account: s.alice,
+    };
+    s.host.call_sol(s.carol, s.splitter, &release).unwrap();
+
+    let balance = balanceOfCall { owner: s.alice };
+    let balance = s.host.call_sol(s.carol, token, &balance).unwrap()._0;
+    assert_eq!(balance, U256::MAX / U256::from(3));
+}
@@ -0,0 +1,262 @@
+//! Tests of the RewardDistributor, run on the in-memory host of `inkmate-test`.
+#![cfg(all(feature = "reward-distributor", feature = "erc20"))]
+extern crate alloc;
+
+use inkmate::finance::reward_distributor::{
+    RewardDistributor, RewardDistributorError, RewardDistributorParams, WithdrawExceedsStake,
+};
+use inkmate::tokens::erc20::{ERC20Params, ERC20};
+use inkmate_test::TestHost;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolCall},
+    msg,
+    prelude::*,
+};
+
+/// The address of the reward token, which is fixed by the params of the distributor
+const REWARD_TOKEN: Address = Address::new([0xee; 20]);
+
+struct TokenParams;
+
+impl ERC20Params for TokenParams {
+    const NAME: &'static str = "Token";
+    const SYMBOL: &'static str = "TKN";
+    const DECIMALS: u8 = 18;
+}
+
+sol_storage! {
+    struct Token {
+        #[borrow]
+        ERC20<TokenParams> erc20;
+    }
+}
+
+unsafe impl TopLevelStorage for Token {}
+
+#[external]
+#[inherit(ERC20<TokenParams>)]
+impl Token {
+    pub fn mint(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        ERC20::_mint(&mut self.erc20, msg::sender(), amount)
+    }
+}
+
+struct DistributorParams;
+
+impl RewardDistributorParams for DistributorParams {
+    const REWARD_TOKEN: Address = REWARD_TOKEN;
+}
+
+sol_storage! {
+    struct Distributor {
+        #[borrow]
+        RewardDistributor<DistributorParams> distributor;
+    }
+}
+
+unsafe impl TopLevelStorage for Distributor {}
+
+#[external]
+#[inherit(RewardDistributor<DistributorParams>)]
+impl Distributor {
+    pub fn add_pool(&mut self, staked_token: Address, alloc_point: U256) -> Result<(), Vec<u8>> {
+        self.distributor._add_pool(staked_token, alloc_point)?;
+        Ok(())
+    }
+
+    pub fn set_reward_per_second(&mut self, reward_per_second: U256) -> Result<(), Vec<u8>> {
+        Ok(self.distributor._set_reward_per_second(reward_per_second)?)
+    }
+}
+
+sol! {
+    function mint(uint256 amount);
+    function balanceOf(address owner) returns (uint256);
+    function transfer(address to, uint256 amount) returns (bool);
+    function approve(address spender, uint256 amount) returns (bool);
+
+    function addPool(address stakedToken, uint256 allocPoint);
+    function setRewardPerSecond(uint256 rewardPerSecond);
+    function pendingReward(uint256 pid, address account) returns (uint256);
+    function userInfo(uint256 pid, address account) returns (uint256, uint256);
+    function deposit(uint256 pid, uint256 amount);
+    function withdraw(uint256 pid, uint256 amount);
+    function emergencyWithdraw(uint256 pid);
+}
+
+/// The stake minted to each staker
+const STAKE: u64 = 1000;
+
+struct Setup {
+    host: TestHost,
+    distributor: Address,
+    staked: Address,
+    alice: Address,
+    bob: Address,
+}
+
+/// Deploys a distributor emitting 100 reward tokens per second, split between pool 0 staking
+/// `staked` with 1 allocation point and another pool with 3, and funds it with reward tokens.
+fn setup() -> Setup {
+    let mut host = TestHost::new();
+    host.deploy_at::<Token>(REWARD_TOKEN);
+    let staked = host.deploy::<Token>();
+    let other = host.deploy::<Token>();
+    let distributor = host.deploy::<Distributor>();
+    let owner = TestHost::address("owner");
+    let alice = TestHost::address("alice");
+    let bob = TestHost::address("bob");
+
+    for (token, alloc_point) in [(staked, 1), (other, 3)] {
+        let add_pool = addPoolCall {
+            stakedToken: token,
+            allocPoint: U256::from(alloc_point),
+        };
+        host.call_sol(owner, distributor, &add_pool).unwrap();
+    }
+    let emissions = setRewardPerSecondCall {
+        rewardPerSecond: U256::from(100),
+    };
+    host.call_sol(owner, distributor, &emissions).unwrap();
+
+    let funding = U256::from(1_000_000);
+    host.call_sol(owner, REWARD_TOKEN, &mintCall { amount: funding })
+        .unwrap();
+    let fund = transferCall {
+        to: distributor,
+        amount: funding,
+    };
+    host.call_sol(owner, REWARD_TOKEN, &fund).unwrap();
+
+    for staker in [alice, bob] {
+        let mint = mintCall {
+            amount: U256::from(STAKE),
+        };
+        host.call_sol(staker, staked, &mint).unwrap();
+        let approve = approveCall {
+            spender: distributor,
+            amount: U256::MAX,
+        };
+        host.call_sol(staker, staked, &approve).unwrap();
+    }
+    Setup {
+        host,
+        distributor,
+        staked,
+        alice,
+        bob,
+    }
+}
+
+impl Setup {
+    fn deposit(&mut self, account: Address, amount: u64) {
+        let deposit = depositCall {
+            pid: U256::ZERO,
+            amount: U256::from(amount),
+        };
+        self.host
+            .call_sol(account, self.distributor, &deposit)
+            .unwrap();
+    }
+
+    fn pending(&mut self, account: Address) -> U256 {
+        let pending = pendingRewardCall {
+            pid: U256::ZERO,
+            account,
+        };
+        self.host
+            .call_sol(account, self.distributor, &pending)
+            .unwrap()
+            ._0
+    }
+
+    fn balance(&mut self, token: Address, owner: Address) -> U256 {
+        let balance = balanceOfCall { owner };
+        self.host.call_sol(owner, token, &balance).unwrap()._0
+    }
+}
+
+#[test]
+fn test_rewards_accrue_to_stakers() {
+    let mut s = setup();
+    let (alice, bob, staked) = (s.alice, s.bob, s.staked);
+
+    // Pool 0 receives 1/4 of the 100 tokens emitted per second
+    s.deposit(alice, 100);
+    s.host.advance(10);
+    assert_eq!(s.pending(alice), U256::from(250));
+
+    // Rewards are split in proportion to the stakes once bob deposits
+    s.deposit(bob, 100);
+    assert_eq!(s.pending(bob), U256::ZERO);
+    s.host.advance(10);
+    assert_eq!(s.pending(alice), U256::from(375));
+    assert_eq!(s.pending(bob), U256::from(125));
+
+    // Withdrawing returns the stake and harvests the pending reward
+    let withdraw = withdrawCall {
+        pid: U256::ZERO,
+        amount: U256::from(100),
+    };
+    s.host.call_sol(alice, s.distributor, &withdraw).unwrap();
+    assert_eq!(s.balance(REWARD_TOKEN, alice), U256::from(375));
+    assert_eq!(s.balance(staked, alice), U256::from(STAKE));
+    assert_eq!(s.pending(alice), U256::ZERO);
+
+    // Bob is now the only staker, and harvests with a deposit of 0
+    s.host.advance(10);
+    assert_eq!(s.pending(bob), U256::from(375));
+    s.deposit(bob, 0);
+    assert_eq!(s.balance(REWARD_TOKEN, bob), U256::from(375));
+    assert_eq!(s.pending(bob), U256::ZERO);
+}
+
+#[test]
+fn test_withdraw_exceeding_stake() {
+    let mut s = setup();
+    let alice = s.alice;
+    s.deposit(alice, 100);
+
+    let withdraw = withdrawCall {
+        pid: U256::ZERO,
+        amount: U256::from(101),
+    };
+    let revert = s
+        .host
+        .call(alice, s.distributor, &withdraw.encode())
+        .unwrap_err();
+    let error = RewardDistributorError::WithdrawExceedsStake(WithdrawExceedsStake {
+        pid: U256::ZERO,
+        amount: U256::from(101),
+        staked: U256::from(100),
+    });
+    assert_eq!(revert, Vec::<u8>::from(error));
+}
+
+#[test]
+fn test_emergency_withdraw_forfeits_rewards() {
+    let mut s = setup();
+    let (alice, bob, staked) = (s.alice, s.bob, s.staked);
+    s.deposit(alice, 100);
+    s.deposit(bob, 100);
+    s.host.advance(10);
+    assert_eq!(s.pending(alice), U256::from(125));
+
+    let emergency = emergencyWithdrawCall { pid: U256::ZERO };
+    s.host.call_sol(alice, s.distributor, &emergency).unwrap();
+    assert_eq!(s.balance(staked, alice), U256::from(STAKE));
+    assert_eq!(s.balance(REWARD_TOKEN, alice), U256::ZERO);
+    assert_eq!(s.pending(alice), U256::ZERO);
+    let info = userInfoCall {
+        pid: U256::ZERO,
+        account: alice,
+    };
+    let info = s.host.call_sol(alice, s.distributor, &info).unwrap();
+    assert_eq!((info._0, info._1), (U256::ZERO, U256::ZERO));
+
+    // The pool is not updated, so the rewards accrued since its last update go to bob, who then
+    // keeps accruing alone
+    s.host.advance(10);
+    assert_eq!(s.pending(bob), U256::from(250 + 250));
+}
@@ -0,0 +1,250 @@
+//! Tests of the payment Streams, run on the in-memory host of `inkmate-test`.
+#![cfg(all(feature = "streams", feature = "erc20"))]
+extern crate alloc;
+
+use inkmate::finance::streams::{
+    CancelStream, StreamDoesNotExist, StreamInsufficientBalance, StreamInvalidDeposit,
+    StreamUnauthorized, Streams, StreamsError,
+};
+use inkmate::tokens::erc20::{ERC20Params, ERC20};
+use inkmate_test::TestHost;
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::{sol, SolCall},
+    msg,
+    prelude::*,
+};
+
+struct TokenParams;
+
+impl ERC20Params for TokenParams {
+    const NAME: &'static str = "Token";
+    const SYMBOL: &'static str = "TKN";
+    const DECIMALS: u8 = 18;
+}
+
+sol_storage! {
+    struct Token {
+        #[borrow]
+        ERC20<TokenParams> erc20;
+    }
+}
+
+unsafe impl TopLevelStorage for Token {}
+
+#[external]
+#[inherit(ERC20<TokenParams>)]
+impl Token {
+    pub fn mint(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        ERC20::_mint(&mut self.erc20, msg::sender(), amount)?;
+        Ok(())
+    }
+}
+
+sol_storage! {
+    struct StreamsContract {
+        #[borrow]
+        Streams streams;
+    }
+}
+
+unsafe impl TopLevelStorage for StreamsContract {}
+
+#[external]
+#[inherit(Streams)]
+impl StreamsContract {}
+
+sol! {
+    function mint(uint256 amount);
+    function approve(address spender, uint256 amount) returns (bool);
+    function balanceOf(address owner) returns (uint256);
+    function createStream(address recipient, uint256 deposit, address tokenAddress, uint256 startTime, uint256 stopTime) returns (uint256);
+    function getStream(uint256 streamId) returns (address, address, uint256, address, uint256, uint256, uint256, uint256);
+    function withdrawFromStream(uint256 streamId, uint256 amount);
+    function cancelStream(uint256 streamId);
+    function balanceOf(uint256 streamId, address who) returns (uint256);
+}
+
+/// The stream starts 10 seconds after the setup, and lasts 100 seconds
+const START: u64 = 11;
+const STOP: u64 = 111;
+
+struct Setup {
+    host: TestHost,
+    streams: Address,
+    token: Address,
+    alice: Address,
+    bob: Address,
+}
+
+fn setup() -> Setup {
+    let mut host = TestHost::new();
+    let streams = host.deploy::<StreamsContract>();
+    let token = host.deploy::<Token>();
+    let alice = TestHost::address("alice");
+    let bob = TestHost::address("bob");
+    let mint = mintCall {
+        amount: U256::from(10_000),
+    };
+    host.call_sol(alice, token, &mint).unwrap();
+    let approve = approveCall {
+        spender: streams,
+        amount: U256::MAX,
+    };
+    host.call_sol(alice, token, &approve).unwrap();
+    Setup {
+        host,
+        streams,
+        token,
+        alice,
+        bob,
+    }
+}
+
+impl Setup {
+    /// Creates a stream of `deposit` tokens from alice to bob
+    fn create(&mut self, deposit: u64) -> Result<Vec<u8>, Vec<u8>> {
+        let create = createStreamCall {
+            recipient: self.bob,
+            deposit: U256::from(deposit),
+            tokenAddress: self.token,
+            startTime: U256::from(START),
+            stopTime: U256::from(STOP),
+        };
+        self.host.call(self.alice, self.streams, &create.encode())
+    }
+
+    fn stream_balance(&mut self, who: Address) -> U256 {
+        let balance = balanceOf_1Call {
+            streamId: U256::from(1),
+            who,
+        };
+        self.host.call_sol(who, self.streams, &balance).unwrap()._0
+    }
+
+    fn token_balance(&mut self, owner: Address) -> U256 {
+        let balance = balanceOf_0Call { owner };
+        self.host.call_sol(owner, self.token, &balance).unwrap()._0
+    }
+
+    fn withdraw(&mut self, amount: u64) -> Result<Vec<u8>, Vec<u8>> {
+        let withdraw = withdrawFromStreamCall {
+            streamId: U256::from(1),
+            amount: U256::from(amount),
+        };
+        self.host.call(self.bob, self.streams, &withdraw.encode())
+    }
+}
+
+#[test]
+fn test_create_stream_deposit_not_multiple_of_duration() {
+    let mut s = setup();
+
+    let revert = s.create(1001).unwrap_err();
+
+    let error = StreamsError::StreamInvalidDeposit(StreamInvalidDeposit {
+        deposit: U256::from(1001),
+        duration: U256::from(100),
+    });
+    assert_eq!(revert, Vec::<u8>::from(error));
+    assert_eq!(s.token_balance(s.alice), U256::from(10_000));
+}
+
+#[test]
+fn test_balance_over_time() {
+    let mut s = setup();
+    s.create(1000).unwrap();
+    let (alice, bob) = (s.alice, s.bob);
+    assert_eq!(s.token_balance(s.streams), U256::from(1000));
+
+    // Nothing is streamed before the start
+    assert_eq!(s.stream_balance(bob), U256::ZERO);
+    assert_eq!(s.stream_balance(alice), U256::from(1000));
+
+    s.host.set_block_timestamp(START + 25);
+    assert_eq!(s.stream_balance(bob), U256::from(250));
+    assert_eq!(s.stream_balance(alice), U256::from(750));
+
+    // The stream stops at the stop time
+    s.host.set_block_timestamp(STOP + 100);
+    assert_eq!(s.stream_balance(bob), U256::from(1000));
+    assert_eq!(s.stream_balance(alice), U256::ZERO);
+    assert_eq!(s.stream_balance(TestHost::address("carol")), U256::ZERO);
+}
+
+#[test]
+fn test_partial_withdraw() {
+    let mut s = setup();
+    s.create(1000).unwrap();
+    let bob = s.bob;
+    s.host.set_block_timestamp(START + 40);
+
+    s.withdraw(300).unwrap();
+
+    assert_eq!(s.token_balance(bob), U256::from(300));
+    assert_eq!(s.stream_balance(bob), U256::from(100));
+    let revert = s.withdraw(101).unwrap_err();
+    let error = StreamsError::StreamInsufficientBalance(StreamInsufficientBalance {
+        balance: U256::from(100),
+        amount: U256::from(101),
+    });
+    assert_eq!(revert, Vec::<u8>::from(error));
+
+    // The stream is deleted once it has been fully withdrawn
+    s.host.set_block_timestamp(STOP);
+    s.withdraw(700).unwrap();
+    assert_eq!(s.token_balance(bob), U256::from(1000));
+    let get = getStreamCall {
+        streamId: U256::from(1),
+    };
+    let revert = s.host.call(bob, s.streams, &get.encode()).unwrap_err();
+    let error = StreamsError::StreamDoesNotExist(StreamDoesNotExist {
+        streamId: U256::from(1),
+    });
+    assert_eq!(revert, Vec::<u8>::from(error));
+}
+
+#[test]
+fn test_cancel_stream() {
+    let mut s = setup();
+    s.create(1000).unwrap();
+    let (alice, bob) = (s.alice, s.bob);
+    s.host.set_block_timestamp(START + 30);
+    s.withdraw(100).unwrap();
+    s.host.take_logs();
+
+    let cancel = cancelStreamCall {
+        streamId: U256::from(1),
+    };
+    s.host.call_sol(alice, s.streams, &cancel).unwrap();
+
+    // Bob is paid what was streamed and not withdrawn, and alice is refunded the rest
+    assert_eq!(s.token_balance(bob), U256::from(300));
+    assert_eq!(s.token_balance(alice), U256::from(9_700));
+    assert_eq!(s.token_balance(s.streams), U256::ZERO);
+    let logs = s.host.take_logs();
+    let event = logs.last().unwrap().decode::<CancelStream>().unwrap();
+    assert_eq!(
+        (event.senderBalance, event.recipientBalance),
+        (U256::from(700), U256::from(200))
+    );
+    assert!(s.host.call(bob, s.streams, &cancel.encode()).is_err());
+}
+
+#[test]
+fn test_cancel_stream_unauthorized() {
+    let mut s = setup();
+    s.create(1000).unwrap();
+    let carol = TestHost::address("carol");
+
+    let cancel = cancelStreamCall {
+        streamId: U256::from(1),
+    };
+    let revert = s.host.call(carol, s.streams, &cancel.encode()).unwrap_err();
+
+    let error = StreamsError::StreamUnauthorized(StreamUnauthorized {
+        streamId: U256::from(1),
+        caller: carol,
+    });
+    assert_eq!(revert, Vec::<u8>::from(error));
+}
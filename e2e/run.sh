@@ -0,0 +1,41 @@
+#!/usr/bin/env bash
+# Starts nitro-testnode, deploys the erc20 and erc721 samples, and runs the end-to-end tests.
+#
+# Environment:
+# - TESTNODE_DIR: the nitro-testnode checkout, cloned to target/nitro-testnode if missing
+# - TESTNODE_BRANCH: the branch of nitro-testnode to clone, `stylus` by default
+# - SKIP_TESTNODE: set to skip starting the node, ex. when it is already running
+# - RPC_URL, PRIVATE_KEY: the node and a funded account, which default to the dev node and its
+#   prefunded dev account
+set -euo pipefail
+
+ROOT="$(cd "$(dirname "$0")/.." && pwd)"
+TESTNODE_DIR="${TESTNODE_DIR:-$ROOT/target/nitro-testnode}"
+TESTNODE_BRANCH="${TESTNODE_BRANCH:-stylus}"
+export RPC_URL="${RPC_URL:-http://localhost:8547}"
+export PRIVATE_KEY="${PRIVATE_KEY:-0xb6b15c8cb491557369f3c7d2c287b053eb229daa9c22138887752191c9520659}"
+
+if [ -z "${SKIP_TESTNODE:-}" ]; then
+    if [ ! -d "$TESTNODE_DIR" ]; then
+        git clone -b "$TESTNODE_BRANCH" --recurse-submodules https://github.com/OffchainLabs/nitro-testnode.git "$TESTNODE_DIR"
+    fi
+    (cd "$TESTNODE_DIR" && ./test-node.bash --init --detach)
+fi
+
+# Builds the sample of `feature`, deploys it, and prints its address
+deploy() {
+    local feature="$1"
+    cargo +nightly build --target wasm32-unknown-unknown --lib --release --features="$feature" -p samples \
+        -Z build-std=std,panic_abort -Z build-std-features=panic_immediate_abort >&2
+    cargo stylus deploy -e "$RPC_URL" --private-key="$PRIVATE_KEY" \
+        --wasm-file-path "$ROOT/target/wasm32-unknown-unknown/release/samples.wasm" \
+        | sed 's/\x1b\[[0-9;]*m//g' | tee /dev/stderr \
+        | grep -oE 'address 0x[0-9a-fA-F]{40}' | tail -1 | cut -d' ' -f2
+}
+
+cd "$ROOT"
+export ERC20_ADDRESS="$(deploy erc20)"
+export ERC721_ADDRESS="$(deploy erc721)"
+
+# The tests share the funded account, so they are run one at a time
+cargo test -p inkmate-e2e -- --ignored --test-threads=1
@@ -0,0 +1,69 @@
+//! Helpers of the end-to-end tests, which call the samples deployed to a nitro dev node.
+//!
+//! The tests are ignored by default since they need a node, and are run by `e2e/run.sh`, which
+//! starts nitro-testnode, deploys the samples and sets the environment read here:
+//! - `RPC_URL`: the node, `http://localhost:8547` by default
+//! - `PRIVATE_KEY`: the key of a funded account
+//! - `ERC20_ADDRESS`, `ERC721_ADDRESS`: the deployed `erc20` and `erc721` samples
+
+use std::{env, sync::Arc};
+
+use ethers::{
+    middleware::SignerMiddleware,
+    providers::{Http, Middleware, Provider},
+    signers::{LocalWallet, Signer},
+    types::{Address, Bytes, TransactionReceipt, TransactionRequest},
+};
+use eyre::{eyre, Result};
+
+pub type Client = SignerMiddleware<Provider<Http>, LocalWallet>;
+
+/// Returns a client sending transactions from the account of `PRIVATE_KEY`.
+pub async fn client() -> Result<Arc<Client>> {
+    let rpc_url = env::var("RPC_URL").unwrap_or_else(|_| "http://localhost:8547".into());
+    let provider = Provider::<Http>::try_from(rpc_url)?;
+    let chain_id = provider.get_chainid().await?.as_u64();
+    let wallet = env::var("PRIVATE_KEY")
+        .map_err(|_| eyre!("PRIVATE_KEY must be set to the key of a funded account"))?
+        .parse::<LocalWallet>()?
+        .with_chain_id(chain_id);
+    Ok(Arc::new(SignerMiddleware::new(provider, wallet)))
+}
+
+/// Returns the address set in the environment variable `var`.
+pub fn address(var: &str) -> Result<Address> {
+    let address = env::var(var).map_err(|_| eyre!("{var} must be set to a deployed sample"))?;
+    Ok(address.parse()?)
+}
+
+/// Runtime code returning the `onERC721Received` selector to any call.
+const ACCEPTING_RECEIVER: &str = "63150b7a0260e01b60005260206000f3";
+
+/// Runtime code reverting any call.
+const REVERTING_RECEIVER: &str = "60006000fd";
+
+/// Deploys an ERC721 receiver, which accepts all tokens if `accepting` and reverts otherwise.
+pub async fn deploy_receiver(client: &Client, accepting: bool) -> Result<Address> {
+    let runtime = if accepting {
+        ACCEPTING_RECEIVER
+    } else {
+        REVERTING_RECEIVER
+    };
+    // Init code copying the runtime code that follows it to memory, and returning it
+    let len = runtime.len() / 2;
+    let init_code = format!("60{len:02x}80600b6000396000f3{runtime}");
+    let tx = TransactionRequest::new().data(init_code.parse::<Bytes>()?);
+    let receipt = send(client, tx).await?;
+    receipt
+        .contract_address
+        .ok_or_else(|| eyre!("receiver not deployed"))
+}
+
+/// Sends `tx`, and returns its receipt.
+async fn send(client: &Client, tx: TransactionRequest) -> Result<TransactionReceipt> {
+    client
+        .send_transaction(tx, None)
+        .await?
+        .await?
+        .ok_or_else(|| eyre!("transaction dropped"))
+}
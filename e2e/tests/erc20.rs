@@ -0,0 +1,104 @@
+//! End-to-end tests of the `erc20` sample (`samples/src/erc20_mock.rs`).
+
+use ethers::{
+    abi::{encode, Token},
+    contract::{abigen, parse_log},
+    types::{Address, H256, U256},
+    utils::keccak256,
+};
+use eyre::Result;
+use inkmate_e2e::{address, client};
+
+abigen!(
+    ERC20Mock,
+    r#"[
+        event Transfer(address indexed from, address indexed to, uint256 value)
+        event Approval(address indexed owner, address indexed spender, uint256 value)
+        function balanceOf(address owner) external view returns (uint256)
+        function allowance(address owner, address spender) external view returns (uint256)
+        function nonces(address owner) external view returns (uint256)
+        function DOMAIN_SEPARATOR() external view returns (bytes32)
+        function mint(uint256 qty) external
+        function transfer(address to, uint256 amount) external returns (bool)
+        function transferFrom(address from, address to, uint256 amount) external returns (bool)
+        function permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external
+    ]"#
+);
+
+#[tokio::test]
+#[ignore = "requires a nitro dev node, run with e2e/run.sh"]
+async fn test_transfer() -> Result<()> {
+    let client = client().await?;
+    let token = ERC20Mock::new(address("ERC20_ADDRESS")?, client.clone());
+    let owner = client.address();
+    let recipient = Address::random();
+    let amount = U256::from(100);
+
+    token.mint(amount).send().await?.await?;
+    let receipt = token
+        .transfer(recipient, amount)
+        .send()
+        .await?
+        .await?
+        .unwrap();
+
+    assert_eq!(token.balance_of(recipient).call().await?, amount);
+    let event = parse_log::<TransferFilter>(receipt.logs[0].clone())?;
+    assert_eq!(
+        (event.from, event.to, event.value),
+        (owner, recipient, amount)
+    );
+
+    // Transfers above the balance revert
+    let balance = token.balance_of(owner).call().await?;
+    assert!(token.transfer(recipient, balance + 1).call().await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires a nitro dev node, run with e2e/run.sh"]
+async fn test_permit() -> Result<()> {
+    let client = client().await?;
+    let token = ERC20Mock::new(address("ERC20_ADDRESS")?, client.clone());
+    let owner = client.address();
+    let spender = Address::random();
+    let amount = U256::from(25);
+
+    let nonce = token.nonces(owner).call().await?;
+    let domain_separator = token.domain_separator().call().await?;
+    let deadline = U256::MAX;
+    let struct_hash = keccak256(encode(&[
+        Token::FixedBytes(
+            keccak256(
+                "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)",
+            )
+            .to_vec(),
+        ),
+        Token::Address(owner),
+        Token::Address(spender),
+        Token::Uint(amount),
+        Token::Uint(nonce),
+        Token::Uint(deadline),
+    ]));
+    let digest = keccak256([&[0x19, 0x01], &domain_separator[..], &struct_hash[..]].concat());
+    let signature = client.signer().sign_hash(H256(digest))?;
+    let mut r = [0; 32];
+    let mut s = [0; 32];
+    signature.r.to_big_endian(&mut r);
+    signature.s.to_big_endian(&mut s);
+
+    let permit = token.permit(owner, spender, amount, deadline, signature.v as u8, r, s);
+    let receipt = permit.send().await?.await?.unwrap();
+
+    assert_eq!(token.allowance(owner, spender).call().await?, amount);
+    assert_eq!(token.nonces(owner).call().await?, nonce + 1);
+    let event = parse_log::<ApprovalFilter>(receipt.logs[0].clone())?;
+    assert_eq!(
+        (event.owner, event.spender, event.value),
+        (owner, spender, amount)
+    );
+
+    // The signature cannot be replayed
+    assert!(permit.call().await.is_err());
+    Ok(())
+}
@@ -0,0 +1,80 @@
+//! End-to-end tests of the `erc721` sample (`samples/src/erc721_mock.rs`).
+
+use ethers::{
+    contract::{abigen, parse_log},
+    types::{Address, U256},
+};
+use eyre::Result;
+use inkmate_e2e::{address, client, deploy_receiver};
+
+abigen!(
+    ERC721Mock,
+    r#"[
+        event Transfer(address indexed from, address indexed to, uint256 indexed id)
+        function totalSupply() external view returns (uint256)
+        function ownerOf(uint256 tokenId) external view returns (address)
+        function balanceOf(address owner) external view returns (uint256)
+        function mintLoop(uint256 qty) external
+        function transferFrom(address from, address to, uint256 tokenId) external
+        function safeTransferFrom(address from, address to, uint256 tokenId) external
+    ]"#
+);
+
+/// Mints a token to the caller, and returns its id.
+async fn mint(nft: &ERC721Mock<inkmate_e2e::Client>) -> Result<U256> {
+    let id = nft.total_supply().call().await?;
+    nft.mint_loop(U256::one()).send().await?.await?;
+    Ok(id)
+}
+
+#[tokio::test]
+#[ignore = "requires a nitro dev node, run with e2e/run.sh"]
+async fn test_transfer_from() -> Result<()> {
+    let client = client().await?;
+    let nft = ERC721Mock::new(address("ERC721_ADDRESS")?, client.clone());
+    let owner = client.address();
+    let recipient = Address::random();
+    let id = mint(&nft).await?;
+
+    let receipt = nft
+        .transfer_from(owner, recipient, id)
+        .send()
+        .await?
+        .await?
+        .unwrap();
+
+    assert_eq!(nft.owner_of(id).call().await?, recipient);
+    assert_eq!(nft.balance_of(recipient).call().await?, U256::one());
+    let event = parse_log::<TransferFilter>(receipt.logs[0].clone())?;
+    assert_eq!((event.from, event.to, event.id), (owner, recipient, id));
+
+    // The previous owner cannot transfer the token anymore
+    assert!(nft
+        .transfer_from(owner, recipient, id)
+        .call()
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires a nitro dev node, run with e2e/run.sh"]
+async fn test_safe_transfer_to_contract() -> Result<()> {
+    let client = client().await?;
+    let nft = ERC721Mock::new(address("ERC721_ADDRESS")?, client.clone());
+    let owner = client.address();
+    let id = mint(&nft).await?;
+
+    // Contracts refusing the token make the transfer revert
+    let refusing = deploy_receiver(&client, false).await?;
+    let transfer = nft.safe_transfer_from(owner, refusing, id);
+    assert!(transfer.call().await.is_err());
+
+    let receiver = deploy_receiver(&client, true).await?;
+    nft.safe_transfer_from(owner, receiver, id)
+        .send()
+        .await?
+        .await?;
+    assert_eq!(nft.owner_of(id).call().await?, receiver);
+    Ok(())
+}
@@ -0,0 +1,249 @@
+//! `#[derive(EIP712)]` implementation.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    Attribute, Data, DataStruct, DeriveInput, Error, Expr, ExprLit, Fields, GenericArgument, Lit,
+    LitStr, PathArguments, Type,
+};
+
+/// Generates the `EIP712Struct` implementation of the struct `input`.
+pub fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "EIP712 can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    let attrs = Eip712Attrs::parse(&input.attrs)?;
+    if attrs.ty.is_some() {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "`type` can only be set on fields",
+        ));
+    }
+    let struct_name = attrs.name.unwrap_or_else(|| input.ident.to_string());
+
+    let mut members = Vec::with_capacity(fields.len());
+    let mut idents = Vec::with_capacity(fields.len());
+    for field in fields {
+        let attrs = Eip712Attrs::parse(&field.attrs)?;
+        let ident = field.ident.as_ref().expect("named field");
+        let ty = match attrs.ty {
+            Some(ty) => ty,
+            None => solidity_type(&field.ty).ok_or_else(|| {
+                Error::new_spanned(
+                    &field.ty,
+                    "unsupported EIP-712 field type, set its Solidity type with #[eip712(type = \"...\")]",
+                )
+            })?,
+        };
+        let name = attrs
+            .name
+            .unwrap_or_else(|| to_camel_case(&ident.to_string()));
+        members.push(format!("{ty} {name}"));
+        idents.push(ident);
+    }
+    let encoded_type = format!("{struct_name}({})", members.join(","));
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::inkmate::utils::eip712::EIP712Struct for #ident #ty_generics #where_clause {
+            const ENCODED_TYPE: &'static str = #encoded_type;
+            const TYPEHASH: ::inkmate::macros::B256 = ::inkmate::const_keccak!(#encoded_type);
+
+            fn struct_hash(&self) -> ::inkmate::macros::B256 {
+                ::inkmate::utils::eip712::hash_struct(
+                    Self::TYPEHASH,
+                    &[#(::inkmate::utils::eip712::EIP712Encode::encode_eip712(&self.#idents)),*],
+                )
+            }
+        }
+    })
+}
+
+/// The `#[eip712(...)]` attributes of a struct or field
+#[derive(Default)]
+struct Eip712Attrs {
+    /// The name of the struct or field in the encoded type
+    name: Option<String>,
+    /// The Solidity type of the field
+    ty: Option<String>,
+}
+
+impl Eip712Attrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut parsed = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("eip712")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    parsed.name = Some(meta.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else if meta.path.is_ident("type") {
+                    parsed.ty = Some(meta.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported eip712 attribute, expected `name` or `type`"))
+                }
+            })?;
+        }
+        Ok(parsed)
+    }
+}
+
+/// Returns the Solidity type of the Rust type `ty`, if it is supported.
+fn solidity_type(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Array(array) => {
+            let len = int_literal(&array.len)?;
+            Some(format!("{}[{len}]", solidity_type(&array.elem)?))
+        }
+        Type::Path(path) if path.qself.is_none() => {
+            let segment = path.path.segments.last()?;
+            let ident = segment.ident.to_string();
+            let generic = || match &segment.arguments {
+                PathArguments::AngleBracketed(args) => args.args.first(),
+                _ => None,
+            };
+            let generic_len = || match generic()? {
+                GenericArgument::Const(expr) => int_literal(expr),
+                _ => None,
+            };
+            match ident.as_str() {
+                "Address" => Some("address".into()),
+                "bool" => Some("bool".into()),
+                "String" => Some("string".into()),
+                "Bytes" => Some("bytes".into()),
+                "FixedBytes" => fixed_bytes(generic_len()? * 8),
+                "Uint" => int_type("uint", generic_len()?),
+                "Signed" => int_type("int", generic_len()?),
+                "Vec" => match generic()? {
+                    GenericArgument::Type(elem) => Some(format!("{}[]", solidity_type(elem)?)),
+                    _ => None,
+                },
+                _ => {
+                    let (prefix, bits) = ident.split_at(ident.chars().next()?.len_utf8());
+                    let bits = bits.parse().ok()?;
+                    match prefix {
+                        "B" => fixed_bytes(bits),
+                        "U" | "u" => int_type("uint", bits),
+                        "I" | "i" => int_type("int", bits),
+                        _ => None,
+                    }
+                }
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the `bytesN` type of `bits` bits, if valid.
+fn fixed_bytes(bits: usize) -> Option<String> {
+    (bits.is_multiple_of(8) && (8..=256).contains(&bits)).then(|| format!("bytes{}", bits / 8))
+}
+
+/// Returns the `uintN` or `intN` type of `bits` bits, if valid.
+fn int_type(prefix: &str, bits: usize) -> Option<String> {
+    (bits.is_multiple_of(8) && (8..=256).contains(&bits)).then(|| format!("{prefix}{bits}"))
+}
+
+/// Returns the value of the integer literal `expr`.
+fn int_literal(expr: &Expr) -> Option<usize> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse().ok(),
+        _ => None,
+    }
+}
+
+/// Converts a snake case field name to the camel case used by Solidity (ex. `token_id` to
+/// `tokenId`).
+fn to_camel_case(name: &str) -> String {
+    let mut camel = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.trim_start_matches("r#").chars() {
+        if c == '_' {
+            upper = !camel.is_empty();
+        } else if upper {
+            camel.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            camel.push(c);
+        }
+    }
+    camel
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_solidity_type() {
+        let cases: [(Type, &str); 12] = [
+            (parse_quote!(Address), "address"),
+            (parse_quote!(bool), "bool"),
+            (parse_quote!(String), "string"),
+            (parse_quote!(stylus_sdk::abi::Bytes), "bytes"),
+            (parse_quote!(B256), "bytes32"),
+            (parse_quote!(FixedBytes<4>), "bytes4"),
+            (parse_quote!(U256), "uint256"),
+            (parse_quote!(Uint<96, 2>), "uint96"),
+            (parse_quote!(I128), "int128"),
+            (parse_quote!(u64), "uint64"),
+            (parse_quote!(Vec<Address>), "address[]"),
+            (parse_quote!([Vec<u8>; 3]), "uint8[][3]"),
+        ];
+        for (ty, expected) in cases {
+            assert_eq!(solidity_type(&ty).as_deref(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_unsupported_type() {
+        let cases: [Type; 4] = [
+            parse_quote!(Order),
+            parse_quote!(U7),
+            parse_quote!(B512),
+            parse_quote!(Option<U256>),
+        ];
+        for ty in cases {
+            assert_eq!(solidity_type(&ty), None);
+        }
+    }
+
+    #[test]
+    fn test_encoded_type() {
+        let input: DeriveInput = parse_quote! {
+            #[eip712(name = "MintVoucher")]
+            struct Voucher {
+                token_id: U256,
+                uri: String,
+                #[eip712(type = "uint96")]
+                price: Price,
+                #[eip712(name = "to")]
+                recipient: Address,
+            }
+        };
+        let expected = "MintVoucher(uint256 tokenId,string uri,uint96 price,address to)";
+        assert!(derive(input).unwrap().to_string().contains(expected));
+    }
+
+    #[test]
+    fn test_to_camel_case() {
+        assert_eq!(to_camel_case("token_id"), "tokenId");
+        assert_eq!(to_camel_case("verifying_contract"), "verifyingContract");
+        assert_eq!(to_camel_case("nonce"), "nonce");
+        assert_eq!(to_camel_case("_unused_field"), "unusedField");
+    }
+}
@@ -0,0 +1,46 @@
+//! Procedural macros for inkmate
+//!
+//! The macros are re-exported by the `inkmate` crate behind feature flags, and the code they
+//! generate refers to items of `inkmate`, so they should be used through `inkmate` rather than
+//! by depending on this crate directly.
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, ItemStruct, LitStr};
+
+mod eip712;
+mod namespace;
+mod storage_layout;
+
+/// Implements `inkmate::utils::eip712::EIP712Struct` for a struct, generating its EIP-712 encoded
+/// type, typehash and struct hash. See `inkmate::utils::eip712` for the supported field types and
+/// attributes.
+#[proc_macro_derive(EIP712, attributes(eip712))]
+pub fn derive_eip712(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    eip712::derive(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `inkmate::export::layout::StorageLayout` for a `sol_storage!` struct, so that its
+/// storage layout can be exported. Every field must implement `StorageLayout`, which the SDK storage
+/// types and the annotated structs do. See `inkmate::export::layout`.
+#[proc_macro_attribute]
+pub fn storage_layout(_attr: TokenStream, input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ItemStruct);
+    storage_layout::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Roots the fields of a `sol_storage!` struct at the ERC-7201 slot of the namespace passed as
+/// argument, instead of the next slots of the struct containing it, in which it then takes no
+/// space. Must be placed after `#[storage_layout]`. See the `erc7201` feature of `inkmate`.
+#[proc_macro_attribute]
+pub fn namespace(attr: TokenStream, input: TokenStream) -> TokenStream {
+    let id = parse_macro_input!(attr as LitStr);
+    let input = parse_macro_input!(input as ItemStruct);
+    namespace::expand(id, input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
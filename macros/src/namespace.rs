@@ -0,0 +1,161 @@
+//! `#[namespace]` implementation.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Error, Fields, ItemStruct, LitStr};
+
+/// Returns the struct `input` with a `StorageType` implementation placing its fields from the
+/// ERC-7201 slot of the namespace `id`, in place of the one of `#[solidity_storage]`.
+pub fn expand(id: LitStr, mut input: ItemStruct) -> syn::Result<TokenStream> {
+    // `sol_storage!` puts `#[solidity_storage]` after the attributes of the struct, so it has not
+    // run yet
+    input.attrs.retain(|attr| !is_solidity_storage(attr));
+
+    let ident = input.ident.clone();
+    let generics = input.generics.clone();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let Fields::Named(fields) = &mut input.fields else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "namespace can only be used on structs with named fields",
+        ));
+    };
+
+    let mut init = Vec::with_capacity(fields.named.len());
+    let mut borrows = Vec::new();
+    for field in &mut fields.named {
+        let ty = &field.ty;
+        let name = field.ident.as_ref().expect("named field");
+        let len = field.attrs.len();
+        field.attrs.retain(|attr| !attr.path().is_ident("borrow"));
+        if field.attrs.len() != len {
+            borrows.push(quote! {
+                impl #impl_generics ::core::borrow::Borrow<#ty> for #ident #ty_generics #where_clause {
+                    fn borrow(&self) -> &#ty {
+                        &self.#name
+                    }
+                }
+                impl #impl_generics ::core::borrow::BorrowMut<#ty> for #ident #ty_generics #where_clause {
+                    fn borrow_mut(&mut self) -> &mut #ty {
+                        &mut self.#name
+                    }
+                }
+            });
+        }
+        // Same placement as `#[solidity_storage]`, from the namespace rather than `root`
+        init.push(quote! {
+            #name: {
+                let bytes = <#ty as storage::StorageType>::SLOT_BYTES;
+                let words = <#ty as storage::StorageType>::REQUIRED_SLOTS;
+                if space < bytes {
+                    space = 32;
+                    slot += 1;
+                }
+                space -= bytes;
+
+                let root = root + alloy_primitives::U256::from(slot);
+                let field = <#ty as storage::StorageType>::new(root, space as u8);
+                if words > 0 {
+                    slot += words;
+                    space = 32;
+                }
+                field
+            },
+        });
+    }
+
+    Ok(quote! {
+        #input
+
+        impl #impl_generics stylus_sdk::storage::StorageType for #ident #ty_generics #where_clause {
+            type Wraps<'a> = stylus_sdk::storage::StorageGuard<'a, Self> where Self: 'a;
+            type WrapsMut<'a> = stylus_sdk::storage::StorageGuardMut<'a, Self> where Self: 'a;
+
+            // Takes no space in the struct containing it
+            const SLOT_BYTES: usize = 0;
+            const REQUIRED_SLOTS: usize = 0;
+
+            #[allow(unused_mut, unused_variables)]
+            unsafe fn new(_root: stylus_sdk::alloy_primitives::U256, _offset: u8) -> Self {
+                use stylus_sdk::{storage, alloy_primitives};
+                const NAMESPACE: ::inkmate::macros::B256 = ::inkmate::erc7201_slot!(#id);
+
+                let root = alloy_primitives::U256::from_be_bytes(NAMESPACE.0);
+                let mut space: usize = 32;
+                let mut slot: usize = 0;
+                Self {
+                    #(#init)*
+                }
+            }
+
+            fn load<'s>(self) -> Self::Wraps<'s> {
+                stylus_sdk::storage::StorageGuard::new(self)
+            }
+
+            fn load_mut<'s>(self) -> Self::WrapsMut<'s> {
+                stylus_sdk::storage::StorageGuardMut::new(self)
+            }
+        }
+
+        #(#borrows)*
+    })
+}
+
+/// Returns the namespace of the struct with the attributes `attrs`, if it has a `#[namespace]`.
+pub fn find(attrs: &[Attribute]) -> syn::Result<Option<LitStr>> {
+    attrs
+        .iter()
+        .find(|attr| {
+            attr.path()
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "namespace")
+        })
+        .map(|attr| attr.parse_args())
+        .transpose()
+}
+
+fn is_solidity_storage(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "solidity_storage")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_expand() {
+        let input: ItemStruct = parse_quote! {
+            #[stylus_sdk::stylus_proc::solidity_storage]
+            pub struct Ownable {
+                #[borrow]
+                pub owner: StorageAddress,
+            }
+        };
+        let output = expand(parse_quote!("inkmate.storage.Ownable"), input)
+            .unwrap()
+            .to_string();
+        assert!(!output.contains("solidity_storage"));
+        assert!(!output.contains("# [borrow]"));
+        assert!(output.contains("impl :: core :: borrow :: Borrow < StorageAddress > for Ownable"));
+        assert!(output.contains("erc7201_slot ! (\"inkmate.storage.Ownable\")"));
+        assert!(output.contains("const SLOT_BYTES : usize = 0"));
+    }
+
+    #[test]
+    fn test_find() {
+        let input: ItemStruct = parse_quote! {
+            #[inkmate_macros::namespace("inkmate.storage.Ownable")]
+            pub struct Ownable {}
+        };
+        assert_eq!(
+            find(&input.attrs).unwrap().unwrap().value(),
+            "inkmate.storage.Ownable"
+        );
+        assert!(find(&[]).unwrap().is_none());
+    }
+}
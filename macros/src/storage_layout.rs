@@ -0,0 +1,100 @@
+//! `#[storage_layout]` implementation.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Error, Fields, ItemStruct};
+
+use crate::namespace;
+
+/// Returns the struct `input` unchanged, with its `StorageLayout` implementation.
+pub fn expand(input: ItemStruct) -> syn::Result<TokenStream> {
+    let Fields::Named(fields) = &input.fields else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "storage_layout can only be used on structs with named fields",
+        ));
+    };
+
+    let ident = &input.ident;
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    let mut places = Vec::with_capacity(fields.named.len());
+    for field in &fields.named {
+        let ty = &field.ty;
+        let name = field.ident.as_ref().expect("named field").to_string();
+        where_clause
+            .predicates
+            .push(parse_quote!(#ty: ::inkmate::export::layout::StorageLayout));
+        places.push(quote! {
+            cursor.field::<#ty>(label, #name, fields);
+        });
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    // The fields of a namespaced struct are placed from its namespace, whatever its slot
+    let root = match namespace::find(&input.attrs)? {
+        Some(id) => quote! {
+            ::inkmate::export::layout::U256::from_be_bytes(::inkmate::erc7201_slot!(#id).0)
+        },
+        None => quote!(slot),
+    };
+
+    Ok(quote! {
+        #input
+
+        impl #impl_generics ::inkmate::export::layout::StorageLayout for #ident #ty_generics #where_clause {
+            #[allow(unused_mut, unused_variables)]
+            fn layout(
+                label: &str,
+                slot: ::inkmate::export::layout::U256,
+                _offset: u8,
+                fields: &mut ::std::vec::Vec<::inkmate::export::layout::Field>,
+            ) {
+                let mut cursor = ::inkmate::export::layout::Cursor::new(#root);
+                #(#places)*
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand() {
+        let input: ItemStruct = parse_quote! {
+            pub struct Token<T> {
+                pub total_supply: StorageU256,
+                pub phantom: PhantomData<T>,
+            }
+        };
+        let output = expand(input).unwrap().to_string();
+        assert!(output
+            .contains("cursor . field :: < StorageU256 > (label , \"total_supply\" , fields)"));
+        assert!(
+            output.contains("PhantomData < T > : :: inkmate :: export :: layout :: StorageLayout")
+        );
+    }
+
+    #[test]
+    fn test_namespaced() {
+        let input: ItemStruct = parse_quote! {
+            #[inkmate_macros::namespace("inkmate.storage.Ownable")]
+            pub struct Ownable {
+                pub owner: StorageAddress,
+            }
+        };
+        let output = expand(input).unwrap().to_string();
+        assert!(output.contains("Cursor :: new (:: inkmate :: export :: layout :: U256 :: from_be_bytes (:: inkmate :: erc7201_slot ! (\"inkmate.storage.Ownable\") . 0))"));
+        // The namespace still has to run
+        assert!(output.contains("# [inkmate_macros :: namespace (\"inkmate.storage.Ownable\")]"));
+    }
+
+    #[test]
+    fn test_tuple_struct() {
+        let input: ItemStruct = parse_quote!(
+            struct Wrapper(StorageU256);
+        );
+        assert!(expand(input).is_err());
+    }
+}
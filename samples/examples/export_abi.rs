@@ -0,0 +1,7 @@
+//! Exports the ABI of the sample selected by its feature. Prints its Solidity interface by default,
+//! ex. `cargo run -p samples --example export_abi --features erc20-preset,export-abi`, or writes
+//! its JSON ABI and Foundry-style artifact with `-- --json out/ --foundry out/`.
+
+fn main() {
+    inkmate::export::abi::main::<samples::Exported>();
+}
@@ -0,0 +1,8 @@
+//! Exports the storage layout of the sample selected by its feature. Prints it as JSON by default,
+//! ex. `cargo run -p samples --example export_layout --features erc20-preset,export-layout`, writes
+//! it to a file with `-- --json out/`, or checks that an upgrade keeps the layout of a previous
+//! version with `-- --check out/ERC20PresetMock.layout.json`.
+
+fn main() {
+    inkmate::export::layout::main::<samples::Exported>();
+}
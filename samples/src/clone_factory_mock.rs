@@ -0,0 +1,41 @@
+//! An example factory deploying EIP-1167 clones of an implementation contract
+extern crate alloc;
+
+use crate::inkmate::utils::clones;
+use stylus_sdk::{
+    alloy_primitives::{Address, B256},
+    prelude::*,
+};
+
+sol_storage! {
+    #[entrypoint] // Makes CloneFactoryMock the entrypoint
+    struct CloneFactoryMock {
+        address implementation;
+    }
+}
+
+#[external]
+impl CloneFactoryMock {
+    pub fn implementation(&self) -> Address {
+        self.implementation.get()
+    }
+
+    pub fn set_implementation(&mut self, implementation: Address) {
+        self.implementation.set(implementation);
+    }
+
+    pub fn create_clone(&mut self) -> Result<Address, Vec<u8>> {
+        Ok(clones::clone(self.implementation.get())?)
+    }
+
+    pub fn create_clone_deterministic(&mut self, salt: B256) -> Result<Address, Vec<u8>> {
+        Ok(clones::clone_deterministic(
+            self.implementation.get(),
+            salt,
+        )?)
+    }
+
+    pub fn predict_clone_address(&self, salt: B256) -> Address {
+        clones::predict_deterministic_address(self.implementation.get(), salt)
+    }
+}
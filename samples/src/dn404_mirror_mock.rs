@@ -0,0 +1,22 @@
+//! An example DN404 mirror contract, paired with the base in `dn404_mock`
+extern crate alloc;
+
+use crate::inkmate::tokens::dn404_mirror::DN404Mirror;
+use stylus_sdk::{alloy_primitives::Address, prelude::*};
+
+sol_storage! {
+    #[entrypoint] // Makes DN404MirrorMock the entrypoint
+    struct DN404MirrorMock {
+        #[borrow]
+        DN404Mirror mirror;
+    }
+}
+
+#[external]
+#[inherit(DN404Mirror)]
+impl DN404MirrorMock {
+    pub fn init(&mut self, base: Address) -> Result<(), Vec<u8>> {
+        self.mirror._initialize_mirror(base)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+//! An example DN404 contract, paired with the mirror in `dn404_mirror_mock`
+extern crate alloc;
+
+use crate::inkmate::{
+    access::ownable::Ownable,
+    tokens::dn404::{DN404Params, DN404},
+};
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+struct DN404MockParams;
+
+/// Immutable definitions
+impl DN404Params for DN404MockParams {
+    const NAME: &'static str = "DN404 Stylus Example";
+    const SYMBOL: &'static str = "MOCK";
+
+    fn token_uri(_token_id: U256) -> String {
+        String::new()
+    }
+}
+
+sol_storage! {
+    #[entrypoint] // Makes DN404Mock the entrypoint
+    struct DN404Mock {
+        #[borrow]
+        DN404<DN404MockParams> dn404;
+        #[borrow]
+        Ownable ownable;
+    }
+}
+
+#[external]
+#[inherit(DN404<DN404MockParams>, Ownable)]
+impl DN404Mock {
+    pub fn init(&mut self, initial_supply: U256, mirror: Address) -> Result<(), Vec<u8>> {
+        self.ownable._initialize_owner(msg::sender())?;
+        self.dn404
+            ._initialize_dn404(initial_supply, msg::sender(), mirror)?;
+        Ok(())
+    }
+
+    pub fn mint(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.dn404._mint(to, amount)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+//! An example ERC721 collection sold through a Dutch auction with rebates
+extern crate alloc;
+
+use crate::inkmate::{
+    access::ownable::Ownable,
+    market::dutch_auction_mint::{DutchAuctionMint, DutchAuctionParams},
+    tokens::erc721::{ERC721Params, ERC721},
+};
+use core::borrow::{Borrow, BorrowMut};
+use stylus_sdk::{alloy_primitives::U256, msg, prelude::*};
+
+struct DutchAuctionMockParams;
+
+/// Immutable definitions
+impl ERC721Params for DutchAuctionMockParams {
+    const NAME: &'static str = "Dutch Auction Stylus Example";
+    const SYMBOL: &'static str = "MOCK";
+
+    fn token_uri(_token_id: U256) -> String {
+        String::new()
+    }
+}
+
+/// Drop from 1 ETH to 0.1 ETH over an hour, every 10 minutes
+impl DutchAuctionParams for DutchAuctionMockParams {
+    const START_PRICE: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+    const RESTING_PRICE: U256 = U256::from_limbs([100_000_000_000_000_000, 0, 0, 0]);
+    const DURATION: u64 = 3600;
+    const DROP_INTERVAL: u64 = 600;
+    const MAX_SUPPLY: u64 = 1000;
+    const MAX_PER_WALLET: u64 = 5;
+    const REBATE: bool = true;
+}
+
+sol_storage! {
+    #[entrypoint] // Makes DutchAuctionMock the entrypoint
+    struct DutchAuctionMock {
+        #[borrow]
+        DutchAuctionMint<DutchAuctionMockParams> auction;
+    }
+}
+
+// DutchAuctionMint wraps ERC721 and Ownable, so the entrypoint must be able to borrow both
+impl Borrow<ERC721<DutchAuctionMockParams>> for DutchAuctionMock {
+    fn borrow(&self) -> &ERC721<DutchAuctionMockParams> {
+        &self.auction.erc721
+    }
+}
+
+impl BorrowMut<ERC721<DutchAuctionMockParams>> for DutchAuctionMock {
+    fn borrow_mut(&mut self) -> &mut ERC721<DutchAuctionMockParams> {
+        &mut self.auction.erc721
+    }
+}
+
+impl Borrow<Ownable> for DutchAuctionMock {
+    fn borrow(&self) -> &Ownable {
+        &self.auction.ownable
+    }
+}
+
+impl BorrowMut<Ownable> for DutchAuctionMock {
+    fn borrow_mut(&mut self) -> &mut Ownable {
+        &mut self.auction.ownable
+    }
+}
+
+#[external]
+#[inherit(
+    DutchAuctionMint<DutchAuctionMockParams>,
+    ERC721<DutchAuctionMockParams>,
+    Ownable
+)]
+impl DutchAuctionMock {
+    pub fn init(&mut self) -> Result<(), Vec<u8>> {
+        self.auction.ownable._initialize_owner(msg::sender())?;
+        Ok(())
+    }
+}
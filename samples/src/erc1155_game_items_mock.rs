@@ -0,0 +1,54 @@
+//! An example deployment of the ERC1155GameItems preset
+extern crate alloc;
+
+use crate::inkmate::access::access_control::AccessControl;
+use crate::inkmate::presets::erc1155_game_items::ERC1155GameItems;
+use crate::inkmate::tokens::erc1155::ERC1155;
+use core::borrow::{Borrow, BorrowMut};
+use stylus_sdk::{alloy_primitives::Address, prelude::*};
+
+sol_storage! {
+    #[entrypoint] // Makes ERC1155GameItemsMock the entrypoint
+    #[cfg_attr(feature = "export-layout", inkmate::export::layout::storage_layout)]
+    pub struct ERC1155GameItemsMock {
+        #[borrow]
+        ERC1155GameItems items;
+    }
+}
+
+// The preset wraps ERC1155 and AccessControl, so the entrypoint must be able to borrow them
+impl Borrow<ERC1155> for ERC1155GameItemsMock {
+    fn borrow(&self) -> &ERC1155 {
+        &self.items.erc1155
+    }
+}
+
+impl BorrowMut<ERC1155> for ERC1155GameItemsMock {
+    fn borrow_mut(&mut self) -> &mut ERC1155 {
+        &mut self.items.erc1155
+    }
+}
+
+impl Borrow<AccessControl> for ERC1155GameItemsMock {
+    fn borrow(&self) -> &AccessControl {
+        &self.items.access
+    }
+}
+
+impl BorrowMut<AccessControl> for ERC1155GameItemsMock {
+    fn borrow_mut(&mut self) -> &mut AccessControl {
+        &mut self.items.access
+    }
+}
+
+#[external]
+#[inherit(ERC1155GameItems, ERC1155, AccessControl)]
+impl ERC1155GameItemsMock {
+    /// Grants every role of the collection to `admin` and sets the URI of all items, and must be
+    /// called right after the deployment.
+    pub fn initialize(&mut self, admin: Address) -> Result<(), Vec<u8>> {
+        self.items
+            ._initialize(admin, "https://game.example/api/items/{id}.json")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+//! An example ERC20FlashMint contract
+extern crate alloc;
+
+use crate::inkmate::tokens::erc20::{ERC20Params, ERC20};
+use crate::inkmate::tokens::extensions::erc20_flashmint::{ERC20FlashMint, ERC20FlashMintParams};
+use core::borrow::{Borrow, BorrowMut};
+use stylus_sdk::{alloy_primitives::U256, msg, prelude::*};
+
+struct ERC20FlashMintMockParams;
+
+/// Immutable definitions
+impl ERC20Params for ERC20FlashMintMockParams {
+    const NAME: &'static str = "ERC20FlashMint Stylus Example";
+    const SYMBOL: &'static str = "MOCK";
+    const DECIMALS: u8 = 18;
+}
+
+/// Flash loans are free and the borrowed tokens are burned on repayment
+impl ERC20FlashMintParams for ERC20FlashMintMockParams {}
+
+sol_storage! {
+    #[entrypoint] // Makes ERC20FlashMintMock the entrypoint
+    struct ERC20FlashMintMock {
+        #[borrow]
+        ERC20FlashMint<ERC20FlashMintMockParams> flashmint;
+    }
+}
+
+// ERC20FlashMint wraps ERC20, so the entrypoint must be able to borrow the underlying token
+impl Borrow<ERC20<ERC20FlashMintMockParams>> for ERC20FlashMintMock {
+    fn borrow(&self) -> &ERC20<ERC20FlashMintMockParams> {
+        &self.flashmint.erc20
+    }
+}
+
+impl BorrowMut<ERC20<ERC20FlashMintMockParams>> for ERC20FlashMintMock {
+    fn borrow_mut(&mut self) -> &mut ERC20<ERC20FlashMintMockParams> {
+        &mut self.flashmint.erc20
+    }
+}
+
+#[external]
+#[inherit(ERC20FlashMint<ERC20FlashMintMockParams>, ERC20<ERC20FlashMintMockParams>)]
+impl ERC20FlashMintMock {
+    pub fn mint(&mut self, qty: U256) -> Result<(), Vec<u8>> {
+        ERC20::_mint(&mut self.flashmint.erc20, msg::sender(), qty)?;
+        Ok(())
+    }
+}
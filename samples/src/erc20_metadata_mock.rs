@@ -0,0 +1,41 @@
+//! An example ERC20 contract whose metadata is set at runtime, ex. when deployed as a clone
+extern crate alloc;
+
+use crate::inkmate::tokens::erc20::{ERC20Params, ERC20};
+use crate::inkmate::tokens::extensions::erc20_metadata::ERC20Metadata;
+use stylus_sdk::{alloy_primitives::U256, msg, prelude::*};
+
+struct ERC20MetadataMockParams;
+
+/// Only used as the default EIP-712 domain name, which is overridden in `init`
+impl ERC20Params for ERC20MetadataMockParams {
+    const NAME: &'static str = "";
+    const SYMBOL: &'static str = "";
+    const DECIMALS: u8 = 18;
+}
+
+sol_storage! {
+    #[entrypoint] // Makes ERC20MetadataMock the entrypoint
+    struct ERC20MetadataMock {
+        #[borrow]
+        ERC20Metadata metadata;
+        #[borrow]
+        ERC20<ERC20MetadataMockParams> erc20;
+    }
+}
+
+#[external]
+#[inherit(ERC20Metadata, ERC20<ERC20MetadataMockParams>)]
+impl ERC20MetadataMock {
+    pub fn init(&mut self, name: String, symbol: String, decimals: u8) -> Result<(), Vec<u8>> {
+        self.metadata
+            ._initialize_metadata(&name, &symbol, decimals)?;
+        self.erc20.eip712._set_name(&name);
+        Ok(())
+    }
+
+    pub fn mint(&mut self, qty: U256) -> Result<(), Vec<u8>> {
+        ERC20::_mint(&mut self.erc20, msg::sender(), qty)?;
+        Ok(())
+    }
+}
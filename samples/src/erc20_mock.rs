@@ -25,12 +25,12 @@ sol_storage! {
 #[inherit(ERC20<ERC20MockParams>)]
 impl ERC20Mock {
     pub fn mint(&mut self, qty: U256) -> Result<(), Vec<u8>> {
-        self.erc20._mint(msg::sender(), qty);
+        ERC20::_mint(&mut self.erc20, msg::sender(), qty)?;
         Ok(())
     }
 
     pub fn burn(&mut self, qty: U256) -> Result<(), Vec<u8>> {
-        self.erc20._burn(msg::sender(), qty)?;
+        ERC20::_burn(&mut self.erc20, msg::sender(), qty)?;
         Ok(())
     }
 }
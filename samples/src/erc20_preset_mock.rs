@@ -0,0 +1,89 @@
+//! An example deployment of the ERC20MinterPauserCapped preset
+extern crate alloc;
+
+use crate::inkmate::access::access_control::AccessControl;
+use crate::inkmate::presets::erc20_minter_pauser_capped::{
+    ERC20MinterPauserCapped, ERC20PresetParams,
+};
+use crate::inkmate::security::pausable::Pausable;
+use crate::inkmate::tokens::erc20::{ERC20Params, ERC20};
+use core::borrow::{Borrow, BorrowMut};
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    prelude::*,
+};
+
+struct ERC20PresetMockParams;
+
+/// Immutable definitions
+impl ERC20Params for ERC20PresetMockParams {
+    const NAME: &'static str = "ERC20 Preset Stylus Example";
+    const SYMBOL: &'static str = "MOCK";
+    const DECIMALS: u8 = 18;
+}
+
+/// A cap of one billion tokens, 10^27 with 18 decimals
+impl ERC20PresetParams for ERC20PresetMockParams {
+    const CAP: U256 = U256::from_limbs([0x9fd0803ce8000000, 0x33b2e3c, 0, 0]);
+}
+
+sol_storage! {
+    #[entrypoint] // Makes ERC20PresetMock the entrypoint
+    #[cfg_attr(feature = "export-layout", inkmate::export::layout::storage_layout)]
+    pub struct ERC20PresetMock {
+        #[borrow]
+        ERC20MinterPauserCapped<ERC20PresetMockParams> preset;
+    }
+}
+
+// The preset wraps ERC20, AccessControl and Pausable, so the entrypoint must be able to borrow them
+impl Borrow<ERC20<ERC20PresetMockParams>> for ERC20PresetMock {
+    fn borrow(&self) -> &ERC20<ERC20PresetMockParams> {
+        &self.preset.erc20
+    }
+}
+
+impl BorrowMut<ERC20<ERC20PresetMockParams>> for ERC20PresetMock {
+    fn borrow_mut(&mut self) -> &mut ERC20<ERC20PresetMockParams> {
+        &mut self.preset.erc20
+    }
+}
+
+impl Borrow<AccessControl> for ERC20PresetMock {
+    fn borrow(&self) -> &AccessControl {
+        &self.preset.access
+    }
+}
+
+impl BorrowMut<AccessControl> for ERC20PresetMock {
+    fn borrow_mut(&mut self) -> &mut AccessControl {
+        &mut self.preset.access
+    }
+}
+
+impl Borrow<Pausable> for ERC20PresetMock {
+    fn borrow(&self) -> &Pausable {
+        &self.preset.pausable
+    }
+}
+
+impl BorrowMut<Pausable> for ERC20PresetMock {
+    fn borrow_mut(&mut self) -> &mut Pausable {
+        &mut self.preset.pausable
+    }
+}
+
+#[external]
+#[inherit(
+    ERC20MinterPauserCapped<ERC20PresetMockParams>,
+    ERC20<ERC20PresetMockParams>,
+    AccessControl,
+    Pausable
+)]
+impl ERC20PresetMock {
+    /// Grants every role of the token to `admin`, and must be called right after the deployment.
+    pub fn initialize(&mut self, admin: Address) -> Result<(), Vec<u8>> {
+        self.preset._initialize(admin)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+//! An example ERC20Votes contract
+extern crate alloc;
+
+use crate::inkmate::governance::votes::{Clock, Votes, VotesParams};
+use crate::inkmate::tokens::erc20::{ERC20Params, ERC20};
+use crate::inkmate::tokens::extensions::erc20_votes::ERC20Votes;
+use core::borrow::{Borrow, BorrowMut};
+use stylus_sdk::{alloy_primitives::U256, msg, prelude::*};
+
+struct ERC20VotesMockParams;
+
+/// Immutable definitions
+impl ERC20Params for ERC20VotesMockParams {
+    const NAME: &'static str = "ERC20Votes Stylus Example";
+    const SYMBOL: &'static str = "MOCK";
+    const DECIMALS: u8 = 18;
+}
+
+/// Key the vote checkpoints by timestamp rather than by block number
+impl VotesParams for ERC20VotesMockParams {
+    const CLOCK: Clock = Clock::Timestamp;
+}
+
+sol_storage! {
+    #[entrypoint] // Makes ERC20VotesMock the entrypoint
+    struct ERC20VotesMock {
+        #[borrow]
+        ERC20Votes<ERC20VotesMockParams> votes;
+    }
+}
+
+// ERC20Votes wraps Votes and ERC20, so the entrypoint must be able to borrow both
+impl Borrow<Votes<ERC20VotesMockParams>> for ERC20VotesMock {
+    fn borrow(&self) -> &Votes<ERC20VotesMockParams> {
+        &self.votes.votes
+    }
+}
+
+impl BorrowMut<Votes<ERC20VotesMockParams>> for ERC20VotesMock {
+    fn borrow_mut(&mut self) -> &mut Votes<ERC20VotesMockParams> {
+        &mut self.votes.votes
+    }
+}
+
+impl Borrow<ERC20<ERC20VotesMockParams>> for ERC20VotesMock {
+    fn borrow(&self) -> &ERC20<ERC20VotesMockParams> {
+        &self.votes.erc20
+    }
+}
+
+impl BorrowMut<ERC20<ERC20VotesMockParams>> for ERC20VotesMock {
+    fn borrow_mut(&mut self) -> &mut ERC20<ERC20VotesMockParams> {
+        &mut self.votes.erc20
+    }
+}
+
+#[external]
+#[inherit(
+    ERC20Votes<ERC20VotesMockParams>,
+    Votes<ERC20VotesMockParams>,
+    ERC20<ERC20VotesMockParams>
+)]
+impl ERC20VotesMock {
+    pub fn mint(&mut self, qty: U256) -> Result<(), Vec<u8>> {
+        ERC20::_mint(&mut self.votes, msg::sender(), qty)?;
+        Ok(())
+    }
+
+    pub fn burn(&mut self, qty: U256) -> Result<(), Vec<u8>> {
+        ERC20::_burn(&mut self.votes, msg::sender(), qty)?;
+        Ok(())
+    }
+}
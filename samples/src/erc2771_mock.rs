@@ -0,0 +1,56 @@
+//! An example ERC20 contract whose holders can transfer through a trusted forwarder
+extern crate alloc;
+
+use crate::inkmate::metatx::erc2771_context::{msg_sender, ERC2771Context};
+use crate::inkmate::tokens::erc20::{ERC20Params, ERC20};
+use stylus_sdk::{alloy_primitives::Address, alloy_primitives::U256, prelude::*};
+
+struct ERC2771MockParams;
+
+/// Immutable definitions
+impl ERC20Params for ERC2771MockParams {
+    const NAME: &'static str = "ERC2771 Stylus Example";
+    const SYMBOL: &'static str = "MOCK";
+    const DECIMALS: u8 = 18;
+}
+
+sol_storage! {
+    /// The token methods, which are routed after the context is captured
+    struct ERC2771Token {
+        ERC20<ERC2771MockParams> erc20;
+    }
+
+    #[entrypoint]
+    struct ERC2771Mock {
+        #[borrow]
+        ERC2771Context context;
+        #[borrow]
+        ERC2771Token token;
+    }
+}
+
+#[external]
+impl ERC2771Token {
+    pub fn balance_of(&self, owner: Address) -> U256 {
+        self.erc20.balance_of(owner)
+    }
+
+    pub fn mint(&mut self, qty: U256) -> Result<(), Vec<u8>> {
+        ERC20::_mint(&mut self.erc20, msg_sender(), qty)
+    }
+
+    pub fn transfer(&mut self, to: Address, amount: U256) -> Result<bool, Vec<u8>> {
+        ERC20::_transfer(&mut self.erc20, msg_sender(), to, amount)?;
+        Ok(true)
+    }
+}
+
+#[external]
+#[inherit(ERC2771Context, ERC2771Token)]
+impl ERC2771Mock {
+    // Returns a `Result`, as `export-abi` fails to infer the ABI types of impls returning nothing
+    pub fn set_trusted_forwarder(&mut self, forwarder: Address) -> Result<(), Vec<u8>> {
+        self.context._set_trusted_forwarder(forwarder);
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+//! An example rentable ERC721 contract
+extern crate alloc;
+
+use crate::inkmate::tokens::erc721::{ERC721Params, ERC721};
+use crate::inkmate::tokens::extensions::erc4907::ERC4907;
+use core::borrow::{Borrow, BorrowMut};
+use stylus_sdk::{alloy_primitives::U256, msg, prelude::*};
+
+struct ERC4907MockParams;
+
+/// Immutable definitions
+impl ERC721Params for ERC4907MockParams {
+    const NAME: &'static str = "ERC4907 Stylus Example";
+    const SYMBOL: &'static str = "RENT";
+
+    fn token_uri(_token_id: U256) -> String {
+        String::new()
+    }
+}
+
+sol_storage! {
+    #[entrypoint] // Makes ERC4907Mock the entrypoint
+    struct ERC4907Mock {
+        #[borrow]
+        ERC4907<ERC4907MockParams> rentable;
+        uint256 total_supply;
+    }
+}
+
+// ERC4907 wraps ERC721, so the entrypoint must be able to borrow it
+impl Borrow<ERC721<ERC4907MockParams>> for ERC4907Mock {
+    fn borrow(&self) -> &ERC721<ERC4907MockParams> {
+        &self.rentable.erc721
+    }
+}
+
+impl BorrowMut<ERC721<ERC4907MockParams>> for ERC4907Mock {
+    fn borrow_mut(&mut self) -> &mut ERC721<ERC4907MockParams> {
+        &mut self.rentable.erc721
+    }
+}
+
+#[external]
+#[inherit(ERC4907<ERC4907MockParams>, ERC721<ERC4907MockParams>)]
+impl ERC4907Mock {
+    pub fn mint(&mut self) -> Result<(), Vec<u8>> {
+        let id = self.total_supply.get();
+        ERC721::_mint(&mut self.rentable, msg::sender(), id)?;
+        self.total_supply.set(id + U256::from(1));
+        Ok(())
+    }
+
+    pub fn burn(&mut self, id: U256) -> Result<(), Vec<u8>> {
+        ERC721::_burn(&mut self.rentable, id)?;
+        Ok(())
+    }
+}
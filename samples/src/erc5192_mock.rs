@@ -0,0 +1,57 @@
+//! An example soulbound ERC721 contract
+extern crate alloc;
+
+use crate::inkmate::tokens::erc721::{ERC721Params, ERC721};
+use crate::inkmate::tokens::extensions::erc5192::ERC5192;
+use core::borrow::{Borrow, BorrowMut};
+use stylus_sdk::{alloy_primitives::U256, msg, prelude::*};
+
+struct ERC5192MockParams;
+
+/// Immutable definitions
+impl ERC721Params for ERC5192MockParams {
+    const NAME: &'static str = "ERC5192 Stylus Example";
+    const SYMBOL: &'static str = "SBT";
+
+    fn token_uri(_token_id: U256) -> String {
+        String::new()
+    }
+}
+
+sol_storage! {
+    #[entrypoint] // Makes ERC5192Mock the entrypoint
+    struct ERC5192Mock {
+        #[borrow]
+        ERC5192<ERC5192MockParams> soulbound;
+        uint256 total_supply;
+    }
+}
+
+// ERC5192 wraps ERC721, so the entrypoint must be able to borrow it
+impl Borrow<ERC721<ERC5192MockParams>> for ERC5192Mock {
+    fn borrow(&self) -> &ERC721<ERC5192MockParams> {
+        &self.soulbound.erc721
+    }
+}
+
+impl BorrowMut<ERC721<ERC5192MockParams>> for ERC5192Mock {
+    fn borrow_mut(&mut self) -> &mut ERC721<ERC5192MockParams> {
+        &mut self.soulbound.erc721
+    }
+}
+
+#[external]
+#[inherit(ERC5192<ERC5192MockParams>, ERC721<ERC5192MockParams>)]
+impl ERC5192Mock {
+    pub fn mint(&mut self) -> Result<(), Vec<u8>> {
+        let id = self.total_supply.get();
+        ERC721::_mint(&mut self.soulbound, msg::sender(), id)?;
+        self.total_supply.set(id + U256::from(1));
+        Ok(())
+    }
+
+    pub fn burn(&mut self, id: U256) -> Result<(), Vec<u8>> {
+        ERC721::_burn(&mut self.soulbound, id)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+//! An example deployment of the ERC721Drop preset
+//! See `samples/README.md` for the deployment steps.
+extern crate alloc;
+
+use crate::inkmate::{
+    access::ownable::Ownable,
+    presets::erc721_drop::{ERC721Drop, ERC721DropParams},
+    tokens::{
+        erc721::{ERC721Params, ERC721},
+        extensions::{erc2981::ERC2981, mint_phases::MintPhases},
+    },
+    utils::strings::{Strings, StringsTrait},
+};
+use core::borrow::{Borrow, BorrowMut};
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    prelude::*,
+};
+
+pub struct ERC721DropMockParams;
+
+/// Immutable definitions
+impl ERC721Params for ERC721DropMockParams {
+    const NAME: &'static str = "ERC721 Drop Stylus Example";
+    const SYMBOL: &'static str = "MOCK";
+
+    fn token_uri(token_id: U256) -> String {
+        // Avoid `format!` as the formatting machinery inflates the compiled WASM
+        let mut uri = String::from("ipfs://QmZcH4YvBVVRJtdn4RdbaqgspFU8gH6P9vomDpBVpAL3u4/");
+        uri.push_str(&Strings::to_decimal_string(token_id));
+        uri
+    }
+}
+
+/// A collection of 10,000 tokens
+impl ERC721DropParams for ERC721DropMockParams {
+    const MAX_SUPPLY: u64 = 10_000;
+}
+
+sol_storage! {
+    #[entrypoint] // Makes ERC721DropMock the entrypoint
+    #[cfg_attr(feature = "export-layout", inkmate::export::layout::storage_layout)]
+    pub struct ERC721DropMock {
+        #[borrow]
+        ERC721Drop<ERC721DropMockParams> drop;
+    }
+}
+
+// The preset wraps MintPhases, ERC721, Ownable and ERC2981, so the entrypoint must be able to
+// borrow them
+impl Borrow<MintPhases<ERC721DropMockParams>> for ERC721DropMock {
+    fn borrow(&self) -> &MintPhases<ERC721DropMockParams> {
+        &self.drop.phases
+    }
+}
+
+impl BorrowMut<MintPhases<ERC721DropMockParams>> for ERC721DropMock {
+    fn borrow_mut(&mut self) -> &mut MintPhases<ERC721DropMockParams> {
+        &mut self.drop.phases
+    }
+}
+
+impl Borrow<ERC721<ERC721DropMockParams>> for ERC721DropMock {
+    fn borrow(&self) -> &ERC721<ERC721DropMockParams> {
+        &self.drop.phases.erc721
+    }
+}
+
+impl BorrowMut<ERC721<ERC721DropMockParams>> for ERC721DropMock {
+    fn borrow_mut(&mut self) -> &mut ERC721<ERC721DropMockParams> {
+        &mut self.drop.phases.erc721
+    }
+}
+
+impl Borrow<Ownable> for ERC721DropMock {
+    fn borrow(&self) -> &Ownable {
+        &self.drop.phases.ownable
+    }
+}
+
+impl BorrowMut<Ownable> for ERC721DropMock {
+    fn borrow_mut(&mut self) -> &mut Ownable {
+        &mut self.drop.phases.ownable
+    }
+}
+
+impl Borrow<ERC2981> for ERC721DropMock {
+    fn borrow(&self) -> &ERC2981 {
+        &self.drop.royalties
+    }
+}
+
+impl BorrowMut<ERC2981> for ERC721DropMock {
+    fn borrow_mut(&mut self) -> &mut ERC2981 {
+        &mut self.drop.royalties
+    }
+}
+
+#[external]
+#[inherit(
+    ERC721Drop<ERC721DropMockParams>,
+    MintPhases<ERC721DropMockParams>,
+    ERC721<ERC721DropMockParams>,
+    Ownable,
+    ERC2981
+)]
+impl ERC721DropMock {
+    /// Sets `owner` as the owner and the default royalty to `royalty_fee` basis points paid to
+    /// `royalty_receiver`, and must be called right after the deployment.
+    pub fn initialize(
+        &mut self,
+        owner: Address,
+        royalty_receiver: Address,
+        royalty_fee: U256,
+    ) -> Result<(), Vec<u8>> {
+        self.drop._initialize(owner, royalty_receiver, royalty_fee)
+    }
+}
@@ -1,8 +1,10 @@
 //! An example ERC721 contract
 extern crate alloc;
 
-use crate::inkmate::tokens::erc721::{ERC721Params, ERC721};
-use alloc::format;
+use crate::inkmate::{
+    tokens::erc721::{ERC721Params, ERC721},
+    utils::strings::{Strings, StringsTrait},
+};
 use stylus_sdk::{alloy_primitives::U256, msg, prelude::*};
 
 pub struct ERC721MockParams;
@@ -13,10 +15,10 @@ impl ERC721Params for ERC721MockParams {
     const SYMBOL: &'static str = "MOCK";
 
     fn token_uri(token_id: U256) -> String {
-        format!(
-            "ipfs://QmZcH4YvBVVRJtdn4RdbaqgspFU8gH6P9vomDpBVpAL3u4/{}",
-            token_id
-        )
+        // Avoid `format!` as the formatting machinery inflates the compiled WASM
+        let mut uri = String::from("ipfs://QmZcH4YvBVVRJtdn4RdbaqgspFU8gH6P9vomDpBVpAL3u4/");
+        uri.push_str(&Strings::to_decimal_string(token_id));
+        uri
     }
 }
 
@@ -37,19 +39,19 @@ impl ERC721Mock {
     }
 
     pub fn mint_loop(&mut self, qty: U256) -> Result<(), Vec<u8>> {
-        let supply = self.total_supply.get();
-        let supply: u32 = supply.try_into().unwrap();
-        let qty: u32 = qty.try_into().unwrap();
-
-        for i in 0..qty.try_into().unwrap() {
-            self.erc721._mint(msg::sender(), U256::from(supply + i))?;
+        // Counts with `U256` rather than converting with `unwrap`, which links the panic machinery
+        let mut token_id = self.total_supply.get();
+        let end = token_id + qty;
+        while token_id < end {
+            ERC721::_mint(&mut self.erc721, msg::sender(), token_id)?;
+            token_id += U256::from(1);
         }
-        self.total_supply.set(U256::from(supply + qty));
+        self.total_supply.set(end);
         Ok(())
     }
 
     pub fn burn(&mut self, token_id: U256) -> Result<(), Vec<u8>> {
-        self.erc721._burn(token_id)?;
+        ERC721::_burn(&mut self.erc721, token_id)?;
         let supply = self.total_supply.get();
         self.total_supply.set(supply - U256::from(1));
         Ok(())
@@ -0,0 +1,77 @@
+//! An example ERC721Votes contract
+extern crate alloc;
+
+use crate::inkmate::governance::votes::{Votes, VotesParams};
+use crate::inkmate::tokens::erc721::{ERC721Params, ERC721};
+use crate::inkmate::tokens::extensions::erc721_votes::ERC721Votes;
+use core::borrow::{Borrow, BorrowMut};
+use stylus_sdk::{alloy_primitives::U256, msg, prelude::*};
+
+struct ERC721VotesMockParams;
+
+/// Immutable definitions
+impl ERC721Params for ERC721VotesMockParams {
+    const NAME: &'static str = "ERC721Votes Stylus Example";
+    const SYMBOL: &'static str = "MOCK";
+
+    fn token_uri(_token_id: U256) -> String {
+        String::new()
+    }
+}
+
+/// Key the vote checkpoints by block number
+impl VotesParams for ERC721VotesMockParams {}
+
+sol_storage! {
+    #[entrypoint] // Makes ERC721VotesMock the entrypoint
+    struct ERC721VotesMock {
+        #[borrow]
+        ERC721Votes<ERC721VotesMockParams> votes;
+        uint256 total_supply;
+    }
+}
+
+// ERC721Votes wraps Votes and ERC721, so the entrypoint must be able to borrow both
+impl Borrow<Votes<ERC721VotesMockParams>> for ERC721VotesMock {
+    fn borrow(&self) -> &Votes<ERC721VotesMockParams> {
+        &self.votes.votes
+    }
+}
+
+impl BorrowMut<Votes<ERC721VotesMockParams>> for ERC721VotesMock {
+    fn borrow_mut(&mut self) -> &mut Votes<ERC721VotesMockParams> {
+        &mut self.votes.votes
+    }
+}
+
+impl Borrow<ERC721<ERC721VotesMockParams>> for ERC721VotesMock {
+    fn borrow(&self) -> &ERC721<ERC721VotesMockParams> {
+        &self.votes.erc721
+    }
+}
+
+impl BorrowMut<ERC721<ERC721VotesMockParams>> for ERC721VotesMock {
+    fn borrow_mut(&mut self) -> &mut ERC721<ERC721VotesMockParams> {
+        &mut self.votes.erc721
+    }
+}
+
+#[external]
+#[inherit(
+    ERC721Votes<ERC721VotesMockParams>,
+    Votes<ERC721VotesMockParams>,
+    ERC721<ERC721VotesMockParams>
+)]
+impl ERC721VotesMock {
+    pub fn mint(&mut self) -> Result<(), Vec<u8>> {
+        let id = self.total_supply.get();
+        ERC721::_mint(&mut self.votes, msg::sender(), id)?;
+        self.total_supply.set(id + U256::from(1));
+        Ok(())
+    }
+
+    pub fn burn(&mut self, id: U256) -> Result<(), Vec<u8>> {
+        ERC721::_burn(&mut self.votes, id)?;
+        Ok(())
+    }
+}
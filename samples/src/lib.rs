@@ -8,3 +8,83 @@ mod erc20_mock;
 
 #[cfg(feature = "erc721")]
 mod erc721_mock;
+
+#[cfg(feature = "erc20-votes")]
+mod erc20_votes_mock;
+
+#[cfg(feature = "erc721-votes")]
+mod erc721_votes_mock;
+
+#[cfg(feature = "erc4907")]
+mod erc4907_mock;
+
+#[cfg(feature = "erc5192")]
+mod erc5192_mock;
+
+#[cfg(feature = "erc20-flashmint")]
+mod erc20_flashmint_mock;
+
+#[cfg(feature = "erc20-metadata")]
+mod erc20_metadata_mock;
+
+#[cfg(feature = "dn404")]
+mod dn404_mock;
+
+#[cfg(feature = "dn404-mirror")]
+mod dn404_mirror_mock;
+
+#[cfg(feature = "dutch-auction")]
+mod dutch_auction_mock;
+
+#[cfg(feature = "mint-limits")]
+mod mint_limits_mock;
+
+#[cfg(feature = "clones")]
+mod clone_factory_mock;
+
+#[cfg(feature = "uups")]
+mod uups_mock;
+
+#[cfg(feature = "proxy-admin")]
+mod proxy_admin_mock;
+
+#[cfg(feature = "multicall")]
+mod multicall_mock;
+
+#[cfg(feature = "erc2771")]
+mod erc2771_mock;
+
+#[cfg(feature = "erc20-preset")]
+mod erc20_preset_mock;
+
+#[cfg(feature = "erc721-drop")]
+mod erc721_drop_mock;
+
+#[cfg(feature = "erc1155-game-items")]
+mod erc1155_game_items_mock;
+
+#[cfg(feature = "receivers")]
+pub mod receivers;
+
+// The entrypoint of the selected sample, whose ABI and storage layout are exported by
+// `examples/export_abi.rs` and `examples/export_layout.rs`. If several samples are enabled, the
+// first one below is exported.
+#[cfg(all(
+    any(feature = "export-abi", feature = "export-layout"),
+    feature = "erc20-preset"
+))]
+pub use erc20_preset_mock::ERC20PresetMock as Exported;
+
+#[cfg(all(
+    any(feature = "export-abi", feature = "export-layout"),
+    feature = "erc721-drop",
+    not(feature = "erc20-preset")
+))]
+pub use erc721_drop_mock::ERC721DropMock as Exported;
+
+#[cfg(all(
+    any(feature = "export-abi", feature = "export-layout"),
+    feature = "erc1155-game-items",
+    not(any(feature = "erc20-preset", feature = "erc721-drop"))
+))]
+pub use erc1155_game_items_mock::ERC1155GameItemsMock as Exported;
@@ -0,0 +1,60 @@
+//! An example ERC721 contract with a capped supply and per-wallet mint limits
+extern crate alloc;
+
+use crate::inkmate::tokens::erc721::{ERC721Params, ERC721};
+use crate::inkmate::tokens::extensions::mint_limits::{MintLimits, MintLimitsParams};
+use core::borrow::{Borrow, BorrowMut};
+use stylus_sdk::{alloy_primitives::U256, msg, prelude::*};
+
+struct MintLimitsMockParams;
+
+/// Immutable definitions
+impl ERC721Params for MintLimitsMockParams {
+    const NAME: &'static str = "Mint Limits Stylus Example";
+    const SYMBOL: &'static str = "LIMIT";
+
+    fn token_uri(_token_id: U256) -> String {
+        String::new()
+    }
+}
+
+/// 10000 tokens, at most 3 per wallet
+impl MintLimitsParams for MintLimitsMockParams {
+    const MAX_SUPPLY: u64 = 10000;
+    const MAX_PER_WALLET: u64 = 3;
+}
+
+sol_storage! {
+    #[entrypoint] // Makes MintLimitsMock the entrypoint
+    struct MintLimitsMock {
+        #[borrow]
+        MintLimits<MintLimitsMockParams> limits;
+    }
+}
+
+// MintLimits wraps ERC721, so the entrypoint must be able to borrow it
+impl Borrow<ERC721<MintLimitsMockParams>> for MintLimitsMock {
+    fn borrow(&self) -> &ERC721<MintLimitsMockParams> {
+        &self.limits.erc721
+    }
+}
+
+impl BorrowMut<ERC721<MintLimitsMockParams>> for MintLimitsMock {
+    fn borrow_mut(&mut self) -> &mut ERC721<MintLimitsMockParams> {
+        &mut self.limits.erc721
+    }
+}
+
+#[external]
+#[inherit(MintLimits<MintLimitsMockParams>, ERC721<MintLimitsMockParams>)]
+impl MintLimitsMock {
+    pub fn mint(&mut self) -> Result<(), Vec<u8>> {
+        let id = self.limits.total_minted.get();
+        self.limits._mint(msg::sender(), id)
+    }
+
+    pub fn burn(&mut self, id: U256) -> Result<(), Vec<u8>> {
+        ERC721::_burn(&mut self.limits.erc721, id)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+//! An example ERC20 contract that can batch approvals, transfers and mints
+extern crate alloc;
+
+use crate::inkmate::tokens::erc20::{ERC20Params, ERC20};
+use crate::inkmate::utils::multicall::Multicall;
+use stylus_sdk::{alloy_primitives::U256, msg, prelude::*};
+
+struct MulticallMockParams;
+
+/// Immutable definitions
+impl ERC20Params for MulticallMockParams {
+    const NAME: &'static str = "Multicall Stylus Example";
+    const SYMBOL: &'static str = "MOCK";
+    const DECIMALS: u8 = 18;
+}
+
+sol_storage! {
+    #[entrypoint]
+    struct MulticallMock {
+        #[borrow]
+        ERC20<MulticallMockParams> erc20;
+        #[borrow]
+        Multicall<MulticallMock> multicall;
+    }
+}
+
+#[external]
+#[inherit(ERC20<MulticallMockParams>, Multicall<MulticallMock>)]
+impl MulticallMock {
+    pub fn mint(&mut self, qty: U256) -> Result<(), Vec<u8>> {
+        ERC20::_mint(&mut self.erc20, msg::sender(), qty)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+//! An example admin of transparent and UUPS proxies, owned by a single account
+extern crate alloc;
+
+use crate::inkmate::{access::ownable::Ownable, proxy::proxy_admin::ProxyAdmin};
+use core::borrow::{Borrow, BorrowMut};
+use stylus_sdk::{alloy_primitives::Address, prelude::*};
+
+sol_storage! {
+    #[entrypoint] // Makes ProxyAdminMock the entrypoint
+    struct ProxyAdminMock {
+        #[borrow]
+        ProxyAdmin admin;
+    }
+}
+
+// ProxyAdmin wraps Ownable, so the entrypoint must be able to borrow it
+impl Borrow<Ownable> for ProxyAdminMock {
+    fn borrow(&self) -> &Ownable {
+        &self.admin.ownable
+    }
+}
+
+impl BorrowMut<Ownable> for ProxyAdminMock {
+    fn borrow_mut(&mut self) -> &mut Ownable {
+        &mut self.admin.ownable
+    }
+}
+
+#[external]
+#[inherit(ProxyAdmin, Ownable)]
+impl ProxyAdminMock {
+    /// Sets `owner` as the owner of the admin, right after the deployment.
+    pub fn initialize(&mut self, owner: Address) -> Result<(), Vec<u8>> {
+        self.admin._initialize(owner)?;
+        Ok(())
+    }
+}
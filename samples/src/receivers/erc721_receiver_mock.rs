@@ -0,0 +1,53 @@
+//! A receiver accepting all tokens, which records the transfers it receives.
+extern crate alloc;
+
+use crate::inkmate::tokens::erc721_holder::ERC721_RECEIVED;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, FixedBytes, U256},
+    alloy_sol_types::sol,
+    evm,
+    prelude::*,
+};
+
+sol! {
+    /// Emitted when token `tokenId` is received from `from`.
+    event Received(address operator, address from, uint256 tokenId, bytes data);
+}
+
+sol_storage! {
+    pub struct ERC721ReceiverMock {
+        /// The number of tokens received
+        uint256 received;
+    }
+}
+
+unsafe impl TopLevelStorage for ERC721ReceiverMock {}
+
+#[external]
+impl ERC721ReceiverMock {
+    /// Accepts the token, and emits a {Received} event.
+    #[selector(name = "onERC721Received")]
+    pub fn on_erc721_received(
+        &mut self,
+        operator: Address,
+        from: Address,
+        token_id: U256,
+        data: Bytes,
+    ) -> FixedBytes<4> {
+        let received = self.received.get();
+        self.received.set(received + U256::from(1));
+        evm::log(Received {
+            operator,
+            from,
+            tokenId: token_id,
+            data: data.0,
+        });
+        FixedBytes(ERC721_RECEIVED.to_be_bytes())
+    }
+
+    /// Returns the number of tokens received.
+    pub fn received(&self) -> U256 {
+        self.received.get()
+    }
+}
@@ -0,0 +1,14 @@
+//! Mock ERC721 receivers, used to test the safe transfers and safe mints of tokens.
+//!
+//! Unlike the other samples, the receivers do not define the contract entrypoint, so that several
+//! of them can be deployed next to a token in the same test (ex. with `inkmate-test`).
+
+mod erc721_receiver_mock;
+mod reentrant_receiver_mock;
+mod reverting_receiver_mock;
+mod wrong_selector_receiver_mock;
+
+pub use erc721_receiver_mock::{ERC721ReceiverMock, Received};
+pub use reentrant_receiver_mock::ReentrantReceiverMock;
+pub use reverting_receiver_mock::{ReceiverRejected, RevertingReceiverMock};
+pub use wrong_selector_receiver_mock::{WrongSelectorReceiverMock, WRONG_SELECTOR};
@@ -0,0 +1,46 @@
+//! A receiver calling back the token when it receives a token (ex. to mint another one), which
+//! only accepts the token if the call succeeds.
+//!
+//! Stylus contracts revert reentrant calls unless the `reentrant` feature of the SDK is enabled,
+//! so transfers to this receiver are expected to revert.
+extern crate alloc;
+
+use crate::inkmate::tokens::erc721_holder::ERC721_RECEIVED;
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, FixedBytes, U256},
+    call, msg,
+    prelude::*,
+};
+
+sol_storage! {
+    pub struct ReentrantReceiverMock {
+        /// The calldata of the call made to the token
+        bytes reentry_data;
+    }
+}
+
+unsafe impl TopLevelStorage for ReentrantReceiverMock {}
+
+#[external]
+impl ReentrantReceiverMock {
+    /// Sets the calldata of the call made to the token when receiving a token.
+    pub fn set_reentry_data(&mut self, data: Bytes) {
+        self.reentry_data.set_bytes(data.0);
+    }
+
+    /// Calls the token with the reentry data, and accepts the token if the call succeeds.
+    /// Reverts with the revert data of the call otherwise.
+    #[selector(name = "onERC721Received")]
+    pub fn on_erc721_received(
+        &mut self,
+        _operator: Address,
+        _from: Address,
+        _token_id: U256,
+        _data: Bytes,
+    ) -> Result<FixedBytes<4>, Vec<u8>> {
+        let data = self.reentry_data.get_bytes();
+        call::call(self, msg::sender(), &data)?;
+        Ok(FixedBytes(ERC721_RECEIVED.to_be_bytes()))
+    }
+}
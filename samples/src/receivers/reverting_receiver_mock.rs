@@ -0,0 +1,34 @@
+//! A receiver rejecting all tokens by reverting.
+extern crate alloc;
+
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, FixedBytes, U256},
+    alloy_sol_types::{sol, SolError},
+    prelude::*,
+};
+
+sol! {
+    /// The receiver does not accept tokens.
+    error ReceiverRejected();
+}
+
+sol_storage! {
+    pub struct RevertingReceiverMock {}
+}
+
+unsafe impl TopLevelStorage for RevertingReceiverMock {}
+
+#[external]
+impl RevertingReceiverMock {
+    /// Reverts with a {ReceiverRejected} error.
+    #[selector(name = "onERC721Received")]
+    pub fn on_erc721_received(
+        _operator: Address,
+        _from: Address,
+        _token_id: U256,
+        _data: Bytes,
+    ) -> Result<FixedBytes<4>, Vec<u8>> {
+        Err(ReceiverRejected {}.encode())
+    }
+}
@@ -0,0 +1,31 @@
+//! A receiver that does not revert, but returns a value other than `ERC721_RECEIVED`.
+extern crate alloc;
+
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, FixedBytes, U256},
+    prelude::*,
+};
+
+/// The value returned by the receiver instead of `ERC721_RECEIVED`
+pub const WRONG_SELECTOR: u32 = 0xdeadbeef;
+
+sol_storage! {
+    pub struct WrongSelectorReceiverMock {}
+}
+
+unsafe impl TopLevelStorage for WrongSelectorReceiverMock {}
+
+#[external]
+impl WrongSelectorReceiverMock {
+    /// Returns `WRONG_SELECTOR`.
+    #[selector(name = "onERC721Received")]
+    pub fn on_erc721_received(
+        _operator: Address,
+        _from: Address,
+        _token_id: U256,
+        _data: Bytes,
+    ) -> FixedBytes<4> {
+        FixedBytes(WRONG_SELECTOR.to_be_bytes())
+    }
+}
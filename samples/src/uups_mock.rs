@@ -0,0 +1,55 @@
+//! An example UUPS upgradeable contract owned by a single account
+extern crate alloc;
+
+use crate::inkmate::{
+    access::ownable::Ownable,
+    proxy::{initializable::Initializable, uups::UUPSUpgradeable},
+};
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{Address, U256},
+    msg,
+    prelude::*,
+};
+
+sol_storage! {
+    #[entrypoint] // Makes UUPSMock the entrypoint
+    struct UUPSMock {
+        #[borrow]
+        Ownable ownable;
+        #[borrow]
+        UUPSUpgradeable uups;
+        uint256 value;
+    }
+}
+
+#[external]
+#[inherit(Ownable, UUPSUpgradeable)]
+impl UUPSMock {
+    /// Initializes the proxy's storage, setting the caller as the owner.
+    pub fn initialize(&mut self) -> Result<(), Vec<u8>> {
+        Initializable::_initializer_enter()?;
+        self.ownable._initialize_owner(msg::sender())?;
+        Initializable::_initializer_exit();
+        Ok(())
+    }
+
+    /// Upgrades the implementation of the proxy, restricted to the owner.
+    #[payable]
+    pub fn upgrade_to_and_call(
+        &mut self,
+        new_implementation: Address,
+        data: Bytes,
+    ) -> Result<(), Vec<u8>> {
+        self.ownable.only_owner()?;
+        self.uups._upgrade_to_and_call(new_implementation, &data.0)
+    }
+
+    pub fn value(&self) -> U256 {
+        self.value.get()
+    }
+
+    pub fn set_value(&mut self, value: U256) {
+        self.value.set(value);
+    }
+}
@@ -0,0 +1,472 @@
+//! Call frames, which run the code of a contract in a forked process, and the Stylus host I/Os
+//! they use.
+//!
+//! The Stylus SDK caches the values of the host (ex. `msg::sender`) and the storage in process-wide
+//! statics for the duration of a call, which can't be reset. Each call to a contract therefore runs
+//! in a process forked from the host, which never runs contract code and whose statics are never
+//! initialized. The frame sends the calls it makes and its outcome to the host, which owns the
+//! state of the chain.
+
+// The host I/Os are only called by the SDK, with valid pointers
+#![allow(clippy::missing_safety_doc)]
+
+use std::borrow::BorrowMut;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::os::unix::net::UnixStream;
+use std::panic::{self, AssertUnwindSafe};
+
+use alloy_primitives::{keccak256, Address, B256, U256};
+use stylus_sdk::{
+    abi::Router,
+    msg,
+    storage::{StorageCache, StorageType, TopLevelStorage},
+    ArbResult,
+};
+
+use crate::world::{Message, Msg, World};
+
+/// The entrypoint of a contract
+pub type Entrypoint = fn(&[u8]) -> ArbResult;
+
+/// Routes `input` to the methods of `S`, like the entrypoint generated by `#[entrypoint]`.
+pub fn entrypoint<S>(input: &[u8]) -> ArbResult
+where
+    S: StorageType + TopLevelStorage + Router<S> + BorrowMut<<S as Router<S>>::Storage>,
+{
+    // Reentrant calls revert unless the `reentrant` feature of the SDK is enabled
+    if msg::reentrant() {
+        return Err(vec![]);
+    }
+    if input.len() < 4 {
+        return Err(vec![]);
+    }
+    let selector = u32::from_be_bytes(input[..4].try_into().unwrap());
+    let mut storage = unsafe { S::new(U256::ZERO, 0) };
+    <S as Router<S>>::route(&mut storage, selector, &input[4..]).unwrap_or(Err(vec![]))
+}
+
+/// The block and transaction values read by contracts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Env {
+    pub block_number: u64,
+    pub block_timestamp: u64,
+    pub block_basefee: U256,
+    pub block_coinbase: Address,
+    pub block_gas_limit: u64,
+    pub chain_id: u64,
+    pub tx_origin: Address,
+    pub tx_gas_price: U256,
+}
+
+/// The state of the call frame running in this process
+struct Frame {
+    /// The connection to the host
+    stream: UnixStream,
+    /// The state of the chain
+    world: World,
+    /// The accounts with code
+    contracts: BTreeSet<Address>,
+    env: Env,
+    msg: Msg,
+    reentrant: bool,
+    /// The return data of the last call
+    return_data: Vec<u8>,
+}
+
+thread_local! {
+    static FRAME: RefCell<Option<Frame>> = const { RefCell::new(None) };
+}
+
+/// Runs the frame of a call to `entry` in this (forked) process, reports its outcome to the host
+/// through `stream`, and exits the process.
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    stream: UnixStream,
+    world: World,
+    contracts: BTreeSet<Address>,
+    env: Env,
+    msg: Msg,
+    reentrant: bool,
+    entry: Entrypoint,
+) -> ! {
+    let input = msg.data.clone();
+    FRAME.with(|frame| {
+        *frame.borrow_mut() = Some(Frame {
+            stream,
+            world,
+            contracts,
+            env,
+            msg,
+            reentrant,
+            return_data: vec![],
+        })
+    });
+
+    // A panic traps the program, which reverts without data
+    let (success, data) = match panic::catch_unwind(AssertUnwindSafe(|| entry(&input))) {
+        Ok(Ok(data)) => {
+            StorageCache::flush();
+            (true, data)
+        }
+        Ok(Err(data)) => (false, data),
+        Err(_) => (false, vec![]),
+    };
+
+    let frame = FRAME.with(|frame| frame.borrow_mut().take()).unwrap();
+    let mut stream = frame.stream;
+    let done = Message::Done {
+        world: frame.world,
+        success,
+        data,
+    };
+    let code = match done.send(&mut stream) {
+        Ok(()) => 0,
+        Err(_) => 1,
+    };
+    // Skip the destructors and exit handlers, which belong to the host
+    unsafe { libc::_exit(code) }
+}
+
+/// Runs `f` with the frame of this process.
+fn with_frame<R>(f: impl FnOnce(&mut Frame) -> R) -> R {
+    FRAME.with(|frame| {
+        let mut frame = frame.borrow_mut();
+        let frame = frame
+            .as_mut()
+            .expect("Stylus host I/Os can only be used by contracts called through a TestHost");
+        f(frame)
+    })
+}
+
+/// Calls `to` through the host, and returns the length of the return data and whether the call
+/// succeeded.
+fn call(to: Address, data: &[u8], value: U256, is_static: bool) -> (usize, bool) {
+    with_frame(|frame| {
+        // Static calls cannot send value
+        if frame.msg.is_static && !value.is_zero() {
+            frame.return_data.clear();
+            return (0, false);
+        }
+        let msg = Msg {
+            sender: frame.msg.to,
+            to,
+            value,
+            data: data.to_vec(),
+            is_static: is_static || frame.msg.is_static,
+        };
+        let world = std::mem::take(&mut frame.world);
+        Message::Call { world, msg }
+            .send(&mut frame.stream)
+            .expect("host disconnected");
+        match Message::receive(&mut frame.stream).expect("host disconnected") {
+            Message::Return {
+                world,
+                success,
+                data,
+            } => {
+                frame.world = world;
+                let len = data.len();
+                frame.return_data = data;
+                (len, success)
+            }
+            message => panic!("unexpected message {message:?}"),
+        }
+    })
+}
+
+unsafe fn read<const N: usize>(ptr: *const u8) -> [u8; N] {
+    let mut data = [0; N];
+    data.copy_from_slice(std::slice::from_raw_parts(ptr, N));
+    data
+}
+
+unsafe fn write(ptr: *mut u8, data: &[u8]) {
+    std::ptr::copy_nonoverlapping(data.as_ptr(), ptr, data.len());
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn account_balance(address: *const u8, dest: *mut u8) {
+    let address = Address::from(read::<20>(address));
+    let balance = with_frame(|frame| {
+        frame
+            .world
+            .accounts
+            .get(&address)
+            .map_or(U256::ZERO, |account| account.balance)
+    });
+    write(dest, &balance.to_be_bytes::<32>());
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn account_codehash(address: *const u8, dest: *mut u8) {
+    let address = Address::from(read::<20>(address));
+    let hash = with_frame(|frame| {
+        if frame.contracts.contains(&address) {
+            keccak256(address)
+        } else {
+            B256::ZERO
+        }
+    });
+    write(dest, hash.as_slice());
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn storage_load_bytes32(key: *const u8, dest: *mut u8) {
+    let key = B256::from(read::<32>(key));
+    let value = with_frame(|frame| {
+        frame
+            .world
+            .accounts
+            .get(&frame.msg.to)
+            .and_then(|account| account.storage.get(&key).copied())
+            .unwrap_or_default()
+    });
+    write(dest, value.as_slice());
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn storage_store_bytes32(key: *const u8, value: *const u8) {
+    let key = B256::from(read::<32>(key));
+    let value = B256::from(read::<32>(value));
+    with_frame(|frame| {
+        assert!(!frame.msg.is_static, "storage write in a static call");
+        let account = frame.world.accounts.entry(frame.msg.to).or_default();
+        if value.is_zero() {
+            account.storage.remove(&key);
+        } else {
+            account.storage.insert(key, value);
+        }
+    });
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn block_basefee(basefee: *mut u8) {
+    let value = with_frame(|frame| frame.env.block_basefee);
+    write(basefee, &value.to_be_bytes::<32>());
+}
+
+#[no_mangle]
+pub extern "C" fn chainid() -> u64 {
+    with_frame(|frame| frame.env.chain_id)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn block_coinbase(coinbase: *mut u8) {
+    let value = with_frame(|frame| frame.env.block_coinbase);
+    write(coinbase, value.as_slice());
+}
+
+#[no_mangle]
+pub extern "C" fn block_gas_limit() -> u64 {
+    with_frame(|frame| frame.env.block_gas_limit)
+}
+
+#[no_mangle]
+pub extern "C" fn block_number() -> u64 {
+    with_frame(|frame| frame.env.block_number)
+}
+
+#[no_mangle]
+pub extern "C" fn block_timestamp() -> u64 {
+    with_frame(|frame| frame.env.block_timestamp)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn call_contract(
+    contract: *const u8,
+    calldata: *const u8,
+    calldata_len: usize,
+    value: *const u8,
+    _gas: u64,
+    return_data_len: *mut usize,
+) -> u8 {
+    let to = Address::from(read::<20>(contract));
+    let data = std::slice::from_raw_parts(calldata, calldata_len);
+    let value = U256::from_be_bytes(read::<32>(value));
+    let (len, success) = call(to, data, value, false);
+    *return_data_len = len;
+    !success as u8
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn static_call_contract(
+    contract: *const u8,
+    calldata: *const u8,
+    calldata_len: usize,
+    _gas: u64,
+    return_data_len: *mut usize,
+) -> u8 {
+    let to = Address::from(read::<20>(contract));
+    let data = std::slice::from_raw_parts(calldata, calldata_len);
+    let (len, success) = call(to, data, U256::ZERO, true);
+    *return_data_len = len;
+    !success as u8
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn delegate_call_contract(
+    _contract: *const u8,
+    _calldata: *const u8,
+    _calldata_len: usize,
+    _gas: u64,
+    return_data_len: *mut usize,
+) -> u8 {
+    eprintln!("delegate calls are not supported by the TestHost");
+    with_frame(|frame| frame.return_data.clear());
+    *return_data_len = 0;
+    1
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn create1(
+    _code: *const u8,
+    _code_len: usize,
+    _endowment: *const u8,
+    contract: *mut u8,
+    revert_data_len: *mut usize,
+) {
+    eprintln!("contract deployments are not supported by the TestHost");
+    with_frame(|frame| frame.return_data.clear());
+    write(contract, Address::ZERO.as_slice());
+    *revert_data_len = 0;
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn create2(
+    code: *const u8,
+    code_len: usize,
+    endowment: *const u8,
+    _salt: *const u8,
+    contract: *mut u8,
+    revert_data_len: *mut usize,
+) {
+    create1(code, code_len, endowment, contract, revert_data_len)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn contract_address(address: *mut u8) {
+    let value = with_frame(|frame| frame.msg.to);
+    write(address, value.as_slice());
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn emit_log(data: *const u8, len: usize, topics: usize) {
+    let data = std::slice::from_raw_parts(data, len);
+    let (topics, data) = data.split_at(topics * 32);
+    with_frame(|frame| {
+        assert!(!frame.msg.is_static, "log emitted in a static call");
+        frame.world.logs.push(crate::Log {
+            address: frame.msg.to,
+            topics: topics.chunks(32).map(B256::from_slice).collect(),
+            data: data.to_vec(),
+        })
+    });
+}
+
+#[no_mangle]
+pub extern "C" fn evm_gas_left() -> u64 {
+    u64::MAX
+}
+
+#[no_mangle]
+pub extern "C" fn evm_ink_left() -> u64 {
+    u64::MAX
+}
+
+#[no_mangle]
+pub extern "C" fn memory_grow(_pages: u16) {}
+
+#[no_mangle]
+pub extern "C" fn msg_reentrant() -> bool {
+    with_frame(|frame| frame.reentrant)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn msg_sender(sender: *mut u8) {
+    let value = with_frame(|frame| frame.msg.sender);
+    write(sender, value.as_slice());
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn msg_value(value: *mut u8) {
+    let data = with_frame(|frame| frame.msg.value);
+    write(value, &data.to_be_bytes::<32>());
+}
+
+/// Also used outside of contracts, ex. by `keccak256` of `alloy_primitives` in tests.
+#[no_mangle]
+pub unsafe extern "C" fn native_keccak256(bytes: *const u8, len: usize, output: *mut u8) {
+    let data = std::slice::from_raw_parts(bytes, len);
+    let hash = stylus_sdk::keccak_const::Keccak256::new()
+        .update(data)
+        .finalize();
+    write(output, &hash);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn read_args(dest: *mut u8) {
+    with_frame(|frame| write(dest, &frame.msg.data));
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn read_return_data(dest: *mut u8, offset: usize, size: usize) -> usize {
+    with_frame(|frame| {
+        let data = frame.return_data.get(offset..).unwrap_or_default();
+        let data = &data[..size.min(data.len())];
+        write(dest, data);
+        data.len()
+    })
+}
+
+/// The output of a frame is the value returned by its entrypoint.
+#[no_mangle]
+pub extern "C" fn write_result(_data: *const u8, _len: usize) {}
+
+#[no_mangle]
+pub extern "C" fn return_data_size() -> usize {
+    with_frame(|frame| frame.return_data.len())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn tx_gas_price(gas_price: *mut u8) {
+    let value = with_frame(|frame| frame.env.tx_gas_price);
+    write(gas_price, &value.to_be_bytes::<32>());
+}
+
+#[no_mangle]
+pub extern "C" fn tx_ink_price() -> u32 {
+    1
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn tx_origin(origin: *mut u8) {
+    let value = with_frame(|frame| frame.env.tx_origin);
+    write(origin, value.as_slice());
+}
+
+#[no_mangle]
+pub extern "C" fn log_f32(value: f32) {
+    eprintln!("{value}");
+}
+
+#[no_mangle]
+pub extern "C" fn log_f64(value: f64) {
+    eprintln!("{value}");
+}
+
+#[no_mangle]
+pub extern "C" fn log_i32(value: i32) {
+    eprintln!("{value}");
+}
+
+#[no_mangle]
+pub extern "C" fn log_i64(value: i64) {
+    eprintln!("{value}");
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn log_txt(text: *const u8, len: usize) {
+    let text = std::slice::from_raw_parts(text, len);
+    eprintln!("{}", String::from_utf8_lossy(text));
+}
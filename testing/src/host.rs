@@ -0,0 +1,362 @@
+//! The in-memory chain that runs the calls of a test.
+
+use std::borrow::BorrowMut;
+use std::collections::BTreeMap;
+use std::os::unix::net::UnixStream;
+use std::rc::Rc;
+
+use alloy_primitives::{keccak256, Address, B256, U256};
+use alloy_sol_types::SolCall;
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use stylus_sdk::{
+    abi::Router,
+    storage::{StorageType, TopLevelStorage},
+};
+
+use crate::frame::{self, Entrypoint, Env};
+use crate::world::{Log, Message, Msg, World};
+
+/// The address of the ecrecover precompile
+pub const ECRECOVER: Address =
+    Address::new([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+/// The first address used by `TestHost::deploy`
+const FIRST_CONTRACT: u64 = 0x1000;
+
+/// An account implemented in Rust, which is called with the host and the call it receives, and
+/// returns its output or revert data.
+pub type Handler = Rc<dyn Fn(&mut TestHost, &Msg) -> Result<Vec<u8>, Vec<u8>>>;
+
+#[derive(Clone)]
+enum Code {
+    Contract(Entrypoint),
+    Handler(Handler),
+}
+
+/// An in-memory chain running Stylus contracts natively.
+///
+/// Each call to a contract runs in a forked process, so contracts can only be called through the
+/// host, and `msg::sender` or `block::timestamp` cannot be used outside of them.
+pub struct TestHost {
+    world: World,
+    code: BTreeMap<Address, Code>,
+    env: Env,
+    /// The accounts of the calls being executed
+    stack: Vec<Address>,
+    deployed: u64,
+}
+
+impl Default for TestHost {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestHost {
+    /// Creates a chain without accounts, at block 1 and timestamp 1.
+    pub fn new() -> Self {
+        Self {
+            world: World::default(),
+            code: BTreeMap::new(),
+            env: Env {
+                block_number: 1,
+                block_timestamp: 1,
+                block_basefee: U256::ZERO,
+                block_coinbase: Address::ZERO,
+                block_gas_limit: 30_000_000,
+                chain_id: 412346,
+                tx_origin: Address::ZERO,
+                tx_gas_price: U256::ZERO,
+            },
+            stack: vec![],
+            deployed: 0,
+        }
+    }
+
+    /// Returns an address derived from `label` (ex. `TestHost::address("alice")`).
+    pub fn address(label: &str) -> Address {
+        Address::from_slice(&keccak256(label)[12..])
+    }
+
+    /// Deploys the contract `S` at a new address, and returns it.
+    pub fn deploy<S>(&mut self) -> Address
+    where
+        S: StorageType + TopLevelStorage + Router<S> + BorrowMut<<S as Router<S>>::Storage>,
+    {
+        self.deployed += 1;
+        let mut address = [0; 20];
+        address[12..].copy_from_slice(&(FIRST_CONTRACT + self.deployed).to_be_bytes());
+        let address = Address::from(address);
+        self.deploy_at::<S>(address);
+        address
+    }
+
+    /// Deploys the contract `S` at `address`, keeping its balance and storage.
+    pub fn deploy_at<S>(&mut self, address: Address)
+    where
+        S: StorageType + TopLevelStorage + Router<S> + BorrowMut<<S as Router<S>>::Storage>,
+    {
+        self.code
+            .insert(address, Code::Contract(frame::entrypoint::<S>));
+    }
+
+    /// Implements the account at `address` with `handler` (ex. to mock a token receiver).
+    pub fn set_code(
+        &mut self,
+        address: Address,
+        handler: impl Fn(&mut TestHost, &Msg) -> Result<Vec<u8>, Vec<u8>> + 'static,
+    ) {
+        self.code.insert(address, Code::Handler(Rc::new(handler)));
+    }
+
+    /// Returns whether the account at `address` has code.
+    pub fn has_code(&self, address: Address) -> bool {
+        self.code.contains_key(&address)
+    }
+
+    pub fn balance(&self, address: Address) -> U256 {
+        self.world
+            .accounts
+            .get(&address)
+            .map_or(U256::ZERO, |account| account.balance)
+    }
+
+    pub fn set_balance(&mut self, address: Address, balance: U256) {
+        self.world.accounts.entry(address).or_default().balance = balance;
+    }
+
+    /// Returns the value of the storage `slot` of `address`.
+    pub fn storage(&self, address: Address, slot: B256) -> B256 {
+        self.world
+            .accounts
+            .get(&address)
+            .and_then(|account| account.storage.get(&slot).copied())
+            .unwrap_or_default()
+    }
+
+    pub fn set_storage(&mut self, address: Address, slot: B256, value: B256) {
+        self.world
+            .accounts
+            .entry(address)
+            .or_default()
+            .storage
+            .insert(slot, value);
+    }
+
+    pub fn block_number(&self) -> u64 {
+        self.env.block_number
+    }
+
+    pub fn set_block_number(&mut self, number: u64) {
+        self.env.block_number = number;
+    }
+
+    pub fn block_timestamp(&self) -> u64 {
+        self.env.block_timestamp
+    }
+
+    pub fn set_block_timestamp(&mut self, timestamp: u64) {
+        self.env.block_timestamp = timestamp;
+    }
+
+    /// Moves to the next block, `seconds` later.
+    pub fn advance(&mut self, seconds: u64) {
+        self.env.block_number += 1;
+        self.env.block_timestamp += seconds;
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        self.env.chain_id
+    }
+
+    pub fn set_chain_id(&mut self, chain_id: u64) {
+        self.env.chain_id = chain_id;
+    }
+
+    /// Returns the logs emitted since the host was created or the logs were taken.
+    pub fn logs(&self) -> &[Log] {
+        &self.world.logs
+    }
+
+    /// Returns and clears the logs emitted since the host was created or the logs were taken.
+    pub fn take_logs(&mut self) -> Vec<Log> {
+        std::mem::take(&mut self.world.logs)
+    }
+
+    /// Calls `to` from `from` with `data`, and returns its output or revert data.
+    /// The changes of the call are reverted if it fails.
+    pub fn call(&mut self, from: Address, to: Address, data: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+        self.call_with_value(from, to, U256::ZERO, data)
+    }
+
+    /// Calls `to` from `from` with `data`, sending `value` wei from the balance of `from`.
+    pub fn call_with_value(
+        &mut self,
+        from: Address,
+        to: Address,
+        value: U256,
+        data: &[u8],
+    ) -> Result<Vec<u8>, Vec<u8>> {
+        if self.stack.is_empty() {
+            self.env.tx_origin = from;
+        }
+        self.execute(Msg {
+            sender: from,
+            to,
+            value,
+            data: data.to_vec(),
+            is_static: false,
+        })
+    }
+
+    /// Calls `to` from `from` without allowing state changes.
+    pub fn static_call(
+        &mut self,
+        from: Address,
+        to: Address,
+        data: &[u8],
+    ) -> Result<Vec<u8>, Vec<u8>> {
+        self.execute(Msg {
+            sender: from,
+            to,
+            value: U256::ZERO,
+            data: data.to_vec(),
+            is_static: true,
+        })
+    }
+
+    /// Calls the Solidity function `C` of `to` from `from`, and decodes its return values.
+    /// Returns the revert data if the call fails.
+    pub fn call_sol<C: SolCall>(
+        &mut self,
+        from: Address,
+        to: Address,
+        call: &C,
+    ) -> Result<C::Return, Vec<u8>> {
+        let output = self.call(from, to, &call.encode())?;
+        Ok(C::decode_returns(&output, true).expect("invalid return data"))
+    }
+
+    /// Executes `msg`, and reverts its changes if it fails.
+    fn execute(&mut self, msg: Msg) -> Result<Vec<u8>, Vec<u8>> {
+        let snapshot = self.world.clone();
+        if !msg.value.is_zero() {
+            let balance = self.balance(msg.sender);
+            if msg.is_static || balance < msg.value {
+                return Err(vec![]);
+            }
+            self.set_balance(msg.sender, balance - msg.value);
+            let balance = self.balance(msg.to);
+            self.set_balance(msg.to, balance + msg.value);
+        }
+
+        let result = match self.code.get(&msg.to).cloned() {
+            Some(Code::Contract(entry)) => self.run_frame(entry, &msg),
+            Some(Code::Handler(handler)) => {
+                self.stack.push(msg.to);
+                let result = handler(self, &msg);
+                self.stack.pop();
+                result
+            }
+            None if msg.to == ECRECOVER => Ok(ecrecover(&msg.data)),
+            None => Ok(vec![]),
+        };
+        if result.is_err() {
+            self.world = snapshot;
+        }
+        result
+    }
+
+    /// Runs the call `msg` to the contract `entry` in a forked process, and executes the calls it
+    /// makes until it is done.
+    fn run_frame(&mut self, entry: Entrypoint, msg: &Msg) -> Result<Vec<u8>, Vec<u8>> {
+        let (mut stream, frame_stream) = UnixStream::pair().expect("failed to create a socket");
+        let reentrant = self.stack.contains(&msg.to);
+        self.stack.push(msg.to);
+
+        let pid = unsafe { libc::fork() };
+        if pid < 0 {
+            panic!("failed to fork a call frame");
+        }
+        if pid == 0 {
+            drop(stream);
+            let contracts = self.code.keys().copied().collect();
+            frame::run(
+                frame_stream,
+                self.world.clone(),
+                contracts,
+                self.env.clone(),
+                msg.clone(),
+                reentrant,
+                entry,
+            );
+        }
+        drop(frame_stream);
+
+        let result = loop {
+            match Message::receive(&mut stream) {
+                Ok(Message::Call { world, msg }) => {
+                    self.world = world;
+                    let (success, data) = match self.execute(msg) {
+                        Ok(data) => (true, data),
+                        Err(data) => (false, data),
+                    };
+                    let message = Message::Return {
+                        world: self.world.clone(),
+                        success,
+                        data,
+                    };
+                    if message.send(&mut stream).is_err() {
+                        break Err(vec![]);
+                    }
+                }
+                Ok(Message::Done {
+                    world,
+                    success: true,
+                    data,
+                }) => {
+                    self.world = world;
+                    break Ok(data);
+                }
+                Ok(Message::Done { data, .. }) => break Err(data),
+                // The frame aborted, ex. after a panic in a host I/O
+                Ok(Message::Return { .. }) | Err(_) => break Err(vec![]),
+            }
+        };
+
+        unsafe { libc::waitpid(pid, std::ptr::null_mut(), 0) };
+        self.stack.pop();
+        result
+    }
+}
+
+/// Runs the ecrecover precompile on `input`, returning the recovered address as a word or no data
+/// if the signature is invalid.
+/// Based off of revm's precompile: https://github.com/bluealloy/revm/blob/main/crates/precompile/src/secp256k1.rs
+fn ecrecover(input: &[u8]) -> Vec<u8> {
+    let mut padded = [0; 128];
+    let len = input.len().min(128);
+    padded[..len].copy_from_slice(&input[..len]);
+
+    // `v` must be a 32-byte big-endian integer equal to 27 or 28.
+    if !(padded[32..63].iter().all(|&b| b == 0) && matches!(padded[63], 27 | 28)) {
+        return vec![];
+    }
+    let mut recid = padded[63] - 27;
+    let Ok(mut sig) = Signature::from_slice(&padded[64..128]) else {
+        return vec![];
+    };
+    // normalize signature and flip recovery id if needed.
+    if let Some(normalized) = sig.normalize_s() {
+        sig = normalized;
+        recid ^= 1;
+    }
+    let recid = RecoveryId::from_byte(recid).expect("recovery ID is valid");
+    let Ok(key) = VerifyingKey::recover_from_prehash(&padded[..32], &sig, recid) else {
+        return vec![];
+    };
+    let hash = keccak256(&key.to_encoded_point(false).as_bytes()[1..]);
+    let mut output = vec![0; 32];
+    output[12..].copy_from_slice(&hash[12..]);
+    output
+}
@@ -0,0 +1,37 @@
+//! In-memory Stylus host for unit testing contracts with `cargo test`.
+//!
+//! A `TestHost` deploys contracts natively, and implements the host I/Os used by the Stylus SDK
+//! (storage, `msg::sender`, `msg::value`, the block values, logs and calls to other accounts) on
+//! top of an in-memory state:
+//!
+//! ```ignore
+//! let mut host = TestHost::new();
+//! let token = host.deploy::<ERC20Mock>();
+//! let alice = TestHost::address("alice");
+//! host.call_sol(alice, token, &mintCall { qty: U256::from(100) }).unwrap();
+//! assert_eq!(host.logs().len(), 1);
+//! ```
+//!
+//! The contracts under test must not use `#[entrypoint]`, which can only be used once per binary,
+//! and implement `TopLevelStorage` instead:
+//!
+//! ```ignore
+//! unsafe impl TopLevelStorage for ERC20Mock {}
+//! ```
+//!
+//! Calls run the contract in a forked process, since the SDK caches the values of the host for
+//! the lifetime of the program. The harness therefore only supports unix targets.
+//!
+//! Deploying contracts from contracts and delegate calls are not supported.
+
+#[cfg(not(unix))]
+compile_error!("inkmate-test forks the test process, and only supports unix targets");
+
+pub mod frame;
+pub mod host;
+pub mod signer;
+pub mod world;
+
+pub use host::{Handler, TestHost, ECRECOVER};
+pub use signer::Signer;
+pub use world::{Log, Msg};
@@ -0,0 +1,43 @@
+//! Keys signing digests like the wallets of EOAs, ex. for permits.
+
+use alloy_primitives::{keccak256, Address, B256};
+use k256::ecdsa::SigningKey;
+
+/// A secp256k1 key, with the address of its account
+pub struct Signer {
+    key: SigningKey,
+}
+
+impl Signer {
+    /// Creates the key derived from `label` (ex. `Signer::new("alice")`).
+    pub fn new(label: &str) -> Self {
+        let key = SigningKey::from_slice(keccak256(label).as_slice()).expect("valid private key");
+        Self { key }
+    }
+
+    /// Returns the address of the account of the key.
+    pub fn address(&self) -> Address {
+        let point = self.key.verifying_key().to_encoded_point(false);
+        Address::from_slice(&keccak256(&point.as_bytes()[1..])[12..])
+    }
+
+    /// Signs `digest`, and returns the `v`, `r` and `s` values of the signature.
+    pub fn sign(&self, digest: B256) -> (u8, B256, B256) {
+        let (signature, recid) = self
+            .key
+            .sign_prehash_recoverable(digest.as_slice())
+            .expect("signing failed");
+        let bytes = signature.to_bytes();
+        (
+            27 + recid.to_byte(),
+            B256::from_slice(&bytes[..32]),
+            B256::from_slice(&bytes[32..]),
+        )
+    }
+
+    /// Signs `digest`, and returns the 65 bytes `r ‖ s ‖ v` signature.
+    pub fn sign_bytes(&self, digest: B256) -> Vec<u8> {
+        let (v, r, s) = self.sign(digest);
+        [r.as_slice(), s.as_slice(), &[v]].concat()
+    }
+}
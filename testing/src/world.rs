@@ -0,0 +1,306 @@
+//! The state of the simulated chain, and its encoding between the host and the call frames.
+
+use std::collections::BTreeMap;
+use std::io::{self, Read, Write};
+
+use alloy_primitives::{Address, B256, U256};
+use alloy_sol_types::SolEvent;
+
+/// A log emitted by a contract
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Log {
+    /// The contract that emitted the log
+    pub address: Address,
+    /// The indexed topics of the log, starting with the event signature hash
+    pub topics: Vec<B256>,
+    /// The non-indexed data of the log
+    pub data: Vec<u8>,
+}
+
+impl Log {
+    /// Decodes the log as the event `E`, or returns `None` if it is another event.
+    pub fn decode<E: SolEvent>(&self) -> Option<E> {
+        if E::ANONYMOUS || self.topics.first() != Some(&E::SIGNATURE_HASH) {
+            return None;
+        }
+        E::decode_log(self.topics.iter().copied(), &self.data, true).ok()
+    }
+}
+
+/// The balance and storage of an account
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Account {
+    pub balance: U256,
+    pub storage: BTreeMap<B256, B256>,
+}
+
+/// The mutable state of the chain, which is reverted along with the call that changed it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct World {
+    pub accounts: BTreeMap<Address, Account>,
+    pub logs: Vec<Log>,
+}
+
+/// A call, as seen by the called account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Msg {
+    /// The caller
+    pub sender: Address,
+    /// The called account
+    pub to: Address,
+    /// The wei sent with the call
+    pub value: U256,
+    /// The calldata
+    pub data: Vec<u8>,
+    /// Whether the call cannot change the state
+    pub is_static: bool,
+}
+
+/// Messages sent between the host and a call frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// The frame calls another account, with the state of the chain at the time of the call
+    Call { world: World, msg: Msg },
+    /// The host returns the outcome of a call made by the frame
+    Return {
+        world: World,
+        success: bool,
+        data: Vec<u8>,
+    },
+    /// The frame is done, with the state of the chain at the end of the call
+    Done {
+        world: World,
+        success: bool,
+        data: Vec<u8>,
+    },
+}
+
+const CALL: u8 = 0;
+const RETURN: u8 = 1;
+const DONE: u8 = 2;
+
+impl Message {
+    /// Writes the message to `stream`, prefixed by its length.
+    pub fn send(&self, stream: &mut impl Write) -> io::Result<()> {
+        let mut encoder = Encoder::default();
+        match self {
+            Message::Call { world, msg } => {
+                encoder.u8(CALL);
+                encoder.world(world);
+                encoder.address(msg.sender);
+                encoder.address(msg.to);
+                encoder.b256(msg.value.into());
+                encoder.bytes(&msg.data);
+                encoder.u8(msg.is_static as u8);
+            }
+            Message::Return {
+                world,
+                success,
+                data,
+            } => {
+                encoder.u8(RETURN);
+                encoder.world(world);
+                encoder.u8(*success as u8);
+                encoder.bytes(data);
+            }
+            Message::Done {
+                world,
+                success,
+                data,
+            } => {
+                encoder.u8(DONE);
+                encoder.world(world);
+                encoder.u8(*success as u8);
+                encoder.bytes(data);
+            }
+        }
+        stream.write_all(&(encoder.0.len() as u64).to_be_bytes())?;
+        stream.write_all(&encoder.0)?;
+        stream.flush()
+    }
+
+    /// Reads a message written by `send` from `stream`.
+    pub fn receive(stream: &mut impl Read) -> io::Result<Self> {
+        let mut len = [0; 8];
+        stream.read_exact(&mut len)?;
+        let mut buf = vec![0; u64::from_be_bytes(len) as usize];
+        stream.read_exact(&mut buf)?;
+
+        let mut decoder = Decoder(&buf);
+        let message = match decoder.u8() {
+            CALL => Message::Call {
+                world: decoder.world(),
+                msg: Msg {
+                    sender: decoder.address(),
+                    to: decoder.address(),
+                    value: decoder.b256().into(),
+                    data: decoder.bytes(),
+                    is_static: decoder.u8() != 0,
+                },
+            },
+            RETURN => Message::Return {
+                world: decoder.world(),
+                success: decoder.u8() != 0,
+                data: decoder.bytes(),
+            },
+            DONE => Message::Done {
+                world: decoder.world(),
+                success: decoder.u8() != 0,
+                data: decoder.bytes(),
+            },
+            kind => panic!("unknown message kind {kind}"),
+        };
+        Ok(message)
+    }
+}
+
+#[derive(Default)]
+struct Encoder(Vec<u8>);
+
+impl Encoder {
+    fn u8(&mut self, value: u8) {
+        self.0.push(value);
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.0.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn bytes(&mut self, value: &[u8]) {
+        self.u64(value.len() as u64);
+        self.0.extend_from_slice(value);
+    }
+
+    fn b256(&mut self, value: B256) {
+        self.0.extend_from_slice(value.as_slice());
+    }
+
+    fn address(&mut self, value: Address) {
+        self.0.extend_from_slice(value.as_slice());
+    }
+
+    fn world(&mut self, world: &World) {
+        self.u64(world.accounts.len() as u64);
+        for (address, account) in &world.accounts {
+            self.address(*address);
+            self.b256(account.balance.into());
+            self.u64(account.storage.len() as u64);
+            for (key, value) in &account.storage {
+                self.b256(*key);
+                self.b256(*value);
+            }
+        }
+        self.u64(world.logs.len() as u64);
+        for log in &world.logs {
+            self.address(log.address);
+            self.u64(log.topics.len() as u64);
+            for topic in &log.topics {
+                self.b256(*topic);
+            }
+            self.bytes(&log.data);
+        }
+    }
+}
+
+struct Decoder<'a>(&'a [u8]);
+
+impl Decoder<'_> {
+    fn take(&mut self, len: usize) -> &[u8] {
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        taken
+    }
+
+    fn u8(&mut self) -> u8 {
+        self.take(1)[0]
+    }
+
+    fn u64(&mut self) -> u64 {
+        u64::from_be_bytes(self.take(8).try_into().unwrap())
+    }
+
+    fn bytes(&mut self) -> Vec<u8> {
+        let len = self.u64() as usize;
+        self.take(len).to_vec()
+    }
+
+    fn b256(&mut self) -> B256 {
+        B256::from_slice(self.take(32))
+    }
+
+    fn address(&mut self) -> Address {
+        Address::from_slice(self.take(20))
+    }
+
+    fn world(&mut self) -> World {
+        let mut world = World::default();
+        for _ in 0..self.u64() {
+            let address = self.address();
+            let mut account = Account {
+                balance: self.b256().into(),
+                ..Default::default()
+            };
+            for _ in 0..self.u64() {
+                let key = self.b256();
+                account.storage.insert(key, self.b256());
+            }
+            world.accounts.insert(address, account);
+        }
+        for _ in 0..self.u64() {
+            let address = self.address();
+            let topics = (0..self.u64()).map(|_| self.b256()).collect();
+            world.logs.push(Log {
+                address,
+                topics,
+                data: self.bytes(),
+            });
+        }
+        world
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_roundtrip() {
+        let mut world = World::default();
+        let account = world.accounts.entry(Address::repeat_byte(1)).or_default();
+        account.balance = U256::from(100);
+        account
+            .storage
+            .insert(B256::repeat_byte(2), B256::repeat_byte(3));
+        world.logs.push(Log {
+            address: Address::repeat_byte(1),
+            topics: vec![B256::repeat_byte(4)],
+            data: vec![5, 6],
+        });
+        let messages = [
+            Message::Call {
+                world: world.clone(),
+                msg: Msg {
+                    sender: Address::repeat_byte(7),
+                    to: Address::repeat_byte(1),
+                    value: U256::from(8),
+                    data: vec![9],
+                    is_static: true,
+                },
+            },
+            Message::Done {
+                world,
+                success: false,
+                data: vec![10, 11],
+            },
+        ];
+
+        let mut stream = vec![];
+        for message in &messages {
+            message.send(&mut stream).unwrap();
+        }
+        let mut stream = stream.as_slice();
+        for message in messages {
+            assert_eq!(Message::receive(&mut stream).unwrap(), message);
+        }
+    }
+}
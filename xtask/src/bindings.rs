@@ -0,0 +1,131 @@
+//! `cargo xtask bindings`: the TypeScript bindings of the presets.
+//!
+//! The JSON ABI of each preset is exported from its sample, and written to `bindings/src` as a
+//! constant asserted `as const`. viem and wagmi infer the function names, arguments and return
+//! types of a contract from such a constant, so dapps get typed calls without a codegen step.
+
+use std::{fs, path::Path, process::Command};
+
+use serde_json::Value;
+
+use crate::{run_command, workspace_root, Result};
+
+/// The presets with bindings, by feature of `samples` and name of their constant
+const PRESETS: &[(&str, &str)] = &[
+    ("erc20-preset", "erc20MinterPauserCapped"),
+    ("erc721-drop", "erc721Drop"),
+    ("erc1155-game-items", "erc1155GameItems"),
+];
+
+pub fn run(args: &[String]) -> Result<()> {
+    let mut check = false;
+    for arg in args {
+        match arg.as_str() {
+            "--check" => check = true,
+            arg => return Err(format!("unknown argument {arg}")),
+        }
+    }
+
+    let root = workspace_root();
+    let src = root.join("bindings/src");
+    let mut files = vec![];
+    for (feature, name) in PRESETS {
+        let abi = export_abi(&root, feature)?;
+        files.push((format!("{name}.ts"), render(feature, name, &abi)?));
+    }
+    files.push(("index.ts".into(), render_index(PRESETS)));
+
+    let mut stale = vec![];
+    for (file, contents) in &files {
+        let path = src.join(file);
+        if fs::read_to_string(&path).ok().as_deref() == Some(contents.as_str()) {
+            continue;
+        }
+        if check {
+            stale.push(file.as_str());
+        } else {
+            fs::create_dir_all(&src).map_err(|err| format!("{}: {err}", src.display()))?;
+            fs::write(&path, contents).map_err(|err| format!("{}: {err}", path.display()))?;
+            println!("Wrote {}", path.display());
+        }
+    }
+
+    if stale.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "bindings out of date, run `cargo xtask bindings`: {}",
+            stale.join(", ")
+        ))
+    }
+}
+
+/// Exports the JSON ABI of the sample of `feature`.
+fn export_abi(root: &Path, feature: &str) -> Result<Value> {
+    let out_dir = root.join("target/bindings").join(feature);
+    // Start from an empty directory, as the name of the ABI file is the name of the contract
+    let _ = fs::remove_dir_all(&out_dir);
+    run_command(
+        Command::new("cargo")
+            .current_dir(root)
+            .args(["run", "-q", "-p", "samples", "--example", "export_abi"])
+            .arg(format!("--features={feature},export-abi"))
+            .arg("--")
+            .arg("--json")
+            .arg(&out_dir),
+    )?;
+
+    let entries = fs::read_dir(&out_dir).map_err(|err| format!("{}: {err}", out_dir.display()))?;
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    let path = match paths.pop() {
+        Some(path) if paths.is_empty() => path,
+        _ => return Err(format!("expected a single ABI in {}", out_dir.display())),
+    };
+    let abi = fs::read_to_string(&path).map_err(|err| format!("{}: {err}", path.display()))?;
+    serde_json::from_str(&abi).map_err(|err| format!("{}: {err}", path.display()))
+}
+
+/// Returns the TypeScript module of the ABI of the preset `name`.
+fn render(feature: &str, name: &str, abi: &Value) -> Result<String> {
+    // JSON is valid TypeScript, and `as const` keeps its literal types for viem
+    let abi = serde_json::to_string_pretty(abi).map_err(|err| err.to_string())?;
+    Ok(format!(
+        "// Generated by `cargo xtask bindings` from the `{feature}` sample. Do not edit.\n\n\
+         export const {name}Abi = {abi} as const;\n"
+    ))
+}
+
+/// Returns the TypeScript module exporting the ABIs of `presets`.
+fn render_index(presets: &[(&str, &str)]) -> String {
+    let mut index = String::from("// Generated by `cargo xtask bindings`. Do not edit.\n\n");
+    for (_, name) in presets {
+        index.push_str(&format!("export {{ {name}Abi }} from \"./{name}\";\n"));
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let abi = serde_json::json!([{ "type": "function", "name": "cap" }]);
+        let module = render("erc20-preset", "erc20MinterPauserCapped", &abi).unwrap();
+        assert!(module.starts_with("// Generated by `cargo xtask bindings`"));
+        assert!(module.contains("export const erc20MinterPauserCappedAbi = [\n  {\n"));
+        assert!(module.ends_with("] as const;\n"));
+    }
+
+    #[test]
+    fn test_render_index() {
+        assert_eq!(
+            render_index(&[("erc721-drop", "erc721Drop")]),
+            "// Generated by `cargo xtask bindings`. Do not edit.\n\n\
+             export { erc721DropAbi } from \"./erc721Drop\";\n"
+        );
+    }
+}
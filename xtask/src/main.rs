@@ -0,0 +1,56 @@
+//! Development tasks of the workspace, run with `cargo xtask <task>`.
+
+mod bindings;
+mod wasm_size;
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::{Command, ExitCode},
+};
+
+const USAGE: &str = "Usage: cargo xtask <task>
+
+Tasks:
+  wasm-size [--features <a,b>] [--budgets <path>] [--no-opt]
+      Builds each sample to WASM, and reports its compressed size against its budget and the
+      24KB Stylus limit. Fails if a sample exceeds its budget.
+  bindings [--check]
+      Exports the JSON ABI of each preset, and generates its typed TypeScript constant for viem
+      and wagmi in `bindings/src`. With --check, fails if the bindings are out of date instead.";
+
+type Result<T> = std::result::Result<T, String>;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("wasm-size") => wasm_size::run(&args[1..]),
+        Some("bindings") => bindings::run(&args[1..]),
+        _ => Err(USAGE.into()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("xtask is in the workspace")
+        .to_path_buf()
+}
+
+fn run_command(command: &mut Command) -> Result<()> {
+    let status = command
+        .status()
+        .map_err(|err| format!("{command:?}: {err}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{command:?} failed with {status}"))
+    }
+}
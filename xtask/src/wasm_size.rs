@@ -0,0 +1,241 @@
+//! `cargo xtask wasm-size`: the size of each sample compiled to WASM.
+//!
+//! Stylus limits the brotli-compressed WASM of a contract to 24KB, and each sample is a contract
+//! built from a feature of `samples`. Each sample is built like the deployed contracts, optimized
+//! with `wasm-opt` when it is installed, and compressed with the `brotli` CLI. The sizes are
+//! compared with the budgets of `wasm-size.toml`, so that regressions of the bases fail the task.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use serde::Deserialize;
+
+use crate::{run_command, workspace_root, Result};
+
+/// The maximum size of the compressed WASM of a Stylus contract
+pub const STYLUS_LIMIT: u64 = 24 * 1024;
+
+/// Features of `samples` that are not contracts
+const NOT_SAMPLES: &[&str] = &["default", "export-abi", "receivers"];
+
+/// The size budgets of the samples, in bytes of compressed WASM
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Budgets {
+    /// The budget of the samples without their own budget
+    #[serde(default = "default_budget")]
+    default: u64,
+    /// The budgets by sample feature
+    #[serde(default)]
+    samples: BTreeMap<String, u64>,
+}
+
+fn default_budget() -> u64 {
+    STYLUS_LIMIT
+}
+
+impl Budgets {
+    pub fn parse(toml: &str) -> Result<Self> {
+        toml::from_str(toml).map_err(|err| format!("invalid budgets: {err}"))
+    }
+
+    /// Returns the budget of `sample`, which cannot exceed the Stylus limit.
+    pub fn budget(&self, sample: &str) -> u64 {
+        let budget = self.samples.get(sample).copied().unwrap_or(self.default);
+        budget.min(STYLUS_LIMIT)
+    }
+}
+
+/// The sizes of a sample, in bytes
+struct Sizes {
+    raw: u64,
+    optimized: Option<u64>,
+    compressed: u64,
+}
+
+pub fn run(args: &[String]) -> Result<()> {
+    let root = workspace_root();
+    let mut features = None;
+    let mut budgets_path = None;
+    let mut optimize = true;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--features" => {
+                let value = args.next().ok_or("--features takes a list of samples")?;
+                features = Some(value.split(',').map(String::from).collect::<Vec<_>>());
+            }
+            "--budgets" => {
+                budgets_path = Some(PathBuf::from(args.next().ok_or("--budgets takes a path")?));
+            }
+            "--no-opt" => optimize = false,
+            arg => return Err(format!("unknown argument {arg}")),
+        }
+    }
+
+    // The default budgets file is optional
+    let budgets = match budgets_path {
+        Some(path) => {
+            fs::read_to_string(&path).map_err(|err| format!("{}: {err}", path.display()))?
+        }
+        None => fs::read_to_string(root.join("wasm-size.toml")).unwrap_or_default(),
+    };
+    let budgets = Budgets::parse(&budgets)?;
+    let features = match features {
+        Some(features) => features,
+        None => sample_features(&root)?,
+    };
+    let optimize = optimize && has_tool("wasm-opt");
+    if !optimize {
+        println!("Skipping wasm-opt, the sizes are of the unoptimized WASM");
+    }
+    if !has_tool("brotli") {
+        return Err("the brotli CLI is required to compress the WASM".into());
+    }
+
+    let out_dir = root.join("target").join("wasm-size");
+    fs::create_dir_all(&out_dir).map_err(|err| err.to_string())?;
+    let mut over_budget = vec![];
+    println!(
+        "| Sample | WASM | Optimized | Compressed | Budget | |\n| --- | ---: | ---: | ---: | ---: | --- |"
+    );
+    for feature in &features {
+        let sizes = measure(&root, &out_dir, feature, optimize)?;
+        let budget = budgets.budget(feature);
+        let status = if sizes.compressed > budget {
+            over_budget.push(feature.as_str());
+            "over budget"
+        } else {
+            "ok"
+        };
+        println!(
+            "| {feature} | {} | {} | {} | {} | {status} |",
+            kb(sizes.raw),
+            sizes.optimized.map_or("-".into(), kb),
+            kb(sizes.compressed),
+            kb(budget),
+        );
+    }
+
+    if over_budget.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "samples over their size budget: {}",
+            over_budget.join(", ")
+        ))
+    }
+}
+
+/// Builds the sample of `feature`, and returns its sizes.
+fn measure(root: &Path, out_dir: &Path, feature: &str, optimize: bool) -> Result<Sizes> {
+    // Same build as the deployed contracts, see the README
+    run_command(
+        Command::new("cargo")
+            .current_dir(root)
+            .args(["+nightly", "build", "--target", "wasm32-unknown-unknown"])
+            .args(["--lib", "--release", "-p", "samples"])
+            .arg(format!("--features={feature}"))
+            .args(["-Z", "build-std=std,panic_abort"])
+            .args(["-Z", "build-std-features=panic_immediate_abort"]),
+    )?;
+    let built = root.join("target/wasm32-unknown-unknown/release/samples.wasm");
+    let wasm = out_dir.join(format!("{feature}.wasm"));
+    fs::copy(&built, &wasm).map_err(|err| format!("{}: {err}", built.display()))?;
+    let raw = file_size(&wasm)?;
+
+    let optimized = if optimize {
+        let optimized = out_dir.join(format!("{feature}.opt.wasm"));
+        run_command(
+            Command::new("wasm-opt")
+                .arg(&wasm)
+                .args(["-Oz", "--strip-debug", "--strip-producers", "-o"])
+                .arg(&optimized),
+        )?;
+        Some(optimized)
+    } else {
+        None
+    };
+    let deployed = optimized.as_deref().unwrap_or(&wasm);
+
+    // Stylus compresses contracts with brotli at its maximum quality
+    let compressed = out_dir.join(format!("{feature}.wasm.br"));
+    run_command(
+        Command::new("brotli")
+            .args(["-f", "-q", "11", "-o"])
+            .arg(&compressed)
+            .arg(deployed),
+    )?;
+
+    Ok(Sizes {
+        raw,
+        optimized: optimized.as_deref().map(file_size).transpose()?,
+        compressed: file_size(&compressed)?,
+    })
+}
+
+/// Returns the sample features of `samples/Cargo.toml`.
+fn sample_features(root: &Path) -> Result<Vec<String>> {
+    #[derive(Deserialize)]
+    struct Manifest {
+        features: BTreeMap<String, toml::Value>,
+    }
+
+    let path = root.join("samples/Cargo.toml");
+    let manifest = fs::read_to_string(&path).map_err(|err| format!("{}: {err}", path.display()))?;
+    let manifest: Manifest = toml::from_str(&manifest).map_err(|err| err.to_string())?;
+    Ok(manifest
+        .features
+        .into_keys()
+        .filter(|feature| !NOT_SAMPLES.contains(&feature.as_str()))
+        .collect())
+}
+
+fn has_tool(tool: &str) -> bool {
+    Command::new(tool)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok()
+}
+
+fn file_size(path: &Path) -> Result<u64> {
+    fs::metadata(path)
+        .map(|metadata| metadata.len())
+        .map_err(|err| format!("{}: {err}", path.display()))
+}
+
+/// Formats `bytes` in KB, ex. `12.3 KB`.
+fn kb(bytes: u64) -> String {
+    format!("{:.1} KB", bytes as f64 / 1024.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budgets() {
+        let budgets =
+            Budgets::parse("default = 20000\n[samples]\nerc20 = 8000\ndn404 = 30000\n").unwrap();
+        assert_eq!(budgets.budget("erc20"), 8000);
+        assert_eq!(budgets.budget("erc721"), 20000);
+        // Budgets cannot exceed the Stylus limit
+        assert_eq!(budgets.budget("dn404"), STYLUS_LIMIT);
+        assert_eq!(Budgets::parse("").unwrap().budget("erc20"), STYLUS_LIMIT);
+        assert!(Budgets::parse("erc20 = 8000").is_err());
+    }
+
+    #[test]
+    fn test_sample_features() {
+        let features = sample_features(&workspace_root()).unwrap();
+        assert!(features.contains(&"erc20".to_string()));
+        assert!(!features.contains(&"export-abi".to_string()));
+        assert!(!features.contains(&"receivers".to_string()));
+    }
+}